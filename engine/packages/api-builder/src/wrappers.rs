@@ -136,3 +136,27 @@ pub mod bin {
 	create_binary_method_wrapper!(put, axum_put, with_body);
 	create_binary_method_wrapper!(patch, axum_patch, with_body);
 }
+
+/// Wrappers for handlers that build their own response, e.g. to stream a body or set headers.
+pub mod raw {
+	use super::*;
+
+	pub fn get<P, Q, F, Fut>(handler: F) -> axum::routing::MethodRouter<crate::GlobalApiCtx>
+	where
+		P: DeserializeOwned + Send + 'static,
+		Q: DeserializeOwned + Send + 'static,
+		F: FnOnce(ApiCtx, P, Q) -> Fut + Clone + Send + Sync + 'static,
+		Fut: Future<Output = Result<axum::response::Response>> + Send,
+	{
+		axum_get(
+			move |Extension(ctx): Extension<ApiCtx>,
+			      Path(path): Path<P>,
+			      Query(query): Query<Q>| async move {
+				match handler(ctx, path, query).await {
+					Ok(response) => response,
+					Err(err) => ApiError::from(err).into_response(),
+				}
+			},
+		)
+	}
+}
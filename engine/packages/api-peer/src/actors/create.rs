@@ -1,7 +1,10 @@
 use anyhow::Result;
+use depot::types::SnapshotSelector;
 use gas::prelude::*;
-use rivet_api_builder::ApiCtx;
-use rivet_api_types::actors::create::{CreateQuery, CreateRequest, CreateResponse};
+use rivet_api_builder::{ApiBadRequest, ApiCtx};
+use rivet_api_types::actors::create::{CreateQuery, CreateRequest, CreateResponse, ForkFrom};
+
+use super::restore_points::parse_restore_point_id;
 
 const MAX_ACTOR_KEY_SIZE: usize = 1024;
 
@@ -25,6 +28,8 @@ pub async fn create(
 		}
 	}
 
+	let fork_from = body.fork_from.map(fork_source).transpose()?;
+
 	let namespace = ctx
 		.op(namespace::ops::resolve_for_name_global::Input {
 			name: query.namespace.clone(),
@@ -48,8 +53,32 @@ pub async fn create(
 			forward_request: true,
 			// api-peer is always creating in its own datacenter
 			datacenter_name: None,
+			fork_from,
 		})
 		.await?;
 
 	Ok(CreateResponse { actor: res.actor })
 }
+
+fn fork_source(fork_from: ForkFrom) -> Result<pegboard::actor_fork::ForkSource> {
+	let selector = match (fork_from.restore_point_id, fork_from.ts) {
+		(None, None) => SnapshotSelector::Latest,
+		(Some(restore_point_id), None) => SnapshotSelector::RestorePoint {
+			restore_point: parse_restore_point_id(restore_point_id)?,
+		},
+		(None, Some(timestamp_ms)) => SnapshotSelector::AtTimestamp { timestamp_ms },
+		(Some(_), Some(_)) => {
+			return Err(ApiBadRequest {
+				reason:
+					"at most one of `fork_from.restore_point_id` or `fork_from.ts` may be provided"
+						.to_string(),
+			}
+			.build());
+		}
+	};
+
+	Ok(pegboard::actor_fork::ForkSource {
+		actor_id: fork_from.actor_id,
+		selector,
+	})
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use base64::{Engine, prelude::BASE64_STANDARD};
+use depot::{conveyer::Db, replica::replica_database_id};
+use depot_client::types::{BindParam, ColumnValue, QueryResult};
+use gas::prelude::*;
+use rivet_api_builder::{ApiBadRequest, ApiCtx};
+use rivet_api_types::actors::database_query::*;
+use serde_json::{Value, json};
+
+use super::restore_points::{actor_db, depot_api_error};
+
+/// Runs a read-only query against the actor's SQLite database in this datacenter.
+#[tracing::instrument(skip_all)]
+pub async fn query(
+	ctx: ApiCtx,
+	path: DatabaseQueryPath,
+	query: DatabaseQueryQuery,
+	body: DatabaseQueryRequest,
+) -> Result<DatabaseQueryResponse> {
+	let params = bind_params(body.params)?;
+	let db = actor_db(&ctx, path.actor_id, query.namespace).await?;
+	let result = run_read_only_query(db, path.actor_id, body.sql, params).await?;
+
+	query_response(&ctx, result, None)
+}
+
+/// Runs a read-only query against this datacenter's replica of the actor's database. Returns
+/// `None` when there is no replica here or it is further behind than `max_staleness_ms`, in which
+/// case the query must go to the actor's datacenter.
+#[tracing::instrument(skip_all)]
+pub async fn query_replica(
+	ctx: ApiCtx,
+	path: DatabaseQueryPath,
+	query: DatabaseQueryQuery,
+	body: DatabaseQueryRequest,
+) -> Result<Option<DatabaseQueryResponse>> {
+	let Some(max_staleness_ms) = body.max_staleness_ms else {
+		return Ok(None);
+	};
+	let params = bind_params(body.params)?;
+
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: query.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	// Replicas are stored under the source namespace, so a replica found here also proves the
+	// actor belongs to the namespace.
+	let udb = ctx.pools().udb()?;
+	let db = Db::from_config(
+		ctx.config(),
+		Arc::new((*udb).clone()),
+		namespace.namespace_id,
+		replica_database_id(&path.actor_id.to_string()),
+		ctx.pools().node_id(),
+	)
+	.await?;
+	let Some(state) = db.replica_state().await.map_err(depot_api_error)? else {
+		return Ok(None);
+	};
+	let Some(staleness_ms) = state
+		.staleness_ms(util::timestamp::now())
+		.filter(|staleness_ms| *staleness_ms <= max_staleness_ms)
+	else {
+		return Ok(None);
+	};
+
+	let result = run_read_only_query(db, path.actor_id, body.sql, params).await?;
+
+	query_response(&ctx, result, Some(staleness_ms)).map(Some)
+}
+
+async fn run_read_only_query(
+	db: Db,
+	actor_id: Id,
+	sql: String,
+	params: Option<Vec<BindParam>>,
+) -> Result<QueryResult> {
+	// Every query registers its own VFS, so the name must not collide with the actor's handle or
+	// other queries.
+	let vfs_actor_id = format!("{actor_id}-query-{}", uuid::Uuid::new_v4().simple());
+	let sqlite = depot_client_embedded::open_read_only_database_from_embedded_depot(
+		Arc::new(db),
+		vfs_actor_id,
+		tokio::runtime::Handle::current(),
+	)
+	.await
+	.map_err(depot_api_error)?;
+
+	let result = sqlite.query_readonly(sql, params).await;
+	if let Err(err) = sqlite.close().await {
+		tracing::warn!(?err, %actor_id, "failed to close sqlite query handle");
+	}
+
+	result.map_err(|err| {
+		ApiBadRequest {
+			reason: format!("{err:#}"),
+		}
+		.build()
+	})
+}
+
+fn bind_params(params: Option<Vec<Value>>) -> Result<Option<Vec<BindParam>>> {
+	params
+		.map(|params| params.into_iter().map(bind_param).collect())
+		.transpose()
+}
+
+fn bind_param(value: Value) -> Result<BindParam> {
+	let invalid = |value: &Value| {
+		ApiBadRequest {
+			reason: format!("unsupported query parameter: {value}"),
+		}
+		.build()
+	};
+
+	match value {
+		Value::Null => Ok(BindParam::Null),
+		Value::Bool(value) => Ok(BindParam::Integer(i64::from(value))),
+		Value::Number(ref number) => match number.as_i64() {
+			Some(value) => Ok(BindParam::Integer(value)),
+			None => number
+				.as_f64()
+				.map(BindParam::Float)
+				.ok_or_else(|| invalid(&value)),
+		},
+		Value::String(value) => Ok(BindParam::Text(value)),
+		Value::Object(ref object) if object.get("type") == Some(&json!("blob")) => object
+			.get("base64")
+			.and_then(Value::as_str)
+			.and_then(|encoded| BASE64_STANDARD.decode(encoded).ok())
+			.map(BindParam::Blob)
+			.ok_or_else(|| invalid(&value)),
+		_ => Err(invalid(&value)),
+	}
+}
+
+fn query_response(
+	ctx: &ApiCtx,
+	result: QueryResult,
+	staleness_ms: Option<i64>,
+) -> Result<DatabaseQueryResponse> {
+	Ok(DatabaseQueryResponse {
+		columns: result.columns,
+		rows: result
+			.rows
+			.into_iter()
+			.map(|row| row.into_iter().map(column_value_json).collect())
+			.collect(),
+		datacenter: ctx.config().dc_name()?.to_string(),
+		staleness_ms,
+	})
+}
+
+fn column_value_json(value: ColumnValue) -> Value {
+	match value {
+		ColumnValue::Null => Value::Null,
+		ColumnValue::Integer(value) => json!(value),
+		ColumnValue::Float(value) => json!(value),
+		ColumnValue::Text(value) => json!(value),
+		ColumnValue::Blob(value) => json!({
+			"type": "blob",
+			"base64": BASE64_STANDARD.encode(value),
+		}),
+	}
+}
@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use base64::{Engine, prelude::BASE64_STANDARD};
+use depot::{conveyer::Db, replica::replica_database_id, types::decode_replica_batch};
+use gas::prelude::*;
+use pegboard::workflows::sqlite_replicator::{ApplyReplicaBatchRequest, ApplyReplicaBatchResponse};
+use rivet_api_builder::{ApiBadRequest, ApiCtx};
+use rivet_api_types::actors::database_replicas::*;
+
+use super::restore_points::{actor_namespace_id, depot_api_error};
+
+#[tracing::instrument(skip_all)]
+pub async fn get(
+	ctx: ApiCtx,
+	path: DatabaseReplicasPath,
+	query: DatabaseReplicasQuery,
+) -> Result<DatabaseReplicasResponse> {
+	let namespace_id = actor_namespace_id(&ctx, path.actor_id, query.namespace).await?;
+	let res = ctx
+		.op(pegboard::ops::actor::get_sqlite_replicas::Input {
+			namespace_id,
+			actor_id: path.actor_id,
+		})
+		.await?;
+
+	Ok(replicas_response(&ctx, res.datacenter_labels))
+}
+
+/// Replaces the datacenters holding a read replica of the actor's database. Replicas start
+/// serving reads once their first snapshot lands.
+#[tracing::instrument(skip_all)]
+pub async fn set(
+	ctx: ApiCtx,
+	path: DatabaseReplicasPath,
+	query: DatabaseReplicasQuery,
+	body: SetDatabaseReplicasRequest,
+) -> Result<DatabaseReplicasResponse> {
+	let namespace_id = actor_namespace_id(&ctx, path.actor_id, query.namespace).await?;
+	let datacenter_labels = body
+		.datacenters
+		.iter()
+		.map(|name| {
+			ctx.config()
+				.dc_for_name(name)
+				.map(|dc| dc.datacenter_label)
+				.ok_or_else(|| rivet_api_util::errors::Datacenter::NotFound.build())
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	ctx.op(pegboard::ops::actor::set_sqlite_replicas::Input {
+		namespace_id,
+		actor_id: path.actor_id,
+		datacenter_labels,
+	})
+	.await?;
+
+	let res = ctx
+		.op(pegboard::ops::actor::get_sqlite_replicas::Input {
+			namespace_id,
+			actor_id: path.actor_id,
+		})
+		.await?;
+
+	Ok(replicas_response(&ctx, res.datacenter_labels))
+}
+
+/// Applies a batch shipped by the actor's datacenter to the local replica.
+#[tracing::instrument(skip_all)]
+pub async fn apply(
+	ctx: ApiCtx,
+	_path: (),
+	_query: (),
+	body: ApplyReplicaBatchRequest,
+) -> Result<ApplyReplicaBatchResponse> {
+	let batch = BASE64_STANDARD
+		.decode(&body.batch)
+		.map_err(|err| {
+			ApiBadRequest {
+				reason: format!("invalid replica batch encoding: {err}"),
+			}
+			.build()
+		})
+		.and_then(|batch| decode_replica_batch(&batch))?;
+
+	let udb = ctx.pools().udb()?;
+	let db = Db::from_config(
+		ctx.config(),
+		Arc::new((*udb).clone()),
+		body.namespace_id,
+		replica_database_id(&body.actor_id.to_string()),
+		ctx.pools().node_id(),
+	)
+	.await?;
+	let state = db
+		.apply_replica_batch(batch, util::timestamp::now())
+		.await
+		.map_err(depot_api_error)?;
+
+	Ok(ApplyReplicaBatchResponse { state })
+}
+
+fn replicas_response(ctx: &ApiCtx, datacenter_labels: Vec<u16>) -> DatabaseReplicasResponse {
+	DatabaseReplicasResponse {
+		datacenters: datacenter_labels
+			.into_iter()
+			.filter_map(|label| ctx.config().dc_for_label(label))
+			.map(|dc| dc.name.clone())
+			.collect(),
+	}
+}
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use axum::{
+	body::Body,
+	http::header,
+	response::{IntoResponse, Response},
+};
+use futures_util::TryStreamExt;
+use gas::prelude::*;
+use rivet_api_builder::ApiCtx;
+use rivet_api_types::actors::export::*;
+
+use super::restore_points::{actor_db, depot_api_error};
+
+/// Streams the actor's SQLite database as a standard SQLite file.
+#[tracing::instrument(skip_all)]
+pub async fn export(ctx: ApiCtx, path: ExportPath, query: ExportQuery) -> Result<Response> {
+	let db = actor_db(&ctx, path.actor_id, query.namespace).await?;
+	let export = db.export_sqlite_file().await.map_err(depot_api_error)?;
+
+	let actor_id = path.actor_id;
+	let chunks = export.chunks.inspect_err(move |err| {
+		tracing::warn!(?err, %actor_id, "sqlite export stream failed");
+	});
+
+	Ok((
+		[
+			(header::CONTENT_TYPE, "application/vnd.sqlite3".to_string()),
+			(
+				header::CONTENT_DISPOSITION,
+				format!("attachment; filename=\"{actor_id}.sqlite\""),
+			),
+			(header::CONTENT_LENGTH, export.size_bytes.to_string()),
+		],
+		Body::from_stream(chunks),
+	)
+		.into_response())
+}
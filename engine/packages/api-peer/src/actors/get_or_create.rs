@@ -65,6 +65,7 @@ pub async fn get_or_create(
 					forward_request: true,
 					// api-peer is always creating in its own datacenter
 					datacenter_name: None,
+					fork_from: None,
 				})
 				.await
 			{
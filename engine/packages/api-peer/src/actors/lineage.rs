@@ -0,0 +1,57 @@
+use anyhow::Result;
+use depot::{conveyer::branch as depot_branch, error::SqliteStorageError, types::BucketId};
+use gas::prelude::*;
+use rivet_api_builder::ApiCtx;
+use rivet_api_types::actors::lineage::*;
+
+use super::restore_points::{actor_namespace_id, depot_api_error};
+
+#[tracing::instrument(skip_all)]
+pub async fn get(ctx: ApiCtx, path: LineagePath, query: LineageQuery) -> Result<LineageResponse> {
+	let namespace_id = actor_namespace_id(&ctx, path.actor_id, query.namespace).await?;
+	let udb = ctx.pools().udb()?;
+
+	let (forked_from_actor_id, lineage) = tokio::try_join!(
+		pegboard::actor_fork::read_fork_source(&udb, path.actor_id),
+		async {
+			match depot_branch::database_lineage(
+				&udb,
+				BucketId::from_gas_id(namespace_id),
+				path.actor_id.to_string(),
+			)
+			.await
+			{
+				Ok(lineage) => Ok(lineage),
+				// The actor has not opened its database yet
+				Err(err)
+					if err.chain().any(|source| {
+						matches!(
+							source.downcast_ref::<SqliteStorageError>(),
+							Some(SqliteStorageError::DatabaseNotFound)
+						)
+					}) =>
+				{
+					Ok(Vec::new())
+				}
+				Err(err) => Err(depot_api_error(err)),
+			}
+		},
+	)?;
+
+	let branches = lineage
+		.into_iter()
+		.map(|record| DatabaseBranch {
+			branch_id: record.branch_id.as_uuid().to_string(),
+			parent_branch_id: record.parent.map(|parent| parent.as_uuid().to_string()),
+			create_ts: record.created_at_ms,
+			restore_point_id: record
+				.created_from_restore_point
+				.map(|restore_point| restore_point.restore_point.into_string()),
+		})
+		.collect();
+
+	Ok(LineageResponse {
+		forked_from_actor_id,
+		branches,
+	})
+}
@@ -1,8 +1,14 @@
 pub mod create;
+pub mod database_query;
+pub mod database_replicas;
 pub mod delete;
+pub mod export;
 pub mod get_or_create;
 pub mod kv_get;
+pub mod lineage;
 pub mod list;
 pub mod list_names;
 pub mod reschedule;
+pub mod restore;
+pub mod restore_points;
 pub mod sleep;
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use depot::types::SnapshotSelector;
+use gas::prelude::*;
+use rivet_api_builder::{ApiBadRequest, ApiCtx};
+use rivet_api_types::actors::restore::*;
+
+use super::restore_points::{actor_db, depot_api_error, parse_restore_point_id};
+
+/// Rolls the actor's database back to a restore point or timestamp, then sleeps the actor so it
+/// reloads from the restored state on its next wake.
+///
+/// A running actor's next commit fails the head fence once the rollback lands, so it can not write
+/// on top of the restored state before it stops.
+#[tracing::instrument(skip_all)]
+pub async fn restore(
+	ctx: ApiCtx,
+	path: RestorePath,
+	query: RestoreQuery,
+	body: RestoreRequest,
+) -> Result<RestoreResponse> {
+	let selector = match (body.restore_point_id, body.ts) {
+		(Some(restore_point_id), None) => SnapshotSelector::RestorePoint {
+			restore_point: parse_restore_point_id(restore_point_id)?,
+		},
+		(None, Some(timestamp_ms)) => SnapshotSelector::AtTimestamp { timestamp_ms },
+		_ => {
+			return Err(ApiBadRequest {
+				reason: "exactly one of `restore_point_id` or `ts` must be provided".to_string(),
+			}
+			.build());
+		}
+	};
+
+	let db = actor_db(&ctx, path.actor_id, query.namespace).await?;
+	let undo_restore_point_id = db
+		.restore_database(selector)
+		.await
+		.map_err(depot_api_error)?;
+
+	let res = ctx
+		.signal(pegboard::workflows::actor2::Sleep {})
+		.to_workflow::<pegboard::workflows::actor2::Workflow>()
+		.tag("actor_id", path.actor_id)
+		.graceful_not_found()
+		.send()
+		.await?;
+
+	if res.is_none() {
+		tracing::debug!(
+			actor_id=?path.actor_id,
+			"actor workflow not found, restored database will load on next start"
+		);
+	}
+
+	Ok(RestoreResponse {
+		undo_restore_point_id: undo_restore_point_id.into_string(),
+	})
+}
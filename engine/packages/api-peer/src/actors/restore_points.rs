@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use depot::{
+	conveyer::Db,
+	error::SqliteStorageError,
+	types::{PinStatus, RestorePointId, RestorePointRecord, SnapshotSelector},
+};
+use gas::prelude::*;
+use rivet_api_builder::{ApiBadRequest, ApiCtx};
+use rivet_api_types::actors::restore_points::*;
+
+#[tracing::instrument(skip_all)]
+pub async fn list(
+	ctx: ApiCtx,
+	path: RestorePointsPath,
+	query: RestorePointsQuery,
+) -> Result<ListRestorePointsResponse> {
+	let db = actor_db(&ctx, path.actor_id, query.namespace).await?;
+
+	let restore_points = db
+		.list_restore_points()
+		.await
+		.map_err(depot_api_error)?
+		.into_iter()
+		.map(restore_point_from_record)
+		.collect();
+
+	Ok(ListRestorePointsResponse { restore_points })
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create(
+	ctx: ApiCtx,
+	path: RestorePointsPath,
+	query: RestorePointsQuery,
+	body: CreateRestorePointRequest,
+) -> Result<CreateRestorePointResponse> {
+	let db = actor_db(&ctx, path.actor_id, query.namespace).await?;
+
+	let selector = match body.ts {
+		Some(timestamp_ms) => SnapshotSelector::AtTimestamp { timestamp_ms },
+		None => SnapshotSelector::Latest,
+	};
+	let restore_point_id = db
+		.create_restore_point(selector)
+		.await
+		.map_err(depot_api_error)?;
+	let status = db
+		.restore_point_status(restore_point_id.clone())
+		.await
+		.map_err(depot_api_error)?
+		.unwrap_or(PinStatus::Pending);
+	let (create_ts, _txid) = restore_point_id.parse()?;
+
+	Ok(CreateRestorePointResponse {
+		restore_point: RestorePoint {
+			restore_point_id: restore_point_id.into_string(),
+			create_ts,
+			status: restore_point_status(status),
+		},
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn delete(
+	ctx: ApiCtx,
+	path: RestorePointPath,
+	query: RestorePointsQuery,
+) -> Result<DeleteRestorePointResponse> {
+	let db = actor_db(&ctx, path.actor_id, query.namespace).await?;
+	let restore_point_id = parse_restore_point_id(path.restore_point_id)?;
+
+	if db
+		.restore_point_status(restore_point_id.clone())
+		.await
+		.map_err(depot_api_error)?
+		.is_none()
+	{
+		return Err(SqliteStorageError::RestorePointNotFound.build());
+	}
+
+	db.delete_restore_point(restore_point_id)
+		.await
+		.map_err(depot_api_error)?;
+
+	Ok(DeleteRestorePointResponse {})
+}
+
+/// Builds a depot handle for the actor's SQLite database after verifying the actor belongs to the
+/// namespace.
+pub(crate) async fn actor_db(ctx: &ApiCtx, actor_id: Id, namespace: String) -> Result<Db> {
+	let namespace_id = actor_namespace_id(ctx, actor_id, namespace).await?;
+	let udb = ctx.pools().udb()?;
+
+	Db::from_config(
+		ctx.config(),
+		Arc::new((*udb).clone()),
+		namespace_id,
+		actor_id.to_string(),
+		ctx.pools().node_id(),
+	)
+	.await
+}
+
+/// Resolves the namespace and verifies the actor belongs to it.
+pub(crate) async fn actor_namespace_id(
+	ctx: &ApiCtx,
+	actor_id: Id,
+	namespace: String,
+) -> Result<Id> {
+	let actors_res = ctx
+		.op(pegboard::ops::actor::get::Input {
+			actor_ids: vec![actor_id],
+			fetch_error: false,
+		})
+		.await?;
+
+	let actor = actors_res
+		.actors
+		.into_iter()
+		.next()
+		.ok_or_else(|| pegboard::errors::Actor::NotFound.build())?;
+
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input { name: namespace })
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	if actor.namespace_id != namespace.namespace_id {
+		return Err(pegboard::errors::Actor::NotFound.build());
+	}
+
+	Ok(namespace.namespace_id)
+}
+
+pub(crate) fn parse_restore_point_id(restore_point_id: String) -> Result<RestorePointId> {
+	RestorePointId::new(restore_point_id).map_err(|err| {
+		ApiBadRequest {
+			reason: format!("invalid restore point id: {err}"),
+		}
+		.build()
+	})
+}
+
+/// Depot surfaces its errors as raw `SqliteStorageError`s. Rebuild them so the API returns the
+/// typed error instead of an internal error.
+pub(crate) fn depot_api_error(err: anyhow::Error) -> anyhow::Error {
+	match err
+		.chain()
+		.find_map(|source| source.downcast_ref::<SqliteStorageError>())
+	{
+		Some(depot_err) => depot_err.clone().build(),
+		None => err,
+	}
+}
+
+fn restore_point_from_record(record: RestorePointRecord) -> RestorePoint {
+	RestorePoint {
+		restore_point_id: record.restore_point_id.into_string(),
+		create_ts: record.created_at_ms,
+		status: restore_point_status(record.status),
+	}
+}
+
+fn restore_point_status(status: PinStatus) -> RestorePointStatus {
+	match status {
+		PinStatus::Pending => RestorePointStatus::Pending,
+		PinStatus::Ready => RestorePointStatus::Ready,
+		PinStatus::Failed => RestorePointStatus::Failed,
+	}
+}
@@ -1,9 +1,20 @@
 use anyhow::Result;
 use gas::prelude::*;
 use rivet_api_builder::{ApiBadRequest, ApiCtx};
-use rivet_api_types::{namespaces::list::*, pagination::Pagination};
+use rivet_api_types::{
+	namespaces::{
+		auth_policy::*, canary_policy::*, database_policy::*, domains::*, list::*,
+		rate_limit_policy::*, region_policy::*, usage::*,
+	},
+	pagination::Pagination,
+};
+use rivet_types::namespaces::{
+	AuthMethod, AuthPolicy, CanaryPolicy, DomainRoute, Jwk, RateLimitPolicy, RegionMode,
+	RegionPolicy,
+};
 use rivet_util::Id;
 use serde::{Deserialize, Serialize};
+use universaldb::utils::IsolationLevel::*;
 use utoipa::ToSchema;
 
 #[tracing::instrument(skip_all)]
@@ -135,3 +146,758 @@ pub async fn create(
 
 	Ok(CreateResponse { namespace })
 }
+
+/// Returns the storage used by a namespace (or one of its actors) in this datacenter.
+#[tracing::instrument(skip_all)]
+pub async fn usage(ctx: ApiCtx, path: UsagePath, query: UsageQuery) -> Result<UsageResponse> {
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: path.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let usage = ctx
+		.op(namespace::ops::get_storage_usage_local::Input {
+			namespace_id: namespace.namespace_id,
+			actor_id: query.actor_id,
+		})
+		.await?;
+
+	Ok(UsageResponse {
+		actor_kv_bytes: usage.actor_kv_bytes,
+		sqlite_bytes: usage.sqlite_bytes,
+		workflow_history_bytes: usage.workflow_history_bytes,
+		workflow_queue_bytes: usage.workflow_queue_bytes,
+		total_bytes: usage.actor_kv_bytes
+			+ usage.sqlite_bytes
+			+ usage.workflow_history_bytes
+			+ usage.workflow_queue_bytes,
+	})
+}
+
+/// Returns the database policy of a namespace and how its databases in this datacenter measure
+/// against the storage limits.
+#[tracing::instrument(skip_all)]
+pub async fn get_database_policy(
+	ctx: ApiCtx,
+	path: DatabasePolicyPath,
+	_query: DatabasePolicyQuery,
+) -> Result<GetDatabasePolicyResponse> {
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: path.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let policy = read_database_policy(&ctx, namespace.namespace_id).await?;
+	let usage = ctx
+		.op(namespace::ops::get_storage_usage_local::Input {
+			namespace_id: namespace.namespace_id,
+			actor_id: None,
+		})
+		.await?;
+
+	Ok(GetDatabasePolicyResponse {
+		datacenters: vec![DatabaseStorageStatus {
+			datacenter: ctx.config().dc_name()?.to_string(),
+			sqlite_bytes: usage.sqlite_bytes,
+			soft_limit_exceeded: policy
+				.storage_soft_limit_bytes
+				.is_some_and(|limit| usage.sqlite_bytes > limit),
+			quota_exceeded: policy
+				.storage_quota_bytes
+				.is_some_and(|limit| usage.sqlite_bytes > limit),
+		}],
+		policy,
+	})
+}
+
+/// Updates the PITR policy and storage limits of every actor database in a namespace in this
+/// datacenter. Omitted fields keep their current value.
+#[tracing::instrument(skip_all)]
+pub async fn upsert_database_policy(
+	ctx: ApiCtx,
+	path: DatabasePolicyPath,
+	_query: (),
+	body: UpsertDatabasePolicyRequest,
+) -> Result<UpsertDatabasePolicyResponse> {
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: path.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	depot::policy::update_namespace_policy(
+		&ctx.pools().udb()?,
+		namespace.namespace_id,
+		depot::policy::NamespacePolicyUpdate {
+			pitr_interval_ms: body.policy.pitr_interval_ms,
+			pitr_retention_ms: body.policy.pitr_retention_ms,
+			hard_limit_bytes: body.policy.storage_quota_bytes,
+			soft_limit_bytes: body.policy.storage_soft_limit_bytes,
+		},
+	)
+	.await
+	.map_err(crate::actors::restore_points::depot_api_error)?;
+
+	Ok(UpsertDatabasePolicyResponse {
+		policy: read_database_policy(&ctx, namespace.namespace_id).await?,
+	})
+}
+
+async fn read_database_policy(ctx: &ApiCtx, namespace_id: Id) -> Result<DatabasePolicy> {
+	let udb = ctx.pools().udb()?;
+	let pitr = depot::policy::get_bucket_pitr_policy(
+		&udb,
+		depot::types::BucketId::from_gas_id(namespace_id),
+	)
+	.await?;
+	let quota = depot::policy::get_namespace_quota(&udb, namespace_id).await?;
+
+	Ok(DatabasePolicy {
+		pitr_interval_ms: pitr.interval_ms,
+		pitr_retention_ms: pitr.retention_ms,
+		storage_quota_bytes: quota.hard_limit_bytes,
+		storage_soft_limit_bytes: quota.soft_limit_bytes,
+	})
+}
+
+/// A namespace policy managed through the API. Every datacenter stores its own copy, which
+/// api-public writes to each datacenter so the services reading the local copy agree.
+pub trait ApiPolicy: namespace::keys::policy::Policy + Clone + Send + Sync + 'static {
+	/// Path segment of the policy routes, `/namespaces/{namespace}/{PATH}`.
+	const PATH: &'static str;
+
+	fn validate(&self, ctx: &ApiCtx) -> Result<()>;
+
+	/// Fills fields omitted on write from the stored policy.
+	fn merge_existing(&mut self, _existing: &Self) -> Result<()> {
+		Ok(())
+	}
+
+	/// Removes fields that are only accepted on write.
+	fn redact(&mut self) {}
+}
+
+/// Returns a policy of a namespace in this datacenter.
+#[tracing::instrument(skip_all, fields(policy = P::PATH))]
+pub async fn get_policy<P: ApiPolicy>(ctx: &ApiCtx, namespace: String) -> Result<P> {
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input { name: namespace })
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let mut policy = ctx
+		.udb()?
+		.txn("api_peer_get_policy", |tx| async move {
+			let tx = tx.with_subspace(namespace::keys::subspace());
+			namespace::keys::policy::read::<P>(&tx, namespace.namespace_id, Serializable).await
+		})
+		.await?;
+	policy.redact();
+
+	Ok(policy)
+}
+
+/// Replaces a policy of a namespace in this datacenter. Services read policies through a cache,
+/// so the new policy applies within a few seconds.
+#[tracing::instrument(skip_all, fields(policy = P::PATH))]
+pub async fn upsert_policy<P: ApiPolicy>(ctx: &ApiCtx, namespace: String, policy: P) -> Result<P> {
+	policy.validate(ctx)?;
+
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input { name: namespace })
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let policy = &policy;
+	let mut policy = ctx
+		.udb()?
+		.txn("api_peer_upsert_policy", |tx| async move {
+			let tx = tx.with_subspace(namespace::keys::subspace());
+			let existing =
+				namespace::keys::policy::read::<P>(&tx, namespace.namespace_id, Serializable)
+					.await?;
+			let mut policy = policy.clone();
+			policy.merge_existing(&existing)?;
+			namespace::keys::policy::write(&tx, namespace.namespace_id, policy.clone())?;
+
+			Ok(policy)
+		})
+		.await?;
+	policy.redact();
+
+	Ok(policy)
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn get_rate_limit_policy(
+	ctx: ApiCtx,
+	path: RateLimitPolicyPath,
+	_query: RateLimitPolicyQuery,
+) -> Result<GetRateLimitPolicyResponse> {
+	Ok(GetRateLimitPolicyResponse {
+		policy: get_policy(&ctx, path.namespace).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert_rate_limit_policy(
+	ctx: ApiCtx,
+	path: RateLimitPolicyPath,
+	_query: (),
+	body: UpsertRateLimitPolicyRequest,
+) -> Result<UpsertRateLimitPolicyResponse> {
+	Ok(UpsertRateLimitPolicyResponse {
+		policy: upsert_policy(&ctx, path.namespace, body.policy).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn get_auth_policy(
+	ctx: ApiCtx,
+	path: AuthPolicyPath,
+	_query: AuthPolicyQuery,
+) -> Result<GetAuthPolicyResponse> {
+	Ok(GetAuthPolicyResponse {
+		policy: get_policy(&ctx, path.namespace).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert_auth_policy(
+	ctx: ApiCtx,
+	path: AuthPolicyPath,
+	_query: (),
+	body: UpsertAuthPolicyRequest,
+) -> Result<UpsertAuthPolicyResponse> {
+	Ok(UpsertAuthPolicyResponse {
+		policy: upsert_policy(&ctx, path.namespace, body.policy).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn get_canary_policy(
+	ctx: ApiCtx,
+	path: CanaryPolicyPath,
+	_query: CanaryPolicyQuery,
+) -> Result<GetCanaryPolicyResponse> {
+	Ok(GetCanaryPolicyResponse {
+		policy: get_policy(&ctx, path.namespace).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert_canary_policy(
+	ctx: ApiCtx,
+	path: CanaryPolicyPath,
+	_query: (),
+	body: UpsertCanaryPolicyRequest,
+) -> Result<UpsertCanaryPolicyResponse> {
+	Ok(UpsertCanaryPolicyResponse {
+		policy: upsert_policy(&ctx, path.namespace, body.policy).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn get_region_policy(
+	ctx: ApiCtx,
+	path: RegionPolicyPath,
+	_query: RegionPolicyQuery,
+) -> Result<GetRegionPolicyResponse> {
+	Ok(GetRegionPolicyResponse {
+		policy: get_policy(&ctx, path.namespace).await?,
+	})
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn upsert_region_policy(
+	ctx: ApiCtx,
+	path: RegionPolicyPath,
+	_query: (),
+	body: UpsertRegionPolicyRequest,
+) -> Result<UpsertRegionPolicyResponse> {
+	Ok(UpsertRegionPolicyResponse {
+		policy: upsert_policy(&ctx, path.namespace, body.policy).await?,
+	})
+}
+
+/// Longest rate limit period, one day.
+const MAX_RATE_LIMIT_PERIOD_MS: u64 = 24 * 60 * 60 * 1000;
+
+impl ApiPolicy for RateLimitPolicy {
+	const PATH: &'static str = "rate-limit-policy";
+
+	fn validate(&self, _ctx: &ApiCtx) -> Result<()> {
+		let invalid =
+			|reason: String| namespace::errors::Namespace::InvalidRateLimitPolicy { reason };
+
+		let mut names = std::collections::HashSet::new();
+		for rule in &self.rules {
+			if rule.name.is_empty() {
+				return Err(invalid("rule name cannot be empty".to_string()).build());
+			}
+			if !names.insert(rule.name.as_str()) {
+				return Err(invalid(format!("duplicate rule name `{}`", rule.name)).build());
+			}
+			if rule.requests == 0 {
+				return Err(invalid(format!(
+					"rule `{}` must allow at least 1 request",
+					rule.name
+				))
+				.build());
+			}
+			if rule.period_ms == 0 || rule.period_ms > MAX_RATE_LIMIT_PERIOD_MS {
+				return Err(invalid(format!(
+					"rule `{}` must have a period between 1 and {MAX_RATE_LIMIT_PERIOD_MS} ms",
+					rule.name
+				))
+				.build());
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl ApiPolicy for AuthPolicy {
+	const PATH: &'static str = "auth-policy";
+
+	fn validate(&self, ctx: &ApiCtx) -> Result<()> {
+		let invalid = |reason: String| namespace::errors::Namespace::InvalidAuthPolicy { reason };
+
+		let allow_private_jwks_urls = ctx.config().guard().allow_private_jwks_urls();
+		let mut names = std::collections::HashSet::new();
+		for rule in &self.rules {
+			if rule.name.is_empty() {
+				return Err(invalid("rule name cannot be empty".to_string()).build());
+			}
+			if !names.insert(rule.name.as_str()) {
+				return Err(invalid(format!("duplicate rule name `{}`", rule.name)).build());
+			}
+			if rule.actor_name.as_deref() == Some("") {
+				return Err(invalid(format!(
+					"rule `{}` cannot have an empty actor name",
+					rule.name
+				))
+				.build());
+			}
+
+			let res = match &rule.method {
+				AuthMethod::Jwt { jwks_url, jwks, .. } => match (jwks_url, jwks) {
+					(Some(jwks_url), None) if allow_private_jwks_urls => url::Url::parse(jwks_url)
+						.map(|_| ())
+						.map_err(|err| format!("jwks invalid url: {err}")),
+					(Some(jwks_url), None) => rivet_pools::reqwest::check_public_url(jwks_url)
+						.map(|_| ())
+						.map_err(|reason| format!("jwks {reason}")),
+					(None, Some(jwks)) => jwks.keys.iter().try_for_each(validate_jwk),
+					_ => Err("exactly one of jwks url or jwks must be set".to_string()),
+				},
+				AuthMethod::ApiKey { keys } => {
+					let mut key_names = std::collections::HashSet::new();
+					if keys.is_empty() {
+						Err("must have at least 1 key".to_string())
+					} else if let Some(key) = keys.iter().find(|key| !key_names.insert(&key.name)) {
+						Err(format!("duplicate key name `{}`", key.name))
+					} else if let Some(key) = keys.iter().find(|key| {
+						key.sha256.len() != 64 || !key.sha256.bytes().all(|b| b.is_ascii_hexdigit())
+					}) {
+						Err(format!("key `{}` must be a hex-encoded sha256", key.name))
+					} else {
+						Ok(())
+					}
+				}
+				AuthMethod::SignedUrl { secret } => {
+					if secret.as_ref().is_some_and(|secret| secret.len() < 32) {
+						Err("secret must be at least 32 bytes".to_string())
+					} else {
+						Ok(())
+					}
+				}
+			};
+			if let Err(reason) = res {
+				return Err(invalid(format!("rule `{}`: {reason}", rule.name)).build());
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Signed URL rules written without a secret keep the secret of the stored rule with the same
+	/// name, so a policy read from the API can be written back.
+	fn merge_existing(&mut self, existing: &Self) -> Result<()> {
+		for rule in &mut self.rules {
+			let AuthMethod::SignedUrl {
+				secret: secret @ None,
+			} = &mut rule.method
+			else {
+				continue;
+			};
+
+			*secret = existing
+				.rules
+				.iter()
+				.find(|existing_rule| existing_rule.name == rule.name)
+				.and_then(|existing_rule| match &existing_rule.method {
+					AuthMethod::SignedUrl { secret } => secret.clone(),
+					_ => None,
+				});
+			if secret.is_none() {
+				return Err(namespace::errors::Namespace::InvalidAuthPolicy {
+					reason: format!("rule `{}`: secret is required", rule.name),
+				}
+				.build());
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Signed URL secrets are only accepted on write.
+	fn redact(&mut self) {
+		for rule in &mut self.rules {
+			if let AuthMethod::SignedUrl { secret } = &mut rule.method {
+				*secret = None;
+			}
+		}
+	}
+}
+
+fn validate_jwk(jwk: &Jwk) -> std::result::Result<(), String> {
+	let has_params = match jwk.kty.as_str() {
+		"RSA" => jwk.n.is_some() && jwk.e.is_some(),
+		"EC" => jwk.crv.as_deref() == Some("P-256") && jwk.x.is_some() && jwk.y.is_some(),
+		"OKP" => jwk.crv.as_deref() == Some("Ed25519") && jwk.x.is_some(),
+		kty => return Err(format!("unsupported jwk key type `{kty}`")),
+	};
+
+	if has_params {
+		Ok(())
+	} else {
+		Err(format!(
+			"jwk {} is missing key parameters or uses an unsupported curve",
+			jwk.kid.as_deref().unwrap_or("without kid")
+		))
+	}
+}
+
+impl ApiPolicy for CanaryPolicy {
+	const PATH: &'static str = "canary-policy";
+
+	fn validate(&self, _ctx: &ApiCtx) -> Result<()> {
+		let invalid = |reason: String| namespace::errors::Namespace::InvalidCanaryPolicy { reason };
+
+		let mut rules = std::collections::HashSet::new();
+		for rule in &self.rules {
+			if rule.pool_name.is_empty() || rule.canary_pool_name.is_empty() {
+				return Err(invalid("pool names cannot be empty".to_string()).build());
+			}
+			if rule.pool_name == rule.canary_pool_name {
+				return Err(invalid(format!(
+					"pool `{}` cannot be its own canary",
+					rule.pool_name
+				))
+				.build());
+			}
+			if rule.weight > 100 {
+				return Err(invalid(format!(
+					"weight of pool `{}` must be between 0 and 100",
+					rule.pool_name
+				))
+				.build());
+			}
+			if rule.actor_name.as_deref() == Some("") {
+				return Err(invalid(format!(
+					"rule for pool `{}` cannot have an empty actor name",
+					rule.pool_name
+				))
+				.build());
+			}
+			if !rules.insert((rule.pool_name.as_str(), rule.actor_name.as_deref())) {
+				return Err(
+					invalid(format!("duplicate rule for pool `{}`", rule.pool_name)).build(),
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl ApiPolicy for RegionPolicy {
+	const PATH: &'static str = "region-policy";
+
+	fn validate(&self, ctx: &ApiCtx) -> Result<()> {
+		let invalid = |reason: String| namespace::errors::Namespace::InvalidRegionPolicy { reason };
+
+		let mut actor_names = std::collections::HashSet::new();
+		for rule in &self.rules {
+			if rule.actor_name.as_deref() == Some("") {
+				return Err(invalid("actor name cannot be empty".to_string()).build());
+			}
+			if !actor_names.insert(rule.actor_name.as_deref()) {
+				return Err(match &rule.actor_name {
+					Some(actor_name) => invalid(format!("duplicate rule for actor `{actor_name}`")),
+					None => invalid("duplicate rule without an actor name".to_string()),
+				}
+				.build());
+			}
+			if let RegionMode::Pinned { region } = &rule.mode
+				&& ctx.config().dc_for_name(region).is_none()
+			{
+				return Err(invalid(format!("unknown region `{region}`")).build());
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Lists the domain routes of a namespace in this datacenter.
+#[tracing::instrument(skip_all)]
+pub async fn list_domains(
+	ctx: ApiCtx,
+	path: DomainsPath,
+	_query: ListDomainsQuery,
+) -> Result<ListDomainsResponse> {
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: path.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let domains = ctx
+		.udb()?
+		.txn("api_peer_list_domains", |tx| async move {
+			let tx = tx.with_subspace(namespace::keys::subspace());
+			namespace::keys::domain_route::list(&tx, namespace.namespace_id, Serializable).await
+		})
+		.await?;
+
+	Ok(ListDomainsResponse { domains })
+}
+
+/// Creates or replaces a domain route of a namespace in this datacenter. Guard nodes pick up the
+/// new route within a few seconds.
+///
+/// The leader datacenter also claims the hostname for the namespace, after verifying that the
+/// namespace controls it. api-public writes the leader before the other datacenters, so they only
+/// store routes for claimed hostnames.
+#[tracing::instrument(skip_all)]
+pub async fn upsert_domain(
+	ctx: ApiCtx,
+	path: DomainsPath,
+	_query: (),
+	body: UpsertDomainRequest,
+) -> Result<UpsertDomainResponse> {
+	let domain = validate_domain_route(body.domain)?;
+
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: path.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let is_leader = ctx.config().is_leader();
+	if is_leader {
+		let owner = ctx
+			.udb()?
+			.txn("api_peer_read_domain_owner", |tx| {
+				let hostname = domain.hostname.clone();
+				async move {
+					let tx = tx.with_subspace(namespace::keys::subspace());
+					namespace::keys::domain_route::owner(&tx, &hostname, Serializable).await
+				}
+			})
+			.await?;
+
+		// Hostnames are only verified when first claimed, DNS lookups stay out of the txn
+		match owner {
+			Some(owner_id) if owner_id != namespace.namespace_id => {
+				return Err(namespace::errors::Namespace::DomainInUse {
+					hostname: domain.hostname,
+				}
+				.build());
+			}
+			Some(_) => {}
+			None => {
+				if ctx.config().guard().verify_domain_ownership() {
+					verify_domain_ownership(namespace.namespace_id, &domain.hostname).await?;
+				}
+			}
+		}
+	}
+
+	let domain_ref = &domain;
+	ctx.udb()?
+		.txn("api_peer_upsert_domain", |tx| async move {
+			let tx = tx.with_subspace(namespace::keys::subspace());
+			if is_leader {
+				namespace::keys::domain_route::claim(
+					&tx,
+					namespace.namespace_id,
+					&domain_ref.hostname,
+				)
+				.await?;
+			}
+			namespace::keys::domain_route::write(&tx, namespace.namespace_id, domain_ref.clone())
+				.await
+		})
+		.await?;
+
+	Ok(UpsertDomainResponse { domain })
+}
+
+/// Deletes a domain route of a namespace in this datacenter.
+#[tracing::instrument(skip_all)]
+pub async fn delete_domain(
+	ctx: ApiCtx,
+	path: DeleteDomainPath,
+	query: DeleteDomainQuery,
+) -> Result<DeleteDomainResponse> {
+	let namespace = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: path.namespace,
+		})
+		.await?
+		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
+
+	let hostname = path.hostname.to_ascii_lowercase();
+	let path_prefix = normalize_path_prefix(query.path_prefix);
+	let deleted = ctx
+		.udb()?
+		.txn("api_peer_delete_domain", |tx| {
+			let hostname = hostname.clone();
+			let path_prefix = path_prefix.clone();
+			async move {
+				let tx = tx.with_subspace(namespace::keys::subspace());
+				namespace::keys::domain_route::delete(
+					&tx,
+					namespace.namespace_id,
+					hostname,
+					path_prefix,
+				)
+				.await
+			}
+		})
+		.await?;
+
+	if !deleted {
+		return Err(namespace::errors::Namespace::DomainRouteNotFound.build());
+	}
+
+	Ok(DeleteDomainResponse {})
+}
+
+/// Checks the DNS TXT record that proves a namespace controls a hostname. Wildcard hostnames are
+/// verified on their base hostname.
+async fn verify_domain_ownership(namespace_id: Id, hostname: &str) -> Result<()> {
+	let base_hostname = hostname.strip_prefix("*.").unwrap_or(hostname);
+	let record_name = format!("_rivet-challenge.{base_hostname}");
+	let record_value = format!("rivet-namespace={namespace_id}");
+
+	let resolver = hickory_resolver::TokioAsyncResolver::tokio_from_system_conf()?;
+	let verified = match resolver.txt_lookup(format!("{record_name}.")).await {
+		Ok(records) => records
+			.iter()
+			.any(|record| record.to_string() == record_value),
+		Err(err) => {
+			tracing::debug!(?err, %record_name, "domain challenge lookup failed");
+			false
+		}
+	};
+
+	if verified {
+		Ok(())
+	} else {
+		Err(namespace::errors::Namespace::DomainNotVerified {
+			hostname: hostname.to_string(),
+			record_name,
+			record_value,
+		}
+		.build())
+	}
+}
+
+/// Validates a domain route and normalizes its hostname and path prefix.
+fn validate_domain_route(mut route: DomainRoute) -> Result<DomainRoute> {
+	let invalid = |reason: String| namespace::errors::Namespace::InvalidDomainRoute { reason };
+
+	route.hostname = route.hostname.to_ascii_lowercase();
+	let (wildcard, base_hostname) = match route.hostname.strip_prefix("*.") {
+		Some(base_hostname) => (true, base_hostname),
+		None => (false, route.hostname.as_str()),
+	};
+	if !is_valid_hostname(base_hostname) {
+		return Err(invalid(format!("invalid hostname `{}`", route.hostname)).build());
+	}
+
+	if let Some(path_prefix) = &route.path_prefix {
+		if !path_prefix.starts_with('/') {
+			return Err(invalid("path prefix must start with `/`".to_string()).build());
+		}
+		if path_prefix.contains(['?', '#']) || path_prefix.contains("//") {
+			return Err(invalid(format!("invalid path prefix `{path_prefix}`")).build());
+		}
+	}
+	route.path_prefix = normalize_path_prefix(route.path_prefix);
+
+	if route.actor.name.is_empty() {
+		return Err(invalid("actor name cannot be empty".to_string()).build());
+	}
+	if route
+		.actor
+		.pool_name
+		.as_ref()
+		.is_some_and(|pool_name| pool_name.is_empty())
+	{
+		return Err(invalid("pool name cannot be empty".to_string()).build());
+	}
+
+	for template in &route.actor.key {
+		let parts = namespace::utils::parse_domain_key_template(template)
+			.map_err(|reason| invalid(reason).build())?;
+
+		if !wildcard
+			&& parts
+				.iter()
+				.any(|part| *part == namespace::utils::DomainKeyPart::Subdomain)
+		{
+			return Err(invalid(
+				"`{subdomain}` can only be used with a wildcard hostname".to_string(),
+			)
+			.build());
+		}
+	}
+
+	Ok(route)
+}
+
+/// Strips trailing slashes. The root prefix is the same as no prefix.
+fn normalize_path_prefix(path_prefix: Option<String>) -> Option<String> {
+	path_prefix
+		.map(|path_prefix| path_prefix.trim_end_matches('/').to_string())
+		.filter(|path_prefix| !path_prefix.is_empty())
+}
+
+fn is_valid_hostname(hostname: &str) -> bool {
+	let labels = hostname.split('.').collect::<Vec<_>>();
+
+	hostname.len() <= 253
+		&& labels.len() >= 2
+		&& labels.iter().all(|label| {
+			!label.is_empty()
+				&& label.len() <= 63
+				&& !label.starts_with('-')
+				&& !label.ends_with('-')
+				&& label
+					.bytes()
+					.all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+		})
+}
@@ -13,6 +13,59 @@ pub async fn router(
 			// MARK: Namespaces
 			.route("/namespaces", get(namespaces::list))
 			.route("/namespaces", post(namespaces::create))
+			.route("/namespaces/{namespace}/usage", get(namespaces::usage))
+			.route(
+				"/namespaces/{namespace}/database-policy",
+				get(namespaces::get_database_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/database-policy",
+				put(namespaces::upsert_database_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/rate-limit-policy",
+				get(namespaces::get_rate_limit_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/rate-limit-policy",
+				put(namespaces::upsert_rate_limit_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/auth-policy",
+				get(namespaces::get_auth_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/auth-policy",
+				put(namespaces::upsert_auth_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/canary-policy",
+				get(namespaces::get_canary_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/canary-policy",
+				put(namespaces::upsert_canary_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/region-policy",
+				get(namespaces::get_region_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/region-policy",
+				put(namespaces::upsert_region_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/domains",
+				get(namespaces::list_domains),
+			)
+			.route(
+				"/namespaces/{namespace}/domains",
+				put(namespaces::upsert_domain),
+			)
+			.route(
+				"/namespaces/{namespace}/domains/{hostname}",
+				delete(namespaces::delete_domain),
+			)
 			// MARK: Runner configs
 			.route("/runner-configs", get(runner_configs::list))
 			.route("/runner-configs/{runner_name}", put(runner_configs::upsert))
@@ -35,6 +88,42 @@ pub async fn router(
 				"/actors/{actor_id}/reschedule",
 				post(actors::reschedule::reschedule),
 			)
+			.route(
+				"/actors/{actor_id}/database/restore-points",
+				get(actors::restore_points::list),
+			)
+			.route(
+				"/actors/{actor_id}/database/restore-points",
+				post(actors::restore_points::create),
+			)
+			.route(
+				"/actors/{actor_id}/database/restore-points/{restore_point_id}",
+				delete(actors::restore_points::delete),
+			)
+			.route(
+				"/actors/{actor_id}/database/restore",
+				post(actors::restore::restore),
+			)
+			.route(
+				"/actors/{actor_id}/database/lineage",
+				get(actors::lineage::get),
+			)
+			.route(
+				"/actors/{actor_id}/database/export",
+				raw::get(actors::export::export),
+			)
+			.route(
+				"/actors/{actor_id}/database/query",
+				post(actors::database_query::query),
+			)
+			.route(
+				"/actors/{actor_id}/database/replicas",
+				get(actors::database_replicas::get),
+			)
+			.route(
+				"/actors/{actor_id}/database/replicas",
+				put(actors::database_replicas::set),
+			)
 			// MARK: Runners
 			.route("/runners", get(runners::list))
 			.route("/runners/names", get(runners::list_names))
@@ -98,6 +187,10 @@ pub async fn router(
 				get(internal::get_epoxy_kv_optimistic),
 			)
 			.route("/epoxy/replica/kv/{key}", put(internal::set_epoxy_kv))
+			.route(
+				"/depot/replicas/apply",
+				post(actors::database_replicas::apply),
+			)
 			.route("/debug/tracing/config", put(internal::set_tracing_config))
 			.route("/debug/profile/config", put(internal::set_profiling_config))
 	})
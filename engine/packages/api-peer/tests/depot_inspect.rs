@@ -9,6 +9,7 @@ async fn depot_inspect_routes_are_registered_on_api_peer() -> Result<()> {
 	let mut root = Root::default();
 	root.database = Some(Database::FileSystem(FileSystem {
 		path: tempdir.path().join("udb"),
+		replication: None,
 	}));
 	let config = rivet_config::Config::from_root(root);
 	let pools = rivet_pools::Pools::test(config.clone()).await?;
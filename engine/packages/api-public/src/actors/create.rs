@@ -25,6 +25,10 @@ use crate::ctx::ApiCtx;
 /// - [pegboard::workflows::actor] Create actor workflow (includes Epoxy key allocation)
 ///
 /// actor::get will always be in the same datacenter.
+///
+/// **If `fork_from` is set:**
+///
+/// The actor is created in the source actor's datacenter.
 #[utoipa::path(
 	post,
 	operation_id = "actors_create",
@@ -52,7 +56,12 @@ async fn create_inner(
 	query: CreateQuery,
 	body: CreateRequest,
 ) -> Result<CreateResponse> {
-	ctx.skip_auth();
+	// Forking reads another actor's storage, so it needs the same access as the restore point APIs
+	if body.fork_from.is_some() {
+		ctx.auth().await?;
+	} else {
+		ctx.skip_auth();
+	}
 
 	let namespace = ctx
 		.op(namespace::ops::resolve_for_name_global::Input {
@@ -61,12 +70,34 @@ async fn create_inner(
 		.await?
 		.ok_or_else(|| namespace::errors::Namespace::NotFound.build())?;
 
+	// Forks are created in the source actor's datacenter since that is where its storage lives
+	let fork_dc_name = if let Some(fork_from) = &body.fork_from {
+		let fork_dc = ctx
+			.config()
+			.dc_for_label(fork_from.actor_id.label())
+			.ok_or_else(|| pegboard::errors::Actor::ForkSourceNotFound.build())?;
+		if body
+			.datacenter
+			.as_ref()
+			.is_some_and(|dc_name| *dc_name != fork_dc.name)
+		{
+			return Err(pegboard::errors::Actor::ForkSourceInDifferentDatacenter {
+				datacenter_label: fork_dc.datacenter_label,
+			}
+			.build());
+		}
+
+		Some(fork_dc.name.as_str())
+	} else {
+		None
+	};
+
 	let target_dc_label = super::utils::find_dc_for_actor_creation(
 		&ctx,
 		namespace.namespace_id,
 		&query.namespace,
 		&body.runner_name_selector,
-		body.datacenter.as_ref().map(String::as_str),
+		fork_dc_name.or(body.datacenter.as_deref()),
 	)
 	.await?;
 
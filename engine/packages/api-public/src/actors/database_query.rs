@@ -0,0 +1,90 @@
+use anyhow::Result;
+use axum::response::{IntoResponse, Response};
+use rivet_api_builder::{
+	ApiError,
+	extract::{Extension, Json, Path, Query},
+};
+use rivet_api_types::actors::database_query::*;
+use rivet_api_util::request_remote_datacenter_raw;
+use rivet_util::Id;
+
+use crate::ctx::ApiCtx;
+
+/// Runs a read-only query against the actor's SQLite database.
+///
+/// With `max_staleness_ms` set, a read replica in the receiving datacenter serves the query when
+/// it is at most that far behind. Otherwise the query runs in the actor's datacenter.
+#[utoipa::path(
+	post,
+	operation_id = "actors_query_database",
+	path = "/actors/{actor_id}/database/query",
+	params(
+		("actor_id" = Id, Path),
+		DatabaseQueryQuery,
+	),
+	request_body(content = DatabaseQueryRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = DatabaseQueryResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn query(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DatabaseQueryPath>,
+	Query(query): Query<DatabaseQueryQuery>,
+	Json(body): Json<DatabaseQueryRequest>,
+) -> Response {
+	match query_inner(ctx, path, query, body).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn query_inner(
+	ctx: ApiCtx,
+	path: DatabaseQueryPath,
+	query: DatabaseQueryQuery,
+	body: DatabaseQueryRequest,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res =
+			rivet_api_peer::actors::database_query::query(ctx.into(), path, query, body).await?;
+
+		return Ok(Json(res).into_response());
+	}
+
+	if body.max_staleness_ms.is_some() {
+		let replica_res = rivet_api_peer::actors::database_query::query_replica(
+			ctx.clone().into(),
+			DatabaseQueryPath {
+				actor_id: path.actor_id,
+			},
+			DatabaseQueryQuery {
+				namespace: query.namespace.clone(),
+			},
+			DatabaseQueryRequest {
+				sql: body.sql.clone(),
+				params: body.params.clone(),
+				max_staleness_ms: body.max_staleness_ms,
+			},
+		)
+		.await?;
+		if let Some(res) = replica_res {
+			return Ok(Json(res).into_response());
+		}
+	}
+
+	request_remote_datacenter_raw(
+		&ctx,
+		path.actor_id.label(),
+		&format!("/actors/{}/database/query", path.actor_id),
+		axum::http::Method::POST,
+		Some(&query),
+		Some(&body),
+	)
+	.await
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use axum::response::{IntoResponse, Response};
+use rivet_api_builder::{
+	ApiError,
+	extract::{Extension, Json, Path, Query},
+};
+use rivet_api_types::actors::database_replicas::*;
+use rivet_api_util::request_remote_datacenter_raw;
+use rivet_util::Id;
+
+use crate::ctx::ApiCtx;
+
+/// Lists the datacenters holding a read replica of the actor's SQLite database.
+#[utoipa::path(
+	get,
+	operation_id = "actors_get_database_replicas",
+	path = "/actors/{actor_id}/database/replicas",
+	params(
+		("actor_id" = Id, Path),
+		DatabaseReplicasQuery,
+	),
+	responses(
+		(status = 200, body = DatabaseReplicasResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DatabaseReplicasPath>,
+	Query(query): Query<DatabaseReplicasQuery>,
+) -> Response {
+	match get_inner(ctx, path, query).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn get_inner(
+	ctx: ApiCtx,
+	path: DatabaseReplicasPath,
+	query: DatabaseReplicasQuery,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res = rivet_api_peer::actors::database_replicas::get(ctx.into(), path, query).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/replicas", path.actor_id),
+			axum::http::Method::GET,
+			Some(&query),
+			Option::<&()>::None,
+		)
+		.await
+	}
+}
+
+/// Replaces the datacenters holding a read replica of the actor's SQLite database.
+#[utoipa::path(
+	put,
+	operation_id = "actors_set_database_replicas",
+	path = "/actors/{actor_id}/database/replicas",
+	params(
+		("actor_id" = Id, Path),
+		DatabaseReplicasQuery,
+	),
+	request_body(content = SetDatabaseReplicasRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = DatabaseReplicasResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn set(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DatabaseReplicasPath>,
+	Query(query): Query<DatabaseReplicasQuery>,
+	Json(body): Json<SetDatabaseReplicasRequest>,
+) -> Response {
+	match set_inner(ctx, path, query, body).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn set_inner(
+	ctx: ApiCtx,
+	path: DatabaseReplicasPath,
+	query: DatabaseReplicasQuery,
+	body: SetDatabaseReplicasRequest,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res =
+			rivet_api_peer::actors::database_replicas::set(ctx.into(), path, query, body).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/replicas", path.actor_id),
+			axum::http::Method::PUT,
+			Some(&query),
+			Some(&body),
+		)
+		.await
+	}
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use axum::response::{IntoResponse, Response};
+use rivet_api_builder::{
+	ApiError,
+	extract::{Extension, Path, Query},
+};
+use rivet_api_types::actors::export::*;
+use rivet_api_util::request_remote_datacenter_raw;
+use rivet_util::Id;
+
+use crate::ctx::ApiCtx;
+
+/// Downloads a consistent snapshot of the actor's SQLite database as a standard SQLite file.
+#[utoipa::path(
+	get,
+	operation_id = "actors_export_database",
+	path = "/actors/{actor_id}/database/export",
+	params(
+		("actor_id" = Id, Path),
+		ExportQuery,
+	),
+	responses(
+		(status = 200, content_type = "application/vnd.sqlite3", body = Vec<u8>),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn export(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<ExportPath>,
+	Query(query): Query<ExportQuery>,
+) -> Response {
+	match export_inner(ctx, path, query).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn export_inner(ctx: ApiCtx, path: ExportPath, query: ExportQuery) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		rivet_api_peer::actors::export::export(ctx.into(), path, query).await
+	} else {
+		// NOTE: The remote response is buffered before being returned
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/export", path.actor_id),
+			axum::http::Method::GET,
+			Some(&query),
+			Option::<&()>::None,
+		)
+		.await
+	}
+}
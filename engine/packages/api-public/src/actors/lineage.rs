@@ -0,0 +1,58 @@
+use anyhow::Result;
+use axum::response::{IntoResponse, Response};
+use rivet_api_builder::{
+	ApiError,
+	extract::{Extension, Json, Path, Query},
+};
+use rivet_api_types::actors::lineage::*;
+use rivet_api_util::request_remote_datacenter_raw;
+use rivet_util::Id;
+
+use crate::ctx::ApiCtx;
+
+/// Returns the actor this actor was forked from and the branches of its SQLite database.
+#[utoipa::path(
+	get,
+	operation_id = "actors_get_database_lineage",
+	path = "/actors/{actor_id}/database/lineage",
+	params(
+		("actor_id" = Id, Path),
+		LineageQuery,
+	),
+	responses(
+		(status = 200, body = LineageResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<LineagePath>,
+	Query(query): Query<LineageQuery>,
+) -> Response {
+	match get_inner(ctx, path, query).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn get_inner(ctx: ApiCtx, path: LineagePath, query: LineageQuery) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res = rivet_api_peer::actors::lineage::get(ctx.into(), path, query).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/lineage", path.actor_id),
+			axum::http::Method::GET,
+			Some(&query),
+			Option::<&()>::None,
+		)
+		.await
+	}
+}
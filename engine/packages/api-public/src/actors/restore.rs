@@ -0,0 +1,64 @@
+use anyhow::Result;
+use axum::response::{IntoResponse, Response};
+use rivet_api_builder::{
+	ApiError,
+	extract::{Extension, Json, Path, Query},
+};
+use rivet_api_types::actors::restore::*;
+use rivet_api_util::request_remote_datacenter_raw;
+use rivet_util::Id;
+
+use crate::ctx::ApiCtx;
+
+#[utoipa::path(
+	post,
+	operation_id = "actors_restore_database",
+	path = "/actors/{actor_id}/database/restore",
+	params(
+		("actor_id" = Id, Path),
+		RestoreQuery,
+	),
+	request_body(content = RestoreRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = RestoreResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn restore(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RestorePath>,
+	Query(query): Query<RestoreQuery>,
+	Json(body): Json<RestoreRequest>,
+) -> Response {
+	match restore_inner(ctx, path, query, body).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn restore_inner(
+	ctx: ApiCtx,
+	path: RestorePath,
+	query: RestoreQuery,
+	body: RestoreRequest,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res = rivet_api_peer::actors::restore::restore(ctx.into(), path, query, body).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/restore", path.actor_id),
+			axum::http::Method::POST,
+			Some(&query),
+			Some(&body),
+		)
+		.await
+	}
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use axum::response::{IntoResponse, Response};
+use rivet_api_builder::{
+	ApiError,
+	extract::{Extension, Json, Path, Query},
+};
+use rivet_api_types::actors::restore_points::*;
+use rivet_api_util::request_remote_datacenter_raw;
+use rivet_util::Id;
+
+use crate::ctx::ApiCtx;
+
+#[utoipa::path(
+	get,
+	operation_id = "actors_list_restore_points",
+	path = "/actors/{actor_id}/database/restore-points",
+	params(
+		("actor_id" = Id, Path),
+		RestorePointsQuery,
+	),
+	responses(
+		(status = 200, body = ListRestorePointsResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RestorePointsPath>,
+	Query(query): Query<RestorePointsQuery>,
+) -> Response {
+	match list_inner(ctx, path, query).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn list_inner(
+	ctx: ApiCtx,
+	path: RestorePointsPath,
+	query: RestorePointsQuery,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res = rivet_api_peer::actors::restore_points::list(ctx.into(), path, query).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/restore-points", path.actor_id),
+			axum::http::Method::GET,
+			Some(&query),
+			Option::<&()>::None,
+		)
+		.await
+	}
+}
+
+#[utoipa::path(
+	post,
+	operation_id = "actors_create_restore_point",
+	path = "/actors/{actor_id}/database/restore-points",
+	params(
+		("actor_id" = Id, Path),
+		RestorePointsQuery,
+	),
+	request_body(content = CreateRestorePointRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = CreateRestorePointResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn create(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RestorePointsPath>,
+	Query(query): Query<RestorePointsQuery>,
+	Json(body): Json<CreateRestorePointRequest>,
+) -> Response {
+	match create_inner(ctx, path, query, body).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn create_inner(
+	ctx: ApiCtx,
+	path: RestorePointsPath,
+	query: RestorePointsQuery,
+	body: CreateRestorePointRequest,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res =
+			rivet_api_peer::actors::restore_points::create(ctx.into(), path, query, body).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!("/actors/{}/database/restore-points", path.actor_id),
+			axum::http::Method::POST,
+			Some(&query),
+			Some(&body),
+		)
+		.await
+	}
+}
+
+#[utoipa::path(
+	delete,
+	operation_id = "actors_delete_restore_point",
+	path = "/actors/{actor_id}/database/restore-points/{restore_point_id}",
+	params(
+		("actor_id" = Id, Path),
+		("restore_point_id" = String, Path),
+		RestorePointsQuery,
+	),
+	responses(
+		(status = 200, body = DeleteRestorePointResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn delete(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RestorePointPath>,
+	Query(query): Query<RestorePointsQuery>,
+) -> Response {
+	match delete_inner(ctx, path, query).await {
+		Ok(response) => response,
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn delete_inner(
+	ctx: ApiCtx,
+	path: RestorePointPath,
+	query: RestorePointsQuery,
+) -> Result<Response> {
+	ctx.auth().await?;
+
+	if path.actor_id.label() == ctx.config().dc_label() {
+		let res = rivet_api_peer::actors::restore_points::delete(ctx.into(), path, query).await?;
+
+		Ok(Json(res).into_response())
+	} else {
+		request_remote_datacenter_raw(
+			&ctx,
+			path.actor_id.label(),
+			&format!(
+				"/actors/{}/database/restore-points/{}",
+				path.actor_id,
+				urlencoding::encode(&path.restore_point_id)
+			),
+			axum::http::Method::DELETE,
+			Some(&query),
+			Option::<&()>::None,
+		)
+		.await
+	}
+}
@@ -2,11 +2,17 @@ use anyhow::Result;
 use axum::response::{IntoResponse, Response};
 use rivet_api_builder::{
 	ApiError,
-	extract::{Extension, Json, Query},
+	extract::{Extension, Json, Path, Query},
 };
 use rivet_api_peer::namespaces::*;
-use rivet_api_types::namespaces::list::*;
-use rivet_api_util::request_remote_datacenter;
+use rivet_api_types::namespaces::{
+	auth_policy::*, canary_policy::*, database_policy::*, domains::*, list::*,
+	rate_limit_policy::*, region_policy::*, usage::*,
+};
+use rivet_api_util::{
+	fanout_to_datacenters, fanout_write_to_datacenters, request_remote_datacenter,
+};
+use serde::Serialize;
 
 use crate::ctx::ApiCtx;
 
@@ -87,3 +93,624 @@ async fn create_inner(ctx: ApiCtx, body: CreateRequest) -> Result<CreateResponse
 		.await
 	}
 }
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - GET /namespaces/{namespace}/usage (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_usage",
+	path = "/namespaces/{namespace}/usage",
+	params(
+		("namespace" = String, Path),
+		UsageQuery,
+	),
+	responses(
+		(status = 200, body = UsageResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn usage(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<UsagePath>,
+	Query(query): Query<UsageQuery>,
+) -> Response {
+	match usage_inner(ctx, path, query).await {
+		Ok(response) => Json(response).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn usage_inner(ctx: ApiCtx, path: UsagePath, query: UsageQuery) -> Result<UsageResponse> {
+	ctx.auth().await?;
+
+	// Usage is metered per datacenter, sum it across all of them
+	fanout_to_datacenters::<UsageResponse, _, _, _, _, UsageResponse>(
+		&ctx,
+		&format!("/namespaces/{}/usage", urlencoding::encode(&path.namespace)),
+		query,
+		|ctx, query| {
+			let path = path.clone();
+			async move { rivet_api_peer::namespaces::usage(ctx, path, query).await }
+		},
+		|_, res, agg| agg.merge(res),
+	)
+	.await
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - GET /namespaces/{namespace}/database-policy (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_get_database_policy",
+	path = "/namespaces/{namespace}/database-policy",
+	params(
+		("namespace" = String, Path),
+		DatabasePolicyQuery,
+	),
+	responses(
+		(status = 200, body = GetDatabasePolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_database_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DatabasePolicyPath>,
+	Query(query): Query<DatabasePolicyQuery>,
+) -> Response {
+	match get_database_policy_inner(ctx, path, query).await {
+		Ok(response) => Json(response).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn get_database_policy_inner(
+	ctx: ApiCtx,
+	path: DatabasePolicyPath,
+	query: DatabasePolicyQuery,
+) -> Result<GetDatabasePolicyResponse> {
+	ctx.auth().await?;
+
+	// Storage limits are enforced per datacenter, collect the status of each
+	let res = fanout_to_datacenters::<
+		GetDatabasePolicyResponse,
+		_,
+		_,
+		_,
+		_,
+		Option<GetDatabasePolicyResponse>,
+	>(
+		&ctx,
+		&format!(
+			"/namespaces/{}/database-policy",
+			urlencoding::encode(&path.namespace)
+		),
+		query,
+		|ctx, query| {
+			let path = path.clone();
+			async move { rivet_api_peer::namespaces::get_database_policy(ctx, path, query).await }
+		},
+		|_, res, agg| match agg {
+			Some(agg) => agg.datacenters.extend(res.datacenters),
+			None => *agg = Some(res),
+		},
+	)
+	.await?;
+
+	let mut res = res.ok_or_else(|| rivet_api_util::errors::Datacenter::NotFound.build())?;
+	res.datacenters
+		.sort_by(|a, b| a.datacenter.cmp(&b.datacenter));
+
+	Ok(res)
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - PUT /namespaces/{namespace}/database-policy (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	put,
+	operation_id = "namespaces_upsert_database_policy",
+	path = "/namespaces/{namespace}/database-policy",
+	params(
+		("namespace" = String, Path),
+	),
+	request_body(content = UpsertDatabasePolicyRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = UpsertDatabasePolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn upsert_database_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DatabasePolicyPath>,
+	Json(body): Json<UpsertDatabasePolicyRequest>,
+) -> Response {
+	match upsert_database_policy_inner(ctx, path, body).await {
+		Ok(response) => Json(response).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn upsert_database_policy_inner(
+	ctx: ApiCtx,
+	path: DatabasePolicyPath,
+	body: UpsertDatabasePolicyRequest,
+) -> Result<UpsertDatabasePolicyResponse> {
+	ctx.auth().await?;
+
+	// Actor databases live in the datacenter of their actor, so every datacenter stores the policy
+	fanout_write_to_datacenters(
+		&ctx,
+		axum::http::Method::PUT,
+		&format!(
+			"/namespaces/{}/database-policy",
+			urlencoding::encode(&path.namespace)
+		),
+		Option::<&()>::None,
+		Some(&body),
+		|ctx| rivet_api_peer::namespaces::upsert_database_policy(ctx, path, (), body.clone()),
+	)
+	.await
+}
+
+/// Services read policies from the copy stored in their own datacenter. Reads return the copy of
+/// this datacenter and writes go to every datacenter.
+#[tracing::instrument(skip_all, fields(policy = P::PATH))]
+async fn get_policy_inner<P: ApiPolicy>(ctx: ApiCtx, namespace: String) -> Result<P> {
+	ctx.auth().await?;
+
+	rivet_api_peer::namespaces::get_policy(&ctx.into(), namespace).await
+}
+
+#[tracing::instrument(skip_all, fields(policy = P::PATH))]
+async fn upsert_policy_inner<P: ApiPolicy>(ctx: ApiCtx, namespace: String, policy: P) -> Result<P> {
+	ctx.auth().await?;
+
+	fanout_write_to_datacenters(
+		&ctx,
+		axum::http::Method::PUT,
+		&format!(
+			"/namespaces/{}/{}",
+			urlencoding::encode(&namespace),
+			P::PATH
+		),
+		Option::<&()>::None,
+		Some(&UpsertPolicyBody {
+			policy: policy.clone(),
+		}),
+		|ctx| async move { rivet_api_peer::namespaces::upsert_policy(&ctx, namespace, policy).await },
+	)
+	.await
+}
+
+/// Body of the api-peer upsert route of every policy.
+#[derive(Serialize)]
+struct UpsertPolicyBody<P> {
+	policy: P,
+}
+
+/// ## Datacenter Round Trips
+///
+/// 1 round trip:
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_get_rate_limit_policy",
+	path = "/namespaces/{namespace}/rate-limit-policy",
+	params(
+		("namespace" = String, Path),
+		RateLimitPolicyQuery,
+	),
+	responses(
+		(status = 200, body = GetRateLimitPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_rate_limit_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RateLimitPolicyPath>,
+	Query(_query): Query<RateLimitPolicyQuery>,
+) -> Response {
+	match get_policy_inner(ctx, path.namespace).await {
+		Ok(policy) => Json(GetRateLimitPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - PUT /namespaces/{namespace}/rate-limit-policy (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	put,
+	operation_id = "namespaces_upsert_rate_limit_policy",
+	path = "/namespaces/{namespace}/rate-limit-policy",
+	params(
+		("namespace" = String, Path),
+	),
+	request_body(content = UpsertRateLimitPolicyRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = UpsertRateLimitPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn upsert_rate_limit_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RateLimitPolicyPath>,
+	Json(body): Json<UpsertRateLimitPolicyRequest>,
+) -> Response {
+	match upsert_policy_inner(ctx, path.namespace, body.policy).await {
+		Ok(policy) => Json(UpsertRateLimitPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 1 round trip:
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_get_auth_policy",
+	path = "/namespaces/{namespace}/auth-policy",
+	params(
+		("namespace" = String, Path),
+		AuthPolicyQuery,
+	),
+	responses(
+		(status = 200, body = GetAuthPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_auth_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<AuthPolicyPath>,
+	Query(_query): Query<AuthPolicyQuery>,
+) -> Response {
+	match get_policy_inner(ctx, path.namespace).await {
+		Ok(policy) => Json(GetAuthPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - PUT /namespaces/{namespace}/auth-policy (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	put,
+	operation_id = "namespaces_upsert_auth_policy",
+	path = "/namespaces/{namespace}/auth-policy",
+	params(
+		("namespace" = String, Path),
+	),
+	request_body(content = UpsertAuthPolicyRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = UpsertAuthPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn upsert_auth_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<AuthPolicyPath>,
+	Json(body): Json<UpsertAuthPolicyRequest>,
+) -> Response {
+	match upsert_policy_inner(ctx, path.namespace, body.policy).await {
+		Ok(policy) => Json(UpsertAuthPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 1 round trip:
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_get_canary_policy",
+	path = "/namespaces/{namespace}/canary-policy",
+	params(
+		("namespace" = String, Path),
+		CanaryPolicyQuery,
+	),
+	responses(
+		(status = 200, body = GetCanaryPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_canary_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<CanaryPolicyPath>,
+	Query(_query): Query<CanaryPolicyQuery>,
+) -> Response {
+	match get_policy_inner(ctx, path.namespace).await {
+		Ok(policy) => Json(GetCanaryPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - PUT /namespaces/{namespace}/canary-policy (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	put,
+	operation_id = "namespaces_upsert_canary_policy",
+	path = "/namespaces/{namespace}/canary-policy",
+	params(
+		("namespace" = String, Path),
+	),
+	request_body(content = UpsertCanaryPolicyRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = UpsertCanaryPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn upsert_canary_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<CanaryPolicyPath>,
+	Json(body): Json<UpsertCanaryPolicyRequest>,
+) -> Response {
+	match upsert_policy_inner(ctx, path.namespace, body.policy).await {
+		Ok(policy) => Json(UpsertCanaryPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 1 round trip:
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_get_region_policy",
+	path = "/namespaces/{namespace}/region-policy",
+	params(
+		("namespace" = String, Path),
+		RegionPolicyQuery,
+	),
+	responses(
+		(status = 200, body = GetRegionPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_region_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RegionPolicyPath>,
+	Query(_query): Query<RegionPolicyQuery>,
+) -> Response {
+	match get_policy_inner(ctx, path.namespace).await {
+		Ok(policy) => Json(GetRegionPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - PUT /namespaces/{namespace}/region-policy (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	put,
+	operation_id = "namespaces_upsert_region_policy",
+	path = "/namespaces/{namespace}/region-policy",
+	params(
+		("namespace" = String, Path),
+	),
+	request_body(content = UpsertRegionPolicyRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = UpsertRegionPolicyResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn upsert_region_policy(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<RegionPolicyPath>,
+	Json(body): Json<UpsertRegionPolicyRequest>,
+) -> Response {
+	match upsert_policy_inner(ctx, path.namespace, body.policy).await {
+		Ok(policy) => Json(UpsertRegionPolicyResponse { policy }).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+/// ## Datacenter Round Trips
+///
+/// 1 round trip:
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	get,
+	operation_id = "namespaces_list_domains",
+	path = "/namespaces/{namespace}/domains",
+	params(
+		("namespace" = String, Path),
+		ListDomainsQuery,
+	),
+	responses(
+		(status = 200, body = ListDomainsResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn list_domains(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DomainsPath>,
+	Query(query): Query<ListDomainsQuery>,
+) -> Response {
+	match list_domains_inner(ctx, path, query).await {
+		Ok(response) => Json(response).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn list_domains_inner(
+	ctx: ApiCtx,
+	path: DomainsPath,
+	query: ListDomainsQuery,
+) -> Result<ListDomainsResponse> {
+	ctx.auth().await?;
+
+	// Every datacenter stores the same routes, read the local copy
+	rivet_api_peer::namespaces::list_domains(ctx.into(), path, query).await
+}
+
+/// ## Datacenter Round Trips
+///
+/// 3 round trips:
+/// - PUT /namespaces/{namespace}/domains (leader)
+/// - PUT /namespaces/{namespace}/domains (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	put,
+	operation_id = "namespaces_upsert_domain",
+	path = "/namespaces/{namespace}/domains",
+	params(
+		("namespace" = String, Path),
+	),
+	request_body(content = UpsertDomainRequest, content_type = "application/json"),
+	responses(
+		(status = 200, body = UpsertDomainResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn upsert_domain(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DomainsPath>,
+	Json(body): Json<UpsertDomainRequest>,
+) -> Response {
+	match upsert_domain_inner(ctx, path, body).await {
+		Ok(response) => Json(response).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn upsert_domain_inner(
+	ctx: ApiCtx,
+	path: DomainsPath,
+	body: UpsertDomainRequest,
+) -> Result<UpsertDomainResponse> {
+	ctx.auth().await?;
+
+	let endpoint = format!(
+		"/namespaces/{}/domains",
+		urlencoding::encode(&path.namespace)
+	);
+
+	// The leader verifies and claims the hostname, write it first so other datacenters never
+	// store routes for a hostname another namespace owns
+	if ctx.config().is_leader() {
+		rivet_api_peer::namespaces::upsert_domain(
+			ctx.clone().into(),
+			path.clone(),
+			(),
+			body.clone(),
+		)
+		.await?;
+	} else {
+		let leader_dc = ctx.config().leader_dc()?;
+		request_remote_datacenter::<UpsertDomainResponse>(
+			ctx.config(),
+			leader_dc.datacenter_label,
+			&endpoint,
+			axum::http::Method::PUT,
+			Option::<&()>::None,
+			Some(&body),
+		)
+		.await?;
+	}
+
+	// Guard routes with the domains stored in its own datacenter, so every datacenter stores the
+	// route. Writing the leader again is a no-op.
+	fanout_write_to_datacenters(
+		&ctx,
+		axum::http::Method::PUT,
+		&endpoint,
+		Option::<&()>::None,
+		Some(&body),
+		|ctx| rivet_api_peer::namespaces::upsert_domain(ctx, path, (), body.clone()),
+	)
+	.await
+}
+
+/// ## Datacenter Round Trips
+///
+/// 2 round trips:
+/// - DELETE /namespaces/{namespace}/domains/{hostname} (fanout)
+/// - [api-peer] namespace::ops::resolve_for_name_global
+#[utoipa::path(
+	delete,
+	operation_id = "namespaces_delete_domain",
+	path = "/namespaces/{namespace}/domains/{hostname}",
+	params(
+		("namespace" = String, Path),
+		("hostname" = String, Path),
+		DeleteDomainQuery,
+	),
+	responses(
+		(status = 200, body = DeleteDomainResponse),
+	),
+	security(("bearer_auth" = [])),
+)]
+#[tracing::instrument(skip_all)]
+pub async fn delete_domain(
+	Extension(ctx): Extension<ApiCtx>,
+	Path(path): Path<DeleteDomainPath>,
+	Query(query): Query<DeleteDomainQuery>,
+) -> Response {
+	match delete_domain_inner(ctx, path, query).await {
+		Ok(response) => Json(response).into_response(),
+		Err(err) => ApiError::from(err).into_response(),
+	}
+}
+
+#[tracing::instrument(skip_all)]
+async fn delete_domain_inner(
+	ctx: ApiCtx,
+	path: DeleteDomainPath,
+	query: DeleteDomainQuery,
+) -> Result<DeleteDomainResponse> {
+	ctx.auth().await?;
+
+	fanout_write_to_datacenters(
+		&ctx,
+		axum::http::Method::DELETE,
+		&format!(
+			"/namespaces/{}/domains/{}",
+			urlencoding::encode(&path.namespace),
+			urlencoding::encode(&path.hostname)
+		),
+		Some(&query),
+		Option::<&()>::None,
+		|ctx| rivet_api_peer::namespaces::delete_domain(ctx, path, query.clone()),
+	)
+	.await?;
+
+	Ok(DeleteDomainResponse {})
+}
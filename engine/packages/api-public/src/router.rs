@@ -23,11 +23,34 @@ use crate::{
 		actors::kv_get::kv_get,
 		actors::sleep::sleep,
 		actors::reschedule::reschedule,
+		actors::restore_points::list,
+		actors::restore_points::create,
+		actors::restore_points::delete,
+		actors::restore::restore,
+		actors::lineage::get,
+		actors::export::export,
+		actors::database_query::query,
+		actors::database_replicas::get,
+		actors::database_replicas::set,
 		runners::list,
 		runners::list_names,
 		envoys::list,
 		namespaces::list,
 		namespaces::create,
+		namespaces::usage,
+		namespaces::get_database_policy,
+		namespaces::upsert_database_policy,
+		namespaces::get_rate_limit_policy,
+		namespaces::upsert_rate_limit_policy,
+		namespaces::get_auth_policy,
+		namespaces::upsert_auth_policy,
+		namespaces::get_canary_policy,
+		namespaces::upsert_canary_policy,
+		namespaces::get_region_policy,
+		namespaces::upsert_region_policy,
+		namespaces::list_domains,
+		namespaces::upsert_domain,
+		namespaces::delete_domain,
 		runner_configs::list::list,
 		runner_configs::upsert::upsert,
 		runner_configs::delete::delete,
@@ -64,6 +87,62 @@ pub async fn router(
 			// MARK: Namespaces
 			.route("/namespaces", axum::routing::get(namespaces::list))
 			.route("/namespaces", axum::routing::post(namespaces::create))
+			.route(
+				"/namespaces/{namespace}/usage",
+				axum::routing::get(namespaces::usage),
+			)
+			.route(
+				"/namespaces/{namespace}/database-policy",
+				axum::routing::get(namespaces::get_database_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/database-policy",
+				axum::routing::put(namespaces::upsert_database_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/rate-limit-policy",
+				axum::routing::get(namespaces::get_rate_limit_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/rate-limit-policy",
+				axum::routing::put(namespaces::upsert_rate_limit_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/auth-policy",
+				axum::routing::get(namespaces::get_auth_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/auth-policy",
+				axum::routing::put(namespaces::upsert_auth_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/canary-policy",
+				axum::routing::get(namespaces::get_canary_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/canary-policy",
+				axum::routing::put(namespaces::upsert_canary_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/region-policy",
+				axum::routing::get(namespaces::get_region_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/region-policy",
+				axum::routing::put(namespaces::upsert_region_policy),
+			)
+			.route(
+				"/namespaces/{namespace}/domains",
+				axum::routing::get(namespaces::list_domains),
+			)
+			.route(
+				"/namespaces/{namespace}/domains",
+				axum::routing::put(namespaces::upsert_domain),
+			)
+			.route(
+				"/namespaces/{namespace}/domains/{hostname}",
+				axum::routing::delete(namespaces::delete_domain),
+			)
 			.route("/runner-configs", axum::routing::get(runner_configs::list))
 			.route(
 				"/runner-configs/serverless-health-check",
@@ -108,6 +187,42 @@ pub async fn router(
 				"/actors/{actor_id}/reschedule",
 				axum::routing::post(actors::reschedule::reschedule),
 			)
+			.route(
+				"/actors/{actor_id}/database/restore-points",
+				axum::routing::get(actors::restore_points::list),
+			)
+			.route(
+				"/actors/{actor_id}/database/restore-points",
+				axum::routing::post(actors::restore_points::create),
+			)
+			.route(
+				"/actors/{actor_id}/database/restore-points/{restore_point_id}",
+				axum::routing::delete(actors::restore_points::delete),
+			)
+			.route(
+				"/actors/{actor_id}/database/restore",
+				axum::routing::post(actors::restore::restore),
+			)
+			.route(
+				"/actors/{actor_id}/database/lineage",
+				axum::routing::get(actors::lineage::get),
+			)
+			.route(
+				"/actors/{actor_id}/database/export",
+				axum::routing::get(actors::export::export),
+			)
+			.route(
+				"/actors/{actor_id}/database/query",
+				axum::routing::post(actors::database_query::query),
+			)
+			.route(
+				"/actors/{actor_id}/database/replicas",
+				axum::routing::get(actors::database_replicas::get),
+			)
+			.route(
+				"/actors/{actor_id}/database/replicas",
+				axum::routing::put(actors::database_replicas::set),
+			)
 			// MARK: Runners
 			.route("/runners", axum::routing::get(runners::list))
 			// MARK: Envoys
@@ -1,3 +1,4 @@
+use rivet_util::Id;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
@@ -20,6 +21,28 @@ pub struct CreateRequest {
 	pub input: Option<String>,
 	pub runner_name_selector: String,
 	pub crash_policy: rivet_types::actors::CrashPolicy,
+	/// Starts the actor from a copy of another actor's state instead of empty storage.
+	#[serde(default)]
+	pub fork_from: Option<ForkFrom>,
+}
+
+/// The source actor must be in the same namespace and datacenter as the new actor.
+///
+/// The SQLite database is forked copy-on-write at the selected snapshot, defaulting to the latest
+/// commit. Actor KV state has no history and is always copied as of the create request, so a fork
+/// of an earlier snapshot pairs the old database with the current KV state. At most one of
+/// `restore_point_id` or `ts` may be set.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsForkFrom)]
+pub struct ForkFrom {
+	pub actor_id: Id,
+	/// Forks the database at this restore point. KV state is still copied as it is now.
+	#[serde(default)]
+	pub restore_point_id: Option<String>,
+	/// Forks the last commit at or before this timestamp. KV state is still copied as it is now.
+	#[serde(default)]
+	pub ts: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, ToSchema)]
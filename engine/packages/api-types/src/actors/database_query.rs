@@ -0,0 +1,45 @@
+use rivet_util::Id;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct DatabaseQueryQuery {
+	pub namespace: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseQueryPath {
+	pub actor_id: Id,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsDatabaseQueryRequest)]
+pub struct DatabaseQueryRequest {
+	/// Read-only SQL statement.
+	pub sql: String,
+	/// Positional parameters. Blobs are passed as `{"type": "blob", "base64": "..."}`.
+	#[serde(default)]
+	pub params: Option<Vec<serde_json::Value>>,
+	/// How far behind the actor's database the result may be. When set, the query may be served
+	/// by a read replica in the local datacenter. Omit to always read the actor's database.
+	#[serde(default)]
+	pub max_staleness_ms: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsDatabaseQueryResponse)]
+pub struct DatabaseQueryResponse {
+	pub columns: Vec<String>,
+	/// Rows as arrays of column values. Blobs are returned as `{"type": "blob", "base64": "..."}`.
+	pub rows: Vec<Vec<serde_json::Value>>,
+	/// Datacenter that served the query.
+	pub datacenter: String,
+	/// Upper bound on how far behind the actor's database the result is. Null when the actor's
+	/// database served the query.
+	pub staleness_ms: Option<i64>,
+}
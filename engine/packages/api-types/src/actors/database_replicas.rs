@@ -0,0 +1,33 @@
+use rivet_util::Id;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct DatabaseReplicasQuery {
+	pub namespace: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseReplicasPath {
+	pub actor_id: Id,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsSetDatabaseReplicasRequest)]
+pub struct SetDatabaseReplicasRequest {
+	/// Names of the datacenters that should hold a read replica. An empty list removes all
+	/// replicas.
+	pub datacenters: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsDatabaseReplicasResponse)]
+pub struct DatabaseReplicasResponse {
+	/// Names of the datacenters holding a read replica of the actor's database.
+	pub datacenters: Vec<String>,
+}
@@ -0,0 +1,38 @@
+use rivet_util::Id;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct LineageQuery {
+	pub namespace: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LineagePath {
+	pub actor_id: Id,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsDatabaseBranch)]
+pub struct DatabaseBranch {
+	pub branch_id: String,
+	pub parent_branch_id: Option<String>,
+	pub create_ts: i64,
+	/// Restore point the branch was derived from, if it was forked or restored from one.
+	pub restore_point_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsGetDatabaseLineageResponse)]
+pub struct LineageResponse {
+	/// Actor this actor was created from with `fork_from`.
+	pub forked_from_actor_id: Option<Id>,
+	/// Branches of the actor's SQLite database from the current branch up to the root. Restores and
+	/// forks each add a branch.
+	pub branches: Vec<DatabaseBranch>,
+}
@@ -0,0 +1,36 @@
+use gas::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct RestoreQuery {
+	pub namespace: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestorePath {
+	pub actor_id: Id,
+}
+
+/// Exactly one of `restore_point_id` or `ts` must be set.
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsRestoreRequestBody)]
+pub struct RestoreRequest {
+	#[serde(default)]
+	pub restore_point_id: Option<String>,
+	/// Restores to the last commit at or before this timestamp.
+	#[serde(default)]
+	pub ts: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsRestoreResponse)]
+pub struct RestoreResponse {
+	/// Restore point of the database state before the restore. Restoring to it undoes the restore.
+	pub undo_restore_point_id: String,
+}
@@ -0,0 +1,70 @@
+use gas::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct RestorePointsQuery {
+	pub namespace: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestorePointsPath {
+	pub actor_id: Id,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestorePointPath {
+	pub actor_id: Id,
+	pub restore_point_id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[schema(as = ActorsRestorePointStatus)]
+pub enum RestorePointStatus {
+	Pending,
+	Ready,
+	Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsRestorePoint)]
+pub struct RestorePoint {
+	pub restore_point_id: String,
+	/// Wall-clock time of the commit the restore point pins.
+	pub create_ts: i64,
+	pub status: RestorePointStatus,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsCreateRestorePointRequestBody)]
+pub struct CreateRestorePointRequest {
+	/// Pins the last commit at or before this timestamp. Defaults to the latest commit.
+	#[serde(default)]
+	pub ts: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsCreateRestorePointResponse)]
+pub struct CreateRestorePointResponse {
+	pub restore_point: RestorePoint,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsListRestorePointsResponse)]
+pub struct ListRestorePointsResponse {
+	pub restore_points: Vec<RestorePoint>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = ActorsDeleteRestorePointResponse)]
+pub struct DeleteRestorePointResponse {}
@@ -0,0 +1,35 @@
+use rivet_types::namespaces::AuthPolicy;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AuthPolicyPath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct AuthPolicyQuery {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesGetAuthPolicyResponse)]
+pub struct GetAuthPolicyResponse {
+	pub policy: AuthPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertAuthPolicyRequestBody)]
+pub struct UpsertAuthPolicyRequest {
+	pub policy: AuthPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertAuthPolicyResponse)]
+pub struct UpsertAuthPolicyResponse {
+	pub policy: AuthPolicy,
+}
@@ -0,0 +1,35 @@
+use rivet_types::namespaces::CanaryPolicy;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct CanaryPolicyPath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct CanaryPolicyQuery {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesGetCanaryPolicyResponse)]
+pub struct GetCanaryPolicyResponse {
+	pub policy: CanaryPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertCanaryPolicyRequestBody)]
+pub struct UpsertCanaryPolicyRequest {
+	pub policy: CanaryPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertCanaryPolicyResponse)]
+pub struct UpsertCanaryPolicyResponse {
+	pub policy: CanaryPolicy,
+}
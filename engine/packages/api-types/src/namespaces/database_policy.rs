@@ -0,0 +1,106 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DatabasePolicyPath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct DatabasePolicyQuery {}
+
+/// Point-in-time restore and storage policy applied to every actor database in the namespace.
+/// Storage limits apply to each datacenter separately.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesDatabasePolicy)]
+pub struct DatabasePolicy {
+	/// Spacing of the history kept for timestamp restores, in milliseconds.
+	pub pitr_interval_ms: i64,
+	/// How far back timestamp restores can reach, in milliseconds.
+	pub pitr_retention_ms: i64,
+	/// Writes that would grow the namespace's databases in a datacenter past this many bytes fail.
+	/// Each datacenter checks its own usage, so the namespace can store this much in every
+	/// datacenter. Unlimited when unset.
+	pub storage_quota_bytes: Option<i64>,
+	/// Writes past this many bytes in a datacenter succeed but are reported as over the soft limit.
+	/// Unlimited when unset.
+	pub storage_soft_limit_bytes: Option<i64>,
+}
+
+/// Database storage of a namespace in one datacenter, as of the last metering pass.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesDatabaseStorageStatus)]
+pub struct DatabaseStorageStatus {
+	pub datacenter: String,
+	pub sqlite_bytes: i64,
+	pub soft_limit_exceeded: bool,
+	pub quota_exceeded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesGetDatabasePolicyResponse)]
+pub struct GetDatabasePolicyResponse {
+	pub policy: DatabasePolicy,
+	pub datacenters: Vec<DatabaseStorageStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertDatabasePolicyRequestBody)]
+pub struct UpsertDatabasePolicyRequest {
+	pub policy: DatabasePolicyUpdate,
+}
+
+/// Changes to the database policy of a namespace. Omitted fields keep their current value.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesDatabasePolicyUpdate)]
+pub struct DatabasePolicyUpdate {
+	/// Spacing of the history kept for timestamp restores, in milliseconds.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub pitr_interval_ms: Option<i64>,
+	/// How far back timestamp restores can reach, in milliseconds.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub pitr_retention_ms: Option<i64>,
+	/// Writes that would grow the namespace's databases in a datacenter past this many bytes fail.
+	/// Each datacenter checks its own usage, so the namespace can store this much in every
+	/// datacenter. `null` removes the quota.
+	#[serde(
+		default,
+		deserialize_with = "explicit_null",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[schema(value_type = Option<i64>)]
+	pub storage_quota_bytes: Option<Option<i64>>,
+	/// Writes past this many bytes in a datacenter succeed but are reported as over the soft limit.
+	/// `null` removes the soft limit.
+	#[serde(
+		default,
+		deserialize_with = "explicit_null",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[schema(value_type = Option<i64>)]
+	pub storage_soft_limit_bytes: Option<Option<i64>>,
+}
+
+/// Reads a present field as `Some`, so an explicit `null` is told apart from an omitted field.
+fn explicit_null<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+	D: Deserializer<'de>,
+	T: Deserialize<'de>,
+{
+	Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertDatabasePolicyResponse)]
+pub struct UpsertDatabasePolicyResponse {
+	pub policy: DatabasePolicy,
+}
@@ -0,0 +1,55 @@
+use rivet_types::namespaces::DomainRoute;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DomainsPath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct ListDomainsQuery {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesListDomainsResponse)]
+pub struct ListDomainsResponse {
+	pub domains: Vec<DomainRoute>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertDomainRequestBody)]
+pub struct UpsertDomainRequest {
+	pub domain: DomainRoute,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertDomainResponse)]
+pub struct UpsertDomainResponse {
+	pub domain: DomainRoute,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteDomainPath {
+	pub namespace: String,
+	pub hostname: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteDomainQuery {
+	/// Path prefix of the route to delete. Deletes the route without a prefix when unset.
+	pub path_prefix: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesDeleteDomainResponse)]
+pub struct DeleteDomainResponse {}
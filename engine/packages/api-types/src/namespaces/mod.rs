@@ -1,2 +1,9 @@
+pub mod auth_policy;
+pub mod canary_policy;
+pub mod database_policy;
+pub mod domains;
 pub mod list;
+pub mod rate_limit_policy;
+pub mod region_policy;
 pub mod runner_configs;
+pub mod usage;
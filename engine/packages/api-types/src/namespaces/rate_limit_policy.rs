@@ -0,0 +1,35 @@
+use rivet_types::namespaces::RateLimitPolicy;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitPolicyPath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct RateLimitPolicyQuery {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesGetRateLimitPolicyResponse)]
+pub struct GetRateLimitPolicyResponse {
+	pub policy: RateLimitPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertRateLimitPolicyRequestBody)]
+pub struct UpsertRateLimitPolicyRequest {
+	pub policy: RateLimitPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertRateLimitPolicyResponse)]
+pub struct UpsertRateLimitPolicyResponse {
+	pub policy: RateLimitPolicy,
+}
@@ -0,0 +1,35 @@
+use rivet_types::namespaces::RegionPolicy;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RegionPolicyPath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct RegionPolicyQuery {}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesGetRegionPolicyResponse)]
+pub struct GetRegionPolicyResponse {
+	pub policy: RegionPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertRegionPolicyRequestBody)]
+pub struct UpsertRegionPolicyRequest {
+	pub policy: RegionPolicy,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUpsertRegionPolicyResponse)]
+pub struct UpsertRegionPolicyResponse {
+	pub policy: RegionPolicy,
+}
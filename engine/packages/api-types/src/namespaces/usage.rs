@@ -0,0 +1,40 @@
+use gas::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct UsagePath {
+	pub namespace: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, IntoParams)]
+#[serde(deny_unknown_fields)]
+#[into_params(parameter_in = Query)]
+pub struct UsageQuery {
+	/// Only return the usage of this actor.
+	pub actor_id: Option<Id>,
+}
+
+/// Bytes stored, as of the last metering pass.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+#[schema(as = NamespacesUsageResponse)]
+pub struct UsageResponse {
+	pub actor_kv_bytes: i64,
+	pub sqlite_bytes: i64,
+	pub workflow_history_bytes: i64,
+	pub workflow_queue_bytes: i64,
+	pub total_bytes: i64,
+}
+
+impl UsageResponse {
+	/// Adds the usage of another datacenter to this one.
+	pub fn merge(&mut self, other: UsageResponse) {
+		self.actor_kv_bytes += other.actor_kv_bytes;
+		self.sqlite_bytes += other.sqlite_bytes;
+		self.workflow_history_bytes += other.workflow_history_bytes;
+		self.workflow_queue_bytes += other.workflow_queue_bytes;
+		self.total_bytes += other.total_bytes;
+	}
+}
@@ -6,4 +6,11 @@ use serde::{Deserialize, Serialize};
 pub enum Datacenter {
 	#[error("not_found", "The provided datacenter does not exist.")]
 	NotFound,
+
+	#[error(
+		"write_incomplete",
+		"The write did not reach every datacenter.",
+		"The write did not reach datacenters {datacenters}. Retry the request."
+	)]
+	WriteIncomplete { datacenters: String },
 }
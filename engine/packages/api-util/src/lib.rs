@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use axum::{body::Body, response::Response};
-use futures_util::StreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 use gas::prelude::*;
 use rivet_api_builder::{ApiCtx, ErrorResponse, RawErrorResponse, X_RIVET_RAY_ID};
 use rivet_cache::{CacheKey, RawCacheKey};
@@ -178,6 +178,97 @@ where
 	Ok(aggregated)
 }
 
+/// Sends a write to every datacenter and returns the response of the local datacenter. The local
+/// datacenter is handled by `local_handler`.
+///
+/// The local datacenter is written first so a rejected write never reaches the others. Remote
+/// writes are retried until they succeed or are rejected. If any remote datacenter still missed
+/// the write, this errors with the datacenters that did not apply it. Writes must be idempotent
+/// so retrying the request repairs them.
+#[tracing::instrument(skip_all, fields(endpoint, method))]
+pub async fn fanout_write_to_datacenters<R, F, Fut>(
+	ctx: &ApiCtx,
+	method: Method,
+	endpoint: &str,
+	query: Option<impl Serialize>,
+	body: Option<impl Serialize>,
+	local_handler: F,
+) -> Result<R>
+where
+	F: FnOnce(ApiCtx) -> Fut,
+	Fut: Future<Output = Result<R>>,
+{
+	let local = local_handler(ctx.clone()).await?;
+
+	let local_dc_label = ctx.config().dc_label();
+	let remote_dcs = ctx
+		.config()
+		.topology()
+		.datacenters
+		.iter()
+		.filter(|dc| dc.datacenter_label != local_dc_label)
+		.cloned()
+		.collect::<Vec<_>>();
+
+	let failed_dcs = futures_util::stream::iter(remote_dcs)
+		.map(|dc| {
+			let method = method.clone();
+			let query = query.as_ref();
+			let body = body.as_ref();
+
+			async move {
+				let mut backoff = rivet_util::throttle::Backoff::new(4, Some(4), 100, 50);
+				loop {
+					if !backoff.tick().await {
+						return Some(dc.name);
+					}
+
+					let res = request_remote_datacenter::<serde::de::IgnoredAny>(
+						ctx.config(),
+						dc.datacenter_label,
+						endpoint,
+						method.clone(),
+						query,
+						body,
+					)
+					.await;
+					let err = match res {
+						Ok(_) => return None,
+						Err(err) => err,
+					};
+
+					tracing::warn!(
+						?err,
+						dc_label = dc.datacenter_label,
+						"failed to write to remote dc"
+					);
+
+					// Retrying a rejected write cannot succeed
+					if err
+						.chain()
+						.find_map(|x| x.downcast_ref::<RawErrorResponse>())
+						.is_some_and(|res| res.0.is_client_error())
+					{
+						return Some(dc.name);
+					}
+				}
+			}
+		})
+		.buffer_unordered(16)
+		.filter_map(std::future::ready)
+		.collect::<Vec<_>>()
+		.await;
+
+	if !failed_dcs.is_empty() {
+		return Err(errors::Datacenter::WriteIncomplete {
+			datacenters: failed_dcs.join(", "),
+		}
+		.build());
+	}
+
+	Ok(local)
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn reqwest_to_axum_response(reqwest_response: reqwest::Response) -> Result<Response> {
 	let status = reqwest_response.status();
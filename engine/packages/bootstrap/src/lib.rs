@@ -23,6 +23,9 @@ pub async fn start(config: rivet_config::Config, pools: rivet_pools::Pools) -> R
 		create_default_namespace(&ctx),
 		backfill::run(&ctx),
 		setup_pegboard_metrics_aggregator(&ctx),
+		setup_depot_cold_drainer(&ctx),
+		setup_depot_key_rotator(&ctx),
+		setup_pegboard_actor_kv_key_rotator(&ctx),
 		setup_gas_pruner(&ctx),
 		setup_datacenter_ping(&ctx),
 	)?;
@@ -113,6 +116,59 @@ async fn setup_pegboard_metrics_aggregator(ctx: &StandaloneCtx) -> Result<()> {
 	Ok(())
 }
 
+async fn setup_depot_cold_drainer(ctx: &StandaloneCtx) -> Result<()> {
+	if ctx.config().sqlite().cold_tier.is_none() {
+		tracing::debug!("sqlite cold tier is not configured, skipping creating depot cold drainer");
+		return Ok(());
+	}
+
+	// Create cold drainer if does not exist
+	let workflow_id = ctx
+		.workflow(depot::workflows::cold_drainer::ColdDrainerInput {})
+		.unique()
+		.dispatch()
+		.await?;
+	tracing::debug!(%workflow_id, "created depot cold drainer");
+
+	Ok(())
+}
+
+async fn setup_depot_key_rotator(ctx: &StandaloneCtx) -> Result<()> {
+	if ctx.config().sqlite().encryption.is_none() {
+		tracing::debug!("sqlite encryption is not configured, skipping creating depot key rotator");
+		return Ok(());
+	}
+
+	// Create key rotator if does not exist
+	let workflow_id = ctx
+		.workflow(depot::workflows::key_rotator::KeyRotatorInput {})
+		.unique()
+		.dispatch()
+		.await?;
+	tracing::debug!(%workflow_id, "created depot key rotator");
+
+	Ok(())
+}
+
+async fn setup_pegboard_actor_kv_key_rotator(ctx: &StandaloneCtx) -> Result<()> {
+	if ctx.config().sqlite().encryption.is_none() {
+		tracing::debug!(
+			"sqlite encryption is not configured, skipping creating pegboard actor kv key rotator"
+		);
+		return Ok(());
+	}
+
+	// Create actor kv key rotator if does not exist
+	let workflow_id = ctx
+		.workflow(pegboard::workflows::actor_kv_key_rotator::Input {})
+		.unique()
+		.dispatch()
+		.await?;
+	tracing::debug!(%workflow_id, "created pegboard actor kv key rotator");
+
+	Ok(())
+}
+
 async fn setup_gas_pruner(ctx: &StandaloneCtx) -> Result<()> {
 	// Create gas pruner if does not exist
 	let workflow_id = ctx
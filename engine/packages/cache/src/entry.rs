@@ -0,0 +1,44 @@
+use anyhow::{Context, Result, bail};
+
+use crate::CacheValue;
+
+const TAG_VALUE: u8 = 0;
+const TAG_MISSING: u8 = 1;
+
+/// Wraps an encoded value with the timestamp it stays fresh until.
+///
+/// The driver's own expiration is the hard expiration (`fresh_until` plus the stale while
+/// revalidate window). `None` marks a key that the getter reported missing.
+pub(crate) fn encode(value: Option<&[u8]>, fresh_until: i64) -> CacheValue {
+	let mut buf = Vec::with_capacity(9 + value.map_or(0, |x| x.len()));
+
+	match value {
+		Some(value) => {
+			buf.push(TAG_VALUE);
+			buf.extend_from_slice(&fresh_until.to_be_bytes());
+			buf.extend_from_slice(value);
+		}
+		None => {
+			buf.push(TAG_MISSING);
+			buf.extend_from_slice(&fresh_until.to_be_bytes());
+		}
+	}
+
+	buf
+}
+
+/// Returns the encoded value (`None` if the key was cached as missing) and its fresh until
+/// timestamp.
+pub(crate) fn decode(raw: &[u8]) -> Result<(Option<&[u8]>, i64)> {
+	let (tag, raw) = raw.split_first().context("empty cache entry")?;
+	let (fresh_until, value) = raw
+		.split_first_chunk::<8>()
+		.context("cache entry too short")?;
+	let fresh_until = i64::from_be_bytes(*fresh_until);
+
+	match *tag {
+		TAG_VALUE => Ok((Some(value), fresh_until)),
+		TAG_MISSING => Ok((None, fresh_until)),
+		tag => bail!("invalid cache entry tag: {tag}"),
+	}
+}
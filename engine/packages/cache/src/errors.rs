@@ -9,6 +9,9 @@ pub enum Error {
 	#[error("pools: {0}")]
 	Pools(#[from] rivet_pools::Error),
 
+	#[error("l2 cache: {0}")]
+	L2(anyhow::Error),
+
 	#[error("cache getter: {0}")]
 	Getter(anyhow::Error),
 
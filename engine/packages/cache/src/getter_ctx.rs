@@ -12,6 +12,9 @@ pub(super) struct GetterCtxEntry<V> {
 	/// then this value was read from the getter and will be written to the
 	/// cache.
 	from_cache: bool,
+
+	/// If this key was cached as missing (see `RequestConfig::negative_ttl`).
+	missing: bool,
 }
 
 /// Context passed to the getter function. This is used to resolve and configure
@@ -38,6 +41,7 @@ where
 						GetterCtxEntry {
 							value: None,
 							from_cache: false,
+							missing: false,
 						},
 					)
 				})
@@ -61,20 +65,40 @@ where
 		self.entries.iter()
 	}
 
-	/// If all entries have an associated value.
-	pub(super) fn all_entries_have_value(&self) -> bool {
-		self.entries.iter().all(|(_, x)| x.value.is_some())
+	/// If all entries have an associated value or were cached as missing.
+	pub(super) fn all_entries_resolved(&self) -> bool {
+		self.entries
+			.iter()
+			.all(|(_, x)| x.value.is_some() || x.missing)
 	}
 
-	/// Keys that do not have a value yet.
+	/// Keys that do not have a value yet and were not cached as missing.
 	pub(super) fn unresolved_keys(&self) -> Vec<K> {
 		self.entries
 			.iter()
-			.filter(|(_, x)| x.value.is_none())
+			.filter(|(_, x)| x.value.is_none() && !x.missing)
 			.map(|(k, _)| k.clone())
 			.collect()
 	}
 
+	/// Number of entries without a value, including ones cached as missing.
+	pub(super) fn empty_len(&self) -> usize {
+		self.entries
+			.iter()
+			.filter(|(_, x)| x.value.is_none())
+			.count()
+	}
+
+	/// Marks an entry as missing based on a negative cache entry.
+	pub(super) fn resolve_missing_from_cache(&mut self, key: &K) {
+		if let Some(entry) = self.entries.get_mut(key) {
+			entry.missing = true;
+			entry.from_cache = true;
+		} else {
+			tracing::warn!(?key, "resolving nonexistent cache entry");
+		}
+	}
+
 	/// Entries that have been resolved in a getter and need to be written to the
 	/// cache.
 	pub(super) fn entries_needing_cache_write(&self) -> Vec<(&K, &V)> {
@@ -1,6 +1,7 @@
 use std::{
 	fmt::Debug,
-	sync::{Arc, OnceLock},
+	sync::{Arc, OnceLock, atomic::AtomicU64},
+	time::Duration,
 };
 
 use tokio::sync::broadcast;
@@ -15,7 +16,11 @@ pub type Cache = Arc<CacheInner>;
 /// Utility type used to hold information relating to caching.
 pub struct CacheInner {
 	pub(crate) driver: Option<Driver>,
+	pub(crate) l2: Option<L2Driver>,
 	pub(crate) ups: Option<universalpubsub::PubSub>,
+	/// Incremented on every local purge. Values read from the l2 tier are only backfilled into the
+	/// local tier if no purge landed while they were being read.
+	pub(crate) purge_epoch: AtomicU64,
 }
 
 impl Debug for CacheInner {
@@ -33,8 +38,23 @@ impl CacheInner {
 		let ups = pools.ups().ok();
 
 		if config.cache().enabled {
+			let l2 = if let Some(l2_config) = &config.cache().l2 {
+				let udb = pools.udb().map_err(Error::L2)?;
+				let l2 = L2Driver::new((*udb).clone());
+				l2.start_sweeper(
+					Duration::from_millis(l2_config.sweep_interval_ms()),
+					l2_config.sweep_batch_size(),
+				);
+
+				Some(l2)
+			} else {
+				None
+			};
+
 			match &config.cache().driver() {
-				rivet_config::config::CacheDriver::InMemory => Ok(Self::new_in_memory(10000, ups)),
+				rivet_config::config::CacheDriver::InMemory => {
+					Ok(Self::new_in_memory_with_l2(10000, ups, l2))
+				}
 			}
 		} else {
 			Ok(Self::new_disabled())
@@ -43,18 +63,32 @@ impl CacheInner {
 
 	#[tracing::instrument(skip(ups))]
 	pub fn new_in_memory(max_capacity: u64, ups: Option<universalpubsub::PubSub>) -> Cache {
+		Self::new_in_memory_with_l2(max_capacity, ups, None)
+	}
+
+	/// Creates an in-memory cache backed by an optional shared l2 tier.
+	#[tracing::instrument(skip(ups, l2))]
+	pub fn new_in_memory_with_l2(
+		max_capacity: u64,
+		ups: Option<universalpubsub::PubSub>,
+		l2: Option<L2Driver>,
+	) -> Cache {
 		let driver = Driver::InMemory(InMemoryDriver::new(max_capacity));
 
 		Arc::new(CacheInner {
 			driver: Some(driver),
+			l2,
 			ups,
+			purge_epoch: AtomicU64::new(0),
 		})
 	}
 
 	pub fn new_disabled() -> Cache {
 		Arc::new(CacheInner {
 			driver: None,
+			l2: None,
 			ups: None,
+			purge_epoch: AtomicU64::new(0),
 		})
 	}
 
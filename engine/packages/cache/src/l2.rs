@@ -0,0 +1,400 @@
+use std::{sync::OnceLock, time::Duration};
+
+use anyhow::{Context, Result};
+use futures_util::TryStreamExt;
+use universaldb::prelude::*;
+use uuid::Uuid;
+
+use crate::{CacheValue, RawCacheKey, metrics};
+
+static SWEEPER: OnceLock<()> = OnceLock::new();
+
+pub fn subspace() -> universaldb::utils::Subspace {
+	universaldb::utils::Subspace::new(&(RIVET, CACHE))
+}
+
+/// Shared cache tier stored in UniversalDB.
+///
+/// Entries are shared by every engine node. Expired entries are treated as misses on read and
+/// are removed from the database by a background sweeper.
+#[derive(Clone)]
+pub struct L2Driver {
+	db: universaldb::Database,
+}
+
+impl L2Driver {
+	pub fn new(db: universaldb::Database) -> Self {
+		L2Driver { db }
+	}
+
+	/// Spawns the expiry sweeper. Only one sweeper is spawned per process and only the node holding
+	/// the sweeper lease sweeps on each tick, so nodes do not contend on the same expiry keys.
+	pub fn start_sweeper(&self, interval: Duration, batch_size: usize) {
+		if SWEEPER.set(()).is_err() {
+			return;
+		}
+
+		let driver = self.clone();
+		let node_id = Uuid::new_v4();
+		// Outlives a missed tick so the lease does not bounce between nodes
+		let lease_duration = interval * 3;
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(interval);
+			interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+			loop {
+				interval.tick().await;
+
+				match driver.acquire_sweeper_lease(node_id, lease_duration).await {
+					Ok(true) => {}
+					Ok(false) => continue,
+					Err(err) => {
+						tracing::error!(?err, "failed to acquire l2 cache sweeper lease");
+						continue;
+					}
+				}
+
+				if let Err(err) = driver.sweep(batch_size).await {
+					tracing::error!(?err, "failed to sweep expired l2 cache entries");
+				}
+			}
+		});
+	}
+
+	/// Acquires or renews the cluster-wide sweeper lease. Returns false if another node holds it.
+	#[tracing::instrument(skip_all)]
+	pub async fn acquire_sweeper_lease(&self, node_id: Uuid, duration: Duration) -> Result<bool> {
+		self.db
+			.txn("cache_l2_sweeper_lease", |tx| async move {
+				let tx = tx.with_subspace(subspace());
+				let now = rivet_util::timestamp::now();
+
+				if let Some(lease) = tx.read_opt(&SweeperLeaseKey, Serializable).await? {
+					if lease.node_id != node_id && lease.expire_at > now {
+						return Ok(false);
+					}
+				}
+
+				tx.write(
+					&SweeperLeaseKey,
+					SweeperLease {
+						node_id,
+						expire_at: now + duration.as_millis() as i64,
+					},
+				)?;
+
+				Ok(true)
+			})
+			.await
+	}
+
+	/// Returns the value and expiration timestamp of each key.
+	#[tracing::instrument(skip_all, fields(keys=keys.len()))]
+	pub async fn get(&self, keys: &[RawCacheKey]) -> Result<Vec<Option<(CacheValue, i64)>>> {
+		let now = rivet_util::timestamp::now();
+
+		let entries = self
+			.db
+			.txn("cache_l2_get", |tx| async move {
+				let tx = tx.with_subspace(subspace());
+
+				let entry_futs = keys.iter().map(|key| {
+					let tx = tx.clone();
+					async move {
+						let entry_key = EntryKey::new(key.clone());
+						// NOTE: Snapshot so that reads do not conflict with concurrent cache writes
+						tx.read_opt(&entry_key, Snapshot).await
+					}
+				});
+
+				futures_util::future::try_join_all(entry_futs).await
+			})
+			.await?;
+
+		let entries = entries
+			.into_iter()
+			.map(|entry| {
+				entry
+					.filter(|entry| entry.expire_at > now)
+					.map(|entry| (entry.value, entry.expire_at))
+			})
+			.collect::<Vec<_>>();
+
+		tracing::debug!(
+			cached_len = entries.iter().filter(|x| x.is_some()).count(),
+			total_len = entries.len(),
+			"read from l2 cache"
+		);
+
+		Ok(entries)
+	}
+
+	#[tracing::instrument(skip_all, fields(keys=keys_values.len()))]
+	pub async fn set(&self, keys_values: &[(RawCacheKey, CacheValue, i64)]) -> Result<()> {
+		self.db
+			.txn("cache_l2_set", |tx| async move {
+				let tx = tx.with_subspace(subspace());
+
+				for (key, value, expire_at) in keys_values {
+					tx.write(
+						&EntryKey::new(key.clone()),
+						L2Entry {
+							value: value.clone(),
+							expire_at: *expire_at,
+						},
+					)?;
+
+					// Stale expiry keys left behind by overwritten entries are cleaned up by the
+					// sweeper, which always checks the entry's own expiration
+					tx.write(&ExpireKey::new(*expire_at, key.clone()), ())?;
+				}
+
+				Ok(())
+			})
+			.await
+	}
+
+	#[tracing::instrument(skip_all, fields(keys=keys.len()))]
+	pub async fn delete(&self, keys: &[RawCacheKey]) -> Result<()> {
+		self.db
+			.txn("cache_l2_delete", |tx| async move {
+				let tx = tx.with_subspace(subspace());
+
+				for key in keys {
+					tx.delete(&EntryKey::new(key.clone()));
+				}
+
+				Ok(())
+			})
+			.await
+	}
+
+	/// Deletes expired entries. Returns the number of expiry keys processed.
+	#[tracing::instrument(skip_all)]
+	pub async fn sweep(&self, batch_size: usize) -> Result<usize> {
+		let mut total = 0;
+
+		loop {
+			let now = rivet_util::timestamp::now();
+
+			let swept = self
+				.db
+				.txn("cache_l2_sweep", |tx| async move {
+					let tx = tx.with_subspace(subspace());
+
+					let expire_subspace = subspace().subspace(&(EXPIRE_TS,));
+					let (start, _) = expire_subspace.range();
+					let end = subspace().pack(&(EXPIRE_TS, now + 1));
+
+					let expire_keys = tx
+						.get_ranges_keyvalues(
+							universaldb::RangeOption {
+								mode: StreamingMode::WantAll,
+								limit: Some(batch_size),
+								..(start, end).into()
+							},
+							Serializable,
+						)
+						.map_ok(|entry| tx.unpack::<ExpireKey>(entry.key()))
+						.try_collect::<Vec<_>>()
+						.await?
+						.into_iter()
+						.collect::<Result<Vec<_>>>()?;
+
+					for expire_key in &expire_keys {
+						let entry_key = EntryKey::new(expire_key.key.clone());
+
+						// The entry may have been rewritten with a later expiration
+						if let Some(entry) = tx.read_opt(&entry_key, Serializable).await? {
+							if entry.expire_at <= now {
+								tx.delete(&entry_key);
+							}
+						}
+
+						tx.delete(expire_key);
+					}
+
+					Ok(expire_keys.len())
+				})
+				.await?;
+
+			total += swept;
+
+			if swept < batch_size {
+				break;
+			}
+		}
+
+		metrics::CACHE_L2_SWEEP_TOTAL.inc_by(total as u64);
+		tracing::debug!(swept = total, "swept expired l2 cache entries");
+
+		Ok(total)
+	}
+}
+
+#[derive(Debug)]
+pub struct L2Entry {
+	pub value: CacheValue,
+	/// Epoch milliseconds.
+	pub expire_at: i64,
+}
+
+#[derive(Debug)]
+pub struct EntryKey {
+	key: RawCacheKey,
+}
+
+impl EntryKey {
+	pub fn new(key: RawCacheKey) -> Self {
+		EntryKey { key }
+	}
+}
+
+impl FormalKey for EntryKey {
+	type Value = L2Entry;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		let (expire_at, value) = raw
+			.split_first_chunk::<8>()
+			.context("l2 cache entry too short")?;
+
+		Ok(L2Entry {
+			value: value.to_vec(),
+			expire_at: i64::from_be_bytes(*expire_at),
+		})
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		let mut buf = Vec::with_capacity(8 + value.value.len());
+		buf.extend_from_slice(&value.expire_at.to_be_bytes());
+		buf.extend_from_slice(&value.value);
+
+		Ok(buf)
+	}
+}
+
+impl TuplePack for EntryKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (DATA, self.key.as_str());
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for EntryKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, key)) = <(usize, String)>::unpack(input, tuple_depth)?;
+
+		let v = EntryKey {
+			key: RawCacheKey::from(key),
+		};
+
+		Ok((input, v))
+	}
+}
+
+#[derive(Debug)]
+pub struct ExpireKey {
+	expire_at: i64,
+	key: RawCacheKey,
+}
+
+impl ExpireKey {
+	pub fn new(expire_at: i64, key: RawCacheKey) -> Self {
+		ExpireKey { expire_at, key }
+	}
+}
+
+impl FormalKey for ExpireKey {
+	type Value = ();
+
+	fn deserialize(&self, _raw: &[u8]) -> Result<Self::Value> {
+		Ok(())
+	}
+
+	fn serialize(&self, _value: Self::Value) -> Result<Vec<u8>> {
+		Ok(Vec::new())
+	}
+}
+
+impl TuplePack for ExpireKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (EXPIRE_TS, self.expire_at, self.key.as_str());
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for ExpireKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, expire_at, key)) = <(usize, i64, String)>::unpack(input, tuple_depth)?;
+
+		let v = ExpireKey {
+			expire_at,
+			key: RawCacheKey::from(key),
+		};
+
+		Ok((input, v))
+	}
+}
+
+#[derive(Debug)]
+pub struct SweeperLease {
+	pub node_id: Uuid,
+	/// Epoch milliseconds.
+	pub expire_at: i64,
+}
+
+#[derive(Debug)]
+pub struct SweeperLeaseKey;
+
+impl FormalKey for SweeperLeaseKey {
+	type Value = SweeperLease;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		let (node_id, expire_at) = raw
+			.split_first_chunk::<16>()
+			.context("l2 cache sweeper lease too short")?;
+		let expire_at = expire_at
+			.try_into()
+			.context("invalid l2 cache sweeper lease expiration")?;
+
+		Ok(SweeperLease {
+			node_id: Uuid::from_bytes(*node_id),
+			expire_at: i64::from_be_bytes(expire_at),
+		})
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		let mut buf = Vec::with_capacity(24);
+		buf.extend_from_slice(value.node_id.as_bytes());
+		buf.extend_from_slice(&value.expire_at.to_be_bytes());
+
+		Ok(buf)
+	}
+}
+
+impl TuplePack for SweeperLeaseKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (LEASE,);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for SweeperLeaseKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, _) = <(usize,)>::unpack(input, tuple_depth)?;
+
+		Ok((input, SweeperLeaseKey))
+	}
+}
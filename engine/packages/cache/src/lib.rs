@@ -1,8 +1,10 @@
 mod driver;
+mod entry;
 mod errors;
 mod getter_ctx;
 mod inner;
 mod key;
+mod l2;
 mod metrics;
 mod purge;
 mod req_config;
@@ -13,5 +15,6 @@ pub use errors::*;
 pub use getter_ctx::*;
 pub use inner::*;
 pub use key::*;
+pub use l2::L2Driver;
 pub use purge::*;
 pub use req_config::*;
@@ -37,6 +37,35 @@ lazy_static::lazy_static! {
 		&["key"],
 		*REGISTRY
 	).unwrap();
+	pub static ref CACHE_VALUE_STALE_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"cache_value_stale_total",
+		"Total number of stale cache values served while revalidating.",
+		&["key"],
+		*REGISTRY
+	).unwrap();
+	pub static ref CACHE_VALUE_NEGATIVE_HIT_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"cache_value_negative_hit_total",
+		"Total number of cache hits for keys cached as missing.",
+		&["key"],
+		*REGISTRY
+	).unwrap();
+	pub static ref CACHE_REVALIDATE_ERRORS: IntCounterVec = register_int_counter_vec_with_registry!(
+		"cache_revalidate_errors",
+		"Total number of failed background revalidations.",
+		&["key"],
+		*REGISTRY
+	).unwrap();
+	pub static ref CACHE_L2_VALUE_HIT_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"cache_l2_value_hit_total",
+		"Total number of local cache misses resolved by the shared l2 cache.",
+		&["key"],
+		*REGISTRY
+	).unwrap();
+	pub static ref CACHE_L2_SWEEP_TOTAL: IntCounter = register_int_counter_with_registry!(
+		"cache_l2_sweep_total",
+		"Total number of expired l2 cache entries swept.",
+		*REGISTRY
+	).unwrap();
 	pub static ref CACHE_PURGE_VALUE_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
 		"cache_purge_value_total",
 		"Total number of cache values purged.",
@@ -1,6 +1,8 @@
 use std::{
+	collections::HashSet,
 	fmt::{Debug, Display},
 	future::Future,
+	sync::atomic::{AtomicU64, Ordering},
 	time::Duration,
 };
 
@@ -9,9 +11,10 @@ use futures_util::StreamExt;
 use itertools::{Either, Itertools};
 use serde::{Serialize, de::DeserializeOwned};
 use tokio::sync::broadcast;
+use tracing::Instrument;
 
 use super::*;
-use crate::{errors::Error, metrics};
+use crate::{entry, errors::Error, metrics};
 
 /// How long to wait for an in flight cache req before proceeding to execute the same req anyway.
 const IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(5);
@@ -21,6 +24,9 @@ const IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(5);
 pub struct RequestConfig {
 	pub(super) cache: Cache,
 	ttl: i64,
+	stale_while_revalidate: Option<i64>,
+	negative_ttl: Option<i64>,
+	local: bool,
 }
 
 impl Debug for RequestConfig {
@@ -28,6 +34,9 @@ impl Debug for RequestConfig {
 		f.debug_struct("RequestConfig")
 			.field("cache", &self.cache)
 			.field("ttl", &self.ttl)
+			.field("stale_while_revalidate", &self.stale_while_revalidate)
+			.field("negative_ttl", &self.negative_ttl)
+			.field("local", &self.local)
 			.finish()
 	}
 }
@@ -37,6 +46,9 @@ impl RequestConfig {
 		RequestConfig {
 			cache,
 			ttl: rivet_util::duration::hours(2),
+			stale_while_revalidate: None,
+			negative_ttl: None,
+			local: false,
 		}
 	}
 
@@ -47,6 +59,37 @@ impl RequestConfig {
 		self.ttl = ttl;
 		self
 	}
+
+	/// Keeps serving values for this long (in ms) after their TTL has passed while they are
+	/// refreshed in the background.
+	///
+	/// Only the `*_swr` fetch methods serve stale values since the refresh outlives the request and
+	/// needs a `'static` getter. Other fetch methods treat stale values as misses.
+	pub fn stale_while_revalidate(mut self, duration: i64) -> Self {
+		self.stale_while_revalidate = Some(duration);
+		self
+	}
+
+	/// Caches keys that the getter did not resolve for this long (in ms).
+	///
+	/// Disabled by default.
+	pub fn negative_ttl(mut self, ttl: i64) -> Self {
+		self.negative_ttl = Some(ttl);
+		self
+	}
+
+	/// Keeps keys in the cache of this node. They skip the l2 tier and purges are not broadcast to
+	/// other nodes.
+	pub fn local(mut self) -> Self {
+		self.local = true;
+		self
+	}
+}
+
+struct FetchOutput<Key, Value> {
+	values: Vec<(Key, Value)>,
+	/// Keys that were served stale and need to be revalidated.
+	stale_keys: Vec<Key>,
 }
 
 // MARK: Fetch
@@ -59,7 +102,8 @@ impl RequestConfig {
 		getter: Getter,
 		encoder: Encoder,
 		decoder: Decoder,
-	) -> Result<Vec<(Key, Value)>>
+		serve_stale: bool,
+	) -> Result<FetchOutput<Key, Value>>
 	where
 		Key: CacheKey + Send + Sync,
 		Value: Debug + Send + Sync,
@@ -70,10 +114,14 @@ impl RequestConfig {
 	{
 		let base_key = base_key.to_string();
 		let keys = keys.into_iter().collect::<Vec<Key>>();
+		let serve_stale = serve_stale && self.stale_while_revalidate.is_some();
 
 		// Ignore empty keys
 		if keys.is_empty() {
-			return Ok(Vec::new());
+			return Ok(FetchOutput {
+				values: Vec::new(),
+				stale_keys: Vec::new(),
+			});
 		}
 
 		metrics::CACHE_REQUEST_TOTAL
@@ -92,9 +140,12 @@ impl RequestConfig {
 
 			metrics::CACHE_VALUE_EMPTY_TOTAL
 				.with_label_values(&[&base_key])
-				.inc_by(ctx.unresolved_keys().len() as u64);
+				.inc_by(ctx.empty_len() as u64);
 
-			return Ok(ctx.into_values());
+			return Ok(FetchOutput {
+				values: ctx.into_values(),
+				stale_keys: Vec::new(),
+			});
 		};
 
 		// Build driver-specific cache keys
@@ -113,23 +164,43 @@ impl RequestConfig {
 					"cache returned wrong number of values"
 				);
 
+				let now = rivet_util::timestamp::now();
+				let mut stale_keys = Vec::new();
+
 				// Resolve the cached values
 				for (key, value) in keys.iter().zip(cached_values.into_iter()) {
 					if let Some(value_bytes) = value {
-						// Try to decode the value using the driver
-						match decoder(&value_bytes) {
-							Ok(value) => {
-								ctx.resolve_from_cache(key, value);
-							}
-							Err(err) => {
-								tracing::error!(?err, "Failed to decode value");
-							}
-						}
+						resolve_cached(
+							&base_key,
+							&mut ctx,
+							key,
+							&value_bytes,
+							now,
+							serve_stale.then_some(&mut stale_keys),
+							&decoder,
+						);
+					}
+				}
+
+				// Fall back to the shared tier before calling the getter
+				if !ctx.all_entries_resolved() && !self.local {
+					if let Some(l2) = &self.cache.l2 {
+						resolve_from_l2(
+							l2,
+							driver,
+							&self.cache.purge_epoch,
+							&base_key,
+							&mut ctx,
+							now,
+							serve_stale.then_some(&mut stale_keys),
+							&decoder,
+						)
+						.await;
 					}
 				}
 
 				// Fetch remaining values and add to the cached list
-				if !ctx.all_entries_have_value() {
+				if !ctx.all_entries_resolved() {
 					// Call the getter
 					let remaining_keys = ctx.unresolved_keys();
 					let unresolved_len = remaining_keys.len();
@@ -226,15 +297,15 @@ impl RequestConfig {
 										succeeded_keys.iter().zip(cached_values.into_iter())
 									{
 										if let Some(value_bytes) = value {
-											// Try to decode the value using the driver
-											match decoder(&value_bytes) {
-												Ok(value) => {
-													ctx3.resolve_from_cache(key, value);
-												}
-												Err(err) => {
-													tracing::error!(?err, "Failed to decode value");
-												}
-											}
+											resolve_cached(
+												&base_key2,
+												&mut ctx3,
+												key,
+												&value_bytes,
+												now,
+												None,
+												&decoder,
+											);
 										}
 									}
 								}
@@ -254,41 +325,13 @@ impl RequestConfig {
 					ctx.merge(ctx2);
 					ctx.merge(ctx3);
 
-					// Write the values to cache
-					let expire_at = rivet_util::timestamp::now() + self.ttl;
-					let entries_needing_cache_write = ctx.entries_needing_cache_write();
-
-					tracing::trace!(
-						unresolved_len,
-						fetched_len = entries_needing_cache_write.len(),
-						"writing new values to cache"
-					);
-
-					// Convert values to cache bytes
-					let entries_values = entries_needing_cache_write
-						.into_iter()
-						.filter_map(|(key, value)| {
-							// Process the key with the appropriate driver
-							let cache_key = driver.process_key(&base_key, key);
-							// Try to decode the value using the driver
-							match encoder(value) {
-								Ok(value_bytes) => Some((cache_key, value_bytes, expire_at)),
-								Err(err) => {
-									tracing::error!(?err, "Failed to encode value");
-
-									None
-								}
-							}
-						})
-						.collect::<Vec<_>>();
-
-					if !entries_values.is_empty() {
-						let base_key_clone = base_key.clone();
-
-						if let Err(err) = driver.set(&base_key_clone, entries_values).await {
-							tracing::error!(?err, "failed to write to cache");
-						}
+					tracing::trace!(unresolved_len, "writing new values to cache");
 
+					// Write the values to cache
+					if self
+						.write_entries(driver, &base_key, &ctx, &leased_keys, &encoder)
+						.await
+					{
 						let _ = broadcast_tx.send(());
 					}
 
@@ -299,11 +342,20 @@ impl RequestConfig {
 					}
 				}
 
+				if !stale_keys.is_empty() {
+					metrics::CACHE_VALUE_STALE_TOTAL
+						.with_label_values(&[&base_key])
+						.inc_by(stale_keys.len() as u64);
+				}
+
 				metrics::CACHE_VALUE_EMPTY_TOTAL
 					.with_label_values(&[&base_key])
-					.inc_by(ctx.unresolved_keys().len() as u64);
+					.inc_by(ctx.empty_len() as u64);
 
-				Ok(ctx.into_values())
+				Ok(FetchOutput {
+					values: ctx.into_values(),
+					stale_keys,
+				})
 			}
 			Err(err) => {
 				tracing::error!(
@@ -326,11 +378,169 @@ impl RequestConfig {
 
 				metrics::CACHE_VALUE_EMPTY_TOTAL
 					.with_label_values(&[&base_key])
-					.inc_by(ctx.unresolved_keys().len() as u64);
+					.inc_by(ctx.empty_len() as u64);
+
+				Ok(FetchOutput {
+					values: ctx.into_values(),
+					stale_keys: Vec::new(),
+				})
+			}
+		}
+	}
+
+	/// Writes values resolved by the getter to all cache tiers. Keys in `fetched_keys` that the
+	/// getter did not resolve are cached as missing if `negative_ttl` is set.
+	///
+	/// Returns true if anything was written.
+	async fn write_entries<Key, Value, Encoder>(
+		&self,
+		driver: &Driver,
+		base_key: &str,
+		ctx: &GetterCtx<Key, Value>,
+		fetched_keys: &[Key],
+		encoder: &Encoder,
+	) -> bool
+	where
+		Key: CacheKey + Send + Sync,
+		Value: Debug + Send + Sync,
+		Encoder: Fn(&Value) -> Result<Vec<u8>>,
+	{
+		let now = rivet_util::timestamp::now();
+		let stale_while_revalidate = self.stale_while_revalidate.unwrap_or(0);
+
+		// Convert values to cache bytes
+		let fresh_until = now + self.ttl;
+		let mut entries_values = ctx
+			.entries_needing_cache_write()
+			.into_iter()
+			.filter_map(|(key, value)| {
+				// Process the key with the appropriate driver
+				let cache_key = driver.process_key(base_key, key);
+				// Try to encode the value
+				match encoder(value) {
+					Ok(value_bytes) => Some((
+						cache_key,
+						entry::encode(Some(&value_bytes), fresh_until),
+						fresh_until + stale_while_revalidate,
+					)),
+					Err(err) => {
+						tracing::error!(?err, "Failed to encode value");
+
+						None
+					}
+				}
+			})
+			.collect::<Vec<_>>();
+
+		// Misses are only cached locally. Keys are often client-controlled (e.g. hostnames), and
+		// writing misses to the l2 tier would turn every unknown key into a database write.
+		let l2_entries_len = entries_values.len();
+		if let Some(negative_ttl) = self.negative_ttl {
+			let fresh_until = now + negative_ttl;
+			let unresolved_keys = ctx.unresolved_keys().into_iter().collect::<HashSet<_>>();
+
+			entries_values.extend(
+				fetched_keys
+					.iter()
+					.filter(|key| unresolved_keys.contains(*key))
+					.map(|key| {
+						(
+							driver.process_key(base_key, key),
+							entry::encode(None, fresh_until),
+							fresh_until + stale_while_revalidate,
+						)
+					}),
+			);
+		}
+
+		if entries_values.is_empty() {
+			return false;
+		}
+
+		tracing::trace!(len = entries_values.len(), "writing entries to cache");
+
+		let (l1_res, l2_res) = tokio::join!(driver.set(base_key, entries_values.clone()), async {
+			if let Some(l2) = &self.cache.l2
+				&& l2_entries_len > 0
+				&& !self.local
+			{
+				l2.set(&entries_values[..l2_entries_len]).await
+			} else {
+				Ok(())
+			}
+		},);
+
+		if let Err(err) = l1_res {
+			tracing::error!(?err, "failed to write to cache");
+		}
+
+		if let Err(err) = l2_res {
+			tracing::error!(?err, "failed to write to l2 cache");
+		}
+
+		true
+	}
+
+	/// Calls the getter for keys that were served stale and writes the fresh values. Keys that are
+	/// already being fetched by another request are skipped.
+	async fn revalidate<Key, Value, Getter, Fut, Encoder>(
+		self,
+		base_key: String,
+		keys: Vec<Key>,
+		getter: Getter,
+		encoder: Encoder,
+	) where
+		Key: CacheKey + Send + Sync,
+		Value: Debug + Send + Sync,
+		Getter: Fn(GetterCtx<Key, Value>, Vec<Key>) -> Fut,
+		Fut: Future<Output = Result<GetterCtx<Key, Value>>>,
+		Encoder: Fn(&Value) -> Result<Vec<u8>>,
+	{
+		let Some(driver) = &self.cache.driver else {
+			return;
+		};
+
+		let (broadcast_tx, _) = broadcast::channel::<()>(16);
+		let mut leased_keys = Vec::new();
+		for key in keys {
+			let cache_key = driver.process_key(&base_key, &key);
+			if let scc::hash_map::Entry::Vacant(entry) =
+				self.cache.in_flight().entry_async(cache_key).await
+			{
+				entry.insert_entry(broadcast_tx.clone());
+				leased_keys.push(key);
+			}
+		}
+
+		if leased_keys.is_empty() {
+			return;
+		}
+
+		tracing::debug!(len = leased_keys.len(), "revalidating stale cache entries");
+
+		match getter(GetterCtx::new(leased_keys.clone()), leased_keys.clone()).await {
+			Ok(ctx) => {
+				if self
+					.write_entries(driver, &base_key, &ctx, &leased_keys, &encoder)
+					.await
+				{
+					let _ = broadcast_tx.send(());
+				}
+			}
+			Err(err) => {
+				tracing::error!(?err, "failed to revalidate stale cache entries");
 
-				Ok(ctx.into_values())
+				metrics::CACHE_REVALIDATE_ERRORS
+					.with_label_values(&[&base_key])
+					.inc();
 			}
 		}
+
+		// Release leases
+		for key in leased_keys {
+			let cache_key = driver.process_key(&base_key, &key);
+			self.cache.in_flight().remove_async(&cache_key).await;
+		}
 	}
 
 	#[tracing::instrument(err, skip_all, fields(%base_key))]
@@ -358,6 +568,21 @@ impl RequestConfig {
 			return Ok(());
 		}
 
+		if self.local {
+			return self.purge_local(&base_key, cache_keys).await;
+		}
+
+		// Delete from the shared tier before notifying other nodes so they do not backfill their local
+		// cache with the purged value
+		if let Some(l2) = &self.cache.l2 {
+			if let Err(err) = l2.delete(&cache_keys).await {
+				tracing::error!(
+					?err,
+					"failed to delete from l2 cache, proceeding regardless"
+				);
+			}
+		}
+
 		// Publish cache purge message to all services via UPS
 		if let Some(ups) = &self.cache.ups {
 			let message = CachePurgeMessage {
@@ -389,7 +614,7 @@ impl RequestConfig {
 		self.purge_local(&base_key, cache_keys).await
 	}
 
-	/// Purges keys from the local cache without publishing to NATS.
+	/// Purges keys from the local cache without publishing to NATS or touching the l2 tier.
 	/// This is used by the cache-purge service to avoid recursive publishing.
 	#[tracing::instrument(err, skip_all, fields(%base_key))]
 	pub async fn purge_local(
@@ -408,6 +633,8 @@ impl RequestConfig {
 			return Ok(());
 		}
 
+		self.cache.purge_epoch.fetch_add(1, Ordering::AcqRel);
+
 		metrics::CACHE_PURGE_REQUEST_TOTAL
 			.with_label_values(&[&base_key])
 			.inc();
@@ -494,17 +721,227 @@ impl RequestConfig {
 			base_key,
 			keys,
 			getter,
-			|value: &Value| -> Result<Vec<u8>> {
-				rivet_util::serde::json_to_vec!(&value)
-					.map_err(Error::SerdeEncode)
-					.map_err(Into::into)
-			},
-			|value: &[u8]| -> Result<Value> {
-				rivet_util::serde::json_from_slice!(value)
-					.map_err(Error::SerdeDecode)
-					.map_err(Into::into)
-			},
+			encode_json::<Value>,
+			decode_json::<Value>,
+			false,
 		)
 		.await
+		.map(|x| x.values)
+	}
+}
+
+// MARK: JSON stale while revalidate fetch
+impl RequestConfig {
+	/// Same as `fetch_one_json`, but serves stale values while they are refreshed in the background.
+	/// See `stale_while_revalidate`.
+	pub async fn fetch_one_json_swr<Key, Value, Getter, Fut>(
+		self,
+		base_key: impl Display + Debug,
+		key: Key,
+		getter: Getter,
+	) -> Result<Option<Value>>
+	where
+		Key: CacheKey + Send + Sync + 'static,
+		Value: Serialize + DeserializeOwned + Debug + Send + Sync + 'static,
+		Getter: Fn(GetterCtx<Key, Value>, Key) -> Fut + Clone + Send + Sync + 'static,
+		Fut: Future<Output = Result<GetterCtx<Key, Value>>> + Send + 'static,
+	{
+		let values = self
+			.fetch_all_json_with_keys_swr(base_key, [key], move |cache, keys| {
+				let getter = getter.clone();
+				async move {
+					debug_assert_eq!(1, keys.len());
+					if let Some(key) = keys.into_iter().next() {
+						getter(cache, key).await
+					} else {
+						tracing::error!("no keys provided to fetch one");
+						Ok(cache)
+					}
+				}
+			})
+			.await?;
+		Ok(values.into_iter().next().map(|(_, v)| v))
+	}
+
+	/// Same as `fetch_all_json_with_keys`, but serves stale values while they are refreshed in the
+	/// background. See `stale_while_revalidate`.
+	pub async fn fetch_all_json_with_keys_swr<Key, Value, Getter, Fut>(
+		self,
+		base_key: impl Display + Debug,
+		keys: impl IntoIterator<Item = Key>,
+		getter: Getter,
+	) -> Result<Vec<(Key, Value)>>
+	where
+		Key: CacheKey + Send + Sync + 'static,
+		Value: Serialize + DeserializeOwned + Debug + Send + Sync + 'static,
+		Getter: Fn(GetterCtx<Key, Value>, Vec<Key>) -> Fut + Clone + Send + Sync + 'static,
+		Fut: Future<Output = Result<GetterCtx<Key, Value>>> + Send + 'static,
+	{
+		let base_key = base_key.to_string();
+		let output = self
+			.clone()
+			.fetch_all_convert(
+				&base_key,
+				keys,
+				getter.clone(),
+				encode_json::<Value>,
+				decode_json::<Value>,
+				true,
+			)
+			.await?;
+
+		if !output.stale_keys.is_empty() {
+			tokio::spawn(
+				self.revalidate(base_key, output.stale_keys, getter, encode_json::<Value>)
+					.instrument(tracing::info_span!("cache_revalidate")),
+			);
+		}
+
+		Ok(output.values)
+	}
+}
+
+fn encode_json<Value: Serialize>(value: &Value) -> Result<Vec<u8>> {
+	rivet_util::serde::json_to_vec!(&value)
+		.map_err(Error::SerdeEncode)
+		.map_err(Into::into)
+}
+
+fn decode_json<Value: DeserializeOwned>(value: &[u8]) -> Result<Value> {
+	rivet_util::serde::json_from_slice!(value)
+		.map_err(Error::SerdeDecode)
+		.map_err(Into::into)
+}
+
+/// Resolves a raw cache entry into the getter context. Stale entries are only resolved if
+/// `stale_keys` is provided, in which case the key is recorded for revalidation.
+///
+/// Returns true if the entry was resolved.
+fn resolve_cached<Key, Value, Decoder>(
+	base_key: &str,
+	ctx: &mut GetterCtx<Key, Value>,
+	key: &Key,
+	raw: &[u8],
+	now: i64,
+	stale_keys: Option<&mut Vec<Key>>,
+	decoder: &Decoder,
+) -> bool
+where
+	Key: CacheKey,
+	Value: Debug,
+	Decoder: Fn(&[u8]) -> Result<Value>,
+{
+	let (value_bytes, fresh_until) = match entry::decode(raw) {
+		Ok(x) => x,
+		Err(err) => {
+			tracing::error!(?err, "Failed to decode cache entry");
+			return false;
+		}
+	};
+
+	let stale = fresh_until <= now;
+	if stale && stale_keys.is_none() {
+		return false;
+	}
+
+	match value_bytes {
+		Some(value_bytes) => match decoder(value_bytes) {
+			Ok(value) => {
+				ctx.resolve_from_cache(key, value);
+			}
+			Err(err) => {
+				tracing::error!(?err, "Failed to decode value");
+				return false;
+			}
+		},
+		None => {
+			metrics::CACHE_VALUE_NEGATIVE_HIT_TOTAL
+				.with_label_values(&[base_key])
+				.inc();
+
+			ctx.resolve_missing_from_cache(key);
+		}
+	}
+
+	if let (true, Some(stale_keys)) = (stale, stale_keys) {
+		stale_keys.push(key.clone());
+	}
+
+	true
+}
+
+/// Resolves unresolved keys from the l2 tier and backfills the local cache with any hits.
+async fn resolve_from_l2<Key, Value, Decoder>(
+	l2: &L2Driver,
+	driver: &Driver,
+	purge_epoch: &AtomicU64,
+	base_key: &str,
+	ctx: &mut GetterCtx<Key, Value>,
+	now: i64,
+	mut stale_keys: Option<&mut Vec<Key>>,
+	decoder: &Decoder,
+) where
+	Key: CacheKey,
+	Value: Debug,
+	Decoder: Fn(&[u8]) -> Result<Value>,
+{
+	let (keys, cache_keys): (Vec<_>, Vec<_>) = ctx
+		.unresolved_keys()
+		.into_iter()
+		.map(|key| {
+			let cache_key = driver.process_key(base_key, &key);
+			(key, cache_key)
+		})
+		.unzip();
+
+	let epoch = purge_epoch.load(Ordering::Acquire);
+	let l2_values = match l2.get(&cache_keys).await {
+		Ok(x) => x,
+		Err(err) => {
+			tracing::error!(?err, "failed to read batch keys from l2 cache");
+
+			metrics::CACHE_REQUEST_ERRORS
+				.with_label_values(&[base_key])
+				.inc();
+
+			return;
+		}
+	};
+
+	let mut backfill = Vec::new();
+	for ((key, cache_key), value) in keys.iter().zip(cache_keys).zip(l2_values) {
+		if let Some((value_bytes, expire_at)) = value {
+			if resolve_cached(
+				base_key,
+				ctx,
+				key,
+				&value_bytes,
+				now,
+				stale_keys.as_deref_mut(),
+				decoder,
+			) {
+				backfill.push((cache_key, value_bytes, expire_at));
+			}
+		}
+	}
+
+	if backfill.is_empty() {
+		return;
+	}
+
+	metrics::CACHE_L2_VALUE_HIT_TOTAL
+		.with_label_values(&[base_key])
+		.inc_by(backfill.len() as u64);
+
+	// A purge that arrived while reading may have been for one of these values. The purging node
+	// deletes from l2 before publishing, so a purge received after the read started can race with
+	// a value read before the delete.
+	if purge_epoch.load(Ordering::Acquire) != epoch {
+		tracing::debug!("skipping l2 backfill after concurrent purge");
+		return;
+	}
+
+	if let Err(err) = driver.set(base_key, backfill).await {
+		tracing::error!(?err, "failed to backfill cache from l2");
 	}
 }
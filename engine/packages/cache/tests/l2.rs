@@ -0,0 +1,206 @@
+use std::sync::{
+	Arc,
+	atomic::{AtomicUsize, Ordering},
+};
+
+use tempfile::TempDir;
+
+async fn build_l2() -> (rivet_cache::L2Driver, TempDir) {
+	let dir = tempfile::Builder::new()
+		.prefix("rivet-cache-l2")
+		.tempdir()
+		.unwrap();
+	let driver = universaldb::driver::RocksDbDatabaseDriver::new(dir.path().to_path_buf())
+		.await
+		.unwrap();
+	let db = universaldb::Database::new(Arc::new(driver));
+
+	(rivet_cache::L2Driver::new(db), dir)
+}
+
+fn build_cache(l2: &rivet_cache::L2Driver) -> rivet_cache::Cache {
+	rivet_cache::CacheInner::new_in_memory_with_l2(1000, None, Some(l2.clone()))
+}
+
+async fn fetch(cache: &rivet_cache::Cache, base_key: &str, calls: &Arc<AtomicUsize>) -> String {
+	let calls = calls.clone();
+	cache
+		.clone()
+		.request()
+		.fetch_one_json(base_key, "a", move |mut cache, key| {
+			let calls = calls.clone();
+			async move {
+				calls.fetch_add(1, Ordering::SeqCst);
+				cache.resolve(&key, "aaa".to_string());
+				Ok(cache)
+			}
+		})
+		.await
+		.unwrap()
+		.unwrap()
+}
+
+/// A value purged from the local tier only (as happens on another node) is served from l2.
+#[tokio::test(flavor = "multi_thread")]
+async fn local_miss_reads_from_l2() {
+	let (l2, _dir) = build_l2().await;
+	let cache = build_cache(&l2);
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	assert_eq!("aaa", fetch(&cache, "l2_local_miss", &calls).await);
+	assert_eq!(1, calls.load(Ordering::SeqCst));
+
+	cache
+		.clone()
+		.request()
+		.purge_local(
+			"l2_local_miss",
+			vec![rivet_cache::RawCacheKey::from(
+				"l2_local_miss:a".to_string(),
+			)],
+		)
+		.await
+		.unwrap();
+
+	assert_eq!("aaa", fetch(&cache, "l2_local_miss", &calls).await);
+	assert_eq!(
+		1,
+		calls.load(Ordering::SeqCst),
+		"getter should not be called"
+	);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn purge_clears_both_tiers() {
+	let (l2, _dir) = build_l2().await;
+	let cache = build_cache(&l2);
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	fetch(&cache, "l2_purge", &calls).await;
+	assert_eq!(1, calls.load(Ordering::SeqCst));
+
+	cache
+		.clone()
+		.request()
+		.purge("l2_purge", ["a"])
+		.await
+		.unwrap();
+
+	let key = rivet_cache::RawCacheKey::from("l2_purge:a".to_string());
+	assert!(l2.get(&[key]).await.unwrap()[0].is_none());
+
+	fetch(&cache, "l2_purge", &calls).await;
+	assert_eq!(2, calls.load(Ordering::SeqCst), "getter should be called");
+}
+
+/// Negative entries stay in the local tier so unknown keys do not write to l2.
+#[tokio::test(flavor = "multi_thread")]
+async fn misses_are_not_written_to_l2() {
+	let (l2, _dir) = build_l2().await;
+	let cache = build_cache(&l2);
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	for _ in 0..2 {
+		let calls = calls.clone();
+		let value: Option<String> = cache
+			.clone()
+			.request()
+			.negative_ttl(60_000)
+			.fetch_one_json("l2_miss", "a", move |cache, _key| {
+				let calls = calls.clone();
+				async move {
+					calls.fetch_add(1, Ordering::SeqCst);
+					Ok::<_, anyhow::Error>(cache)
+				}
+			})
+			.await
+			.unwrap();
+		assert!(value.is_none());
+	}
+	assert_eq!(
+		1,
+		calls.load(Ordering::SeqCst),
+		"miss should be cached locally"
+	);
+
+	let key = rivet_cache::RawCacheKey::from("l2_miss:a".to_string());
+	assert!(l2.get(&[key]).await.unwrap()[0].is_none());
+}
+
+/// Local requests neither write to nor read from l2, so purging them only refetches on this node.
+#[tokio::test(flavor = "multi_thread")]
+async fn local_requests_skip_l2() {
+	let (l2, _dir) = build_l2().await;
+	let cache = build_cache(&l2);
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	let fetch_local = || {
+		let calls = calls.clone();
+		cache
+			.clone()
+			.request()
+			.local()
+			.fetch_one_json("l2_local", "a", move |mut cache, key| {
+				let calls = calls.clone();
+				async move {
+					calls.fetch_add(1, Ordering::SeqCst);
+					cache.resolve(&key, "aaa".to_string());
+					Ok(cache)
+				}
+			})
+	};
+
+	assert_eq!(Some("aaa".to_string()), fetch_local().await.unwrap());
+	let key = rivet_cache::RawCacheKey::from("l2_local:a".to_string());
+	assert!(l2.get(&[key]).await.unwrap()[0].is_none());
+
+	cache
+		.clone()
+		.request()
+		.local()
+		.purge("l2_local", ["a"])
+		.await
+		.unwrap();
+
+	assert_eq!(Some("aaa".to_string()), fetch_local().await.unwrap());
+	assert_eq!(2, calls.load(Ordering::SeqCst), "getter should be called");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sweep_removes_expired() {
+	let (l2, _dir) = build_l2().await;
+	let now = rivet_util::timestamp::now();
+
+	let expired = rivet_cache::RawCacheKey::from("l2_sweep:expired".to_string());
+	let live = rivet_cache::RawCacheKey::from("l2_sweep:live".to_string());
+	l2.set(&[
+		(expired.clone(), b"x".to_vec(), now - 1000),
+		(live.clone(), b"y".to_vec(), now + 60_000),
+	])
+	.await
+	.unwrap();
+
+	assert_eq!(1, l2.sweep(16).await.unwrap());
+
+	let values = l2.get(&[expired, live]).await.unwrap();
+	assert!(values[0].is_none());
+	assert_eq!(b"y".to_vec(), values[1].as_ref().unwrap().0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sweeper_lease_is_exclusive() {
+	let (l2, _dir) = build_l2().await;
+	let node_a = uuid::Uuid::new_v4();
+	let node_b = uuid::Uuid::new_v4();
+	let duration = std::time::Duration::from_millis(200);
+
+	assert!(l2.acquire_sweeper_lease(node_a, duration).await.unwrap());
+	assert!(!l2.acquire_sweeper_lease(node_b, duration).await.unwrap());
+	// The holder renews its own lease
+	assert!(l2.acquire_sweeper_lease(node_a, duration).await.unwrap());
+
+	tokio::time::sleep(duration * 2).await;
+
+	assert!(l2.acquire_sweeper_lease(node_b, duration).await.unwrap());
+	assert!(!l2.acquire_sweeper_lease(node_a, duration).await.unwrap());
+}
@@ -0,0 +1,108 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicUsize, Ordering},
+	},
+	time::Duration,
+};
+
+fn build_cache() -> rivet_cache::Cache {
+	rivet_cache::CacheInner::new_in_memory(1000, None)
+}
+
+async fn fetch_swr(cache: &rivet_cache::Cache, base_key: &str, calls: &Arc<AtomicUsize>) -> String {
+	let calls = calls.clone();
+	cache
+		.clone()
+		.request()
+		.ttl(100)
+		.stale_while_revalidate(10_000)
+		.fetch_one_json_swr(base_key, "a", move |mut cache, key| {
+			let calls = calls.clone();
+			async move {
+				let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+				cache.resolve(&key, format!("v{call}"));
+				Ok(cache)
+			}
+		})
+		.await
+		.unwrap()
+		.unwrap()
+}
+
+/// An expired value is served immediately while the getter refreshes it in the background.
+#[tokio::test(flavor = "multi_thread")]
+async fn serves_stale_while_revalidating() {
+	let cache = build_cache();
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	assert_eq!("v1", fetch_swr(&cache, "swr_stale", &calls).await);
+
+	tokio::time::sleep(Duration::from_millis(200)).await;
+
+	// Stale value is returned without waiting on the getter
+	assert_eq!("v1", fetch_swr(&cache, "swr_stale", &calls).await);
+
+	// Wait for the background refresh to land
+	tokio::time::sleep(Duration::from_millis(100)).await;
+	assert_eq!(2, calls.load(Ordering::SeqCst));
+	assert_eq!("v2", fetch_swr(&cache, "swr_stale", &calls).await);
+}
+
+/// Fetch methods that cannot revalidate in the background treat stale values as misses.
+#[tokio::test(flavor = "multi_thread")]
+async fn stale_is_miss_without_swr_fetch() {
+	let cache = build_cache();
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	assert_eq!("v1", fetch_swr(&cache, "swr_blocking", &calls).await);
+
+	tokio::time::sleep(Duration::from_millis(200)).await;
+
+	let value = cache
+		.clone()
+		.request()
+		.ttl(100)
+		.stale_while_revalidate(10_000)
+		.fetch_one_json("swr_blocking", "a", |mut cache, key| async move {
+			cache.resolve(&key, "fresh".to_string());
+			Ok(cache)
+		})
+		.await
+		.unwrap();
+	assert_eq!(Some("fresh".to_string()), value);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn negative_ttl() {
+	let cache = build_cache();
+	let calls = Arc::new(AtomicUsize::new(0));
+
+	let fetch = || {
+		let calls = calls.clone();
+		cache.clone().request().negative_ttl(200).fetch_one_json(
+			"negative_ttl",
+			"a",
+			move |cache, _key| {
+				let calls = calls.clone();
+				async move {
+					calls.fetch_add(1, Ordering::SeqCst);
+					Ok::<rivet_cache::GetterCtx<&str, String>, _>(cache)
+				}
+			},
+		)
+	};
+
+	assert_eq!(None, fetch().await.unwrap());
+	assert_eq!(None, fetch().await.unwrap());
+	assert_eq!(
+		1,
+		calls.load(Ordering::SeqCst),
+		"missing key should be cached"
+	);
+
+	tokio::time::sleep(Duration::from_millis(400)).await;
+
+	assert_eq!(None, fetch().await.unwrap());
+	assert_eq!(2, calls.load(Ordering::SeqCst));
+}
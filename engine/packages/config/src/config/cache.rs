@@ -1,3 +1,4 @@
+use anyhow::{Result, bail};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,6 +8,12 @@ use serde::{Deserialize, Serialize};
 pub struct Cache {
 	pub enabled: bool,
 	pub driver: Option<CacheDriver>,
+	/// Shared second cache tier stored in UniversalDB.
+	///
+	/// When set, local cache misses are looked up in the shared tier before calling the getter so
+	/// that engine nodes do not each cold-miss independently after a deploy.
+	#[serde(default)]
+	pub l2: Option<CacheL2>,
 }
 
 impl Default for Cache {
@@ -14,6 +21,7 @@ impl Default for Cache {
 		Self {
 			enabled: true,
 			driver: None,
+			l2: None,
 		}
 	}
 }
@@ -22,6 +30,16 @@ impl Cache {
 	pub fn driver(&self) -> CacheDriver {
 		self.driver.clone().unwrap_or(CacheDriver::InMemory)
 	}
+
+	pub fn validate(&self) -> Result<()> {
+		if let Some(l2) = &self.l2
+			&& l2.sweep_batch_size == Some(0)
+		{
+			bail!("cache.l2.sweep_batch_size must be greater than 0");
+		}
+
+		Ok(())
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -29,3 +47,26 @@ impl Cache {
 pub enum CacheDriver {
 	InMemory,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CacheL2 {
+	/// How often expired entries are swept from the shared tier, in milliseconds.
+	///
+	/// Defaults to 60 seconds.
+	pub sweep_interval_ms: Option<u64>,
+	/// Maximum number of expired entries deleted per sweep transaction.
+	///
+	/// Must be greater than 0. Defaults to 1000.
+	pub sweep_batch_size: Option<usize>,
+}
+
+impl CacheL2 {
+	pub fn sweep_interval_ms(&self) -> u64 {
+		self.sweep_interval_ms.unwrap_or(60_000)
+	}
+
+	pub fn sweep_batch_size(&self) -> usize {
+		self.sweep_batch_size.unwrap_or(1000)
+	}
+}
@@ -7,6 +7,8 @@ pub mod postgres;
 
 pub use postgres::{Postgres, PostgresSsl};
 
+use super::pubsub::Nats;
+
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub enum Database {
@@ -24,6 +26,33 @@ impl Default for Database {
 #[serde(deny_unknown_fields)]
 pub struct FileSystem {
 	pub path: PathBuf,
+
+	/// Replicates the database across a small replica set of engine nodes for high availability
+	/// without an external database.
+	///
+	/// When absent, the database is local to this node.
+	#[serde(default)]
+	pub replication: Option<FileSystemReplication>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FileSystemReplication {
+	/// Id of this node in the replica set. Must be one of `members` and stable across restarts.
+	pub node_id: String,
+
+	/// Ids of every node in the replica set, including this one.
+	///
+	/// Must be 3 or 5 nodes (any odd number works). A majority of members must be up to accept
+	/// writes.
+	pub members: Vec<String>,
+
+	/// NATS configuration for elections, log replication, and commit transport.
+	///
+	/// If unset but the UPS pubsub is configured for NATS, this is inherited from that config at
+	/// startup (see `Root::validate_and_set_defaults`).
+	#[serde(default)]
+	pub nats: Option<Nats>,
 }
 
 impl Default for FileSystem {
@@ -32,6 +61,9 @@ impl Default for FileSystem {
 			.map(|dir| dir.join("rivet-engine").join("db"))
 			.unwrap_or_else(|| PathBuf::from("./data/db"));
 
-		Self { path: default_path }
+		Self {
+			path: default_path,
+			replication: None,
+		}
 	}
 }
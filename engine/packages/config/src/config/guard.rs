@@ -1,6 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::{net::IpAddr, path::PathBuf};
+use std::{collections::HashMap, net::IpAddr, path::PathBuf, time::Duration};
+use url::Url;
 
 pub const DEFAULT_WEBSOCKET_MAX_MESSAGE_SIZE: usize = 32 * 1024 * 1024;
 pub const DEFAULT_WEBSOCKET_MAX_FRAME_SIZE: usize = 32 * 1024 * 1024;
@@ -56,6 +57,31 @@ pub struct Guard {
 	/// Enables W3C trace context propagation (extract from incoming requests, inject into
 	/// upstream requests/websockets).
 	pub trace_propagation: Option<bool>,
+
+	/// Write a structured access log line for every request.
+	pub access_log: Option<AccessLog>,
+
+	/// Compression of HTTP responses that are not already encoded and of WebSocket messages sent
+	/// to clients that negotiate permessage-deflate.
+	pub compression: Option<Compression>,
+
+	/// Copy HTTP requests to a shadow target, e.g. a new version of a service before it receives
+	/// real traffic. Shadow responses are discarded.
+	pub mirror: Option<Mirror>,
+
+	/// Locate clients with a GeoIP database so actors created by `getOrCreate` without a region
+	/// can be placed according to the namespace's region policy.
+	pub geo: Option<Geo>,
+
+	/// Require a DNS TXT record proving control of a hostname before a namespace can route it to
+	/// actors. Only disable for local development with hostnames that are not in public DNS.
+	/// Defaults to true.
+	pub verify_domain_ownership: Option<bool>,
+
+	/// Allow JWKS URLs of auth policies to use http and point at private or loopback hosts. Only
+	/// enable for tests and local development with an identity provider on the same network.
+	/// Defaults to false.
+	pub allow_private_jwks_urls: Option<bool>,
 }
 
 impl Guard {
@@ -154,6 +180,18 @@ impl Guard {
 	pub fn trace_propagation(&self) -> bool {
 		self.trace_propagation.unwrap_or(false)
 	}
+
+	pub fn compression(&self) -> Compression {
+		self.compression.clone().unwrap_or_default()
+	}
+
+	pub fn verify_domain_ownership(&self) -> bool {
+		self.verify_domain_ownership.unwrap_or(true)
+	}
+
+	pub fn allow_private_jwks_urls(&self) -> bool {
+		self.allow_private_jwks_urls.unwrap_or(false)
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -161,7 +199,31 @@ impl Guard {
 #[derive(Default)]
 pub struct Https {
 	pub port: u16, // Port for HTTPS traffic
-	pub tls: Tls,  // TLS configuration
+	/// Certificates loaded from disk. Exactly one of `tls` and `acme` must be set.
+	#[serde(default)]
+	pub tls: Option<Tls>,
+	/// Certificates issued by an ACME server. Exactly one of `tls` and `acme` must be set.
+	#[serde(default)]
+	pub acme: Option<Acme>,
+	/// Advertise HTTP/2 over ALPN so HTTPS clients can use gRPC and other HTTP/2 only protocols.
+	/// Plaintext HTTP/2 (h2c) is always accepted.
+	///
+	/// Enabled by default.
+	#[serde(default)]
+	pub http2: Option<bool>,
+}
+
+impl Https {
+	pub fn http2(&self) -> bool {
+		self.http2.unwrap_or(true)
+	}
+
+	/// Whether the HTTPS listener must answer TLS-ALPN-01 challenges.
+	pub fn acme_tls_alpn(&self) -> bool {
+		self.acme
+			.as_ref()
+			.is_some_and(|acme| matches!(acme.challenge(), AcmeChallenge::TlsAlpn01))
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
@@ -172,4 +234,291 @@ pub struct Tls {
 	pub actor_key_path: PathBuf,
 	pub api_cert_path: PathBuf,
 	pub api_key_path: PathBuf,
+	/// How often the certificate files are checked for changes, in milliseconds. Changed
+	/// certificates are served to new connections without dropping open ones.
+	///
+	/// The files are polled rather than watched so symlink swaps of mounted secrets are picked up.
+	///
+	/// Defaults to 10 seconds.
+	pub reload_interval_ms: Option<u64>,
+}
+
+impl Tls {
+	pub fn reload_interval(&self) -> Duration {
+		Duration::from_millis(self.reload_interval_ms.unwrap_or(10_000))
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Acme {
+	/// ACME directory URL, e.g. `https://acme-v02.api.letsencrypt.org/directory`.
+	pub directory_url: Url,
+	/// PEM file with the root certificate of the ACME directory. Only needed for private CAs such
+	/// as Pebble.
+	pub directory_root_cert_path: Option<PathBuf>,
+	/// Contact emails registered with the ACME account.
+	#[serde(default)]
+	pub contact_emails: Vec<String>,
+	/// Hostnames of the certificate served for the API.
+	pub api_domains: Vec<String>,
+	/// Hostnames of the certificate served for actors. When empty, the API certificate is served
+	/// for every hostname.
+	#[serde(default)]
+	pub actor_domains: Vec<String>,
+	/// Challenge used to prove control of the domains.
+	///
+	/// Defaults to `http_01`.
+	pub challenge: Option<AcmeChallenge>,
+	/// Certificates are renewed once they expire within this many milliseconds.
+	///
+	/// Defaults to 30 days.
+	pub renew_before_ms: Option<u64>,
+	/// How often certificates are checked for renewal and reloaded from the database, in
+	/// milliseconds.
+	///
+	/// Defaults to 1 hour.
+	pub check_interval_ms: Option<u64>,
+}
+
+impl Acme {
+	pub fn challenge(&self) -> AcmeChallenge {
+		self.challenge.clone().unwrap_or(AcmeChallenge::Http01)
+	}
+
+	pub fn renew_before(&self) -> Duration {
+		Duration::from_millis(self.renew_before_ms.unwrap_or(30 * 24 * 60 * 60 * 1000))
+	}
+
+	pub fn check_interval(&self) -> Duration {
+		Duration::from_millis(self.check_interval_ms.unwrap_or(60 * 60 * 1000))
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum AcmeChallenge {
+	/// Served by guard's HTTP listener at `/.well-known/acme-challenge/{token}`. The HTTP listener
+	/// must be reachable on port 80.
+	Http01,
+	/// Served by guard's HTTPS listener. The HTTPS listener must be reachable on port 443.
+	TlsAlpn01,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccessLog {
+	/// Directory access logs are written to. Files are rotated daily.
+	pub path: PathBuf,
+	/// Defaults to `json`.
+	pub format: Option<AccessLogFormat>,
+	/// Fraction of requests that are logged, between 0 and 1. Requests that respond with a 5xx
+	/// status are always logged.
+	///
+	/// Defaults to 1.
+	pub sample_rate: Option<f64>,
+	/// Request headers whose values are replaced with `[redacted]`, case insensitive.
+	///
+	/// Defaults to `authorization`, `cookie`, `x-rivet-token` and `sec-websocket-protocol`.
+	pub redact_headers: Option<Vec<String>>,
+	/// How many days access log files are kept.
+	///
+	/// Defaults to 7.
+	pub retention_days: Option<u64>,
+}
+
+impl AccessLog {
+	pub fn format(&self) -> AccessLogFormat {
+		self.format.clone().unwrap_or(AccessLogFormat::Json)
+	}
+
+	pub fn sample_rate(&self) -> f64 {
+		self.sample_rate.unwrap_or(1.0).clamp(0.0, 1.0)
+	}
+
+	pub fn redact_headers(&self) -> Vec<String> {
+		self.redact_headers.clone().unwrap_or_else(|| {
+			vec![
+				"authorization".to_string(),
+				"cookie".to_string(),
+				"x-rivet-token".to_string(),
+				"sec-websocket-protocol".to_string(),
+			]
+		})
+	}
+
+	pub fn retention(&self) -> Duration {
+		Duration::from_secs(self.retention_days.unwrap_or(7) * 24 * 60 * 60)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum AccessLogFormat {
+	/// One JSON object per line including redacted request headers.
+	Json,
+	/// Common Log Format, as written by Apache and nginx.
+	Common,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Compression {
+	/// Defaults to false.
+	pub enabled: Option<bool>,
+	/// Responses and WebSocket messages smaller than this many bytes are sent uncompressed.
+	/// Streamed responses without a known length are always compressed and flushed whenever the
+	/// upstream has no more data ready, so chunks are not delayed.
+	///
+	/// Defaults to 1 KiB.
+	pub min_size: Option<u64>,
+	/// Content types that are compressed. Entries ending with `/*` match every subtype.
+	///
+	/// Defaults to `text/*`, `application/json`, `application/javascript`, `application/xml`,
+	/// `application/wasm` and `image/svg+xml`.
+	pub content_types: Option<Vec<String>>,
+	/// Encodings offered to clients, most preferred first.
+	///
+	/// Defaults to `zstd`, `br` and `gzip`.
+	pub encodings: Option<Vec<CompressionEncoding>>,
+	/// Negotiate the permessage-deflate extension with WebSocket clients.
+	///
+	/// Defaults to true.
+	pub websocket: Option<bool>,
+}
+
+impl Compression {
+	pub fn enabled(&self) -> bool {
+		self.enabled.unwrap_or(false)
+	}
+
+	pub fn min_size(&self) -> u64 {
+		self.min_size.unwrap_or(1024)
+	}
+
+	pub fn content_types(&self) -> Vec<String> {
+		self.content_types.clone().unwrap_or_else(|| {
+			vec![
+				"text/*".to_string(),
+				"application/json".to_string(),
+				"application/javascript".to_string(),
+				"application/xml".to_string(),
+				"application/wasm".to_string(),
+				"image/svg+xml".to_string(),
+			]
+		})
+	}
+
+	pub fn encodings(&self) -> Vec<CompressionEncoding> {
+		self.encodings.clone().unwrap_or_else(|| {
+			vec![
+				CompressionEncoding::Zstd,
+				CompressionEncoding::Br,
+				CompressionEncoding::Gzip,
+			]
+		})
+	}
+
+	pub fn websocket(&self) -> bool {
+		self.websocket.unwrap_or(true)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum CompressionEncoding {
+	Zstd,
+	/// Brotli.
+	Br,
+	Gzip,
+}
+
+impl CompressionEncoding {
+	/// Token used in `Accept-Encoding` and `Content-Encoding`.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			CompressionEncoding::Zstd => "zstd",
+			CompressionEncoding::Br => "br",
+			CompressionEncoding::Gzip => "gzip",
+		}
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Mirror {
+	/// Base URL copies are sent to. The path and query of the original request are appended.
+	///
+	/// The shadow target must not have side effects visible to clients.
+	pub url: Url,
+	/// Label the mirror's metrics are recorded with.
+	///
+	/// Defaults to `shadow`.
+	pub name: Option<String>,
+	/// Fraction of requests that are copied, between 0 and 1.
+	///
+	/// Defaults to 1.
+	pub sample_rate: Option<f64>,
+	/// Only copy requests handled by these routers, e.g. `api` or `gateway`.
+	///
+	/// Defaults to every router.
+	pub route_kinds: Option<Vec<String>>,
+	/// Timeout for shadow requests in milliseconds.
+	///
+	/// Defaults to 10 seconds.
+	pub timeout_ms: Option<u64>,
+	/// Only copy requests with these methods.
+	///
+	/// Defaults to the read-only methods `GET`, `HEAD` and `OPTIONS`, so writes are not applied
+	/// twice by a shadow target that shares state with the primary.
+	pub methods: Option<Vec<String>>,
+	/// Max copies in flight per guard node. Copies beyond this are dropped so a slow shadow target
+	/// cannot pile up tasks and connections.
+	///
+	/// Defaults to 100.
+	pub max_in_flight: Option<usize>,
+}
+
+impl Mirror {
+	pub fn name(&self) -> &str {
+		self.name.as_deref().unwrap_or("shadow")
+	}
+
+	pub fn sample_rate(&self) -> f64 {
+		self.sample_rate.unwrap_or(1.0).clamp(0.0, 1.0)
+	}
+
+	pub fn timeout(&self) -> Duration {
+		Duration::from_millis(self.timeout_ms.unwrap_or(10_000))
+	}
+
+	pub fn methods(&self) -> Vec<String> {
+		self.methods
+			.clone()
+			.unwrap_or_else(|| vec!["GET".to_string(), "HEAD".to_string(), "OPTIONS".to_string()])
+	}
+
+	pub fn max_in_flight(&self) -> usize {
+		self.max_in_flight.unwrap_or(100)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Geo {
+	/// GeoIP database in CSV format with a header row containing `network`, `latitude` and
+	/// `longitude` columns, such as the MaxMind GeoLite2 City blocks files. IPv4 and IPv6 files can
+	/// be concatenated. Read once when guard starts.
+	pub geoip_database_path: PathBuf,
+	/// Location of each datacenter, keyed by datacenter name. Datacenters without a location are
+	/// never chosen as the nearest region.
+	#[serde(default)]
+	pub datacenters: HashMap<String, GeoLocation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GeoLocation {
+	pub latitude: f64,
+	pub longitude: f64,
 }
@@ -195,7 +195,21 @@ impl Root {
 			pg.nats = Some(nats);
 		}
 
+		// Same for a replicated file system database, which requires NATS.
+		if let Some(Database::FileSystem(fs)) = &mut self.database
+			&& let Some(replication) = &mut fs.replication
+			&& replication.nats.is_none()
+		{
+			let Some(PubSub::Nats(nats)) = self.pubsub.clone() else {
+				bail!(
+					"database.file_system.replication requires nats (set it directly or use `pubsub: nats`)"
+				);
+			};
+			replication.nats = Some(nats);
+		}
+
 		self.pegboard().validate()?;
+		self.cache().validate()?;
 
 		// Validate that all datacenters have valid_hosts configured when there's more than one datacenter
 		let topology = self.topology();
@@ -235,6 +249,15 @@ impl Root {
 			}
 		}
 
+		// Validate geo locations refer to known datacenters
+		if let Some(geo) = self.guard.as_ref().and_then(|guard| guard.geo.as_ref()) {
+			for name in geo.datacenters.keys() {
+				if self.topology().dc_for_name(name).is_none() {
+					bail!("guard.geo.datacenters references unknown datacenter '{name}'");
+				}
+			}
+		}
+
 		// Validate force_shutdown_duration covers worker and guard shutdown durations
 		let worker = self.runtime.worker_shutdown_duration();
 		let guard = self.runtime.guard_shutdown_duration();
@@ -123,6 +123,23 @@ pub struct Pegboard {
 	///
 	/// Unit is in milliseconds.
 	pub gateway_websocket_rate_limit_drip_rate_ms: Option<u64>,
+	/// Max burst of inbound WebSocket bytes on a single connection before throttling.
+	pub gateway_websocket_rate_limit_bytes: Option<u64>,
+	/// Inbound WebSocket bytes regained per second on a single connection.
+	pub gateway_websocket_rate_limit_bytes_per_sec: Option<u64>,
+	/// Max bytes of actor messages buffered for a single WebSocket client before the slow consumer
+	/// policy applies.
+	pub gateway_websocket_max_buffered_size: Option<u64>,
+	/// Max bytes of client messages on a single WebSocket that the actor has not handled yet. The
+	/// gateway stops reading from the client once this is reached.
+	///
+	/// Only applies to envoys that return credit for handled messages.
+	pub gateway_websocket_max_in_flight_size: Option<u64>,
+	/// What to do when a WebSocket client reads actor messages slower than the actor sends them.
+	pub gateway_websocket_slow_consumer_policy: Option<SlowConsumerPolicy>,
+	/// Close code sent to clients disconnected by the slow consumer policy. Must be a code
+	/// endpoints may send: 1000-1003, 1007-1014 or 3000-4999.
+	pub gateway_websocket_slow_consumer_close_code: Option<u16>,
 
 	// === Envoy Settings ===
 	/// How long to wait before considering an envoy lost and evicting all of its actors.
@@ -217,6 +234,28 @@ impl Pegboard {
 			bail!("pegboard.envoy_expire_scheduler_max_concurrent_expires must be greater than 0");
 		}
 
+		if self.gateway_websocket_rate_limit_bytes_per_sec == Some(0) {
+			bail!("pegboard.gateway_websocket_rate_limit_bytes_per_sec must be greater than 0");
+		}
+
+		// 1004-1006 and 1015 are reserved, other codes below 3000 are for future protocol use
+		if let Some(code) = self.gateway_websocket_slow_consumer_close_code
+			&& !matches!(code, 1000..=1003 | 1007..=1014 | 3000..=4999)
+		{
+			bail!(
+				"pegboard.gateway_websocket_slow_consumer_close_code must be in 1000..=1003, 1007..=1014 or 3000..=4999"
+			);
+		}
+
+		// Both are sent over the tunnel as u32 credit
+		if !(1..=u32::MAX as u64).contains(&self.gateway_websocket_max_buffered_size()) {
+			bail!("pegboard.gateway_websocket_max_buffered_size must be in 1..=4294967295");
+		}
+
+		if !(1..=u32::MAX as u64).contains(&self.gateway_websocket_max_in_flight_size()) {
+			bail!("pegboard.gateway_websocket_max_in_flight_size must be in 1..=4294967295");
+		}
+
 		Ok(())
 	}
 
@@ -399,6 +438,36 @@ impl Pegboard {
 		self.gateway_websocket_rate_limit_drip_rate_ms.unwrap_or(10)
 	}
 
+	pub fn gateway_websocket_rate_limit_bytes(&self) -> u64 {
+		self.gateway_websocket_rate_limit_bytes
+			.unwrap_or(32 * 1024 * 1024) // 32 MiB
+	}
+
+	pub fn gateway_websocket_rate_limit_bytes_per_sec(&self) -> u64 {
+		self.gateway_websocket_rate_limit_bytes_per_sec
+			.unwrap_or(8 * 1024 * 1024) // 8 MiB
+	}
+
+	pub fn gateway_websocket_max_buffered_size(&self) -> u64 {
+		self.gateway_websocket_max_buffered_size
+			.unwrap_or(8 * 1024 * 1024) // 8 MiB
+	}
+
+	pub fn gateway_websocket_max_in_flight_size(&self) -> u64 {
+		self.gateway_websocket_max_in_flight_size
+			.unwrap_or(8 * 1024 * 1024) // 8 MiB
+	}
+
+	pub fn gateway_websocket_slow_consumer_policy(&self) -> SlowConsumerPolicy {
+		self.gateway_websocket_slow_consumer_policy
+			.unwrap_or_default()
+	}
+
+	pub fn gateway_websocket_slow_consumer_close_code(&self) -> u16 {
+		self.gateway_websocket_slow_consumer_close_code
+			.unwrap_or(1008)
+	}
+
 	pub fn envoy_websocket_rate_limit_requests(&self) -> u64 {
 		self.envoy_websocket_rate_limit_requests.unwrap_or(16_384)
 	}
@@ -420,6 +489,19 @@ impl Pegboard {
 	}
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowConsumerPolicy {
+	/// Discards actor messages that do not fit in the client's buffer.
+	Drop,
+	/// Closes the client connection once its buffer is full.
+	Disconnect,
+	/// Grants the actor a send window and stops it from sending until the client catches up.
+	/// Envoys without flow control are disconnected once the buffer is full.
+	#[default]
+	Pause,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, JsonSchema)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub enum EnvoyLoadBalancer {
@@ -1,6 +1,10 @@
+use std::path::PathBuf;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::secret::Secret;
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Sqlite {
@@ -10,6 +14,16 @@ pub struct Sqlite {
 	/// UNSTABLE: disables SQLite hot compaction.
 	#[serde(default)]
 	pub unstable_disable_compaction: Option<bool>,
+	/// Offloads compacted SQLite history older than the hot window to object storage.
+	///
+	/// When absent, all history stays in UniversalDB.
+	#[serde(default)]
+	pub cold_tier: Option<SqliteColdTier>,
+	/// Encrypts SQLite pages and actor KV values at rest with per-namespace data keys.
+	///
+	/// When absent, new data is stored in plaintext.
+	#[serde(default)]
+	pub encryption: Option<SqliteEncryption>,
 }
 
 impl Sqlite {
@@ -22,3 +36,112 @@ impl Sqlite {
 		self.unstable_disable_compaction.unwrap_or(true)
 	}
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteColdTier {
+	/// Object store that cold history is written to.
+	pub store: SqliteColdStore,
+	/// Number of most recent txids per database that are always kept in UniversalDB. Shard versions
+	/// superseded within this window are never offloaded.
+	///
+	/// Defaults to 10,000.
+	#[serde(default)]
+	pub hot_window_txids: Option<u64>,
+	/// How often the cold drainer scans for history to offload, in milliseconds.
+	///
+	/// Defaults to 5 minutes.
+	#[serde(default)]
+	pub drain_interval_ms: Option<u64>,
+}
+
+impl SqliteColdTier {
+	pub fn hot_window_txids(&self) -> u64 {
+		self.hot_window_txids.unwrap_or(10_000)
+	}
+
+	pub fn drain_interval_ms(&self) -> u64 {
+		self.drain_interval_ms.unwrap_or(5 * 60 * 1000)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteEncryption {
+	/// Base64-encoded 32-byte key that wraps every namespace data key.
+	pub master_key: Secret<String>,
+	/// Retired base64-encoded master keys. Data sealed under them stays readable until the key
+	/// rotator re-encrypts it under `master_key`.
+	#[serde(default)]
+	pub previous_master_keys: Option<Vec<Secret<String>>>,
+	/// Age after which a namespace data key is replaced by a fresh one, in milliseconds.
+	///
+	/// Defaults to 90 days.
+	#[serde(default)]
+	pub data_key_rotation_interval_ms: Option<u64>,
+	/// How often the key rotator scans for data sealed under retired keys, in milliseconds.
+	///
+	/// Defaults to 1 hour.
+	#[serde(default)]
+	pub rotation_scan_interval_ms: Option<u64>,
+}
+
+impl SqliteEncryption {
+	pub fn previous_master_keys(&self) -> &[Secret<String>] {
+		self.previous_master_keys.as_deref().unwrap_or_default()
+	}
+
+	pub fn data_key_rotation_interval_ms(&self) -> u64 {
+		self.data_key_rotation_interval_ms
+			.unwrap_or(90 * 24 * 60 * 60 * 1000)
+	}
+
+	pub fn rotation_scan_interval_ms(&self) -> u64 {
+		self.rotation_scan_interval_ms.unwrap_or(60 * 60 * 1000)
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum SqliteColdStore {
+	/// Stores cold objects under a local directory. Intended for development and single-node
+	/// deployments.
+	FileSystem(SqliteColdStoreFileSystem),
+	/// Stores cold objects in an S3-compatible bucket (AWS S3, MinIO, R2, etc).
+	S3(SqliteColdStoreS3),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteColdStoreFileSystem {
+	pub path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteColdStoreS3 {
+	pub bucket: String,
+	/// Key prefix prepended to every object written by the cold tier.
+	#[serde(default)]
+	pub prefix: Option<String>,
+	#[serde(default)]
+	pub region: Option<String>,
+	/// Custom endpoint for S3-compatible stores such as MinIO.
+	#[serde(default)]
+	pub endpoint: Option<String>,
+	/// Uses path-style addressing (`{endpoint}/{bucket}/{key}`). Required by most MinIO
+	/// deployments.
+	#[serde(default)]
+	pub force_path_style: Option<bool>,
+	/// When unset, credentials are loaded from the default AWS credential chain.
+	#[serde(default)]
+	pub access_key_id: Option<Secret<String>>,
+	#[serde(default)]
+	pub secret_access_key: Option<Secret<String>>,
+}
+
+impl SqliteColdStoreS3 {
+	pub fn force_path_style(&self) -> bool {
+		self.force_path_style.unwrap_or_default()
+	}
+}
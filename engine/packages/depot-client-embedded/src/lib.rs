@@ -6,7 +6,7 @@
 
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use async_trait::async_trait;
 use depot::error::SqliteStorageError;
 use depot_client::{
@@ -18,11 +18,24 @@ use tokio::runtime::Handle;
 
 pub struct EmbeddedDepotSqliteTransport {
 	db: Arc<depot::conveyer::Db>,
+	read_only: bool,
 }
 
 impl EmbeddedDepotSqliteTransport {
 	pub fn new(db: Arc<depot::conveyer::Db>) -> Self {
-		Self { db }
+		Self {
+			db,
+			read_only: false,
+		}
+	}
+
+	/// Transport that rejects every commit, for handles that only read a database another writer
+	/// owns.
+	pub fn read_only(db: Arc<depot::conveyer::Db>) -> Self {
+		Self {
+			db,
+			read_only: true,
+		}
 	}
 }
 
@@ -43,6 +56,23 @@ pub async fn open_database_from_embedded_depot(
 	.await
 }
 
+/// Opens a handle that can only read. `actor_id` names the VFS, so concurrent handles on the same
+/// database need distinct values.
+pub async fn open_read_only_database_from_embedded_depot(
+	db: Arc<depot::conveyer::Db>,
+	actor_id: String,
+	rt_handle: Handle,
+) -> Result<NativeDatabaseHandle> {
+	open_database_from_transport(
+		Arc::new(EmbeddedDepotSqliteTransport::read_only(db)),
+		actor_id,
+		0,
+		rt_handle,
+		None,
+	)
+	.await
+}
+
 #[async_trait]
 impl SqliteTransport for EmbeddedDepotSqliteTransport {
 	async fn get_pages(
@@ -84,26 +114,54 @@ impl SqliteTransport for EmbeddedDepotSqliteTransport {
 		&self,
 		request: protocol::SqliteCommitRequest,
 	) -> Result<protocol::SqliteCommitResponse> {
-		match self
-			.db
-			.commit_with_options(
-				request
-					.dirty_pages
-					.into_iter()
-					.map(|page| depot::types::DirtyPage {
-						pgno: page.pgno,
-						bytes: page.bytes,
-					})
-					.collect(),
-				request.db_size_pages,
-				request.now_ms,
-				depot::types::CommitOptions {
-					expected_head_txid: request.expected_head_txid,
-					..Default::default()
-				},
-			)
-			.await
-		{
+		ensure!(!self.read_only, "sqlite database was opened read-only");
+
+		let dirty_pages = request
+			.dirty_pages
+			.into_iter()
+			.map(|page| depot::types::DirtyPage {
+				pgno: page.pgno,
+				bytes: page.bytes,
+			})
+			.collect();
+		let options = depot::types::CommitOptions {
+			expected_head_txid: request.expected_head_txid,
+			..Default::default()
+		};
+		let result = match request.row_changes {
+			Some(row_changes) => {
+				self.db
+					.commit_with_row_changes(
+						dirty_pages,
+						request.db_size_pages,
+						request.now_ms,
+						options,
+						depot::types::RowChangeLogAppend {
+							changes: row_changes
+								.changes
+								.into_iter()
+								.map(|change| (change.index, change.change))
+								.collect(),
+							floor: depot::types::RowChangePosition {
+								txid: row_changes.floor.txid,
+								index: row_changes.floor.index,
+							},
+						},
+					)
+					.await
+			}
+			None => {
+				self.db
+					.commit_with_options(
+						dirty_pages,
+						request.db_size_pages,
+						request.now_ms,
+						options,
+					)
+					.await
+			}
+		};
+		match result {
 			Ok(result) => Ok(protocol::SqliteCommitResponse::SqliteCommitOk(
 				protocol::SqliteCommitOk {
 					head_txid: Some(result.head_txid),
@@ -114,6 +172,45 @@ impl SqliteTransport for EmbeddedDepotSqliteTransport {
 			)),
 		}
 	}
+
+	async fn get_row_change_log(
+		&self,
+		_request: protocol::SqliteGetRowChangeLogRequest,
+	) -> Result<protocol::SqliteGetRowChangeLogResponse> {
+		match self.db.row_change_log().await {
+			Ok(log) => Ok(
+				protocol::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(
+					protocol::SqliteGetRowChangeLogOk {
+						log: log.map(|log| protocol::SqliteRowChangeLog {
+							floor: protocol_row_change_position(log.floor),
+							changes: log
+								.changes
+								.into_iter()
+								.map(|change| protocol::SqliteLoggedRowChange {
+									position: protocol_row_change_position(change.position),
+									change: change.change,
+								})
+								.collect(),
+						}),
+					},
+				),
+			),
+			Err(err) => Ok(
+				protocol::SqliteGetRowChangeLogResponse::SqliteErrorResponse(
+					sqlite_error_response(&err),
+				),
+			),
+		}
+	}
+}
+
+fn protocol_row_change_position(
+	position: depot::types::RowChangePosition,
+) -> protocol::SqliteRowChangePosition {
+	protocol::SqliteRowChangePosition {
+		txid: position.txid,
+		index: position.index,
+	}
 }
 
 fn sqlite_error_reason(err: &anyhow::Error) -> String {
@@ -7,6 +7,14 @@ pub fn is_head_fence_mismatch(group: &str, code: &str) -> bool {
 	group == HEAD_FENCE_MISMATCH_GROUP && code == HEAD_FENCE_MISMATCH_CODE
 }
 
+pub const QUOTA_EXCEEDED_GROUP: &str = "depot";
+pub const QUOTA_EXCEEDED_CODES: &[&str] = &["quota_exceeded", "namespace_quota_exceeded"];
+
+/// Whether a commit was rejected because the database or its namespace is out of storage.
+pub fn is_quota_exceeded(group: &str, code: &str) -> bool {
+	group == QUOTA_EXCEEDED_GROUP && QUOTA_EXCEEDED_CODES.contains(&code)
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum BindParam {
 	Null,
@@ -59,6 +67,52 @@ pub enum ColumnValue {
 	Blob(Vec<u8>),
 }
 
+/// Position of a row change in the change stream.
+///
+/// `txid` is the depot commit that made the change durable, so consumers can line up changes
+/// with restore points and resume after a reconnect. `index` orders changes within one commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RowChangeSeq {
+	pub txid: u64,
+	pub index: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowChangeOp {
+	Insert,
+	Update,
+	Delete,
+}
+
+impl RowChangeOp {
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Insert => "insert",
+			Self::Update => "update",
+			Self::Delete => "delete",
+		}
+	}
+}
+
+/// Net change to one row made by a committed transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowChange {
+	pub seq: RowChangeSeq,
+	pub table: String,
+	pub op: RowChangeOp,
+	/// Rowid of the changed row. `None` for `WITHOUT ROWID` tables.
+	pub rowid: Option<i64>,
+	/// Column names of the table, in declaration order.
+	pub columns: Vec<String>,
+	/// Primary key of the changed row, in primary key order. The rowid for tables without a
+	/// declared primary key.
+	pub primary_key: Vec<ColumnValue>,
+	/// Row values before the transaction. Empty for inserts.
+	pub old_values: Vec<ColumnValue>,
+	/// Row values after the transaction committed. Empty for deletes.
+	pub values: Vec<ColumnValue>,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{ColumnValue, ExecuteResult};
@@ -8,9 +8,11 @@ use tokio::runtime::Handle;
 
 use crate::{
 	query::{BindParam, ExecResult, ExecuteResult, QueryResult},
+	read_pool::SqliteReadPool,
+	row_changes::{RowChangeSeq, RowChangeStream},
 	vfs::{
-		NativeVfsHandle, SqliteOpenPhase, SqliteTransportHandle, SqliteVfs, SqliteVfsMetrics,
-		SqliteVfsMetricsSnapshot, VfsConfig, VfsPreloadHintSnapshot,
+		NativeVfsHandle, SqliteOpenPhase, SqliteQuotaError, SqliteTransportHandle, SqliteVfs,
+		SqliteVfsMetrics, SqliteVfsMetricsSnapshot, VfsConfig, VfsPreloadHintSnapshot,
 		fetch_initial_pages_for_registration,
 	},
 	worker::{SqliteWorkerFatalError, SqliteWorkerHandle},
@@ -20,6 +22,7 @@ use crate::{
 pub struct NativeDatabaseHandle {
 	vfs: NativeVfsHandle,
 	worker: SqliteWorkerHandle,
+	read_pool: Arc<SqliteReadPool>,
 }
 
 pub fn vfs_name_for_actor_database(actor_id: &str, generation: u64) -> String {
@@ -48,6 +51,14 @@ impl crate::vfs::SqliteTransport for GenerationFencedTransport {
 		request.expected_generation.get_or_insert(self.generation);
 		self.inner.commit(request).await
 	}
+
+	async fn get_row_change_log(
+		&self,
+		mut request: protocol::SqliteGetRowChangeLogRequest,
+	) -> Result<protocol::SqliteGetRowChangeLogResponse> {
+		request.expected_generation.get_or_insert(self.generation);
+		self.inner.get_row_change_log(request).await
+	}
 }
 
 pub async fn open_database_from_transport(
@@ -210,7 +221,8 @@ impl NativeDatabaseHandle {
 		metrics: Option<Arc<dyn SqliteVfsMetrics>>,
 	) -> Result<Self> {
 		Ok(Self {
-			worker: SqliteWorkerHandle::start(vfs.clone(), file_name, metrics)?,
+			worker: SqliteWorkerHandle::start(vfs.clone(), file_name.clone(), metrics)?,
+			read_pool: Arc::new(SqliteReadPool::new(vfs.clone(), file_name)),
 			vfs,
 		})
 	}
@@ -242,7 +254,28 @@ impl NativeDatabaseHandle {
 		self.map_worker_result(self.worker.execute(sql, params).await)
 	}
 
+	/// Runs a read-only statement on the read pool against a snapshot of the latest commit.
+	///
+	/// Writes and uncommitted changes of an open worker transaction are not visible.
+	pub async fn query_readonly(
+		&self,
+		sql: String,
+		params: Option<Vec<BindParam>>,
+	) -> Result<QueryResult> {
+		self.check_fatal_error()?;
+		self.map_worker_result(self.read_pool.query(sql, params).await)
+	}
+
+	pub async fn row_changes(&self, after: Option<RowChangeSeq>) -> Result<RowChangeStream> {
+		self.check_fatal_error()?;
+		self.map_worker_result(self.worker.row_changes(after).await)
+	}
+
 	pub async fn close(&self) -> Result<()> {
+		// Readers share the worker's VFS, so they stop first.
+		if let Err(error) = self.read_pool.close().await {
+			tracing::warn!(?error, "failed to close sqlite read pool");
+		}
 		match self.worker.close().await {
 			Ok(()) => Ok(()),
 			Err(error) => Err(self.fatal_error().unwrap_or(error)),
@@ -297,12 +330,18 @@ impl NativeDatabaseHandle {
 	}
 
 	fn map_worker_result<T>(&self, result: Result<T>) -> Result<T> {
+		// SQLite reports a commit rejected for quota as an I/O error, so the typed error the VFS
+		// kept replaces it.
+		let quota_error = self.vfs.take_quota_error();
 		match result {
 			Ok(value) => {
 				self.check_fatal_error()?;
 				Ok(value)
 			}
-			Err(error) => Err(self.fatal_error().unwrap_or(error)),
+			Err(error) => Err(self
+				.fatal_error()
+				.or_else(|| quota_error.map(quota_exceeded_error))
+				.unwrap_or(error)),
 		}
 	}
 
@@ -312,6 +351,19 @@ impl NativeDatabaseHandle {
 	}
 }
 
+fn quota_exceeded_error(error: SqliteQuotaError) -> anyhow::Error {
+	anyhow::Error::new(rivet_error::RivetError {
+		kind: rivet_error::RivetErrorKind::Dynamic {
+			group: error.group,
+			code: error.code,
+			default_message: error.message.clone(),
+		},
+		meta: None,
+		message: Some(error.message),
+		actor: None,
+	})
+}
+
 #[cfg(test)]
 mod tests {
 	use super::vfs_name_for_actor_database;
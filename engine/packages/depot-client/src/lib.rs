@@ -23,6 +23,12 @@ pub mod optimization_flags;
 /// SQLite query execution helpers.
 pub mod query;
 
+/// Read-only SQLite connection pool for queries that run beside the worker.
+pub mod read_pool;
+
+/// Row-level change capture for committed SQLite transactions.
+pub mod row_changes;
+
 /// Lightweight SQLite page classification for prefetch planning.
 pub mod sqlite_page;
 
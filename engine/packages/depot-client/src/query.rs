@@ -13,7 +13,7 @@ use libsqlite3_sys::{
 	sqlite3_column_bytes, sqlite3_column_count, sqlite3_column_double, sqlite3_column_int64,
 	sqlite3_column_name, sqlite3_column_text, sqlite3_column_type, sqlite3_errmsg,
 	sqlite3_extended_errcode, sqlite3_finalize, sqlite3_last_insert_rowid, sqlite3_prepare_v2,
-	sqlite3_step,
+	sqlite3_step, sqlite3_stmt,
 };
 
 #[derive(Debug)]
@@ -31,6 +31,26 @@ impl fmt::Display for SqliteStatementError {
 
 impl Error for SqliteStatementError {}
 
+/// Runs around each statement of a command, between preparing and stepping it.
+pub(crate) trait StatementHook {
+	fn before(&mut self, db: *mut sqlite3, stmt: *mut sqlite3_stmt) -> Result<()>;
+
+	/// Runs once the statement is finalized. An error fails the statement.
+	fn after(&mut self, db: *mut sqlite3, succeeded: bool) -> Result<()>;
+}
+
+struct NoHook;
+
+impl StatementHook for NoHook {
+	fn before(&mut self, _db: *mut sqlite3, _stmt: *mut sqlite3_stmt) -> Result<()> {
+		Ok(())
+	}
+
+	fn after(&mut self, _db: *mut sqlite3, _succeeded: bool) -> Result<()> {
+		Ok(())
+	}
+}
+
 pub fn execute_statement(
 	db: *mut sqlite3,
 	sql: &str,
@@ -129,6 +149,15 @@ pub fn execute_single_statement(
 	db: *mut sqlite3,
 	sql: &str,
 	params: Option<&[BindParam]>,
+) -> Result<ExecuteResult> {
+	execute_single_statement_with_hook(db, sql, params, &mut NoHook)
+}
+
+pub(crate) fn execute_single_statement_with_hook(
+	db: *mut sqlite3,
+	sql: &str,
+	params: Option<&[BindParam]>,
+	hook: &mut dyn StatementHook,
 ) -> Result<ExecuteResult> {
 	let c_sql = CString::new(sql).map_err(|err| anyhow!(err.to_string()))?;
 	let mut stmt = ptr::null_mut();
@@ -162,6 +191,12 @@ pub fn execute_single_statement(
 			last_insert_row_id: None,
 		});
 	}
+	if let Err(err) = hook.before(db, stmt) {
+		unsafe {
+			sqlite3_finalize(stmt);
+		}
+		return Err(err);
+	}
 
 	let result = (|| {
 		if let Some(params) = params {
@@ -203,10 +238,21 @@ pub fn execute_single_statement(
 		sqlite3_finalize(stmt);
 	}
 
-	result
+	let after = hook.after(db, result.is_ok());
+	let result = result?;
+	after?;
+	Ok(result)
 }
 
 pub fn exec_statements(db: *mut sqlite3, sql: &str) -> Result<QueryResult> {
+	exec_statements_with_hook(db, sql, &mut NoHook)
+}
+
+pub(crate) fn exec_statements_with_hook(
+	db: *mut sqlite3,
+	sql: &str,
+	hook: &mut dyn StatementHook,
+) -> Result<QueryResult> {
 	let c_sql = CString::new(sql).map_err(|err| anyhow!(err.to_string()))?;
 	let mut remaining = c_sql.as_ptr();
 	let mut statement_index = 0_u32;
@@ -234,6 +280,12 @@ pub fn exec_statements(db: *mut sqlite3, sql: &str) -> Result<QueryResult> {
 			remaining = tail;
 			continue;
 		}
+		if let Err(err) = hook.before(db, stmt) {
+			unsafe {
+				sqlite3_finalize(stmt);
+			}
+			return Err(err);
+		}
 
 		let result = (|| {
 			let columns = collect_columns(stmt);
@@ -265,7 +317,9 @@ pub fn exec_statements(db: *mut sqlite3, sql: &str) -> Result<QueryResult> {
 			sqlite3_finalize(stmt);
 		}
 
+		let after = hook.after(db, result.is_ok());
 		let (columns, rows) = result?;
+		after?;
 		if !columns.is_empty() || !rows.is_empty() {
 			final_result = QueryResult { columns, rows };
 		}
@@ -0,0 +1,148 @@
+use std::thread::JoinHandle;
+
+use anyhow::{Context, Result, anyhow};
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+use crate::{
+	query::{BindParam, QueryResult, query_statement},
+	vfs::{NativeReadConnection, NativeVfsHandle, open_read_connection},
+	worker::{SqliteWorkerClosingError, SqliteWorkerOverloadedError, panic_message},
+};
+
+/// Read-only connections per database. Each one keeps its own SQLite page cache.
+pub const SQLITE_READ_POOL_SIZE: usize = 4;
+// Bounded for the same reason as the writer queue: a full queue maps to
+// actor.overloaded instead of hidden native backlog.
+pub const SQLITE_READ_POOL_QUEUE_CAPACITY: usize = 128;
+
+/// Threads running read-only queries beside the SQLite worker.
+///
+/// Threads start on the first query, and each opens its connection before running its first
+/// query, so actors that never read through the pool pay nothing for it.
+pub struct SqliteReadPool {
+	vfs: NativeVfsHandle,
+	file_name: String,
+	// Forced-sync: started and closed from async callers without awaiting under the lock.
+	state: Mutex<ReadPoolState>,
+}
+
+enum ReadPoolState {
+	Idle,
+	Running {
+		query_tx: Sender<ReadQuery>,
+		threads: Vec<JoinHandle<()>>,
+	},
+	Closed,
+}
+
+struct ReadQuery {
+	sql: String,
+	params: Option<Vec<BindParam>>,
+	reply: oneshot::Sender<Result<QueryResult>>,
+}
+
+impl SqliteReadPool {
+	pub fn new(vfs: NativeVfsHandle, file_name: String) -> Self {
+		Self {
+			vfs,
+			file_name,
+			state: Mutex::new(ReadPoolState::Idle),
+		}
+	}
+
+	/// Runs a single read-only statement against a snapshot of the latest commit.
+	pub async fn query(&self, sql: String, params: Option<Vec<BindParam>>) -> Result<QueryResult> {
+		let (reply, result) = oneshot::channel();
+		self.enqueue(ReadQuery { sql, params, reply })?;
+		result.await.map_err(|_| SqliteWorkerClosingError)?
+	}
+
+	/// Stops accepting queries and waits for the threads to finish the queued ones.
+	pub async fn close(&self) -> Result<()> {
+		let state = std::mem::replace(&mut *self.state.lock(), ReadPoolState::Closed);
+		let ReadPoolState::Running { query_tx, threads } = state else {
+			return Ok(());
+		};
+		// Threads exit once the queue is drained and every sender is gone.
+		drop(query_tx);
+
+		tokio::task::spawn_blocking(move || {
+			for thread in threads {
+				thread.join().map_err(|panic| {
+					anyhow!("sqlite reader panicked: {}", panic_message(&panic))
+				})?;
+			}
+			Ok(())
+		})
+		.await
+		.context("join sqlite reader threads")?
+	}
+
+	fn enqueue(&self, query: ReadQuery) -> Result<()> {
+		let mut state = self.state.lock();
+		if matches!(*state, ReadPoolState::Idle) {
+			*state = self.start()?;
+		}
+
+		let ReadPoolState::Running { query_tx, .. } = &*state else {
+			return Err(SqliteWorkerClosingError.into());
+		};
+		match query_tx.try_send(query) {
+			Ok(()) => Ok(()),
+			Err(TrySendError::Full(_)) => Err(SqliteWorkerOverloadedError.into()),
+			Err(TrySendError::Disconnected(_)) => Err(SqliteWorkerClosingError.into()),
+		}
+	}
+
+	fn start(&self) -> Result<ReadPoolState> {
+		let (query_tx, query_rx) = crossbeam_channel::bounded(SQLITE_READ_POOL_QUEUE_CAPACITY);
+		let mut threads = Vec::with_capacity(SQLITE_READ_POOL_SIZE);
+		for index in 0..SQLITE_READ_POOL_SIZE {
+			let vfs = self.vfs.clone();
+			let file_name = self.file_name.clone();
+			let query_rx = query_rx.clone();
+			let thread = std::thread::Builder::new()
+				.name(format!("sqlite-reader-{index}-{}", self.file_name))
+				.spawn(move || reader_main(vfs, file_name, query_rx))
+				.context("spawn sqlite reader thread")?;
+			threads.push(thread);
+		}
+
+		Ok(ReadPoolState::Running { query_tx, threads })
+	}
+}
+
+fn reader_main(vfs: NativeVfsHandle, file_name: String, query_rx: Receiver<ReadQuery>) {
+	let mut connection: Option<NativeReadConnection> = None;
+	for query in query_rx.iter() {
+		if query.reply.is_closed() {
+			continue;
+		}
+
+		if connection.is_none() {
+			match open_read_connection(vfs.clone(), &file_name) {
+				Ok(opened) => connection = Some(opened),
+				Err(err) => {
+					let _ = query
+						.reply
+						.send(Err(anyhow!("failed to open sqlite read connection: {err}")));
+					continue;
+				}
+			}
+		}
+		let Some(connection) = connection.as_mut() else {
+			continue;
+		};
+
+		let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+			connection.with_snapshot(|db| query_statement(db, &query.sql, query.params.as_deref()))
+		}))
+		.unwrap_or_else(|panic| {
+			tracing::error!(message = panic_message(&panic), "sqlite reader panicked");
+			Err(anyhow!("sqlite reader panicked"))
+		});
+		let _ = query.reply.send(result);
+	}
+}
@@ -0,0 +1,864 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	error::Error,
+	ffi::{CStr, c_void},
+	fmt,
+	os::raw::{c_char, c_int},
+	ptr,
+};
+
+use anyhow::{Result, anyhow};
+use depot_client_types::ColumnValue;
+pub use depot_client_types::{RowChange, RowChangeOp, RowChangeSeq};
+use libsqlite3_sys::{
+	SQLITE_BLOB, SQLITE_DELETE, SQLITE_FLOAT, SQLITE_INSERT, SQLITE_INTEGER, SQLITE_OK,
+	SQLITE_TEXT, SQLITE_UPDATE, sqlite3, sqlite3_int64, sqlite3_preupdate_count,
+	sqlite3_preupdate_hook, sqlite3_preupdate_new, sqlite3_preupdate_old, sqlite3_rollback_hook,
+	sqlite3_value, sqlite3_value_blob, sqlite3_value_bytes, sqlite3_value_double,
+	sqlite3_value_int64, sqlite3_value_text, sqlite3_value_type,
+};
+use parking_lot::Mutex;
+use rivet_envoy_protocol as protocol;
+use tokio::sync::broadcast;
+
+use crate::query::{BindParam, execute_single_statement};
+
+/// Committed changes kept for subscribers that resume from an earlier sequence. Depot keeps the
+/// same window in the row change log, so subscribers can resume across restarts.
+pub const ROW_CHANGE_HISTORY_CAPACITY: usize = 1024;
+/// Changes buffered per live subscriber before it is reported as lagged.
+const ROW_CHANGE_CHANNEL_CAPACITY: usize = 1024;
+/// Largest encoded change written to the log. Larger changes are still streamed live, but the
+/// floor moves past them.
+const ROW_CHANGE_LOG_MAX_ENTRY_BYTES: usize = 64 * 1024;
+/// Encoded bytes of changes written to the log with one commit.
+const ROW_CHANGE_LOG_MAX_COMMIT_BYTES: usize = 1024 * 1024;
+
+/// Fan-out of committed row changes from the SQLite worker to subscribers.
+pub(crate) struct RowChangeFeed {
+	// Forced-sync: published from the SQLite worker thread.
+	inner: Mutex<RowChangeFeedInner>,
+}
+
+struct RowChangeFeedInner {
+	sender: Option<broadcast::Sender<RowChange>>,
+	history: VecDeque<RowChange>,
+	/// Oldest sequence a subscriber can resume after without missing changes. `None` until
+	/// capture is enabled.
+	floor: Option<RowChangeSeq>,
+}
+
+/// Stream of committed row changes, in commit order.
+pub struct RowChangeStream {
+	replay: VecDeque<RowChange>,
+	receiver: broadcast::Receiver<RowChange>,
+}
+
+impl RowChangeFeed {
+	pub(crate) fn new() -> Self {
+		let (sender, _) = broadcast::channel(ROW_CHANGE_CHANNEL_CAPACITY);
+		Self {
+			inner: Mutex::new(RowChangeFeedInner {
+				sender: Some(sender),
+				history: VecDeque::new(),
+				floor: None,
+			}),
+		}
+	}
+
+	/// Marks capture as started with the history loaded from the row change log. Changes at or
+	/// before `floor` are never replayed.
+	pub(crate) fn start(&self, floor: RowChangeSeq, history: Vec<RowChange>) {
+		let mut inner = self.inner.lock();
+		if inner.floor.is_some() {
+			return;
+		}
+		inner.floor = Some(floor);
+		inner.history = history.into_iter().collect();
+		while inner.history.len() > ROW_CHANGE_HISTORY_CAPACITY {
+			if let Some(evicted) = inner.history.pop_front() {
+				inner.floor = Some(evicted.seq);
+			}
+		}
+	}
+
+	/// Oldest sequence a subscriber can resume after, or `None` until capture is enabled.
+	pub(crate) fn floor(&self) -> Option<RowChangeSeq> {
+		self.inner.lock().floor
+	}
+
+	pub(crate) fn subscribe(&self, after: Option<RowChangeSeq>) -> Result<RowChangeStream> {
+		let inner = self.inner.lock();
+		let Some(sender) = &inner.sender else {
+			return Err(RowChangesClosedError.into());
+		};
+		let floor = inner.floor.ok_or(RowChangesClosedError)?;
+
+		let replay = match after {
+			Some(after) if after < floor => {
+				return Err(RowChangesTruncatedError { after, floor }.into());
+			}
+			Some(after) => inner
+				.history
+				.iter()
+				.filter(|change| change.seq > after)
+				.cloned()
+				.collect(),
+			None => VecDeque::new(),
+		};
+
+		// Subscribing while holding the lock means nothing published after the replay snapshot
+		// can be missed.
+		Ok(RowChangeStream {
+			replay,
+			receiver: sender.subscribe(),
+		})
+	}
+
+	/// Floor the feed will have once `changes` are published, raised to at least `floor`. Older
+	/// changes are dropped to keep the history within its capacity.
+	pub(crate) fn floor_after(&self, changes: &[RowChange], floor: RowChangeSeq) -> RowChangeSeq {
+		let inner = self.inner.lock();
+		let floor = inner.floor.map_or(floor, |current| current.max(floor));
+		let retained = inner
+			.history
+			.iter()
+			.chain(changes)
+			.filter(|change| change.seq > floor)
+			.collect::<Vec<_>>();
+		match retained.len().checked_sub(ROW_CHANGE_HISTORY_CAPACITY + 1) {
+			Some(last_evicted) => retained[last_evicted].seq,
+			None => floor,
+		}
+	}
+
+	/// Sends committed changes to subscribers and keeps the ones after `floor` for resuming.
+	pub(crate) fn publish(&self, changes: Vec<RowChange>, floor: RowChangeSeq) {
+		let mut inner = self.inner.lock();
+		let floor = inner.floor.map_or(floor, |current| current.max(floor));
+		inner.floor = Some(floor);
+		inner.history.retain(|change| change.seq > floor);
+		for change in changes {
+			if change.seq > floor {
+				if inner.history.len() == ROW_CHANGE_HISTORY_CAPACITY
+					&& let Some(evicted) = inner.history.pop_front()
+				{
+					inner.floor = Some(evicted.seq);
+				}
+				inner.history.push_back(change.clone());
+			}
+			if let Some(sender) = &inner.sender {
+				// No receivers is not an error, changes stay in history for resuming subscribers.
+				let _ = sender.send(change);
+			}
+		}
+	}
+
+	/// Ends every stream once buffered changes are drained.
+	pub(crate) fn close(&self) {
+		self.inner.lock().sender = None;
+	}
+}
+
+impl RowChangeStream {
+	/// Returns the next change, or `None` once the database is closed.
+	///
+	/// A subscriber that falls more than the channel capacity behind gets
+	/// `RowChangesLaggedError` and should resubscribe from its last sequence.
+	pub async fn recv(&mut self) -> Option<Result<RowChange>> {
+		if let Some(change) = self.replay.pop_front() {
+			return Some(Ok(change));
+		}
+
+		loop {
+			match self.receiver.recv().await {
+				Ok(change) => return Some(Ok(change)),
+				Err(broadcast::error::RecvError::Lagged(skipped)) => {
+					return Some(Err(RowChangesLaggedError { skipped }.into()));
+				}
+				Err(broadcast::error::RecvError::Closed) => return None,
+			}
+		}
+	}
+}
+
+/// Row operations recorded by the SQLite preupdate hook for the open transaction.
+///
+/// The hook sees each row before and after the write, including the primary key of `WITHOUT
+/// ROWID` tables. Several writes to the same row collapse into its net change. SQLite fires no
+/// hook for `ROLLBACK TO`, so each net change is checked against the row as the transaction left
+/// it before it is reported. Registering a preupdate hook also turns off the `DELETE` truncate
+/// optimization, so every deleted row is seen.
+pub(crate) struct RowChangeCapture {
+	pending: Vec<PendingRowChange>,
+	/// Depot head txid of the last commit the row change log covers.
+	covered_txid: u64,
+}
+
+struct PendingRowChange {
+	table: String,
+	op: RowChangeOp,
+	old_rowid: i64,
+	new_rowid: i64,
+	/// Row before the write. Empty for inserts.
+	old_values: Vec<ColumnValue>,
+	/// Row after the write. Empty for deletes.
+	new_values: Vec<ColumnValue>,
+}
+
+struct TableInfo {
+	columns: Vec<String>,
+	/// Column indexes of the declared primary key, in key order.
+	primary_key: Vec<usize>,
+	without_rowid: bool,
+}
+
+/// Net effect of a transaction on one row.
+struct NetRowChange {
+	table: String,
+	primary_key: Vec<ColumnValue>,
+	rowid: i64,
+	/// Row before the first write of the transaction. `None` if the row did not exist.
+	before: Option<Vec<ColumnValue>>,
+}
+
+impl RowChangeCapture {
+	/// Decodes the row change log depot returned for a database whose head is `head_txid`.
+	///
+	/// Without a log, some commit up to the head was written without its changes, so nothing at
+	/// or before the head can be replayed.
+	pub(crate) fn decode_log(
+		log: Option<protocol::SqliteRowChangeLog>,
+		head_txid: u64,
+	) -> Result<(RowChangeSeq, Vec<RowChange>)> {
+		let Some(log) = log else {
+			return Ok((
+				RowChangeSeq {
+					txid: head_txid,
+					index: u32::MAX,
+				},
+				Vec::new(),
+			));
+		};
+
+		let history = log
+			.changes
+			.into_iter()
+			.map(|logged| {
+				decode_row_change(
+					RowChangeSeq {
+						txid: logged.position.txid,
+						index: logged.position.index,
+					},
+					&logged.change,
+				)
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok((
+			RowChangeSeq {
+				txid: log.floor.txid,
+				index: log.floor.index,
+			},
+			history,
+		))
+	}
+
+	/// Installs the capture hooks on `db`, whose row change log covers `covered_txid`. The
+	/// returned box must outlive the connection or be uninstalled first.
+	pub(crate) fn install(db: *mut sqlite3, covered_txid: u64) -> Box<Self> {
+		let mut capture = Box::new(Self {
+			pending: Vec::new(),
+			covered_txid,
+		});
+		let ctx = ptr::addr_of_mut!(*capture).cast::<c_void>();
+		unsafe {
+			sqlite3_preupdate_hook(db, Some(preupdate_hook), ctx);
+			sqlite3_rollback_hook(db, Some(rollback_hook), ctx);
+		}
+		capture
+	}
+
+	pub(crate) fn uninstall(db: *mut sqlite3) {
+		unsafe {
+			sqlite3_preupdate_hook(db, None, ptr::null_mut());
+			sqlite3_rollback_hook(db, None, ptr::null_mut());
+		}
+	}
+
+	/// Whether every commit up to `head_txid` was written with its changes.
+	pub(crate) fn covers(&self, head_txid: u64) -> bool {
+		self.covered_txid == head_txid
+	}
+
+	/// Records that the commit at `txid` was written with its changes.
+	pub(crate) fn mark_covered(&mut self, txid: u64) {
+		self.covered_txid = txid;
+	}
+
+	/// Resolves the changes of the open transaction, numbered as the commit at `txid`.
+	///
+	/// Must run inside the transaction right before it commits, so reads observe the rows as it
+	/// leaves them. The recorded writes are kept until [`Self::discard`], in case the statement
+	/// does not end the transaction.
+	pub(crate) fn resolve(&self, db: *mut sqlite3, txid: u64) -> Vec<RowChange> {
+		if self.pending.is_empty() {
+			return Vec::new();
+		}
+
+		let mut tables = HashMap::<String, Option<TableInfo>>::new();
+		let mut rows = Vec::<NetRowChange>::new();
+		let mut index_by_row = HashMap::<(String, Vec<u8>), usize>::new();
+		for pending in &self.pending {
+			let info = tables.entry(pending.table.clone()).or_insert_with(|| {
+				match read_table_info(db, &pending.table) {
+					Ok(info) => Some(info),
+					Err(err) => {
+						// The table was dropped or renamed in the same transaction.
+						tracing::debug!(
+							?err,
+							table = %pending.table,
+							"skipping row changes for unreadable table"
+						);
+						None
+					}
+				}
+			});
+			let Some(info) = info else {
+				continue;
+			};
+
+			for (rowid, primary_key, before) in row_effects(info, pending.op, pending) {
+				let key = (pending.table.clone(), encode_values(&primary_key));
+				if index_by_row.contains_key(&key) {
+					continue;
+				}
+				index_by_row.insert(key, rows.len());
+				rows.push(NetRowChange {
+					table: pending.table.clone(),
+					primary_key,
+					rowid,
+					before,
+				});
+			}
+		}
+
+		let mut changes = Vec::new();
+		for row in rows {
+			let Some(Some(info)) = tables.get(&row.table) else {
+				continue;
+			};
+			let after = match read_row(db, &row.table, info, &row.primary_key, row.rowid) {
+				Ok(after) => after,
+				Err(err) => {
+					tracing::debug!(?err, table = %row.table, "skipping unreadable row change");
+					continue;
+				}
+			};
+
+			let (op, old_values, values) = match (row.before, after) {
+				(None, Some(after)) => (RowChangeOp::Insert, Vec::new(), after),
+				(Some(before), Some(after)) if before != after => {
+					(RowChangeOp::Update, before, after)
+				}
+				(Some(before), None) => (RowChangeOp::Delete, before, Vec::new()),
+				// Net no-op, or undone by a savepoint rollback.
+				_ => continue,
+			};
+
+			changes.push(RowChange {
+				seq: RowChangeSeq {
+					txid,
+					index: changes.len() as u32,
+				},
+				table: row.table,
+				op,
+				rowid: (!info.without_rowid).then_some(row.rowid),
+				columns: info.columns.clone(),
+				primary_key: row.primary_key,
+				old_values,
+				values,
+			});
+		}
+
+		changes
+	}
+
+	pub(crate) fn discard(&mut self) {
+		self.pending.clear();
+	}
+}
+
+/// Splits one write into its effect on each row it touched, as `(rowid, primary key, row before
+/// the write)`. An update that changes the primary key removes one row and adds another.
+fn row_effects(
+	info: &TableInfo,
+	op: RowChangeOp,
+	pending: &PendingRowChange,
+) -> Vec<(i64, Vec<ColumnValue>, Option<Vec<ColumnValue>>)> {
+	let old = || {
+		(
+			pending.old_rowid,
+			primary_key(info, &pending.old_values, pending.old_rowid),
+			Some(pending.old_values.clone()),
+		)
+	};
+	let new = || {
+		(
+			pending.new_rowid,
+			primary_key(info, &pending.new_values, pending.new_rowid),
+			None,
+		)
+	};
+
+	match op {
+		RowChangeOp::Insert => vec![new()],
+		RowChangeOp::Delete => vec![old()],
+		RowChangeOp::Update => {
+			let old = old();
+			let new = new();
+			if old.1 == new.1 {
+				vec![old]
+			} else {
+				vec![old, new]
+			}
+		}
+	}
+}
+
+fn primary_key(info: &TableInfo, values: &[ColumnValue], rowid: i64) -> Vec<ColumnValue> {
+	if info.primary_key.is_empty() {
+		return vec![ColumnValue::Integer(rowid)];
+	}
+
+	info.primary_key
+		.iter()
+		.map(|&index| values.get(index).cloned().unwrap_or(ColumnValue::Null))
+		.collect()
+}
+
+fn read_table_info(db: *mut sqlite3, table: &str) -> Result<TableInfo> {
+	let quoted = quote_identifier(table);
+	let columns =
+		execute_single_statement(db, &format!("PRAGMA main.table_xinfo({quoted});"), None)?;
+	let name_index = column_index(&columns.columns, "name")?;
+	let pk_index = column_index(&columns.columns, "pk")?;
+	if columns.rows.is_empty() {
+		return Err(anyhow!("table {table} does not exist"));
+	}
+
+	let mut primary_key = Vec::new();
+	let mut names = Vec::with_capacity(columns.rows.len());
+	for (index, row) in columns.rows.iter().enumerate() {
+		let Some(ColumnValue::Text(name)) = row.get(name_index) else {
+			return Err(anyhow!("malformed table_xinfo row for {table}"));
+		};
+		names.push(name.clone());
+		if let Some(ColumnValue::Integer(ordinal)) = row.get(pk_index)
+			&& *ordinal > 0
+		{
+			primary_key.push((*ordinal, index));
+		}
+	}
+	primary_key.sort_unstable();
+
+	let list = execute_single_statement(db, &format!("PRAGMA main.table_list({quoted});"), None)?;
+	let wr_index = column_index(&list.columns, "wr")?;
+	let without_rowid = list
+		.rows
+		.first()
+		.and_then(|row| row.get(wr_index))
+		.is_some_and(|wr| *wr == ColumnValue::Integer(1));
+
+	Ok(TableInfo {
+		columns: names,
+		primary_key: primary_key.into_iter().map(|(_, index)| index).collect(),
+		without_rowid,
+	})
+}
+
+fn column_index(columns: &[String], name: &str) -> Result<usize> {
+	columns
+		.iter()
+		.position(|column| column == name)
+		.ok_or_else(|| anyhow!("pragma result is missing column {name}"))
+}
+
+/// Reads the committed row with the given key. `None` if it does not exist.
+fn read_row(
+	db: *mut sqlite3,
+	table: &str,
+	info: &TableInfo,
+	primary_key: &[ColumnValue],
+	rowid: i64,
+) -> Result<Option<Vec<ColumnValue>>> {
+	let columns = info
+		.columns
+		.iter()
+		.map(|column| quote_identifier(column))
+		.collect::<Vec<_>>()
+		.join(", ");
+	let (filter, params) = if info.primary_key.is_empty() {
+		("rowid = ?".to_string(), vec![BindParam::Integer(rowid)])
+	} else {
+		let filter = info
+			.primary_key
+			.iter()
+			.map(|&index| format!("{} IS ?", quote_identifier(&info.columns[index])))
+			.collect::<Vec<_>>()
+			.join(" AND ");
+		(filter, primary_key.iter().map(bind_param).collect())
+	};
+	let sql = format!(
+		"SELECT {columns} FROM main.{} WHERE {filter};",
+		quote_identifier(table)
+	);
+	let result = execute_single_statement(db, &sql, Some(&params))?;
+
+	Ok(result.rows.into_iter().next())
+}
+
+fn quote_identifier(name: &str) -> String {
+	format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn bind_param(value: &ColumnValue) -> BindParam {
+	match value {
+		ColumnValue::Null => BindParam::Null,
+		ColumnValue::Integer(value) => BindParam::Integer(*value),
+		ColumnValue::Float(value) => BindParam::Float(*value),
+		ColumnValue::Text(value) => BindParam::Text(value.clone()),
+		ColumnValue::Blob(value) => BindParam::Blob(value.clone()),
+	}
+}
+
+fn is_captured_table(table: &str) -> bool {
+	!table.starts_with("sqlite_")
+}
+
+unsafe extern "C" fn preupdate_hook(
+	ctx: *mut c_void,
+	db: *mut sqlite3,
+	op: c_int,
+	db_name: *const c_char,
+	table: *const c_char,
+	old_rowid: sqlite3_int64,
+	new_rowid: sqlite3_int64,
+) {
+	let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+		if ctx.is_null() || db.is_null() || db_name.is_null() || table.is_null() {
+			return;
+		}
+		if unsafe { CStr::from_ptr(db_name) }.to_bytes() != b"main" {
+			return;
+		}
+		let table = unsafe { CStr::from_ptr(table) }.to_string_lossy();
+		if !is_captured_table(&table) {
+			return;
+		}
+		let op = match op {
+			SQLITE_INSERT => RowChangeOp::Insert,
+			SQLITE_UPDATE => RowChangeOp::Update,
+			SQLITE_DELETE => RowChangeOp::Delete,
+			_ => return,
+		};
+
+		let old_values = if op == RowChangeOp::Insert {
+			Vec::new()
+		} else {
+			unsafe { preupdate_values(db, sqlite3_preupdate_old) }
+		};
+		let new_values = if op == RowChangeOp::Delete {
+			Vec::new()
+		} else {
+			unsafe { preupdate_values(db, sqlite3_preupdate_new) }
+		};
+
+		let capture = unsafe { &mut *ctx.cast::<RowChangeCapture>() };
+		capture.pending.push(PendingRowChange {
+			table: table.into_owned(),
+			op,
+			old_rowid,
+			new_rowid,
+			old_values,
+			new_values,
+		});
+	}));
+}
+
+/// Reads every column of the row being written through `sqlite3_preupdate_old` or
+/// `sqlite3_preupdate_new`. Only valid inside the preupdate hook.
+unsafe fn preupdate_values(
+	db: *mut sqlite3,
+	read: unsafe extern "C" fn(*mut sqlite3, c_int, *mut *mut sqlite3_value) -> c_int,
+) -> Vec<ColumnValue> {
+	let count = unsafe { sqlite3_preupdate_count(db) };
+	(0..count)
+		.map(|index| {
+			let mut value = ptr::null_mut();
+			if unsafe { read(db, index, &mut value) } != SQLITE_OK || value.is_null() {
+				return ColumnValue::Null;
+			}
+			unsafe { sqlite_value(value) }
+		})
+		.collect()
+}
+
+unsafe fn sqlite_value(value: *mut sqlite3_value) -> ColumnValue {
+	match unsafe { sqlite3_value_type(value) } {
+		SQLITE_INTEGER => ColumnValue::Integer(unsafe { sqlite3_value_int64(value) }),
+		SQLITE_FLOAT => ColumnValue::Float(unsafe { sqlite3_value_double(value) }),
+		SQLITE_TEXT => {
+			let text = unsafe { sqlite3_value_text(value) };
+			if text.is_null() {
+				return ColumnValue::Null;
+			}
+			let len = unsafe { sqlite3_value_bytes(value) } as usize;
+			ColumnValue::Text(
+				String::from_utf8_lossy(unsafe { std::slice::from_raw_parts(text, len) })
+					.into_owned(),
+			)
+		}
+		SQLITE_BLOB => {
+			let len = unsafe { sqlite3_value_bytes(value) } as usize;
+			let blob = unsafe { sqlite3_value_blob(value) };
+			if blob.is_null() || len == 0 {
+				return ColumnValue::Blob(Vec::new());
+			}
+			ColumnValue::Blob(
+				unsafe { std::slice::from_raw_parts(blob.cast::<u8>(), len) }.to_vec(),
+			)
+		}
+		_ => ColumnValue::Null,
+	}
+}
+
+unsafe extern "C" fn rollback_hook(ctx: *mut c_void) {
+	if ctx.is_null() {
+		return;
+	}
+	let capture = unsafe { &mut *ctx.cast::<RowChangeCapture>() };
+	capture.discard();
+}
+
+/// Builds the row change log append for the changes of one commit. Changes that do not fit the
+/// per-commit limits are left out, oldest first, and the floor moves past them.
+pub(crate) fn log_append(
+	changes: &[RowChange],
+	floor: RowChangeSeq,
+) -> protocol::SqliteRowChangeLogAppend {
+	let mut floor = floor;
+	let mut total_bytes = 0;
+	let mut logged = Vec::new();
+	for change in changes.iter().rev() {
+		if change.seq <= floor {
+			break;
+		}
+		let encoded = encode_row_change(change);
+		total_bytes += encoded.len();
+		if encoded.len() > ROW_CHANGE_LOG_MAX_ENTRY_BYTES
+			|| total_bytes > ROW_CHANGE_LOG_MAX_COMMIT_BYTES
+		{
+			floor = change.seq;
+			break;
+		}
+		logged.push(protocol::SqliteRowChange {
+			index: change.seq.index,
+			change: encoded,
+		});
+	}
+	logged.reverse();
+
+	protocol::SqliteRowChangeLogAppend {
+		changes: logged,
+		floor: protocol::SqliteRowChangePosition {
+			txid: floor.txid,
+			index: floor.index,
+		},
+	}
+}
+
+// Encoding of a row change in the row change log. Little-endian, length-prefixed.
+
+fn encode_row_change(change: &RowChange) -> Vec<u8> {
+	let mut buf = Vec::new();
+	buf.push(match change.op {
+		RowChangeOp::Insert => 0,
+		RowChangeOp::Update => 1,
+		RowChangeOp::Delete => 2,
+	});
+	match change.rowid {
+		Some(rowid) => {
+			buf.push(1);
+			buf.extend_from_slice(&rowid.to_le_bytes());
+		}
+		None => buf.push(0),
+	}
+	encode_bytes(&mut buf, change.table.as_bytes());
+	buf.extend_from_slice(&(change.columns.len() as u32).to_le_bytes());
+	for column in &change.columns {
+		encode_bytes(&mut buf, column.as_bytes());
+	}
+	for values in [&change.primary_key, &change.old_values, &change.values] {
+		buf.extend_from_slice(&encode_values(values));
+	}
+	buf
+}
+
+fn decode_row_change(seq: RowChangeSeq, buf: &[u8]) -> Result<RowChange> {
+	let mut reader = Reader(buf);
+	let op = match reader.u8()? {
+		0 => RowChangeOp::Insert,
+		1 => RowChangeOp::Update,
+		2 => RowChangeOp::Delete,
+		op => return Err(anyhow!("unknown row change op {op}")),
+	};
+	let rowid = match reader.u8()? {
+		0 => None,
+		_ => Some(reader.i64()?),
+	};
+	let table = reader.string()?;
+	let columns = (0..reader.u32()?)
+		.map(|_| reader.string())
+		.collect::<Result<Vec<_>>>()?;
+	let primary_key = reader.values()?;
+	let old_values = reader.values()?;
+	let values = reader.values()?;
+
+	Ok(RowChange {
+		seq,
+		table,
+		op,
+		rowid,
+		columns,
+		primary_key,
+		old_values,
+		values,
+	})
+}
+
+fn encode_values(values: &[ColumnValue]) -> Vec<u8> {
+	let mut buf = Vec::new();
+	buf.extend_from_slice(&(values.len() as u32).to_le_bytes());
+	for value in values {
+		match value {
+			ColumnValue::Null => buf.push(0),
+			ColumnValue::Integer(value) => {
+				buf.push(1);
+				buf.extend_from_slice(&value.to_le_bytes());
+			}
+			ColumnValue::Float(value) => {
+				buf.push(2);
+				buf.extend_from_slice(&value.to_le_bytes());
+			}
+			ColumnValue::Text(value) => {
+				buf.push(3);
+				encode_bytes(&mut buf, value.as_bytes());
+			}
+			ColumnValue::Blob(value) => {
+				buf.push(4);
+				encode_bytes(&mut buf, value);
+			}
+		}
+	}
+	buf
+}
+
+fn encode_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+	buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+	buf.extend_from_slice(bytes);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+	fn take(&mut self, len: usize) -> Result<&[u8]> {
+		if self.0.len() < len {
+			return Err(anyhow!("truncated sqlite row change log entry"));
+		}
+		let (head, tail) = self.0.split_at(len);
+		self.0 = tail;
+		Ok(head)
+	}
+
+	fn u8(&mut self) -> Result<u8> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn u32(&mut self) -> Result<u32> {
+		Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+	}
+
+	fn i64(&mut self) -> Result<i64> {
+		Ok(i64::from_le_bytes(self.take(8)?.try_into()?))
+	}
+
+	fn bytes(&mut self) -> Result<Vec<u8>> {
+		let len = self.u32()? as usize;
+		Ok(self.take(len)?.to_vec())
+	}
+
+	fn string(&mut self) -> Result<String> {
+		Ok(String::from_utf8(self.bytes()?)?)
+	}
+
+	fn values(&mut self) -> Result<Vec<ColumnValue>> {
+		(0..self.u32()?)
+			.map(|_| {
+				Ok(match self.u8()? {
+					0 => ColumnValue::Null,
+					1 => ColumnValue::Integer(self.i64()?),
+					2 => ColumnValue::Float(f64::from_le_bytes(self.take(8)?.try_into()?)),
+					3 => ColumnValue::Text(self.string()?),
+					4 => ColumnValue::Blob(self.bytes()?),
+					tag => return Err(anyhow!("unknown column value tag {tag}")),
+				})
+			})
+			.collect()
+	}
+}
+
+#[derive(Debug)]
+pub struct RowChangesTruncatedError {
+	pub after: RowChangeSeq,
+	pub floor: RowChangeSeq,
+}
+
+impl fmt::Display for RowChangesTruncatedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"sqlite row changes after txid {} index {} are no longer retained, oldest resumable is txid {} index {}",
+			self.after.txid, self.after.index, self.floor.txid, self.floor.index
+		)
+	}
+}
+
+impl Error for RowChangesTruncatedError {}
+
+#[derive(Debug)]
+pub struct RowChangesLaggedError {
+	pub skipped: u64,
+}
+
+impl fmt::Display for RowChangesLaggedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"sqlite row change subscriber lagged and skipped {} changes",
+			self.skipped
+		)
+	}
+}
+
+impl Error for RowChangesLaggedError {}
+
+#[derive(Debug)]
+pub struct RowChangesClosedError;
+
+impl fmt::Display for RowChangesClosedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("sqlite row change capture is not running")
+	}
+}
+
+impl Error for RowChangesClosedError {}
@@ -2,17 +2,17 @@
 //!
 //! This crate owns the KV-backed SQLite behavior used by `rivetkit-napi`.
 
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::ffi::{CStr, CString, c_char, c_int, c_void};
 use std::ptr;
 use std::slice;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use depot_client_types::is_head_fence_mismatch;
+use depot_client_types::{is_head_fence_mismatch, is_quota_exceeded};
 use libsqlite3_sys::*;
 use moka::sync::Cache;
 use parking_lot::{Mutex, RwLock};
@@ -31,6 +31,7 @@ const DEFAULT_MAX_PREFETCH_BYTES: usize = 256 * 1024;
 const DEFAULT_ADAPTIVE_PREFETCH_DEPTH: usize = 256;
 const DEFAULT_ADAPTIVE_MAX_PREFETCH_BYTES: usize = 1024 * 1024;
 const DEFAULT_MAX_PAGES_PER_STAGE: usize = 4_000;
+const DEFAULT_MAX_READ_SNAPSHOT_PRESERVED_BYTES: usize = 64 * 1024 * 1024;
 const DEFAULT_RECENT_HINT_PAGE_BUDGET: usize = 128;
 const DEFAULT_RECENT_HINT_RANGE_BUDGET: usize = 16;
 const DEFAULT_PAGE_SIZE: usize = 4096;
@@ -118,6 +119,19 @@ pub trait SqliteTransport: Send + Sync {
 		&self,
 		request: protocol::SqliteCommitRequest,
 	) -> Result<protocol::SqliteCommitResponse>;
+
+	/// Reads the row change log depot keeps next to the database. Transports without one report
+	/// no log, so row change capture starts from the head.
+	async fn get_row_change_log(
+		&self,
+		_request: protocol::SqliteGetRowChangeLogRequest,
+	) -> Result<protocol::SqliteGetRowChangeLogResponse> {
+		Ok(
+			protocol::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(
+				protocol::SqliteGetRowChangeLogOk { log: None },
+			),
+		)
+	}
 }
 
 pub type SqliteTransportHandle = Arc<dyn SqliteTransport>;
@@ -141,6 +155,9 @@ pub struct VfsConfig {
 	pub max_prefetch_bytes: usize,
 	pub adaptive_max_prefetch_bytes: usize,
 	pub max_pages_per_stage: usize,
+	/// Read snapshots that need more than this many bytes of preserved pages are aborted, so a
+	/// long read cannot hold an unbounded copy of the database.
+	pub max_read_snapshot_preserved_bytes: usize,
 	pub startup_preload_max_bytes: usize,
 	pub startup_preload_first_pages: bool,
 	pub startup_preload_first_page_count: u32,
@@ -189,6 +206,7 @@ impl VfsConfig {
 			max_prefetch_bytes: DEFAULT_MAX_PREFETCH_BYTES,
 			adaptive_max_prefetch_bytes: DEFAULT_ADAPTIVE_MAX_PREFETCH_BYTES,
 			max_pages_per_stage: DEFAULT_MAX_PAGES_PER_STAGE,
+			max_read_snapshot_preserved_bytes: DEFAULT_MAX_READ_SNAPSHOT_PRESERVED_BYTES,
 			startup_preload_max_bytes: flags.startup_preload_max_bytes,
 			startup_preload_first_pages: flags.startup_preload_first_pages,
 			startup_preload_first_page_count: flags.startup_preload_first_page_count,
@@ -258,6 +276,7 @@ pub struct BufferedCommitRequest {
 	pub new_db_size_pages: u32,
 	pub dirty_pages: Vec<protocol::SqliteDirtyPage>,
 	pub expected_head_txid: Option<u64>,
+	pub row_changes: Option<protocol::SqliteRowChangeLogAppend>,
 }
 
 #[derive(Debug, Clone)]
@@ -270,9 +289,20 @@ pub struct BufferedCommitOutcome {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CommitBufferError {
 	FenceMismatch(String),
+	/// Depot rejected the commit because the database or its namespace is out of storage.
+	QuotaExceeded(SqliteQuotaError),
 	Other(String),
 }
 
+/// Storage quota error returned by depot for a commit, kept so the statement that caused the
+/// commit can fail with it instead of a generic I/O error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqliteQuotaError {
+	pub group: String,
+	pub code: String,
+	pub message: String,
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SqliteVfsMetricsSnapshot {
 	pub request_build_ns: u64,
@@ -417,6 +447,7 @@ pub struct VfsContext {
 	state: RwLock<VfsState>,
 	aux_files: RwLock<BTreeMap<String, Arc<AuxFileState>>>,
 	last_error: Mutex<Option<String>>,
+	quota_error: Mutex<Option<SqliteQuotaError>>,
 	transient_commit_error: Mutex<Option<String>>,
 	fatal_error: RwLock<Option<String>>,
 	#[cfg(test)]
@@ -425,6 +456,10 @@ pub struct VfsContext {
 	fail_next_aux_delete: Mutex<Option<String>>,
 	commit_atomic_count: AtomicU64,
 	io_methods: Box<sqlite3_io_methods>,
+	read_snapshots: Mutex<Vec<Weak<ReadSnapshot>>>,
+	// Held exclusively from preserving snapshot pages until a commit is applied locally, so a
+	// snapshot never starts while depot is ahead of the local head.
+	commit_gate: RwLock<()>,
 	// Performance counters
 	pub resolve_pages_total: AtomicU64,
 	pub resolve_pages_cache_hits: AtomicU64,
@@ -449,6 +484,11 @@ struct VfsState {
 	committed_page_cache: Cache<u32, Vec<u8>>,
 	protected_page_cache: Arc<SccHashMap<u32, Vec<u8>>>,
 	write_buffer: WriteBuffer,
+	/// Row changes of the transaction being committed, staged by the SQLite worker and sent with
+	/// the next commit.
+	staged_row_changes: Option<protocol::SqliteRowChangeLogAppend>,
+	/// Head txid of the commit that carried the staged row changes.
+	row_changes_txid: Option<u64>,
 	predictor: ClassifiedPredictor,
 	read_ahead: ClassifiedReadAhead,
 	recent_pages: RecentPageTracker,
@@ -582,6 +622,41 @@ struct VfsFile {
 	base: sqlite3_file,
 	ctx: *const VfsContext,
 	aux: *mut AuxFileHandle,
+	reader: *mut ReadFileState,
+}
+
+/// Committed database state a read-only connection reads at.
+///
+/// Commits that land while the snapshot is open first copy the pages they overwrite in here, so
+/// readers keep seeing the database as of `head_txid` without blocking the writer.
+struct ReadSnapshot {
+	head_txid: Option<u64>,
+	db_size_pages: u32,
+	preserved: Mutex<PreservedPages>,
+}
+
+#[derive(Default)]
+struct PreservedPages {
+	/// Snapshot versions of pages rewritten since. `None` marks a page that was absent.
+	pages: HashMap<u32, Option<Vec<u8>>>,
+	bytes: usize,
+	/// Set once the pages outgrew `VfsConfig::max_read_snapshot_preserved_bytes`. Every read of
+	/// the snapshot fails from then on.
+	aborted: bool,
+}
+
+/// Main database file opened by a read-only connection.
+#[derive(Default)]
+struct ReadFileState {
+	snapshot: Mutex<Option<Arc<ReadSnapshot>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PageReadSource {
+	/// The writer connection, which sees its own uncommitted pages.
+	Writer,
+	/// A read-only connection, which sees committed pages only.
+	Snapshot,
 }
 
 #[derive(Default)]
@@ -965,6 +1040,8 @@ impl VfsState {
 			committed_page_cache,
 			protected_page_cache: Arc::new(SccHashMap::new()),
 			write_buffer: WriteBuffer::default(),
+			staged_row_changes: None,
+			row_changes_txid: None,
 			predictor: ClassifiedPredictor::default(),
 			read_ahead: ClassifiedReadAhead::default(),
 			recent_pages: RecentPageTracker::new(
@@ -1085,6 +1162,16 @@ impl VfsState {
 			|| self.page_cache.contains_key(&pgno)
 	}
 
+	/// Records a committed page, refreshing any older copy read-only connections could see.
+	fn apply_committed_page(&mut self, config: &VfsConfig, pgno: u32, bytes: Vec<u8>) {
+		if self.page_cache.contains_key(&pgno) {
+			self.page_cache.insert(pgno, bytes.clone());
+		}
+		self.protected_page_cache
+			.update_sync(&pgno, |_, page| *page = bytes.clone());
+		self.cache_committed_page(config, pgno, bytes);
+	}
+
 	fn cache_committed_page(&mut self, config: &VfsConfig, pgno: u32, bytes: Vec<u8>) {
 		if config.staging_cache_ttl_ms == 0 || !config.page_cache_mode.caches_any_pages() {
 			return;
@@ -1198,6 +1285,7 @@ impl VfsContext {
 			state: RwLock::new(state),
 			aux_files: RwLock::new(BTreeMap::new()),
 			last_error: Mutex::new(None),
+			quota_error: Mutex::new(None),
 			transient_commit_error: Mutex::new(None),
 			fatal_error: RwLock::new(None),
 			#[cfg(test)]
@@ -1206,6 +1294,8 @@ impl VfsContext {
 			fail_next_aux_delete: Mutex::new(None),
 			commit_atomic_count: AtomicU64::new(0),
 			io_methods: Box::new(io_methods),
+			read_snapshots: Mutex::new(Vec::new()),
+			commit_gate: RwLock::new(()),
 			resolve_pages_total: AtomicU64::new(0),
 			resolve_pages_cache_hits: AtomicU64::new(0),
 			resolve_pages_fetches: AtomicU64::new(0),
@@ -1249,6 +1339,14 @@ impl VfsContext {
 		self.last_error.lock().take()
 	}
 
+	fn set_quota_error(&self, error: SqliteQuotaError) {
+		*self.quota_error.lock() = Some(error);
+	}
+
+	pub(crate) fn take_quota_error(&self) -> Option<SqliteQuotaError> {
+		self.quota_error.lock().take()
+	}
+
 	fn add_commit_phase_metrics(
 		&self,
 		request_build_ns: u64,
@@ -1414,6 +1512,15 @@ impl VfsContext {
 		&self,
 		target_pgnos: &[u32],
 		prefetch: bool,
+	) -> std::result::Result<HashMap<u32, Option<Vec<u8>>>, GetPagesError> {
+		self.resolve_pages_from(target_pgnos, prefetch, PageReadSource::Writer)
+	}
+
+	fn resolve_pages_from(
+		&self,
+		target_pgnos: &[u32],
+		prefetch: bool,
+		source: PageReadSource,
 	) -> std::result::Result<HashMap<u32, Option<Vec<u8>>>, GetPagesError> {
 		use std::sync::atomic::Ordering::Relaxed;
 		self.resolve_pages_total.fetch_add(1, Relaxed);
@@ -1437,9 +1544,11 @@ impl VfsContext {
 				if !seen.insert(pgno) {
 					continue;
 				}
-				if let Some(bytes) = state.write_buffer.dirty.get(&pgno) {
-					resolved.insert(pgno, Some(bytes.clone()));
-					continue;
+				if source == PageReadSource::Writer {
+					if let Some(bytes) = state.write_buffer.dirty.get(&pgno) {
+						resolved.insert(pgno, Some(bytes.clone()));
+						continue;
+					}
 				}
 				if let Some(bytes) = state.cached_page(&self.config, pgno) {
 					resolved.insert(pgno, Some(bytes));
@@ -1537,7 +1646,12 @@ impl VfsContext {
 				predicted_pgnos,
 				skipped_cached_predicted_pages,
 				state.db_size_pages,
-				state.head_txid,
+				// Snapshot reads may overlap a commit in flight. Unfenced reads are safe because
+				// pages that commit rewrites were preserved before it was sent.
+				match source {
+					PageReadSource::Writer => state.head_txid,
+					PageReadSource::Snapshot => None,
+				},
 			)
 		};
 
@@ -1596,7 +1710,7 @@ impl VfsContext {
 		match response {
 			protocol::SqliteGetPagesResponse::SqliteGetPagesOk(ok) => {
 				let response_head_txid = ok.head_txid;
-				if let Some(head_txid) = response_head_txid {
+				if let (PageReadSource::Writer, Some(head_txid)) = (source, response_head_txid) {
 					self.state.write().head_txid = Some(head_txid);
 				}
 				let missing_pages = missing.iter().copied().collect::<HashSet<_>>();
@@ -1604,6 +1718,18 @@ impl VfsContext {
 					let state = self.state.read();
 					(state.page_cache.clone(), state.protected_page_cache.clone())
 				};
+				// A commit applied while a snapshot read was in flight makes its pages stale for
+				// the shared cache. Holding the state lock keeps a commit from being applied
+				// between this check and the inserts below.
+				let snapshot_cache_guard = match source {
+					PageReadSource::Writer => None,
+					PageReadSource::Snapshot => {
+						let state = self.state.read();
+						(state.head_txid == response_head_txid).then_some(state)
+					}
+				};
+				let cache_fetched =
+					source == PageReadSource::Writer || snapshot_cache_guard.is_some();
 				#[cfg(debug_assertions)]
 				let mut returned_pgnos = HashSet::new();
 				#[cfg(debug_assertions)]
@@ -1626,12 +1752,14 @@ impl VfsContext {
 						&& missing_pages.contains(&fetched.pgno)
 						&& fetched.pgno == 1
 					{
-						self.state.write().head_txid = Some(0);
+						if source == PageReadSource::Writer {
+							self.state.write().head_txid = Some(0);
+						}
 						Some(empty_db_page())
 					} else {
 						fetched.bytes
 					};
-					if let Some(bytes) = &bytes {
+					if let Some(bytes) = bytes.as_ref().filter(|_| cache_fetched) {
 						let kind = if missing_pages.contains(&fetched.pgno) {
 							PageCacheInsertKind::Target
 						} else {
@@ -1677,6 +1805,7 @@ impl VfsContext {
 						);
 					}
 				}
+				drop(snapshot_cache_guard);
 				for pgno in missing {
 					resolved.entry(pgno).or_insert(None);
 				}
@@ -1746,15 +1875,22 @@ impl VfsContext {
 						bytes: bytes.clone(),
 					})
 					.collect(),
+				row_changes: state.staged_row_changes.clone(),
 			}
 		};
 		let request_build_ns = request_build_start.elapsed().as_nanos() as u64;
 
+		let mut old_pages = HashMap::new();
+		let read_old_pages = self.read_pages_for_read_snapshots(&request, &mut old_pages);
+		let _commit_gate = self.commit_gate.write();
 		let (outcome, transport_metrics) =
 			// Transport rejection, including envoy shutdown while a VFS callback is
 			// active, becomes CommitBufferError here. xSync and xClose then surface
 			// it to SQLite as SQLITE_IOERR_*.
-			match self.block_on_buffered_commit(request.clone(), timeout) {
+			match read_old_pages
+				.and_then(|_| self.preserve_pages_for_read_snapshots(&request, old_pages))
+				.and_then(|()| self.block_on_buffered_commit(request.clone(), timeout))
+			{
 				Ok(CommitWait::Completed(outcome)) => outcome,
 				Ok(CommitWait::TimedOut) => return Ok(CommitWait::TimedOut),
 				Err(err) => {
@@ -1803,8 +1939,12 @@ impl VfsContext {
 		state.head_txid = outcome
 			.head_txid
 			.or_else(|| state.head_txid.map(|head_txid| head_txid.saturating_add(1)));
+		if request.row_changes.is_some() {
+			state.staged_row_changes = None;
+			state.row_changes_txid = state.head_txid;
+		}
 		for dirty_page in &request.dirty_pages {
-			state.cache_committed_page(&self.config, dirty_page.pgno, dirty_page.bytes.clone());
+			state.apply_committed_page(&self.config, dirty_page.pgno, dirty_page.bytes.clone());
 		}
 		state.write_buffer.dirty.clear();
 		let state_update_ns = state_update_start.elapsed().as_nanos() as u64;
@@ -1860,26 +2000,32 @@ impl VfsContext {
 						bytes: bytes.clone(),
 					})
 					.collect(),
+				row_changes: state.staged_row_changes.clone(),
 			}
 		};
 		let request_build_ns = request_build_start.elapsed().as_nanos() as u64;
 
-		let (outcome, transport_metrics) =
-			match self.block_on_buffered_commit(request.clone(), timeout) {
-				Ok(CommitWait::Completed(outcome)) => outcome,
-				Ok(CommitWait::TimedOut) => return Ok(CommitWait::TimedOut),
-				Err(err) => {
-					tracing::error!(
-						actor_id = %self.actor_id,
-						new_db_size_pages = request.new_db_size_pages,
-						dirty_pages = request.dirty_pages.len(),
-						?err,
-						"sqlite atomic commit failed"
-					);
-					handle_non_finalize_commit_error(self, &err);
-					return Err(err);
-				}
-			};
+		let mut old_pages = HashMap::new();
+		let read_old_pages = self.read_pages_for_read_snapshots(&request, &mut old_pages);
+		let _commit_gate = self.commit_gate.write();
+		let (outcome, transport_metrics) = match read_old_pages
+			.and_then(|_| self.preserve_pages_for_read_snapshots(&request, old_pages))
+			.and_then(|()| self.block_on_buffered_commit(request.clone(), timeout))
+		{
+			Ok(CommitWait::Completed(outcome)) => outcome,
+			Ok(CommitWait::TimedOut) => return Ok(CommitWait::TimedOut),
+			Err(err) => {
+				tracing::error!(
+					actor_id = %self.actor_id,
+					new_db_size_pages = request.new_db_size_pages,
+					dirty_pages = request.dirty_pages.len(),
+					?err,
+					"sqlite atomic commit failed"
+				);
+				handle_non_finalize_commit_error(self, &err);
+				return Err(err);
+			}
+		};
 		self.commit_total
 			.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 		if let Some(metrics) = &self.metrics {
@@ -1915,8 +2061,12 @@ impl VfsContext {
 		state.head_txid = outcome
 			.head_txid
 			.or_else(|| state.head_txid.map(|head_txid| head_txid.saturating_add(1)));
+		if request.row_changes.is_some() {
+			state.staged_row_changes = None;
+			state.row_changes_txid = state.head_txid;
+		}
 		for dirty_page in &request.dirty_pages {
-			state.cache_committed_page(&self.config, dirty_page.pgno, dirty_page.bytes.clone());
+			state.apply_committed_page(&self.config, dirty_page.pgno, dirty_page.bytes.clone());
 		}
 		state.write_buffer.dirty.clear();
 		state.write_buffer.in_atomic_write = false;
@@ -1941,6 +2091,211 @@ impl VfsContext {
 			.retain(|pgno, _| *pgno <= truncated_pages);
 		state.invalidate_page_cache();
 	}
+
+	/// Pins the latest committed state for a read-only connection.
+	fn begin_read_snapshot(&self) -> Arc<ReadSnapshot> {
+		let _commit_gate = self.commit_gate.read();
+		let snapshot = {
+			let state = self.state.read();
+			Arc::new(ReadSnapshot {
+				head_txid: state.head_txid,
+				db_size_pages: state.db_size_pages,
+				preserved: Mutex::new(PreservedPages::default()),
+			})
+		};
+
+		let mut snapshots = self.read_snapshots.lock();
+		snapshots.retain(|snapshot| snapshot.strong_count() > 0);
+		snapshots.push(Arc::downgrade(&snapshot));
+		snapshot
+	}
+
+	/// Resolves pages as of `snapshot`, preferring the copies commits preserved for it.
+	fn resolve_snapshot_pages(
+		&self,
+		snapshot: &ReadSnapshot,
+		target_pgnos: &[u32],
+	) -> std::result::Result<HashMap<u32, Option<Vec<u8>>>, GetPagesError> {
+		let mut resolved = HashMap::new();
+		let mut remaining = Vec::new();
+		{
+			let preserved = snapshot.preserved.lock();
+			check_read_snapshot_not_aborted(&preserved)?;
+			for pgno in target_pgnos.iter().copied() {
+				match preserved.pages.get(&pgno) {
+					Some(bytes) => {
+						resolved.insert(pgno, bytes.clone());
+					}
+					None => remaining.push(pgno),
+				}
+			}
+		}
+		if remaining.is_empty() {
+			return Ok(resolved);
+		}
+
+		let fetched = self.resolve_pages_from(&remaining, true, PageReadSource::Snapshot)?;
+		// A commit may have been sent while these pages were read. It preserved every page it
+		// rewrites before sending, so checking again picks up the snapshot version.
+		let preserved = snapshot.preserved.lock();
+		check_read_snapshot_not_aborted(&preserved)?;
+		for pgno in remaining {
+			let bytes = match preserved.pages.get(&pgno) {
+				Some(bytes) => bytes.clone(),
+				None => fetched.get(&pgno).cloned().flatten(),
+			};
+			resolved.insert(pgno, bytes);
+		}
+		Ok(resolved)
+	}
+
+	/// Reads the committed versions of the pages `request` overwrites or truncates that open read
+	/// snapshots do not have yet and `old_pages` does not hold. Returns the open snapshots.
+	///
+	/// Runs before the commit gate is taken, so new read snapshots are not blocked on depot reads.
+	/// Only the writer commits, so the pages stay current until the commit is sent.
+	fn read_pages_for_read_snapshots(
+		&self,
+		request: &BufferedCommitRequest,
+		old_pages: &mut HashMap<u32, Option<Vec<u8>>>,
+	) -> std::result::Result<Vec<Arc<ReadSnapshot>>, CommitBufferError> {
+		let snapshots = {
+			let mut snapshots = self.read_snapshots.lock();
+			snapshots.retain(|snapshot| snapshot.strong_count() > 0);
+			snapshots
+				.iter()
+				.filter_map(Weak::upgrade)
+				.collect::<Vec<_>>()
+		};
+
+		let mut needed = BTreeSet::new();
+		for snapshot in &snapshots {
+			let preserved = snapshot.preserved.lock();
+			if preserved.aborted {
+				continue;
+			}
+			let rewritten = request.dirty_pages.iter().map(|page| page.pgno);
+			let truncated = request.new_db_size_pages.saturating_add(1)..=snapshot.db_size_pages;
+			needed.extend(rewritten.chain(truncated).filter(|pgno| {
+				*pgno <= snapshot.db_size_pages
+					&& !preserved.pages.contains_key(pgno)
+					&& !old_pages.contains_key(pgno)
+			}));
+		}
+		if needed.is_empty() {
+			return Ok(snapshots);
+		}
+
+		let mut missing = Vec::new();
+		{
+			let state = self.state.read();
+			for pgno in needed {
+				match state.cached_page(&self.config, pgno) {
+					Some(bytes) => {
+						old_pages.insert(pgno, Some(bytes));
+					}
+					None => missing.push(pgno),
+				}
+			}
+		}
+		for pgnos in missing.chunks(self.config.max_pages_per_stage.max(1)) {
+			old_pages.extend(self.fetch_committed_pages(pgnos, request.expected_head_txid)?);
+		}
+
+		Ok(snapshots)
+	}
+
+	/// Copies the pages `request` overwrites or truncates into every open read snapshot that does
+	/// not have them yet. Must run under the commit gate before the commit is sent. `old_pages`
+	/// holds the pages read before the gate was taken.
+	///
+	/// Snapshots that would preserve more than `VfsConfig::max_read_snapshot_preserved_bytes` are
+	/// aborted instead of holding up the commit.
+	fn preserve_pages_for_read_snapshots(
+		&self,
+		request: &BufferedCommitRequest,
+		mut old_pages: HashMap<u32, Option<Vec<u8>>>,
+	) -> std::result::Result<(), CommitBufferError> {
+		// Snapshots that began since the pages were read need their pages too
+		let snapshots = self.read_pages_for_read_snapshots(request, &mut old_pages)?;
+
+		for snapshot in &snapshots {
+			let mut preserved = snapshot.preserved.lock();
+			if preserved.aborted {
+				continue;
+			}
+
+			for (pgno, bytes) in &old_pages {
+				if *pgno <= snapshot.db_size_pages && !preserved.pages.contains_key(pgno) {
+					preserved.bytes += bytes.as_ref().map_or(0, Vec::len);
+					preserved.pages.insert(*pgno, bytes.clone());
+				}
+			}
+
+			if preserved.bytes > self.config.max_read_snapshot_preserved_bytes {
+				tracing::warn!(
+					actor_id = %self.actor_id,
+					head_txid = ?snapshot.head_txid,
+					preserved_bytes = preserved.bytes,
+					"aborting sqlite read snapshot that preserved too many pages"
+				);
+				*preserved = PreservedPages {
+					aborted: true,
+					..Default::default()
+				};
+			}
+		}
+		Ok(())
+	}
+
+	/// Reads committed pages straight from depot, bypassing the write buffer and caches.
+	fn fetch_committed_pages(
+		&self,
+		pgnos: &[u32],
+		expected_head_txid: Option<u64>,
+	) -> std::result::Result<HashMap<u32, Option<Vec<u8>>>, CommitBufferError> {
+		let response = self
+			.runtime
+			.block_on(self.transport.get_pages(protocol::SqliteGetPagesRequest {
+				actor_id: self.actor_id.clone(),
+				pgnos: pgnos.to_vec(),
+				expected_generation: None,
+				expected_head_txid,
+			}))
+			.map_err(|err| CommitBufferError::Other(err.to_string()))?;
+
+		match response {
+			protocol::SqliteGetPagesResponse::SqliteGetPagesOk(ok) => {
+				let mut pages = pgnos
+					.iter()
+					.map(|pgno| (*pgno, None))
+					.collect::<HashMap<_, _>>();
+				for fetched in ok.pages {
+					pages.insert(fetched.pgno, fetched.bytes);
+				}
+				Ok(pages)
+			}
+			protocol::SqliteGetPagesResponse::SqliteErrorResponse(error) => {
+				if is_head_fence_mismatch_response(&error) {
+					return Err(CommitBufferError::FenceMismatch(error.message));
+				}
+				Err(CommitBufferError::Other(error.message))
+			}
+		}
+	}
+}
+
+fn check_read_snapshot_not_aborted(
+	preserved: &PreservedPages,
+) -> std::result::Result<(), GetPagesError> {
+	if preserved.aborted {
+		return Err(GetPagesError::Other(
+			"sqlite read snapshot was aborted because concurrent commits rewrote too many pages"
+				.to_string(),
+		));
+	}
+
+	Ok(())
 }
 
 impl Drop for VfsContext {
@@ -2029,6 +2384,10 @@ fn assert_batch_atomic_probe(db: *mut sqlite3, vfs: &SqliteVfs) -> std::result::
 fn handle_non_finalize_commit_error(ctx: &VfsContext, err: &CommitBufferError) {
 	match err {
 		CommitBufferError::FenceMismatch(message) => ctx.mark_fatal(message.clone()),
+		CommitBufferError::QuotaExceeded(error) => {
+			ctx.set_last_error(error.message.clone());
+			ctx.set_quota_error(error.clone());
+		}
 		CommitBufferError::Other(message) => ctx.set_last_error(message.clone()),
 	}
 }
@@ -2162,6 +2521,10 @@ unsafe fn get_aux_state(file: &VfsFile) -> Option<&AuxFileHandle> {
 	(!file.aux.is_null()).then(|| &*file.aux)
 }
 
+unsafe fn get_reader_state(file: &VfsFile) -> Option<&ReadFileState> {
+	(!file.reader.is_null()).then(|| &*file.reader)
+}
+
 async fn commit_buffered_pages(
 	transport: &dyn SqliteTransport,
 	request: BufferedCommitRequest,
@@ -2175,6 +2538,7 @@ async fn commit_buffered_pages(
 		now_ms: sqlite_now_ms().map_err(|err| CommitBufferError::Other(err.to_string()))?,
 		expected_generation: None,
 		expected_head_txid: request.expected_head_txid,
+		row_changes: request.row_changes.clone(),
 	};
 	metrics.serialize_ns += serialize_start.elapsed().as_nanos() as u64;
 	let transport_start = Instant::now();
@@ -2197,6 +2561,12 @@ async fn commit_buffered_pages(
 		protocol::SqliteCommitResponse::SqliteErrorResponse(error) => {
 			if is_head_fence_mismatch_response(&error) {
 				Err(CommitBufferError::FenceMismatch(error.message))
+			} else if is_quota_exceeded(&error.group, &error.code) {
+				Err(CommitBufferError::QuotaExceeded(SqliteQuotaError {
+					group: error.group,
+					code: error.code,
+					message: error.message,
+				}))
 			} else {
 				Err(CommitBufferError::Other(error.message))
 			}
@@ -2448,6 +2818,10 @@ unsafe extern "C" fn io_close(p_file: *mut sqlite3_file) -> c_int {
 			}
 			file.aux = ptr::null_mut();
 			Ok(())
+		} else if !file.reader.is_null() {
+			drop(Box::from_raw(file.reader));
+			file.reader = ptr::null_mut();
+			Ok(())
 		} else {
 			let ctx = &*file.ctx;
 			let should_flush = {
@@ -2517,21 +2891,45 @@ unsafe extern "C" fn io_read(
 			return SQLITE_IOERR_READ;
 		}
 
+		let snapshot = match get_reader_state(file) {
+			Some(reader) => match reader.snapshot.lock().clone() {
+				Some(snapshot) => Some(snapshot),
+				None => {
+					tracing::error!(
+						actor_id = %ctx.actor_id,
+						"sqlite read-only connection read outside a snapshot"
+					);
+					return SQLITE_IOERR_READ;
+				}
+			},
+			None => None,
+		};
+
 		let buf = slice::from_raw_parts_mut(p_buf.cast::<u8>(), i_amt as usize);
 		let requested_pages = match page_span(i_offset, i_amt as usize, ctx.page_size()) {
 			Ok(pages) => pages,
 			Err(_) => return SQLITE_IOERR_READ,
 		};
 		let page_size = ctx.page_size();
-		let (file_size, db_size_pages) = {
-			let state = ctx.state.read();
-			(
-				state.db_size_pages as usize * state.page_size,
-				state.db_size_pages,
-			)
+		let (file_size, db_size_pages) = match &snapshot {
+			Some(snapshot) => (
+				snapshot.db_size_pages as usize * page_size,
+				snapshot.db_size_pages,
+			),
+			None => {
+				let state = ctx.state.read();
+				(
+					state.db_size_pages as usize * state.page_size,
+					state.db_size_pages,
+				)
+			}
 		};
 
-		let resolved = match ctx.resolve_pages(&requested_pages, true) {
+		let resolved = match &snapshot {
+			Some(snapshot) => ctx.resolve_snapshot_pages(snapshot, &requested_pages),
+			None => ctx.resolve_pages(&requested_pages, true),
+		};
+		let resolved = match resolved {
 			Ok(pages) => pages,
 			Err(GetPagesError::FenceMismatch(message)) => {
 				tracing::error!(
@@ -2618,6 +3016,9 @@ unsafe extern "C" fn io_write(
 		if i_amt <= 0 {
 			return SQLITE_OK;
 		}
+		if get_reader_state(get_file(p_file)).is_some() {
+			return SQLITE_READONLY;
+		}
 
 		let file = get_file(p_file);
 		if let Some(aux) = get_aux_state(file) {
@@ -2770,6 +3171,9 @@ unsafe extern "C" fn io_truncate(p_file: *mut sqlite3_file, size: sqlite3_int64)
 			aux.state.bytes.lock().truncate(size as usize);
 			return SQLITE_OK;
 		}
+		if get_reader_state(file).is_some() {
+			return SQLITE_READONLY;
+		}
 		let ctx = &*file.ctx;
 		ctx.truncate_main_file(size);
 		SQLITE_OK
@@ -2782,7 +3186,7 @@ unsafe extern "C" fn io_truncate(p_file: *mut sqlite3_file, size: sqlite3_int64)
 unsafe extern "C" fn io_sync(p_file: *mut sqlite3_file, _flags: c_int) -> c_int {
 	vfs_catch_unwind!(SQLITE_IOERR_FSYNC, {
 		let file = get_file(p_file);
-		if get_aux_state(file).is_some() {
+		if get_aux_state(file).is_some() || get_reader_state(file).is_some() {
 			return SQLITE_OK;
 		}
 		let ctx = &*file.ctx;
@@ -2814,6 +3218,12 @@ unsafe extern "C" fn io_file_size(p_file: *mut sqlite3_file, p_size: *mut sqlite
 			return SQLITE_OK;
 		}
 		let ctx = &*file.ctx;
+		if let Some(reader) = get_reader_state(file) {
+			if let Some(snapshot) = reader.snapshot.lock().as_ref() {
+				*p_size = (snapshot.db_size_pages as usize * ctx.page_size()) as sqlite3_int64;
+				return SQLITE_OK;
+			}
+		}
 		let state = ctx.state.read();
 		*p_size = (state.db_size_pages as usize * state.page_size) as sqlite3_int64;
 		SQLITE_OK
@@ -2821,11 +3231,10 @@ unsafe extern "C" fn io_file_size(p_file: *mut sqlite3_file, p_size: *mut sqlite
 }
 
 // Lock callbacks are intentional no-ops. Pegboard guarantees a single actor
-// process per actor_id, the database is opened with `locking_mode=EXCLUSIVE`,
-// and only one SQLite connection runs against it, so SQLite's internal lock
-// state machine is single-party and has nothing to coordinate with. Flipping
-// to a non-EXCLUSIVE locking mode or a multi-connection setup would require
-// implementing a real lock state ladder here.
+// process per actor_id, the writer connection is opened with
+// `locking_mode=EXCLUSIVE`, and it is the only connection that writes, so
+// SQLite's internal lock state machine has nothing to coordinate with.
+// Read-only connections get isolation from `ReadSnapshot` instead of locks.
 unsafe extern "C" fn io_lock(_p_file: *mut sqlite3_file, _level: c_int) -> c_int {
 	vfs_catch_unwind!(SQLITE_IOERR_LOCK, SQLITE_OK)
 }
@@ -2835,11 +3244,13 @@ unsafe extern "C" fn io_unlock(_p_file: *mut sqlite3_file, _level: c_int) -> c_i
 }
 
 unsafe extern "C" fn io_check_reserved_lock(
-	_p_file: *mut sqlite3_file,
+	p_file: *mut sqlite3_file,
 	p_res_out: *mut c_int,
 ) -> c_int {
 	vfs_catch_unwind!(SQLITE_IOERR, {
-		*p_res_out = 0;
+		// Read-only connections must treat a journal the writer has open as live, never as a hot
+		// journal to roll back.
+		*p_res_out = c_int::from(get_reader_state(get_file(p_file)).is_some());
 		SQLITE_OK
 	})
 }
@@ -2851,7 +3262,7 @@ unsafe extern "C" fn io_file_control(
 ) -> c_int {
 	vfs_catch_unwind!(SQLITE_IOERR, {
 		let file = get_file(p_file);
-		if get_aux_state(file).is_some() {
+		if get_aux_state(file).is_some() || get_reader_state(file).is_some() {
 			return SQLITE_NOTFOUND;
 		}
 		let ctx = &*file.ctx;
@@ -2950,6 +3361,11 @@ unsafe extern "C" fn vfs_open(
 		let base = sqlite3_file {
 			pMethods: ctx.io_methods.as_ref(),
 		};
+		let reader = if is_main && (flags & SQLITE_OPEN_READONLY) != 0 {
+			Box::into_raw(Box::new(ReadFileState::default()))
+		} else {
+			ptr::null_mut()
+		};
 		let aux = if is_main {
 			ptr::null_mut()
 		} else {
@@ -2965,6 +3381,7 @@ unsafe extern "C" fn vfs_open(
 				base,
 				ctx: ctx as *const VfsContext,
 				aux,
+				reader,
 			},
 		);
 
@@ -3121,6 +3538,10 @@ impl SqliteVfs {
 		self.ctx.take_last_error()
 	}
 
+	pub(crate) fn take_quota_error(&self) -> Option<SqliteQuotaError> {
+		self.ctx.take_quota_error()
+	}
+
 	fn clone_last_error(&self) -> Option<String> {
 		self.ctx.clone_last_error()
 	}
@@ -3309,6 +3730,44 @@ impl NativeDatabase {
 		self._vfs.ctx.round_trip_counts()
 	}
 
+	/// Depot txid of the last commit this connection observed.
+	pub fn head_txid(&self) -> Option<u64> {
+		self._vfs.ctx.state.read().head_txid
+	}
+
+	/// Stages row changes to be written with the next commit, replacing any staged before.
+	pub(crate) fn stage_row_changes(&self, row_changes: protocol::SqliteRowChangeLogAppend) {
+		let mut state = self._vfs.ctx.state.write();
+		state.staged_row_changes = Some(row_changes);
+		state.row_changes_txid = None;
+	}
+
+	/// Drops staged row changes. Returns the txid of the commit that carried them, or `None` if
+	/// none did.
+	pub(crate) fn take_row_changes_txid(&self) -> Option<u64> {
+		let mut state = self._vfs.ctx.state.write();
+		state.staged_row_changes = None;
+		state.row_changes_txid.take()
+	}
+
+	/// Reads the row change log depot keeps for this database.
+	pub(crate) fn row_change_log(&self) -> Result<Option<protocol::SqliteRowChangeLog>> {
+		let ctx = self._vfs.ctx();
+		let response = ctx.runtime.block_on(ctx.transport.get_row_change_log(
+			protocol::SqliteGetRowChangeLogRequest {
+				actor_id: ctx.actor_id.clone(),
+				expected_generation: None,
+			},
+		))?;
+		match response {
+			protocol::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(ok) => Ok(ok.log),
+			protocol::SqliteGetRowChangeLogResponse::SqliteErrorResponse(error) => Err(anyhow!(
+				"failed to read sqlite row change log: {}",
+				error.message
+			)),
+		}
+	}
+
 	pub fn snapshot_preload_hints(&self) -> VfsPreloadHintSnapshot {
 		self._vfs.snapshot_preload_hints()
 	}
@@ -3365,6 +3824,61 @@ impl Drop for NativeDatabase {
 	}
 }
 
+/// Read-only connection to a database written through a `NativeDatabase` on the same VFS.
+///
+/// Every statement runs against a snapshot pinned by `with_snapshot`, so reads neither block
+/// the writer nor observe a commit that is partly applied.
+pub struct NativeReadConnection {
+	db: *mut sqlite3,
+	file: *mut VfsFile,
+	vfs: NativeVfsHandle,
+}
+
+unsafe impl Send for NativeReadConnection {}
+
+impl NativeReadConnection {
+	/// Runs `f` against a snapshot of the latest commit.
+	pub fn with_snapshot<T>(&mut self, f: impl FnOnce(*mut sqlite3) -> T) -> T {
+		struct ClearSnapshot<'a>(&'a ReadFileState);
+
+		impl Drop for ClearSnapshot<'_> {
+			fn drop(&mut self) {
+				*self.0.snapshot.lock() = None;
+			}
+		}
+
+		let reader = unsafe { &*(*self.file).reader };
+		let snapshot = self.vfs.ctx.begin_read_snapshot();
+		// The writer holds an exclusive lock and never bumps the file change counter, so SQLite
+		// cannot tell its cached pages are stale. Dropping them forces reads through the snapshot.
+		unsafe {
+			sqlite3_db_release_memory(self.db);
+		}
+		*reader.snapshot.lock() = Some(snapshot);
+		let _clear = ClearSnapshot(reader);
+
+		f(self.db)
+	}
+}
+
+impl Drop for NativeReadConnection {
+	fn drop(&mut self) {
+		if self.db.is_null() {
+			return;
+		}
+
+		let rc = unsafe { sqlite3_close_v2(self.db) };
+		if rc != SQLITE_OK {
+			tracing::warn!(
+				rc,
+				error = sqlite_error_message(self.db),
+				"failed to close sqlite read connection"
+			);
+		}
+		self.db = ptr::null_mut();
+	}
+}
+
 pub fn open_database(
 	vfs: SqliteVfs,
 	file_name: &str,
@@ -3386,6 +3900,67 @@ pub fn open_connection(
 	file_name: &str,
 	flags: c_int,
 ) -> std::result::Result<NativeConnection, String> {
+	let db = open_raw_connection(&vfs, file_name, flags)?;
+	Ok(NativeDatabase { db, _vfs: vfs })
+}
+
+/// Opens a read-only connection that reads from snapshots of the database `vfs` writes.
+pub fn open_read_connection(
+	vfs: NativeVfsHandle,
+	file_name: &str,
+) -> std::result::Result<NativeReadConnection, String> {
+	let db = open_raw_connection(&vfs, file_name, SQLITE_OPEN_READONLY)?;
+	let mut connection = NativeReadConnection {
+		db,
+		file: ptr::null_mut(),
+		vfs,
+	};
+
+	let mut file: *mut sqlite3_file = ptr::null_mut();
+	let rc = unsafe {
+		sqlite3_file_control(
+			db,
+			c"main".as_ptr(),
+			SQLITE_FCNTL_FILE_POINTER,
+			(&mut file as *mut *mut sqlite3_file).cast(),
+		)
+	};
+	if rc != SQLITE_OK || file.is_null() || unsafe { (*file.cast::<VfsFile>()).reader.is_null() } {
+		return Err(format!(
+			"sqlite read connection for {file_name} did not open a read-only main file"
+		));
+	}
+	connection.file = file.cast::<VfsFile>();
+
+	// SQLite interprets a negative cache_size as a KiB budget instead of a page count.
+	let cache_size_kib = sqlite_optimization_flags().pager_cache_size_kib;
+	let cache_size_pragma = format!("PRAGMA cache_size = -{cache_size_kib};");
+	// NORMAL locking makes SQLite revalidate its cache at the start of every read transaction.
+	let pragmas = [
+		"PRAGMA locking_mode = NORMAL;",
+		"PRAGMA query_only = 1;",
+		cache_size_pragma.as_str(),
+	];
+	for pragma in &pragmas {
+		if let Err(err) = connection.with_snapshot(|db| sqlite_exec(db, pragma)) {
+			tracing::error!(
+				file_name,
+				pragma,
+				%err,
+				"failed to configure sqlite read connection"
+			);
+			return Err(err);
+		}
+	}
+
+	Ok(connection)
+}
+
+fn open_raw_connection(
+	vfs: &NativeVfsHandle,
+	file_name: &str,
+	flags: c_int,
+) -> std::result::Result<*mut sqlite3, String> {
 	let c_name = CString::new(file_name).map_err(|err| err.to_string())?;
 	let mut db: *mut sqlite3 = ptr::null_mut();
 
@@ -3407,7 +3982,7 @@ pub fn open_connection(
 		return Err(format!("sqlite3_open_v2 failed with code {rc}: {message}"));
 	}
 
-	Ok(NativeDatabase { db, _vfs: vfs })
+	Ok(db)
 }
 
 pub fn configure_connection_for_database(
@@ -1,5 +1,6 @@
 use std::{
 	error::Error,
+	ffi::CStr,
 	fmt,
 	sync::{
 		Arc,
@@ -9,14 +10,23 @@ use std::{
 	time::{Duration, Instant},
 };
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use crossbeam_channel::{Receiver, Sender, TrySendError};
-use libsqlite3_sys::{SQLITE_OPEN_CREATE, SQLITE_OPEN_READWRITE, sqlite3_get_autocommit};
+use libsqlite3_sys::{
+	SQLITE_OPEN_CREATE, SQLITE_OPEN_READWRITE, sqlite3, sqlite3_get_autocommit, sqlite3_sql,
+	sqlite3_stmt, sqlite3_stmt_readonly,
+};
 use parking_lot::Mutex;
 use tokio::sync::{Notify, oneshot};
 
 use crate::{
-	query::{BindParam, ExecuteResult, QueryResult, exec_statements, execute_single_statement},
+	query::{
+		BindParam, ExecuteResult, QueryResult, StatementHook, exec_statements,
+		exec_statements_with_hook, execute_single_statement, execute_single_statement_with_hook,
+	},
+	row_changes::{
+		RowChange, RowChangeCapture, RowChangeFeed, RowChangeSeq, RowChangeStream, log_append,
+	},
 	vfs::{
 		NativeConnection, NativeVfsHandle, SqliteRoundTripCounts, SqliteVfsMetrics,
 		configure_connection_for_database, open_connection, verify_batch_atomic_writes,
@@ -50,6 +60,7 @@ struct SqliteWorkerInner {
 	closed: Notify,
 	join: Mutex<Option<JoinHandle<()>>>,
 	ready: Mutex<Option<oneshot::Receiver<Result<()>>>>,
+	row_changes: RowChangeFeed,
 }
 
 enum SqliteCommand {
@@ -62,6 +73,9 @@ enum SqliteCommand {
 		sql: String,
 		reply: oneshot::Sender<Result<QueryResult>>,
 	},
+	EnableRowChanges {
+		reply: oneshot::Sender<Result<()>>,
+	},
 	#[cfg(test)]
 	Pause {
 		entered: oneshot::Sender<()>,
@@ -109,6 +123,7 @@ impl SqliteWorkerHandle {
 			closed: Notify::new(),
 			join: Mutex::new(None),
 			ready: Mutex::new(Some(ready_rx)),
+			row_changes: RowChangeFeed::new(),
 		});
 
 		let thread_inner = Arc::clone(&inner);
@@ -127,6 +142,7 @@ impl SqliteWorkerHandle {
 					std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| worker_main(ctx)))
 				{
 					thread_inner.state.store(STATE_DEAD, Ordering::Release);
+					thread_inner.row_changes.close();
 					if let Some(metrics) = &thread_inner.metrics {
 						metrics.record_worker_crash();
 					}
@@ -171,6 +187,18 @@ impl SqliteWorkerHandle {
 		result.await.map_err(|_| sqlite_worker_dead_error())?
 	}
 
+	/// Subscribes to row changes committed after `after`, or from now on if `after` is `None`.
+	///
+	/// Capture is enabled on the first call and stays on until the worker closes. Streams end when
+	/// the worker closes.
+	pub async fn row_changes(&self, after: Option<RowChangeSeq>) -> Result<RowChangeStream> {
+		let (reply, result) = oneshot::channel();
+		self.enqueue(SqliteCommand::EnableRowChanges { reply })?;
+		result.await.map_err(|_| sqlite_worker_dead_error())??;
+
+		self.inner.row_changes.subscribe(after)
+	}
+
 	pub async fn close(&self) -> Result<()> {
 		let start = Instant::now();
 		if self.inner.mark_closing() {
@@ -365,12 +393,14 @@ fn worker_main(mut ctx: WorkerContext) {
 			}
 			fail_queued_sql(&ctx.sql_rx);
 			ctx.inner.state.store(STATE_DEAD, Ordering::Release);
+			ctx.inner.row_changes.close();
 			ctx.inner.closed.notify_waiters();
 			return;
 		}
 	};
 
 	let mut transaction: Option<TransactionTracker> = None;
+	let mut row_change_capture: Option<Box<RowChangeCapture>> = None;
 	loop {
 		if ctx.close_rx.try_recv().is_ok()
 			|| ctx.inner.state.load(Ordering::Acquire) == STATE_CLOSING
@@ -414,12 +444,19 @@ fn worker_main(mut ctx: WorkerContext) {
 					ctx.inner.metrics.as_deref(),
 					&ctx.file_name,
 					&mut transaction,
+					&mut row_change_capture,
+					&ctx.inner.row_changes,
 				);
 			}
 		}
 	}
 
+	if row_change_capture.is_some() {
+		RowChangeCapture::uninstall(db.as_ptr());
+	}
 	drop(db);
+	drop(row_change_capture);
+	ctx.inner.row_changes.close();
 	ctx.inner.state.store(STATE_CLOSED, Ordering::Release);
 	ctx.inner.closed.notify_waiters();
 }
@@ -444,6 +481,8 @@ fn run_command(
 	metrics: Option<&dyn SqliteVfsMetrics>,
 	file_name: &str,
 	transaction: &mut Option<TransactionTracker>,
+	row_change_capture: &mut Option<Box<RowChangeCapture>>,
+	row_changes: &RowChangeFeed,
 ) {
 	let start = Instant::now();
 	match command {
@@ -457,7 +496,15 @@ fn run_command(
 			// behind (a BEGIN flips autocommit off, a COMMIT flips it back on).
 			let in_tx = command_in_tx(db);
 			let stmt_kind = classify_statement(&sql);
-			let result = execute_single_statement(db.as_ptr(), &sql, params.as_deref());
+			let result = match row_change_capture {
+				Some(capture) => execute_single_statement_with_hook(
+					db.as_ptr(),
+					&sql,
+					params.as_deref(),
+					&mut RowChangeCommitHook::new(db, capture, row_changes),
+				),
+				None => execute_single_statement(db.as_ptr(), &sql, params.as_deref()),
+			};
 			record_command_metrics(
 				metrics,
 				"execute",
@@ -467,6 +514,7 @@ fn run_command(
 				start.elapsed(),
 			);
 			finalize_transaction_if_complete(db, metrics, file_name, transaction);
+			discard_row_changes_if_idle(db, row_change_capture);
 			let _ = reply.send(result);
 		}
 		SqliteCommand::Exec { sql, reply } => {
@@ -476,9 +524,27 @@ fn run_command(
 			begin_transaction_if_needed(db, transaction);
 			let in_tx = command_in_tx(db);
 			let stmt_kind = classify_statement(&sql);
-			let result = exec_statements(db.as_ptr(), &sql);
+			let result = match row_change_capture {
+				Some(capture) => exec_statements_with_hook(
+					db.as_ptr(),
+					&sql,
+					&mut RowChangeCommitHook::new(db, capture, row_changes),
+				),
+				None => exec_statements(db.as_ptr(), &sql),
+			};
 			record_command_metrics(metrics, "exec", in_tx, stmt_kind, &result, start.elapsed());
 			finalize_transaction_if_complete(db, metrics, file_name, transaction);
+			discard_row_changes_if_idle(db, row_change_capture);
+			let _ = reply.send(result);
+		}
+		SqliteCommand::EnableRowChanges { reply } => {
+			let result = if row_change_capture.is_none() {
+				enable_row_changes(db, row_changes).map(|capture| {
+					*row_change_capture = Some(capture);
+				})
+			} else {
+				Ok(())
+			};
 			let _ = reply.send(result);
 		}
 		#[cfg(test)]
@@ -563,6 +629,184 @@ fn finalize_transaction_if_complete(
 	}
 }
 
+/// Writes the row changes of each transaction with its commit.
+///
+/// Changes are resolved inside the transaction right before a statement that may commit it, and
+/// staged on the VFS so the commit carries them to depot. A write outside an explicit transaction
+/// runs in one this hook opens, so its changes are resolved before it commits too. Statements
+/// that cannot run in a transaction, and pragmas, commit without their changes, which moves the
+/// floor past them.
+struct RowChangeCommitHook<'a> {
+	db: &'a NativeConnection,
+	capture: &'a mut RowChangeCapture,
+	row_changes: &'a RowChangeFeed,
+	/// Set while a statement runs in a transaction this hook opened.
+	wrapped: bool,
+	staged: Option<StagedRowChanges>,
+}
+
+struct StagedRowChanges {
+	/// Txid the commit is expected to get.
+	txid: u64,
+	changes: Vec<RowChange>,
+	floor: RowChangeSeq,
+}
+
+impl<'a> RowChangeCommitHook<'a> {
+	fn new(
+		db: &'a NativeConnection,
+		capture: &'a mut RowChangeCapture,
+		row_changes: &'a RowChangeFeed,
+	) -> Self {
+		Self {
+			db,
+			capture,
+			row_changes,
+			wrapped: false,
+			staged: None,
+		}
+	}
+
+	/// Resolves the changes of the open transaction and stages them for its commit.
+	fn stage(&mut self) {
+		// Depot txids grow by one per commit.
+		let head_txid = self.db.head_txid().unwrap_or(0);
+		let txid = head_txid + 1;
+		let head_floor = RowChangeSeq {
+			txid: head_txid,
+			index: u32::MAX,
+		};
+		let mut floor = self.row_changes.floor().unwrap_or(head_floor);
+		if !self.capture.covers(head_txid) {
+			// A commit since the last logged one went out without its changes.
+			floor = floor.max(head_floor);
+		}
+
+		let changes = self.capture.resolve(self.db.as_ptr(), txid);
+		let append = log_append(&changes, self.row_changes.floor_after(&changes, floor));
+		let floor = RowChangeSeq {
+			txid: append.floor.txid,
+			index: append.floor.index,
+		};
+		self.db.stage_row_changes(append);
+		self.staged = Some(StagedRowChanges {
+			txid,
+			changes,
+			floor,
+		});
+	}
+
+	/// Publishes the staged changes if the statement committed them.
+	fn finish(&mut self, db: *mut sqlite3) {
+		let committed_txid = self.db.take_row_changes_txid();
+		let Some(staged) = self.staged.take() else {
+			return;
+		};
+
+		match committed_txid {
+			Some(txid) => {
+				let mut changes = staged.changes;
+				let mut floor = staged.floor;
+				if txid == staged.txid {
+					self.capture.mark_covered(txid);
+				} else {
+					// An earlier commit of the transaction carried no changes, so the next
+					// logged commit moves the floor past it.
+					floor = floor.max(RowChangeSeq {
+						txid: txid - 1,
+						index: u32::MAX,
+					});
+					for change in &mut changes {
+						change.seq.txid = txid;
+					}
+				}
+				self.capture.discard();
+				self.row_changes.publish(changes, floor);
+			}
+			// The transaction ended without writing anything.
+			None if !in_transaction(db) => self.capture.discard(),
+			None => {}
+		}
+	}
+}
+
+impl StatementHook for RowChangeCommitHook<'_> {
+	fn before(&mut self, db: *mut sqlite3, stmt: *mut sqlite3_stmt) -> Result<()> {
+		let sql = unsafe { sqlite3_sql(stmt) };
+		let kind = if sql.is_null() {
+			"other"
+		} else {
+			classify_statement(&unsafe { CStr::from_ptr(sql) }.to_string_lossy())
+		};
+
+		if in_transaction(db) {
+			if matches!(kind, "commit" | "release") {
+				self.stage();
+			}
+		} else if unsafe { sqlite3_stmt_readonly(stmt) } == 0
+			&& !matches!(kind, "vacuum" | "attach" | "detach" | "pragma")
+		{
+			exec_statements(db, "BEGIN;")?;
+			self.wrapped = true;
+		}
+
+		Ok(())
+	}
+
+	fn after(&mut self, db: *mut sqlite3, succeeded: bool) -> Result<()> {
+		if !std::mem::take(&mut self.wrapped) {
+			self.finish(db);
+			return Ok(());
+		}
+
+		if !succeeded {
+			// The statement already failed, a failed rollback has nothing to add.
+			let _ = exec_statements(db, "ROLLBACK;");
+			self.finish(db);
+			return Ok(());
+		}
+
+		self.stage();
+		let result = exec_statements(db, "COMMIT;");
+		if result.is_err() && in_transaction(db) {
+			let _ = exec_statements(db, "ROLLBACK;");
+		}
+		self.finish(db);
+		result.map(|_| ())
+	}
+}
+
+/// Drops recorded row changes once no transaction is open. Any left belong to a commit written
+/// without them, which the next logged commit accounts for.
+fn discard_row_changes_if_idle(
+	db: &mut NativeConnection,
+	row_change_capture: &mut Option<Box<RowChangeCapture>>,
+) {
+	if let Some(capture) = row_change_capture
+		&& !command_in_tx(db)
+	{
+		capture.discard();
+	}
+}
+
+/// Turns on row change capture, resuming the feed from the row change log depot keeps.
+///
+/// Must run with no transaction open.
+fn enable_row_changes(
+	db: &mut NativeConnection,
+	row_changes: &RowChangeFeed,
+) -> Result<Box<RowChangeCapture>> {
+	if command_in_tx(db) {
+		bail!("cannot enable row changes inside a transaction");
+	}
+
+	let head_txid = db.head_txid().unwrap_or(0);
+	let (floor, history) = RowChangeCapture::decode_log(db.row_change_log()?, head_txid)?;
+	row_changes.start(floor, history);
+
+	Ok(RowChangeCapture::install(db.as_ptr(), head_txid))
+}
+
 fn record_command_metrics<T>(
 	metrics: Option<&dyn SqliteVfsMetrics>,
 	operation: &'static str,
@@ -591,7 +835,11 @@ fn record_command_metrics<T>(
 /// transaction) and zero once a `BEGIN` opens one. Implicit per-statement
 /// transactions keep autocommit on, so this only reports explicit transactions.
 fn command_in_tx(db: &mut NativeConnection) -> bool {
-	unsafe { sqlite3_get_autocommit(db.as_ptr()) == 0 }
+	in_transaction(db.as_ptr())
+}
+
+fn in_transaction(db: *mut sqlite3) -> bool {
+	unsafe { sqlite3_get_autocommit(db) == 0 }
 }
 
 /// Classifies a SQL command by its leading keyword for metric labeling.
@@ -662,6 +910,9 @@ fn fail_command(command: SqliteCommand) {
 		SqliteCommand::Exec { reply, .. } => {
 			let _ = reply.send(Err(sqlite_closing_error()));
 		}
+		SqliteCommand::EnableRowChanges { reply } => {
+			let _ = reply.send(Err(sqlite_closing_error()));
+		}
 		#[cfg(test)]
 		SqliteCommand::Pause { resume, .. } => {
 			drop(resume);
@@ -768,7 +1019,7 @@ impl fmt::Display for SqliteWorkerFatalError {
 
 impl Error for SqliteWorkerFatalError {}
 
-fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+pub(crate) fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
 	if let Some(message) = payload.downcast_ref::<&str>() {
 		message.to_string()
 	} else if let Some(message) = payload.downcast_ref::<String>() {
@@ -26,6 +26,7 @@ use crate::optimization_flags::{
 	SqliteVfsPageCacheMode,
 };
 use crate::query::{BindParam, ColumnValue};
+use crate::row_changes::{RowChangeOp, RowChangeSeq, RowChangesTruncatedError};
 use crate::vfs::SqliteVfsMetrics;
 use crate::worker::SqliteWorkerFatalError;
 
@@ -924,6 +925,288 @@ fn worker_records_basic_metrics() {
 	assert_eq!(metrics.command_errors.load(Ordering::Acquire), 0);
 }
 
+#[test]
+fn worker_row_changes_report_primary_keys_and_resume_after_reopen() {
+	let runtime = direct_runtime();
+	let harness = DirectEngineHarness::new();
+	let db = open_worker_handle(&runtime, &harness);
+
+	let (insert, update) = runtime.block_on(async {
+		db.exec("CREATE TABLE tags(name TEXT PRIMARY KEY, hits INTEGER) WITHOUT ROWID;".to_owned())
+			.await
+			.expect("table should be created");
+		let mut changes = db.row_changes(None).await.expect("capture should start");
+
+		db.execute(
+			"INSERT INTO tags(name, hits) VALUES ('a', 1);".to_owned(),
+			None,
+		)
+		.await
+		.expect("insert should succeed");
+		db.execute(
+			"UPDATE tags SET hits = 2 WHERE name = 'a';".to_owned(),
+			None,
+		)
+		.await
+		.expect("update should succeed");
+
+		let insert = changes.recv().await.unwrap().unwrap();
+		let update = changes.recv().await.unwrap().unwrap();
+		assert_eq!(insert.op, RowChangeOp::Insert);
+		assert_eq!(insert.rowid, None);
+		assert_eq!(insert.primary_key, vec![ColumnValue::Text("a".to_owned())]);
+		assert_eq!(update.op, RowChangeOp::Update);
+		assert_eq!(update.primary_key, vec![ColumnValue::Text("a".to_owned())]);
+		assert_eq!(
+			update.old_values,
+			vec![ColumnValue::Text("a".to_owned()), ColumnValue::Integer(1)]
+		);
+		assert_eq!(
+			update.values,
+			vec![ColumnValue::Text("a".to_owned()), ColumnValue::Integer(2)]
+		);
+
+		// Each write is a single commit, and the log stays out of the schema.
+		assert_eq!(update.seq.txid, insert.seq.txid + 1);
+		let schema = db
+			.query(
+				"SELECT group_concat(name, ',') FROM sqlite_master WHERE name NOT LIKE 'sqlite_%';"
+					.to_owned(),
+				None,
+			)
+			.await
+			.expect("schema query should succeed");
+		assert_eq!(
+			schema.rows,
+			vec![vec![ColumnValue::Text("tags".to_owned())]]
+		);
+
+		db.close().await.expect("worker should close");
+		(insert, update)
+	});
+
+	// The log survives the restart, so a subscriber resumes where it left off.
+	let db = open_worker_handle(&runtime, &harness);
+	runtime.block_on(async {
+		let mut resumed = db
+			.row_changes(Some(insert.seq))
+			.await
+			.expect("resume after reopen should succeed");
+		assert_eq!(resumed.recv().await.unwrap().unwrap(), update);
+
+		db.close().await.expect("worker should close");
+	});
+}
+
+#[test]
+fn worker_streams_net_row_changes_of_committed_transactions() {
+	let runtime = direct_runtime();
+	let harness = DirectEngineHarness::new();
+	let db = open_worker_handle(&runtime, &harness);
+
+	runtime.block_on(async {
+		db.exec("CREATE TABLE items(id INTEGER PRIMARY KEY, label TEXT);".to_owned())
+			.await
+			.expect("table should be created");
+		let mut changes = db.row_changes(None).await.expect("capture should start");
+
+		db.exec(
+			"BEGIN; \
+			 INSERT INTO items(id, label) VALUES (1, 'a'); \
+			 INSERT INTO items(id, label) VALUES (2, 'b'); \
+			 UPDATE items SET label = 'a2' WHERE id = 1; \
+			 INSERT INTO items(id, label) VALUES (3, 'gone'); \
+			 DELETE FROM items WHERE id = 3; \
+			 SAVEPOINT undo; \
+			 INSERT INTO items(id, label) VALUES (4, 'undone'); \
+			 ROLLBACK TO undo; \
+			 COMMIT;"
+				.to_owned(),
+		)
+		.await
+		.expect("transaction should commit");
+		db.exec("BEGIN; INSERT INTO items(id, label) VALUES (5, 'x'); ROLLBACK;".to_owned())
+			.await
+			.expect("rolled back transaction should run");
+		db.execute("DELETE FROM items WHERE id = 2;".to_owned(), None)
+			.await
+			.expect("delete should succeed");
+
+		let first = changes.recv().await.unwrap().unwrap();
+		let second = changes.recv().await.unwrap().unwrap();
+		let third = changes.recv().await.unwrap().unwrap();
+
+		assert_eq!(first.op, RowChangeOp::Insert);
+		assert_eq!(first.table, "items");
+		assert_eq!(first.rowid, Some(1));
+		assert_eq!(first.primary_key, vec![ColumnValue::Integer(1)]);
+		assert!(first.old_values.is_empty());
+		assert_eq!(first.columns, vec!["id", "label"]);
+		assert_eq!(
+			first.values,
+			vec![ColumnValue::Integer(1), ColumnValue::Text("a2".to_owned())]
+		);
+		assert_eq!(second.op, RowChangeOp::Insert);
+		assert_eq!(second.rowid, Some(2));
+		assert_eq!(second.seq.txid, first.seq.txid);
+		assert_eq!((first.seq.index, second.seq.index), (0, 1));
+
+		assert_eq!(third.op, RowChangeOp::Delete);
+		assert_eq!(third.rowid, Some(2));
+		assert_eq!(
+			third.old_values,
+			vec![ColumnValue::Integer(2), ColumnValue::Text("b".to_owned())]
+		);
+		assert!(third.values.is_empty());
+		assert!(third.seq.txid > first.seq.txid);
+
+		// Resuming replays only what came after the given sequence.
+		let mut resumed = db
+			.row_changes(Some(second.seq))
+			.await
+			.expect("resume should succeed");
+		assert_eq!(resumed.recv().await.unwrap().unwrap(), third);
+
+		let err = match db
+			.row_changes(Some(RowChangeSeq { txid: 0, index: 0 }))
+			.await
+		{
+			Ok(_) => panic!("resuming before capture started should fail"),
+			Err(err) => err,
+		};
+		assert!(err.downcast_ref::<RowChangesTruncatedError>().is_some());
+
+		db.close().await.expect("worker should close");
+		assert!(changes.recv().await.is_none());
+	});
+}
+
+#[test]
+fn worker_readonly_queries_see_committed_state_only() {
+	let runtime = direct_runtime();
+	let harness = DirectEngineHarness::new();
+	let db = open_worker_handle(&runtime, &harness);
+
+	runtime.block_on(async {
+		db.exec(
+			"CREATE TABLE items(id INTEGER PRIMARY KEY); INSERT INTO items(id) VALUES (1);"
+				.to_owned(),
+		)
+		.await
+		.expect("table should be created");
+		db.exec("BEGIN; INSERT INTO items(id) VALUES (2);".to_owned())
+			.await
+			.expect("transaction should open");
+
+		// The open transaction neither blocks nor leaks into read-only queries.
+		let result = db
+			.query_readonly("SELECT COUNT(*) FROM items;".to_owned(), None)
+			.await
+			.expect("read-only query should run");
+		assert_eq!(result.rows, vec![vec![ColumnValue::Integer(1)]]);
+
+		let err = db
+			.query_readonly("INSERT INTO items(id) VALUES (3);".to_owned(), None)
+			.await
+			.expect_err("read-only connection should reject writes");
+		assert!(err.to_string().contains("readonly"), "{err:#}");
+
+		db.exec("COMMIT;".to_owned())
+			.await
+			.expect("transaction should commit");
+		let result = db
+			.query_readonly(
+				"SELECT id FROM items WHERE id > ?;".to_owned(),
+				Some(vec![BindParam::Integer(1)]),
+			)
+			.await
+			.expect("read-only query should run");
+		assert_eq!(result.rows, vec![vec![ColumnValue::Integer(2)]]);
+
+		db.close().await.expect("worker should close");
+	});
+}
+
+#[test]
+fn read_snapshot_is_not_torn_by_concurrent_commit() {
+	let runtime = direct_runtime();
+	let harness = DirectEngineHarness::new();
+	let db = harness.open_db(&runtime);
+	sqlite_exec(
+		db.as_ptr(),
+		"CREATE TABLE items(id INTEGER PRIMARY KEY, value INTEGER); \
+		 INSERT INTO items(id, value) VALUES (1, 10);",
+	)
+	.expect("table should be created");
+	let mut reader =
+		open_read_connection(db._vfs.clone(), &harness.actor_id).expect("reader should open");
+
+	reader.with_snapshot(|reader_db| {
+		sqlite_exec(
+			db.as_ptr(),
+			"UPDATE items SET value = 20 WHERE id = 1; \
+			 WITH RECURSIVE n(id) AS (SELECT 2 UNION ALL SELECT id + 1 FROM n WHERE id < 500) \
+			 INSERT INTO items(id, value) SELECT id, 0 FROM n;",
+		)
+		.expect("writer should commit while the snapshot is open");
+
+		assert_eq!(
+			sqlite_query_i64(reader_db, "SELECT value FROM items WHERE id = 1;"),
+			Ok(10)
+		);
+		assert_eq!(
+			sqlite_query_i64(reader_db, "SELECT COUNT(*) FROM items;"),
+			Ok(1)
+		);
+	});
+
+	reader.with_snapshot(|reader_db| {
+		assert_eq!(
+			sqlite_query_i64(reader_db, "SELECT COUNT(*) FROM items;"),
+			Ok(500)
+		);
+	});
+}
+
+#[test]
+fn read_snapshot_is_aborted_once_it_preserves_too_many_pages() {
+	let runtime = direct_runtime();
+	let harness = DirectEngineHarness::new();
+	let engine = runtime.block_on(harness.open_engine());
+	let db = harness.open_db_on_engine(
+		&runtime,
+		engine,
+		&harness.actor_id,
+		VfsConfig {
+			max_read_snapshot_preserved_bytes: DEFAULT_PAGE_SIZE * 2,
+			..VfsConfig::default()
+		},
+	);
+	sqlite_exec(
+		db.as_ptr(),
+		"CREATE TABLE items(id INTEGER PRIMARY KEY, value BLOB); \
+		 WITH RECURSIVE n(id) AS (SELECT 1 UNION ALL SELECT id + 1 FROM n WHERE id < 500) \
+		 INSERT INTO items(id, value) SELECT id, zeroblob(100) FROM n;",
+	)
+	.expect("table should be created");
+	let mut reader =
+		open_read_connection(db._vfs.clone(), &harness.actor_id).expect("reader should open");
+
+	reader.with_snapshot(|reader_db| {
+		sqlite_exec(db.as_ptr(), "UPDATE items SET value = zeroblob(101);")
+			.expect("writer should commit past an oversized snapshot");
+
+		assert!(sqlite_query_i64(reader_db, "SELECT COUNT(*) FROM items;").is_err());
+	});
+
+	reader.with_snapshot(|reader_db| {
+		assert_eq!(
+			sqlite_query_i64(reader_db, "SELECT length(value) FROM items WHERE id = 1;"),
+			Ok(101)
+		);
+	});
+}
+
 fn sqlite_query_i64(db: *mut sqlite3, sql: &str) -> std::result::Result<i64, String> {
 	let c_sql = CString::new(sql).map_err(|err| err.to_string())?;
 	let mut stmt = ptr::null_mut();
@@ -2985,6 +3268,7 @@ fn concurrent_reader_during_commit_atomic_observes_consistent_snapshot() {
 				base: unsafe { std::mem::zeroed() },
 				ctx: &ctx,
 				aux: ptr::null_mut(),
+				reader: ptr::null_mut(),
 			};
 			let mut buf = vec![0; 8192];
 			let rc = unsafe {
@@ -3986,6 +4270,7 @@ fn head_fence_mismatch_on_xread_poisoned_vfs_fails_later_operations_closed() {
 		base: unsafe { std::mem::zeroed() },
 		ctx: &ctx,
 		aux: ptr::null_mut(),
+		reader: ptr::null_mut(),
 	};
 	let mut buf = vec![0; 4096];
 	let rc = unsafe {
@@ -5678,7 +5963,7 @@ fn warm_pidx_stale_read_then_rmw_commit_via_natural_reopen() {
 					request.now_ms,
 					CommitOptions {
 						expected_head_txid: request.expected_head_txid,
-						disable_size_cap: false,
+						..Default::default()
 					},
 				)
 				.await
@@ -6126,6 +6411,7 @@ fn commit_buffered_pages_uses_fast_path() {
 					pgno: 1,
 					bytes: empty_db_page(),
 				}],
+				row_changes: None,
 			},
 		))
 		.expect("fast-path commit should succeed");
@@ -311,18 +311,44 @@ impl SqliteTransport for DirectDepotTransport {
 			.map(storage_dirty_page)
 			.collect::<Vec<_>>();
 		let actor_db = self.storage.actor_db(actor_id).await;
-		match actor_db
-			.commit_with_options(
-				dirty_pages,
-				request.db_size_pages,
-				request.now_ms,
-				depot::types::CommitOptions {
-					expected_head_txid: request.expected_head_txid,
-					..Default::default()
-				},
-			)
-			.await
-		{
+		let options = depot::types::CommitOptions {
+			expected_head_txid: request.expected_head_txid,
+			..Default::default()
+		};
+		let result = match request.row_changes {
+			Some(row_changes) => {
+				actor_db
+					.commit_with_row_changes(
+						dirty_pages,
+						request.db_size_pages,
+						request.now_ms,
+						options,
+						depot::types::RowChangeLogAppend {
+							changes: row_changes
+								.changes
+								.into_iter()
+								.map(|change| (change.index, change.change))
+								.collect(),
+							floor: depot::types::RowChangePosition {
+								txid: row_changes.floor.txid,
+								index: row_changes.floor.index,
+							},
+						},
+					)
+					.await
+			}
+			None => {
+				actor_db
+					.commit_with_options(
+						dirty_pages,
+						request.db_size_pages,
+						request.now_ms,
+						options,
+					)
+					.await
+			}
+		};
+		match result {
 			Ok(result) => {
 				if let Some(message) = self.storage.hooks.take_commit_after_apply_error() {
 					return Err(anyhow::anyhow!(message));
@@ -338,6 +364,36 @@ impl SqliteTransport for DirectDepotTransport {
 			)),
 		}
 	}
+
+	async fn get_row_change_log(
+		&self,
+		request: protocol::SqliteGetRowChangeLogRequest,
+	) -> Result<protocol::SqliteGetRowChangeLogResponse> {
+		let actor_db = self.storage.actor_db(request.actor_id).await;
+		let log = actor_db.row_change_log().await?;
+		let position =
+			|position: depot::types::RowChangePosition| protocol::SqliteRowChangePosition {
+				txid: position.txid,
+				index: position.index,
+			};
+		Ok(
+			protocol::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(
+				protocol::SqliteGetRowChangeLogOk {
+					log: log.map(|log| protocol::SqliteRowChangeLog {
+						floor: position(log.floor),
+						changes: log
+							.changes
+							.into_iter()
+							.map(|change| protocol::SqliteLoggedRowChange {
+								position: position(change.position),
+								change: change.change,
+							})
+							.collect(),
+					}),
+				},
+			),
+		)
+	}
 }
 
 pub(crate) struct DirectMirrorTransport {
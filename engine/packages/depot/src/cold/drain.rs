@@ -0,0 +1,561 @@
+//! Moves superseded shard versions out of UniversalDB into the cold store.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Context, Result};
+use futures_util::{TryStreamExt, future::try_join_all};
+use sha2::{Digest, Sha256};
+use universaldb::{
+	RangeOption,
+	options::StreamingMode,
+	utils::IsolationLevel::{Serializable, Snapshot},
+};
+
+use super::{ColdStore, decode_object_branch_id, shard_object_key};
+use crate::conveyer::{
+	error::SqliteStorageError,
+	keys, metrics,
+	types::{
+		ColdShardRef, CompactionRoot, DatabaseBranchId, decode_commit_row, decode_compaction_root,
+		decode_db_head, encode_cold_shard_ref, encode_compaction_root,
+	},
+};
+
+/// Rows read per transaction when scanning shard keys or the branch list.
+const SCAN_BATCH_ROWS: usize = 64;
+/// Upper bound on shard versions offloaded from one branch per drain pass.
+const MAX_DRAIN_PER_BRANCH: usize = 256;
+/// Objects listed per orphan sweep batch.
+const SWEEP_BATCH_OBJECTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchDrainOutcome {
+	pub shards_drained: usize,
+	pub bytes_drained: u64,
+	/// Shard versions that changed between the scan and the install and were left hot.
+	pub shards_skipped: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DrainCandidate {
+	shard_id: u32,
+	as_of_txid: u64,
+}
+
+/// Offloads every shard version of `branch_id` that was superseded at least `hot_window_txids`
+/// txids before the branch head.
+///
+/// The newest version of each shard is never offloaded, so reads at the head never touch the
+/// cold store.
+#[tracing::instrument(skip_all, fields(branch_id = %branch_id.as_uuid()))]
+pub async fn drain_branch(
+	udb: &universaldb::Database,
+	store: &dyn ColdStore,
+	branch_id: DatabaseBranchId,
+	hot_window_txids: u64,
+) -> Result<BranchDrainOutcome> {
+	let mut outcome = BranchDrainOutcome::default();
+
+	let head_txid = udb
+		.txn("depot_cold_read_head", move |tx| async move {
+			let Some(head) = tx
+				.informal()
+				.get(&keys::branch_meta_head_key(branch_id), Snapshot)
+				.await?
+			else {
+				return Ok(None);
+			};
+
+			Ok(Some(
+				decode_db_head(&head)
+					.context("decode sqlite head for cold drain")?
+					.head_txid,
+			))
+		})
+		.await?;
+	let Some(head_txid) = head_txid else {
+		return Ok(outcome);
+	};
+	let Some(drain_floor) = head_txid.checked_sub(hot_window_txids) else {
+		return Ok(outcome);
+	};
+
+	let candidates = select_candidates(scan_shard_versions(udb, branch_id).await?, drain_floor);
+	if candidates.is_empty() {
+		return Ok(outcome);
+	}
+
+	let mut max_drained_txid = None;
+	for candidate in candidates {
+		let hot_key = keys::branch_shard_key(branch_id, candidate.shard_id, candidate.as_of_txid);
+		let blob = udb
+			.txn("depot_cold_read_shard", |tx| {
+				let hot_key = hot_key.clone();
+				async move {
+					Ok(tx
+						.informal()
+						.get(&hot_key, Snapshot)
+						.await?
+						.map(Vec::<u8>::from))
+				}
+			})
+			.await?;
+		let Some(blob) = blob else {
+			outcome.shards_skipped += 1;
+			continue;
+		};
+
+		let cold_ref = ColdShardRef {
+			object_key: shard_object_key(branch_id, candidate.shard_id, candidate.as_of_txid),
+			size_bytes: u64::try_from(blob.len()).unwrap_or(u64::MAX),
+			content_hash: content_hash(&blob),
+		};
+		store
+			.put(&cold_ref.object_key, blob)
+			.await
+			.context("upload shard version to cold store")?;
+
+		let installed = udb
+			.txn("depot_cold_install_shard", |tx| {
+				let hot_key = hot_key.clone();
+				let cold_ref = cold_ref.clone();
+				async move {
+					// The hot row may have been pruned (truncate) or replaced since the scan.
+					let Some(current) = tx.informal().get(&hot_key, Serializable).await? else {
+						return Ok(false);
+					};
+					if content_hash(&current) != cold_ref.content_hash {
+						return Ok(false);
+					}
+
+					tx.informal().set(
+						&keys::branch_cold_shard_key(
+							branch_id,
+							candidate.shard_id,
+							candidate.as_of_txid,
+						),
+						&encode_cold_shard_ref(cold_ref)?,
+					);
+					tx.informal().clear(&hot_key);
+
+					Ok(true)
+				}
+			})
+			.await?;
+
+		if installed {
+			outcome.shards_drained += 1;
+			outcome.bytes_drained += cold_ref.size_bytes;
+			max_drained_txid = max_drained_txid.max(Some(candidate.as_of_txid));
+			metrics::SQLITE_COLD_SHARD_DRAINED_TOTAL.inc();
+			metrics::SQLITE_COLD_SHARD_DRAINED_BYTES.inc_by(cold_ref.size_bytes);
+		} else {
+			outcome.shards_skipped += 1;
+			store
+				.delete(&cold_ref.object_key)
+				.await
+				.context("delete uninstalled cold object")?;
+		}
+	}
+
+	if let Some(max_drained_txid) = max_drained_txid {
+		advance_cold_watermark(udb, branch_id, max_drained_txid).await?;
+	}
+
+	Ok(outcome)
+}
+
+/// Picks shard versions whose successor is at or below `drain_floor`. Reads capped at or above the
+/// successor never need them, so only history older than the hot window reaches the cold store.
+fn select_candidates(versions: BTreeMap<u32, Vec<u64>>, drain_floor: u64) -> Vec<DrainCandidate> {
+	let mut candidates = Vec::new();
+	for (shard_id, txids) in versions {
+		for pair in txids.windows(2) {
+			if pair[1] > drain_floor {
+				break;
+			}
+			candidates.push(DrainCandidate {
+				shard_id,
+				as_of_txid: pair[0],
+			});
+			if candidates.len() >= MAX_DRAIN_PER_BRANCH {
+				return candidates;
+			}
+		}
+	}
+
+	candidates
+}
+
+/// Lists `(shard_id, as_of_txid)` for every hot shard version of the branch, in chunks so large
+/// branches do not exceed the transaction limits.
+async fn scan_shard_versions(
+	udb: &universaldb::Database,
+	branch_id: DatabaseBranchId,
+) -> Result<BTreeMap<u32, Vec<u64>>> {
+	let prefix = keys::branch_shard_prefix(branch_id);
+	let (begin, end) = universaldb::tuple::Subspace::from_bytes(prefix.clone()).range();
+
+	let mut versions = BTreeMap::<u32, Vec<u64>>::new();
+	let mut cursor = begin;
+	loop {
+		let rows = udb
+			.txn("depot_cold_scan_shards", |tx| {
+				let cursor = cursor.clone();
+				let end = end.clone();
+				async move {
+					let informal = tx.informal();
+					let mut stream = informal.get_ranges_keyvalues(
+						RangeOption {
+							mode: StreamingMode::WantAll,
+							limit: Some(SCAN_BATCH_ROWS),
+							..(cursor.as_slice(), end.as_slice()).into()
+						},
+						Snapshot,
+					);
+					let mut keys = Vec::new();
+					while let Some(entry) = stream.try_next().await? {
+						keys.push(entry.key().to_vec());
+					}
+
+					Ok(keys)
+				}
+			})
+			.await?;
+
+		let Some(last_key) = rows.last() else {
+			break;
+		};
+		cursor = universaldb::utils::end_of_key_range(last_key);
+
+		for key in &rows {
+			if let Some((shard_id, as_of_txid)) = decode_shard_version_key(&prefix, key) {
+				versions.entry(shard_id).or_default().push(as_of_txid);
+			}
+		}
+
+		if rows.len() < SCAN_BATCH_ROWS {
+			break;
+		}
+	}
+
+	Ok(versions)
+}
+
+fn decode_shard_version_key(prefix: &[u8], key: &[u8]) -> Option<(u32, u64)> {
+	let suffix = key.strip_prefix(prefix)?;
+	if suffix.len() != std::mem::size_of::<u32>() + 1 + std::mem::size_of::<u64>()
+		|| suffix[std::mem::size_of::<u32>()] != b'/'
+	{
+		return None;
+	}
+	let shard_id = u32::from_be_bytes(suffix[..std::mem::size_of::<u32>()].try_into().ok()?);
+	let as_of_txid = u64::from_be_bytes(suffix[std::mem::size_of::<u32>() + 1..].try_into().ok()?);
+
+	Some((shard_id, as_of_txid))
+}
+
+async fn advance_cold_watermark(
+	udb: &universaldb::Database,
+	branch_id: DatabaseBranchId,
+	drained_txid: u64,
+) -> Result<()> {
+	udb.txn("depot_cold_advance_watermark", move |tx| async move {
+		let root_key = keys::branch_compaction_root_key(branch_id);
+		let Some(root) = tx.informal().get(&root_key, Serializable).await? else {
+			return Ok(());
+		};
+		let root = decode_compaction_root(&root).context("decode sqlite compaction root")?;
+		if root.cold_watermark_txid >= drained_txid {
+			return Ok(());
+		}
+
+		// History older than the commit GC floor no longer has a commit row, keep the previous
+		// versionstamp in that case.
+		let versionstamp = match tx
+			.informal()
+			.get(&keys::branch_commit_key(branch_id, drained_txid), Snapshot)
+			.await?
+		{
+			Some(row) => decode_commit_row(&row)?.versionstamp,
+			None => root.cold_watermark_versionstamp,
+		};
+
+		tx.informal().set(
+			&root_key,
+			&encode_compaction_root(CompactionRoot {
+				cold_watermark_txid: drained_txid,
+				cold_watermark_versionstamp: versionstamp,
+				..root
+			})?,
+		);
+
+		Ok(())
+	})
+	.await
+}
+
+/// Lists every database branch with a branch record.
+pub async fn list_branches(udb: &universaldb::Database) -> Result<Vec<DatabaseBranchId>> {
+	let mut branches = Vec::new();
+	let mut cursor = None;
+	loop {
+		let (page, next_cursor) = list_branches_page(udb, cursor).await?;
+		branches.extend(page);
+
+		let Some(next_cursor) = next_cursor else {
+			break;
+		};
+		cursor = Some(next_cursor);
+	}
+
+	Ok(branches)
+}
+
+/// Lists one batch of branches starting at `cursor`, or at the first branch when `None`. Returns
+/// the cursor of the next batch, or `None` after the last one.
+pub async fn list_branches_page(
+	udb: &universaldb::Database,
+	cursor: Option<Vec<u8>>,
+) -> Result<(Vec<DatabaseBranchId>, Option<Vec<u8>>)> {
+	let (begin, end) =
+		universaldb::tuple::Subspace::from_bytes(keys::branches_list_prefix()).range();
+	let cursor = cursor.unwrap_or(begin);
+
+	let rows = udb
+		.txn("depot_cold_list_branches", |tx| {
+			let cursor = cursor.clone();
+			let end = end.clone();
+			async move {
+				let informal = tx.informal();
+				let mut stream = informal.get_ranges_keyvalues(
+					RangeOption {
+						mode: StreamingMode::WantAll,
+						limit: Some(SCAN_BATCH_ROWS),
+						..(cursor.as_slice(), end.as_slice()).into()
+					},
+					Snapshot,
+				);
+				let mut keys = Vec::new();
+				while let Some(entry) = stream.try_next().await? {
+					keys.push(entry.key().to_vec());
+				}
+
+				Ok(keys)
+			}
+		})
+		.await?;
+
+	let branches = rows
+		.iter()
+		.filter_map(|key| keys::decode_branches_list_key(key))
+		.collect();
+	let next_cursor = match rows.last() {
+		Some(last_key) if rows.len() >= SCAN_BATCH_ROWS => {
+			Some(universaldb::utils::end_of_key_range(last_key))
+		}
+		_ => None,
+	};
+
+	Ok((branches, next_cursor))
+}
+
+/// Deletes cold objects of branches that no longer exist. Branch deletion clears the
+/// `/COLD/shard/` index with the rest of the branch, so the objects are unreachable afterwards.
+pub async fn sweep_orphaned_objects(
+	udb: &universaldb::Database,
+	store: &dyn ColdStore,
+) -> Result<usize> {
+	let mut deleted = 0;
+	let mut start_after = None;
+	loop {
+		let (page_deleted, next_start_after) =
+			sweep_orphaned_objects_page(udb, store, start_after).await?;
+		deleted += page_deleted;
+
+		let Some(next_start_after) = next_start_after else {
+			break;
+		};
+		start_after = Some(next_start_after);
+	}
+
+	Ok(deleted)
+}
+
+/// Sweeps one batch of objects listed after `start_after`, or from the first object when `None`.
+/// Returns the number of deleted objects and the key to continue after, or `None` after the last
+/// batch.
+pub async fn sweep_orphaned_objects_page(
+	udb: &universaldb::Database,
+	store: &dyn ColdStore,
+	start_after: Option<String>,
+) -> Result<(usize, Option<String>)> {
+	let object_keys = store
+		.list_page("", start_after.as_deref(), SWEEP_BATCH_OBJECTS)
+		.await
+		.context("list cold objects")?;
+	let next_start_after = if object_keys.len() >= SWEEP_BATCH_OBJECTS {
+		object_keys.last().cloned()
+	} else {
+		None
+	};
+
+	let mut objects_by_branch = BTreeMap::<DatabaseBranchId, Vec<String>>::new();
+	for key in object_keys {
+		if let Some(branch_id) = decode_object_branch_id(&key) {
+			objects_by_branch.entry(branch_id).or_default().push(key);
+		}
+	}
+
+	let branch_ids = objects_by_branch.keys().copied().collect::<Vec<_>>();
+	let live_branches = udb
+		.txn("depot_cold_read_live_branches", |tx| {
+			let branch_ids = branch_ids.clone();
+			async move {
+				let tx = &tx;
+				let records = try_join_all(branch_ids.iter().map(|branch_id| async move {
+					tx.informal()
+						.get(&keys::branches_list_key(*branch_id), Snapshot)
+						.await
+				}))
+				.await?;
+
+				Ok(branch_ids
+					.into_iter()
+					.zip(records)
+					.filter_map(|(branch_id, record)| record.map(|_| branch_id))
+					.collect::<BTreeSet<_>>())
+			}
+		})
+		.await?;
+
+	let mut deleted = 0;
+	for (branch_id, object_keys) in objects_by_branch {
+		if live_branches.contains(&branch_id) {
+			continue;
+		}
+		for key in object_keys {
+			store.delete(&key).await?;
+			deleted += 1;
+		}
+	}
+
+	Ok((deleted, next_start_after))
+}
+
+/// Deletes the cold objects a truncate queued after clearing their index rows.
+pub async fn delete_queued_objects(
+	udb: &universaldb::Database,
+	store: &dyn ColdStore,
+) -> Result<usize> {
+	let (begin, end) =
+		universaldb::tuple::Subspace::from_bytes(keys::cold_object_delete_prefix()).range();
+
+	let mut deleted = 0;
+	loop {
+		// Processed rows are cleared, so every batch starts at the front of the queue.
+		let rows = udb
+			.txn("depot_cold_read_delete_queue", |tx| {
+				let begin = begin.clone();
+				let end = end.clone();
+				async move {
+					let informal = tx.informal();
+					let mut stream = informal.get_ranges_keyvalues(
+						RangeOption {
+							mode: StreamingMode::WantAll,
+							limit: Some(SCAN_BATCH_ROWS),
+							..(begin.as_slice(), end.as_slice()).into()
+						},
+						Snapshot,
+					);
+					let mut keys = Vec::new();
+					while let Some(entry) = stream.try_next().await? {
+						keys.push(entry.key().to_vec());
+					}
+
+					Ok(keys)
+				}
+			})
+			.await?;
+		if rows.is_empty() {
+			break;
+		}
+
+		for key in &rows {
+			store
+				.delete(&keys::decode_cold_object_delete_key(key)?)
+				.await
+				.context("delete queued cold object")?;
+		}
+		udb.txn("depot_cold_clear_delete_queue", |tx| {
+			let rows = rows.clone();
+			async move {
+				for key in &rows {
+					tx.informal().clear(key);
+				}
+
+				Ok(())
+			}
+		})
+		.await?;
+		deleted += rows.len();
+
+		if rows.len() < SCAN_BATCH_ROWS {
+			break;
+		}
+	}
+
+	Ok(deleted)
+}
+
+/// Fetches an offloaded shard blob and verifies it against its index row.
+pub(crate) async fn fetch_shard(
+	store: Option<&dyn ColdStore>,
+	shard_id: u32,
+	as_of_txid: u64,
+	cold_ref: &ColdShardRef,
+) -> Result<Vec<u8>> {
+	let unavailable = |reason: String| SqliteStorageError::ColdShardUnavailable {
+		shard_id,
+		as_of_txid,
+		reason,
+	};
+
+	let Some(store) = store else {
+		metrics::SQLITE_COLD_SHARD_READ_TOTAL
+			.with_label_values(&["not_configured"])
+			.inc();
+		return Err(unavailable("cold tier is not configured".to_string()).into());
+	};
+	let Some(blob) = store.get(&cold_ref.object_key).await? else {
+		metrics::SQLITE_COLD_SHARD_READ_TOTAL
+			.with_label_values(&["missing"])
+			.inc();
+		return Err(unavailable(format!("object {} is missing", cold_ref.object_key)).into());
+	};
+	if u64::try_from(blob.len()).unwrap_or(u64::MAX) != cold_ref.size_bytes
+		|| content_hash(&blob) != cold_ref.content_hash
+	{
+		metrics::SQLITE_COLD_SHARD_READ_TOTAL
+			.with_label_values(&["checksum_mismatch"])
+			.inc();
+		return Err(unavailable(format!(
+			"object {} failed checksum verification",
+			cold_ref.object_key
+		))
+		.into());
+	}
+
+	metrics::SQLITE_COLD_SHARD_READ_TOTAL
+		.with_label_values(&["ok"])
+		.inc();
+
+	Ok(blob)
+}
+
+pub(crate) fn content_hash(bytes: &[u8]) -> [u8; 32] {
+	let digest = Sha256::digest(bytes);
+	let mut hash = [0_u8; 32];
+	hash.copy_from_slice(&digest);
+	hash
+}
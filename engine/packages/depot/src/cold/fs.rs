@@ -0,0 +1,133 @@
+use std::{
+	io::ErrorKind,
+	path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use super::ColdStore;
+
+/// Stores cold objects as files under a root directory.
+pub struct FileSystemColdStore {
+	root: PathBuf,
+}
+
+impl FileSystemColdStore {
+	pub fn new(root: PathBuf) -> Self {
+		FileSystemColdStore { root }
+	}
+
+	fn path(&self, key: &str) -> PathBuf {
+		self.root.join(key)
+	}
+}
+
+#[async_trait]
+impl ColdStore for FileSystemColdStore {
+	async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+		let path = self.path(key);
+		let parent = path.parent().context("cold object path has no parent")?;
+		tokio::fs::create_dir_all(parent)
+			.await
+			.with_context(|| format!("create cold object dir {}", parent.display()))?;
+
+		// Write to a temp file and rename so readers never observe a partial object.
+		let tmp_path = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+		tokio::fs::write(&tmp_path, bytes)
+			.await
+			.with_context(|| format!("write cold object {}", tmp_path.display()))?;
+		tokio::fs::rename(&tmp_path, &path)
+			.await
+			.with_context(|| format!("rename cold object to {}", path.display()))?;
+
+		Ok(())
+	}
+
+	async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+		let path = self.path(key);
+		match tokio::fs::read(&path).await {
+			Ok(bytes) => Ok(Some(bytes)),
+			Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+			Err(err) => Err(err).with_context(|| format!("read cold object {}", path.display())),
+		}
+	}
+
+	async fn delete(&self, key: &str) -> Result<()> {
+		let path = self.path(key);
+		match tokio::fs::remove_file(&path).await {
+			Ok(()) => Ok(()),
+			Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+			Err(err) => Err(err).with_context(|| format!("delete cold object {}", path.display())),
+		}
+	}
+
+	async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+		let root = self.root.clone();
+		let prefix = prefix.to_string();
+		tokio::task::spawn_blocking(move || list_blocking(&root, &prefix))
+			.await
+			.context("join cold object list task")?
+	}
+
+	async fn list_page(
+		&self,
+		prefix: &str,
+		start_after: Option<&str>,
+		limit: usize,
+	) -> Result<Vec<String>> {
+		// The directory walk has no cursor, so every page walks the whole prefix. Fine for the
+		// single-node deployments this store is meant for.
+		let keys = self.list(prefix).await?;
+		Ok(keys
+			.into_iter()
+			.filter(|key| start_after.is_none_or(|start_after| key.as_str() > start_after))
+			.take(limit)
+			.collect())
+	}
+}
+
+fn list_blocking(root: &Path, prefix: &str) -> Result<Vec<String>> {
+	// Only walk the deepest directory fully named by the prefix.
+	let start_dir = match prefix.rfind('/') {
+		Some(idx) => root.join(&prefix[..idx]),
+		None => root.to_path_buf(),
+	};
+
+	let mut keys = Vec::new();
+	let mut stack = vec![start_dir];
+	while let Some(dir) = stack.pop() {
+		let entries = match std::fs::read_dir(&dir) {
+			Ok(entries) => entries,
+			Err(err) if err.kind() == ErrorKind::NotFound => continue,
+			Err(err) => {
+				return Err(err).with_context(|| format!("list cold dir {}", dir.display()));
+			}
+		};
+
+		for entry in entries {
+			let entry = entry?;
+			let path = entry.path();
+			if entry.file_type()?.is_dir() {
+				stack.push(path);
+				continue;
+			}
+
+			let Ok(relative) = path.strip_prefix(root) else {
+				continue;
+			};
+			let key = relative
+				.components()
+				.map(|component| component.as_os_str().to_string_lossy())
+				.collect::<Vec<_>>()
+				.join("/");
+			// Skip in-flight temp files from `put`.
+			if key.starts_with(prefix) && !key.contains(".tmp-") {
+				keys.push(key);
+			}
+		}
+	}
+
+	keys.sort();
+	Ok(keys)
+}
@@ -0,0 +1,89 @@
+//! Cold tier for compacted depot history.
+//!
+//! Shard versions that were superseded more than the configured hot window ago are only needed by
+//! historical reads (forks, restore points, PITR). The cold drainer moves them out of UniversalDB
+//! into an object store and leaves a `/COLD/shard/` index row behind. Reads consult that index
+//! with the same txid cap as `/SHARD/` and fetch the blob from the store on demand.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rivet_config::config::SqliteColdStore;
+use tokio::sync::OnceCell;
+
+use crate::conveyer::types::DatabaseBranchId;
+
+pub mod drain;
+mod fs;
+mod s3;
+
+pub use fs::FileSystemColdStore;
+pub use s3::S3ColdStore;
+
+/// Object store backing the cold tier. Keys are `/`-separated relative paths.
+#[async_trait]
+pub trait ColdStore: Send + Sync {
+	async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()>;
+
+	/// Returns `None` if the object does not exist.
+	async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+	/// Deleting a missing object is not an error.
+	async fn delete(&self, key: &str) -> Result<()>;
+
+	/// Lists every key starting with `prefix`.
+	async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+
+	/// Lists up to `limit` keys starting with `prefix` that sort after `start_after`, in order.
+	async fn list_page(
+		&self,
+		prefix: &str,
+		start_after: Option<&str>,
+		limit: usize,
+	) -> Result<Vec<String>>;
+}
+
+/// Builds the configured cold store. Returns `None` when the cold tier is disabled.
+pub async fn from_config(config: &rivet_config::Config) -> Result<Option<Arc<dyn ColdStore>>> {
+	let Some(cold_tier) = &config.sqlite().cold_tier else {
+		return Ok(None);
+	};
+
+	let store: Arc<dyn ColdStore> = match &cold_tier.store {
+		SqliteColdStore::FileSystem(fs) => Arc::new(FileSystemColdStore::new(fs.path.clone())),
+		SqliteColdStore::S3(s3) => Arc::new(S3ColdStore::new(s3).await?),
+	};
+
+	Ok(Some(store))
+}
+
+/// Process-wide cold store built from the config on first use. Connection handlers call this
+/// instead of [`from_config`] so the S3 client and its credential cache are shared.
+pub async fn shared(config: &rivet_config::Config) -> Result<Option<Arc<dyn ColdStore>>> {
+	static SHARED: OnceCell<Option<Arc<dyn ColdStore>>> = OnceCell::const_new();
+
+	SHARED
+		.get_or_try_init(|| from_config(config))
+		.await
+		.cloned()
+}
+
+pub fn branch_object_prefix(branch_id: DatabaseBranchId) -> String {
+	format!("{}/", branch_id.as_uuid())
+}
+
+pub fn shard_object_key(branch_id: DatabaseBranchId, shard_id: u32, as_of_txid: u64) -> String {
+	format!(
+		"{}shard/{shard_id:08x}/{as_of_txid:016x}",
+		branch_object_prefix(branch_id)
+	)
+}
+
+/// Parses the branch id out of an object key written by [`shard_object_key`].
+pub fn decode_object_branch_id(key: &str) -> Option<DatabaseBranchId> {
+	let (branch, _) = key.split_once('/')?;
+	uuid::Uuid::parse_str(branch)
+		.ok()
+		.map(DatabaseBranchId::from_uuid)
+}
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::{
+	config::{Credentials, Region},
+	error::SdkError,
+	operation::get_object::GetObjectError,
+	primitives::ByteStream,
+};
+use rivet_config::config::SqliteColdStoreS3;
+
+use super::ColdStore;
+
+/// Region used when neither the config nor the environment provides one. MinIO and most other
+/// S3-compatible stores accept any region.
+const FALLBACK_REGION: &str = "us-east-1";
+
+/// Stores cold objects in an S3-compatible bucket.
+pub struct S3ColdStore {
+	client: aws_sdk_s3::Client,
+	bucket: String,
+	prefix: String,
+}
+
+impl S3ColdStore {
+	pub async fn new(config: &SqliteColdStoreS3) -> Result<Self> {
+		let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+		if let Some(region) = &config.region {
+			loader = loader.region(Region::new(region.clone()));
+		}
+		if let (Some(access_key_id), Some(secret_access_key)) =
+			(&config.access_key_id, &config.secret_access_key)
+		{
+			loader = loader.credentials_provider(Credentials::new(
+				access_key_id.read().clone(),
+				secret_access_key.read().clone(),
+				None,
+				None,
+				"rivet-config",
+			));
+		}
+		let sdk_config = loader.load().await;
+
+		let mut builder = aws_sdk_s3::config::Builder::from(&sdk_config)
+			.force_path_style(config.force_path_style());
+		if sdk_config.region().is_none() {
+			builder = builder.region(Region::from_static(FALLBACK_REGION));
+		}
+		if let Some(endpoint) = &config.endpoint {
+			builder = builder.endpoint_url(endpoint);
+		}
+
+		Ok(S3ColdStore {
+			client: aws_sdk_s3::Client::from_conf(builder.build()),
+			bucket: config.bucket.clone(),
+			prefix: config.prefix.clone().unwrap_or_default(),
+		})
+	}
+
+	fn object_key(&self, key: &str) -> String {
+		format!("{}{key}", self.prefix)
+	}
+}
+
+#[async_trait]
+impl ColdStore for S3ColdStore {
+	async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+		self.client
+			.put_object()
+			.bucket(&self.bucket)
+			.key(self.object_key(key))
+			.body(ByteStream::from(bytes))
+			.send()
+			.await
+			.with_context(|| format!("put cold object {key}"))?;
+
+		Ok(())
+	}
+
+	async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+		let res = self
+			.client
+			.get_object()
+			.bucket(&self.bucket)
+			.key(self.object_key(key))
+			.send()
+			.await;
+
+		match res {
+			Ok(output) => {
+				let body = output
+					.body
+					.collect()
+					.await
+					.with_context(|| format!("read cold object body {key}"))?;
+				Ok(Some(body.into_bytes().to_vec()))
+			}
+			Err(err) if is_s3_no_such_key_error(&err) => Ok(None),
+			Err(err) => Err(err).with_context(|| format!("get cold object {key}")),
+		}
+	}
+
+	async fn delete(&self, key: &str) -> Result<()> {
+		// S3 delete is idempotent, missing keys succeed.
+		self.client
+			.delete_object()
+			.bucket(&self.bucket)
+			.key(self.object_key(key))
+			.send()
+			.await
+			.with_context(|| format!("delete cold object {key}"))?;
+
+		Ok(())
+	}
+
+	async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+		let mut keys = Vec::new();
+		let mut continuation_token = None;
+		loop {
+			let output = self
+				.client
+				.list_objects_v2()
+				.bucket(&self.bucket)
+				.prefix(self.object_key(prefix))
+				.set_continuation_token(continuation_token)
+				.send()
+				.await
+				.with_context(|| format!("list cold objects under {prefix}"))?;
+
+			for object in output.contents() {
+				if let Some(key) = object.key().and_then(|key| key.strip_prefix(&self.prefix)) {
+					keys.push(key.to_string());
+				}
+			}
+
+			match output.next_continuation_token() {
+				Some(token) if output.is_truncated() == Some(true) => {
+					continuation_token = Some(token.to_string());
+				}
+				_ => break,
+			}
+		}
+
+		Ok(keys)
+	}
+
+	async fn list_page(
+		&self,
+		prefix: &str,
+		start_after: Option<&str>,
+		limit: usize,
+	) -> Result<Vec<String>> {
+		let output = self
+			.client
+			.list_objects_v2()
+			.bucket(&self.bucket)
+			.prefix(self.object_key(prefix))
+			.set_start_after(start_after.map(|key| self.object_key(key)))
+			.max_keys(i32::try_from(limit).unwrap_or(i32::MAX))
+			.send()
+			.await
+			.with_context(|| format!("list cold objects under {prefix}"))?;
+
+		Ok(output
+			.contents()
+			.iter()
+			.filter_map(|object| object.key()?.strip_prefix(&self.prefix))
+			.map(str::to_string)
+			.collect())
+	}
+}
+
+/// A missing object is reported as a typed `NoSuchKey` service error.
+pub(super) fn is_s3_no_such_key_error<R>(err: &SdkError<GetObjectError, R>) -> bool {
+	err.as_service_error()
+		.is_some_and(GetObjectError::is_no_such_key)
+}
+
+#[cfg(test)]
+#[path = "../../tests/inline/cold_tier.rs"]
+mod tests;
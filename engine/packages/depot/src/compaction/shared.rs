@@ -1102,6 +1102,10 @@ pub(crate) async fn write_staged_hot_shards(
 	hot_inputs: &HotInputSnapshot,
 ) -> Result<Vec<HotShardOutputRef>> {
 	let deltas = decode_hot_delta_chunks(input.database_branch_id, &hot_inputs.delta_chunks)?;
+	// Staged shards are sealed like the newest input delta, so they pick up the bucket's current
+	// data key as compaction folds deltas in.
+	let seal_template =
+		newest_delta_first_chunk(input.database_branch_id, &hot_inputs.delta_chunks)?;
 	let selected_db_size_pages = hot_inputs
 		.commits
 		.iter()
@@ -1124,6 +1128,11 @@ pub(crate) async fn write_staged_hot_shards(
 				page_updates,
 			)
 			.await?;
+			let encoded = match seal_template {
+				Some(template) => encryption::reseal_like(template, encoded)
+					.context("encrypt staged hot shard blob")?,
+				None => encoded,
+			};
 			let key = keys::branch_compaction_stage_hot_shard_key(
 				input.database_branch_id,
 				input.job_id,
@@ -1174,6 +1183,24 @@ pub(crate) fn decode_hot_delta_chunks(
 		.collect()
 }
 
+fn newest_delta_first_chunk(
+	branch_id: DatabaseBranchId,
+	delta_chunks: &[(Vec<u8>, Vec<u8>)],
+) -> Result<Option<&[u8]>> {
+	let mut newest = None;
+	for (key, value) in delta_chunks {
+		let txid = keys::decode_branch_delta_chunk_txid(branch_id, key)?;
+		if keys::decode_branch_delta_chunk_idx(branch_id, txid, key)? != 0 {
+			continue;
+		}
+		if newest.is_none_or(|(newest_txid, _)| txid > newest_txid) {
+			newest = Some((txid, value.as_slice()));
+		}
+	}
+
+	Ok(newest.map(|(_, value)| value))
+}
+
 pub(crate) fn collect_hot_pages_by_shard(
 	db_size_pages: u32,
 	deltas: &BTreeMap<u64, DecodedLtx>,
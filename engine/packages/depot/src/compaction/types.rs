@@ -31,6 +31,7 @@ pub(crate) use crate::{
 		},
 		udb,
 	},
+	encryption,
 };
 
 pub const DATABASE_BRANCH_ID_TAG: &str = "database_branch_id";
@@ -1,14 +1,18 @@
 mod catalog;
 mod fork;
 mod lifecycle;
+mod lineage;
 mod resolve;
 mod shared;
 
 pub use catalog::list_databases;
 pub(crate) use catalog::{write_bucket_catalog_marker, write_bucket_catalog_marker_with_root};
-pub use fork::{derive_branch_at, derive_bucket_branch_at, fork_bucket, fork_database};
+pub use fork::{
+	derive_branch_at, derive_bucket_branch_at, fork_bucket, fork_database, fork_database_into,
+};
 pub(crate) use lifecycle::rollback_database_to_target_tx;
 pub use lifecycle::{delete_database, rollback_bucket, rollback_database};
+pub use lineage::database_lineage;
 pub use resolve::{
 	BucketBranchResolution, resolve_bucket_branch, resolve_database_branch,
 	resolve_database_branch_in_bucket, resolve_database_pointer,
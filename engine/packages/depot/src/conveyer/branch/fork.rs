@@ -3,7 +3,7 @@ use universaldb::{options::MutationType, utils::IsolationLevel::Serializable};
 
 use super::{
 	catalog::{write_bucket_catalog_marker, write_bucket_fork_facts},
-	resolve::{resolve_bucket_branch, resolve_database_branch_in_bucket},
+	resolve::{resolve_bucket_branch, resolve_database_branch_in_bucket, resolve_database_pointer},
 	shared::{
 		lookup_txid_at_versionstamp, now_ms, read_bucket_branch_record, read_commit_row,
 		read_database_branch_record, read_versionstamp_pin,
@@ -53,6 +53,32 @@ where
 	T: Into<DatabaseForkTarget>,
 {
 	let new_database_id = format!("fork-{}", uuid::Uuid::new_v4().simple());
+	fork_database_into(
+		udb,
+		source_bucket,
+		source_database_id,
+		target,
+		target_bucket,
+		new_database_id.clone(),
+	)
+	.await?;
+
+	Ok(new_database_id)
+}
+
+/// Forks a database under a caller-chosen id. Fails with `DatabaseAlreadyExists` if the target
+/// bucket already resolves a database with that id.
+pub async fn fork_database_into<T>(
+	udb: &universaldb::Database,
+	source_bucket: BucketId,
+	source_database_id: String,
+	target: T,
+	target_bucket: BucketId,
+	new_database_id: String,
+) -> Result<DatabaseBranchId>
+where
+	T: Into<DatabaseForkTarget>,
+{
 	let new_database_branch_id = DatabaseBranchId::new_v4();
 	let target = match target.into() {
 		DatabaseForkTarget::Resolved(at) => ResolvedForkTarget::CurrentSourceBranch(at),
@@ -83,6 +109,17 @@ where
 				let target_bucket_branch = resolve_bucket_branch(&tx, target_bucket, Serializable)
 					.await?
 					.ok_or(SqliteStorageError::DatabaseNotFound)?;
+				if resolve_database_pointer(
+					&tx,
+					target_bucket_branch,
+					&new_database_id,
+					Serializable,
+				)
+				.await?
+				.is_some()
+				{
+					return Err(SqliteStorageError::DatabaseAlreadyExists.into());
+				}
 				let (source_database_branch, at_versionstamp, restore_point) = match target {
 					ResolvedForkTarget::CurrentSourceBranch(at) => {
 						let source_database_branch = resolve_database_branch_in_bucket(
@@ -136,7 +173,7 @@ where
 	})
 	.await?;
 
-	Ok(new_database_id)
+	Ok(new_database_branch_id)
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use universaldb::utils::IsolationLevel::Snapshot;
+
+use super::{resolve::resolve_database_branch, shared::read_database_branch_record};
+use crate::conveyer::{
+	constants::MAX_FORK_DEPTH,
+	error::SqliteStorageError,
+	types::{BucketId, DatabaseBranchRecord},
+};
+
+/// Returns the branch records from the database's current branch up to its root, nearest first.
+pub async fn database_lineage(
+	udb: &universaldb::Database,
+	bucket: BucketId,
+	database_id: String,
+) -> Result<Vec<DatabaseBranchRecord>> {
+	udb.txn("depot_database_lineage", move |tx| {
+		let database_id = database_id.clone();
+
+		async move {
+			let mut branch_id = resolve_database_branch(&tx, bucket, &database_id, Snapshot)
+				.await?
+				.ok_or(SqliteStorageError::DatabaseNotFound)?;
+			let mut lineage = Vec::new();
+
+			for _ in 0..=MAX_FORK_DEPTH {
+				let record = read_database_branch_record(&tx, branch_id).await?;
+				let parent = record.parent;
+				lineage.push(record);
+
+				match parent {
+					Some(parent) => branch_id = parent,
+					None => return Ok(lineage),
+				}
+			}
+
+			Err(SqliteStorageError::ForkChainTooDeep.into())
+		}
+	})
+	.await
+}
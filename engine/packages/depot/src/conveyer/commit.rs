@@ -12,4 +12,5 @@ pub mod test_hooks;
 #[cfg(not(debug_assertions))]
 mod test_hooks;
 
+pub(crate) use apply::DELTA_CHUNK_BYTES;
 pub use dirty::clear_sqlite_cmp_dirty_if_observed_idle;
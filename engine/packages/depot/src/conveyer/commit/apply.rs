@@ -22,13 +22,15 @@ use crate::{
 		metrics,
 		page_index::DeltaPageIndex,
 		quota,
+		row_changes::PreparedRowChanges,
 		types::{
 			BranchState, CommitOptions, CommitResult, CommitRow, DBHead, DatabaseBranchId,
-			DirtyPage, decode_compaction_root, decode_database_branch_record, decode_db_head,
-			encode_commit_row, encode_db_head,
+			DirtyPage, RowChangeLogAppend, SqliteImportCommit, decode_compaction_root,
+			decode_database_branch_record, decode_db_head, encode_commit_row, encode_db_head,
 		},
 		udb,
 	},
+	encryption,
 	workflows::compaction::DeltasAvailable,
 };
 
@@ -40,7 +42,7 @@ use super::{
 	truncate::{collect_truncate_cleanup, fence_truncate_cleanup_row},
 };
 
-const DELTA_CHUNK_BYTES: usize = 10_000;
+pub(crate) const DELTA_CHUNK_BYTES: usize = 10_000;
 
 impl Db {
 	pub async fn commit(
@@ -60,6 +62,38 @@ impl Db {
 		db_size_pages: u32,
 		now_ms: i64,
 		options: CommitOptions,
+	) -> Result<CommitResult> {
+		self.commit_pages(dirty_pages, db_size_pages, now_ms, options, None)
+			.await
+	}
+
+	/// Commits like [`Self::commit_with_options`] and writes the row change log of the commit in
+	/// the same transaction.
+	pub async fn commit_with_row_changes(
+		&self,
+		dirty_pages: Vec<DirtyPage>,
+		db_size_pages: u32,
+		now_ms: i64,
+		options: CommitOptions,
+		row_changes: RowChangeLogAppend,
+	) -> Result<CommitResult> {
+		self.commit_pages(
+			dirty_pages,
+			db_size_pages,
+			now_ms,
+			options,
+			Some(row_changes),
+		)
+		.await
+	}
+
+	async fn commit_pages(
+		&self,
+		dirty_pages: Vec<DirtyPage>,
+		db_size_pages: u32,
+		now_ms: i64,
+		options: CommitOptions,
+		row_changes: Option<RowChangeLogAppend>,
 	) -> Result<CommitResult> {
 		validate_dirty_pages(&dirty_pages, options.disable_size_cap)?;
 		#[cfg(feature = "test-faults")]
@@ -107,7 +141,22 @@ impl Db {
 		let bucket_id = self.sqlite_bucket_id();
 		let dirty_pages_for_tx = dirty_pages.clone();
 		let expected_head_txid = options.expected_head_txid;
+		let sqlite_import = options.sqlite_import;
+		let namespace_id = (!options.skip_namespace_quota).then_some(self.bucket_id);
 		let phase_node_id = node_id.clone();
+		let data_key = match encryption::installed() {
+			Some(keyring) => Some(
+				keyring
+					.current_data_key(&self.udb, bucket_id)
+					.await
+					.context("load sqlite data key")?,
+			),
+			None => None,
+		};
+		let row_changes = row_changes
+			.map(|log| PreparedRowChanges::prepare(log, data_key.as_deref()))
+			.transpose()?
+			.map(Arc::new);
 		#[cfg(feature = "test-faults")]
 		let fault_controller = self.fault_controller.clone();
 
@@ -119,10 +168,14 @@ impl Db {
 				let bucket_id = bucket_id;
 				let dirty_pages = dirty_pages_for_tx.clone();
 				let expected_head_txid = expected_head_txid;
+				let sqlite_import = sqlite_import;
+				let namespace_id = namespace_id;
 				let cached_ancestry = cached_ancestry.clone();
 				let cached_access_bucket = cached_access_bucket;
 				let compaction_enabled = compaction_enabled;
 				let last_deltas_available_at_ms = last_deltas_available_at_ms;
+				let data_key = data_key.clone();
+				let row_changes = row_changes.clone();
 				#[cfg(feature = "test-faults")]
 				let fault_controller = fault_controller.clone();
 
@@ -274,6 +327,12 @@ impl Db {
 					let encoded_delta =
 						encode_ltx_v3(LtxHeader::delta(txid, db_size_pages, now_ms), &dirty_pages)
 							.context("encode commit delta")?;
+					let encoded_delta = match &data_key {
+						Some(data_key) => data_key
+							.seal(&encoded_delta)
+							.context("encrypt commit delta")?,
+						None => encoded_delta,
+					};
 					let delta_chunks = encoded_delta
 						.chunks(DELTA_CHUNK_BYTES)
 						.enumerate()
@@ -386,6 +445,12 @@ impl Db {
 						burst_signal,
 					)?;
 					quota::cap_check_with_cap(would_be, hot_quota_cap)?;
+					let soft_limit_exceeded = match namespace_id {
+						Some(namespace_id) if quota_delta > 0 => {
+							quota::namespace_cap_check(&tx, namespace_id, quota_delta).await?
+						}
+						_ => None,
+					};
 
 					#[cfg(feature = "test-faults")]
 					maybe_fire_commit_fault(
@@ -430,6 +495,14 @@ impl Db {
 						fence_truncate_cleanup_row(&tx, row).await?;
 						tx.informal().set(&row.key, value);
 					}
+					for row in &truncate_cleanup.cold_shard_clears {
+						fence_truncate_cleanup_row(&tx, row).await?;
+						tx.informal().clear(&row.key);
+					}
+					for object_key in &truncate_cleanup.cold_object_deletes {
+						tx.informal()
+							.set(&keys::cold_object_delete_key(object_key), &[]);
+					}
 					metrics::observe_commit_phase(
 						&phase_node_id,
 						"write_page_index",
@@ -449,6 +522,20 @@ impl Db {
 					if head_at_fork_bytes.is_some() {
 						tx.informal().clear(&head_at_fork_key);
 					}
+					// Row changes are not counted against the database quota.
+					if let Some(row_changes) = &row_changes {
+						row_changes.write(&tx, branch_id, txid)?;
+					}
+					match sqlite_import {
+						Some(SqliteImportCommit::Partial) => {
+							tx.informal()
+								.set(&keys::branch_meta_import_key(branch_id), &txid_bytes);
+						}
+						Some(SqliteImportCommit::Final) => {
+							tx.informal().clear(&keys::branch_meta_import_key(branch_id));
+						}
+						None => {}
+					}
 					if branch_resolution.bucket_initialized {
 						branch::write_root_bucket_metadata(
 							&tx,
@@ -523,6 +610,7 @@ impl Db {
 						truncated_pgnos: truncate_cleanup.truncated_pgnos,
 						added_bytes,
 						storage_used: would_be,
+						soft_limit_exceeded,
 					})
 				}
 			})
@@ -536,6 +624,19 @@ impl Db {
 		)
 		.await?;
 
+		if let Some(exceeded) = result.soft_limit_exceeded {
+			metrics::SQLITE_NAMESPACE_SOFT_LIMIT_EXCEEDED_TOTAL.inc();
+			if exceeded.crossed {
+				tracing::warn!(
+					namespace_id = %self.bucket_id,
+					database_id = %self.database_id,
+					would_be_bytes = exceeded.would_be_bytes,
+					limit_bytes = exceeded.limit_bytes,
+					"sqlite commit crossed the namespace database soft limit"
+				);
+			}
+		}
+
 		let phase_start = Instant::now();
 		*self.storage_used.write().await = Some(result.storage_used);
 		self.commit_bytes_since_rollup.fetch_add(
@@ -713,4 +814,5 @@ struct CommitTxResult {
 	truncated_pgnos: Vec<u32>,
 	added_bytes: i64,
 	storage_used: i64,
+	soft_limit_exceeded: Option<quota::SoftLimitExceeded>,
 }
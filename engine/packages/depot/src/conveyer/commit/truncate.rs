@@ -1,10 +1,13 @@
 use anyhow::{Context, Result};
 use universaldb::{error::DatabaseError, utils::IsolationLevel::Serializable};
 
-use crate::conveyer::{
-	keys::{self, SHARD_SIZE},
-	ltx::{decode_ltx_v3, encode_ltx_v3},
-	types::DatabaseBranchId,
+use crate::{
+	conveyer::{
+		keys::{self, SHARD_SIZE},
+		ltx::{decode_ltx_v3, encode_ltx_v3},
+		types::{DatabaseBranchId, decode_cold_shard_ref},
+	},
+	encryption,
 };
 
 use super::helpers::{
@@ -17,6 +20,9 @@ pub(super) struct TruncateCleanup {
 	pub(super) pidx_clears: Vec<ObservedCleanupRow>,
 	pub(super) shard_clears: Vec<ObservedCleanupRow>,
 	pub(super) shard_writes: Vec<(ObservedCleanupRow, Vec<u8>)>,
+	pub(super) cold_shard_clears: Vec<ObservedCleanupRow>,
+	/// Cold objects behind `cold_shard_clears`, queued for deletion by the cold drainer.
+	pub(super) cold_object_deletes: Vec<String>,
 	pub(super) truncated_pgnos: Vec<u32>,
 	pub(super) added_bytes: i64,
 	pub(super) deleted_bytes: i64,
@@ -48,6 +54,7 @@ pub(super) async fn collect_truncate_cleanup(
 	}
 
 	let boundary_shard_id = new_db_size_pages / SHARD_SIZE;
+	let mut boundary_shard_kept = false;
 	for (key, value) in tx_scan_prefix_values(tx, &keys::branch_shard_prefix(branch_id)).await? {
 		let shard_id = decode_branch_shard_id(branch_id, &key)?;
 		if shard_id > boundary_shard_id {
@@ -60,15 +67,35 @@ pub(super) async fn collect_truncate_cleanup(
 				cleanup.deleted_bytes += tracked_entry_size(&key, &value)?;
 				let observed = ObservedCleanupRow { key, value };
 				if !pruned_value.is_empty() {
+					boundary_shard_kept = true;
 					cleanup.added_bytes += tracked_entry_size(&observed.key, &pruned_value)?;
 					cleanup.shard_writes.push((observed, pruned_value));
 				} else {
 					cleanup.shard_clears.push(observed);
 				}
+			} else {
+				boundary_shard_kept = true;
 			}
 		}
 	}
 
+	// Offloaded versions cannot be pruned in place, and a read falls back to them once no hot
+	// version of the shard is left. The drainer only offloads the oldest versions of a shard, so
+	// those of the boundary shard stay shadowed as long as one of its hot versions is kept.
+	for (key, value) in
+		tx_scan_prefix_values(tx, &keys::branch_cold_shard_prefix(branch_id)).await?
+	{
+		let (shard_id, _) = keys::decode_branch_cold_shard_key(branch_id, &key)?;
+		if shard_id > boundary_shard_id || (shard_id == boundary_shard_id && !boundary_shard_kept) {
+			let cold_ref =
+				decode_cold_shard_ref(&value).context("decode truncated sqlite cold shard ref")?;
+			cleanup.cold_object_deletes.push(cold_ref.object_key);
+			cleanup
+				.cold_shard_clears
+				.push(ObservedCleanupRow { key, value });
+		}
+	}
+
 	Ok(cleanup)
 }
 
@@ -99,7 +126,9 @@ fn prune_truncated_shard_value(value: &[u8], new_db_size_pages: u32) -> Result<O
 		return Ok(Some(Vec::new()));
 	}
 
-	encode_ltx_v3(decoded.header, &live_pages)
-		.context("encode pruned sqlite boundary shard")
+	let encoded = encode_ltx_v3(decoded.header, &live_pages)
+		.context("encode pruned sqlite boundary shard")?;
+	encryption::reseal_like(value, encoded)
+		.context("encrypt pruned sqlite boundary shard")
 		.map(Some)
 }
@@ -49,3 +49,13 @@ pub const CMP_ACTIVITY_TARGET_MS: i64 = 30 * 1000;
 
 /// DB manager schedules its next reclaim/GC check this far in the future after arming.
 pub const MANAGER_RECLAIM_INTERVAL_MS: i64 = 10 * 60 * 1000;
+
+/// Row changes logged with one commit. Clients only keep this many changes for resuming, so a
+/// larger commit logs its last changes and moves the log floor past the rest.
+pub const MAX_ROW_CHANGE_LOG_ENTRIES_PER_COMMIT: usize = 1024;
+
+/// Encoded size of one logged row change, below the FDB value limit after sealing.
+pub const MAX_ROW_CHANGE_LOG_ENTRY_BYTES: usize = 64 * 1024;
+
+/// Encoded size of all row changes logged with one commit.
+pub const MAX_ROW_CHANGE_LOG_BYTES_PER_COMMIT: usize = 1024 * 1024;
@@ -10,6 +10,7 @@ use rivet_pools::NodeId;
 use tokio::sync::RwLock;
 use universaldb::Database;
 
+use crate::cold::ColdStore;
 #[cfg(feature = "test-faults")]
 use crate::fault::DepotFaultController;
 use crate::workflows::compaction::DeltasAvailable;
@@ -100,6 +101,8 @@ pub struct Db {
 	/// Last wall-clock time this database sent a workflow compaction wakeup.
 	pub(super) last_deltas_available_at_ms: RwLock<Option<i64>>,
 	pub(super) compaction_signaler: Option<CompactionSignaler>,
+	/// Object store holding offloaded shard versions. Reads of offloaded history fail without it.
+	pub(super) cold_store: Option<Arc<dyn ColdStore>>,
 	#[cfg(feature = "test-faults")]
 	pub(super) fault_controller: Option<DepotFaultController>,
 }
@@ -109,6 +112,20 @@ impl Db {
 		Self::new_inner(udb, bucket_id, database_id, node_id, None)
 	}
 
+	/// Builds a handle with the configured cold store attached. Handles that may read history must be
+	/// built through this, since reads of shard versions offloaded to the cold tier fail without it.
+	pub async fn from_config(
+		config: &rivet_config::Config,
+		udb: Arc<Database>,
+		bucket_id: Id,
+		database_id: String,
+		node_id: NodeId,
+	) -> Result<Self> {
+		let cold_store = crate::cold::shared(config).await?;
+
+		Ok(Self::new(udb, bucket_id, database_id, node_id).with_cold_store(cold_store))
+	}
+
 	pub fn new_with_compaction_signaler(
 		udb: Arc<Database>,
 		bucket_id: Id,
@@ -180,11 +197,18 @@ impl Db {
 			read_bytes_since_rollup: AtomicU64::new(0),
 			last_deltas_available_at_ms: RwLock::new(None),
 			compaction_signaler,
+			cold_store: None,
 			#[cfg(feature = "test-faults")]
 			fault_controller: None,
 		}
 	}
 
+	/// Enables read-through of shard versions offloaded to the cold tier.
+	pub fn with_cold_store(mut self, cold_store: Option<Arc<dyn ColdStore>>) -> Self {
+		self.cold_store = cold_store;
+		self
+	}
+
 	pub(super) fn sqlite_bucket_id(&self) -> BucketId {
 		BucketId::from_gas_id(self.bucket_id)
 	}
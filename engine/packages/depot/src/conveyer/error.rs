@@ -54,6 +54,13 @@ pub enum SqliteStorageError {
 		payload_size: i64,
 	},
 
+	#[error(
+		"namespace_quota_exceeded",
+		"Namespace database storage quota exceeded.",
+		"Namespace database storage quota exceeded ({used_bytes} bytes used, limit is {limit_bytes} bytes)."
+	)]
+	NamespaceQuotaExceeded { used_bytes: i64, limit_bytes: i64 },
+
 	#[error("invalid_v1_migration_state", "Invalid SQLite v1 migration state.")]
 	InvalidV1MigrationState,
 
@@ -111,6 +118,17 @@ pub enum SqliteStorageError {
 	)]
 	ShardCacheCorrupt { shard_id: u32, as_of_txid: u64 },
 
+	#[error(
+		"cold_shard_unavailable",
+		"SQLite cold-tier shard is unavailable.",
+		"SQLite cold-tier shard {shard_id} at txid {as_of_txid} is unavailable: {reason}."
+	)]
+	ColdShardUnavailable {
+		shard_id: u32,
+		as_of_txid: u64,
+		reason: String,
+	},
+
 	#[error("too_many_pins", "Bucket has too many restore_points.")]
 	TooManyPins,
 
@@ -130,6 +148,33 @@ pub enum SqliteStorageError {
 
 	#[error("database_not_found", "Database was not found in this bucket branch.")]
 	DatabaseNotFound,
+
+	#[error(
+		"database_already_exists",
+		"Database already exists in this bucket branch."
+	)]
+	DatabaseAlreadyExists,
+
+	#[error(
+		"invalid_sqlite_file",
+		"SQLite database file is invalid.",
+		"SQLite database file is invalid: {reason}."
+	)]
+	InvalidSqliteFile { reason: String },
+
+	#[error(
+		"replica_out_of_sync",
+		"SQLite replica is out of sync with its source database.",
+		"SQLite replica is out of sync with its source database: {reason}."
+	)]
+	ReplicaOutOfSync { reason: String },
+
+	#[error(
+		"invalid_row_change_log",
+		"SQLite row change log is invalid.",
+		"SQLite row change log is invalid: {reason}."
+	)]
+	InvalidRowChangeLog { reason: String },
 }
 
 impl fmt::Display for SqliteStorageError {
@@ -163,6 +208,13 @@ impl fmt::Display for SqliteStorageError {
 				f,
 				"SqliteStorageQuotaExceeded: not enough space left in depot ({remaining_bytes} bytes remaining, current payload is {payload_size} bytes)"
 			),
+			SqliteStorageError::NamespaceQuotaExceeded {
+				used_bytes,
+				limit_bytes,
+			} => write!(
+				f,
+				"namespace database storage quota exceeded ({used_bytes} bytes used, limit is {limit_bytes} bytes)"
+			),
 			SqliteStorageError::InvalidV1MigrationState => {
 				write!(f, "invalid sqlite v1 migration state")
 			}
@@ -208,6 +260,16 @@ impl fmt::Display for SqliteStorageError {
 					"sqlite shard cache has conflicting bytes for shard {shard_id} at txid {as_of_txid}"
 				)
 			}
+			SqliteStorageError::ColdShardUnavailable {
+				shard_id,
+				as_of_txid,
+				reason,
+			} => {
+				write!(
+					f,
+					"sqlite cold-tier shard {shard_id} at txid {as_of_txid} is unavailable: {reason}"
+				)
+			}
 			SqliteStorageError::TooManyPins => {
 				write!(f, "sqlite bucket has too many restore_points")
 			}
@@ -227,6 +289,18 @@ impl fmt::Display for SqliteStorageError {
 			SqliteStorageError::DatabaseNotFound => {
 				write!(f, "sqlite database was not found in this bucket branch")
 			}
+			SqliteStorageError::DatabaseAlreadyExists => {
+				write!(f, "sqlite database already exists in this bucket branch")
+			}
+			SqliteStorageError::InvalidSqliteFile { reason } => {
+				write!(f, "sqlite database file is invalid: {reason}")
+			}
+			SqliteStorageError::ReplicaOutOfSync { reason } => {
+				write!(f, "sqlite replica is out of sync with its source: {reason}")
+			}
+			SqliteStorageError::InvalidRowChangeLog { reason } => {
+				write!(f, "sqlite row change log is invalid: {reason}")
+			}
 		}
 	}
 }
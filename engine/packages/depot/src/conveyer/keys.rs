@@ -22,6 +22,10 @@ pub const BUCKET_CHILD_PARTITION: u8 = 0x72;
 pub const BUCKET_CATALOG_BY_DB_PARTITION: u8 = 0x73;
 pub const BUCKET_PROOF_EPOCH_PARTITION: u8 = 0x74;
 pub const SQLITE_CMP_DIRTY_PARTITION: u8 = 0x75;
+pub const REPAIR_JOURNAL_PARTITION: u8 = 0x76;
+pub const DATA_KEY_PARTITION: u8 = 0x77;
+pub const REPLICA_STATE_PARTITION: u8 = 0x78;
+pub const COLD_DELETE_PARTITION: u8 = 0x79;
 pub const PAGE_SIZE: u32 = 4096;
 pub const SHARD_SIZE: u32 = 64;
 
@@ -30,11 +34,15 @@ const META_HEAD_AT_FORK_PATH: &[u8] = b"/META/head_at_fork";
 const META_COMPACT_PATH: &[u8] = b"/META/compact";
 const META_QUOTA_PATH: &[u8] = b"/META/quota";
 const META_COMPACTOR_LEASE_PATH: &[u8] = b"/META/compactor_lease";
+const META_ROW_CHANGES_PATH: &[u8] = b"/META/row_changes";
+const META_IMPORT_PATH: &[u8] = b"/META/import";
 const CMP_ROOT_PATH: &[u8] = b"/CMP/root";
 const CMP_STAGE_PATH: &[u8] = b"/CMP/stage/";
 const CMP_STAGE_HOT_SHARD_PATH: &[u8] = b"/hot_shard/";
 const SHARD_PATH: &[u8] = b"/SHARD/";
+const COLD_SHARD_PATH: &[u8] = b"/COLD/shard/";
 const DELTA_PATH: &[u8] = b"/DELTA/";
+const ROW_CHANGES_PATH: &[u8] = b"/ROW_CHANGES/";
 const PIDX_DELTA_PATH: &[u8] = b"/PIDX/delta/";
 const BR_PIDX_PATH: &[u8] = b"/PIDX/";
 const COMMITS_PATH: &[u8] = b"/COMMITS/";
@@ -65,6 +73,10 @@ const BUCKET_CHILD_PATH: &[u8] = b"/";
 const BUCKET_CATALOG_BY_DB_PATH: &[u8] = b"/";
 const BUCKET_PROOF_EPOCH_PATH: &[u8] = b"/";
 const SQLITE_CMP_DIRTY_PATH: &[u8] = b"/";
+const REPAIR_JOURNAL_PATH: &[u8] = b"/";
+const DATA_KEY_PATH: &[u8] = b"/";
+const REPLICA_STATE_PATH: &[u8] = b"/";
+const COLD_DELETE_PATH: &[u8] = b"/";
 
 fn partition_prefix(partition: u8) -> Vec<u8> {
 	vec![SQLITE_SUBSPACE_PREFIX, partition]
@@ -280,6 +292,18 @@ pub fn branches_list_key(branch_id: DatabaseBranchId) -> Vec<u8> {
 	branch_record_base(branch_id)
 }
 
+pub fn branches_list_prefix() -> Vec<u8> {
+	with_suffix(partition_prefix(BRANCHES_PARTITION), LIST_PATH)
+}
+
+/// Returns the branch id if `key` is a branch record key. Returns `None` for the per-branch
+/// suffix rows (`/refcount`, `/desc_pin`, ...) stored under the same prefix.
+pub fn decode_branches_list_key(key: &[u8]) -> Option<DatabaseBranchId> {
+	let suffix = key.strip_prefix(branches_list_prefix().as_slice())?;
+	let bytes: [u8; 16] = suffix.try_into().ok()?;
+	Some(DatabaseBranchId::from_uuid(uuid::Uuid::from_bytes(bytes)))
+}
+
 pub fn branches_refcount_key(branch_id: DatabaseBranchId) -> Vec<u8> {
 	with_suffix(branch_record_base(branch_id), REFCOUNT_PATH)
 }
@@ -424,6 +448,15 @@ pub fn branch_meta_compactor_lease_key(branch_id: DatabaseBranchId) -> Vec<u8> {
 	with_suffix(database_branch_base(branch_id), META_COMPACTOR_LEASE_PATH)
 }
 
+pub fn branch_meta_row_changes_key(branch_id: DatabaseBranchId) -> Vec<u8> {
+	with_suffix(database_branch_base(branch_id), META_ROW_CHANGES_PATH)
+}
+
+/// Present while a SQLite file import into the branch is incomplete.
+pub fn branch_meta_import_key(branch_id: DatabaseBranchId) -> Vec<u8> {
+	with_suffix(database_branch_base(branch_id), META_IMPORT_PATH)
+}
+
 pub fn branch_manifest_last_hot_pass_txid_key(branch_id: DatabaseBranchId) -> Vec<u8> {
 	with_suffix(
 		database_branch_base(branch_id),
@@ -610,6 +643,43 @@ pub fn decode_branch_delta_chunk_idx(
 	)?))
 }
 
+pub fn branch_row_change_prefix(branch_id: DatabaseBranchId) -> Vec<u8> {
+	with_suffix(database_branch_base(branch_id), ROW_CHANGES_PATH)
+}
+
+pub fn branch_row_change_key(branch_id: DatabaseBranchId, txid: u64, index: u32) -> Vec<u8> {
+	let mut key = branch_row_change_prefix(branch_id);
+	key.extend_from_slice(&txid.to_be_bytes());
+	key.extend_from_slice(&index.to_be_bytes());
+	key
+}
+
+pub fn decode_branch_row_change_key(branch_id: DatabaseBranchId, key: &[u8]) -> Result<(u64, u32)> {
+	let prefix = branch_row_change_prefix(branch_id);
+	let suffix = key
+		.strip_prefix(prefix.as_slice())
+		.context("branch row change key did not start with expected prefix")?;
+	ensure!(
+		suffix.len() == std::mem::size_of::<u64>() + std::mem::size_of::<u32>(),
+		"branch row change key suffix had {} bytes, expected {}",
+		suffix.len(),
+		std::mem::size_of::<u64>() + std::mem::size_of::<u32>()
+	);
+	let (txid, index) = suffix.split_at(std::mem::size_of::<u64>());
+
+	Ok((
+		u64::from_be_bytes(
+			txid.try_into()
+				.context("branch row change txid should decode as u64")?,
+		),
+		u32::from_be_bytes(
+			index
+				.try_into()
+				.context("branch row change index should decode as u32")?,
+		),
+	))
+}
+
 pub fn branch_shard_prefix(branch_id: DatabaseBranchId) -> Vec<u8> {
 	with_suffix(database_branch_base(branch_id), SHARD_PATH)
 }
@@ -627,6 +697,60 @@ pub fn branch_shard_key(branch_id: DatabaseBranchId, shard_id: u32, as_of_txid:
 	key
 }
 
+/// Index of shard versions that were offloaded to the cold tier. Mirrors the `/SHARD/` layout so
+/// reads can cap the scan at the same txid.
+pub fn branch_cold_shard_prefix(branch_id: DatabaseBranchId) -> Vec<u8> {
+	with_suffix(database_branch_base(branch_id), COLD_SHARD_PATH)
+}
+
+pub fn branch_cold_shard_version_prefix(branch_id: DatabaseBranchId, shard_id: u32) -> Vec<u8> {
+	let mut key = branch_cold_shard_prefix(branch_id);
+	key.extend_from_slice(&shard_id.to_be_bytes());
+	key.push(b'/');
+	key
+}
+
+pub fn branch_cold_shard_key(
+	branch_id: DatabaseBranchId,
+	shard_id: u32,
+	as_of_txid: u64,
+) -> Vec<u8> {
+	let mut key = branch_cold_shard_version_prefix(branch_id, shard_id);
+	key.extend_from_slice(&as_of_txid.to_be_bytes());
+	key
+}
+
+pub fn decode_branch_cold_shard_key(branch_id: DatabaseBranchId, key: &[u8]) -> Result<(u32, u64)> {
+	let prefix = branch_cold_shard_prefix(branch_id);
+	let suffix = key
+		.strip_prefix(prefix.as_slice())
+		.context("cold shard key did not start with expected prefix")?;
+	let expected_len = std::mem::size_of::<u32>() + 1 + std::mem::size_of::<u64>();
+	ensure!(
+		suffix.len() == expected_len,
+		"cold shard key suffix had {} bytes, expected {}",
+		suffix.len(),
+		expected_len
+	);
+	ensure!(
+		suffix[std::mem::size_of::<u32>()] == b'/',
+		"cold shard key missing shard/txid separator"
+	);
+
+	let shard_id = u32::from_be_bytes(
+		suffix[..std::mem::size_of::<u32>()]
+			.try_into()
+			.context("cold shard id should decode as u32")?,
+	);
+	let as_of_txid = u64::from_be_bytes(
+		suffix[std::mem::size_of::<u32>() + 1..]
+			.try_into()
+			.context("cold shard txid should decode as u64")?,
+	);
+
+	Ok((shard_id, as_of_txid))
+}
+
 pub fn ctr_quota_global_key() -> Vec<u8> {
 	with_suffix(partition_prefix(CTR_PARTITION), CTR_QUOTA_GLOBAL_PATH)
 }
@@ -782,6 +906,75 @@ pub fn sqlite_cmp_dirty_key(branch_id: DatabaseBranchId) -> Vec<u8> {
 	key
 }
 
+pub fn repair_journal_prefix(database_id: &str, restore_point: &str) -> Vec<u8> {
+	let mut key = with_suffix(
+		partition_prefix(REPAIR_JOURNAL_PARTITION),
+		REPAIR_JOURNAL_PATH,
+	);
+	append_database_id(&mut key, database_id);
+	key.push(b'/');
+	key.extend_from_slice(restore_point.as_bytes());
+	key.push(b'/');
+	key
+}
+
+pub fn repair_journal_key(database_id: &str, restore_point: &str, seq: u32) -> Vec<u8> {
+	let mut key = repair_journal_prefix(database_id, restore_point);
+	key.extend_from_slice(&seq.to_be_bytes());
+	key
+}
+
+pub fn bucket_data_key_key(bucket_id: BucketId) -> Vec<u8> {
+	let mut key = bucket_data_key_prefix();
+	append_uuid(&mut key, bucket_id.as_uuid());
+	key
+}
+
+pub fn bucket_data_key_prefix() -> Vec<u8> {
+	with_suffix(partition_prefix(DATA_KEY_PARTITION), DATA_KEY_PATH)
+}
+
+pub fn decode_bucket_data_key_bucket_id(key: &[u8]) -> Result<BucketId> {
+	let prefix = bucket_data_key_prefix();
+	let suffix = key
+		.strip_prefix(prefix.as_slice())
+		.context("bucket data key did not start with expected prefix")?;
+	let uuid = uuid::Uuid::from_slice(suffix).context("decode bucket data key uuid")?;
+
+	Ok(BucketId::from_uuid(uuid))
+}
+
+pub fn replica_state_key(bucket_id: BucketId, database_id: &str) -> Vec<u8> {
+	let mut key = with_suffix(
+		partition_prefix(REPLICA_STATE_PARTITION),
+		REPLICA_STATE_PATH,
+	);
+	append_uuid(&mut key, bucket_id.as_uuid());
+	append_database_id(&mut key, database_id);
+	key
+}
+
+/// Queue of cold objects whose `/COLD/shard/` index row was cleared while the branch stays live.
+/// The cold drainer deletes the objects and the rows.
+pub fn cold_object_delete_key(object_key: &str) -> Vec<u8> {
+	let mut key = cold_object_delete_prefix();
+	key.extend_from_slice(object_key.as_bytes());
+	key
+}
+
+pub fn cold_object_delete_prefix() -> Vec<u8> {
+	with_suffix(partition_prefix(COLD_DELETE_PARTITION), COLD_DELETE_PATH)
+}
+
+pub fn decode_cold_object_delete_key(key: &[u8]) -> Result<String> {
+	let prefix = cold_object_delete_prefix();
+	let suffix = key
+		.strip_prefix(prefix.as_slice())
+		.context("cold object delete key did not start with expected prefix")?;
+
+	String::from_utf8(suffix.to_vec()).context("decode cold object delete key")
+}
+
 // Legacy database-scoped keys are v1-only compatibility helpers for pegboard actors.
 pub fn meta_head_key(database_id: &str) -> Vec<u8> {
 	let prefix = database_prefix(database_id);
@@ -2,7 +2,10 @@
 
 use anyhow::{Result, bail, ensure};
 
-use crate::types::{DirtyPage, SQLITE_PAGE_SIZE};
+use crate::{
+	encryption,
+	types::{DirtyPage, SQLITE_PAGE_SIZE},
+};
 
 pub const LTX_MAGIC: &[u8; 4] = b"LTX1";
 pub const LTX_VERSION: u32 = 3;
@@ -149,8 +152,12 @@ pub struct LtxBlob {
 }
 
 impl LtxBlob {
-	/// Parses the header and page index without decompressing any page frames.
-	pub fn decode_index(bytes: Vec<u8>) -> Result<Self> {
+	/// Parses the header and page index without decompressing any page frames. Sealed blobs are
+	/// decrypted first.
+	pub fn decode_index(mut bytes: Vec<u8>) -> Result<Self> {
+		if encryption::is_sealed(&bytes) {
+			bytes = encryption::open_blob(&bytes)?.into_owned();
+		}
 		ensure!(
 			bytes.len()
 				>= LTX_HEADER_SIZE
@@ -435,8 +442,10 @@ impl<'a> LtxDecoder<'a> {
 	}
 }
 
+/// Decodes an LTX blob, decrypting it first when it is sealed.
 pub fn decode_ltx_v3(bytes: &[u8]) -> Result<DecodedLtx> {
-	LtxDecoder::new(bytes).decode()
+	let bytes = encryption::open_blob(bytes)?;
+	LtxDecoder::new(&bytes).decode()
 }
 
 fn append_uvarint(buf: &mut Vec<u8>, mut value: u64) {
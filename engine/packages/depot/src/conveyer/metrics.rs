@@ -79,6 +79,31 @@ lazy_static::lazy_static! {
 		*REGISTRY
 	).unwrap();
 
+	pub static ref SQLITE_COLD_SHARD_READ_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"sqlite_cold_shard_read_total",
+		"Total sqlite shard versions read through from the cold tier.",
+		&["outcome"],
+		*REGISTRY
+	).unwrap();
+
+	pub static ref SQLITE_COLD_SHARD_DRAINED_TOTAL: IntCounter = register_int_counter_with_registry!(
+		"sqlite_cold_shard_drained_total",
+		"Total sqlite shard versions offloaded to the cold tier.",
+		*REGISTRY
+	).unwrap();
+
+	pub static ref SQLITE_COLD_SHARD_DRAINED_BYTES: IntCounter = register_int_counter_with_registry!(
+		"sqlite_cold_shard_drained_bytes",
+		"Total bytes of sqlite shard versions offloaded to the cold tier.",
+		*REGISTRY
+	).unwrap();
+
+	pub static ref SQLITE_NAMESPACE_SOFT_LIMIT_EXCEEDED_TOTAL: IntCounter = register_int_counter_with_registry!(
+		"sqlite_namespace_soft_limit_exceeded_total",
+		"Total sqlite commits that left their namespace over its database soft limit.",
+		*REGISTRY
+	).unwrap();
+
 	pub static ref SQLITE_BRANCH_FORK_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
 		"sqlite_branch_fork_total",
 		"Total sqlite branch fork operations.",
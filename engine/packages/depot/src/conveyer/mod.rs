@@ -14,7 +14,11 @@ pub mod pitr_interval;
 pub mod policy;
 pub mod quota;
 pub mod read;
+pub mod repair;
+pub mod replica;
 pub mod restore_point;
+pub mod row_changes;
+pub mod sqlite_file;
 pub mod types;
 pub mod udb;
 
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
-use universaldb::utils::IsolationLevel::Snapshot;
+use gas::prelude::Id;
+use namespace::keys::database_quota::{self, DatabaseQuota};
+use universaldb::utils::IsolationLevel::{Serializable, Snapshot};
 
 use super::{
 	error::SqliteStorageError,
@@ -146,6 +148,95 @@ pub async fn get_effective_shard_cache_policy(
 	get_bucket_shard_cache_policy(udb, bucket_id).await
 }
 
+/// Sets the storage limits enforced on commits to the namespace's databases in this datacenter. The
+/// limits are stored with the namespace rather than under the bucket.
+pub async fn set_namespace_quota(
+	udb: &universaldb::Database,
+	namespace_id: Id,
+	quota: DatabaseQuota,
+) -> Result<()> {
+	validate_namespace_quota(quota)?;
+
+	udb.txn("depot_policy_write", move |tx| async move {
+		let tx = tx.with_subspace(namespace::keys::subspace());
+		database_quota::write(&tx, namespace_id, quota)
+	})
+	.await
+}
+
+pub async fn get_namespace_quota(
+	udb: &universaldb::Database,
+	namespace_id: Id,
+) -> Result<DatabaseQuota> {
+	udb.txn("depot_policy_read", move |tx| async move {
+		let tx = tx.with_subspace(namespace::keys::subspace());
+		database_quota::read(&tx, namespace_id, Snapshot).await
+	})
+	.await
+}
+
+/// Changes to the database policy of a namespace in this datacenter. Unset fields keep their
+/// current value.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NamespacePolicyUpdate {
+	pub pitr_interval_ms: Option<i64>,
+	pub pitr_retention_ms: Option<i64>,
+	/// `Some(None)` removes the limit.
+	pub hard_limit_bytes: Option<Option<i64>>,
+	/// `Some(None)` removes the limit.
+	pub soft_limit_bytes: Option<Option<i64>>,
+}
+
+/// Applies an update to the PITR policy of the namespace's bucket and to its storage limits in one
+/// transaction. The merged policy is validated before anything is written, so an invalid update
+/// changes nothing.
+pub async fn update_namespace_policy(
+	udb: &universaldb::Database,
+	namespace_id: Id,
+	update: NamespacePolicyUpdate,
+) -> Result<()> {
+	let pitr_key = keys::bucket_policy_pitr_key(BucketId::from_gas_id(namespace_id));
+
+	udb.txn("depot_policy_write", move |tx| {
+		let pitr_key = pitr_key.clone();
+
+		async move {
+			let current_pitr = tx
+				.informal()
+				.get(&pitr_key, Serializable)
+				.await?
+				.map(|bytes| decode_pitr_policy(&bytes).context("decode sqlite pitr policy"))
+				.transpose()?
+				.unwrap_or_default();
+			let pitr = PitrPolicy {
+				interval_ms: update.pitr_interval_ms.unwrap_or(current_pitr.interval_ms),
+				retention_ms: update
+					.pitr_retention_ms
+					.unwrap_or(current_pitr.retention_ms),
+			};
+
+			let ns_tx = tx.with_subspace(namespace::keys::subspace());
+			let current_quota = database_quota::read(&ns_tx, namespace_id, Serializable).await?;
+			let quota = DatabaseQuota {
+				hard_limit_bytes: update
+					.hard_limit_bytes
+					.unwrap_or(current_quota.hard_limit_bytes),
+				soft_limit_bytes: update
+					.soft_limit_bytes
+					.unwrap_or(current_quota.soft_limit_bytes),
+			};
+
+			validate_pitr_policy(pitr)?;
+			validate_namespace_quota(quota)?;
+
+			let encoded = encode_pitr_policy(pitr).context("encode sqlite bucket pitr policy")?;
+			tx.informal().set(&pitr_key, &encoded);
+			database_quota::write(&ns_tx, namespace_id, quota)
+		}
+	})
+	.await
+}
+
 fn validate_pitr_policy(policy: PitrPolicy) -> Result<()> {
 	ensure_positive("pitr", "interval_ms", policy.interval_ms)?;
 	ensure_positive("pitr", "retention_ms", policy.retention_ms)
@@ -155,6 +246,30 @@ fn validate_shard_cache_policy(policy: ShardCachePolicy) -> Result<()> {
 	ensure_positive("shard_cache", "retention_ms", policy.retention_ms)
 }
 
+fn validate_namespace_quota(quota: DatabaseQuota) -> Result<()> {
+	if let Some(hard_limit_bytes) = quota.hard_limit_bytes {
+		ensure_positive("namespace_quota", "hard_limit_bytes", hard_limit_bytes)?;
+	}
+	if let Some(soft_limit_bytes) = quota.soft_limit_bytes {
+		ensure_positive("namespace_quota", "soft_limit_bytes", soft_limit_bytes)?;
+
+		// A soft limit past the hard limit could never warn
+		if quota
+			.hard_limit_bytes
+			.is_some_and(|hard_limit_bytes| soft_limit_bytes > hard_limit_bytes)
+		{
+			return Err(SqliteStorageError::InvalidPolicyValue {
+				policy: "namespace_quota",
+				field: "soft_limit_bytes",
+				value: soft_limit_bytes,
+			}
+			.into());
+		}
+	}
+
+	Ok(())
+}
+
 fn ensure_positive(policy: &'static str, field: &'static str, value: i64) -> Result<()> {
 	if value <= 0 {
 		return Err(SqliteStorageError::InvalidPolicyValue {
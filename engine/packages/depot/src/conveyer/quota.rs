@@ -1,4 +1,9 @@
 use anyhow::{Context, Error, Result};
+use gas::prelude::Id;
+use namespace::keys::{
+	database_quota::{self, DatabaseQuota, SoftLimitExceededKey},
+	usage::{StorageKind, StorageUsageKey},
+};
 use universaldb::{options::MutationType, utils::IsolationLevel::Snapshot};
 
 use crate::conveyer::{
@@ -110,3 +115,74 @@ pub fn cap_check_with_cap(would_be: i64, cap_bytes: i64) -> Result<()> {
 
 	Ok(())
 }
+
+/// A commit that leaves its namespace over the namespace's database soft limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftLimitExceeded {
+	/// Namespace usage before the commit.
+	pub used_bytes: i64,
+	pub would_be_bytes: i64,
+	pub limit_bytes: i64,
+	/// Whether this commit is the one that crossed the limit. Later commits over the limit are
+	/// not crossings, even before the usage rollup catches up with them.
+	pub crossed: bool,
+}
+
+/// Checks a commit growing storage by `delta_bytes` against the database limits of its namespace.
+///
+/// The namespace total comes from the metered usage rollup of this datacenter, so the limits apply
+/// to each datacenter separately and enforcement trails actual usage by up to one metering pass. Whether the namespace was already over its soft limit is tracked
+/// separately in `SoftLimitExceededKey`, which the rollup lag does not affect.
+pub async fn namespace_cap_check(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	delta_bytes: i64,
+) -> Result<Option<SoftLimitExceeded>> {
+	let ns_tx = tx.with_subspace(namespace::keys::subspace());
+	let quota = database_quota::read(&ns_tx, namespace_id, Snapshot).await?;
+	if quota == DatabaseQuota::default() {
+		return Ok(None);
+	}
+
+	let used_bytes = ns_tx
+		.read_opt(
+			&StorageUsageKey::new(namespace_id, StorageKind::Sqlite),
+			Snapshot,
+		)
+		.await?
+		.unwrap_or_default()
+		.max(0);
+	let would_be_bytes = used_bytes
+		.checked_add(delta_bytes)
+		.context("sqlite namespace quota check overflowed i64")?;
+
+	if let Some(limit_bytes) = quota.hard_limit_bytes
+		&& would_be_bytes > limit_bytes
+	{
+		return Err(SqliteStorageError::NamespaceQuotaExceeded {
+			used_bytes,
+			limit_bytes,
+		}
+		.into());
+	}
+
+	let exceeded_key = SoftLimitExceededKey::new(namespace_id);
+	let was_exceeded = ns_tx.exists(&exceeded_key, Snapshot).await?;
+	let limit_bytes = quota
+		.soft_limit_bytes
+		.filter(|limit_bytes| would_be_bytes > *limit_bytes);
+
+	// Only transitions write the marker, so commits of the same namespace do not conflict on it
+	match (limit_bytes, was_exceeded) {
+		(Some(_), false) => ns_tx.write(&exceeded_key, ())?,
+		(None, true) => ns_tx.delete(&exceeded_key),
+		_ => {}
+	}
+
+	Ok(limit_bytes.map(|limit_bytes| SoftLimitExceeded {
+		used_bytes,
+		would_be_bytes,
+		limit_bytes,
+		crossed: !was_exceeded,
+	}))
+}
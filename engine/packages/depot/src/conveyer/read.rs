@@ -37,7 +37,10 @@ use crate::conveyer::{
 use self::{
 	pidx::{PageRef, PageRefKind, decode_pidx_txid},
 	plan::{ReadSource, StorageScope, resolve_storage_scope},
-	shard::{DeltaBlobLoad, ShardBlobLoad, tx_load_delta_blob, tx_load_latest_shard_blob},
+	shard::{
+		ColdShardFetch, DeltaBlobLoad, ShardBlob, ShardBlobLoad, fetch_cold_shards,
+		tx_load_delta_blob, tx_load_latest_shard_blob,
+	},
 	tx::{tx_get_value, tx_scan_prefix_values},
 };
 
@@ -248,6 +251,7 @@ impl Db {
 							loaded_pidx_rows: None,
 							page_sources: BTreeMap::new(),
 							source_blobs: BTreeMap::new(),
+							cold_shards: BTreeMap::new(),
 							page_candidates: BTreeMap::new(),
 							selected_candidates: BTreeMap::new(),
 							shard_cache_read_outcomes: BTreeMap::new(),
@@ -385,7 +389,8 @@ impl Db {
 					let mut page_candidates = BTreeMap::<u32, Vec<PageSourceCandidate>>::new();
 					let mut selected_candidates = BTreeMap::<u32, PageSourceCandidate>::new();
 					let mut missing_delta_prefixes = BTreeSet::new();
-					let mut shard_sources = BTreeMap::<u32, Option<(Vec<u8>, Vec<u8>)>>::new();
+					let mut shard_sources = BTreeMap::<u32, Option<(Vec<u8>, ShardBlob)>>::new();
+					let mut cold_shards = BTreeMap::<Vec<u8>, ColdShardFetch>::new();
 					let mut stale_pidx_pgnos = BTreeSet::new();
 					let mut shard_cache_read_outcomes =
 						BTreeMap::<u32, ShardCacheReadOutcome>::new();
@@ -491,8 +496,13 @@ impl Db {
 
 					let shard_blobs: BTreeMap<u32, ShardBlobLoad> = stream::iter(shard_triggers)
 						.map(move |(shard_id, trigger_pgno)| async move {
-							let load =
-								tx_load_latest_shard_blob(tx_ref, scope_ref, shard_id).await?;
+							let load = tx_load_latest_shard_blob(
+								tx_ref,
+								scope_ref,
+								shard_id,
+								ltx_blob_cache_ref,
+							)
+							.await?;
 							#[cfg(feature = "test-faults")]
 							let load = {
 								let mut load = load;
@@ -620,8 +630,13 @@ impl Db {
 						if let Some((source_key, blob)) =
 							shard_sources.get(&shard_id).cloned().flatten()
 						{
-							if !source_blobs.contains_key(&source_key) {
-								source_blobs.insert(source_key.clone(), blob);
+							match blob {
+								ShardBlob::Hot(blob) => {
+									source_blobs.entry(source_key.clone()).or_insert(blob);
+								}
+								ShardBlob::Cold(cold) => {
+									cold_shards.entry(source_key.clone()).or_insert(cold);
+								}
 							}
 							if collect_provenance {
 								let (source_shard_id, source_as_of_txid) =
@@ -673,6 +688,7 @@ impl Db {
 						loaded_pidx_rows,
 						page_sources,
 						source_blobs,
+						cold_shards,
 						page_candidates,
 						selected_candidates,
 						shard_cache_read_outcomes,
@@ -685,6 +701,18 @@ impl Db {
 
 		let mut tx_result = tx_result;
 
+		// Offloaded shards were only referenced inside the transaction. Fetch them now so a slow
+		// object store never holds the transaction open.
+		let cold_shards = std::mem::take(&mut tx_result.cold_shards);
+		tx_result.source_blobs.extend(
+			fetch_cold_shards(
+				self.cold_store.as_deref(),
+				cold_shards,
+				PAGE_SOURCE_FETCH_CONCURRENCY,
+			)
+			.await?,
+		);
+
 		let mut stale_pidx_pgnos = tx_result.stale_pidx_pgnos;
 
 		let mut decoded_blobs = BTreeMap::<Vec<u8>, std::sync::Arc<LtxBlob>>::new();
@@ -1067,6 +1095,7 @@ struct GetPagesTxResult {
 	loaded_pidx_rows: Option<Vec<(u32, u64)>>,
 	page_sources: BTreeMap<u32, Vec<u8>>,
 	source_blobs: BTreeMap<Vec<u8>, Vec<u8>>,
+	cold_shards: BTreeMap<Vec<u8>, ColdShardFetch>,
 	page_candidates: BTreeMap<u32, Vec<PageSourceCandidate>>,
 	selected_candidates: BTreeMap<u32, PageSourceCandidate>,
 	shard_cache_read_outcomes: BTreeMap<u32, ShardCacheReadOutcome>,
@@ -1,12 +1,21 @@
+use std::collections::BTreeMap;
+
 use anyhow::{Context, Result, ensure};
-use futures_util::{TryStreamExt, future::try_join_all};
+use futures_util::{StreamExt, TryStreamExt, future::try_join_all, stream};
 use universaldb::{
 	RangeOption,
 	options::StreamingMode,
 	utils::{IsolationLevel::Serializable, end_of_key_range},
 };
 
-use crate::conveyer::{db::LtxBlobCache, keys};
+use crate::{
+	cold::{self, ColdStore},
+	conveyer::{
+		db::LtxBlobCache,
+		keys,
+		types::{ColdShardRef, decode_cold_shard_ref},
+	},
+};
 
 use super::plan::{ReadSource, StorageScope};
 
@@ -78,6 +87,7 @@ pub(super) async fn tx_load_latest_shard_blob(
 	tx: &universaldb::Transaction,
 	scope: &StorageScope,
 	shard_id: u32,
+	cache: &LtxBlobCache,
 ) -> Result<ShardBlobLoad> {
 	let StorageScope::Branch(plan) = scope;
 
@@ -87,7 +97,7 @@ pub(super) async fn tx_load_latest_shard_blob(
 	let per_source = try_join_all(
 		plan.sources
 			.iter()
-			.map(|source| tx_load_source_shard_blob(tx, *source, shard_id)),
+			.map(|source| tx_load_source_shard_blob(tx, *source, shard_id, cache)),
 	)
 	.await?;
 
@@ -112,36 +122,152 @@ async fn tx_load_source_shard_blob(
 	tx: &universaldb::Transaction,
 	source: ReadSource,
 	shard_id: u32,
-) -> Result<(Option<(Vec<u8>, Vec<u8>)>, usize)> {
+	cache: &LtxBlobCache,
+) -> Result<(Option<(Vec<u8>, ShardBlob)>, usize)> {
 	let ReadSource::Branch(source) = source;
-	let prefix = keys::branch_shard_version_prefix(source.branch_id, shard_id);
-	let end_key = keys::branch_shard_key(source.branch_id, shard_id, source.max_txid);
-	let end = end_of_key_range(&end_key);
+	let hot_prefix = keys::branch_shard_version_prefix(source.branch_id, shard_id);
+	let hot_end = end_of_key_range(&keys::branch_shard_key(
+		source.branch_id,
+		shard_id,
+		source.max_txid,
+	));
+	let cold_prefix = keys::branch_cold_shard_version_prefix(source.branch_id, shard_id);
+	let cold_end = end_of_key_range(&keys::branch_cold_shard_key(
+		source.branch_id,
+		shard_id,
+		source.max_txid,
+	));
+
+	let (hot, cold) = tokio::try_join!(
+		tx_load_newest_row(tx, &hot_prefix, &hot_end),
+		tx_load_newest_row(tx, &cold_prefix, &cold_end),
+	)?;
+	let rows_scanned = usize::from(hot.is_some()) + usize::from(cold.is_some());
+	let hot_source =
+		|hot: Option<(Vec<u8>, Vec<u8>)>| hot.map(|(key, blob)| (key, ShardBlob::Hot(blob)));
+
+	// Offloaded versions are always older than the newest hot version of the same shard, but a
+	// capped read (fork or restore point) can land between them.
+	let hot_txid = hot
+		.as_ref()
+		.map(|(key, _)| decode_version_txid(&hot_prefix, key))
+		.transpose()?;
+	let cold_txid = cold
+		.as_ref()
+		.map(|(key, _)| decode_version_txid(&cold_prefix, key))
+		.transpose()?;
+	let Some((cold_txid, (_, cold_value))) = cold_txid.zip(cold) else {
+		return Ok((hot_source(hot), rows_scanned));
+	};
+	if hot_txid.is_some_and(|hot_txid| hot_txid > cold_txid) {
+		return Ok((hot_source(hot), rows_scanned));
+	}
 
+	// Serve the offloaded blob under its original `/SHARD/` key so provenance and the decoded
+	// blob cache treat it like any other shard version.
+	let source_key = keys::branch_shard_key(source.branch_id, shard_id, cold_txid);
+	if let Some(cached) = cache.get(&source_key).await {
+		return Ok((
+			Some((source_key, ShardBlob::Hot(cached.bytes().to_vec()))),
+			rows_scanned,
+		));
+	}
+	let cold = ColdShardFetch {
+		shard_id,
+		as_of_txid: cold_txid,
+		cold_ref: decode_cold_shard_ref(&cold_value)?,
+	};
+
+	Ok((Some((source_key, ShardBlob::Cold(cold))), rows_scanned))
+}
+
+/// Fetches the offloaded shard blobs a read transaction referenced. Runs after the transaction so
+/// object store latency neither holds it open nor repeats on every transaction retry.
+pub(super) async fn fetch_cold_shards(
+	cold_store: Option<&dyn ColdStore>,
+	fetches: BTreeMap<Vec<u8>, ColdShardFetch>,
+	concurrency: usize,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+	stream::iter(fetches)
+		.map(|(source_key, fetch)| async move {
+			let blob = cold::drain::fetch_shard(
+				cold_store,
+				fetch.shard_id,
+				fetch.as_of_txid,
+				&fetch.cold_ref,
+			)
+			.await?;
+			Result::<_>::Ok((source_key, blob))
+		})
+		.buffer_unordered(concurrency)
+		.try_collect()
+		.await
+}
+
+async fn tx_load_newest_row(
+	tx: &universaldb::Transaction,
+	begin: &[u8],
+	end: &[u8],
+) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
 	let informal = tx.informal();
 	let mut stream = informal.get_ranges_keyvalues(
 		RangeOption {
 			mode: StreamingMode::Iterator,
 			reverse: true,
 			limit: Some(1),
-			..(prefix.as_slice(), end.as_slice()).into()
+			..(begin, end).into()
 		},
 		// TODO: This can probably be made Snapshot again to reduce contention if
 		// read side freshness is not worth the cost.
 		Serializable,
 	);
 
-	let mut rows_scanned = 0usize;
 	let mut latest = None;
 	while let Some(entry) = stream.try_next().await? {
-		rows_scanned += 1;
 		latest = Some((entry.key().to_vec(), entry.value().to_vec()));
 	}
 
-	Ok((latest, rows_scanned))
+	Ok(latest)
+}
+
+fn decode_version_txid(version_prefix: &[u8], key: &[u8]) -> Result<u64> {
+	let suffix = key
+		.strip_prefix(version_prefix)
+		.context("shard version key did not start with expected prefix")?;
+
+	Ok(u64::from_be_bytes(
+		suffix
+			.try_into()
+			.context("shard version suffix should decode as u64")?,
+	))
 }
 
 pub(super) struct ShardBlobLoad {
-	pub(super) source: Option<(Vec<u8>, Vec<u8>)>,
+	pub(super) source: Option<(Vec<u8>, ShardBlob)>,
 	pub(super) rows_scanned: usize,
 }
+
+#[derive(Clone)]
+pub(super) enum ShardBlob {
+	Hot(Vec<u8>),
+	/// Offloaded to the cold tier. Fetched with [`fetch_cold_shards`] once the transaction is done.
+	Cold(ColdShardFetch),
+}
+
+impl ShardBlob {
+	pub(super) fn len(&self) -> usize {
+		match self {
+			ShardBlob::Hot(blob) => blob.len(),
+			ShardBlob::Cold(cold) => {
+				usize::try_from(cold.cold_ref.size_bytes).unwrap_or(usize::MAX)
+			}
+		}
+	}
+}
+
+#[derive(Clone)]
+pub(super) struct ColdShardFetch {
+	shard_id: u32,
+	as_of_txid: u64,
+	cold_ref: ColdShardRef,
+}
@@ -0,0 +1,511 @@
+//! Storage repairs applied by `depot repair`.
+//!
+//! Each repair runs against the database's current branch and refuses to write if a commit
+//! lands while it is being computed. The actor should be stopped while repairs run.
+
+use std::{
+	collections::BTreeMap,
+	sync::Arc,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, bail, ensure};
+use futures_util::TryStreamExt;
+use serde::Serialize;
+use universaldb::{
+	KeySelector, RangeOption,
+	options::StreamingMode,
+	utils::IsolationLevel::{Serializable, Snapshot},
+};
+
+use super::{
+	Db, branch,
+	error::SqliteStorageError,
+	keys,
+	ltx::decode_ltx_v3,
+	quota,
+	types::{
+		DatabaseBranchId, RepairAction, RepairActionStatus, RepairJournalEntry, RestorePointId,
+		SqliteCmpDirty, decode_commit_row, decode_compaction_root, decode_db_head,
+		decode_repair_journal_entry, encode_repair_journal_entry, encode_sqlite_cmp_dirty,
+	},
+};
+use crate::{
+	doctor::{self, DoctorInput, DoctorReport, DoctorSelector, SkipOptions},
+	workflows::compaction::DeltasAvailable,
+};
+
+/// Rows read per transaction while scanning branch history.
+const REPAIR_SCAN_CHUNK_ROWS: usize = 1_024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PageIndexRebuild {
+	pub database_branch_id: DatabaseBranchId,
+	pub head_txid: Option<u64>,
+	pub previous_rows: usize,
+	pub rebuilt_rows: usize,
+	/// Pages whose index row was added, removed or pointed at a different txid.
+	pub changed_rows: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaRecompute {
+	pub database_branch_id: DatabaseBranchId,
+	pub previous_bytes: i64,
+	pub recomputed_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompactionRerun {
+	pub database_branch_id: DatabaseBranchId,
+	pub observed_head_txid: u64,
+	/// False when this handle has no compaction signaler, so the manager only sees the dirty
+	/// marker on its next refresh.
+	pub signaled: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RepairState {
+	branch_id: DatabaseBranchId,
+	head_txid: Option<u64>,
+	hot_watermark_txid: u64,
+}
+
+impl Db {
+	/// Runs `depot doctor` against the database this handle points at.
+	pub async fn doctor(&self, skip: SkipOptions) -> Result<DoctorReport> {
+		doctor::doctor(
+			&self.udb,
+			DoctorInput {
+				selector: DoctorSelector::BucketDatabase {
+					bucket_id: self.sqlite_bucket_id().as_uuid(),
+					database_id: self.database_id.clone(),
+				},
+				artifact_dir: None,
+				skip,
+				min_txid: None,
+				max_txid: None,
+				cold_store: self.cold_store.clone(),
+				progress_hook: None,
+			},
+		)
+		.await
+	}
+
+	/// Rewrites the branch page index from the delta history above the hot compaction
+	/// watermark. Pages last written at or below the watermark are served from shards and get
+	/// no index row.
+	pub async fn rebuild_page_index(&self) -> Result<PageIndexRebuild> {
+		let state = self.read_repair_state().await?;
+		let branch_id = state.branch_id;
+
+		let mut owners = BTreeMap::<u32, u64>::new();
+		let commit_prefix = keys::branch_commit_prefix(branch_id);
+		for (key, value) in scan_prefix(&self.udb, keys::branch_commit_prefix(branch_id)).await? {
+			let txid = decode_u64_suffix(&commit_prefix, &key)?;
+			if txid <= state.hot_watermark_txid || state.head_txid.is_none_or(|head| txid > head) {
+				continue;
+			}
+			let commit = decode_commit_row(&value).context("decode sqlite commit row")?;
+			let chunks =
+				scan_prefix(&self.udb, keys::branch_delta_chunk_prefix(branch_id, txid)).await?;
+			if chunks.is_empty() {
+				bail!(
+					"delta for txid {txid} is missing, truncate the branch before rebuilding the page index"
+				);
+			}
+			let encoded = chunks
+				.into_iter()
+				.flat_map(|(_, chunk)| chunk)
+				.collect::<Vec<_>>();
+			let decoded = decode_ltx_v3(&encoded)
+				.with_context(|| format!("decode sqlite delta for txid {txid}"))?;
+			for page in decoded.pages {
+				owners.insert(page.pgno, txid);
+			}
+			owners.retain(|pgno, _| *pgno <= commit.db_size_pages);
+		}
+
+		let rebuilt_rows = owners.len();
+		let owners = Arc::new(owners);
+		let (previous_rows, changed_rows) = self
+			.udb
+			.txn("depot_repair_rebuild_page_index", move |tx| {
+				let owners = Arc::clone(&owners);
+				async move {
+					ensure_repair_state_unchanged(&tx, &state).await?;
+
+					let pidx_prefix = keys::branch_pidx_prefix(branch_id);
+					let existing = tx_scan_prefix(&tx, &pidx_prefix).await?;
+					let mut removed_bytes = 0i64;
+					let mut changed_rows = 0usize;
+					for (key, value) in &existing {
+						let pgno = decode_u32_suffix(&pidx_prefix, key)?;
+						let rebuilt = owners.get(&pgno).map(|txid| txid.to_be_bytes());
+						if rebuilt.as_ref().map(|txid| txid.as_slice()) != Some(value.as_slice()) {
+							changed_rows += 1;
+						}
+						removed_bytes += entry_size(key, value)?;
+						tx.informal().clear(key);
+					}
+
+					let mut added_bytes = 0i64;
+					let matched_rows = existing.len() - changed_rows;
+					for (pgno, txid) in owners.iter() {
+						let key = keys::branch_pidx_key(branch_id, *pgno);
+						let value = txid.to_be_bytes();
+						added_bytes += entry_size(&key, &value)?;
+						tx.informal().set(&key, &value);
+					}
+					// Every matched row is also a rebuilt row, the other rebuilt rows are new.
+					changed_rows += owners.len() - matched_rows;
+					quota::atomic_add_branch(&tx, branch_id, added_bytes - removed_bytes);
+
+					Ok((existing.len(), changed_rows))
+				}
+			})
+			.await?;
+
+		Ok(PageIndexRebuild {
+			database_branch_id: branch_id,
+			head_txid: state.head_txid,
+			previous_rows,
+			rebuilt_rows,
+			changed_rows,
+		})
+	}
+
+	/// Resets the branch quota counter to the bytes stored in the branch's head, commit, VTX,
+	/// delta, page index and shard rows.
+	pub async fn recompute_quota(&self) -> Result<QuotaRecompute> {
+		let state = self.read_repair_state().await?;
+		let branch_id = state.branch_id;
+
+		let mut stored_bytes = 0i64;
+		for prefix in [
+			keys::branch_commit_prefix(branch_id),
+			keys::branch_vtx_prefix(branch_id),
+			keys::branch_delta_prefix(branch_id),
+			keys::branch_pidx_prefix(branch_id),
+			keys::branch_shard_prefix(branch_id),
+		] {
+			for (key, value) in scan_prefix(&self.udb, prefix).await? {
+				stored_bytes = stored_bytes
+					.checked_add(entry_size(&key, &value)?)
+					.context("sqlite recomputed quota overflowed i64")?;
+			}
+		}
+
+		let (previous_bytes, recomputed_bytes) = self
+			.udb
+			.txn("depot_repair_recompute_quota", move |tx| async move {
+				ensure_repair_state_unchanged(&tx, &state).await?;
+
+				let head_key = keys::branch_meta_head_key(branch_id);
+				let head_bytes = match tx.informal().get(&head_key, Serializable).await? {
+					Some(value) => entry_size(&head_key, &value)?,
+					None => 0,
+				};
+				let previous_bytes = quota::read_branch(&tx, branch_id).await?;
+				let recomputed_bytes = stored_bytes
+					.checked_add(head_bytes)
+					.context("sqlite recomputed quota overflowed i64")?;
+				tx.informal().set(
+					&keys::branch_meta_quota_key(branch_id),
+					&recomputed_bytes.to_le_bytes(),
+				);
+
+				Ok((previous_bytes, recomputed_bytes))
+			})
+			.await?;
+
+		Ok(QuotaRecompute {
+			database_branch_id: branch_id,
+			previous_bytes,
+			recomputed_bytes,
+		})
+	}
+
+	/// Marks the branch dirty at its current head so the compaction manager schedules a hot
+	/// pass, and wakes it when this handle has a compaction signaler.
+	pub async fn rerun_compaction(&self) -> Result<CompactionRerun> {
+		let now_ms = now_ms()?;
+		let dirty = self
+			.udb
+			.txn("depot_repair_rerun_compaction", move |tx| {
+				let database_id = self.database_id.clone();
+				let bucket_id = self.sqlite_bucket_id();
+				async move {
+					let branch_id =
+						branch::resolve_database_branch(&tx, bucket_id, &database_id, Serializable)
+							.await?
+							.ok_or(SqliteStorageError::DatabaseNotFound)?;
+					let head_bytes = tx
+						.informal()
+						.get(&keys::branch_meta_head_key(branch_id), Serializable)
+						.await?
+						.context("sqlite database branch has no commits to compact")?;
+					let head = decode_db_head(&head_bytes)
+						.context("decode sqlite database branch head")?;
+					let dirty = SqliteCmpDirty {
+						observed_head_txid: head.head_txid,
+						updated_at_ms: now_ms,
+					};
+					tx.informal().set(
+						&keys::sqlite_cmp_dirty_key(branch_id),
+						&encode_sqlite_cmp_dirty(dirty.clone())?,
+					);
+
+					Ok((branch_id, dirty))
+				}
+			})
+			.await?;
+		let (branch_id, dirty) = dirty;
+
+		let signaled = match &self.compaction_signaler {
+			Some(signaler) => {
+				signaler(DeltasAvailable {
+					database_branch_id: branch_id,
+					observed_head_txid: dirty.observed_head_txid,
+					dirty_updated_at_ms: dirty.updated_at_ms,
+				})
+				.await?;
+				true
+			}
+			None => false,
+		};
+
+		Ok(CompactionRerun {
+			database_branch_id: branch_id,
+			observed_head_txid: dirty.observed_head_txid,
+			signaled,
+		})
+	}
+
+	/// Records the status of one repair action under the run's safety restore point.
+	///
+	/// The branch recorded with the first status of an action is kept on later updates, so a
+	/// truncate still names the branch it rolled back from.
+	pub async fn record_repair_action(
+		&self,
+		restore_point: &RestorePointId,
+		seq: u32,
+		action: RepairAction,
+		status: RepairActionStatus,
+		detail: Option<String>,
+	) -> Result<RepairJournalEntry> {
+		let now_ms = now_ms()?;
+		let key = keys::repair_journal_key(&self.database_id, restore_point.as_str(), seq);
+		self.udb
+			.txn("depot_repair_record_action", move |tx| {
+				let key = key.clone();
+				let detail = detail.clone();
+				let database_id = self.database_id.clone();
+				let bucket_id = self.sqlite_bucket_id();
+				async move {
+					let database_branch_id = match tx.informal().get(&key, Serializable).await? {
+						Some(existing) => {
+							decode_repair_journal_entry(&existing)?.database_branch_id
+						}
+						None => branch::resolve_database_branch(
+							&tx,
+							bucket_id,
+							&database_id,
+							Serializable,
+						)
+						.await?
+						.ok_or(SqliteStorageError::DatabaseNotFound)?,
+					};
+					let entry = RepairJournalEntry {
+						seq,
+						action,
+						status,
+						database_branch_id,
+						detail,
+						updated_at_ms: now_ms,
+					};
+					tx.informal()
+						.set(&key, &encode_repair_journal_entry(entry.clone())?);
+
+					Ok(entry)
+				}
+			})
+			.await
+	}
+
+	/// Lists the journaled actions of the repair run that took `restore_point`, in order.
+	pub async fn repair_journal(
+		&self,
+		restore_point: &RestorePointId,
+	) -> Result<Vec<RepairJournalEntry>> {
+		scan_prefix(
+			&self.udb,
+			keys::repair_journal_prefix(&self.database_id, restore_point.as_str()),
+		)
+		.await?
+		.into_iter()
+		.map(|(_, value)| decode_repair_journal_entry(&value))
+		.collect()
+	}
+
+	async fn read_repair_state(&self) -> Result<RepairState> {
+		let database_id = self.database_id.clone();
+		let bucket_id = self.sqlite_bucket_id();
+		self.udb
+			.txn("depot_repair_read_state", move |tx| {
+				let database_id = database_id.clone();
+				async move {
+					let branch_id =
+						branch::resolve_database_branch(&tx, bucket_id, &database_id, Snapshot)
+							.await?
+							.ok_or(SqliteStorageError::DatabaseNotFound)?;
+					read_branch_repair_state(&tx, branch_id).await
+				}
+			})
+			.await
+	}
+}
+
+async fn read_branch_repair_state(
+	tx: &universaldb::Transaction,
+	branch_id: DatabaseBranchId,
+) -> Result<RepairState> {
+	let head_txid = tx
+		.informal()
+		.get(&keys::branch_meta_head_key(branch_id), Serializable)
+		.await?
+		.map(|bytes| decode_db_head(&bytes))
+		.transpose()
+		.context("decode sqlite database branch head")?
+		.map(|head| head.head_txid);
+	let hot_watermark_txid = tx
+		.informal()
+		.get(&keys::branch_compaction_root_key(branch_id), Serializable)
+		.await?
+		.map(|bytes| decode_compaction_root(&bytes))
+		.transpose()
+		.context("decode sqlite compaction root")?
+		.map_or(0, |root| root.hot_watermark_txid);
+
+	Ok(RepairState {
+		branch_id,
+		head_txid,
+		hot_watermark_txid,
+	})
+}
+
+async fn ensure_repair_state_unchanged(
+	tx: &universaldb::Transaction,
+	expected: &RepairState,
+) -> Result<()> {
+	let current = read_branch_repair_state(tx, expected.branch_id).await?;
+	ensure!(
+		current.head_txid == expected.head_txid
+			&& current.hot_watermark_txid == expected.hot_watermark_txid,
+		"sqlite database changed during repair, stop the actor and retry"
+	);
+	Ok(())
+}
+
+/// Scans every row under `prefix` in bounded transactions.
+async fn scan_prefix(
+	udb: &universaldb::Database,
+	prefix: Vec<u8>,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+	let mut rows = Vec::new();
+	let mut last_key = None;
+	loop {
+		let chunk = udb
+			.txn("depot_repair_scan", {
+				let prefix = prefix.clone();
+				let last_key = last_key.clone();
+				move |tx| {
+					let prefix = prefix.clone();
+					let last_key = last_key.clone();
+					async move {
+						let (range_start, range_end) =
+							universaldb::tuple::Subspace::from_bytes(prefix).range();
+						let begin = last_key.map_or_else(
+							|| KeySelector::first_greater_or_equal(range_start),
+							KeySelector::first_greater_than,
+						);
+						let informal = tx.informal();
+						let mut stream = informal.get_ranges_keyvalues(
+							RangeOption {
+								begin,
+								end: KeySelector::first_greater_or_equal(range_end),
+								limit: Some(REPAIR_SCAN_CHUNK_ROWS),
+								mode: StreamingMode::WantAll,
+								..RangeOption::default()
+							},
+							Snapshot,
+						);
+						let mut rows = Vec::new();
+						while let Some(entry) = stream.try_next().await? {
+							rows.push((entry.key().to_vec(), entry.value().to_vec()));
+						}
+						Ok(rows)
+					}
+				}
+			})
+			.await?;
+
+		let exhausted = chunk.len() < REPAIR_SCAN_CHUNK_ROWS;
+		last_key = chunk.last().map(|(key, _)| key.clone());
+		rows.extend(chunk);
+		if exhausted {
+			return Ok(rows);
+		}
+	}
+}
+
+async fn tx_scan_prefix(
+	tx: &universaldb::Transaction,
+	prefix: &[u8],
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+	let prefix_subspace =
+		universaldb::Subspace::from(universaldb::tuple::Subspace::from_bytes(prefix.to_vec()));
+	let informal = tx.informal();
+	let mut stream = informal.get_ranges_keyvalues(
+		RangeOption {
+			mode: StreamingMode::WantAll,
+			..RangeOption::from(&prefix_subspace)
+		},
+		Serializable,
+	);
+	let mut rows = Vec::new();
+	while let Some(entry) = stream.try_next().await? {
+		rows.push((entry.key().to_vec(), entry.value().to_vec()));
+	}
+	Ok(rows)
+}
+
+fn entry_size(key: &[u8], value: &[u8]) -> Result<i64> {
+	i64::try_from(key.len() + value.len()).context("sqlite tracked entry size exceeded i64")
+}
+
+fn decode_u64_suffix(prefix: &[u8], key: &[u8]) -> Result<u64> {
+	let suffix = key
+		.strip_prefix(prefix)
+		.context("key did not start with expected prefix")?;
+	Ok(u64::from_be_bytes(
+		suffix.try_into().context("key suffix was not a u64")?,
+	))
+}
+
+fn decode_u32_suffix(prefix: &[u8], key: &[u8]) -> Result<u32> {
+	let suffix = key
+		.strip_prefix(prefix)
+		.context("key did not start with expected prefix")?;
+	Ok(u32::from_be_bytes(
+		suffix.try_into().context("key suffix was not a u32")?,
+	))
+}
+
+fn now_ms() -> Result<i64> {
+	let millis = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.context("system clock is before unix epoch")?
+		.as_millis();
+	i64::try_from(millis).context("current timestamp exceeded i64 milliseconds")
+}
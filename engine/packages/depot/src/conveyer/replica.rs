@@ -0,0 +1,347 @@
+//! Asynchronous replication of databases into other datacenters.
+//!
+//! The source side reads committed deltas after a replica's cursor, or a full snapshot when the
+//! deltas it needs are gone. The replica side commits the shipped pages into a separate database
+//! and records how far behind the source it is. Replicas are read-only copies: nothing but
+//! `apply_replica_batch` should commit to them.
+
+use anyhow::{Context, Result};
+use futures_util::{StreamExt, TryStreamExt, stream::BoxStream};
+use universaldb::{
+	RangeOption,
+	options::StreamingMode,
+	utils::IsolationLevel::{Serializable, Snapshot},
+};
+
+use super::{
+	Db, branch,
+	error::SqliteStorageError,
+	keys::{self, PAGE_SIZE},
+	ltx::decode_ltx_v3,
+	types::{
+		CommitOptions, DatabaseBranchId, DirtyPage, ReplicaBatch, ReplicaBatchKind, ReplicaCursor,
+		ReplicaDelta, ReplicaState, decode_commit_row, decode_db_head, decode_replica_state,
+		encode_replica_state,
+	},
+};
+
+/// Upper bound on the page bytes read into one set of replica changes, sized so a shipped batch
+/// stays within the default API request body limit. A single delta larger than this is still
+/// returned on its own.
+const MAX_REPLICA_CHANGES_BYTES: usize = 1024 * 1024;
+
+/// Database id of the local replica of an actor database owned by another datacenter.
+pub fn replica_database_id(actor_id: &str) -> String {
+	format!("replica/{actor_id}")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicaChanges {
+	/// Deltas following the replica cursor, oldest first. Empty when the replica is caught up.
+	Deltas {
+		head: ReplicaCursor,
+		deltas: Vec<ReplicaDelta>,
+	},
+	/// The replica cannot continue from its cursor and needs a snapshot.
+	ResyncRequired { reason: String },
+}
+
+pub struct ReplicaSnapshot {
+	/// Source position the snapshot reflects.
+	pub head: ReplicaCursor,
+	pub db_size_pages: u32,
+	/// Every page from 1 to `db_size_pages`, in order and in batches a single commit accepts.
+	pub pages: BoxStream<'static, Result<Vec<DirtyPage>>>,
+}
+
+impl Db {
+	/// Reads the committed deltas following `after`, up to `max_deltas`. Returns `None` while the
+	/// database has no commits.
+	pub async fn replica_changes_after(
+		&self,
+		after: Option<ReplicaCursor>,
+		max_deltas: usize,
+	) -> Result<Option<ReplicaChanges>> {
+		let Some(head) = self.replica_head().await? else {
+			return Ok(None);
+		};
+		let Some(after) = after else {
+			return Ok(Some(resync("replica has no snapshot")));
+		};
+		if after.database_branch_id != head.database_branch_id {
+			return Ok(Some(resync(
+				"source database was restored onto a new branch",
+			)));
+		}
+		if after.txid > head.txid {
+			return Ok(Some(resync("replica is ahead of the source head")));
+		}
+
+		let mut deltas = Vec::new();
+		let mut bytes = 0usize;
+		for txid in (after.txid + 1)..=head.txid {
+			if deltas.len() >= max_deltas || bytes >= MAX_REPLICA_CHANGES_BYTES {
+				break;
+			}
+			let Some(delta) = read_replica_delta(&self.udb, head.database_branch_id, txid).await?
+			else {
+				return Ok(Some(resync(&format!(
+					"delta for txid {txid} is no longer retained"
+				))));
+			};
+			bytes += delta.pages.len() * PAGE_SIZE as usize;
+			deltas.push(delta);
+		}
+
+		Ok(Some(ReplicaChanges::Deltas { head, deltas }))
+	}
+
+	/// Streams a consistent copy of the database for a replica resync. Returns `None` while the
+	/// database has no commits.
+	pub async fn replica_snapshot(&self) -> Result<Option<ReplicaSnapshot>> {
+		let Some(head) = self.replica_head().await? else {
+			return Ok(None);
+		};
+		let export = self.export_sqlite_file().await?;
+		let db_size_pages = u32::try_from(export.size_bytes / u64::from(PAGE_SIZE))
+			.context("sqlite export page count overflowed u32")?;
+
+		// A restore between resolving the branch and exporting leaves the cursor on the old
+		// branch, which only costs another resync.
+		let head = ReplicaCursor {
+			database_branch_id: head.database_branch_id,
+			txid: export.head_txid,
+		};
+		let pages = export
+			.chunks
+			.scan(1_u32, |next_pgno, chunk| {
+				let pages = chunk.map(|chunk| {
+					chunk
+						.chunks(PAGE_SIZE as usize)
+						.map(|bytes| {
+							let page = DirtyPage {
+								pgno: *next_pgno,
+								bytes: bytes.to_vec(),
+							};
+							*next_pgno += 1;
+							page
+						})
+						.collect::<Vec<_>>()
+				});
+				futures_util::future::ready(Some(pages))
+			})
+			.boxed();
+
+		Ok(Some(ReplicaSnapshot {
+			head,
+			db_size_pages,
+			pages,
+		}))
+	}
+
+	/// Commits a batch shipped from the source database into this replica and records the new
+	/// replication progress.
+	pub async fn apply_replica_batch(
+		&self,
+		batch: ReplicaBatch,
+		now_ms: i64,
+	) -> Result<ReplicaState> {
+		let previous = self.replica_state().await?;
+		let replica_options = CommitOptions {
+			expected_head_txid: None,
+			disable_size_cap: true,
+			skip_namespace_quota: true,
+			sqlite_import: None,
+		};
+
+		let state = match batch.kind {
+			ReplicaBatchKind::Snapshot {
+				db_size_pages,
+				pages,
+				last,
+			} => {
+				self.commit_with_options(pages, db_size_pages, now_ms, replica_options)
+					.await?;
+
+				ReplicaState {
+					source_dc_label: batch.source_dc_label,
+					cursor: last.then_some(batch.source_head),
+					caught_up_at_ms: if last {
+						batch.observed_at_ms
+					} else {
+						previous.map_or(0, |state| state.caught_up_at_ms)
+					},
+					resyncing: !last,
+					updated_at_ms: now_ms,
+				}
+			}
+			ReplicaBatchKind::Deltas(deltas) => {
+				let out_of_sync = |reason: String| SqliteStorageError::ReplicaOutOfSync { reason };
+				let Some(previous) = previous.filter(|state| !state.resyncing) else {
+					return Err(out_of_sync("replica has no snapshot".to_string()).into());
+				};
+				let Some(mut cursor) = previous.cursor else {
+					return Err(out_of_sync("replica has no snapshot".to_string()).into());
+				};
+				if cursor.database_branch_id != batch.source_head.database_branch_id {
+					return Err(out_of_sync(
+						"source database was restored onto a new branch".to_string(),
+					)
+					.into());
+				}
+
+				for delta in deltas {
+					// Batches are retried as a whole, so deltas applied by an earlier attempt are
+					// skipped.
+					if delta.txid <= cursor.txid {
+						continue;
+					}
+					if delta.txid != cursor.txid + 1 {
+						return Err(out_of_sync(format!(
+							"expected txid {}, received txid {}",
+							cursor.txid + 1,
+							delta.txid
+						))
+						.into());
+					}
+
+					self.commit_with_options(
+						delta.pages,
+						delta.db_size_pages,
+						now_ms,
+						replica_options,
+					)
+					.await?;
+					cursor.txid = delta.txid;
+				}
+
+				ReplicaState {
+					source_dc_label: batch.source_dc_label,
+					cursor: Some(cursor),
+					caught_up_at_ms: if cursor == batch.source_head {
+						batch.observed_at_ms.max(previous.caught_up_at_ms)
+					} else {
+						previous.caught_up_at_ms
+					},
+					resyncing: false,
+					updated_at_ms: now_ms,
+				}
+			}
+		};
+
+		let key = keys::replica_state_key(self.sqlite_bucket_id(), &self.database_id);
+		let encoded = encode_replica_state(state)?;
+		self.udb
+			.txn("depot_replica_write_state", move |tx| {
+				let key = key.clone();
+				let encoded = encoded.clone();
+				async move {
+					tx.informal().set(&key, &encoded);
+					Ok(())
+				}
+			})
+			.await?;
+
+		Ok(state)
+	}
+
+	/// Replication progress of this replica, or `None` if it never received a batch.
+	pub async fn replica_state(&self) -> Result<Option<ReplicaState>> {
+		let key = keys::replica_state_key(self.sqlite_bucket_id(), &self.database_id);
+		self.udb
+			.txn("depot_replica_read_state", move |tx| {
+				let key = key.clone();
+				async move {
+					tx.informal()
+						.get(&key, Serializable)
+						.await?
+						.map(|bytes| decode_replica_state(&bytes))
+						.transpose()
+				}
+			})
+			.await
+	}
+
+	async fn replica_head(&self) -> Result<Option<ReplicaCursor>> {
+		let database_id = self.database_id.clone();
+		let bucket_id = self.sqlite_bucket_id();
+		self.udb
+			.txn("depot_replica_read_head", move |tx| {
+				let database_id = database_id.clone();
+				async move {
+					let Some(branch_id) =
+						branch::resolve_database_branch(&tx, bucket_id, &database_id, Snapshot)
+							.await?
+					else {
+						return Ok(None);
+					};
+					let Some(head_bytes) = tx
+						.informal()
+						.get(&keys::branch_meta_head_key(branch_id), Snapshot)
+						.await?
+					else {
+						return Ok(None);
+					};
+					let head = decode_db_head(&head_bytes).context("decode sqlite db head")?;
+
+					Ok(Some(ReplicaCursor {
+						database_branch_id: branch_id,
+						txid: head.head_txid,
+					}))
+				}
+			})
+			.await
+	}
+}
+
+fn resync(reason: &str) -> ReplicaChanges {
+	ReplicaChanges::ResyncRequired {
+		reason: reason.to_string(),
+	}
+}
+
+/// Reads one committed delta. Returns `None` once compaction has folded it into shards.
+async fn read_replica_delta(
+	udb: &universaldb::Database,
+	branch_id: DatabaseBranchId,
+	txid: u64,
+) -> Result<Option<ReplicaDelta>> {
+	udb.txn("depot_replica_read_delta", move |tx| async move {
+		let Some(commit_bytes) = tx
+			.informal()
+			.get(&keys::branch_commit_key(branch_id, txid), Snapshot)
+			.await?
+		else {
+			return Ok(None);
+		};
+		let commit = decode_commit_row(&commit_bytes).context("decode sqlite commit row")?;
+
+		let prefix = keys::branch_delta_chunk_prefix(branch_id, txid);
+		let (begin, end) = universaldb::tuple::Subspace::from_bytes(prefix).range();
+		let chunks = tx
+			.informal()
+			.get_ranges_keyvalues(
+				RangeOption {
+					mode: StreamingMode::WantAll,
+					..(begin.as_slice(), end.as_slice()).into()
+				},
+				Snapshot,
+			)
+			.map_ok(|entry| entry.value().to_vec())
+			.try_collect::<Vec<_>>()
+			.await?;
+		if chunks.is_empty() {
+			return Ok(None);
+		}
+		let decoded = decode_ltx_v3(&chunks.concat())
+			.with_context(|| format!("decode sqlite delta for txid {txid}"))?;
+
+		Ok(Some(ReplicaDelta {
+			txid,
+			db_size_pages: commit.db_size_pages,
+			commit_ts_ms: commit.wall_clock_ms,
+			pages: decoded.pages,
+		}))
+	})
+	.await
+}
@@ -17,13 +17,16 @@ use super::{
 	error::SqliteStorageError,
 	metrics,
 	types::{
-		PinStatus, ResolvedRestoreTarget, ResolvedVersionstamp, RestorePointId, SnapshotSelector,
+		PinStatus, ResolvedRestoreTarget, ResolvedVersionstamp, RestorePointId, RestorePointRecord,
+		SnapshotSelector,
 	},
 };
 
-pub use pinned::{create_restore_point, delete_restore_point, restore_point_status};
-pub use resolve::{resolve_restore_point, resolve_restore_target};
-pub use restore::restore_database;
+pub use pinned::{
+	create_restore_point, delete_restore_point, list_restore_points, restore_point_status,
+};
+pub use resolve::{resolve_restore_point, resolve_restore_target, resolve_txid_target};
+pub use restore::{restore_database, restore_database_to_txid};
 
 impl Db {
 	pub async fn create_restore_point(&self, selector: SnapshotSelector) -> Result<RestorePointId> {
@@ -57,6 +60,10 @@ impl Db {
 		.await
 	}
 
+	pub async fn list_restore_points(&self) -> Result<Vec<RestorePointRecord>> {
+		list_restore_points(&self.udb, self.sqlite_bucket_id(), self.database_id.clone()).await
+	}
+
 	pub async fn delete_restore_point(&self, restore_point: RestorePointId) -> Result<()> {
 		delete_restore_point(
 			&self.udb,
@@ -124,6 +131,16 @@ impl Db {
 		)
 		.await
 	}
+
+	pub async fn restore_database_to_txid(&self, txid: u64) -> Result<RestorePointId> {
+		restore_database_to_txid(
+			&self.udb,
+			self.sqlite_bucket_id(),
+			self.database_id.clone(),
+			txid,
+		)
+		.await
+	}
 }
 
 fn restore_point_create_outcome(err: Option<&anyhow::Error>) -> &'static str {
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
-use universaldb::options::MutationType;
+use futures_util::TryStreamExt;
+use universaldb::RangeOption;
+use universaldb::options::{MutationType, StreamingMode};
 use universaldb::utils::IsolationLevel::{Serializable, Snapshot};
 
 use crate::conveyer::{
@@ -124,6 +126,42 @@ pub async fn restore_point_status(
 	.await
 }
 
+/// Lists every restore point of a database, oldest first.
+pub async fn list_restore_points(
+	udb: &universaldb::Database,
+	_bucket_id: BucketId,
+	database_id: String,
+) -> Result<Vec<RestorePointRecord>> {
+	udb.txn("depot_restore_point_list", move |tx| {
+		let database_id = database_id.clone();
+
+		async move {
+			let prefix_subspace = universaldb::Subspace::from(
+				universaldb::tuple::Subspace::from_bytes(keys::restore_point_prefix(&database_id)),
+			);
+			let informal = tx.informal();
+			let mut stream = informal.get_ranges_keyvalues(
+				RangeOption {
+					mode: StreamingMode::WantAll,
+					..RangeOption::from(&prefix_subspace)
+				},
+				Snapshot,
+			);
+
+			let mut records = Vec::new();
+			while let Some(entry) = stream.try_next().await? {
+				records.push(
+					decode_restore_point_record(entry.value())
+						.context("decode sqlite restore point record")?,
+				);
+			}
+
+			Ok(records)
+		}
+	})
+	.await
+}
+
 pub(super) async fn create_restore_point_for_resolved(
 	udb: &universaldb::Database,
 	bucket_id: BucketId,
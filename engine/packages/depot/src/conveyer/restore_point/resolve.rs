@@ -89,6 +89,38 @@ pub async fn resolve_restore_target(
 	.await
 }
 
+/// Resolves `txid` on the database's current branch.
+pub async fn resolve_txid_target(
+	udb: &universaldb::Database,
+	bucket_id: BucketId,
+	database_id: String,
+	txid: u64,
+) -> Result<ResolvedRestoreTarget> {
+	udb.txn("depot_restore_point_resolve_txid", move |tx| {
+		let database_id = database_id.clone();
+
+		async move {
+			let (branch_id, bucket_cap) =
+				resolve_visible_database_branch_for_restore_point(&tx, bucket_id, &database_id)
+					.await?;
+			let commit = read_commit_row(&tx, branch_id, txid).await?;
+			if commit.versionstamp > bucket_cap {
+				return Err(SqliteStorageError::RestoreTargetExpired.into());
+			}
+
+			Ok(ResolvedRestoreTarget {
+				database_branch_id: branch_id,
+				txid,
+				versionstamp: commit.versionstamp,
+				wall_clock_ms: commit.wall_clock_ms,
+				kind: SnapshotKind::AtTimestamp,
+				restore_point: None,
+			})
+		}
+	})
+	.await
+}
+
 async fn resolve_visible_database_branch_for_restore_point(
 	tx: &universaldb::Transaction,
 	bucket_id: BucketId,
@@ -7,7 +7,7 @@ use crate::conveyer::{
 	keys,
 	restore_point::{
 		pinned::create_restore_point_for_resolved_tx,
-		resolve::resolve_restore_target,
+		resolve::{resolve_restore_target, resolve_txid_target},
 		shared::{ResolvedRestorePointPin, RestorePointCreateResult},
 		test_hooks,
 	},
@@ -24,6 +24,28 @@ pub async fn restore_database(
 	selector: SnapshotSelector,
 ) -> Result<RestorePointId> {
 	let target = resolve_restore_target(udb, bucket_id, database_id.clone(), selector).await?;
+	restore_database_to_target(udb, bucket_id, database_id, target).await
+}
+
+/// Rolls the database back to `txid` on its current branch, dropping every later commit.
+///
+/// Like `restore_database`, the pre-restore head is pinned so the rollback can be undone.
+pub async fn restore_database_to_txid(
+	udb: &universaldb::Database,
+	bucket_id: BucketId,
+	database_id: String,
+	txid: u64,
+) -> Result<RestorePointId> {
+	let target = resolve_txid_target(udb, bucket_id, database_id.clone(), txid).await?;
+	restore_database_to_target(udb, bucket_id, database_id, target).await
+}
+
+async fn restore_database_to_target(
+	udb: &universaldb::Database,
+	bucket_id: BucketId,
+	database_id: String,
+	target: crate::conveyer::types::ResolvedRestoreTarget,
+) -> Result<RestorePointId> {
 	let undo =
 		capture_current_restore_point_for_restore(udb, bucket_id, database_id.clone()).await?;
 
@@ -0,0 +1,224 @@
+//! Row change log of a database, kept next to its pages.
+//!
+//! Clients that capture row changes send each commit's changes with the commit, and the log is
+//! written in the same transaction as the pages. It lives under the database branch, outside the
+//! SQLite file, so it is not visible to SQL, not exported and not counted against quota. The log
+//! is only complete while every commit up to the head carried one, which the meta row tracks.
+
+use anyhow::{Context, Result};
+use futures_util::TryStreamExt;
+use universaldb::{
+	RangeOption,
+	options::StreamingMode,
+	utils::{IsolationLevel::Snapshot, end_of_key_range},
+};
+
+use super::{
+	Db, branch,
+	constants::{
+		MAX_ROW_CHANGE_LOG_BYTES_PER_COMMIT, MAX_ROW_CHANGE_LOG_ENTRIES_PER_COMMIT,
+		MAX_ROW_CHANGE_LOG_ENTRY_BYTES,
+	},
+	error::SqliteStorageError,
+	keys,
+	types::{
+		DatabaseBranchId, LoggedRowChange, RowChangeLog, RowChangeLogAppend, RowChangeLogMeta,
+		RowChangePosition, decode_db_head, decode_row_change_log_meta, encode_row_change_log_meta,
+	},
+};
+use crate::encryption::{self, DataKey};
+
+/// Format byte in front of a stored change. It keeps a plaintext change from being mistaken for a
+/// sealed value.
+const ROW_CHANGE_FORMAT_V1: u8 = 1;
+
+/// Row changes of one commit, validated and sealed before the commit transaction.
+pub(crate) struct PreparedRowChanges {
+	changes: Vec<(u32, Vec<u8>)>,
+	floor: RowChangePosition,
+}
+
+impl PreparedRowChanges {
+	pub(crate) fn prepare(log: RowChangeLogAppend, data_key: Option<&DataKey>) -> Result<Self> {
+		let invalid = |reason: String| SqliteStorageError::InvalidRowChangeLog { reason };
+		if log.changes.len() > MAX_ROW_CHANGE_LOG_ENTRIES_PER_COMMIT {
+			return Err(invalid(format!(
+				"{} changes in one commit, limit is {MAX_ROW_CHANGE_LOG_ENTRIES_PER_COMMIT}",
+				log.changes.len()
+			))
+			.into());
+		}
+
+		let mut total_bytes = 0;
+		let mut previous_index = None;
+		let mut changes = Vec::with_capacity(log.changes.len());
+		for (index, change) in log.changes {
+			if previous_index.is_some_and(|previous| index <= previous) {
+				return Err(invalid(format!("change index {index} is out of order")).into());
+			}
+			previous_index = Some(index);
+			if change.len() > MAX_ROW_CHANGE_LOG_ENTRY_BYTES {
+				return Err(invalid(format!(
+					"change {index} is {} bytes, limit is {MAX_ROW_CHANGE_LOG_ENTRY_BYTES}",
+					change.len()
+				))
+				.into());
+			}
+			total_bytes += change.len();
+
+			let mut stored = Vec::with_capacity(change.len() + 1);
+			stored.push(ROW_CHANGE_FORMAT_V1);
+			stored.extend_from_slice(&change);
+			let stored = match data_key {
+				Some(data_key) => data_key.seal(&stored).context("encrypt row change")?,
+				None => stored,
+			};
+			changes.push((index, stored));
+		}
+		if total_bytes > MAX_ROW_CHANGE_LOG_BYTES_PER_COMMIT {
+			return Err(invalid(format!(
+				"{total_bytes} bytes of changes in one commit, limit is {MAX_ROW_CHANGE_LOG_BYTES_PER_COMMIT}"
+			))
+			.into());
+		}
+
+		Ok(Self {
+			changes,
+			floor: log.floor,
+		})
+	}
+
+	/// Writes the changes of the commit at `txid`, drops logged changes at or before the floor,
+	/// and records that the log covers `txid`.
+	pub(crate) fn write(
+		&self,
+		tx: &universaldb::Transaction,
+		branch_id: DatabaseBranchId,
+		txid: u64,
+	) -> Result<()> {
+		let invalid = |reason: String| SqliteStorageError::InvalidRowChangeLog { reason };
+		if self.floor.txid > txid {
+			return Err(invalid(format!(
+				"floor txid {} is past the commit txid {txid}",
+				self.floor.txid
+			))
+			.into());
+		}
+		if let Some(&(index, _)) = self.changes.first()
+			&& (RowChangePosition { txid, index }) <= self.floor
+		{
+			return Err(invalid(format!("change {index} is at or before the floor")).into());
+		}
+
+		tx.informal().clear_range(
+			&keys::branch_row_change_prefix(branch_id),
+			&end_of_key_range(&keys::branch_row_change_key(
+				branch_id,
+				self.floor.txid,
+				self.floor.index,
+			)),
+		);
+		for (index, stored) in &self.changes {
+			tx.informal().set(
+				&keys::branch_row_change_key(branch_id, txid, *index),
+				stored,
+			);
+		}
+		tx.informal().set(
+			&keys::branch_meta_row_changes_key(branch_id),
+			&encode_row_change_log_meta(RowChangeLogMeta {
+				covered_txid: txid,
+				floor: self.floor,
+			})?,
+		);
+
+		Ok(())
+	}
+}
+
+impl Db {
+	/// Reads the row change log at the database head. Returns `None` when the log does not cover
+	/// every commit up to the head, for example after a commit written without one.
+	pub async fn row_change_log(&self) -> Result<Option<RowChangeLog>> {
+		let database_id = self.database_id.clone();
+		let bucket_id = self.sqlite_bucket_id();
+		self.udb
+			.txn("depot_row_change_log_read", move |tx| {
+				let database_id = database_id.clone();
+				async move {
+					let Some(branch_id) =
+						branch::resolve_database_branch(&tx, bucket_id, &database_id, Snapshot)
+							.await?
+					else {
+						return Ok(None);
+					};
+					let Some(meta_bytes) = tx
+						.informal()
+						.get(&keys::branch_meta_row_changes_key(branch_id), Snapshot)
+						.await?
+					else {
+						return Ok(None);
+					};
+					let meta = decode_row_change_log_meta(&meta_bytes)?;
+					let Some(head_bytes) = tx
+						.informal()
+						.get(&keys::branch_meta_head_key(branch_id), Snapshot)
+						.await?
+					else {
+						return Ok(None);
+					};
+					let head = decode_db_head(&head_bytes).context("decode sqlite db head")?;
+					if head.head_txid != meta.covered_txid {
+						return Ok(None);
+					}
+
+					let begin = end_of_key_range(&keys::branch_row_change_key(
+						branch_id,
+						meta.floor.txid,
+						meta.floor.index,
+					));
+					let end = end_of_key_range(&keys::branch_row_change_key(
+						branch_id,
+						u64::MAX,
+						u32::MAX,
+					));
+					let rows = tx
+						.informal()
+						.get_ranges_keyvalues(
+							RangeOption {
+								mode: StreamingMode::WantAll,
+								..(begin.as_slice(), end.as_slice()).into()
+							},
+							Snapshot,
+						)
+						.map_ok(|entry| (entry.key().to_vec(), entry.value().to_vec()))
+						.try_collect::<Vec<_>>()
+						.await?;
+					let changes = rows
+						.into_iter()
+						.map(|(key, value)| {
+							let (txid, index) =
+								keys::decode_branch_row_change_key(branch_id, &key)?;
+							let stored = encryption::open_blob(&value)
+								.with_context(|| format!("decrypt row change {txid}/{index}"))?;
+							let change =
+								stored.strip_prefix(&[ROW_CHANGE_FORMAT_V1]).with_context(
+									|| format!("unknown format of row change {txid}/{index}"),
+								)?;
+
+							Ok(LoggedRowChange {
+								position: RowChangePosition { txid, index },
+								change: change.to_vec(),
+							})
+						})
+						.collect::<Result<Vec<_>>>()?;
+
+					Ok(Some(RowChangeLog {
+						floor: meta.floor,
+						changes,
+					}))
+				}
+			})
+			.await
+	}
+}
@@ -0,0 +1,295 @@
+//! Conversion between depot databases and standard SQLite database files.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use futures_util::{
+	StreamExt,
+	stream::{self, BoxStream},
+};
+use universaldb::utils::IsolationLevel::Snapshot;
+
+use super::{
+	Db, branch,
+	constants::MAX_COMMIT_DIRTY_PAGES,
+	error::SqliteStorageError,
+	keys::{self, PAGE_SIZE},
+	types::{
+		BucketId, CommitOptions, CommitResult, DatabaseBranchId, DepotReadMode, DirtyPage,
+		GetPagesOptions, SnapshotSelector, SqliteImportCommit, decode_db_head,
+	},
+};
+
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+
+/// Pages read per `get_pages` call while exporting.
+const EXPORT_BATCH_PAGES: u32 = 256;
+
+pub struct SqliteFileExport {
+	/// Txid of the commit the export reflects.
+	pub head_txid: u64,
+	pub size_bytes: u64,
+	/// Page-aligned chunks of the file, in order.
+	pub chunks: BoxStream<'static, Result<Vec<u8>>>,
+}
+
+impl Db {
+	/// Streams the database at its latest commit as a SQLite database file.
+	///
+	/// Reads go through a temporary fork so commits that land during the export do not tear the
+	/// file. The fork is deleted once the stream finishes or is dropped.
+	pub async fn export_sqlite_file(&self) -> Result<SqliteFileExport> {
+		let bucket = self.sqlite_bucket_id();
+		let snapshot_database_id = format!("export-{}", uuid::Uuid::new_v4().simple());
+		let snapshot_branch_id = branch::fork_database_into(
+			&self.udb,
+			bucket,
+			self.database_id.clone(),
+			SnapshotSelector::Latest,
+			bucket,
+			snapshot_database_id.clone(),
+		)
+		.await?;
+		let guard = ExportSnapshotGuard {
+			udb: self.udb.clone(),
+			bucket,
+			branch_id: snapshot_branch_id,
+		};
+		let snapshot = Arc::new(
+			Db::new(
+				self.udb.clone(),
+				self.bucket_id,
+				snapshot_database_id,
+				self.node_id,
+			)
+			.with_cold_store(self.cold_store.clone()),
+		);
+
+		let head = snapshot
+			.get_pages_with_options(vec![1], export_read_options())
+			.await?;
+		let db_size_pages = head.db_size_pages;
+
+		let chunks = stream::try_unfold(
+			(snapshot, guard, 1_u32),
+			move |(snapshot, guard, next_pgno)| async move {
+				if next_pgno > db_size_pages {
+					return Ok(None);
+				}
+
+				let last_pgno = next_pgno
+					.saturating_add(EXPORT_BATCH_PAGES - 1)
+					.min(db_size_pages);
+				let mut pages = snapshot
+					.get_pages_with_options(
+						(next_pgno..=last_pgno).collect(),
+						export_read_options(),
+					)
+					.await?
+					.pages;
+				pages.sort_by_key(|page| page.pgno);
+
+				let mut chunk = Vec::with_capacity(pages.len() * PAGE_SIZE as usize);
+				for page in pages {
+					// Pages inside the database size that were never written read as zeroes,
+					// matching a sparse file on disk.
+					match page.bytes {
+						Some(bytes) => chunk.extend_from_slice(&bytes),
+						None => chunk.resize(chunk.len() + PAGE_SIZE as usize, 0),
+					}
+				}
+
+				Ok(Some((chunk, (snapshot, guard, last_pgno + 1))))
+			},
+		)
+		.boxed();
+
+		Ok(SqliteFileExport {
+			head_txid: head.head_txid,
+			size_bytes: u64::from(db_size_pages) * u64::from(PAGE_SIZE),
+			chunks,
+		})
+	}
+
+	/// Writes a SQLite database file into this database through the commit path, so quota and
+	/// commit size limits apply as for any other write.
+	///
+	/// The database must be empty or hold an interrupted import, which is overwritten. Page 1 is
+	/// committed last so an interrupted import never carries a valid SQLite header, and every
+	/// earlier batch marks the branch as importing so a retry can take over.
+	pub async fn import_sqlite_file(&self, file: &[u8], now_ms: i64) -> Result<CommitResult> {
+		let db_size_pages = validate_sqlite_file(file)?;
+		let mut expected_head_txid = self.read_head_for_import().await?;
+
+		let page = |pgno: u32| DirtyPage {
+			pgno,
+			bytes: file
+				[(pgno as usize - 1) * PAGE_SIZE as usize..pgno as usize * PAGE_SIZE as usize]
+				.to_vec(),
+		};
+		let mut batches = (2..=db_size_pages)
+			.collect::<Vec<_>>()
+			.chunks(MAX_COMMIT_DIRTY_PAGES)
+			.map(|pgnos| pgnos.to_vec())
+			.collect::<Vec<_>>();
+		match batches.last_mut() {
+			Some(last) if last.len() < MAX_COMMIT_DIRTY_PAGES => last.push(1),
+			_ => batches.push(vec![1]),
+		}
+
+		let batch_count = batches.len();
+		let mut result = None;
+		for (idx, pgnos) in batches.into_iter().enumerate() {
+			let sqlite_import = if idx + 1 == batch_count {
+				SqliteImportCommit::Final
+			} else {
+				SqliteImportCommit::Partial
+			};
+			let res = self
+				.commit_with_options(
+					pgnos.into_iter().map(page).collect(),
+					db_size_pages,
+					now_ms,
+					CommitOptions {
+						expected_head_txid,
+						sqlite_import: Some(sqlite_import),
+						..Default::default()
+					},
+				)
+				.await?;
+			expected_head_txid = Some(res.head_txid);
+			result = Some(res);
+		}
+
+		result.context("sqlite import committed no pages")
+	}
+
+	/// Returns the head txid to fence the first import commit on, failing if the database already
+	/// has pages that were not written by an interrupted import.
+	async fn read_head_for_import(&self) -> Result<Option<u64>> {
+		let bucket = self.sqlite_bucket_id();
+		let database_id = self.database_id.clone();
+
+		self.udb
+			.txn("depot_import_read_head", move |tx| {
+				let database_id = database_id.clone();
+
+				async move {
+					let Some(branch_id) =
+						branch::resolve_database_branch(&tx, bucket, &database_id, Snapshot)
+							.await?
+					else {
+						return Ok(None);
+					};
+					let Some(head_bytes) = tx
+						.informal()
+						.get(&keys::branch_meta_head_key(branch_id), Snapshot)
+						.await?
+					else {
+						return Ok(None);
+					};
+					let head = decode_db_head(&head_bytes).context("decode sqlite db head")?;
+					if head.db_size_pages > 0
+						&& tx
+							.informal()
+							.get(&keys::branch_meta_import_key(branch_id), Snapshot)
+							.await?
+							.is_none()
+					{
+						return Err(SqliteStorageError::DatabaseAlreadyExists.into());
+					}
+
+					Ok(Some(head.head_txid))
+				}
+			})
+			.await
+	}
+}
+
+/// Returns the page count of a SQLite database file that depot can store.
+pub fn validate_sqlite_file(file: &[u8]) -> Result<u32> {
+	let invalid = |reason: String| SqliteStorageError::InvalidSqliteFile { reason };
+
+	if file.len() < 100 || &file[..SQLITE_MAGIC.len()] != SQLITE_MAGIC {
+		return Err(invalid("missing SQLite header".to_string()).into());
+	}
+
+	let raw_page_size = u16::from_be_bytes([file[16], file[17]]);
+	let page_size = if raw_page_size == 1 {
+		65_536
+	} else {
+		u32::from(raw_page_size)
+	};
+	if page_size != PAGE_SIZE {
+		return Err(invalid(format!(
+			"page size {page_size} is not supported, expected {PAGE_SIZE}"
+		))
+		.into());
+	}
+	if file.len() % PAGE_SIZE as usize != 0 {
+		return Err(invalid(format!(
+			"file size {} is not a multiple of the page size",
+			file.len()
+		))
+		.into());
+	}
+	// The depot VFS runs SQLite in rollback journal mode, which cannot open a WAL-mode file.
+	if file[18] == 2 || file[19] == 2 {
+		return Err(invalid("file is in WAL mode".to_string()).into());
+	}
+	if file[20] != 0 {
+		return Err(invalid(format!(
+			"reserved space of {} bytes per page is not supported",
+			file[20]
+		))
+		.into());
+	}
+
+	let db_size_pages = u32::try_from(file.len() / PAGE_SIZE as usize)
+		.map_err(|_| invalid("file has too many pages".to_string()))?;
+	// The header page count is only valid when its version-valid-for number matches the change
+	// counter. Older writers leave it stale, in which case SQLite sizes the file by its length.
+	let header_pages = u32::from_be_bytes([file[28], file[29], file[30], file[31]]);
+	let change_counter = &file[24..28];
+	let version_valid_for = &file[92..96];
+	if header_pages != 0 && change_counter == version_valid_for && header_pages != db_size_pages {
+		return Err(invalid(format!(
+			"header page count {header_pages} does not match file page count {db_size_pages}"
+		))
+		.into());
+	}
+
+	Ok(db_size_pages)
+}
+
+fn export_read_options() -> GetPagesOptions {
+	GetPagesOptions {
+		mode: DepotReadMode::DiagnosticNoSideEffects,
+		..Default::default()
+	}
+}
+
+/// Deletes the temporary fork backing an export.
+struct ExportSnapshotGuard {
+	udb: Arc<universaldb::Database>,
+	bucket: BucketId,
+	branch_id: DatabaseBranchId,
+}
+
+impl Drop for ExportSnapshotGuard {
+	fn drop(&mut self) {
+		let udb = self.udb.clone();
+		let bucket = self.bucket;
+		let branch_id = self.branch_id;
+
+		let Ok(handle) = tokio::runtime::Handle::try_current() else {
+			tracing::warn!(?branch_id, "no runtime to delete sqlite export snapshot");
+			return;
+		};
+		handle.spawn(async move {
+			if let Err(err) = branch::delete_database(&udb, bucket, branch_id).await {
+				tracing::warn!(?err, ?branch_id, "failed to delete sqlite export snapshot");
+			}
+		});
+	}
+}
@@ -1,20 +1,28 @@
 mod branch;
 mod compaction;
+mod encryption;
 mod history_pin;
 mod ids;
 mod pages;
 mod policy;
+mod repair;
+mod replica;
 mod restore_points;
+mod row_changes;
 mod serialization;
 mod storage;
 
 pub use branch::*;
 pub use compaction::*;
+pub use encryption::*;
 pub use history_pin::*;
 pub use ids::*;
 pub use pages::*;
 pub use policy::*;
+pub use repair::*;
+pub use replica::*;
 pub use restore_points::*;
+pub use row_changes::*;
 pub use serialization::*;
 pub use storage::*;
 
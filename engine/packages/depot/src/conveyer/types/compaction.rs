@@ -14,6 +14,14 @@ pub struct CompactionRoot {
 	pub cold_watermark_versionstamp: [u8; 16],
 }
 
+/// Pointer from a `/COLD/shard/` index row to the object holding the shard blob.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColdShardRef {
+	pub object_key: String,
+	pub size_bytes: u64,
+	pub content_hash: [u8; 32],
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SqliteCmpDirty {
 	pub observed_head_txid: u64,
@@ -79,6 +87,10 @@ enum VersionedCompactionRoot {
 	V1(CompactionRoot),
 }
 
+enum VersionedColdShardRef {
+	V1(ColdShardRef),
+}
+
 enum VersionedSqliteCmpDirty {
 	V1(SqliteCmpDirty),
 }
@@ -96,6 +108,7 @@ enum VersionedBucketCatalogDbFact {
 }
 
 impl_compaction_versioned_data!(VersionedCompactionRoot, CompactionRoot, "CompactionRoot");
+impl_compaction_versioned_data!(VersionedColdShardRef, ColdShardRef, "ColdShardRef");
 impl_compaction_versioned_data!(VersionedSqliteCmpDirty, SqliteCmpDirty, "SqliteCmpDirty");
 impl_compaction_versioned_data!(
 	VersionedPitrIntervalCoverage,
@@ -120,6 +133,17 @@ pub fn decode_compaction_root(payload: &[u8]) -> Result<CompactionRoot> {
 		.context("decode sqlite compaction root")
 }
 
+pub fn encode_cold_shard_ref(cold_ref: ColdShardRef) -> Result<Vec<u8>> {
+	VersionedColdShardRef::wrap_latest(cold_ref)
+		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
+		.context("encode sqlite cold shard ref")
+}
+
+pub fn decode_cold_shard_ref(payload: &[u8]) -> Result<ColdShardRef> {
+	VersionedColdShardRef::deserialize_with_embedded_version(payload)
+		.context("decode sqlite cold shard ref")
+}
+
 pub fn encode_sqlite_cmp_dirty(dirty: SqliteCmpDirty) -> Result<Vec<u8>> {
 	VersionedSqliteCmpDirty::wrap_latest(dirty)
 		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
@@ -0,0 +1,59 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use vbare::OwnedVersionedData;
+
+use super::serialization::SQLITE_STORAGE_META_VERSION;
+
+/// Current data key of one bucket, wrapped by the master key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataKeyRecord {
+	/// Bumped every time the bucket's data key is replaced.
+	pub version: u32,
+	pub master_key_fingerprint: [u8; 8],
+	pub wrap_nonce: [u8; 12],
+	/// Data key sealed with AES-256-GCM under the master key, tag included.
+	pub wrapped_key: Vec<u8>,
+	pub created_at_ms: i64,
+}
+
+enum VersionedDataKeyRecord {
+	V1(DataKeyRecord),
+}
+
+impl OwnedVersionedData for VersionedDataKeyRecord {
+	type Latest = DataKeyRecord;
+
+	fn wrap_latest(latest: Self::Latest) -> Self {
+		Self::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			Self::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(Self::V1(rivet_util::serde::bare_from_slice!(payload)?)),
+			_ => bail!("invalid depot DataKeyRecord version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			Self::V1(data) => rivet_util::serde::bare_to_vec!(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub fn encode_data_key_record(record: DataKeyRecord) -> Result<Vec<u8>> {
+	VersionedDataKeyRecord::wrap_latest(record)
+		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
+		.context("encode sqlite data key record")
+}
+
+pub fn decode_data_key_record(payload: &[u8]) -> Result<DataKeyRecord> {
+	VersionedDataKeyRecord::deserialize_with_embedded_version(payload)
+		.context("decode sqlite data key record")
+}
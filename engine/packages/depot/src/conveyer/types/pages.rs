@@ -90,6 +90,20 @@ pub enum PageSourceCandidateResult {
 pub struct CommitOptions {
 	pub expected_head_txid: Option<u64>,
 	pub disable_size_cap: bool,
+	/// Skips the namespace database limits. Used for copies of databases owned elsewhere.
+	pub skip_namespace_quota: bool,
+	/// Set on the commits of a SQLite file import.
+	pub sqlite_import: Option<SqliteImportCommit>,
+}
+
+/// Position of a commit within a SQLite file import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqliteImportCommit {
+	/// A batch before the one carrying page 1. Marks the import as in progress.
+	Partial,
+	/// The batch carrying page 1. Clears the in-progress mark.
+	Final,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
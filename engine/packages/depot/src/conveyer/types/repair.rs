@@ -0,0 +1,79 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use vbare::OwnedVersionedData;
+
+use super::ids::DatabaseBranchId;
+use super::serialization::SQLITE_STORAGE_META_VERSION;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepairAction {
+	/// Rewrite `/PIDX` from the delta history above the hot compaction watermark.
+	RebuildPageIndex,
+	/// Reset the branch quota counter to the bytes of the rows commits charge for.
+	RecomputeQuota,
+	/// Roll the database back to `txid` on its current branch.
+	TruncateToTxid { txid: u64 },
+	/// Mark the branch dirty so the compaction manager schedules a hot pass.
+	RerunCompaction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepairActionStatus {
+	Started,
+	Applied,
+	Failed,
+}
+
+/// One action of a repair run, keyed by the safety restore point taken before the run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairJournalEntry {
+	pub seq: u32,
+	pub action: RepairAction,
+	pub status: RepairActionStatus,
+	/// Branch that was current when the action started.
+	pub database_branch_id: DatabaseBranchId,
+	pub detail: Option<String>,
+	pub updated_at_ms: i64,
+}
+
+enum VersionedRepairJournalEntry {
+	V1(RepairJournalEntry),
+}
+
+impl OwnedVersionedData for VersionedRepairJournalEntry {
+	type Latest = RepairJournalEntry;
+
+	fn wrap_latest(latest: Self::Latest) -> Self {
+		Self::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			Self::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(Self::V1(rivet_util::serde::bare_from_slice!(payload)?)),
+			_ => bail!("invalid depot RepairJournalEntry version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			Self::V1(data) => rivet_util::serde::bare_to_vec!(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub fn encode_repair_journal_entry(entry: RepairJournalEntry) -> Result<Vec<u8>> {
+	VersionedRepairJournalEntry::wrap_latest(entry)
+		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
+		.context("encode sqlite repair journal entry")
+}
+
+pub fn decode_repair_journal_entry(payload: &[u8]) -> Result<RepairJournalEntry> {
+	VersionedRepairJournalEntry::deserialize_with_embedded_version(payload)
+		.context("decode sqlite repair journal entry")
+}
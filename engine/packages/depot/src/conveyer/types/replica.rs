@@ -0,0 +1,157 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use vbare::OwnedVersionedData;
+
+use super::ids::DatabaseBranchId;
+use super::pages::DirtyPage;
+use super::serialization::SQLITE_STORAGE_META_VERSION;
+
+/// Position of a replica in the source database's history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplicaCursor {
+	/// Source branch the txid belongs to. A restore moves the source to a new branch, which
+	/// forces a resync.
+	pub database_branch_id: DatabaseBranchId,
+	pub txid: u64,
+}
+
+/// One committed source transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaDelta {
+	pub txid: u64,
+	pub db_size_pages: u32,
+	pub commit_ts_ms: i64,
+	pub pages: Vec<DirtyPage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplicaBatchKind {
+	/// Consecutive deltas following the replica's cursor. Empty batches only refresh how recently
+	/// the replica was known to be caught up.
+	Deltas(Vec<ReplicaDelta>),
+	/// Part of a full copy of the source at `source_head`. The replica is unreadable until the
+	/// batch with `last` set lands.
+	Snapshot {
+		db_size_pages: u32,
+		pages: Vec<DirtyPage>,
+		last: bool,
+	},
+}
+
+/// Changes shipped from the source datacenter to a replica datacenter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaBatch {
+	pub source_dc_label: u16,
+	/// Source head when the batch was read.
+	pub source_head: ReplicaCursor,
+	/// Source wall clock when `source_head` was read.
+	pub observed_at_ms: i64,
+	pub kind: ReplicaBatchKind,
+}
+
+/// Replication progress of a replica database, stored in the replica datacenter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaState {
+	pub source_dc_label: u16,
+	/// Last source transaction applied. `None` until the first snapshot completes.
+	pub cursor: Option<ReplicaCursor>,
+	/// Source wall clock at which the source head was last known to equal `cursor`.
+	pub caught_up_at_ms: i64,
+	pub resyncing: bool,
+	pub updated_at_ms: i64,
+}
+
+impl ReplicaState {
+	/// How far behind the source the replica may be, or `None` while it is unreadable.
+	pub fn staleness_ms(&self, now_ms: i64) -> Option<i64> {
+		if self.resyncing || self.cursor.is_none() {
+			return None;
+		}
+
+		Some(now_ms.saturating_sub(self.caught_up_at_ms).max(0))
+	}
+}
+
+enum VersionedReplicaBatch {
+	V1(ReplicaBatch),
+}
+
+impl OwnedVersionedData for VersionedReplicaBatch {
+	type Latest = ReplicaBatch;
+
+	fn wrap_latest(latest: Self::Latest) -> Self {
+		Self::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			Self::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(Self::V1(rivet_util::serde::bare_from_slice!(payload)?)),
+			_ => bail!("invalid depot ReplicaBatch version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			Self::V1(data) => rivet_util::serde::bare_to_vec!(&data).map_err(Into::into),
+		}
+	}
+}
+
+enum VersionedReplicaState {
+	V1(ReplicaState),
+}
+
+impl OwnedVersionedData for VersionedReplicaState {
+	type Latest = ReplicaState;
+
+	fn wrap_latest(latest: Self::Latest) -> Self {
+		Self::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			Self::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(Self::V1(rivet_util::serde::bare_from_slice!(payload)?)),
+			_ => bail!("invalid depot ReplicaState version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			Self::V1(data) => rivet_util::serde::bare_to_vec!(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub fn encode_replica_batch(batch: ReplicaBatch) -> Result<Vec<u8>> {
+	VersionedReplicaBatch::wrap_latest(batch)
+		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
+		.context("encode sqlite replica batch")
+}
+
+pub fn decode_replica_batch(payload: &[u8]) -> Result<ReplicaBatch> {
+	VersionedReplicaBatch::deserialize_with_embedded_version(payload)
+		.context("decode sqlite replica batch")
+}
+
+pub fn encode_replica_state(state: ReplicaState) -> Result<Vec<u8>> {
+	VersionedReplicaState::wrap_latest(state)
+		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
+		.context("encode sqlite replica state")
+}
+
+pub fn decode_replica_state(payload: &[u8]) -> Result<ReplicaState> {
+	VersionedReplicaState::deserialize_with_embedded_version(payload)
+		.context("decode sqlite replica state")
+}
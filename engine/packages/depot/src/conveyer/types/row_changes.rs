@@ -0,0 +1,88 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use vbare::OwnedVersionedData;
+
+use super::serialization::SQLITE_STORAGE_META_VERSION;
+
+/// Position of a change in the row change log: the commit txid and the index of the change in
+/// that commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RowChangePosition {
+	pub txid: u64,
+	pub index: u32,
+}
+
+/// Row change encoded by the client. Depot stores it without decoding it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoggedRowChange {
+	pub position: RowChangePosition,
+	pub change: Vec<u8>,
+}
+
+/// Row changes written in the same transaction as a commit's pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowChangeLogAppend {
+	/// Changes of the commit as `(index, change)`. Their txid is the commit's.
+	pub changes: Vec<(u32, Vec<u8>)>,
+	/// Logged changes at or before this position are dropped.
+	pub floor: RowChangePosition,
+}
+
+/// Row change log of a database, read at its head.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowChangeLog {
+	/// Changes at or before this position are not in the log.
+	pub floor: RowChangePosition,
+	/// Changes after `floor`, oldest first.
+	pub changes: Vec<LoggedRowChange>,
+}
+
+/// Which commits the row change log covers, stored next to the branch head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowChangeLogMeta {
+	/// Last commit written with a row change log. The log is complete only while this is the head.
+	pub covered_txid: u64,
+	pub floor: RowChangePosition,
+}
+
+enum VersionedRowChangeLogMeta {
+	V1(RowChangeLogMeta),
+}
+
+impl OwnedVersionedData for VersionedRowChangeLogMeta {
+	type Latest = RowChangeLogMeta;
+
+	fn wrap_latest(latest: Self::Latest) -> Self {
+		Self::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			Self::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(Self::V1(rivet_util::serde::bare_from_slice!(payload)?)),
+			_ => bail!("invalid depot RowChangeLogMeta version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			Self::V1(data) => rivet_util::serde::bare_to_vec!(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub fn encode_row_change_log_meta(meta: RowChangeLogMeta) -> Result<Vec<u8>> {
+	VersionedRowChangeLogMeta::wrap_latest(meta)
+		.serialize_with_embedded_version(SQLITE_STORAGE_META_VERSION)
+		.context("encode sqlite row change log meta")
+}
+
+pub fn decode_row_change_log_meta(payload: &[u8]) -> Result<RowChangeLogMeta> {
+	VersionedRowChangeLogMeta::deserialize_with_embedded_version(payload)
+		.context("decode sqlite row change log meta")
+}
@@ -23,6 +23,7 @@ use universaldb::{
 };
 use uuid::Uuid;
 
+use crate::cold::{ColdStore, drain::content_hash};
 use crate::conveyer::{
 	branch,
 	db::Db,
@@ -31,10 +32,11 @@ use crate::conveyer::{
 	types::{
 		BucketId, CommitRow, DatabaseBranchId, DatabaseBranchRecord, DepotReadMode,
 		GetPagesOptions, PageSourceKind, PageSourceProvenance as DepotPageSourceProvenance,
-		decode_bucket_pointer, decode_commit_row, decode_database_branch_record,
-		decode_database_pointer, decode_db_head,
+		decode_bucket_pointer, decode_cold_shard_ref, decode_commit_row,
+		decode_database_branch_record, decode_database_pointer, decode_db_head,
 	},
 };
+use crate::encryption;
 
 const SQLITE_CHECK_ROW_LIMIT: usize = 50;
 const REPORT_ROW_LIMIT: usize = 100_000;
@@ -49,6 +51,8 @@ pub struct DoctorInput {
 	pub skip: SkipOptions,
 	pub min_txid: Option<u64>,
 	pub max_txid: Option<u64>,
+	/// Cold store used to resolve and verify offloaded shard versions.
+	pub cold_store: Option<Arc<dyn ColdStore>>,
 	#[doc(hidden)]
 	pub progress_hook: Option<DoctorProgressHook>,
 }
@@ -61,6 +65,10 @@ impl std::fmt::Debug for DoctorInput {
 			.field("skip", &self.skip)
 			.field("min_txid", &self.min_txid)
 			.field("max_txid", &self.max_txid)
+			.field(
+				"cold_store",
+				&self.cold_store.as_ref().map(|_| "<cold_store>"),
+			)
 			.field(
 				"progress_hook",
 				&self.progress_hook.as_ref().map(|_| "<hook>"),
@@ -117,6 +125,8 @@ pub enum CorruptionClass {
 	DeltaHistory,
 	DepotReconstruction,
 	HotCompactionState,
+	ColdTier,
+	Encryption,
 	SqliteStructure,
 	Unknown,
 	Unsupported,
@@ -581,6 +591,7 @@ pub async fn doctor(db: &universaldb::Database, input: DoctorInput) -> Result<Do
 			build_resolver_image(
 				&resolver_path,
 				db,
+				input.cold_store.as_ref(),
 				&resolved,
 				&storage,
 				&replay,
@@ -646,6 +657,7 @@ pub async fn doctor(db: &universaldb::Database, input: DoctorInput) -> Result<Do
 				find_first_resolver_divergence(
 					&temp_dir,
 					db,
+					input.cold_store.as_ref(),
 					&resolved,
 					&storage,
 					first_bad_min_txid,
@@ -675,6 +687,11 @@ pub async fn doctor(db: &universaldb::Database, input: DoctorInput) -> Result<Do
 	let versionstamp_index_analysis = analyze_vtx(&storage);
 	let delta_integrity_analysis = analyze_delta_integrity(&replay);
 	let pidx_integrity_analysis = preliminary_pidx_integrity;
+	tracing::info!(phase = "cold_tier", "verifying depot cold tier objects");
+	let cold_tier_analysis =
+		analyze_cold_tier(db, selected_branch, input.cold_store.as_deref()).await?;
+	tracing::info!(phase = "encryption", "verifying depot page blobs decrypt");
+	let encryption_analysis = analyze_encryption(db, selected_branch).await?;
 
 	let mut verdict = classify_report(
 		&sequential_checks,
@@ -692,6 +709,37 @@ pub async fn doctor(db: &universaldb::Database, input: DoctorInput) -> Result<Do
 	);
 	if changed {
 		verdict.reason = Some("database_changed_during_diagnosis");
+	} else if encryption_analysis
+		.get("skip_reason")
+		.and_then(Value::as_str)
+		== Some("master_key_not_configured")
+	{
+		// Sealed blobs fail to decode without the key, which would otherwise read as delta
+		// corruption and plan a truncate.
+		verdict = DoctorVerdict {
+			verdict: DoctorVerdictKind::Inconclusive,
+			corruption_class: CorruptionClass::Unknown,
+			unsupported_reason: None,
+			reason: Some("encryption_key_not_configured"),
+			message: "page blobs are encrypted but no sqlite encryption master key is configured"
+				.to_string(),
+		};
+	} else if !analysis_ok(&encryption_analysis) {
+		verdict = DoctorVerdict {
+			verdict: DoctorVerdictKind::Corrupt,
+			corruption_class: CorruptionClass::Encryption,
+			unsupported_reason: None,
+			reason: Some("encryption_failed"),
+			message: "encrypted page blobs fail to decrypt".to_string(),
+		};
+	} else if verdict.verdict == DoctorVerdictKind::Healthy && !analysis_ok(&cold_tier_analysis) {
+		verdict = DoctorVerdict {
+			verdict: DoctorVerdictKind::Corrupt,
+			corruption_class: CorruptionClass::ColdTier,
+			unsupported_reason: None,
+			reason: Some("cold_tier_failed"),
+			message: "cold tier objects are missing or fail checksum verification".to_string(),
+		};
 	}
 
 	let first_bad_txid = first_bad
@@ -767,6 +815,8 @@ pub async fn doctor(db: &universaldb::Database, input: DoctorInput) -> Result<Do
 		}),
 		"truncate_regrow": analyze_truncate_regrow(&storage.commits, &replay),
 		"storage_consistency": analyze_storage_consistency(&storage, &replay, selected_txid),
+		"cold_tier": cold_tier_analysis,
+		"encryption": encryption_analysis,
 	});
 
 	let mut report = DoctorReport {
@@ -1675,6 +1725,7 @@ fn build_sequential_replay(
 async fn build_resolver_image(
 	path: &Path,
 	udb: &universaldb::Database,
+	cold_store: Option<&Arc<dyn ColdStore>>,
 	resolved: &ResolvedIdentity,
 	storage: &StorageFacts,
 	replay: &ReplayResult,
@@ -1708,7 +1759,8 @@ async fn build_resolver_image(
 		Id::v1(bucket_id.as_uuid(), 0),
 		database_id.clone(),
 		NodeId::new(),
-	);
+	)
+	.with_cold_store(cold_store.cloned());
 	let pgnos = (1..=storage.selected_db_size_pages).collect::<Vec<_>>();
 	let pages = depot_db
 		.get_pages_with_options(
@@ -2249,6 +2301,7 @@ fn find_first_bad_txid(
 async fn find_first_resolver_divergence(
 	temp_dir: &TempDir,
 	db: &universaldb::Database,
+	cold_store: Option<&Arc<dyn ColdStore>>,
 	resolved: &ResolvedIdentity,
 	storage: &StorageFacts,
 	min_txid: u64,
@@ -2281,6 +2334,7 @@ async fn find_first_resolver_divergence(
 		let resolver = build_resolver_image(
 			&resolver_path,
 			db,
+			cold_store,
 			resolved,
 			&storage_at_txid,
 			&replay,
@@ -2586,6 +2640,131 @@ fn analyze_storage_consistency(
 	})
 }
 
+async fn analyze_cold_tier(
+	db: &universaldb::Database,
+	branch_id: DatabaseBranchId,
+	cold_store: Option<&dyn ColdStore>,
+) -> Result<Value> {
+	let prefix = keys::branch_cold_shard_prefix(branch_id);
+	let rows = scan_prefix_rows(db, prefix, "cold_shards").await?;
+	if rows.is_empty() {
+		return Ok(json!({ "ok": true, "entry_count": 0 }));
+	}
+	let Some(cold_store) = cold_store else {
+		return Ok(json!({
+			"skipped": true,
+			"skip_reason": "cold_store_not_configured",
+			"entry_count": rows.len(),
+		}));
+	};
+
+	let mut failures = Vec::new();
+	for row in &rows {
+		let (shard_id, as_of_txid) = keys::decode_branch_cold_shard_key(branch_id, &row.key)?;
+		let cold_ref = decode_cold_shard_ref(&row.value).context("decode cold shard ref")?;
+		let status = match cold_store.get(&cold_ref.object_key).await {
+			Ok(Some(bytes))
+				if bytes.len() as u64 == cold_ref.size_bytes
+					&& content_hash(&bytes) == cold_ref.content_hash =>
+			{
+				continue;
+			}
+			Ok(Some(_)) => "checksum_mismatch",
+			Ok(None) => "missing",
+			Err(err) => {
+				tracing::warn!(
+					?err,
+					shard_id,
+					as_of_txid,
+					"failed to read cold shard object"
+				);
+				"unreadable"
+			}
+		};
+		failures.push(json!({
+			"shard_id": shard_id,
+			"as_of_txid": as_of_txid,
+			"size_bytes": cold_ref.size_bytes,
+			"status": status,
+		}));
+	}
+
+	Ok(json!({
+		"ok": failures.is_empty(),
+		"entry_count": rows.len(),
+		"failures": LimitedRows::from_rows(failures),
+	}))
+}
+
+async fn analyze_encryption(
+	db: &universaldb::Database,
+	branch_id: DatabaseBranchId,
+) -> Result<Value> {
+	let mut deltas = BTreeMap::<u64, BTreeMap<u32, Vec<u8>>>::new();
+	for row in
+		scan_prefix_rows(db, keys::branch_delta_prefix(branch_id), "encryption_delta").await?
+	{
+		let txid = keys::decode_branch_delta_chunk_txid(branch_id, &row.key)?;
+		let chunk_idx = keys::decode_branch_delta_chunk_idx(branch_id, txid, &row.key)?;
+		deltas.entry(txid).or_default().insert(chunk_idx, row.value);
+	}
+	let shards =
+		scan_prefix_rows(db, keys::branch_shard_prefix(branch_id), "encryption_shard").await?;
+
+	let blobs = deltas
+		.into_iter()
+		.map(|(txid, chunks)| {
+			(
+				json!({ "kind": "delta", "txid": txid }),
+				chunks.into_values().flatten().collect::<Vec<_>>(),
+			)
+		})
+		.chain(shards.into_iter().map(|row| {
+			let (shard_id, as_of_txid) =
+				decode_hot_shard_key(branch_id, &row.key).unwrap_or((u32::MAX, u64::MAX));
+			(
+				json!({ "kind": "shard", "shard_id": shard_id, "as_of_txid": as_of_txid }),
+				row.value,
+			)
+		}));
+
+	let keyring = encryption::installed();
+	let mut sealed_count = 0usize;
+	let mut plaintext_count = 0usize;
+	let mut failures = Vec::new();
+	for (mut fact, bytes) in blobs {
+		let Some(key_id) = encryption::sealed_key_id(&bytes).ok().flatten() else {
+			plaintext_count += 1;
+			continue;
+		};
+		sealed_count += 1;
+		let Some(keyring) = &keyring else {
+			continue;
+		};
+		if let Err(err) = keyring.open(&bytes) {
+			fact["data_key_version"] = json!(key_id.version);
+			fact["error"] = json!(format!("{err:#}"));
+			failures.push(fact);
+		}
+	}
+
+	if sealed_count > 0 && keyring.is_none() {
+		return Ok(json!({
+			"skipped": true,
+			"skip_reason": "master_key_not_configured",
+			"sealed_count": sealed_count,
+			"plaintext_count": plaintext_count,
+		}));
+	}
+
+	Ok(json!({
+		"ok": failures.is_empty(),
+		"sealed_count": sealed_count,
+		"plaintext_count": plaintext_count,
+		"failures": LimitedRows::from_rows(failures),
+	}))
+}
+
 fn suspect_pages(
 	replay: &ReplayResult,
 	resolver: Option<&ResolverResult>,
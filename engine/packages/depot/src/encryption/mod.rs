@@ -0,0 +1,553 @@
+//! Envelope encryption for depot page blobs and actor KV values.
+//!
+//! Every bucket (namespace) has one current data key, stored under `/DATA_KEY/` wrapped by the
+//! configured master key. Sealed values carry their wrapped data key inline, so opening one only
+//! needs the master key that wrapped it and never touches UniversalDB. Values written before
+//! encryption was enabled are left in plaintext.
+//!
+//! Page blobs are engine-encoded LTX and always start with the LTX magic, so a blob is told apart
+//! from a sealed one by its prefix and plaintext blobs pass through [`open_blob`] unchanged. Actor
+//! KV values are arbitrary user bytes, so the KV store records which values are sealed next to the
+//! value and opens them with [`Keyring::open_sealed`] instead.
+//!
+//! The process-wide keyring is installed from the config at startup. Page blobs are opened at the
+//! LTX decode boundary, so every reader (conveyer reads, compaction, doctor) sees plaintext.
+
+use std::{
+	borrow::Cow,
+	fmt,
+	sync::{Arc, OnceLock},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use base64::Engine;
+use ring::{
+	aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+	rand::{SecureRandom, SystemRandom},
+};
+use sha2::{Digest, Sha256};
+use universaldb::utils::IsolationLevel::Serializable;
+
+use crate::conveyer::{
+	keys,
+	types::{BucketId, DataKeyRecord, decode_data_key_record, encode_data_key_record},
+};
+
+pub mod rotate;
+
+const SEALED_MAGIC: &[u8; 4] = b"DENC";
+const SEALED_FORMAT_VERSION: u8 = 1;
+const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+const FINGERPRINT_LEN: usize = 8;
+const WRAPPED_KEY_LEN: usize = KEY_LEN + TAG_LEN;
+
+const BUCKET_ID_OFFSET: usize = SEALED_MAGIC.len() + 1;
+const VERSION_OFFSET: usize = BUCKET_ID_OFFSET + 16;
+const FINGERPRINT_OFFSET: usize = VERSION_OFFSET + std::mem::size_of::<u32>();
+const WRAP_NONCE_OFFSET: usize = FINGERPRINT_OFFSET + FINGERPRINT_LEN;
+const WRAPPED_KEY_OFFSET: usize = WRAP_NONCE_OFFSET + NONCE_LEN;
+const NONCE_OFFSET: usize = WRAPPED_KEY_OFFSET + WRAPPED_KEY_LEN;
+
+/// Bytes before the ciphertext of a sealed value: magic, format version, bucket id, data key
+/// version, master key fingerprint, wrap nonce, wrapped data key and value nonce. The header is
+/// authenticated as associated data.
+pub const SEALED_HEADER_SIZE: usize = NONCE_OFFSET + NONCE_LEN;
+/// Bytes a sealed value adds on top of its plaintext.
+pub const SEALED_OVERHEAD: usize = SEALED_HEADER_SIZE + TAG_LEN;
+
+/// How long a bucket's current data key is cached before it is re-read, so rotations made by
+/// other nodes are picked up.
+const CURRENT_DATA_KEY_TTL: Duration = Duration::from_secs(60);
+const CURRENT_DATA_KEY_CACHE_CAPACITY: u64 = 10_000;
+
+static INSTALLED: OnceLock<Arc<Keyring>> = OnceLock::new();
+
+/// Identifies the data key a sealed value was written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SealedKeyId {
+	pub bucket_id: BucketId,
+	pub version: u32,
+	pub master_key_fingerprint: [u8; FINGERPRINT_LEN],
+}
+
+struct MasterKey {
+	fingerprint: [u8; FINGERPRINT_LEN],
+	key: LessSafeKey,
+}
+
+impl MasterKey {
+	fn new(bytes: &[u8]) -> Result<Self> {
+		ensure!(
+			bytes.len() == KEY_LEN,
+			"sqlite encryption master key must be {KEY_LEN} bytes, got {}",
+			bytes.len()
+		);
+
+		Ok(Self {
+			fingerprint: master_key_fingerprint(bytes),
+			key: aes_key(bytes)?,
+		})
+	}
+
+	fn from_base64(encoded: &str) -> Result<Self> {
+		let bytes = base64::engine::general_purpose::STANDARD
+			.decode(encoded.trim())
+			.context("sqlite encryption master key is not valid base64")?;
+		Self::new(&bytes)
+	}
+}
+
+/// Unwrapped data key of one bucket.
+#[derive(Clone)]
+pub struct DataKey {
+	bucket_id: BucketId,
+	record: DataKeyRecord,
+	key: LessSafeKey,
+}
+
+impl fmt::Debug for DataKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("DataKey")
+			.field("bucket_id", &self.bucket_id)
+			.field("version", &self.record.version)
+			.finish()
+	}
+}
+
+impl DataKey {
+	pub fn id(&self) -> SealedKeyId {
+		SealedKeyId {
+			bucket_id: self.bucket_id,
+			version: self.record.version,
+			master_key_fingerprint: self.record.master_key_fingerprint,
+		}
+	}
+
+	pub fn created_at_ms(&self) -> i64 {
+		self.record.created_at_ms
+	}
+
+	pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+		let mut nonce = [0u8; NONCE_LEN];
+		SystemRandom::new()
+			.fill(&mut nonce)
+			.map_err(|_| anyhow!("generate sqlite encryption nonce"))?;
+
+		let mut sealed = Vec::with_capacity(SEALED_OVERHEAD + plaintext.len());
+		sealed.extend_from_slice(SEALED_MAGIC);
+		sealed.push(SEALED_FORMAT_VERSION);
+		sealed.extend_from_slice(self.bucket_id.as_uuid().as_bytes());
+		sealed.extend_from_slice(&self.record.version.to_be_bytes());
+		sealed.extend_from_slice(&self.record.master_key_fingerprint);
+		sealed.extend_from_slice(&self.record.wrap_nonce);
+		sealed.extend_from_slice(&self.record.wrapped_key);
+		sealed.extend_from_slice(&nonce);
+		debug_assert_eq!(sealed.len(), SEALED_HEADER_SIZE);
+
+		let mut ciphertext = plaintext.to_vec();
+		self.key
+			.seal_in_place_append_tag(
+				Nonce::assume_unique_for_key(nonce),
+				Aad::from(&sealed[..SEALED_HEADER_SIZE]),
+				&mut ciphertext,
+			)
+			.map_err(|_| anyhow!("seal sqlite value"))?;
+		sealed.extend_from_slice(&ciphertext);
+
+		Ok(sealed)
+	}
+}
+
+/// Master keys from the config plus a cache of each bucket's current data key.
+pub struct Keyring {
+	current: MasterKey,
+	previous: Vec<MasterKey>,
+	current_data_keys: moka::future::Cache<BucketId, Arc<DataKey>>,
+}
+
+impl fmt::Debug for Keyring {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Keyring")
+			.field("previous_master_keys", &self.previous.len())
+			.finish()
+	}
+}
+
+impl Keyring {
+	/// Builds a keyring from raw 32-byte master keys. `previous` only unwraps existing data.
+	pub fn new(master_key: &[u8], previous: &[Vec<u8>]) -> Result<Self> {
+		Ok(Self::with_master_keys(
+			MasterKey::new(master_key)?,
+			previous
+				.iter()
+				.map(|key| MasterKey::new(key))
+				.collect::<Result<_>>()?,
+		))
+	}
+
+	/// Builds the configured keyring. Returns `None` when encryption is disabled.
+	pub fn from_config(config: &rivet_config::Config) -> Result<Option<Self>> {
+		let Some(encryption) = &config.sqlite().encryption else {
+			return Ok(None);
+		};
+
+		let current = MasterKey::from_base64(encryption.master_key.read())?;
+		let previous = encryption
+			.previous_master_keys()
+			.iter()
+			.map(|key| MasterKey::from_base64(key.read()))
+			.collect::<Result<Vec<_>>>()
+			.context("invalid previous sqlite encryption master key")?;
+
+		Ok(Some(Self::with_master_keys(current, previous)))
+	}
+
+	fn with_master_keys(current: MasterKey, previous: Vec<MasterKey>) -> Self {
+		Self {
+			current,
+			previous,
+			current_data_keys: moka::future::Cache::builder()
+				.max_capacity(CURRENT_DATA_KEY_CACHE_CAPACITY)
+				.time_to_live(CURRENT_DATA_KEY_TTL)
+				.build(),
+		}
+	}
+
+	pub fn master_key_fingerprint(&self) -> [u8; FINGERPRINT_LEN] {
+		self.current.fingerprint
+	}
+
+	/// Returns the data key new values of `bucket_id` are sealed with, creating the bucket's
+	/// first key if it has none.
+	pub async fn current_data_key(
+		&self,
+		udb: &universaldb::Database,
+		bucket_id: BucketId,
+	) -> Result<Arc<DataKey>> {
+		self.current_data_keys
+			.try_get_with(bucket_id, async {
+				let record = udb
+					.txn("depot_encryption_current_data_key", |tx| async move {
+						let key = keys::bucket_data_key_key(bucket_id);
+						if let Some(existing) = tx.informal().get(&key, Serializable).await? {
+							return decode_data_key_record(&existing);
+						}
+
+						let record = self.generate_data_key_record(bucket_id, 1)?;
+						tx.informal()
+							.set(&key, &encode_data_key_record(record.clone())?);
+						Ok(record)
+					})
+					.await?;
+
+				self.unwrap_data_key(bucket_id, record).map(Arc::new)
+			})
+			.await
+			.map_err(|err| anyhow!("{err:#}"))
+	}
+
+	/// Replaces the data key of `bucket_id` with a fresh one. Existing values stay readable and
+	/// are re-encrypted by the key rotator.
+	pub async fn rotate_data_key(
+		&self,
+		udb: &universaldb::Database,
+		bucket_id: BucketId,
+	) -> Result<DataKeyRecord> {
+		let record = udb
+			.txn("depot_encryption_rotate_data_key", |tx| async move {
+				let key = keys::bucket_data_key_key(bucket_id);
+				let version = match tx.informal().get(&key, Serializable).await? {
+					Some(existing) => decode_data_key_record(&existing)?
+						.version
+						.checked_add(1)
+						.context("sqlite data key version overflowed")?,
+					None => 1,
+				};
+				let record = self.generate_data_key_record(bucket_id, version)?;
+				tx.informal()
+					.set(&key, &encode_data_key_record(record.clone())?);
+				Ok(record)
+			})
+			.await?;
+		self.current_data_keys.invalidate(&bucket_id).await;
+
+		Ok(record)
+	}
+
+	/// Re-wraps the current data key of `bucket_id` under the current master key, keeping the
+	/// key itself. Returns false when it was already wrapped by the current master key.
+	pub async fn rewrap_data_key(
+		&self,
+		udb: &universaldb::Database,
+		bucket_id: BucketId,
+	) -> Result<bool> {
+		let rewrapped = udb
+			.txn("depot_encryption_rewrap_data_key", |tx| async move {
+				let key = keys::bucket_data_key_key(bucket_id);
+				let Some(existing) = tx.informal().get(&key, Serializable).await? else {
+					return Ok(false);
+				};
+				let record = decode_data_key_record(&existing)?;
+				if record.master_key_fingerprint == self.current.fingerprint {
+					return Ok(false);
+				}
+
+				let raw_key = self.unwrap_raw_key(bucket_id, &record)?;
+				let (wrap_nonce, wrapped_key) =
+					self.wrap_raw_key(bucket_id, record.version, &raw_key)?;
+				tx.informal().set(
+					&key,
+					&encode_data_key_record(DataKeyRecord {
+						master_key_fingerprint: self.current.fingerprint,
+						wrap_nonce,
+						wrapped_key,
+						..record
+					})?,
+				);
+				Ok(true)
+			})
+			.await?;
+		if rewrapped {
+			self.current_data_keys.invalidate(&bucket_id).await;
+		}
+
+		Ok(rewrapped)
+	}
+
+	/// Decrypts a page blob. Plaintext blobs are returned unchanged.
+	pub fn open<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+		if !is_sealed(bytes) {
+			return Ok(Cow::Borrowed(bytes));
+		}
+
+		self.open_sealed(bytes).map(Cow::Owned)
+	}
+
+	/// Decrypts a value the caller recorded as sealed. Fails instead of passing the bytes through
+	/// when they do not carry a sealed header.
+	pub fn open_sealed(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+		ensure!(
+			bytes.len() >= SEALED_OVERHEAD,
+			"sealed value is too small: {} bytes",
+			bytes.len()
+		);
+		let data_key = self.data_key_of(bytes)?;
+		let mut plaintext = bytes[SEALED_HEADER_SIZE..].to_vec();
+		let nonce: [u8; NONCE_LEN] = bytes[NONCE_OFFSET..SEALED_HEADER_SIZE]
+			.try_into()
+			.expect("sealed nonce should be 12 bytes");
+		let len = data_key
+			.key
+			.open_in_place(
+				Nonce::assume_unique_for_key(nonce),
+				Aad::from(&bytes[..SEALED_HEADER_SIZE]),
+				&mut plaintext,
+			)
+			.map_err(|_| anyhow!("sqlite value failed to decrypt"))?
+			.len();
+		plaintext.truncate(len);
+
+		Ok(plaintext)
+	}
+
+	/// Unwraps the data key embedded in the header of a sealed value.
+	pub fn data_key_of(&self, sealed: &[u8]) -> Result<DataKey> {
+		let id = sealed_key_id(sealed)?.context("sqlite value is not sealed")?;
+		let record = DataKeyRecord {
+			version: id.version,
+			master_key_fingerprint: id.master_key_fingerprint,
+			wrap_nonce: sealed[WRAP_NONCE_OFFSET..WRAPPED_KEY_OFFSET]
+				.try_into()
+				.expect("sealed wrap nonce should be 12 bytes"),
+			wrapped_key: sealed[WRAPPED_KEY_OFFSET..NONCE_OFFSET].to_vec(),
+			created_at_ms: 0,
+		};
+
+		self.unwrap_data_key(id.bucket_id, record)
+	}
+
+	fn generate_data_key_record(&self, bucket_id: BucketId, version: u32) -> Result<DataKeyRecord> {
+		let mut raw_key = [0u8; KEY_LEN];
+		SystemRandom::new()
+			.fill(&mut raw_key)
+			.map_err(|_| anyhow!("generate sqlite data key"))?;
+		let (wrap_nonce, wrapped_key) = self.wrap_raw_key(bucket_id, version, &raw_key)?;
+
+		Ok(DataKeyRecord {
+			version,
+			master_key_fingerprint: self.current.fingerprint,
+			wrap_nonce,
+			wrapped_key,
+			created_at_ms: now_ms()?,
+		})
+	}
+
+	fn wrap_raw_key(
+		&self,
+		bucket_id: BucketId,
+		version: u32,
+		raw_key: &[u8],
+	) -> Result<([u8; NONCE_LEN], Vec<u8>)> {
+		let mut wrap_nonce = [0u8; NONCE_LEN];
+		SystemRandom::new()
+			.fill(&mut wrap_nonce)
+			.map_err(|_| anyhow!("generate sqlite data key wrap nonce"))?;
+		let mut wrapped_key = raw_key.to_vec();
+		self.current
+			.key
+			.seal_in_place_append_tag(
+				Nonce::assume_unique_for_key(wrap_nonce),
+				Aad::from(wrap_aad(bucket_id, version)),
+				&mut wrapped_key,
+			)
+			.map_err(|_| anyhow!("wrap sqlite data key"))?;
+
+		Ok((wrap_nonce, wrapped_key))
+	}
+
+	fn unwrap_raw_key(&self, bucket_id: BucketId, record: &DataKeyRecord) -> Result<Vec<u8>> {
+		let master = std::iter::once(&self.current)
+			.chain(&self.previous)
+			.find(|master| master.fingerprint == record.master_key_fingerprint)
+			.with_context(|| {
+				format!(
+					"sqlite data key version {} of bucket {} is wrapped by an unknown master key",
+					record.version,
+					bucket_id.as_uuid()
+				)
+			})?;
+		let mut raw_key = record.wrapped_key.clone();
+		let len = master
+			.key
+			.open_in_place(
+				Nonce::assume_unique_for_key(record.wrap_nonce),
+				Aad::from(wrap_aad(bucket_id, record.version)),
+				&mut raw_key,
+			)
+			.map_err(|_| anyhow!("sqlite data key failed to unwrap"))?
+			.len();
+		raw_key.truncate(len);
+
+		Ok(raw_key)
+	}
+
+	fn unwrap_data_key(&self, bucket_id: BucketId, record: DataKeyRecord) -> Result<DataKey> {
+		let raw_key = self.unwrap_raw_key(bucket_id, &record)?;
+
+		Ok(DataKey {
+			bucket_id,
+			key: aes_key(&raw_key)?,
+			record,
+		})
+	}
+}
+
+/// Installs the process-wide keyring. Later calls return the keyring installed first.
+pub fn install(keyring: Keyring) -> Arc<Keyring> {
+	INSTALLED.get_or_init(|| Arc::new(keyring)).clone()
+}
+
+/// Installs the keyring built from the config, if encryption is enabled.
+pub fn init(config: &rivet_config::Config) -> Result<Option<Arc<Keyring>>> {
+	if let Some(keyring) = INSTALLED.get() {
+		return Ok(Some(keyring.clone()));
+	}
+
+	Ok(Keyring::from_config(config)?.map(install))
+}
+
+/// Returns the process-wide keyring, or `None` when encryption is disabled.
+pub fn installed() -> Option<Arc<Keyring>> {
+	INSTALLED.get().cloned()
+}
+
+/// Decrypts a sealed page blob with the installed keyring. Plaintext blobs are returned
+/// unchanged.
+pub fn open_blob(bytes: &[u8]) -> Result<Cow<'_, [u8]>> {
+	if !is_sealed(bytes) {
+		return Ok(Cow::Borrowed(bytes));
+	}
+
+	let Some(keyring) = INSTALLED.get() else {
+		bail!("sqlite page blob is encrypted but no sqlite encryption master key is configured");
+	};
+	keyring.open(bytes)
+}
+
+/// Seals `plaintext` with the data key of `original` when `original` is sealed. Used when a
+/// blob is rewritten from its own contents, such as compaction folding deltas into a shard.
+pub fn reseal_like(original: &[u8], plaintext: Vec<u8>) -> Result<Vec<u8>> {
+	if !is_sealed(original) {
+		return Ok(plaintext);
+	}
+
+	let Some(keyring) = INSTALLED.get() else {
+		bail!("sqlite page blob is encrypted but no sqlite encryption master key is configured");
+	};
+	keyring.data_key_of(original)?.seal(&plaintext)
+}
+
+/// Whether a page blob is sealed. Only meaningful for engine-encoded blobs, whose plaintext starts
+/// with the LTX magic. Never use it on user values.
+pub fn is_sealed(bytes: &[u8]) -> bool {
+	bytes.len() >= SEALED_OVERHEAD && bytes.starts_with(SEALED_MAGIC)
+}
+
+/// Reads the data key identity from the header of a sealed value. Only the header bytes are
+/// needed, so the first chunk of a chunked value is enough.
+pub fn sealed_key_id(bytes: &[u8]) -> Result<Option<SealedKeyId>> {
+	if bytes.len() < SEALED_HEADER_SIZE || !bytes.starts_with(SEALED_MAGIC) {
+		return Ok(None);
+	}
+	ensure!(
+		bytes[SEALED_MAGIC.len()] == SEALED_FORMAT_VERSION,
+		"unsupported sealed sqlite value format {}",
+		bytes[SEALED_MAGIC.len()]
+	);
+
+	let bucket_id = uuid::Uuid::from_slice(&bytes[BUCKET_ID_OFFSET..VERSION_OFFSET])
+		.context("decode sealed bucket id")?;
+	Ok(Some(SealedKeyId {
+		bucket_id: BucketId::from_uuid(bucket_id),
+		version: u32::from_be_bytes(
+			bytes[VERSION_OFFSET..FINGERPRINT_OFFSET]
+				.try_into()
+				.expect("sealed data key version should be 4 bytes"),
+		),
+		master_key_fingerprint: bytes[FINGERPRINT_OFFSET..WRAP_NONCE_OFFSET]
+			.try_into()
+			.expect("sealed master key fingerprint should be 8 bytes"),
+	}))
+}
+
+fn master_key_fingerprint(key: &[u8]) -> [u8; FINGERPRINT_LEN] {
+	let digest = Sha256::new()
+		.chain_update(b"rivet-depot-master-key")
+		.chain_update(key)
+		.finalize();
+	digest[..FINGERPRINT_LEN]
+		.try_into()
+		.expect("sha256 digest should hold a fingerprint")
+}
+
+fn aes_key(bytes: &[u8]) -> Result<LessSafeKey> {
+	let key = UnboundKey::new(&AES_256_GCM, bytes).map_err(|_| anyhow!("invalid AES-256 key"))?;
+	Ok(LessSafeKey::new(key))
+}
+
+fn wrap_aad(bucket_id: BucketId, version: u32) -> [u8; 20] {
+	let mut aad = [0u8; 20];
+	aad[..16].copy_from_slice(bucket_id.as_uuid().as_bytes());
+	aad[16..].copy_from_slice(&version.to_be_bytes());
+	aad
+}
+
+fn now_ms() -> Result<i64> {
+	let millis = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.context("system clock is before unix epoch")?
+		.as_millis();
+	i64::try_from(millis).context("current timestamp exceeded i64 milliseconds")
+}
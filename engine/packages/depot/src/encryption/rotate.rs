@@ -0,0 +1,391 @@
+//! Data key rotation and re-encryption of hot page blobs.
+//!
+//! Rotating a data key only bumps the bucket's `/DATA_KEY/` record, and sealed values stay
+//! readable because they carry their wrapped key inline. The rotator then rewrites every hot delta
+//! and shard still sealed under an older key so retired data keys and master keys stop being
+//! needed. Cold objects are not rewritten, so retired master keys must stay in
+//! `previous_master_keys` while cold history sealed under them is retained.
+//!
+//! Actor KV values are re-sealed by pegboard, which owns their layout, through
+//! [`reencrypt_chunked_value`].
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{Context, Result, ensure};
+use futures_util::TryStreamExt;
+use universaldb::{
+	RangeOption,
+	options::StreamingMode,
+	utils::IsolationLevel::{Serializable, Snapshot},
+};
+
+use super::{DataKey, Keyring, SealedKeyId, sealed_key_id};
+use crate::conveyer::{
+	commit::DELTA_CHUNK_BYTES,
+	keys,
+	types::{BucketId, DataKeyRecord, DatabaseBranchId, decode_data_key_record},
+};
+
+/// Rows read per transaction when scanning data keys or branch blobs.
+const SCAN_BATCH_ROWS: usize = 64;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DataKeyRotationOutcome {
+	pub data_keys_rotated: usize,
+	/// Data keys that kept their key material but were re-wrapped under the current master key.
+	pub data_keys_rewrapped: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BranchReencryptOutcome {
+	pub deltas_reencrypted: usize,
+	pub shards_reencrypted: usize,
+	pub row_changes_reencrypted: usize,
+	pub bytes_reencrypted: u64,
+}
+
+/// Rotates data keys older than `max_age_ms` and re-wraps the rest under the current master key.
+pub async fn rotate_data_keys(
+	udb: &universaldb::Database,
+	keyring: &Keyring,
+	max_age_ms: i64,
+	now_ms: i64,
+) -> Result<DataKeyRotationOutcome> {
+	let mut outcome = DataKeyRotationOutcome::default();
+	for (bucket_id, record) in list_data_keys(udb).await? {
+		if now_ms.saturating_sub(record.created_at_ms) >= max_age_ms {
+			let rotated = keyring.rotate_data_key(udb, bucket_id).await?;
+			tracing::info!(
+				bucket_id = %bucket_id.as_uuid(),
+				version = rotated.version,
+				"rotated sqlite data key"
+			);
+			outcome.data_keys_rotated += 1;
+		} else if keyring.rewrap_data_key(udb, bucket_id).await? {
+			tracing::info!(
+				bucket_id = %bucket_id.as_uuid(),
+				version = record.version,
+				"re-wrapped sqlite data key under the current master key"
+			);
+			outcome.data_keys_rewrapped += 1;
+		}
+	}
+
+	Ok(outcome)
+}
+
+/// Re-seals every hot delta, shard and logged row change of the branch that is not sealed with the current data key
+/// of its bucket. Plaintext blobs are left alone.
+pub async fn reencrypt_branch(
+	udb: &universaldb::Database,
+	keyring: &Keyring,
+	branch_id: DatabaseBranchId,
+) -> Result<BranchReencryptOutcome> {
+	let mut current_keys = HashMap::<BucketId, Arc<DataKey>>::new();
+	let mut outcome = BranchReencryptOutcome::default();
+
+	let delta_prefix = keys::branch_delta_prefix(branch_id);
+	for (key, key_id) in scan_sealed_rows(udb, &delta_prefix).await? {
+		let txid = keys::decode_branch_delta_chunk_txid(branch_id, &key)?;
+		if keys::decode_branch_delta_chunk_idx(branch_id, txid, &key)? != 0 {
+			continue;
+		}
+		let data_key = current_key(udb, keyring, &mut current_keys, key_id.bucket_id).await?;
+		if key_id == data_key.id() {
+			continue;
+		}
+
+		if let Some(bytes) = reencrypt_delta(udb, keyring, &data_key, branch_id, txid).await? {
+			outcome.deltas_reencrypted += 1;
+			outcome.bytes_reencrypted += bytes;
+		}
+	}
+
+	let shard_prefix = keys::branch_shard_prefix(branch_id);
+	for (key, key_id) in scan_sealed_rows(udb, &shard_prefix).await? {
+		let data_key = current_key(udb, keyring, &mut current_keys, key_id.bucket_id).await?;
+		if key_id == data_key.id() {
+			continue;
+		}
+
+		if let Some(bytes) = reencrypt_row(udb, keyring, &data_key, key).await? {
+			outcome.shards_reencrypted += 1;
+			outcome.bytes_reencrypted += bytes;
+		}
+	}
+
+	let row_change_prefix = keys::branch_row_change_prefix(branch_id);
+	for (key, key_id) in scan_sealed_rows(udb, &row_change_prefix).await? {
+		let data_key = current_key(udb, keyring, &mut current_keys, key_id.bucket_id).await?;
+		if key_id == data_key.id() {
+			continue;
+		}
+
+		if let Some(bytes) = reencrypt_row(udb, keyring, &data_key, key).await? {
+			outcome.row_changes_reencrypted += 1;
+			outcome.bytes_reencrypted += bytes;
+		}
+	}
+
+	Ok(outcome)
+}
+
+/// Loads the current data key of a bucket once per branch pass, so every blob of the pass is
+/// re-sealed with the same key even if the key rotates midway.
+async fn current_key(
+	udb: &universaldb::Database,
+	keyring: &Keyring,
+	current_keys: &mut HashMap<BucketId, Arc<DataKey>>,
+	bucket_id: BucketId,
+) -> Result<Arc<DataKey>> {
+	if let Some(data_key) = current_keys.get(&bucket_id) {
+		return Ok(data_key.clone());
+	}
+
+	let data_key = keyring.current_data_key(udb, bucket_id).await?;
+	current_keys.insert(bucket_id, data_key.clone());
+	Ok(data_key)
+}
+
+/// Re-seals all chunks of one delta in a single transaction. Returns the sealed size, or `None`
+/// when the delta was removed or already uses `data_key`.
+async fn reencrypt_delta(
+	udb: &universaldb::Database,
+	keyring: &Keyring,
+	data_key: &DataKey,
+	branch_id: DatabaseBranchId,
+	txid: u64,
+) -> Result<Option<u64>> {
+	udb.txn("depot_encryption_reencrypt_delta", move |tx| async move {
+		let prefix = keys::branch_delta_chunk_prefix(branch_id, txid);
+		let (begin, end) = universaldb::tuple::Subspace::from_bytes(prefix).range();
+		let chunks = tx
+			.informal()
+			.get_ranges_keyvalues(
+				RangeOption {
+					mode: StreamingMode::WantAll,
+					..(begin.as_slice(), end.as_slice()).into()
+				},
+				Serializable,
+			)
+			.map_ok(|entry| entry.value().to_vec())
+			.try_collect::<Vec<_>>()
+			.await?;
+		let sealed = chunks.concat();
+		if sealed_key_id(&sealed)?.is_none_or(|key_id| key_id == data_key.id()) {
+			return Ok(None);
+		}
+
+		let plaintext = keyring
+			.open(&sealed)
+			.with_context(|| format!("decrypt sqlite delta {txid}"))?;
+		let resealed = data_key.seal(&plaintext)?;
+
+		// Sealing adds a fixed overhead, so the chunk layout normally stays the same. Clear first
+		// anyway so a shorter layout leaves no stale tail chunks.
+		tx.informal().clear_range(&begin, &end);
+		for (idx, chunk) in resealed.chunks(DELTA_CHUNK_BYTES).enumerate() {
+			let idx = u32::try_from(idx).context("sqlite delta chunk index overflowed")?;
+			tx.informal()
+				.set(&keys::branch_delta_chunk_key(branch_id, txid, idx), chunk);
+		}
+
+		Ok(Some(u64::try_from(resealed.len()).unwrap_or(u64::MAX)))
+	})
+	.await
+}
+
+/// Re-seals a single-row blob. Returns the sealed size, or `None` when the row was removed or
+/// already uses `data_key`.
+async fn reencrypt_row(
+	udb: &universaldb::Database,
+	keyring: &Keyring,
+	data_key: &DataKey,
+	key: Vec<u8>,
+) -> Result<Option<u64>> {
+	udb.txn("depot_encryption_reencrypt_row", |tx| {
+		let key = key.clone();
+		async move {
+			let Some(sealed) = tx.informal().get(&key, Serializable).await? else {
+				return Ok(None);
+			};
+			if sealed_key_id(&sealed)?.is_none_or(|key_id| key_id == data_key.id()) {
+				return Ok(None);
+			}
+
+			let plaintext = keyring.open(&sealed).context("decrypt sqlite blob")?;
+			let resealed = data_key.seal(&plaintext)?;
+			tx.informal().set(&key, &resealed);
+
+			Ok(Some(u64::try_from(resealed.len()).unwrap_or(u64::MAX)))
+		}
+	})
+	.await
+}
+
+/// Re-seals a value stored as consecutive chunk rows in `begin..end` with `data_key`. Sealing adds
+/// a fixed overhead, so every chunk keeps its length and key. Returns the sealed size, or `None`
+/// when the value was removed or already uses `data_key`.
+///
+/// Only pass values recorded as sealed. The chunks are opened without checking for plaintext.
+pub async fn reencrypt_chunked_value(
+	udb: &universaldb::Database,
+	keyring: &Keyring,
+	data_key: &DataKey,
+	begin: &[u8],
+	end: &[u8],
+) -> Result<Option<u64>> {
+	udb.txn(
+		"depot_encryption_reencrypt_chunked_value",
+		|tx| async move {
+			let chunks = tx
+				.informal()
+				.get_ranges_keyvalues(
+					RangeOption {
+						mode: StreamingMode::WantAll,
+						..(begin, end).into()
+					},
+					Serializable,
+				)
+				.map_ok(|entry| (entry.key().to_vec(), entry.value().to_vec()))
+				.try_collect::<Vec<_>>()
+				.await?;
+			let sealed = chunks
+				.iter()
+				.flat_map(|(_, chunk)| chunk.iter().copied())
+				.collect::<Vec<_>>();
+			if sealed.is_empty()
+				|| sealed_key_id(&sealed)?.is_some_and(|key_id| key_id == data_key.id())
+			{
+				return Ok(None);
+			}
+
+			let plaintext = keyring
+				.open_sealed(&sealed)
+				.context("decrypt chunked value")?;
+			let resealed = data_key.seal(&plaintext)?;
+			ensure!(
+				resealed.len() == sealed.len(),
+				"resealed value changed size from {} to {} bytes",
+				sealed.len(),
+				resealed.len()
+			);
+
+			let mut offset = 0;
+			for (key, chunk) in &chunks {
+				tx.informal()
+					.set(key, &resealed[offset..offset + chunk.len()]);
+				offset += chunk.len();
+			}
+
+			Ok(Some(u64::try_from(resealed.len()).unwrap_or(u64::MAX)))
+		},
+	)
+	.await
+}
+
+async fn list_data_keys(udb: &universaldb::Database) -> Result<Vec<(BucketId, DataKeyRecord)>> {
+	let (begin, end) =
+		universaldb::tuple::Subspace::from_bytes(keys::bucket_data_key_prefix()).range();
+
+	let mut records = Vec::new();
+	let mut cursor = begin;
+	loop {
+		let rows = udb
+			.txn("depot_encryption_list_data_keys", |tx| {
+				let cursor = cursor.clone();
+				let end = end.clone();
+				async move {
+					let informal = tx.informal();
+					let mut stream = informal.get_ranges_keyvalues(
+						RangeOption {
+							mode: StreamingMode::WantAll,
+							limit: Some(SCAN_BATCH_ROWS),
+							..(cursor.as_slice(), end.as_slice()).into()
+						},
+						Snapshot,
+					);
+					let mut rows = Vec::new();
+					while let Some(entry) = stream.try_next().await? {
+						rows.push((entry.key().to_vec(), entry.value().to_vec()));
+					}
+
+					Ok(rows)
+				}
+			})
+			.await?;
+
+		let Some((last_key, _)) = rows.last() else {
+			break;
+		};
+		cursor = universaldb::utils::end_of_key_range(last_key);
+
+		for (key, value) in &rows {
+			records.push((
+				keys::decode_bucket_data_key_bucket_id(key)?,
+				decode_data_key_record(value)?,
+			));
+		}
+
+		if rows.len() < SCAN_BATCH_ROWS {
+			break;
+		}
+	}
+
+	Ok(records)
+}
+
+/// Lists the keys of sealed rows under `prefix` with the data key each was sealed with. Only the
+/// sealed header is kept, so large branches are scanned without holding their blobs.
+async fn scan_sealed_rows(
+	udb: &universaldb::Database,
+	prefix: &[u8],
+) -> Result<Vec<(Vec<u8>, SealedKeyId)>> {
+	let (begin, end) = universaldb::tuple::Subspace::from_bytes(prefix.to_vec()).range();
+
+	let mut sealed = Vec::new();
+	let mut cursor = begin;
+	loop {
+		let (last_key, row_count, batch) = udb
+			.txn("depot_encryption_scan_sealed", |tx| {
+				let cursor = cursor.clone();
+				let end = end.clone();
+				async move {
+					let informal = tx.informal();
+					let mut stream = informal.get_ranges_keyvalues(
+						RangeOption {
+							mode: StreamingMode::WantAll,
+							limit: Some(SCAN_BATCH_ROWS),
+							..(cursor.as_slice(), end.as_slice()).into()
+						},
+						Snapshot,
+					);
+					let mut last_key = None;
+					let mut row_count = 0;
+					let mut batch = Vec::new();
+					while let Some(entry) = stream.try_next().await? {
+						row_count += 1;
+						if let Some(key_id) = sealed_key_id(entry.value())? {
+							batch.push((entry.key().to_vec(), key_id));
+						}
+						last_key = Some(entry.key().to_vec());
+					}
+
+					Ok((last_key, row_count, batch))
+				}
+			})
+			.await?;
+
+		let Some(last_key) = last_key else {
+			break;
+		};
+		cursor = universaldb::utils::end_of_key_range(&last_key);
+		sealed.extend(batch);
+
+		if row_count < SCAN_BATCH_ROWS {
+			break;
+		}
+	}
+
+	Ok(sealed)
+}
@@ -19,7 +19,7 @@ use crate::{
 			BucketId, DatabaseBranchId, decode_bucket_branch_record, decode_bucket_pointer,
 			decode_commit_row, decode_compaction_root, decode_database_branch_record,
 			decode_database_pointer, decode_db_head, decode_db_history_pin,
-			decode_pitr_interval_coverage, decode_sqlite_cmp_dirty,
+			decode_pitr_interval_coverage, decode_repair_journal_entry, decode_sqlite_cmp_dirty,
 		},
 	},
 	gc,
@@ -875,6 +875,7 @@ fn decode_key_metadata(key: &[u8]) -> Value {
 			keys::BUCKET_CATALOG_BY_DB_PARTITION => "bucket-catalog-by-db",
 			keys::BUCKET_PROOF_EPOCH_PARTITION => "bucket-proof-epoch",
 			keys::SQLITE_CMP_DIRTY_PARTITION => "sqlite-compaction-dirty",
+			keys::REPAIR_JOURNAL_PARTITION => "repair-journal",
 			_ => "unknown",
 		}
 	})
@@ -896,6 +897,9 @@ fn decode_value_by_key(key: &[u8], value: &[u8]) -> Value {
 				return value_or_error(decode_sqlite_cmp_dirty(value));
 			}
 			keys::DB_PIN_PARTITION => return value_or_error(decode_db_history_pin(value)),
+			keys::REPAIR_JOURNAL_PARTITION => {
+				return value_or_error(decode_repair_journal_entry(value));
+			}
 			_ => {}
 		}
 	}
@@ -1,14 +1,17 @@
 pub mod burst_mode;
 use gas::prelude::*;
 
+pub mod cold;
 mod compaction;
 pub mod conveyer;
 pub mod doctor;
+pub mod encryption;
 #[cfg(feature = "test-faults")]
 pub mod fault;
 pub mod gc;
 pub mod inspect;
 pub mod metrics;
+pub mod repair;
 #[cfg(debug_assertions)]
 pub mod takeover;
 pub mod workflows;
@@ -17,10 +20,12 @@ pub use conveyer::constants::*;
 #[cfg(debug_assertions)]
 pub use conveyer::debug;
 pub use conveyer::pitr_interval;
-pub use conveyer::{constants, error, keys, ltx, page_index, policy, quota, types, udb};
+pub use conveyer::{constants, error, keys, ltx, page_index, policy, quota, replica, types, udb};
 
 pub fn registry() -> WorkflowResult<Registry> {
-	let registry = Registry::new();
+	let mut registry = Registry::new();
+	registry.register_workflow::<workflows::cold_drainer::ColdDrainerWorkflow>()?;
+	registry.register_workflow::<workflows::key_rotator::KeyRotatorWorkflow>()?;
 	// registry.register_workflow::<db_hot_compacter::DbHotCompacterWorkflow>()?;
 	// registry.register_workflow::<db_manager::DbManagerWorkflow>()?;
 	// registry.register_workflow::<db_reclaimer::DbReclaimerWorkflow>()?;
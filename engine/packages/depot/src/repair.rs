@@ -0,0 +1,218 @@
+//! Companion to `depot doctor` that applies storage repairs.
+//!
+//! A run takes a safety restore point at the current head before touching anything, then
+//! journals every action under that restore point. Restoring it with `undo_repair` rolls the
+//! database back to where the run started.
+
+use anyhow::{Context, Result, ensure};
+use serde::Serialize;
+
+use crate::{
+	conveyer::{
+		Db,
+		repair::{CompactionRerun, PageIndexRebuild, QuotaRecompute},
+		types::{
+			RepairAction, RepairActionStatus, RepairJournalEntry, RestorePointId, SnapshotSelector,
+		},
+	},
+	doctor::{CorruptionClass, DoctorReport, DoctorVerdict, DoctorVerdictKind, SkipOptions},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct RepairInput {
+	/// Actions to apply, in order. When empty they are planned from a doctor run.
+	pub actions: Vec<RepairAction>,
+	pub dry_run: bool,
+	/// Doctor checks to skip while planning.
+	pub skip: SkipOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairPlan {
+	pub actions: Vec<RepairAction>,
+	/// Verdict the plan was derived from. `None` when actions were requested explicitly.
+	pub verdict: Option<DoctorVerdict>,
+	pub first_bad_txid: Option<u64>,
+	pub previous_good_txid: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairOutcome {
+	RebuildPageIndex(PageIndexRebuild),
+	RecomputeQuota(QuotaRecompute),
+	TruncateToTxid {
+		txid: u64,
+		/// Restore point pinning the head the truncate rolled back from.
+		undo_restore_point: RestorePointId,
+	},
+	RerunCompaction(CompactionRerun),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairReport {
+	pub dry_run: bool,
+	pub plan: RepairPlan,
+	/// Restore point taken before the first action. `None` for dry runs and empty plans.
+	pub safety_restore_point: Option<RestorePointId>,
+	pub outcomes: Vec<RepairOutcome>,
+	pub journal: Vec<RepairJournalEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairUndoReport {
+	pub restore_point: RestorePointId,
+	/// Restore point pinning the repaired head, in case the undo itself needs undoing.
+	pub undo_restore_point: RestorePointId,
+	pub journal: Vec<RepairJournalEntry>,
+}
+
+pub async fn repair(db: &Db, input: RepairInput) -> Result<RepairReport> {
+	let plan = if input.actions.is_empty() {
+		tracing::info!(phase = "plan", "running depot doctor to plan repairs");
+		let report = db.doctor(input.skip).await.context("run depot doctor")?;
+		plan_repairs(&report)
+	} else {
+		RepairPlan {
+			actions: input.actions,
+			verdict: None,
+			first_bad_txid: None,
+			previous_good_txid: None,
+		}
+	};
+
+	if input.dry_run || plan.actions.is_empty() {
+		return Ok(RepairReport {
+			dry_run: input.dry_run,
+			plan,
+			safety_restore_point: None,
+			outcomes: Vec::new(),
+			journal: Vec::new(),
+		});
+	}
+
+	let restore_point = db
+		.create_restore_point(SnapshotSelector::Latest)
+		.await
+		.context("take safety restore point")?;
+	tracing::info!(
+		restore_point = restore_point.as_str(),
+		"took repair safety restore point"
+	);
+
+	let mut outcomes = Vec::with_capacity(plan.actions.len());
+	for (seq, action) in plan.actions.iter().copied().enumerate() {
+		let seq = u32::try_from(seq).context("too many repair actions")?;
+		db.record_repair_action(
+			&restore_point,
+			seq,
+			action,
+			RepairActionStatus::Started,
+			None,
+		)
+		.await?;
+
+		tracing::info!(seq, ?action, "applying repair action");
+		match apply_action(db, action).await {
+			Ok(outcome) => {
+				db.record_repair_action(
+					&restore_point,
+					seq,
+					action,
+					RepairActionStatus::Applied,
+					Some(serde_json::to_string(&outcome)?),
+				)
+				.await?;
+				outcomes.push(outcome);
+			}
+			Err(err) => {
+				db.record_repair_action(
+					&restore_point,
+					seq,
+					action,
+					RepairActionStatus::Failed,
+					Some(format!("{err:#}")),
+				)
+				.await?;
+				return Err(err.context(format!(
+					"repair action {seq} failed, restore point {} undoes the run",
+					restore_point.as_str()
+				)));
+			}
+		}
+	}
+
+	let journal = db.repair_journal(&restore_point).await?;
+	Ok(RepairReport {
+		dry_run: false,
+		plan,
+		safety_restore_point: Some(restore_point),
+		outcomes,
+		journal,
+	})
+}
+
+/// Restores the safety restore point of a repair run.
+pub async fn undo_repair(db: &Db, restore_point: RestorePointId) -> Result<RepairUndoReport> {
+	let journal = db.repair_journal(&restore_point).await?;
+	ensure!(
+		!journal.is_empty(),
+		"restore point {} was not taken by a repair run",
+		restore_point.as_str()
+	);
+
+	let undo_restore_point = db
+		.restore_database(SnapshotSelector::RestorePoint {
+			restore_point: restore_point.clone(),
+		})
+		.await
+		.context("restore repair safety restore point")?;
+
+	Ok(RepairUndoReport {
+		restore_point,
+		undo_restore_point,
+		journal,
+	})
+}
+
+/// Maps a doctor verdict to the repairs that address it. Verdicts without a known repair get an
+/// empty plan.
+pub fn plan_repairs(report: &DoctorReport) -> RepairPlan {
+	let verdict = &report.verdict;
+	let actions = match (verdict.verdict, verdict.corruption_class, verdict.reason) {
+		(DoctorVerdictKind::Corrupt, CorruptionClass::DeltaHistory, _) => report
+			.previous_good_txid
+			.map(|txid| vec![RepairAction::TruncateToTxid { txid }])
+			.unwrap_or_default(),
+		(DoctorVerdictKind::Corrupt, _, Some("pidx_integrity_failed")) => {
+			vec![RepairAction::RebuildPageIndex, RepairAction::RecomputeQuota]
+		}
+		(DoctorVerdictKind::Corrupt, CorruptionClass::HotCompactionState, _) => {
+			vec![RepairAction::RerunCompaction]
+		}
+		_ => Vec::new(),
+	};
+
+	RepairPlan {
+		actions,
+		verdict: Some(verdict.clone()),
+		first_bad_txid: report.first_bad_txid,
+		previous_good_txid: report.previous_good_txid,
+	}
+}
+
+async fn apply_action(db: &Db, action: RepairAction) -> Result<RepairOutcome> {
+	Ok(match action {
+		RepairAction::RebuildPageIndex => {
+			RepairOutcome::RebuildPageIndex(db.rebuild_page_index().await?)
+		}
+		RepairAction::RecomputeQuota => RepairOutcome::RecomputeQuota(db.recompute_quota().await?),
+		RepairAction::TruncateToTxid { txid } => RepairOutcome::TruncateToTxid {
+			txid,
+			undo_restore_point: db.restore_database_to_txid(txid).await?,
+		},
+		RepairAction::RerunCompaction => {
+			RepairOutcome::RerunCompaction(db.rerun_compaction().await?)
+		}
+	})
+}
@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use futures_util::FutureExt;
+use gas::prelude::*;
+
+use crate::cold::{self, drain};
+
+/// How long the drainer idles before re-reading the config while the cold tier is disabled.
+const DISABLED_RECHECK_INTERVAL_MS: u64 = 5 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdDrainerInput {}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DrainState {
+	cursor: Option<Vec<u8>>,
+	branches_drained: usize,
+	shards_drained: usize,
+	bytes_drained: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SweepState {
+	start_after: Option<String>,
+	objects_swept: usize,
+}
+
+/// Singleton loop that offloads superseded shard versions to the configured cold store. Branches
+/// and stored objects are visited one page per activity so a large deployment never needs a
+/// single long-running activity.
+#[workflow(ColdDrainerWorkflow)]
+pub async fn depot_cold_drainer(ctx: &mut WorkflowCtx, _input: &ColdDrainerInput) -> Result<()> {
+	ctx.repeat(|ctx| {
+		async move {
+			let output = ctx.activity(PlanColdDrainInput {}).await?;
+
+			if output.enabled {
+				let drained = ctx
+					.loope(DrainState::default(), |ctx, state| {
+						async move {
+							let page = ctx
+								.activity(DrainColdBranchesInput {
+									cursor: state.cursor.clone(),
+								})
+								.await?;
+							state.branches_drained += page.branches_drained;
+							state.shards_drained += page.shards_drained;
+							state.bytes_drained += page.bytes_drained;
+
+							let Some(next_cursor) = page.next_cursor else {
+								return Ok(Loop::Break(std::mem::take(state)));
+							};
+							state.cursor = Some(next_cursor);
+
+							Ok(Loop::Continue)
+						}
+						.boxed()
+					})
+					.await?;
+
+				let swept = ctx
+					.loope(SweepState::default(), |ctx, state| {
+						async move {
+							let page = ctx
+								.activity(SweepColdObjectsInput {
+									start_after: state.start_after.clone(),
+								})
+								.await?;
+							state.objects_swept += page.objects_swept;
+
+							let Some(next_start_after) = page.next_start_after else {
+								return Ok(Loop::Break(std::mem::take(state)));
+							};
+							state.start_after = Some(next_start_after);
+
+							Ok(Loop::Continue)
+						}
+						.boxed()
+					})
+					.await?;
+
+				let deleted = ctx.activity(DeleteQueuedColdObjectsInput {}).await?;
+
+				tracing::debug!(
+					branches_drained = drained.branches_drained,
+					shards_drained = drained.shards_drained,
+					bytes_drained = drained.bytes_drained,
+					objects_swept = swept.objects_swept,
+					objects_deleted = deleted.objects_deleted,
+					"drained depot cold tier"
+				);
+			}
+
+			ctx.sleep(Duration::from_millis(output.next_drain_in_ms))
+				.await?;
+
+			Ok(Loop::<()>::Continue)
+		}
+		.boxed()
+	})
+	.await?;
+
+	Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct PlanColdDrainInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanColdDrainOutput {
+	/// False while the cold tier is disabled, in which case nothing is drained.
+	pub enabled: bool,
+	pub next_drain_in_ms: u64,
+}
+
+#[activity(PlanColdDrain)]
+pub async fn plan_cold_drain(
+	ctx: &ActivityCtx,
+	_input: &PlanColdDrainInput,
+) -> Result<PlanColdDrainOutput> {
+	// The cold tier can be disabled after the drainer was dispatched. Keep the workflow idle
+	// instead of failing so re-enabling it does not need a new dispatch.
+	let cold_tier = ctx.config().sqlite().cold_tier.as_ref();
+
+	Ok(PlanColdDrainOutput {
+		enabled: cold_tier.is_some(),
+		next_drain_in_ms: cold_tier
+			.map(|cold_tier| cold_tier.drain_interval_ms())
+			.unwrap_or(DISABLED_RECHECK_INTERVAL_MS),
+	})
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct DrainColdBranchesInput {
+	/// Where the previous page stopped. `None` starts at the first branch.
+	pub cursor: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainColdBranchesOutput {
+	pub branches_drained: usize,
+	pub shards_drained: usize,
+	pub bytes_drained: u64,
+	/// `None` once every branch was visited.
+	pub next_cursor: Option<Vec<u8>>,
+}
+
+#[activity(DrainColdBranches)]
+#[timeout = 600]
+pub async fn drain_cold_branches(
+	ctx: &ActivityCtx,
+	input: &DrainColdBranchesInput,
+) -> Result<DrainColdBranchesOutput> {
+	let mut output = DrainColdBranchesOutput {
+		branches_drained: 0,
+		shards_drained: 0,
+		bytes_drained: 0,
+		next_cursor: None,
+	};
+
+	let (Some(cold_tier), Some(store)) = (
+		ctx.config().sqlite().cold_tier.as_ref(),
+		cold::shared(ctx.config()).await?,
+	) else {
+		return Ok(output);
+	};
+
+	let udb = ctx.udb()?;
+	let (branches, next_cursor) = drain::list_branches_page(&udb, input.cursor.clone()).await?;
+	for branch_id in branches {
+		let outcome = drain::drain_branch(
+			&udb,
+			store.as_ref(),
+			branch_id,
+			cold_tier.hot_window_txids(),
+		)
+		.await?;
+		if outcome.shards_drained > 0 {
+			output.branches_drained += 1;
+		}
+		output.shards_drained += outcome.shards_drained;
+		output.bytes_drained += outcome.bytes_drained;
+	}
+	output.next_cursor = next_cursor;
+
+	Ok(output)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct SweepColdObjectsInput {
+	/// Last object key of the previous page. `None` starts at the first object.
+	pub start_after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepColdObjectsOutput {
+	pub objects_swept: usize,
+	/// `None` once every object was visited.
+	pub next_start_after: Option<String>,
+}
+
+#[activity(SweepColdObjects)]
+#[timeout = 600]
+pub async fn sweep_cold_objects(
+	ctx: &ActivityCtx,
+	input: &SweepColdObjectsInput,
+) -> Result<SweepColdObjectsOutput> {
+	let Some(store) = cold::shared(ctx.config()).await? else {
+		return Ok(SweepColdObjectsOutput {
+			objects_swept: 0,
+			next_start_after: None,
+		});
+	};
+
+	let udb = ctx.udb()?;
+	let (objects_swept, next_start_after) =
+		drain::sweep_orphaned_objects_page(&udb, store.as_ref(), input.start_after.clone()).await?;
+
+	Ok(SweepColdObjectsOutput {
+		objects_swept,
+		next_start_after,
+	})
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct DeleteQueuedColdObjectsInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteQueuedColdObjectsOutput {
+	pub objects_deleted: usize,
+}
+
+#[activity(DeleteQueuedColdObjects)]
+#[timeout = 600]
+pub async fn delete_queued_cold_objects(
+	ctx: &ActivityCtx,
+	_input: &DeleteQueuedColdObjectsInput,
+) -> Result<DeleteQueuedColdObjectsOutput> {
+	let Some(store) = cold::shared(ctx.config()).await? else {
+		return Ok(DeleteQueuedColdObjectsOutput { objects_deleted: 0 });
+	};
+
+	// Deleted rows leave the queue as each batch completes, so a retry resumes where this stopped.
+	let udb = ctx.udb()?;
+	let objects_deleted = drain::delete_queued_objects(&udb, store.as_ref()).await?;
+
+	Ok(DeleteQueuedColdObjectsOutput { objects_deleted })
+}
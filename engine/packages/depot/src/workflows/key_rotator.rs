@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use futures_util::FutureExt;
+use gas::prelude::*;
+
+use crate::{cold::drain, encryption};
+
+/// How long the rotator idles before re-reading the config while encryption is disabled.
+const DISABLED_RECHECK_INTERVAL_MS: u64 = 5 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRotatorInput {}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ReencryptState {
+	cursor: Option<Vec<u8>>,
+	deltas_reencrypted: usize,
+	shards_reencrypted: usize,
+	bytes_reencrypted: u64,
+}
+
+/// Singleton loop that rotates aged data keys and re-encrypts hot page blobs sealed under
+/// retired keys. Branches are re-encrypted one page per activity so a large deployment never
+/// needs a single long-running activity.
+#[workflow(KeyRotatorWorkflow)]
+pub async fn depot_key_rotator(ctx: &mut WorkflowCtx, _input: &KeyRotatorInput) -> Result<()> {
+	ctx.repeat(|ctx| {
+		async move {
+			let output = ctx.activity(RotateDataKeysInput {}).await?;
+
+			if output.enabled {
+				let state = ctx
+					.loope(ReencryptState::default(), |ctx, state| {
+						async move {
+							let page = ctx
+								.activity(ReencryptBranchesInput {
+									cursor: state.cursor.clone(),
+								})
+								.await?;
+							state.deltas_reencrypted += page.deltas_reencrypted;
+							state.shards_reencrypted += page.shards_reencrypted;
+							state.bytes_reencrypted += page.bytes_reencrypted;
+
+							let Some(next_cursor) = page.next_cursor else {
+								return Ok(Loop::Break(std::mem::take(state)));
+							};
+							state.cursor = Some(next_cursor);
+
+							Ok(Loop::Continue)
+						}
+						.boxed()
+					})
+					.await?;
+
+				tracing::debug!(
+					data_keys_rotated = output.data_keys_rotated,
+					data_keys_rewrapped = output.data_keys_rewrapped,
+					deltas_reencrypted = state.deltas_reencrypted,
+					shards_reencrypted = state.shards_reencrypted,
+					bytes_reencrypted = state.bytes_reencrypted,
+					"rotated depot data keys"
+				);
+			}
+
+			ctx.sleep(Duration::from_millis(output.next_rotation_in_ms))
+				.await?;
+
+			Ok(Loop::<()>::Continue)
+		}
+		.boxed()
+	})
+	.await?;
+
+	Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct RotateDataKeysInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotateDataKeysOutput {
+	/// False while encryption is disabled, in which case nothing is re-encrypted.
+	pub enabled: bool,
+	pub data_keys_rotated: usize,
+	pub data_keys_rewrapped: usize,
+	pub next_rotation_in_ms: u64,
+}
+
+#[activity(RotateDataKeys)]
+pub async fn rotate_data_keys(
+	ctx: &ActivityCtx,
+	_input: &RotateDataKeysInput,
+) -> Result<RotateDataKeysOutput> {
+	let encryption_config = ctx.config().sqlite().encryption.as_ref();
+	let mut output = RotateDataKeysOutput {
+		enabled: false,
+		data_keys_rotated: 0,
+		data_keys_rewrapped: 0,
+		next_rotation_in_ms: encryption_config
+			.map(|encryption| encryption.rotation_scan_interval_ms())
+			.unwrap_or(DISABLED_RECHECK_INTERVAL_MS),
+	};
+
+	// Encryption can be disabled after the rotator was dispatched. Keep the workflow idle instead
+	// of failing so re-enabling it does not need a new dispatch.
+	let (Some(encryption_config), Some(keyring)) =
+		(encryption_config, encryption::init(ctx.config())?)
+	else {
+		return Ok(output);
+	};
+
+	let udb = ctx.udb()?;
+	let max_age_ms =
+		i64::try_from(encryption_config.data_key_rotation_interval_ms()).unwrap_or(i64::MAX);
+	let rotation =
+		encryption::rotate::rotate_data_keys(&udb, &keyring, max_age_ms, util::timestamp::now())
+			.await?;
+	output.enabled = true;
+	output.data_keys_rotated = rotation.data_keys_rotated;
+	output.data_keys_rewrapped = rotation.data_keys_rewrapped;
+
+	Ok(output)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+pub struct ReencryptBranchesInput {
+	/// Where the previous page stopped. `None` starts at the first branch.
+	pub cursor: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReencryptBranchesOutput {
+	pub deltas_reencrypted: usize,
+	pub shards_reencrypted: usize,
+	pub bytes_reencrypted: u64,
+	/// `None` once every branch was visited.
+	pub next_cursor: Option<Vec<u8>>,
+}
+
+#[activity(ReencryptBranches)]
+#[timeout = 600]
+pub async fn reencrypt_branches(
+	ctx: &ActivityCtx,
+	input: &ReencryptBranchesInput,
+) -> Result<ReencryptBranchesOutput> {
+	let mut output = ReencryptBranchesOutput {
+		deltas_reencrypted: 0,
+		shards_reencrypted: 0,
+		bytes_reencrypted: 0,
+		next_cursor: None,
+	};
+
+	let Some(keyring) = encryption::init(ctx.config())? else {
+		return Ok(output);
+	};
+
+	let udb = ctx.udb()?;
+	let (branches, next_cursor) = drain::list_branches_page(&udb, input.cursor.clone()).await?;
+	for branch_id in branches {
+		let outcome = encryption::rotate::reencrypt_branch(&udb, &keyring, branch_id).await?;
+		output.deltas_reencrypted += outcome.deltas_reencrypted;
+		output.shards_reencrypted += outcome.shards_reencrypted;
+		output.bytes_reencrypted += outcome.bytes_reencrypted;
+	}
+	output.next_cursor = next_cursor;
+
+	Ok(output)
+}
@@ -1,6 +1,8 @@
+pub mod cold_drainer;
 pub mod db_hot_compacter;
 pub mod db_manager;
 pub mod db_reclaimer;
+pub mod key_rotator;
 
 pub mod compaction {
 	pub use crate::compaction::types::*;
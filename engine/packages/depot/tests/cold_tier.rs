@@ -0,0 +1,455 @@
+mod common;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use depot::{
+	cold::{self, ColdStore, FileSystemColdStore, S3ColdStore, drain},
+	error::SqliteStorageError,
+	keys::{
+		PAGE_SIZE, branch_cold_shard_key, branch_meta_head_key, branch_pidx_key, branch_shard_key,
+		bucket_pointer_cur_key, database_pointer_cur_key,
+	},
+	ltx::{LtxHeader, encode_ltx_v3},
+	types::{
+		BucketId, DBHead, DatabaseBranchId, DirtyPage, FetchedPage, decode_bucket_pointer,
+		decode_cold_shard_ref, decode_database_pointer, encode_db_head,
+	},
+};
+use gas::prelude::Id;
+use rivet_config::{config::SqliteColdStoreS3, secret::Secret};
+use tempfile::TempDir;
+
+const TEST_DATABASE: &str = "test-database";
+
+fn test_bucket() -> Id {
+	Id::v1(uuid::Uuid::from_u128(0x1234), 1)
+}
+
+fn page(pgno: u32, fill: u8) -> DirtyPage {
+	DirtyPage {
+		pgno,
+		bytes: vec![fill; PAGE_SIZE as usize],
+	}
+}
+
+fn fetched_page(pgno: u32, fill: u8) -> FetchedPage {
+	FetchedPage {
+		pgno,
+		bytes: Some(vec![fill; PAGE_SIZE as usize]),
+	}
+}
+
+fn encoded_blob(txid: u64, pages: &[(u32, u8)]) -> Result<Vec<u8>> {
+	let pages = pages
+		.iter()
+		.map(|(pgno, fill)| page(*pgno, *fill))
+		.collect::<Vec<_>>();
+
+	encode_ltx_v3(LtxHeader::delta(txid, 1, 999), &pages)
+}
+
+async fn read_branch_id(db: &universaldb::Database) -> Result<DatabaseBranchId> {
+	let bucket_id = BucketId::from_gas_id(test_bucket());
+	let bucket_pointer_bytes = common::read_value(db, bucket_pointer_cur_key(bucket_id))
+		.await?
+		.expect("bucket pointer should exist");
+	let bucket_branch = decode_bucket_pointer(&bucket_pointer_bytes)?.current_branch;
+	let bytes = common::read_value(db, database_pointer_cur_key(bucket_branch, TEST_DATABASE))
+		.await?
+		.expect("database pointer should exist");
+
+	Ok(decode_database_pointer(&bytes)?.current_branch)
+}
+
+async fn write(
+	db: &universaldb::Database,
+	sets: Vec<(Vec<u8>, Vec<u8>)>,
+	clears: Vec<Vec<u8>>,
+) -> Result<()> {
+	db.txn("test_depotcold_tier", move |tx| {
+		let sets = sets.clone();
+		let clears = clears.clone();
+		async move {
+			for (key, value) in sets {
+				tx.informal().set(&key, &value);
+			}
+			for key in clears {
+				tx.informal().clear(&key);
+			}
+			Ok(())
+		}
+	})
+	.await
+}
+
+/// Creates a database whose page 1 is only served from SHARD versions at txids 10, 20, and 30.
+async fn seed_shard_history(ctx: &common::TestDb) -> Result<DatabaseBranchId> {
+	let writer = ctx.make_db(test_bucket(), TEST_DATABASE);
+	writer.commit(vec![page(1, 0x01)], 1, 1_000).await?;
+	let branch_id = read_branch_id(&ctx.udb).await?;
+
+	write(
+		&ctx.udb,
+		vec![
+			(
+				branch_meta_head_key(branch_id),
+				encode_db_head(DBHead {
+					head_txid: 30,
+					db_size_pages: 1,
+					post_apply_checksum: 0,
+					branch_id,
+				})?,
+			),
+			(
+				branch_shard_key(branch_id, 0, 10),
+				encoded_blob(10, &[(1, 0x10)])?,
+			),
+			(
+				branch_shard_key(branch_id, 0, 20),
+				encoded_blob(20, &[(1, 0x20)])?,
+			),
+			(
+				branch_shard_key(branch_id, 0, 30),
+				encoded_blob(30, &[(1, 0x30)])?,
+			),
+		],
+		vec![branch_pidx_key(branch_id, 1)],
+	)
+	.await?;
+
+	Ok(branch_id)
+}
+
+#[tokio::test]
+async fn drain_offloads_versions_superseded_outside_hot_window() -> Result<()> {
+	let ctx = common::build_test_db("depot-cold-drain", common::TierMode::Disabled).await?;
+	let store_dir = TempDir::new()?;
+	let store = FileSystemColdStore::new(store_dir.path().to_path_buf());
+	let branch_id = seed_shard_history(&ctx).await?;
+
+	// Floor is 25: version 10 was superseded at 20, version 20 only at 30.
+	let outcome = drain::drain_branch(&ctx.udb, &store, branch_id, 5).await?;
+	assert_eq!(outcome.shards_drained, 1);
+	assert_eq!(outcome.shards_skipped, 0);
+
+	assert!(
+		common::read_value(&ctx.udb, branch_shard_key(branch_id, 0, 10))
+			.await?
+			.is_none()
+	);
+	assert!(
+		common::read_value(&ctx.udb, branch_shard_key(branch_id, 0, 20))
+			.await?
+			.is_some()
+	);
+	let cold_ref = decode_cold_shard_ref(
+		&common::read_value(&ctx.udb, branch_cold_shard_key(branch_id, 0, 10))
+			.await?
+			.expect("cold index row should exist"),
+	)?;
+	assert_eq!(
+		cold_ref.object_key,
+		cold::shard_object_key(branch_id, 0, 10)
+	);
+	assert_eq!(
+		store.get(&cold_ref.object_key).await?,
+		Some(encoded_blob(10, &[(1, 0x10)])?)
+	);
+
+	// A second pass has nothing left to offload.
+	let outcome = drain::drain_branch(&ctx.udb, &store, branch_id, 5).await?;
+	assert_eq!(outcome.shards_drained, 0);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn reads_fall_through_to_cold_store() -> Result<()> {
+	let ctx = common::build_test_db("depot-cold-read", common::TierMode::Disabled).await?;
+	let store_dir = TempDir::new()?;
+	let store: Arc<dyn ColdStore> =
+		Arc::new(FileSystemColdStore::new(store_dir.path().to_path_buf()));
+	let branch_id = seed_shard_history(&ctx).await?;
+	drain::drain_branch(&ctx.udb, store.as_ref(), branch_id, 5).await?;
+
+	// Leave the cold version as the only coverage for page 1.
+	write(
+		&ctx.udb,
+		Vec::new(),
+		vec![
+			branch_shard_key(branch_id, 0, 20),
+			branch_shard_key(branch_id, 0, 30),
+		],
+	)
+	.await?;
+
+	let reader = ctx
+		.make_db(test_bucket(), TEST_DATABASE)
+		.with_cold_store(Some(store.clone()));
+	assert_eq!(
+		reader.get_pages(vec![1]).await?,
+		vec![fetched_page(1, 0x10)]
+	);
+
+	let reader = ctx.make_db(test_bucket(), TEST_DATABASE);
+	let err = reader
+		.get_pages(vec![1])
+		.await
+		.expect_err("read without a cold store should fail");
+	assert!(matches!(
+		err.downcast_ref::<SqliteStorageError>(),
+		Some(SqliteStorageError::ColdShardUnavailable {
+			shard_id: 0,
+			as_of_txid: 10,
+			..
+		})
+	));
+
+	store
+		.put(&cold::shard_object_key(branch_id, 0, 10), vec![0xff; 16])
+		.await?;
+	let reader = ctx
+		.make_db(test_bucket(), TEST_DATABASE)
+		.with_cold_store(Some(store));
+	let err = reader
+		.get_pages(vec![1])
+		.await
+		.expect_err("corrupt cold object should fail");
+	assert!(matches!(
+		err.downcast_ref::<SqliteStorageError>(),
+		Some(SqliteStorageError::ColdShardUnavailable { .. })
+	));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn sweep_deletes_objects_of_missing_branches() -> Result<()> {
+	let ctx = common::build_test_db("depot-cold-sweep", common::TierMode::Disabled).await?;
+	let store_dir = TempDir::new()?;
+	let store = FileSystemColdStore::new(store_dir.path().to_path_buf());
+	let branch_id = seed_shard_history(&ctx).await?;
+	drain::drain_branch(&ctx.udb, &store, branch_id, 5).await?;
+
+	let orphan_key = cold::shard_object_key(
+		DatabaseBranchId::from_uuid(uuid::Uuid::from_u128(0x9999)),
+		0,
+		1,
+	);
+	store.put(&orphan_key, vec![1, 2, 3]).await?;
+	let object_key = cold::shard_object_key(branch_id, 0, 10);
+
+	// Listing pages through the keys in order.
+	let mut keys = vec![object_key.clone(), orphan_key.clone()];
+	keys.sort();
+	assert_eq!(store.list_page("", None, 1).await?, vec![keys[0].clone()]);
+	assert_eq!(
+		store.list_page("", Some(&keys[0]), 1).await?,
+		vec![keys[1].clone()]
+	);
+	assert!(store.list_page("", Some(&keys[1]), 1).await?.is_empty());
+
+	assert_eq!(drain::sweep_orphaned_objects(&ctx.udb, &store).await?, 1);
+	assert_eq!(store.get(&orphan_key).await?, None);
+	assert!(store.get(&object_key).await?.is_some());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn truncate_clears_cold_versions_of_truncated_shards() -> Result<()> {
+	let ctx = common::build_test_db("depot-cold-truncate", common::TierMode::Disabled).await?;
+	let store_dir = TempDir::new()?;
+	let store: Arc<dyn ColdStore> =
+		Arc::new(FileSystemColdStore::new(store_dir.path().to_path_buf()));
+	let writer = ctx.make_db(test_bucket(), TEST_DATABASE);
+	writer.commit(vec![page(1, 0x01)], 1, 1_000).await?;
+	let branch_id = read_branch_id(&ctx.udb).await?;
+
+	// Page 65 lives in shard 1 and is only served from SHARD versions at txids 10, 20, and 30.
+	write(
+		&ctx.udb,
+		vec![
+			(
+				branch_meta_head_key(branch_id),
+				encode_db_head(DBHead {
+					head_txid: 30,
+					db_size_pages: 65,
+					post_apply_checksum: 0,
+					branch_id,
+				})?,
+			),
+			(
+				branch_shard_key(branch_id, 1, 10),
+				encoded_blob(10, &[(65, 0x10)])?,
+			),
+			(
+				branch_shard_key(branch_id, 1, 20),
+				encoded_blob(20, &[(65, 0x20)])?,
+			),
+			(
+				branch_shard_key(branch_id, 1, 30),
+				encoded_blob(30, &[(65, 0x30)])?,
+			),
+		],
+		Vec::new(),
+	)
+	.await?;
+	let outcome = drain::drain_branch(&ctx.udb, store.as_ref(), branch_id, 5).await?;
+	assert_eq!(outcome.shards_drained, 1);
+
+	// Truncate below shard 1, then grow back over page 65 without writing it.
+	let writer = ctx.make_db(test_bucket(), TEST_DATABASE);
+	writer.commit(vec![page(1, 0x02)], 1, 2_000).await?;
+	assert!(
+		common::read_value(&ctx.udb, branch_cold_shard_key(branch_id, 1, 10))
+			.await?
+			.is_none()
+	);
+	writer.commit(vec![page(1, 0x03)], 65, 3_000).await?;
+
+	let reader = ctx
+		.make_db(test_bucket(), TEST_DATABASE)
+		.with_cold_store(Some(store.clone()));
+	assert_eq!(
+		reader.get_pages(vec![65]).await?,
+		vec![FetchedPage {
+			pgno: 65,
+			bytes: None,
+		}]
+	);
+
+	let object_key = cold::shard_object_key(branch_id, 1, 10);
+	assert!(store.get(&object_key).await?.is_some());
+	assert_eq!(
+		drain::delete_queued_objects(&ctx.udb, store.as_ref()).await?,
+		1
+	);
+	assert_eq!(store.get(&object_key).await?, None);
+	assert_eq!(
+		drain::delete_queued_objects(&ctx.udb, store.as_ref()).await?,
+		0
+	);
+
+	Ok(())
+}
+
+/// Builds an S3 store against MinIO from `DEPOT_TEST_MINIO_ENDPOINT`, or `None` to skip. The
+/// bucket (`DEPOT_TEST_MINIO_BUCKET`, defaults to `depot-test`) must already exist. Every call
+/// writes under a fresh prefix so runs do not see each other's objects.
+async fn minio_store() -> Result<Option<S3ColdStore>> {
+	let Ok(endpoint) = std::env::var("DEPOT_TEST_MINIO_ENDPOINT") else {
+		eprintln!("skipping, DEPOT_TEST_MINIO_ENDPOINT is not set");
+		return Ok(None);
+	};
+	let env =
+		|name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+
+	let store = S3ColdStore::new(&SqliteColdStoreS3 {
+		bucket: env("DEPOT_TEST_MINIO_BUCKET", "depot-test"),
+		prefix: Some(format!("cold-tier-test/{}/", uuid::Uuid::new_v4())),
+		region: None,
+		endpoint: Some(endpoint),
+		force_path_style: Some(true),
+		access_key_id: Some(Secret::new(env(
+			"DEPOT_TEST_MINIO_ACCESS_KEY",
+			"minioadmin",
+		))),
+		secret_access_key: Some(Secret::new(env(
+			"DEPOT_TEST_MINIO_SECRET_KEY",
+			"minioadmin",
+		))),
+	})
+	.await?;
+
+	Ok(Some(store))
+}
+
+#[tokio::test]
+async fn s3_store_drains_reads_and_sweeps() -> Result<()> {
+	let Some(store) = minio_store().await? else {
+		return Ok(());
+	};
+	let store: Arc<dyn ColdStore> = Arc::new(store);
+
+	// Missing objects read as `None` and delete cleanly.
+	let missing_key = cold::shard_object_key(
+		DatabaseBranchId::from_uuid(uuid::Uuid::from_u128(0x8888)),
+		0,
+		1,
+	);
+	assert_eq!(store.get(&missing_key).await?, None);
+	store.delete(&missing_key).await?;
+
+	// Drain
+	let ctx = common::build_test_db("depot-cold-s3", common::TierMode::Disabled).await?;
+	let branch_id = seed_shard_history(&ctx).await?;
+	let outcome = drain::drain_branch(&ctx.udb, store.as_ref(), branch_id, 5).await?;
+	assert_eq!(outcome.shards_drained, 1);
+
+	let object_key = cold::shard_object_key(branch_id, 0, 10);
+	assert_eq!(
+		store.get(&object_key).await?,
+		Some(encoded_blob(10, &[(1, 0x10)])?)
+	);
+	assert_eq!(
+		store.list(&cold::branch_object_prefix(branch_id)).await?,
+		vec![object_key.clone()]
+	);
+
+	// Read through
+	write(
+		&ctx.udb,
+		Vec::new(),
+		vec![
+			branch_shard_key(branch_id, 0, 20),
+			branch_shard_key(branch_id, 0, 30),
+		],
+	)
+	.await?;
+
+	let reader = ctx
+		.make_db(test_bucket(), TEST_DATABASE)
+		.with_cold_store(Some(store.clone()));
+	assert_eq!(
+		reader.get_pages(vec![1]).await?,
+		vec![fetched_page(1, 0x10)]
+	);
+
+	// Delete
+	let orphan_key = cold::shard_object_key(
+		DatabaseBranchId::from_uuid(uuid::Uuid::from_u128(0x9999)),
+		0,
+		1,
+	);
+	store.put(&orphan_key, vec![1, 2, 3]).await?;
+
+	// Listing pages through the keys in order.
+	let mut keys = vec![object_key.clone(), orphan_key.clone()];
+	keys.sort();
+	assert_eq!(store.list_page("", None, 1).await?, vec![keys[0].clone()]);
+	assert_eq!(
+		store.list_page("", Some(&keys[0]), 1).await?,
+		vec![keys[1].clone()]
+	);
+	assert!(store.list_page("", Some(&keys[1]), 1).await?.is_empty());
+
+	assert_eq!(
+		drain::sweep_orphaned_objects(&ctx.udb, store.as_ref()).await?,
+		1
+	);
+	assert_eq!(store.get(&orphan_key).await?, None);
+	assert!(store.get(&object_key).await?.is_some());
+
+	store.delete(&object_key).await?;
+	assert_eq!(store.get(&object_key).await?, None);
+	assert!(
+		store
+			.list(&cold::branch_object_prefix(branch_id))
+			.await?
+			.is_empty()
+	);
+
+	Ok(())
+}
@@ -60,6 +60,16 @@ fn pitr_errors_have_rivet_error_codes() {
 			"invalid_policy_value",
 		),
 		(SqliteStorageError::DatabaseNotFound, "database_not_found"),
+		(
+			SqliteStorageError::DatabaseAlreadyExists,
+			"database_already_exists",
+		),
+		(
+			SqliteStorageError::InvalidSqliteFile {
+				reason: "missing SQLite header".to_string(),
+			},
+			"invalid_sqlite_file",
+		),
 	];
 
 	for (err, code) in cases {
@@ -2,14 +2,15 @@ use depot::conveyer::keys::{
 	BR_PARTITION, BRANCHES_PARTITION, BUCKET_BRANCH_PARTITION, BUCKET_CATALOG_BY_DB_PARTITION,
 	BUCKET_CHILD_PARTITION, BUCKET_FORK_PIN_PARTITION, BUCKET_PROOF_EPOCH_PARTITION,
 	BUCKET_PTR_PARTITION, CTR_PARTITION, DB_PIN_PARTITION, DBPTR_PARTITION, PAGE_SIZE,
-	RESTORE_POINT_PARTITION, SHARD_SIZE, SQLITE_CMP_DIRTY_PARTITION, SQLITE_SUBSPACE_PREFIX,
-	branch_commit_key, branch_compaction_root_key, branch_compaction_stage_hot_shard_key,
-	branch_compaction_stage_hot_shard_version_prefix, branch_delta_chunk_key,
-	branch_manifest_last_access_bucket_key, branch_manifest_last_access_ts_ms_key,
-	branch_manifest_last_hot_pass_txid_key, branch_meta_compact_key,
-	branch_meta_compactor_lease_key, branch_meta_head_at_fork_key, branch_meta_head_key,
-	branch_meta_quota_key, branch_pidx_key, branch_pitr_interval_key, branch_pitr_interval_prefix,
-	branch_prefix, branch_range, branch_shard_key, branch_shard_version_prefix, branch_vtx_key,
+	REPAIR_JOURNAL_PARTITION, RESTORE_POINT_PARTITION, SHARD_SIZE, SQLITE_CMP_DIRTY_PARTITION,
+	SQLITE_SUBSPACE_PREFIX, branch_commit_key, branch_compaction_root_key,
+	branch_compaction_stage_hot_shard_key, branch_compaction_stage_hot_shard_version_prefix,
+	branch_delta_chunk_key, branch_manifest_last_access_bucket_key,
+	branch_manifest_last_access_ts_ms_key, branch_manifest_last_hot_pass_txid_key,
+	branch_meta_compact_key, branch_meta_compactor_lease_key, branch_meta_head_at_fork_key,
+	branch_meta_head_key, branch_meta_quota_key, branch_meta_row_changes_key, branch_pidx_key,
+	branch_pitr_interval_key, branch_pitr_interval_prefix, branch_prefix, branch_range,
+	branch_row_change_key, branch_shard_key, branch_shard_version_prefix, branch_vtx_key,
 	branches_desc_pin_key, branches_list_key, branches_refcount_key,
 	branches_restore_point_pin_key, bucket_branches_database_name_tombstone_key,
 	bucket_branches_desc_pin_key, bucket_branches_list_key, bucket_branches_refcount_key,
@@ -21,9 +22,9 @@ use depot::conveyer::keys::{
 	database_pointer_cur_key, database_pointer_history_key, database_prefix, database_range,
 	database_shard_cache_policy_key, db_pin_key, db_pin_prefix, decode_ctr_eviction_index_key,
 	delta_chunk_key, delta_chunk_prefix, delta_prefix, meta_compact_key, meta_compactor_lease_key,
-	meta_head_key, meta_quota_key, pidx_delta_key, pidx_delta_prefix, restore_point_key,
-	restore_point_prefix, shard_key, shard_prefix, shard_version_key, shard_version_prefix,
-	sqlite_cmp_dirty_key, vtx_key,
+	meta_head_key, meta_quota_key, pidx_delta_key, pidx_delta_prefix, repair_journal_key,
+	repair_journal_prefix, restore_point_key, restore_point_prefix, shard_key, shard_prefix,
+	shard_version_key, shard_version_prefix, sqlite_cmp_dirty_key, vtx_key,
 };
 use depot::conveyer::types::{BucketBranchId, BucketId, DatabaseBranchId};
 use gas::prelude::Id;
@@ -310,6 +311,7 @@ fn database_branch_data_keys_live_under_br_partition() {
 		branch_meta_compact_key(branch),
 		branch_meta_quota_key(branch),
 		branch_meta_compactor_lease_key(branch),
+		branch_meta_row_changes_key(branch),
 		branch_manifest_last_hot_pass_txid_key(branch),
 		branch_manifest_last_access_ts_ms_key(branch),
 		branch_manifest_last_access_bucket_key(branch),
@@ -320,6 +322,7 @@ fn database_branch_data_keys_live_under_br_partition() {
 		branch_pitr_interval_key(branch, 1_700_000_000_000),
 		branch_pidx_key(branch, 9),
 		branch_delta_chunk_key(branch, 7, 2),
+		branch_row_change_key(branch, 7, 2),
 		branch_shard_key(branch, 4, 7),
 	] {
 		assert!(key.starts_with(&branch_prefix(branch)));
@@ -369,6 +372,24 @@ fn global_restore_point_and_compactor_keys_match_expected_suffixes() {
 		]
 		.concat()
 	);
+
+	let mut expected_journal = vec![SQLITE_SUBSPACE_PREFIX, REPAIR_JOURNAL_PARTITION, b'/'];
+	expected_journal.extend_from_slice(b"test-database/");
+	expected_journal.extend_from_slice(TEST_RESTORE_POINT.as_bytes());
+	expected_journal.push(b'/');
+	assert_eq!(
+		repair_journal_prefix(TEST_DATABASE, TEST_RESTORE_POINT),
+		expected_journal
+	);
+	expected_journal.extend_from_slice(&2_u32.to_be_bytes());
+	assert_eq!(
+		repair_journal_key(TEST_DATABASE, TEST_RESTORE_POINT, 2),
+		expected_journal
+	);
+	assert!(
+		repair_journal_key(TEST_DATABASE, TEST_RESTORE_POINT, 1)
+			< repair_journal_key(TEST_DATABASE, TEST_RESTORE_POINT, 256)
+	);
 }
 
 #[test]
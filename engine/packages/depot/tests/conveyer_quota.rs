@@ -1,9 +1,19 @@
 mod common;
 
 use anyhow::Result;
-use depot::quota::{
-	SQLITE_MAX_STORAGE_BYTES, TRIGGER_MAX_SILENCE_MS, TRIGGER_THROTTLE_MS, atomic_add, cap_check,
-	read,
+use depot::{
+	error::SqliteStorageError,
+	keys::PAGE_SIZE,
+	policy::NamespacePolicyUpdate,
+	quota::{
+		SQLITE_MAX_STORAGE_BYTES, TRIGGER_MAX_SILENCE_MS, TRIGGER_THROTTLE_MS, atomic_add,
+		cap_check, namespace_cap_check, read,
+	},
+	types::DirtyPage,
+};
+use namespace::keys::{
+	database_quota::DatabaseQuota,
+	usage::{StorageKind, StorageUsageKey},
 };
 
 #[tokio::test]
@@ -81,6 +91,188 @@ fn cap_check_rejects_values_over_limit() {
 	);
 }
 
+#[tokio::test]
+async fn namespace_quota_rejects_commits_past_hard_limit() -> Result<()> {
+	let ctx = common::build_test_db("depot-quota-namespace", common::TierMode::Disabled).await?;
+	let namespace_id = ctx.bucket_id;
+	let page = |pgno| DirtyPage {
+		pgno,
+		bytes: vec![0x11; PAGE_SIZE as usize],
+	};
+
+	ctx.udb
+		.txn("test_depot_namespace_usage", move |tx| async move {
+			let tx = tx.with_subspace(namespace::keys::subspace());
+			tx.write(
+				&StorageUsageKey::new(namespace_id, StorageKind::Sqlite),
+				1_000_000,
+			)?;
+			Ok(())
+		})
+		.await?;
+
+	// Over the soft limit only: the commit goes through
+	depot::policy::set_namespace_quota(
+		&ctx.udb,
+		namespace_id,
+		DatabaseQuota {
+			hard_limit_bytes: None,
+			soft_limit_bytes: Some(1_000_000),
+		},
+	)
+	.await?;
+	ctx.db.commit(vec![page(1)], 1, 1_000).await?;
+
+	depot::policy::set_namespace_quota(
+		&ctx.udb,
+		namespace_id,
+		DatabaseQuota {
+			hard_limit_bytes: Some(1_000_000),
+			soft_limit_bytes: Some(1_000_000),
+		},
+	)
+	.await?;
+	let err = ctx
+		.db
+		.commit(vec![page(2)], 2, 2_000)
+		.await
+		.expect_err("commit past the namespace quota should fail");
+	assert_eq!(
+		err.downcast_ref::<SqliteStorageError>(),
+		Some(&SqliteStorageError::NamespaceQuotaExceeded {
+			used_bytes: 1_000_000,
+			limit_bytes: 1_000_000,
+		})
+	);
+
+	// Clearing the limits lets the database grow again
+	depot::policy::set_namespace_quota(&ctx.udb, namespace_id, DatabaseQuota::default()).await?;
+	ctx.db.commit(vec![page(2)], 2, 3_000).await?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn soft_limit_crossing_is_reported_once_while_rollup_lags() -> Result<()> {
+	let ctx =
+		common::build_test_db("depot-quota-soft-crossing", common::TierMode::Disabled).await?;
+	let namespace_id = ctx.bucket_id;
+
+	depot::policy::set_namespace_quota(
+		&ctx.udb,
+		namespace_id,
+		DatabaseQuota {
+			hard_limit_bytes: None,
+			soft_limit_bytes: Some(1_000),
+		},
+	)
+	.await?;
+
+	// The rollup is never updated, so every check sees the same usage before the commit
+	let check = |delta_bytes| {
+		ctx.udb
+			.txn("test_depot_soft_crossing", move |tx| async move {
+				namespace_cap_check(&tx, namespace_id, delta_bytes).await
+			})
+	};
+
+	assert_eq!(check(500).await?, None);
+	assert!(check(2_000).await?.expect("over the soft limit").crossed);
+	assert!(!check(2_000).await?.expect("over the soft limit").crossed);
+
+	// Dropping back under the limit rearms the crossing
+	assert_eq!(check(500).await?, None);
+	assert!(check(2_000).await?.expect("over the soft limit").crossed);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn namespace_policy_update_is_atomic_and_keeps_omitted_fields() -> Result<()> {
+	let ctx =
+		common::build_test_db("depot-quota-policy-update", common::TierMode::Disabled).await?;
+	let namespace_id = ctx.bucket_id;
+	let bucket_id = depot::types::BucketId::from_gas_id(namespace_id);
+
+	depot::policy::update_namespace_policy(
+		&ctx.udb,
+		namespace_id,
+		NamespacePolicyUpdate {
+			pitr_interval_ms: Some(60_000),
+			hard_limit_bytes: Some(Some(1_000_000)),
+			..Default::default()
+		},
+	)
+	.await?;
+
+	// Omitted fields keep their value
+	depot::policy::update_namespace_policy(
+		&ctx.udb,
+		namespace_id,
+		NamespacePolicyUpdate {
+			soft_limit_bytes: Some(Some(500_000)),
+			..Default::default()
+		},
+	)
+	.await?;
+	assert_eq!(
+		depot::policy::get_namespace_quota(&ctx.udb, namespace_id).await?,
+		DatabaseQuota {
+			hard_limit_bytes: Some(1_000_000),
+			soft_limit_bytes: Some(500_000),
+		}
+	);
+	assert_eq!(
+		depot::policy::get_bucket_pitr_policy(&ctx.udb, bucket_id)
+			.await?
+			.interval_ms,
+		60_000
+	);
+
+	// An invalid quota rejects the PITR change in the same update
+	let err = depot::policy::update_namespace_policy(
+		&ctx.udb,
+		namespace_id,
+		NamespacePolicyUpdate {
+			pitr_interval_ms: Some(120_000),
+			soft_limit_bytes: Some(Some(2_000_000)),
+			..Default::default()
+		},
+	)
+	.await
+	.expect_err("soft limit past the hard limit should fail");
+	assert!(err.chain().any(|cause| matches!(
+		cause.downcast_ref::<SqliteStorageError>(),
+		Some(SqliteStorageError::InvalidPolicyValue { .. })
+	)));
+	assert_eq!(
+		depot::policy::get_bucket_pitr_policy(&ctx.udb, bucket_id)
+			.await?
+			.interval_ms,
+		60_000
+	);
+
+	// `Some(None)` removes a limit
+	depot::policy::update_namespace_policy(
+		&ctx.udb,
+		namespace_id,
+		NamespacePolicyUpdate {
+			hard_limit_bytes: Some(None),
+			..Default::default()
+		},
+	)
+	.await?;
+	assert_eq!(
+		depot::policy::get_namespace_quota(&ctx.udb, namespace_id).await?,
+		DatabaseQuota {
+			hard_limit_bytes: None,
+			soft_limit_bytes: Some(500_000),
+		}
+	);
+
+	Ok(())
+}
+
 #[test]
 fn trigger_throttle_constants_match_spec() {
 	assert_eq!(TRIGGER_THROTTLE_MS, 500);
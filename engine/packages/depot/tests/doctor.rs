@@ -37,6 +37,7 @@ async fn run_doctor(
 			skip: SkipOptions::default(),
 			min_txid: None,
 			max_txid,
+			cold_store: None,
 			progress_hook: None,
 		},
 	)
@@ -58,6 +59,7 @@ async fn run_actor_doctor(
 			skip: SkipOptions::default(),
 			min_txid: None,
 			max_txid: None,
+			cold_store: None,
 			progress_hook: None,
 		},
 	)
@@ -802,6 +804,7 @@ async fn doctor_min_txid_only_moves_first_bad_search_start() -> Result<()> {
 			skip: SkipOptions::default(),
 			min_txid: Some(3),
 			max_txid: None,
+			cold_store: None,
 			progress_hook: None,
 		},
 	)
@@ -910,6 +913,7 @@ async fn doctor_marks_changed_during_diagnosis_inconclusive() -> Result<()> {
 			skip: SkipOptions::default(),
 			min_txid: None,
 			max_txid: None,
+			cold_store: None,
 			progress_hook: Some(hook),
 		},
 	)
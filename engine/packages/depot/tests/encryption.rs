@@ -0,0 +1,172 @@
+mod common;
+
+use anyhow::{Context, Result};
+use depot::{
+	encryption::{self, Keyring, rotate},
+	keys::{self, PAGE_SIZE},
+	types::{BucketId, DatabaseBranchId, DirtyPage, FetchedPage},
+};
+use universaldb::utils::IsolationLevel::Snapshot;
+
+const MASTER_KEY: [u8; 32] = [7; 32];
+
+fn keyring() -> std::sync::Arc<Keyring> {
+	encryption::install(Keyring::new(&MASTER_KEY, &[]).expect("test master key should be valid"))
+}
+
+fn page(pgno: u32, fill: u8) -> DirtyPage {
+	DirtyPage {
+		pgno,
+		bytes: vec![fill; PAGE_SIZE as usize],
+	}
+}
+
+fn fetched_page(pgno: u32, fill: u8) -> FetchedPage {
+	FetchedPage {
+		pgno,
+		bytes: Some(vec![fill; PAGE_SIZE as usize]),
+	}
+}
+
+async fn branch_id(ctx: &common::TestDb) -> Result<DatabaseBranchId> {
+	let bucket_id = BucketId::from_gas_id(ctx.bucket_id);
+	let database_id = ctx.database_id.clone();
+	ctx.udb
+		.txn("test_depot_encryption", move |tx| {
+			let database_id = database_id.clone();
+			async move {
+				depot::conveyer::branch::resolve_database_branch(
+					&tx,
+					bucket_id,
+					&database_id,
+					Snapshot,
+				)
+				.await?
+				.context("test database branch should exist")
+			}
+		})
+		.await
+}
+
+async fn delta_key_version(
+	ctx: &common::TestDb,
+	branch_id: DatabaseBranchId,
+	txid: u64,
+) -> Result<u32> {
+	let chunk = common::read_value(&ctx.udb, keys::branch_delta_chunk_key(branch_id, txid, 0))
+		.await?
+		.context("delta chunk should exist")?;
+	let key_id = encryption::sealed_key_id(&chunk)?.context("delta should be sealed")?;
+	assert_eq!(key_id.bucket_id, BucketId::from_gas_id(ctx.bucket_id));
+
+	Ok(key_id.version)
+}
+
+#[tokio::test]
+async fn commit_seals_deltas_and_reads_return_plaintext() -> Result<()> {
+	keyring();
+	let ctx = common::build_test_db("depot-encryption-commit", common::TierMode::Disabled).await?;
+	ctx.db.commit(vec![page(1, 0x42)], 1, 1_000).await?;
+	let branch_id = branch_id(&ctx).await?;
+
+	let chunk = common::read_value(&ctx.udb, keys::branch_delta_chunk_key(branch_id, 1, 0))
+		.await?
+		.context("delta chunk should exist")?;
+	assert!(encryption::is_sealed(&chunk));
+	assert!(
+		!chunk
+			.windows(64)
+			.any(|window| window.iter().all(|byte| *byte == 0x42)),
+		"sealed delta should not contain the page bytes"
+	);
+	assert_eq!(delta_key_version(&ctx, branch_id, 1).await?, 1);
+
+	let reader = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+	assert_eq!(
+		reader.get_pages(vec![1]).await?,
+		vec![fetched_page(1, 0x42)]
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn plaintext_blobs_pass_through() -> Result<()> {
+	let keyring = keyring();
+	let plaintext = b"not a sealed blob".to_vec();
+
+	assert!(!encryption::is_sealed(&plaintext));
+	assert_eq!(keyring.open(&plaintext)?.as_ref(), plaintext.as_slice());
+	assert_eq!(
+		encryption::open_blob(&plaintext)?.as_ref(),
+		plaintext.as_slice()
+	);
+	assert_eq!(
+		encryption::reseal_like(&plaintext, b"rewritten".to_vec())?,
+		b"rewritten".to_vec()
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn rotation_reencrypts_hot_deltas_under_the_new_key() -> Result<()> {
+	let keyring = keyring();
+	let ctx = common::build_test_db("depot-encryption-rotate", common::TierMode::Disabled).await?;
+	let bucket_id = BucketId::from_gas_id(ctx.bucket_id);
+	ctx.db.commit(vec![page(1, 0x01)], 1, 1_000).await?;
+	let branch_id = branch_id(&ctx).await?;
+
+	let rotated = keyring.rotate_data_key(&ctx.udb, bucket_id).await?;
+	assert_eq!(rotated.version, 2);
+	ctx.db.commit(vec![page(2, 0x02)], 2, 2_000).await?;
+	assert_eq!(delta_key_version(&ctx, branch_id, 1).await?, 1);
+	assert_eq!(delta_key_version(&ctx, branch_id, 2).await?, 2);
+
+	let outcome = rotate::reencrypt_branch(&ctx.udb, &keyring, branch_id).await?;
+	assert_eq!(outcome.deltas_reencrypted, 1);
+	assert_eq!(delta_key_version(&ctx, branch_id, 1).await?, 2);
+
+	let reader = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+	assert_eq!(
+		reader.get_pages(vec![1, 2]).await?,
+		vec![fetched_page(1, 0x01), fetched_page(2, 0x02)]
+	);
+
+	let outcome = rotate::reencrypt_branch(&ctx.udb, &keyring, branch_id).await?;
+	assert_eq!(outcome, rotate::BranchReencryptOutcome::default());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn aged_data_keys_are_rotated() -> Result<()> {
+	let keyring = keyring();
+	let ctx = common::build_test_db("depot-encryption-age", common::TierMode::Disabled).await?;
+	let bucket_id = BucketId::from_gas_id(ctx.bucket_id);
+	let data_key = keyring.current_data_key(&ctx.udb, bucket_id).await?;
+
+	let outcome =
+		rotate::rotate_data_keys(&ctx.udb, &keyring, 60_000, data_key.created_at_ms() + 1_000)
+			.await?;
+	assert_eq!(outcome.data_keys_rotated, 0);
+
+	let outcome = rotate::rotate_data_keys(
+		&ctx.udb,
+		&keyring,
+		60_000,
+		data_key.created_at_ms() + 60_000,
+	)
+	.await?;
+	assert_eq!(outcome.data_keys_rotated, 1);
+	assert_eq!(
+		keyring
+			.current_data_key(&ctx.udb, bucket_id)
+			.await?
+			.id()
+			.version,
+		2
+	);
+
+	Ok(())
+}
@@ -426,3 +426,64 @@ async fn fork_database_allows_depth_sixteen_and_rejects_depth_seventeen() -> Res
 	})
 	.await
 }
+
+#[tokio::test]
+async fn fork_database_into_uses_requested_id_and_rejects_existing_database() -> Result<()> {
+	common::test_matrix("depot-fork-database-into", |_tier, ctx| {
+		Box::pin(async move {
+			let db = ctx.udb.clone();
+			let source_bucket = ctx.bucket_id;
+			let bucket_id = BucketId::from_gas_id(source_bucket);
+			let source_database_id = ctx.database_id.clone();
+			let source = ctx.make_db(source_bucket, source_database_id.clone());
+			source.commit(vec![page(1, 0x11)], 2, 1_000).await?;
+			let source_branch =
+				read_database_branch_id(&db, source_bucket, &source_database_id).await?;
+
+			let forked_branch = branch::fork_database_into(
+				&db,
+				bucket_id,
+				source_database_id.clone(),
+				SnapshotSelector::Latest,
+				bucket_id,
+				"forked-database".to_string(),
+			)
+			.await?;
+			assert_eq!(
+				read_database_branch_id(&db, source_bucket, "forked-database").await?,
+				forked_branch
+			);
+			let forked = ctx.make_db(source_bucket, "forked-database");
+			let pages = forked.get_pages(vec![1]).await?;
+			assert_eq!(pages[0].bytes, Some(page_bytes(0x11)));
+
+			let lineage =
+				branch::database_lineage(&db, bucket_id, "forked-database".to_string()).await?;
+			assert_eq!(
+				lineage
+					.iter()
+					.map(|record| record.branch_id)
+					.collect::<Vec<_>>(),
+				vec![forked_branch, source_branch]
+			);
+
+			let err = branch::fork_database_into(
+				&db,
+				bucket_id,
+				source_database_id,
+				SnapshotSelector::Latest,
+				bucket_id,
+				"forked-database".to_string(),
+			)
+			.await
+			.expect_err("forking over an existing database should fail");
+			assert_storage_error(
+				&err,
+				depot::error::SqliteStorageError::DatabaseAlreadyExists,
+			);
+
+			Ok(())
+		})
+	})
+	.await
+}
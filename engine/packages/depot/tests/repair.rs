@@ -0,0 +1,236 @@
+mod common;
+
+use anyhow::{Context, Result, ensure};
+use depot::{
+	keys,
+	ltx::{LtxHeader, encode_ltx_v3},
+	repair::{RepairInput, RepairOutcome, repair, undo_repair},
+	types::{
+		BucketId, DatabaseBranchId, DirtyPage, RepairAction, RepairActionStatus, SnapshotSelector,
+		decode_commit_row,
+	},
+};
+use rusqlite::{Connection, params};
+use universaldb::utils::IsolationLevel::Snapshot;
+
+async fn branch_id(ctx: &common::TestDb) -> Result<DatabaseBranchId> {
+	let bucket_id = BucketId::from_gas_id(ctx.bucket_id);
+	let database_id = ctx.database_id.clone();
+	ctx.udb
+		.txn("test_depot_repair", move |tx| {
+			let database_id = database_id.clone();
+			async move {
+				depot::conveyer::branch::resolve_database_branch(
+					&tx,
+					bucket_id,
+					&database_id,
+					Snapshot,
+				)
+				.await?
+				.context("test database branch should exist")
+			}
+		})
+		.await
+}
+
+async fn set_value(db: &universaldb::Database, key: Vec<u8>, value: Vec<u8>) -> Result<()> {
+	db.txn("test_depot_repair", move |tx| {
+		let key = key.clone();
+		let value = value.clone();
+		async move {
+			tx.informal().set(&key, &value);
+			Ok(())
+		}
+	})
+	.await
+}
+
+async fn read_value(db: &universaldb::Database, key: Vec<u8>) -> Result<Option<Vec<u8>>> {
+	db.txn("test_depot_repair", move |tx| {
+		let key = key.clone();
+		async move {
+			Ok(tx
+				.informal()
+				.get(&key, Snapshot)
+				.await?
+				.map(Vec::<u8>::from))
+		}
+	})
+	.await
+}
+
+fn sqlite_dirty_pages(entries: &[(&str, &str)]) -> Result<Vec<DirtyPage>> {
+	let dir = tempfile::tempdir()?;
+	let path = dir.path().join("fixture.sqlite");
+	let conn = Connection::open(&path)?;
+	conn.execute_batch(
+		"PRAGMA page_size=4096;
+		 PRAGMA journal_mode=DELETE;
+		 CREATE TABLE kv (k TEXT PRIMARY KEY, v TEXT NOT NULL);",
+	)?;
+	for (key, value) in entries {
+		conn.execute(
+			"INSERT OR REPLACE INTO kv (k, v) VALUES (?1, ?2)",
+			params![key, value],
+		)?;
+	}
+	drop(conn);
+
+	let bytes = std::fs::read(&path)?;
+	ensure!(
+		bytes.len() % keys::PAGE_SIZE as usize == 0,
+		"SQLite fixture should be page aligned"
+	);
+	Ok(bytes
+		.chunks(keys::PAGE_SIZE as usize)
+		.enumerate()
+		.map(|(idx, bytes)| DirtyPage {
+			pgno: u32::try_from(idx + 1).expect("fixture page number should fit in u32"),
+			bytes: bytes.to_vec(),
+		})
+		.collect())
+}
+
+async fn commit_sqlite(ctx: &common::TestDb, entries: &[(&str, &str)], now_ms: i64) -> Result<()> {
+	let pages = sqlite_dirty_pages(entries)?;
+	let db_size_pages =
+		u32::try_from(pages.len()).context("fixture page count should fit in u32")?;
+	ctx.db.commit(pages, db_size_pages, now_ms).await
+}
+
+#[tokio::test]
+async fn repair_rebuilds_page_index_and_quota_from_doctor_plan() -> Result<()> {
+	let ctx = common::build_test_db("depot-repair-pidx", common::TierMode::Disabled).await?;
+	commit_sqlite(&ctx, &[("a", "1")], 1_000).await?;
+	commit_sqlite(&ctx, &[("a", "2")], 2_000).await?;
+	let branch_id = branch_id(&ctx).await?;
+	set_value(
+		&ctx.udb,
+		keys::branch_pidx_key(branch_id, 1),
+		1u64.to_be_bytes().to_vec(),
+	)
+	.await?;
+	set_value(
+		&ctx.udb,
+		keys::branch_meta_quota_key(branch_id),
+		7i64.to_le_bytes().to_vec(),
+	)
+	.await?;
+
+	let dry_run = repair(
+		&ctx.db,
+		RepairInput {
+			dry_run: true,
+			..Default::default()
+		},
+	)
+	.await?;
+	assert_eq!(
+		dry_run.plan.actions,
+		vec![RepairAction::RebuildPageIndex, RepairAction::RecomputeQuota]
+	);
+	assert!(dry_run.safety_restore_point.is_none());
+	assert!(ctx.db.list_restore_points().await?.is_empty());
+	assert_eq!(
+		read_value(&ctx.udb, keys::branch_pidx_key(branch_id, 1)).await?,
+		Some(1u64.to_be_bytes().to_vec())
+	);
+
+	let report = repair(&ctx.db, RepairInput::default()).await?;
+	let restore_point = report
+		.safety_restore_point
+		.clone()
+		.context("repair should take a safety restore point")?;
+	assert_eq!(ctx.db.list_restore_points().await?.len(), 1);
+	assert_eq!(
+		read_value(&ctx.udb, keys::branch_pidx_key(branch_id, 1)).await?,
+		Some(2u64.to_be_bytes().to_vec())
+	);
+	let Some(RepairOutcome::RecomputeQuota(quota)) = report.outcomes.get(1) else {
+		panic!("second outcome should be a quota recompute");
+	};
+	assert_eq!(quota.previous_bytes, 7);
+	assert!(quota.recomputed_bytes > 7);
+
+	let journal = ctx.db.repair_journal(&restore_point).await?;
+	assert_eq!(journal, report.journal);
+	assert_eq!(
+		journal
+			.iter()
+			.map(|entry| (entry.seq, entry.action, entry.status))
+			.collect::<Vec<_>>(),
+		vec![
+			(
+				0,
+				RepairAction::RebuildPageIndex,
+				RepairActionStatus::Applied
+			),
+			(1, RepairAction::RecomputeQuota, RepairActionStatus::Applied),
+		]
+	);
+	assert!(
+		journal
+			.iter()
+			.all(|entry| entry.database_branch_id == branch_id)
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn repair_truncates_bad_history_and_undo_restores_it() -> Result<()> {
+	let ctx = common::build_test_db("depot-repair-truncate", common::TierMode::Disabled).await?;
+	commit_sqlite(&ctx, &[("a", "1")], 1_000).await?;
+	commit_sqlite(&ctx, &[("a", "2")], 2_000).await?;
+	let branch_id = branch_id(&ctx).await?;
+	let commit_bytes = read_value(&ctx.udb, keys::branch_commit_key(branch_id, 2))
+		.await?
+		.context("commit row should exist")?;
+	let commit = decode_commit_row(&commit_bytes)?;
+	let corrupt_header_page = DirtyPage {
+		pgno: 1,
+		bytes: vec![0; keys::PAGE_SIZE as usize],
+	};
+	set_value(
+		&ctx.udb,
+		keys::branch_delta_chunk_key(branch_id, 2, 0),
+		encode_ltx_v3(
+			LtxHeader::delta(2, commit.db_size_pages, 10_002),
+			&[corrupt_header_page],
+		)?,
+	)
+	.await?;
+
+	let report = repair(&ctx.db, RepairInput::default()).await?;
+	assert_eq!(
+		report.plan.actions,
+		vec![RepairAction::TruncateToTxid { txid: 1 }]
+	);
+	let restore_point = report
+		.safety_restore_point
+		.clone()
+		.context("repair should take a safety restore point")?;
+	assert_eq!(
+		ctx.db
+			.resolve_restore_target(SnapshotSelector::Latest)
+			.await?
+			.txid,
+		1
+	);
+	let journal = ctx.db.repair_journal(&restore_point).await?;
+	assert_eq!(journal.len(), 1);
+	assert_eq!(journal[0].database_branch_id, branch_id);
+	assert_eq!(journal[0].status, RepairActionStatus::Applied);
+
+	let undo = undo_repair(&ctx.db, restore_point).await?;
+	assert_eq!(undo.journal, journal);
+	assert_eq!(
+		ctx.db
+			.resolve_restore_target(SnapshotSelector::Latest)
+			.await?
+			.txid,
+		2
+	);
+
+	Ok(())
+}
@@ -0,0 +1,179 @@
+mod common;
+mod fork_common;
+
+use anyhow::{Context, Result};
+use depot::{
+	conveyer::Db,
+	error::SqliteStorageError,
+	replica::{ReplicaChanges, replica_database_id},
+	types::{FetchedPage, ReplicaBatch, ReplicaBatchKind, ReplicaCursor},
+};
+use futures_util::TryStreamExt;
+
+use fork_common::{assert_storage_error, page, page_bytes};
+
+const SOURCE_DC_LABEL: u16 = 1;
+
+fn fetched(pgno: u32, fill: u8) -> FetchedPage {
+	FetchedPage {
+		pgno,
+		bytes: Some(page_bytes(fill)),
+	}
+}
+
+/// Ships a full snapshot of `source` into `replica`.
+async fn resync(source: &Db, replica: &Db, observed_at_ms: i64) -> Result<ReplicaCursor> {
+	let snapshot = source
+		.replica_snapshot()
+		.await?
+		.context("source should have commits")?;
+	let batches = snapshot.pages.try_collect::<Vec<_>>().await?;
+	let batch_count = batches.len();
+	for (idx, pages) in batches.into_iter().enumerate() {
+		replica
+			.apply_replica_batch(
+				ReplicaBatch {
+					source_dc_label: SOURCE_DC_LABEL,
+					source_head: snapshot.head,
+					observed_at_ms,
+					kind: ReplicaBatchKind::Snapshot {
+						db_size_pages: snapshot.db_size_pages,
+						pages,
+						last: idx + 1 == batch_count,
+					},
+				},
+				observed_at_ms,
+			)
+			.await?;
+	}
+
+	Ok(snapshot.head)
+}
+
+#[tokio::test]
+async fn replica_follows_source_through_snapshot_and_deltas() -> Result<()> {
+	common::test_matrix("depot-replica-follow", |_tier, ctx| {
+		Box::pin(async move {
+			let source = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+			let replica = ctx.make_db(ctx.bucket_id, replica_database_id("actor"));
+			source
+				.commit(vec![page(1, 0x11), page(2, 0x22)], 2, 1_000)
+				.await?;
+
+			let changes = source.replica_changes_after(None, 16).await?;
+			assert!(matches!(
+				changes,
+				Some(ReplicaChanges::ResyncRequired { .. })
+			));
+			let mut cursor = resync(&source, &replica, 1_500).await?;
+			let state = replica.replica_state().await?.context("replica state")?;
+			assert_eq!(state.cursor, Some(cursor));
+			assert_eq!(state.staleness_ms(2_000), Some(500));
+
+			source.commit(vec![page(2, 0x23)], 2, 2_000).await?;
+			source.commit(vec![page(3, 0x33)], 3, 3_000).await?;
+			let Some(ReplicaChanges::Deltas { head, deltas }) =
+				source.replica_changes_after(Some(cursor), 16).await?
+			else {
+				panic!("source should return deltas");
+			};
+			assert_eq!(deltas.len(), 2);
+			let batch = ReplicaBatch {
+				source_dc_label: SOURCE_DC_LABEL,
+				source_head: head,
+				observed_at_ms: 3_500,
+				kind: ReplicaBatchKind::Deltas(deltas),
+			};
+			replica.apply_replica_batch(batch.clone(), 3_600).await?;
+			// Retried batches skip deltas that were already applied.
+			let state = replica.apply_replica_batch(batch, 3_700).await?;
+			cursor = head;
+			assert_eq!(state.cursor, Some(cursor));
+			assert_eq!(state.caught_up_at_ms, 3_500);
+
+			assert_eq!(
+				replica.get_pages(vec![1, 2, 3]).await?,
+				vec![fetched(1, 0x11), fetched(2, 0x23), fetched(3, 0x33)]
+			);
+
+			let Some(ReplicaChanges::Deltas { deltas, .. }) =
+				source.replica_changes_after(Some(cursor), 16).await?
+			else {
+				panic!("caught up source should return no deltas");
+			};
+			assert!(deltas.is_empty());
+
+			Ok(())
+		})
+	})
+	.await
+}
+
+#[tokio::test]
+async fn replica_rejects_delta_gaps_and_missing_snapshots() -> Result<()> {
+	common::test_matrix("depot-replica-gaps", |_tier, ctx| {
+		Box::pin(async move {
+			let source = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+			let replica = ctx.make_db(ctx.bucket_id, replica_database_id("actor"));
+			source.commit(vec![page(1, 0x11)], 1, 1_000).await?;
+			source.commit(vec![page(1, 0x12)], 1, 2_000).await?;
+			source.commit(vec![page(1, 0x13)], 1, 3_000).await?;
+
+			let Some(ReplicaChanges::Deltas { head, mut deltas }) = source
+				.replica_changes_after(
+					Some(ReplicaCursor {
+						txid: 1,
+						..source_head(&source).await?
+					}),
+					16,
+				)
+				.await?
+			else {
+				panic!("source should return deltas");
+			};
+			let batch = |deltas| ReplicaBatch {
+				source_dc_label: SOURCE_DC_LABEL,
+				source_head: head,
+				observed_at_ms: 3_000,
+				kind: ReplicaBatchKind::Deltas(deltas),
+			};
+
+			let err = replica
+				.apply_replica_batch(batch(deltas.clone()), 3_000)
+				.await
+				.expect_err("deltas without a snapshot should be rejected");
+			assert_storage_error(
+				&err,
+				SqliteStorageError::ReplicaOutOfSync {
+					reason: "replica has no snapshot".to_string(),
+				},
+			);
+
+			resync(&source, &replica, 3_000).await?;
+
+			deltas.remove(0);
+			deltas[0].txid = 5;
+			let err = replica
+				.apply_replica_batch(batch(deltas), 3_000)
+				.await
+				.expect_err("a delta gap should be rejected");
+			assert_storage_error(
+				&err,
+				SqliteStorageError::ReplicaOutOfSync {
+					reason: "expected txid 4, received txid 5".to_string(),
+				},
+			);
+
+			Ok(())
+		})
+	})
+	.await
+}
+
+async fn source_head(source: &Db) -> Result<ReplicaCursor> {
+	let snapshot = source
+		.replica_snapshot()
+		.await?
+		.context("source should have commits")?;
+	Ok(snapshot.head)
+}
@@ -0,0 +1,126 @@
+mod common;
+mod fork_common;
+
+use anyhow::{Context, Result};
+use depot::{
+	error::SqliteStorageError,
+	types::{CommitOptions, RowChangeLogAppend, RowChangePosition},
+};
+
+use fork_common::{assert_storage_error, page};
+
+fn position(txid: u64, index: u32) -> RowChangePosition {
+	RowChangePosition { txid, index }
+}
+
+#[tokio::test]
+async fn row_change_log_is_written_with_the_commit() -> Result<()> {
+	common::test_matrix("depot-row-change-log", |_tier, ctx| {
+		Box::pin(async move {
+			let db = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+			assert_eq!(db.row_change_log().await?, None);
+
+			let result = db
+				.commit_with_row_changes(
+					vec![page(1, 0x11)],
+					1,
+					1_000,
+					CommitOptions::default(),
+					RowChangeLogAppend {
+						changes: vec![(0, b"a".to_vec()), (1, b"b".to_vec())],
+						floor: position(0, 0),
+					},
+				)
+				.await?;
+			assert_eq!(result.head_txid, 1);
+
+			// A new floor drops older changes in the same commit
+			db.commit_with_row_changes(
+				vec![page(1, 0x12)],
+				1,
+				2_000,
+				CommitOptions::default(),
+				RowChangeLogAppend {
+					changes: vec![(0, b"c".to_vec())],
+					floor: position(1, 0),
+				},
+			)
+			.await?;
+			let log = db.row_change_log().await?.context("log covers the head")?;
+			assert_eq!(log.floor, position(1, 0));
+			assert_eq!(
+				log.changes
+					.iter()
+					.map(|change| (change.position, change.change.as_slice()))
+					.collect::<Vec<_>>(),
+				vec![
+					(position(1, 1), b"b".as_slice()),
+					(position(2, 0), b"c".as_slice())
+				]
+			);
+
+			// A commit without a log leaves a gap
+			db.commit(vec![page(1, 0x13)], 1, 3_000).await?;
+			assert_eq!(db.row_change_log().await?, None);
+
+			Ok(())
+		})
+	})
+	.await
+}
+
+#[tokio::test]
+async fn row_change_log_rejects_invalid_appends() -> Result<()> {
+	common::test_matrix("depot-row-change-log-invalid", |_tier, ctx| {
+		Box::pin(async move {
+			let db = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+
+			let err = db
+				.commit_with_row_changes(
+					vec![page(1, 0x11)],
+					1,
+					1_000,
+					CommitOptions::default(),
+					RowChangeLogAppend {
+						changes: vec![(1, b"a".to_vec()), (0, b"b".to_vec())],
+						floor: position(0, 0),
+					},
+				)
+				.await
+				.expect_err("out of order changes should fail");
+			assert_storage_error(
+				&err,
+				SqliteStorageError::InvalidRowChangeLog {
+					reason: "change index 0 is out of order".to_string(),
+				},
+			);
+
+			let err = db
+				.commit_with_row_changes(
+					vec![page(1, 0x11)],
+					1,
+					1_000,
+					CommitOptions::default(),
+					RowChangeLogAppend {
+						changes: vec![(0, b"a".to_vec())],
+						floor: position(5, 0),
+					},
+				)
+				.await
+				.expect_err("floor past the commit should fail");
+			assert_storage_error(
+				&err,
+				SqliteStorageError::InvalidRowChangeLog {
+					reason: "floor txid 5 is past the commit txid 1".to_string(),
+				},
+			);
+
+			// Neither commit landed
+			assert_eq!(db.row_change_log().await?, None);
+			db.commit(vec![page(1, 0x11)], 1, 1_000).await?;
+
+			Ok(())
+		})
+	})
+	.await
+}
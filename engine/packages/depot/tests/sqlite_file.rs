@@ -0,0 +1,186 @@
+mod common;
+mod fork_common;
+
+use anyhow::Result;
+use depot::{constants::MAX_COMMIT_DIRTY_PAGES, error::SqliteStorageError, keys::PAGE_SIZE};
+#[cfg(feature = "test-faults")]
+use depot::{
+	conveyer::Db,
+	fault::{CommitFaultPoint, DepotFaultController, DepotFaultPoint},
+};
+use futures_util::TryStreamExt;
+#[cfg(feature = "test-faults")]
+use rivet_pools::NodeId;
+
+use fork_common::{assert_storage_error, page};
+
+fn sqlite_file(pages: u32) -> Vec<u8> {
+	let mut file = Vec::with_capacity(pages as usize * PAGE_SIZE as usize);
+	for pgno in 1..=pages {
+		file.extend_from_slice(&vec![pgno as u8; PAGE_SIZE as usize]);
+	}
+	file[..100].fill(0);
+	file[..16].copy_from_slice(b"SQLite format 3\0");
+	file[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+	// Rollback journal mode with no reserved space per page.
+	file[18] = 1;
+	file[19] = 1;
+	// A change counter matching version-valid-for makes the header page count authoritative.
+	file[24..28].copy_from_slice(&1_u32.to_be_bytes());
+	file[28..32].copy_from_slice(&pages.to_be_bytes());
+	file[92..96].copy_from_slice(&1_u32.to_be_bytes());
+	file
+}
+
+#[tokio::test]
+async fn sqlite_file_round_trips_through_import_and_export() -> Result<()> {
+	common::test_matrix("depot-sqlite-file-round-trip", |_tier, ctx| {
+		Box::pin(async move {
+			// Spans more than one commit batch so page 1 lands in a later commit.
+			let pages = MAX_COMMIT_DIRTY_PAGES as u32 + 5;
+			let file = sqlite_file(pages);
+			let db = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+
+			let result = db.import_sqlite_file(&file, 1_000).await?;
+			assert_eq!(result.db_size_pages, pages);
+
+			let export = db.export_sqlite_file().await?;
+			assert_eq!(export.head_txid, result.head_txid);
+			assert_eq!(export.size_bytes, file.len() as u64);
+			let exported = export.chunks.try_concat().await?;
+			assert_eq!(exported, file);
+
+			Ok(())
+		})
+	})
+	.await
+}
+
+#[tokio::test]
+async fn sqlite_file_export_fills_unwritten_pages_with_zeroes() -> Result<()> {
+	common::test_matrix("depot-sqlite-file-sparse", |_tier, ctx| {
+		Box::pin(async move {
+			let db = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+			db.commit(vec![page(1, 0x11), page(3, 0x33)], 3, 1_000)
+				.await?;
+
+			let exported = db.export_sqlite_file().await?.chunks.try_concat().await?;
+			let page_size = PAGE_SIZE as usize;
+			assert_eq!(exported.len(), 3 * page_size);
+			assert!(exported[..page_size].iter().all(|b| *b == 0x11));
+			assert!(exported[page_size..2 * page_size].iter().all(|b| *b == 0));
+			assert!(exported[2 * page_size..].iter().all(|b| *b == 0x33));
+
+			Ok(())
+		})
+	})
+	.await
+}
+
+#[tokio::test]
+async fn sqlite_file_import_rejects_non_empty_database_and_invalid_files() -> Result<()> {
+	common::test_matrix("depot-sqlite-file-import-rejects", |_tier, ctx| {
+		Box::pin(async move {
+			let db = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+			db.commit(vec![page(1, 0x11)], 1, 1_000).await?;
+
+			let err = db
+				.import_sqlite_file(&sqlite_file(2), 2_000)
+				.await
+				.expect_err("import into a non-empty database should fail");
+			assert_storage_error(&err, SqliteStorageError::DatabaseAlreadyExists);
+
+			let empty = ctx.make_db(ctx.bucket_id, "empty");
+			let mut truncated = sqlite_file(2);
+			truncated.truncate(PAGE_SIZE as usize + 10);
+			let mut wal = sqlite_file(2);
+			wal[18] = 2;
+			wal[19] = 2;
+			let mut reserved = sqlite_file(2);
+			reserved[20] = 8;
+			let mut stale_page_count = sqlite_file(2);
+			stale_page_count[28..32].copy_from_slice(&3_u32.to_be_bytes());
+			for (file, reason) in [
+				(
+					vec![0; PAGE_SIZE as usize],
+					"missing SQLite header".to_string(),
+				),
+				(
+					truncated,
+					format!(
+						"file size {} is not a multiple of the page size",
+						PAGE_SIZE + 10
+					),
+				),
+				(wal, "file is in WAL mode".to_string()),
+				(
+					reserved,
+					"reserved space of 8 bytes per page is not supported".to_string(),
+				),
+				(
+					stale_page_count,
+					"header page count 3 does not match file page count 2".to_string(),
+				),
+			] {
+				let err = empty
+					.import_sqlite_file(&file, 2_000)
+					.await
+					.expect_err("invalid file should be rejected");
+				assert_storage_error(&err, SqliteStorageError::InvalidSqliteFile { reason });
+			}
+
+			Ok(())
+		})
+	})
+	.await
+}
+
+#[cfg(feature = "test-faults")]
+#[tokio::test]
+async fn sqlite_file_import_retries_after_interrupted_batch() -> Result<()> {
+	common::test_matrix("depot-sqlite-file-import-interrupted", |_tier, ctx| {
+		Box::pin(async move {
+			let pages = MAX_COMMIT_DIRTY_PAGES as u32 + 5;
+			let file = sqlite_file(pages);
+			let controller = DepotFaultController::new();
+			controller
+				.at(DepotFaultPoint::Commit(CommitFaultPoint::BeforeTx))
+				.database_id(ctx.database_id.clone())
+				.nth(2)
+				.fail("stop before the page 1 batch")?;
+			let faulting_db = Db::new_with_fault_controller_for_test(
+				ctx.udb.clone(),
+				ctx.bucket_id,
+				ctx.database_id.clone(),
+				NodeId::new(),
+				controller.clone(),
+			);
+			faulting_db
+				.import_sqlite_file(&file, 1_000)
+				.await
+				.expect_err("interrupted import should fail");
+			controller.assert_expected_fired()?;
+
+			// The first batch landed without page 1.
+			let db = ctx.make_db(ctx.bucket_id, ctx.database_id.clone());
+			let partial = db.get_pages_with_metadata(vec![1]).await?;
+			assert_eq!(partial.db_size_pages, pages);
+			assert_eq!(partial.pages[0].bytes, None);
+
+			let result = db.import_sqlite_file(&file, 2_000).await?;
+			assert_eq!(result.db_size_pages, pages);
+			let exported = db.export_sqlite_file().await?.chunks.try_concat().await?;
+			assert_eq!(exported, file);
+
+			// A completed import no longer admits another one.
+			let err = db
+				.import_sqlite_file(&file, 3_000)
+				.await
+				.expect_err("import into an imported database should fail");
+			assert_storage_error(&err, SqliteStorageError::DatabaseAlreadyExists);
+
+			Ok(())
+		})
+	})
+	.await
+}
@@ -4,9 +4,12 @@ use anyhow::{Context, Result, bail};
 use base64::{Engine, engine::general_purpose::STANDARD};
 use clap::Parser;
 use depot::conveyer::Db;
+use depot::conveyer::types::{BucketId, PitrPolicy, RepairAction, RestorePointId};
 use depot::doctor::{DoctorInput, DoctorSelector, SkipOptions, doctor, exit_code_for_verdict};
+use depot::repair::{RepairInput, repair, undo_repair};
 use depot_client_types::{ColumnValue, QueryResult};
 use gas::prelude::Id;
+use namespace::keys::database_quota::DatabaseQuota;
 use serde_json::{Value, json};
 use universaldb::{Database, utils::IsolationLevel::*};
 use uuid::Uuid;
@@ -17,6 +20,12 @@ pub enum SubCommand {
 	Doctor(DoctorOpts),
 	/// Execute SQL against one Depot-backed SQLite database
 	Execute(ExecuteOpts),
+	/// Import a SQLite database file into one empty Depot-backed SQLite database
+	Import(ImportOpts),
+	/// Show or change the database policy of one namespace in this datacenter
+	Policy(PolicyOpts),
+	/// Repair Depot-backed SQLite storage for one database
+	Repair(RepairOpts),
 }
 
 impl SubCommand {
@@ -24,6 +33,9 @@ impl SubCommand {
 		match self {
 			Self::Doctor(opts) => opts.execute(config).await,
 			Self::Execute(opts) => opts.execute(config).await,
+			Self::Import(opts) => opts.execute(config).await,
+			Self::Policy(opts) => opts.execute(config).await,
+			Self::Repair(opts) => opts.execute(config).await,
 		}
 	}
 }
@@ -56,6 +68,8 @@ pub struct DoctorOpts {
 
 impl DoctorOpts {
 	pub async fn execute(self, config: rivet_config::Config) -> Result<()> {
+		let cold_store = depot::cold::from_config(&config).await?;
+		depot::encryption::init(&config)?;
 		let pools = rivet_pools::Pools::new(config).await?;
 		let udb = pools.udb()?;
 		let selector = self.selector(&udb).await?;
@@ -72,6 +86,7 @@ impl DoctorOpts {
 			min_txid: self.min_txid,
 			max_txid: self.max_txid,
 			progress_hook: None,
+			cold_store,
 		};
 
 		let report = doctor(&udb, input).await.context("run depot doctor")?;
@@ -140,15 +155,20 @@ pub struct ExecuteOpts {
 
 impl ExecuteOpts {
 	pub async fn execute(self, config: rivet_config::Config) -> Result<()> {
+		depot::encryption::init(&config)?;
 		let pools = rivet_pools::Pools::new(config).await?;
 		let udb = pools.udb()?;
 		let target = self.target(&udb).await?;
-		let db = Arc::new(Db::new(
-			Arc::new((*udb).clone()),
-			target.bucket_id,
-			target.database_id.clone(),
-			pools.node_id(),
-		));
+		let db = Arc::new(
+			Db::from_config(
+				pools.config(),
+				Arc::new((*udb).clone()),
+				target.bucket_id,
+				target.database_id.clone(),
+				pools.node_id(),
+			)
+			.await?,
+		);
 
 		let sqlite = depot_client_embedded::open_database_from_embedded_depot(
 			db,
@@ -176,41 +196,257 @@ impl ExecuteOpts {
 	}
 
 	async fn target(&self, udb: &Database) -> Result<ExecuteTarget> {
-		let bucket_database = self.bucket_id.is_some() || self.database_id.is_some();
-		let actor = self.actor_id.is_some();
-		let selector_count = usize::from(bucket_database) + usize::from(actor);
-		if selector_count != 1 {
-			bail!("provide exactly one selector: --bucket-id/--database-id or --actor-id");
+		resolve_target(udb, self.bucket_id, self.database_id.clone(), self.actor_id).await
+	}
+}
+
+#[derive(Parser)]
+pub struct ImportOpts {
+	#[arg(long)]
+	bucket_id: Option<Uuid>,
+	#[arg(long)]
+	database_id: Option<String>,
+	#[arg(long)]
+	actor_id: Option<Id>,
+	/// Path to the SQLite database file
+	#[arg(short = 'f', long)]
+	file: PathBuf,
+}
+
+impl ImportOpts {
+	pub async fn execute(self, config: rivet_config::Config) -> Result<()> {
+		let file = tokio::fs::read(&self.file)
+			.await
+			.with_context(|| format!("read {}", self.file.display()))?;
+
+		depot::encryption::init(&config)?;
+		let pools = rivet_pools::Pools::new(config).await?;
+		let udb = pools.udb()?;
+		let target = resolve_target(&udb, self.bucket_id, self.database_id, self.actor_id).await?;
+		let db = Db::from_config(
+			pools.config(),
+			Arc::new((*udb).clone()),
+			target.bucket_id,
+			target.database_id,
+			pools.node_id(),
+		)
+		.await?;
+
+		let result = db
+			.import_sqlite_file(&file, rivet_util::timestamp::now())
+			.await
+			.context("import SQLite database file")?;
+
+		println!(
+			"{}",
+			serde_json::to_string_pretty(&json!({
+				"head_txid": result.head_txid,
+				"db_size_pages": result.db_size_pages,
+			}))?
+		);
+
+		Ok(())
+	}
+}
+
+#[derive(Parser)]
+pub struct PolicyOpts {
+	#[arg(long)]
+	namespace_id: Id,
+	/// Spacing of the history kept for timestamp restores
+	#[arg(long)]
+	pitr_interval_ms: Option<i64>,
+	/// How far back timestamp restores can reach
+	#[arg(long)]
+	pitr_retention_ms: Option<i64>,
+	/// Reject commits that would grow the namespace's databases past this many bytes
+	#[arg(long, conflicts_with = "clear_storage_quota")]
+	storage_quota_bytes: Option<i64>,
+	/// Warn about commits that grow the namespace's databases past this many bytes
+	#[arg(long, conflicts_with = "clear_storage_soft_limit")]
+	storage_soft_limit_bytes: Option<i64>,
+	/// Remove the storage quota
+	#[arg(long)]
+	clear_storage_quota: bool,
+	/// Remove the storage soft limit
+	#[arg(long)]
+	clear_storage_soft_limit: bool,
+}
+
+impl PolicyOpts {
+	pub async fn execute(self, config: rivet_config::Config) -> Result<()> {
+		let pools = rivet_pools::Pools::new(config).await?;
+		let udb = pools.udb()?;
+		let bucket_id = BucketId::from_gas_id(self.namespace_id);
+
+		let mut pitr = depot::policy::get_bucket_pitr_policy(&udb, bucket_id).await?;
+		if self.pitr_interval_ms.is_some() || self.pitr_retention_ms.is_some() {
+			pitr = PitrPolicy {
+				interval_ms: self.pitr_interval_ms.unwrap_or(pitr.interval_ms),
+				retention_ms: self.pitr_retention_ms.unwrap_or(pitr.retention_ms),
+			};
+			depot::policy::set_bucket_pitr_policy(&udb, bucket_id, pitr)
+				.await
+				.context("set pitr policy")?;
 		}
 
-		if bucket_database {
-			let bucket_id = self
-				.bucket_id
-				.context("--bucket-id is required with --database-id")?;
-			let database_id = self
-				.database_id
-				.clone()
-				.context("--database-id is required with --bucket-id")?;
-			return Ok(ExecuteTarget {
-				bucket_id: Id::v1(bucket_id, 0),
-				database_id,
-			});
+		let mut quota = depot::policy::get_namespace_quota(&udb, self.namespace_id).await?;
+		let current = quota;
+		if self.clear_storage_quota {
+			quota.hard_limit_bytes = None;
+		}
+		if self.clear_storage_soft_limit {
+			quota.soft_limit_bytes = None;
+		}
+		if let Some(bytes) = self.storage_quota_bytes {
+			quota.hard_limit_bytes = Some(bytes);
+		}
+		if let Some(bytes) = self.storage_soft_limit_bytes {
+			quota.soft_limit_bytes = Some(bytes);
+		}
+		if quota != current {
+			depot::policy::set_namespace_quota(&udb, self.namespace_id, quota)
+				.await
+				.context("set namespace storage quota")?;
 		}
 
-		let actor_id = self.actor_id.context("--actor-id is required")?;
-		let namespace_id = lookup_actor_namespace_id(udb, actor_id).await?;
-		Ok(ExecuteTarget {
-			bucket_id: namespace_id,
-			database_id: actor_id.to_string(),
-		})
+		println!(
+			"{}",
+			serde_json::to_string_pretty(&policy_json(pitr, quota))?
+		);
+
+		Ok(())
 	}
 }
 
+#[derive(Parser)]
+pub struct RepairOpts {
+	#[arg(long)]
+	bucket_id: Option<Uuid>,
+	#[arg(long)]
+	database_id: Option<String>,
+	#[arg(long)]
+	actor_id: Option<Id>,
+	/// Print the plan without taking a restore point or changing storage
+	#[arg(long)]
+	dry_run: bool,
+	/// Roll the database back to this txid on its current branch
+	#[arg(long)]
+	truncate_to_txid: Option<u64>,
+	/// Rewrite the page index from the delta history
+	#[arg(long)]
+	rebuild_page_index: bool,
+	/// Reset the quota counter to the bytes the branch stores
+	#[arg(long)]
+	recompute_quota: bool,
+	/// Mark the branch dirty so compaction runs again
+	#[arg(long)]
+	rerun_compaction: bool,
+	/// Undo a repair run by restoring the safety restore point it printed
+	#[arg(long, conflicts_with_all = ["dry_run", "truncate_to_txid", "rebuild_page_index", "recompute_quota", "rerun_compaction"])]
+	undo: Option<String>,
+}
+
+impl RepairOpts {
+	pub async fn execute(self, config: rivet_config::Config) -> Result<()> {
+		depot::encryption::init(&config)?;
+		let pools = rivet_pools::Pools::new(config).await?;
+		let udb = pools.udb()?;
+		let target = resolve_target(&udb, self.bucket_id, self.database_id, self.actor_id).await?;
+		let db = Db::from_config(
+			pools.config(),
+			Arc::new((*udb).clone()),
+			target.bucket_id,
+			target.database_id,
+			pools.node_id(),
+		)
+		.await?;
+
+		if let Some(restore_point) = self.undo {
+			let restore_point = RestorePointId::new(restore_point)?;
+			let report = undo_repair(&db, restore_point)
+				.await
+				.context("undo depot repair")?;
+			println!("{}", serde_json::to_string_pretty(&report)?);
+			return Ok(());
+		}
+
+		// Explicit actions run in this order: truncating first means the rebuilds see the
+		// surviving history.
+		let mut actions = Vec::new();
+		if let Some(txid) = self.truncate_to_txid {
+			actions.push(RepairAction::TruncateToTxid { txid });
+		}
+		if self.rebuild_page_index {
+			actions.push(RepairAction::RebuildPageIndex);
+		}
+		if self.recompute_quota {
+			actions.push(RepairAction::RecomputeQuota);
+		}
+		if self.rerun_compaction {
+			actions.push(RepairAction::RerunCompaction);
+		}
+
+		let report = repair(
+			&db,
+			RepairInput {
+				actions,
+				dry_run: self.dry_run,
+				skip: SkipOptions::default(),
+			},
+		)
+		.await
+		.context("run depot repair")?;
+		println!("{}", serde_json::to_string_pretty(&report)?);
+
+		Ok(())
+	}
+}
+
+async fn resolve_target(
+	udb: &Database,
+	bucket_id: Option<Uuid>,
+	database_id: Option<String>,
+	actor_id: Option<Id>,
+) -> Result<ExecuteTarget> {
+	let bucket_database = bucket_id.is_some() || database_id.is_some();
+	let actor = actor_id.is_some();
+	let selector_count = usize::from(bucket_database) + usize::from(actor);
+	if selector_count != 1 {
+		bail!("provide exactly one selector: --bucket-id/--database-id or --actor-id");
+	}
+
+	if bucket_database {
+		let bucket_id = bucket_id.context("--bucket-id is required with --database-id")?;
+		let database_id = database_id.context("--database-id is required with --bucket-id")?;
+		return Ok(ExecuteTarget {
+			bucket_id: Id::v1(bucket_id, 0),
+			database_id,
+		});
+	}
+
+	let actor_id = actor_id.context("--actor-id is required")?;
+	let namespace_id = lookup_actor_namespace_id(udb, actor_id).await?;
+	Ok(ExecuteTarget {
+		bucket_id: namespace_id,
+		database_id: actor_id.to_string(),
+	})
+}
+
 struct ExecuteTarget {
 	bucket_id: Id,
 	database_id: String,
 }
 
+fn policy_json(pitr: PitrPolicy, quota: DatabaseQuota) -> Value {
+	json!({
+		"pitr_interval_ms": pitr.interval_ms,
+		"pitr_retention_ms": pitr.retention_ms,
+		"storage_quota_bytes": quota.hard_limit_bytes,
+		"storage_soft_limit_bytes": quota.soft_limit_bytes,
+	})
+}
+
 fn query_result_json(result: QueryResult) -> Value {
 	json!({
 		"columns": result.columns,
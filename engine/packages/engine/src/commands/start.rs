@@ -72,6 +72,7 @@ impl Opts {
 			services
 		};
 
+		depot::encryption::init(&config)?;
 		let pools = rivet_pools::Pools::new(config.clone()).await?;
 
 		verify_engine_version(&config, &pools).await?;
@@ -224,6 +224,7 @@ fn refresh_metadata_invalidates_protocol_cache_before_v2_dispatch() {
 					input: None,
 					runner_name_selector: runner_name.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
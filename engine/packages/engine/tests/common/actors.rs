@@ -121,6 +121,7 @@ pub async fn bulk_create_actors(
 				input: None,
 				runner_name_selector: TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
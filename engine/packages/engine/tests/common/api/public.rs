@@ -85,6 +85,30 @@ pub async fn namespaces_create(
 	parse_response(response).await
 }
 
+pub async fn build_namespaces_usage_request(
+	port: u16,
+	path: namespaces::usage::UsagePath,
+	query: namespaces::usage::UsageQuery,
+) -> Result<reqwest::RequestBuilder> {
+	let client = rivet_pools::reqwest::client().await?;
+	Ok(client.get(format!(
+		"{}/namespaces/{}/usage?{}",
+		get_endpoint(port),
+		urlencoding::encode(&path.namespace),
+		serde_html_form::to_string(&query)?
+	)))
+}
+
+pub async fn namespaces_usage(
+	port: u16,
+	path: namespaces::usage::UsagePath,
+	query: namespaces::usage::UsageQuery,
+) -> Result<namespaces::usage::UsageResponse> {
+	let request = build_namespaces_usage_request(port, path, query).await?;
+	let response = request.send().await?;
+	parse_response(response).await
+}
+
 // MARK: Runner Configs
 
 pub async fn build_runner_configs_list_request(
@@ -392,6 +416,86 @@ pub async fn actors_list_names(
 	parse_response(response).await
 }
 
+pub async fn build_actors_list_restore_points_request(
+	port: u16,
+	path: actors::restore_points::RestorePointsPath,
+	query: actors::restore_points::RestorePointsQuery,
+) -> Result<reqwest::RequestBuilder> {
+	let client = rivet_pools::reqwest::client().await?;
+	Ok(client.get(format!(
+		"{}/actors/{}/database/restore-points?{}",
+		get_endpoint(port),
+		path.actor_id,
+		serde_html_form::to_string(&query)?
+	)))
+}
+
+pub async fn actors_list_restore_points(
+	port: u16,
+	path: actors::restore_points::RestorePointsPath,
+	query: actors::restore_points::RestorePointsQuery,
+) -> Result<actors::restore_points::ListRestorePointsResponse> {
+	let request = build_actors_list_restore_points_request(port, path, query).await?;
+	let response = request.send().await?;
+	parse_response(response).await
+}
+
+pub async fn build_actors_create_restore_point_request(
+	port: u16,
+	path: actors::restore_points::RestorePointsPath,
+	query: actors::restore_points::RestorePointsQuery,
+	request: actors::restore_points::CreateRestorePointRequest,
+) -> Result<reqwest::RequestBuilder> {
+	let client = rivet_pools::reqwest::client().await?;
+	Ok(client
+		.post(format!(
+			"{}/actors/{}/database/restore-points?{}",
+			get_endpoint(port),
+			path.actor_id,
+			serde_html_form::to_string(&query)?
+		))
+		.json(&request))
+}
+
+pub async fn actors_create_restore_point(
+	port: u16,
+	path: actors::restore_points::RestorePointsPath,
+	query: actors::restore_points::RestorePointsQuery,
+	request: actors::restore_points::CreateRestorePointRequest,
+) -> Result<actors::restore_points::CreateRestorePointResponse> {
+	let req = build_actors_create_restore_point_request(port, path, query, request).await?;
+	let response = req.send().await?;
+	parse_response(response).await
+}
+
+pub async fn build_actors_restore_database_request(
+	port: u16,
+	path: actors::restore::RestorePath,
+	query: actors::restore::RestoreQuery,
+	request: actors::restore::RestoreRequest,
+) -> Result<reqwest::RequestBuilder> {
+	let client = rivet_pools::reqwest::client().await?;
+	Ok(client
+		.post(format!(
+			"{}/actors/{}/database/restore?{}",
+			get_endpoint(port),
+			path.actor_id,
+			serde_html_form::to_string(&query)?
+		))
+		.json(&request))
+}
+
+pub async fn actors_restore_database(
+	port: u16,
+	path: actors::restore::RestorePath,
+	query: actors::restore::RestoreQuery,
+	request: actors::restore::RestoreRequest,
+) -> Result<actors::restore::RestoreResponse> {
+	let req = build_actors_restore_database_request(port, path, query, request).await?;
+	let response = req.send().await?;
+	parse_response(response).await
+}
+
 // MARK: Runners
 
 pub async fn build_runners_list_request(
@@ -413,6 +413,7 @@ pub async fn create_actor(
 			input: None,
 			runner_name_selector: runner_name.to_string(),
 			crash_policy,
+			fork_from: None,
 		},
 	)
 	.await
@@ -107,6 +107,7 @@ fn envoy_create_actor_with_input() {
 					input: Some(input_data.clone()),
 					runner_name_selector: envoy.pool_name().to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -472,6 +473,7 @@ fn public_create_with_unavailable_datacenter_returns_typed_error() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
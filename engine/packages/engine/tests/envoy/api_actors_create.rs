@@ -21,6 +21,7 @@ fn create_actor_valid_namespace() {
 					input: None,
 					runner_name_selector: runner.pool_name().to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -61,6 +62,7 @@ fn create_actor_with_key() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -99,6 +101,7 @@ fn create_actor_with_input() {
 					input: Some(input_data.clone()),
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -130,6 +133,7 @@ fn create_actor_sleep_crash_policy() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -168,6 +172,7 @@ fn create_actor_specific_datacenter() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -202,6 +207,7 @@ fn create_actor_non_existent_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await;
@@ -234,6 +240,7 @@ fn create_actor_invalid_datacenter() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await;
@@ -267,6 +274,7 @@ fn create_actor_remote_datacenter_verify() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -311,6 +319,7 @@ fn create_actor_input_large() {
 					input: Some(input_data),
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -346,6 +355,7 @@ fn create_actor_input_exceeds_max_size() {
 					input: Some(input_data),
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await;
@@ -379,6 +389,7 @@ fn create_actor_empty_key() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await;
@@ -411,6 +422,7 @@ fn create_actor_key_at_max_size() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -451,6 +463,7 @@ fn create_actor_key_exceeds_max_size() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await;
@@ -22,6 +22,7 @@ fn delete_existing_actor_with_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -72,6 +73,7 @@ fn delete_existing_actor_without_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -122,6 +124,7 @@ fn delete_actor_current_datacenter() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -171,6 +174,7 @@ fn delete_actor_remote_datacenter() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -249,6 +253,7 @@ fn delete_actor_wrong_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -302,6 +307,7 @@ fn delete_with_non_existent_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -351,6 +357,7 @@ fn delete_remote_actor_verify_propagation() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -408,6 +415,7 @@ fn delete_already_destroyed_actor() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -470,6 +478,7 @@ fn delete_actor_twice_rapidly() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -29,6 +29,7 @@ fn list_actors_by_namespace_and_name() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -97,6 +98,7 @@ fn list_with_pagination() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -279,6 +281,7 @@ fn list_actors_by_namespace_name_and_key() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -297,6 +300,7 @@ fn list_actors_by_namespace_name_and_key() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -348,6 +352,7 @@ fn list_with_include_destroyed_false() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -379,6 +384,7 @@ fn list_with_include_destroyed_false() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -431,6 +437,7 @@ fn list_with_include_destroyed_true() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -462,6 +469,7 @@ fn list_with_include_destroyed_true() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -598,6 +606,7 @@ fn list_actors_from_multiple_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -616,6 +625,7 @@ fn list_actors_from_multiple_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -801,6 +811,7 @@ fn verify_sorting_by_create_ts_descending() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -870,6 +881,7 @@ fn list_aggregates_results_from_all_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -888,6 +900,7 @@ fn list_aggregates_results_from_all_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -995,6 +1008,7 @@ fn list_by_key_with_include_destroyed_true() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1026,6 +1040,7 @@ fn list_by_key_with_include_destroyed_true() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1141,6 +1156,7 @@ fn list_with_invalid_actor_id_format_in_comma_list() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1209,6 +1225,7 @@ fn list_with_cursor_pagination() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -1356,6 +1373,7 @@ fn list_cursor_filters_by_timestamp() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -1445,6 +1463,7 @@ fn list_cursor_with_exact_timestamp_boundary() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -1526,6 +1545,7 @@ fn list_cursor_empty_results_when_no_more_actors() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -1650,6 +1670,7 @@ fn list_cursor_across_datacenters() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -1669,6 +1690,7 @@ fn list_cursor_across_datacenters() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -28,6 +28,7 @@ fn list_all_actor_names_in_namespace() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -48,6 +49,7 @@ fn list_all_actor_names_in_namespace() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -107,6 +109,7 @@ fn list_names_with_pagination() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -246,6 +249,7 @@ fn list_names_fanout_to_all_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -263,6 +267,7 @@ fn list_names_fanout_to_all_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -328,6 +333,7 @@ fn list_names_deduplication_across_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -345,6 +351,7 @@ fn list_names_deduplication_across_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -403,6 +410,7 @@ fn list_names_alphabetical_sorting() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -462,6 +470,7 @@ fn list_names_default_limit_100() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -521,6 +530,7 @@ fn list_names_with_metadata() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
 				},
 			)
 			.await
@@ -618,6 +628,7 @@ fn list_names_pagination_no_duplicates_comprehensive() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -710,6 +721,7 @@ fn list_names_pagination_boundary_cases() {
 						input: None,
 						runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 						crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+						fork_from: None,
 					},
 				)
 				.await
@@ -0,0 +1,371 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Context;
+use common::test_envoy::*;
+use depot::{
+	conveyer::{Db, branch},
+	keys::{PAGE_SIZE, branch_commit_key},
+	pitr_interval::write_pitr_interval_coverage,
+	types::{
+		BucketId, CommitRow, DatabaseBranchId, PitrIntervalCoverage, RestorePointId,
+		decode_commit_row,
+	},
+};
+use rivet_envoy_protocol as protocol;
+use rivet_util::Id;
+use tokio::sync::broadcast;
+use universaldb::utils::IsolationLevel::Serializable;
+
+use super::super::common;
+
+struct RestoreTestActor {
+	namespace: String,
+	namespace_id: Id,
+	actor_id: String,
+	generation: u32,
+	envoy: TestEnvoy,
+}
+
+async fn setup_actor(dc: &common::TestDatacenter) -> RestoreTestActor {
+	let (namespace, namespace_id) = common::setup_test_namespace(dc).await;
+	let envoy = common::setup_envoy(dc, &namespace, |builder| {
+		builder.with_actor_behavior("restore-actor", |_| Box::new(EchoActor::new()))
+	})
+	.await;
+	let lifecycle_rx = envoy.subscribe_lifecycle_events();
+
+	let res = common::create_actor(
+		dc.guard_port(),
+		&namespace,
+		"restore-actor",
+		envoy.pool_name(),
+		rivet_types::actors::CrashPolicy::Sleep,
+	)
+	.await;
+	let actor_id = res.actor.actor_id.to_string();
+	let generation = wait_for_started_generation(lifecycle_rx, &actor_id).await;
+
+	RestoreTestActor {
+		namespace,
+		namespace_id,
+		actor_id,
+		generation,
+		envoy,
+	}
+}
+
+async fn wait_for_started_generation(
+	mut rx: broadcast::Receiver<ActorLifecycleEvent>,
+	actor_id: &str,
+) -> u32 {
+	tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			match rx.recv().await {
+				Ok(ActorLifecycleEvent::Started {
+					actor_id: id,
+					generation,
+				}) if id == actor_id => return generation,
+				Ok(_) => {}
+				Err(broadcast::error::RecvError::Lagged(_)) => {}
+				Err(err) => panic!("actor lifecycle event channel closed: {err}"),
+			}
+		}
+	})
+	.await
+	.expect("timed out waiting for actor start")
+}
+
+fn page(fill: u8) -> Vec<u8> {
+	vec![fill; PAGE_SIZE as usize]
+}
+
+/// Commits page 1 filled with `fill` through the envoy and returns the new head txid.
+async fn commit_page(actor: &RestoreTestActor, fill: u8) -> u64 {
+	let response = actor
+		.envoy
+		.sqlite_commit(protocol::SqliteCommitRequest {
+			actor_id: actor.actor_id.clone(),
+			dirty_pages: vec![protocol::SqliteDirtyPage {
+				pgno: 1,
+				bytes: page(fill),
+			}],
+			db_size_pages: 1,
+			now_ms: rivet_util::timestamp::now(),
+			expected_generation: Some(u64::from(actor.generation)),
+			expected_head_txid: None,
+			row_changes: None,
+		})
+		.await
+		.expect("commit request should complete");
+	match response {
+		protocol::SqliteCommitResponse::SqliteCommitOk(ok) => {
+			ok.head_txid.expect("commit should return a head txid")
+		}
+		protocol::SqliteCommitResponse::SqliteErrorResponse(error) => {
+			panic!("commit failed: {}", error.message)
+		}
+	}
+}
+
+async fn read_page(dc: &common::TestDatacenter, actor: &RestoreTestActor) -> Vec<u8> {
+	let udb = dc.pools.udb().expect("udb should be available");
+	let db = Db::from_config(
+		&dc.config,
+		Arc::new((*udb).clone()),
+		actor.namespace_id,
+		actor.actor_id.clone(),
+		dc.pools.node_id(),
+	)
+	.await
+	.expect("depot handle should build");
+
+	db.get_pages(vec![1])
+		.await
+		.expect("page read should succeed")
+		.remove(0)
+		.bytes
+		.expect("page 1 should exist")
+}
+
+async fn commit_row(
+	dc: &common::TestDatacenter,
+	actor: &RestoreTestActor,
+	txid: u64,
+) -> (DatabaseBranchId, CommitRow) {
+	let udb = dc.pools.udb().expect("udb should be available");
+	let bucket_id = BucketId::from_gas_id(actor.namespace_id);
+	let database_id = actor.actor_id.clone();
+	udb.txn("test_api_actors_restore_commit_row", move |tx| {
+		let database_id = database_id.clone();
+
+		async move {
+			let branch_id =
+				branch::resolve_database_branch(&tx, bucket_id, &database_id, Serializable)
+					.await?
+					.context("database branch should exist")?;
+			let bytes = tx
+				.informal()
+				.get(&branch_commit_key(branch_id, txid), Serializable)
+				.await?
+				.context("commit row should exist")?;
+
+			Ok((branch_id, decode_commit_row(&bytes)?))
+		}
+	})
+	.await
+	.expect("commit row should be readable")
+}
+
+/// Records PITR coverage of commit `txid` as the hot compacter would, expiring at `expires_at_ms`.
+async fn seed_pitr_interval(
+	dc: &common::TestDatacenter,
+	actor: &RestoreTestActor,
+	txid: u64,
+	expires_at_ms: i64,
+) -> i64 {
+	let (branch_id, row) = commit_row(dc, actor, txid).await;
+	let coverage = PitrIntervalCoverage {
+		txid,
+		versionstamp: row.versionstamp,
+		wall_clock_ms: row.wall_clock_ms,
+		expires_at_ms,
+	};
+
+	let udb = dc.pools.udb().expect("udb should be available");
+	udb.txn("test_api_actors_restore_seed_pitr", move |tx| {
+		let coverage = coverage.clone();
+
+		async move {
+			write_pitr_interval_coverage(&tx, branch_id, coverage.wall_clock_ms, coverage)?;
+			Ok(())
+		}
+	})
+	.await
+	.expect("pitr interval should be written");
+
+	row.wall_clock_ms
+}
+
+async fn restore(
+	dc: &common::TestDatacenter,
+	actor: &RestoreTestActor,
+	request: common::api_types::actors::restore::RestoreRequest,
+) -> reqwest::Response {
+	common::api::public::build_actors_restore_database_request(
+		dc.guard_port(),
+		common::api_types::actors::restore::RestorePath {
+			actor_id: actor.actor_id.parse().expect("failed to parse actor_id"),
+		},
+		common::api_types::actors::restore::RestoreQuery {
+			namespace: actor.namespace.clone(),
+		},
+		request,
+	)
+	.await
+	.expect("failed to build request")
+	.send()
+	.await
+	.expect("failed to send request")
+}
+
+async fn list_restore_point_ids(
+	dc: &common::TestDatacenter,
+	actor: &RestoreTestActor,
+) -> Vec<String> {
+	common::api::public::actors_list_restore_points(
+		dc.guard_port(),
+		common::api_types::actors::restore_points::RestorePointsPath {
+			actor_id: actor.actor_id.parse().expect("failed to parse actor_id"),
+		},
+		common::api_types::actors::restore_points::RestorePointsQuery {
+			namespace: actor.namespace.clone(),
+		},
+	)
+	.await
+	.expect("failed to list restore points")
+	.restore_points
+	.into_iter()
+	.map(|restore_point| restore_point.restore_point_id)
+	.collect()
+}
+
+#[test]
+fn create_list_and_restore_to_restore_point() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let actor = setup_actor(dc).await;
+			commit_page(&actor, 0x11).await;
+
+			let created = common::api::public::actors_create_restore_point(
+				dc.guard_port(),
+				common::api_types::actors::restore_points::RestorePointsPath {
+					actor_id: actor.actor_id.parse().expect("failed to parse actor_id"),
+				},
+				common::api_types::actors::restore_points::RestorePointsQuery {
+					namespace: actor.namespace.clone(),
+				},
+				common::api_types::actors::restore_points::CreateRestorePointRequest { ts: None },
+			)
+			.await
+			.expect("failed to create restore point")
+			.restore_point;
+			assert!(matches!(
+				created.status,
+				common::api_types::actors::restore_points::RestorePointStatus::Ready
+			));
+			assert_eq!(
+				list_restore_point_ids(dc, &actor).await,
+				vec![created.restore_point_id.clone()]
+			);
+
+			commit_page(&actor, 0x22).await;
+			assert_eq!(read_page(dc, &actor).await, page(0x22));
+
+			let response = restore(
+				dc,
+				&actor,
+				common::api_types::actors::restore::RestoreRequest {
+					restore_point_id: Some(created.restore_point_id.clone()),
+					ts: None,
+				},
+			)
+			.await;
+			common::assert_success_response(&response);
+			let restored: common::api_types::actors::restore::RestoreResponse =
+				response.json().await.expect("invalid restore response");
+
+			assert_eq!(read_page(dc, &actor).await, page(0x11));
+			// The undo restore point is listed next to the one restored to
+			let restore_point_ids = list_restore_point_ids(dc, &actor).await;
+			assert_eq!(restore_point_ids.len(), 2);
+			assert!(restore_point_ids.contains(&created.restore_point_id));
+			assert!(restore_point_ids.contains(&restored.undo_restore_point_id));
+		},
+	);
+}
+
+#[test]
+fn restore_to_timestamp() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let actor = setup_actor(dc).await;
+			let txid = commit_page(&actor, 0x11).await;
+			commit_page(&actor, 0x22).await;
+			let ts = seed_pitr_interval(dc, &actor, txid, i64::MAX).await;
+
+			let response = restore(
+				dc,
+				&actor,
+				common::api_types::actors::restore::RestoreRequest {
+					restore_point_id: None,
+					ts: Some(ts),
+				},
+			)
+			.await;
+			common::assert_success_response(&response);
+
+			assert_eq!(read_page(dc, &actor).await, page(0x11));
+		},
+	);
+}
+
+#[test]
+fn restore_to_unknown_restore_point_fails() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let actor = setup_actor(dc).await;
+			let txid = commit_page(&actor, 0x11).await;
+
+			let unknown = RestorePointId::format(rivet_util::timestamp::now(), txid + 100)
+				.expect("restore point id should format");
+			let response = restore(
+				dc,
+				&actor,
+				common::api_types::actors::restore::RestoreRequest {
+					restore_point_id: Some(unknown.into_string()),
+					ts: None,
+				},
+			)
+			.await;
+			let body = common::assert_error_response(response, "restore_point_not_found").await;
+			assert_eq!(body["group"], "depot");
+
+			assert_eq!(read_page(dc, &actor).await, page(0x11));
+		},
+	);
+}
+
+#[test]
+fn restore_to_timestamp_outside_retention_fails() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let actor = setup_actor(dc).await;
+			let txid = commit_page(&actor, 0x11).await;
+			commit_page(&actor, 0x22).await;
+			// Coverage of the first commit has already expired
+			let ts = seed_pitr_interval(dc, &actor, txid, rivet_util::timestamp::now() - 1).await;
+
+			let response = restore(
+				dc,
+				&actor,
+				common::api_types::actors::restore::RestoreRequest {
+					restore_point_id: None,
+					ts: Some(ts),
+				},
+			)
+			.await;
+			let body = common::assert_error_response(response, "restore_point_expired").await;
+			assert_eq!(body["group"], "depot");
+
+			assert_eq!(read_page(dc, &actor).await, page(0x22));
+		},
+	);
+}
@@ -0,0 +1,218 @@
+use namespace::keys::usage::{StorageKind, record_actor};
+use rivet_util::Id;
+
+use super::super::common;
+
+/// Records an actor's usage the same way the metering pass does and returns the rollup deltas.
+async fn record(
+	dc: &common::TestDatacenter,
+	namespace_id: Id,
+	actor_id: Id,
+	usage: &[(StorageKind, i64)],
+) -> Vec<i64> {
+	let usage = usage.to_vec();
+	dc.workflow_ctx
+		.udb()
+		.expect("failed to get udb")
+		.txn("test_engineenvoy_record_usage", |tx| {
+			let usage = usage.clone();
+			async move {
+				let tx = tx.with_subspace(namespace::keys::subspace());
+
+				let mut deltas = Vec::new();
+				for (kind, bytes) in usage {
+					deltas.push(record_actor(&tx, namespace_id, actor_id, kind, bytes).await?);
+				}
+
+				Ok(deltas)
+			}
+		})
+		.await
+		.expect("failed to record usage")
+}
+
+async fn usage(
+	dc: &common::TestDatacenter,
+	namespace: &str,
+	actor_id: Option<Id>,
+) -> common::api_types::namespaces::usage::UsageResponse {
+	common::api::public::namespaces_usage(
+		dc.guard_port(),
+		common::api_types::namespaces::usage::UsagePath {
+			namespace: namespace.to_string(),
+		},
+		common::api_types::namespaces::usage::UsageQuery { actor_id },
+	)
+	.await
+	.expect("failed to get namespace usage")
+}
+
+#[test]
+fn usage_rollup_tracks_actor_deltas() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let (namespace, namespace_id) = common::setup_test_namespace(dc).await;
+			let actor_a = Id::new_v1(dc.config.dc_label());
+			let actor_b = Id::new_v1(dc.config.dc_label());
+
+			assert_eq!(
+				record(
+					dc,
+					namespace_id,
+					actor_a,
+					&[(StorageKind::ActorKv, 100), (StorageKind::Sqlite, 4096)],
+				)
+				.await,
+				vec![100, 4096]
+			);
+			assert_eq!(
+				record(dc, namespace_id, actor_b, &[(StorageKind::ActorKv, 50)]).await,
+				vec![50]
+			);
+
+			let res = usage(dc, &namespace, None).await;
+			assert_eq!(res.actor_kv_bytes, 150);
+			assert_eq!(res.sqlite_bytes, 4096);
+			assert_eq!(res.total_bytes, 4246);
+
+			// Re-recording only folds the change into the rollup
+			assert_eq!(
+				record(
+					dc,
+					namespace_id,
+					actor_a,
+					&[(StorageKind::ActorKv, 40), (StorageKind::Sqlite, 4096)],
+				)
+				.await,
+				vec![-60, 0]
+			);
+
+			let res = usage(dc, &namespace, None).await;
+			assert_eq!(res.actor_kv_bytes, 90);
+			assert_eq!(res.sqlite_bytes, 4096);
+
+			let res = usage(dc, &namespace, Some(actor_a)).await;
+			assert_eq!(res.actor_kv_bytes, 40);
+			assert_eq!(res.sqlite_bytes, 4096);
+			assert_eq!(res.total_bytes, 4136);
+
+			// Recording zero removes the actor's contribution
+			assert_eq!(
+				record(dc, namespace_id, actor_b, &[(StorageKind::ActorKv, 0)]).await,
+				vec![-50]
+			);
+
+			let res = usage(dc, &namespace, Some(actor_b)).await;
+			assert_eq!(res.total_bytes, 0);
+
+			let res = usage(dc, &namespace, None).await;
+			assert_eq!(res.actor_kv_bytes, 40);
+			assert_eq!(res.total_bytes, 4136);
+		},
+	);
+}
+
+#[test]
+fn usage_of_unknown_namespace_is_not_found() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let res = common::api::public::namespaces_usage(
+				ctx.leader_dc().guard_port(),
+				common::api_types::namespaces::usage::UsagePath {
+					namespace: "non-existent-namespace".to_string(),
+				},
+				common::api_types::namespaces::usage::UsageQuery { actor_id: None },
+			)
+			.await;
+
+			assert!(res.is_err(), "usage of an unknown namespace should fail");
+		},
+	);
+}
+
+#[test]
+fn destroying_actor_clears_its_usage() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let (namespace, namespace_id, _envoy) =
+				common::setup_test_namespace_with_envoy(dc).await;
+
+			let res = common::api::public::actors_create(
+				dc.guard_port(),
+				common::api_types::actors::create::CreateQuery {
+					namespace: namespace.clone(),
+				},
+				common::api_types::actors::create::CreateRequest {
+					datacenter: None,
+					name: "test-actor".to_string(),
+					key: None,
+					input: None,
+					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
+					crash_policy: rivet_types::actors::CrashPolicy::Sleep,
+					fork_from: None,
+				},
+			)
+			.await
+			.expect("failed to create actor");
+			let actor_id = res.actor.actor_id;
+
+			// An actor that is never destroyed keeps its usage
+			let other_actor_id = Id::new_v1(dc.config.dc_label());
+			record(
+				dc,
+				namespace_id,
+				other_actor_id,
+				&[(StorageKind::ActorKv, 10)],
+			)
+			.await;
+			record(
+				dc,
+				namespace_id,
+				actor_id,
+				&[
+					(StorageKind::ActorKv, 100),
+					(StorageKind::Sqlite, 4096),
+					(StorageKind::WorkflowHistory, 2048),
+				],
+			)
+			.await;
+
+			let res = usage(dc, &namespace, None).await;
+			assert_eq!(res.total_bytes, 6254);
+
+			common::api::public::actors_delete(
+				dc.guard_port(),
+				common::api_types::actors::delete::DeletePath { actor_id },
+				common::api_types::actors::delete::DeleteQuery {
+					namespace: namespace.clone(),
+				},
+			)
+			.await
+			.expect("failed to delete actor");
+
+			common::wait_with_poll(
+				std::time::Duration::from_secs(10),
+				std::time::Duration::from_millis(100),
+				|| {
+					let namespace = namespace.clone();
+					async move {
+						(usage(dc, &namespace, Some(actor_id)).await.total_bytes == 0).then_some(())
+					}
+				},
+			)
+			.await
+			.expect("timed out waiting for destroyed actor's usage to clear");
+
+			let res = usage(dc, &namespace, None).await;
+			assert_eq!(res.actor_kv_bytes, 10);
+			assert_eq!(res.sqlite_bytes, 0);
+			assert_eq!(res.workflow_history_bytes, 0);
+			assert_eq!(res.total_bytes, 10);
+		},
+	);
+}
@@ -0,0 +1,58 @@
+use super::super::common;
+
+async fn claim(
+	dc: &common::TestDatacenter,
+	namespace_id: rivet_util::Id,
+	name: &str,
+	dc_label: Option<u16>,
+) -> Option<u16> {
+	dc.workflow_ctx
+		.op(pegboard::ops::actor::claim_first_client_region::Input {
+			namespace_id,
+			name: name.to_string(),
+			dc_label,
+		})
+		.await
+		.expect("failed to claim first client region")
+		.dc_label
+}
+
+#[test]
+fn second_client_follows_first_client_region() {
+	common::run(
+		common::TestOpts::new(2).with_timeout(30),
+		|ctx| async move {
+			let (_, namespace_id) = common::setup_test_namespace(ctx.leader_dc()).await;
+			let dc1 = ctx.get_dc(1);
+			let dc2 = ctx.get_dc(2);
+
+			// First client is nearest to dc 1 and enters through it
+			assert_eq!(claim(dc1, namespace_id, "chat", Some(1)).await, Some(1));
+
+			// Second client is nearest to dc 2 and enters through it, but still gets dc 1
+			assert_eq!(claim(dc2, namespace_id, "chat", Some(2)).await, Some(1));
+			assert_eq!(claim(dc1, namespace_id, "chat", Some(2)).await, Some(1));
+
+			// Other names are claimed independently
+			assert_eq!(claim(dc2, namespace_id, "lobby", Some(2)).await, Some(2));
+		},
+	);
+}
+
+#[test]
+fn unlocated_client_does_not_claim_region() {
+	common::run(
+		common::TestOpts::new(2).with_timeout(30),
+		|ctx| async move {
+			let (_, namespace_id) = common::setup_test_namespace(ctx.leader_dc()).await;
+			let dc1 = ctx.get_dc(1);
+			let dc2 = ctx.get_dc(2);
+
+			assert_eq!(claim(dc1, namespace_id, "chat", None).await, None);
+
+			// The first located client still decides the region
+			assert_eq!(claim(dc2, namespace_id, "chat", Some(2)).await, Some(2));
+			assert_eq!(claim(dc1, namespace_id, "chat", None).await, Some(2));
+		},
+	);
+}
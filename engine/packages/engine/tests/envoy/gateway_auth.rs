@@ -0,0 +1,287 @@
+use std::sync::{Arc, Mutex};
+
+use super::super::common;
+use rivet_types::namespaces::{ApiKey, AuthMethod, AuthPolicy, AuthRule};
+use rivet_util::Id;
+use universaldb::utils::IsolationLevel::Serializable;
+
+/// Token accepted by the api key rule of [`set_auth_policy`].
+const API_KEY: &str = "good-key";
+/// Hex SHA-256 of [`API_KEY`].
+const API_KEY_SHA256: &str = "b8ce88d57f4916859bcecb01b98b041299a759313987af89f9fc6608d7440537";
+
+/// Secret of the signed url rule in [`auth_policy_redacts_signed_url_secret`].
+const SIGNED_URL_SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+async fn put_auth_policy(
+	dc: &common::TestDatacenter,
+	namespace: &str,
+	policy: AuthPolicy,
+) -> reqwest::Response {
+	reqwest::Client::new()
+		.put(format!(
+			"http://127.0.0.1:{}/namespaces/{namespace}/auth-policy",
+			dc.guard_port()
+		))
+		.json(&common::api_types::namespaces::auth_policy::UpsertAuthPolicyRequest { policy })
+		.send()
+		.await
+		.expect("failed to send auth policy request")
+}
+
+async fn set_auth_policy(dc: &common::TestDatacenter, namespace: &str) {
+	let response = put_auth_policy(
+		dc,
+		namespace,
+		AuthPolicy {
+			rules: vec![AuthRule {
+				name: "api-key".to_string(),
+				actor_name: None,
+				method: AuthMethod::ApiKey {
+					keys: vec![ApiKey {
+						name: "test".to_string(),
+						sha256: API_KEY_SHA256.to_string(),
+					}],
+				},
+			}],
+		},
+	)
+	.await;
+	assert!(
+		response.status().is_success(),
+		"failed to set auth policy: {}",
+		response.text().await.unwrap_or_default()
+	);
+}
+
+fn signed_url_policy(rule_name: &str, secret: Option<&str>) -> AuthPolicy {
+	AuthPolicy {
+		rules: vec![AuthRule {
+			name: rule_name.to_string(),
+			actor_name: None,
+			method: AuthMethod::SignedUrl {
+				secret: secret.map(ToString::to_string),
+			},
+		}],
+	}
+}
+
+fn signed_url_secret(policy: &AuthPolicy) -> Option<&str> {
+	match &policy.rules[0].method {
+		AuthMethod::SignedUrl { secret } => secret.as_deref(),
+		_ => panic!("expected a signed url rule"),
+	}
+}
+
+/// Reads the auth policy guard checks requests against.
+async fn read_stored_auth_policy(dc: &common::TestDatacenter, namespace_id: Id) -> AuthPolicy {
+	dc.pools
+		.udb()
+		.expect("udb should be available")
+		.txn("test_gateway_auth_read_policy", |tx| async move {
+			let tx = tx.with_subspace(namespace::keys::subspace());
+			namespace::keys::policy::read::<AuthPolicy>(&tx, namespace_id, Serializable).await
+		})
+		.await
+		.expect("auth policy should be readable")
+}
+
+async fn list_actor_count(dc: &common::TestDatacenter, namespace: &str, name: &str) -> usize {
+	common::api::public::actors_list(
+		dc.guard_port(),
+		common::api_types::actors::list::ListQuery {
+			namespace: namespace.to_string(),
+			name: Some(name.to_string()),
+			key: None,
+			actor_ids: None,
+			actor_id: vec![],
+			include_destroyed: None,
+			limit: None,
+			cursor: None,
+		},
+	)
+	.await
+	.expect("failed to list actors")
+	.actors
+	.len()
+}
+
+#[test]
+fn gateway_rejected_query_does_not_create_actor() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(45),
+		|ctx| async move {
+			let (namespace, _, _envoy) =
+				common::setup_test_namespace_with_envoy(ctx.leader_dc()).await;
+			set_auth_policy(ctx.leader_dc(), &namespace).await;
+
+			let request = || {
+				reqwest::Client::new()
+					.get(format!(
+						"http://127.0.0.1:{}/gateway/test-actor/ping",
+						ctx.leader_dc().guard_port()
+					))
+					.query(&[
+						("rvt-namespace", namespace.as_str()),
+						("rvt-method", "getOrCreate"),
+						("rvt-runner", common::TEST_RUNNER_NAME),
+						("rvt-key", "auth-key"),
+					])
+			};
+
+			let response = request()
+				.send()
+				.await
+				.expect("failed to send query gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+			let response = request()
+				.bearer_auth("bad-key")
+				.send()
+				.await
+				.expect("failed to send query gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+			assert_eq!(
+				list_actor_count(ctx.leader_dc(), &namespace, "test-actor").await,
+				0,
+				"rejected requests should not create the actor"
+			);
+
+			let response = request()
+				.bearer_auth(API_KEY)
+				.send()
+				.await
+				.expect("failed to send query gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::OK);
+			assert_eq!(
+				list_actor_count(ctx.leader_dc(), &namespace, "test-actor").await,
+				1
+			);
+		},
+	);
+}
+
+#[test]
+fn gateway_rejected_request_does_not_wake_actor() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(45),
+		|ctx| async move {
+			let (namespace, _) = common::setup_test_namespace(ctx.leader_dc()).await;
+
+			let (sleep_tx, sleep_rx) = tokio::sync::oneshot::channel();
+			let sleep_tx = Arc::new(Mutex::new(Some(sleep_tx)));
+			let envoy = common::setup_envoy(ctx.leader_dc(), &namespace, |builder| {
+				builder.with_actor_behavior("sleep-actor", move |_| {
+					Box::new(common::test_envoy::SleepImmediatelyActor::new_with_notify(
+						sleep_tx.clone(),
+					))
+				})
+			})
+			.await;
+
+			let res = common::create_actor(
+				ctx.leader_dc().guard_port(),
+				&namespace,
+				"sleep-actor",
+				envoy.pool_name(),
+				rivet_types::actors::CrashPolicy::Sleep,
+			)
+			.await;
+			let actor_id = res.actor.actor_id.to_string();
+
+			sleep_rx
+				.await
+				.expect("actor should have sent sleep intent notification");
+			common::wait_with_poll(
+				std::time::Duration::from_secs(10),
+				std::time::Duration::from_millis(50),
+				|| async {
+					common::try_get_actor(ctx.leader_dc().guard_port(), &actor_id, &namespace)
+						.await
+						.ok()
+						.flatten()
+						.filter(|actor| actor.sleep_ts.is_some())
+				},
+			)
+			.await
+			.expect("actor should sleep");
+
+			set_auth_policy(ctx.leader_dc(), &namespace).await;
+
+			let response = reqwest::Client::new()
+				.get(format!(
+					"http://127.0.0.1:{}/gateway/{actor_id}/ping",
+					ctx.leader_dc().guard_port()
+				))
+				.send()
+				.await
+				.expect("failed to send gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+			// Give a wake that slipped through time to start the actor
+			tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+			let actor = common::try_get_actor(ctx.leader_dc().guard_port(), &actor_id, &namespace)
+				.await
+				.expect("failed to get actor")
+				.expect("actor should exist");
+			assert!(
+				actor.sleep_ts.is_some() && actor.connectable_ts.is_none(),
+				"rejected requests should not wake the actor"
+			);
+		},
+	);
+}
+
+#[test]
+fn auth_policy_redacts_signed_url_secret() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(30),
+		|ctx| async move {
+			let dc = ctx.leader_dc();
+			let (namespace, namespace_id) = common::setup_test_namespace(dc).await;
+
+			let response = put_auth_policy(
+				dc,
+				&namespace,
+				signed_url_policy("signed-url", Some(SIGNED_URL_SECRET)),
+			)
+			.await;
+			common::assert_success_response(&response);
+			let upserted: common::api_types::namespaces::auth_policy::UpsertAuthPolicyResponse =
+				response.json().await.expect("invalid upsert response");
+			assert_eq!(signed_url_secret(&upserted.policy), None);
+
+			let read: common::api_types::namespaces::auth_policy::GetAuthPolicyResponse =
+				reqwest::Client::new()
+					.get(format!(
+						"http://127.0.0.1:{}/namespaces/{namespace}/auth-policy",
+						dc.guard_port()
+					))
+					.send()
+					.await
+					.expect("failed to send auth policy request")
+					.json()
+					.await
+					.expect("invalid auth policy response");
+			assert_eq!(signed_url_secret(&read.policy), None);
+
+			// Writing back the redacted policy keeps the stored secret
+			let response = put_auth_policy(dc, &namespace, read.policy).await;
+			common::assert_success_response(&response);
+			assert_eq!(
+				signed_url_secret(&read_stored_auth_policy(dc, namespace_id).await),
+				Some(SIGNED_URL_SECRET)
+			);
+
+			// Rules without an existing secret must set one
+			let response = put_auth_policy(dc, &namespace, signed_url_policy("other", None)).await;
+			common::assert_error_response(response, "invalid_auth_policy").await;
+			assert_eq!(
+				signed_url_secret(&read_stored_auth_policy(dc, namespace_id).await),
+				Some(SIGNED_URL_SECRET)
+			);
+		},
+	);
+}
@@ -0,0 +1,172 @@
+use super::super::common;
+
+use axum::{Json, Router, extract::State, routing::get};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use ring::{
+	rand::SystemRandom,
+	signature::{Ed25519KeyPair, KeyPair},
+};
+use rivet_types::namespaces::{AuthMethod, AuthPolicy, AuthRule, Jwk, Jwks};
+use std::sync::{
+	Arc, Mutex,
+	atomic::{AtomicUsize, Ordering},
+};
+
+/// Local stand-in for an identity provider that serves its JWKS over http.
+struct MockIssuer {
+	jwks: Mutex<Jwks>,
+	fetches: AtomicUsize,
+}
+
+async fn jwks_handler(State(state): State<Arc<MockIssuer>>) -> Json<Jwks> {
+	state.fetches.fetch_add(1, Ordering::SeqCst);
+	Json(state.jwks.lock().unwrap().clone())
+}
+
+fn key_pair() -> Ed25519KeyPair {
+	let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+	Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+}
+
+fn jwk(kid: &str, key_pair: &Ed25519KeyPair) -> Jwk {
+	Jwk {
+		kty: "OKP".to_string(),
+		kid: Some(kid.to_string()),
+		alg: None,
+		crv: Some("Ed25519".to_string()),
+		n: None,
+		e: None,
+		x: Some(URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref())),
+		y: None,
+	}
+}
+
+fn sign_jwt(kid: &str, key_pair: &Ed25519KeyPair) -> String {
+	let header = URL_SAFE_NO_PAD
+		.encode(serde_json::to_vec(&serde_json::json!({ "alg": "EdDSA", "kid": kid })).unwrap());
+	let exp = rivet_util::timestamp::now() / 1000 + 60 * 60;
+	let claims = URL_SAFE_NO_PAD
+		.encode(serde_json::to_vec(&serde_json::json!({ "sub": "user-1", "exp": exp })).unwrap());
+	let message = format!("{header}.{claims}");
+	let signature = URL_SAFE_NO_PAD.encode(key_pair.sign(message.as_bytes()).as_ref());
+
+	format!("{message}.{signature}")
+}
+
+async fn set_jwks_url_policy(dc: &common::TestDatacenter, namespace: &str, jwks_url: String) {
+	let response = reqwest::Client::new()
+		.put(format!(
+			"http://127.0.0.1:{}/namespaces/{namespace}/auth-policy",
+			dc.guard_port()
+		))
+		.json(
+			&common::api_types::namespaces::auth_policy::UpsertAuthPolicyRequest {
+				policy: AuthPolicy {
+					rules: vec![AuthRule {
+						name: "jwt".to_string(),
+						actor_name: None,
+						method: AuthMethod::Jwt {
+							jwks_url: Some(jwks_url),
+							jwks: None,
+							issuer: None,
+							audience: None,
+						},
+					}],
+				},
+			},
+		)
+		.send()
+		.await
+		.expect("failed to send auth policy request");
+	assert!(
+		response.status().is_success(),
+		"failed to set auth policy: {}",
+		response.text().await.unwrap_or_default()
+	);
+}
+
+#[test]
+fn gateway_jwks_url_is_cached_and_refetched_for_unknown_kid() {
+	common::run(
+		common::TestOpts::new(1).with_timeout(45),
+		|ctx| async move {
+			let (namespace, _, _envoy) =
+				common::setup_test_namespace_with_envoy(ctx.leader_dc()).await;
+
+			let key_1 = key_pair();
+			let key_2 = key_pair();
+			let issuer = Arc::new(MockIssuer {
+				jwks: Mutex::new(Jwks {
+					keys: vec![jwk("key-1", &key_1)],
+				}),
+				fetches: AtomicUsize::new(0),
+			});
+			let app = Router::new()
+				.route("/jwks.json", get(jwks_handler))
+				.with_state(issuer.clone());
+
+			let issuer_port = portpicker::pick_unused_port().expect("failed to pick port");
+			let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{issuer_port}"))
+				.await
+				.expect("failed to bind mock jwks endpoint");
+			let server_handle = tokio::spawn(async move {
+				axum::serve(listener, app).await.expect("server error");
+			});
+
+			set_jwks_url_policy(
+				ctx.leader_dc(),
+				&namespace,
+				format!("http://127.0.0.1:{issuer_port}/jwks.json"),
+			)
+			.await;
+
+			let request = |token: String| {
+				reqwest::Client::new()
+					.get(format!(
+						"http://127.0.0.1:{}/gateway/test-actor/ping",
+						ctx.leader_dc().guard_port()
+					))
+					.query(&[
+						("rvt-namespace", namespace.as_str()),
+						("rvt-method", "getOrCreate"),
+						("rvt-runner", common::TEST_RUNNER_NAME),
+						("rvt-key", "jwks-key"),
+					])
+					.bearer_auth(token)
+					.send()
+			};
+
+			// First request fetches the JWKS
+			let response = request(sign_jwt("key-1", &key_1))
+				.await
+				.expect("failed to send gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::OK);
+			assert_eq!(issuer.fetches.load(Ordering::SeqCst), 1);
+
+			// Known kid is served from the cache
+			let response = request(sign_jwt("key-1", &key_1))
+				.await
+				.expect("failed to send gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::OK);
+			assert_eq!(issuer.fetches.load(Ordering::SeqCst), 1);
+
+			// A key rotated in by the issuer is picked up before the cache expires
+			issuer.jwks.lock().unwrap().keys.push(jwk("key-2", &key_2));
+
+			let response = request(sign_jwt("key-2", &key_2))
+				.await
+				.expect("failed to send gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::OK);
+			assert_eq!(issuer.fetches.load(Ordering::SeqCst), 2);
+
+			// Unknown kids do not refetch again within the refetch interval
+			let response = request(sign_jwt("key-3", &key_2))
+				.await
+				.expect("failed to send gateway request");
+			assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+			assert_eq!(issuer.fetches.load(Ordering::SeqCst), 2);
+
+			server_handle.abort();
+		},
+	);
+}
@@ -10,6 +10,11 @@ pub mod api_actors_delete;
 pub mod api_actors_get_or_create;
 pub mod api_actors_list;
 pub mod api_actors_list_names;
+pub mod api_actors_restore;
+pub mod api_namespaces_usage;
 pub mod auth;
+pub mod first_client_region;
+pub mod gateway_auth;
+pub mod gateway_auth_jwks;
 pub mod network_faults;
 pub mod sqlite_generation;
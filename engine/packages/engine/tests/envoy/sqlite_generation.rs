@@ -96,6 +96,7 @@ async fn seed_empty_db_page(
 			now_ms: rivet_util::timestamp::now(),
 			expected_generation: Some(u64::from(generation)),
 			expected_head_txid: None,
+			row_changes: None,
 		})
 		.await
 		.expect("seed commit request should complete");
@@ -390,6 +391,7 @@ fn inline_sqlite_commit_with_stale_generation_is_rejected_before_depot() {
 					now_ms: rivet_util::timestamp::now(),
 					expected_generation: Some(1),
 					expected_head_txid: None,
+					row_changes: None,
 				})
 				.await
 				.expect("sqlite commit request should receive a response");
@@ -523,6 +525,7 @@ fn inline_sqlite_stale_warm_cache_rmw_lands_under_matching_head_fence() {
 					now_ms: rivet_util::timestamp::now(),
 					expected_generation: Some(gen_u64),
 					expected_head_txid: Some(head_after_seed),
+					row_changes: None,
 				})
 				.await
 				.expect("envoy_b commit should complete");
@@ -587,6 +590,7 @@ fn inline_sqlite_stale_warm_cache_rmw_lands_under_matching_head_fence() {
 					now_ms: rivet_util::timestamp::now(),
 					expected_generation: Some(gen_u64),
 					expected_head_txid: Some(observed_head),
+					row_changes: None,
 				})
 				.await
 				.expect("envoy_a stale commit should complete");
@@ -667,6 +671,7 @@ fn inline_sqlite_rejects_mismatched_generation_before_depot() {
 					now_ms: rivet_util::timestamp::now(),
 					expected_generation: Some(stale_generation),
 					expected_head_txid: Some(head_txid),
+					row_changes: None,
 				})
 				.await
 				.expect("stale commit request should receive a response");
@@ -772,6 +777,7 @@ fn inline_sqlite_rejects_stale_generation_with_pending_start_command() {
 					now_ms: rivet_util::timestamp::now(),
 					expected_generation: Some(u64::from(generation)),
 					expected_head_txid: Some(head_txid),
+					row_changes: None,
 				})
 				.await
 				.expect("stale commit request should receive a response");
@@ -87,6 +87,7 @@ fn create_actor_with_input() {
 				input: Some(input_data.clone()),
 				runner_name_selector: runner.name().to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
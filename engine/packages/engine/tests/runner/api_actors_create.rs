@@ -19,6 +19,7 @@ fn create_actor_valid_namespace() {
 				input: None,
 				runner_name_selector: runner.name().to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -56,6 +57,7 @@ fn create_actor_with_key() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -90,6 +92,7 @@ fn create_actor_with_input() {
 				input: Some(input_data.clone()),
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -121,6 +124,7 @@ fn create_durable_actor() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Restart,
+				fork_from: None,
 			},
 		)
 		.await
@@ -160,6 +164,7 @@ fn create_actor_specific_datacenter() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -190,6 +195,7 @@ fn create_actor_non_existent_namespace() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await;
@@ -219,6 +225,7 @@ fn create_actor_invalid_datacenter() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await;
@@ -252,6 +259,7 @@ fn create_actor_remote_datacenter_verify() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -292,6 +300,7 @@ fn create_actor_input_large() {
 				input: Some(input_data),
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -324,6 +333,7 @@ fn create_actor_input_exceeds_max_size() {
 				input: Some(input_data),
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await;
@@ -354,6 +364,7 @@ fn create_actor_empty_key() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await;
@@ -383,6 +394,7 @@ fn create_actor_key_at_max_size() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -419,6 +431,7 @@ fn create_actor_key_exceeds_max_size() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await;
@@ -20,6 +20,7 @@ fn delete_existing_actor_with_namespace() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -67,6 +68,7 @@ fn delete_existing_actor_without_namespace() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -114,6 +116,7 @@ fn delete_actor_current_datacenter() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -160,6 +163,7 @@ fn delete_actor_remote_datacenter() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -234,6 +238,7 @@ fn delete_actor_wrong_namespace() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -284,6 +289,7 @@ fn delete_with_non_existent_namespace() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -333,6 +339,7 @@ fn delete_remote_actor_verify_propagation() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -387,6 +394,7 @@ fn delete_already_destroyed_actor() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -450,6 +458,7 @@ fn delete_actor_twice_rapidly() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -30,6 +30,7 @@ fn list_actors_by_namespace_and_name() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -95,6 +96,7 @@ fn list_with_pagination() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -272,6 +274,7 @@ fn list_actors_by_namespace_name_and_key() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -290,6 +293,7 @@ fn list_actors_by_namespace_name_and_key() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -338,6 +342,7 @@ fn list_with_include_destroyed_false() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -369,6 +374,7 @@ fn list_with_include_destroyed_false() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -418,6 +424,7 @@ fn list_with_include_destroyed_true() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -449,6 +456,7 @@ fn list_with_include_destroyed_true() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -573,6 +581,7 @@ fn list_actors_from_multiple_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -591,6 +600,7 @@ fn list_actors_from_multiple_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -761,6 +771,7 @@ fn verify_sorting_by_create_ts_descending() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -822,6 +833,7 @@ fn list_aggregates_results_from_all_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -840,6 +852,7 @@ fn list_aggregates_results_from_all_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -941,6 +954,7 @@ fn list_by_key_with_include_destroyed_true() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -972,6 +986,7 @@ fn list_by_key_with_include_destroyed_true() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -1084,6 +1099,7 @@ fn list_with_invalid_actor_id_format_in_comma_list() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -1149,6 +1165,7 @@ fn list_with_cursor_pagination() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1296,6 +1313,7 @@ fn list_cursor_filters_by_timestamp() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1382,6 +1400,7 @@ fn list_cursor_with_exact_timestamp_boundary() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1460,6 +1479,7 @@ fn list_cursor_empty_results_when_no_more_actors() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1573,6 +1593,7 @@ fn list_cursor_across_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -1592,6 +1613,7 @@ fn list_cursor_across_datacenters() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -25,6 +25,7 @@ fn list_all_actor_names_in_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -45,6 +46,7 @@ fn list_all_actor_names_in_namespace() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -101,6 +103,7 @@ fn list_names_with_pagination() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -229,6 +232,7 @@ fn list_names_fanout_to_all_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -246,6 +250,7 @@ fn list_names_fanout_to_all_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -300,6 +305,7 @@ fn list_names_deduplication_across_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -317,6 +323,7 @@ fn list_names_deduplication_across_datacenters() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -371,6 +378,7 @@ fn list_names_alphabetical_sorting() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -427,6 +435,7 @@ fn list_names_default_limit_100() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -481,6 +490,7 @@ fn list_names_with_metadata() {
 				input: None,
 				runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 				crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+				fork_from: None,
 			},
 		)
 		.await
@@ -570,6 +580,7 @@ fn list_names_pagination_no_duplicates_comprehensive() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
@@ -661,6 +672,7 @@ fn list_names_pagination_boundary_cases() {
 					input: None,
 					runner_name_selector: common::TEST_RUNNER_NAME.to_string(),
 					crash_policy: rivet_types::actors::CrashPolicy::Destroy,
+					fork_from: None,
 				},
 			)
 			.await
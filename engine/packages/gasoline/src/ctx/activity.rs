@@ -12,7 +12,7 @@ use crate::{
 		common,
 		message::{MessageCtx, SubscriptionHandle},
 	},
-	db::{DatabaseHandle, WorkflowData},
+	db::{DatabaseHandle, WorkflowData, WorkflowStorageSize},
 	error::{WorkflowError, WorkflowResult},
 	message::Message,
 	operation::{Operation, OperationInput},
@@ -103,6 +103,14 @@ impl ActivityCtx {
 			.await
 	}
 
+	/// Measures the bytes a workflow occupies in the database. Does not include sub workflows.
+	#[tracing::instrument(skip_all, fields(%workflow_id))]
+	pub async fn get_workflow_storage_size(&self, workflow_id: Id) -> Result<WorkflowStorageSize> {
+		common::get_workflow_storage_size(&self.db, workflow_id)
+			.in_current_span()
+			.await
+	}
+
 	/// Creates a signal builder.
 	pub fn signal<T: Signal + Serialize>(&self, body: T) -> builder::signal::SignalBuilder<T> {
 		builder::signal::SignalBuilder::new(
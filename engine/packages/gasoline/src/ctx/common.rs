@@ -10,7 +10,7 @@ pub const WORKFLOW_TIMEOUT: Duration = Duration::from_secs(60);
 
 use crate::{
 	ctx::OperationCtx,
-	db::{BumpSubSubject, DatabaseHandle, WorkflowData},
+	db::{BumpSubSubject, DatabaseHandle, WorkflowData, WorkflowStorageSize},
 	error::WorkflowError,
 	operation::{Operation, OperationInput},
 	utils::tags::AsTags,
@@ -82,6 +82,16 @@ pub async fn get_workflows(
 	db.get_workflows(workflow_ids).await.map_err(Into::into)
 }
 
+/// Measures the bytes a workflow occupies in the database.
+pub async fn get_workflow_storage_size(
+	db: &DatabaseHandle,
+	workflow_id: Id,
+) -> Result<WorkflowStorageSize> {
+	db.get_workflow_storage_size(workflow_id)
+		.await
+		.map_err(Into::into)
+}
+
 pub async fn op<I>(
 	db: &DatabaseHandle,
 	config: &rivet_config::Config,
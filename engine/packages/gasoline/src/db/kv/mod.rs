@@ -18,7 +18,9 @@ use tracing::Instrument;
 use universaldb::prelude::*;
 use universaldb::utils::end_of_key_range;
 
-use super::{BumpSubSubject, Database, PulledWorkflowData, SignalData, WorkflowData};
+use super::{
+	BumpSubSubject, Database, PulledWorkflowData, SignalData, WorkflowData, WorkflowStorageSize,
+};
 use crate::{
 	error::{WorkflowError, WorkflowResult},
 	history::{
@@ -1026,6 +1028,111 @@ impl Database for DatabaseKv {
 			.map_err(WorkflowError::Udb)
 	}
 
+	#[tracing::instrument(skip_all, fields(%workflow_id))]
+	async fn get_workflow_storage_size(
+		&self,
+		workflow_id: Id,
+	) -> WorkflowResult<WorkflowStorageSize> {
+		self.pools
+			.udb()
+			.map_err(WorkflowError::PoolsGeneric)?
+			.txn("gas_get_workflow_storage_size", |tx| async move {
+				tx.tag("get_workflow_storage_size")?;
+				tx.priority(Priority::Low)?;
+
+				let start = Instant::now();
+
+				let data_subspace =
+					self.subspace
+						.subspace(&keys::workflow::DataSubspaceKey::new_with_workflow_id(
+							workflow_id,
+						));
+				let (data_start, data_end) = data_subspace.range();
+
+				let estimate = tx
+					.get_estimated_range_size_bytes(&data_start, &data_end)
+					.await?;
+
+				// FDB recommends you do not trust size estimates below 3mb. (See
+				// https://apple.github.io/foundationdb/api-c.html#c.fdb_transaction_get_estimated_range_size_bytes)
+				let history_bytes = if estimate > rivet_util::size::mebibytes(3) as i64 {
+					estimate
+				} else {
+					let mut stream = tx.get_ranges_keyvalues(
+						universaldb::RangeOption {
+							mode: StreamingMode::WantAll,
+							..(&data_subspace).into()
+						},
+						Snapshot,
+					);
+
+					let mut size = 0;
+					while let Some(entry) = stream.try_next().await? {
+						size += entry.key().len() + entry.value().len();
+
+						if start.elapsed() > EARLY_TXN_TIMEOUT {
+							tracing::warn!(?workflow_id, "timed out scanning workflow data size");
+							break;
+						}
+					}
+
+					size as i64
+				};
+
+				// Pending signals are stored outside of the workflow's data subspace; count their
+				// index entries and bodies
+				let pending_signal_subspace =
+					self.subspace
+						.subspace(&keys::workflow::PendingSignalKey::entire_subspace(
+							workflow_id,
+						));
+				let mut stream = tx.get_ranges_keyvalues(
+					universaldb::RangeOption {
+						mode: StreamingMode::WantAll,
+						..(&pending_signal_subspace).into()
+					},
+					Snapshot,
+				);
+
+				let mut queue_bytes = 0;
+				while let Some(entry) = stream.try_next().await? {
+					if start.elapsed() > EARLY_TXN_TIMEOUT {
+						tracing::warn!(?workflow_id, "timed out scanning workflow queue size");
+						break;
+					}
+
+					let key = self
+						.subspace
+						.unpack::<keys::workflow::PendingSignalKey>(entry.key())?;
+					let body_subspace = self
+						.subspace
+						.subspace(&keys::signal::BodyKey::new(key.signal_id));
+
+					let body_bytes = tx
+						.get_ranges_keyvalues(
+							universaldb::RangeOption {
+								mode: StreamingMode::WantAll,
+								..(&body_subspace).into()
+							},
+							Snapshot,
+						)
+						.try_fold(0, |acc, chunk| async move { Ok(acc + chunk.value().len()) })
+						.await?;
+
+					queue_bytes += (entry.key().len() + body_bytes) as i64;
+				}
+
+				Ok(WorkflowStorageSize {
+					history_bytes,
+					queue_bytes,
+				})
+			})
+			.custom_instrument(tracing::info_span!("get_workflow_storage_size_tx"))
+			.await
+			.context("failed to get workflow storage size")
+			.map_err(WorkflowError::Udb)
+	}
+
 	/// Returns the first incomplete workflow with the given name and tags, first meaning the one with the
 	/// lowest id value (by internal representation) because its in a KV store. There is no way to get any other
 	/// workflow besides the first.
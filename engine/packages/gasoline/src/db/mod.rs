@@ -99,6 +99,12 @@ pub trait Database: Send {
 	/// Retrieves workflows with the given IDs.
 	async fn get_workflows(&self, workflow_ids: Vec<Id>) -> WorkflowResult<Vec<WorkflowData>>;
 
+	/// Measures the bytes a workflow occupies in the database. Does not include sub workflows.
+	async fn get_workflow_storage_size(
+		&self,
+		workflow_id: Id,
+	) -> WorkflowResult<WorkflowStorageSize>;
+
 	/// Retrieves the first incomplete workflow with the given name and tags.
 	async fn find_workflow(
 		&self,
@@ -336,6 +342,14 @@ impl WorkflowData {
 	}
 }
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WorkflowStorageSize {
+	/// Bytes of the workflow's history, state, input, output, and metadata.
+	pub history_bytes: i64,
+	/// Bytes of signals published to the workflow that it has not received yet.
+	pub queue_bytes: i64,
+}
+
 #[derive(Debug)]
 pub struct PulledWorkflowData {
 	pub workflow_id: Id,
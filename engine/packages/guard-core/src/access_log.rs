@@ -0,0 +1,127 @@
+use std::{
+	net::IpAddr,
+	pin::Pin,
+	sync::Arc,
+	task::{Context, Poll},
+	time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use hyper::{Method, Version, header::HeaderMap};
+use rivet_util::Id;
+
+use crate::response_body::ResponseBody;
+
+/// Called once for every request after its response body finished or was dropped.
+pub type AccessLogFn = Arc<dyn Fn(AccessLogEntry) + Send + Sync>;
+
+/// Details about a request that only the routing function knows.
+#[derive(Debug, Clone, Default)]
+pub struct AccessLogFields {
+	/// Which router handled the request, e.g. `gateway` or `api`.
+	pub route_kind: Option<&'static str>,
+	pub namespace_id: Option<Id>,
+	pub actor_id: Option<Id>,
+	/// Envoy or runner the request was forwarded to.
+	pub upstream: Option<String>,
+	/// Time spent waking the actor and waiting for it to become ready.
+	pub wake_duration: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessLogEntry {
+	/// Time the request was received in milliseconds.
+	pub ts: i64,
+	pub ray_id: Id,
+	pub req_id: Id,
+	pub client_ip: IpAddr,
+	pub method: Method,
+	pub version: Version,
+	pub host: String,
+	/// Includes path and query.
+	pub path: String,
+	pub headers: HeaderMap,
+	pub is_websocket: bool,
+	pub status: u16,
+	/// Size of the request body. Always 0 for WebSockets.
+	pub bytes_in: u64,
+	/// Size of the response body sent to the client. Always 0 for WebSockets.
+	pub bytes_out: u64,
+	pub route_duration: Option<Duration>,
+	/// Time until the upstream responded with headers, including retries.
+	pub upstream_duration: Option<Duration>,
+	/// Time until the response body finished.
+	pub duration: Duration,
+	pub fields: AccessLogFields,
+}
+
+/// Response body that counts the bytes sent to the client and hands the access log entry to the
+/// access log function when dropped.
+pub struct AccessLogBody {
+	body: ResponseBody,
+	entry: Option<AccessLogEntry>,
+	start_time: Instant,
+	access_log_fn: AccessLogFn,
+}
+
+impl AccessLogBody {
+	pub(crate) fn new(
+		body: ResponseBody,
+		entry: AccessLogEntry,
+		start_time: Instant,
+		access_log_fn: AccessLogFn,
+	) -> Self {
+		AccessLogBody {
+			body,
+			entry: Some(entry),
+			start_time,
+			access_log_fn,
+		}
+	}
+}
+
+impl std::fmt::Debug for AccessLogBody {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("AccessLogBody")
+			.field("body", &self.body)
+			.finish_non_exhaustive()
+	}
+}
+
+impl http_body::Body for AccessLogBody {
+	type Data = Bytes;
+	type Error = Box<dyn std::error::Error + Send + Sync>;
+
+	fn poll_frame(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+		let this = self.get_mut();
+		let res = Pin::new(&mut this.body).poll_frame(cx);
+
+		if let Poll::Ready(Some(Ok(frame))) = &res
+			&& let (Some(data), Some(entry)) = (frame.data_ref(), &mut this.entry)
+		{
+			entry.bytes_out += data.len() as u64;
+		}
+
+		res
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.body.is_end_stream()
+	}
+
+	fn size_hint(&self) -> http_body::SizeHint {
+		self.body.size_hint()
+	}
+}
+
+impl Drop for AccessLogBody {
+	fn drop(&mut self) {
+		if let Some(mut entry) = self.entry.take() {
+			entry.duration = self.start_time.elapsed();
+			(self.access_log_fn)(entry);
+		}
+	}
+}
@@ -2,9 +2,23 @@ use rustls::server::{ClientHello, ResolvesServerCert};
 use rustls::{ServerConfig, sign::CertifiedKey};
 use std::sync::Arc;
 
+/// ALPN protocol used by ACME servers to validate TLS-ALPN-01 challenges (RFC 8737).
+pub const ACME_TLS_ALPN_PROTOCOL: &[u8] = b"acme-tls/1";
+
+const HTTP2_ALPN_PROTOCOL: &[u8] = b"h2";
+const HTTP1_ALPN_PROTOCOL: &[u8] = b"http/1.1";
+
+/// The parts of a TLS client hello used to pick a certificate.
+#[derive(Debug, Clone, Copy)]
+pub struct CertRequest<'a> {
+	pub server_name: &'a str,
+	/// Set when the client is an ACME server validating a TLS-ALPN-01 challenge.
+	pub acme_tls_alpn: bool,
+}
+
 /// Type signature for a function that resolves a TLS certificate based on the server name
 pub type CertResolverFn = Arc<
-	dyn Fn(&str) -> Result<Arc<CertifiedKey>, Box<dyn std::error::Error + Send + Sync>>
+	dyn Fn(CertRequest<'_>) -> Result<Arc<CertifiedKey>, Box<dyn std::error::Error + Send + Sync>>
 		+ Send
 		+ Sync,
 >;
@@ -34,9 +48,15 @@ impl ResolvesServerCert for CertResolver {
 		if let Some(server_name) = client_hello.server_name() {
 			tracing::debug!("SNI server name requested: {}", server_name);
 
-			// Call the resolver function with the server name directly
+			let acme_tls_alpn = client_hello
+				.alpn()
+				.is_some_and(|mut protocols| protocols.any(|p| p == ACME_TLS_ALPN_PROTOCOL));
+
 			let resolver_fn = &self.resolver_fn;
-			match (resolver_fn)(server_name) {
+			match (resolver_fn)(CertRequest {
+				server_name,
+				acme_tls_alpn,
+			}) {
 				Ok(cert) => {
 					tracing::debug!("Resolved certificate for {}", server_name);
 					return Some(cert);
@@ -54,8 +74,38 @@ impl ResolvesServerCert for CertResolver {
 	}
 }
 
-pub fn create_tls_config(resolver_fn: CertResolverFn) -> ServerConfig {
-	ServerConfig::builder()
+/// Builds the TLS server config. Certificates are resolved on every handshake, so certificates
+/// swapped in by the resolver apply to new connections without affecting open ones.
+pub fn create_tls_config(
+	resolver_fn: CertResolverFn,
+	acme_tls_alpn: bool,
+	http2: bool,
+) -> ServerConfig {
+	let mut config = ServerConfig::builder()
 		.with_no_client_auth()
-		.with_cert_resolver(Arc::new(CertResolver::new(resolver_fn)))
+		.with_cert_resolver(Arc::new(CertResolver::new(resolver_fn)));
+
+	config.alpn_protocols = alpn_protocols(acme_tls_alpn, http2);
+
+	config
+}
+
+/// ALPN protocols in order of preference. HTTP protocols are listed before the ACME protocol so
+/// regular clients are not affected. No protocols are advertised if the client can only speak
+/// HTTP/1.1, which is the default without ALPN.
+fn alpn_protocols(acme_tls_alpn: bool, http2: bool) -> Vec<Vec<u8>> {
+	if !acme_tls_alpn && !http2 {
+		return Vec::new();
+	}
+
+	let mut protocols = Vec::new();
+	if http2 {
+		protocols.push(HTTP2_ALPN_PROTOCOL.to_vec());
+	}
+	protocols.push(HTTP1_ALPN_PROTOCOL.to_vec());
+	if acme_tls_alpn {
+		protocols.push(ACME_TLS_ALPN_PROTOCOL.to_vec());
+	}
+
+	protocols
 }
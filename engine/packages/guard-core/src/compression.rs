@@ -0,0 +1,348 @@
+use async_compression::{
+	Level,
+	tokio::write::{BrotliEncoder, GzipEncoder, ZstdEncoder},
+};
+use bytes::Bytes;
+use futures_util::FutureExt;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::{
+	Method, Response, StatusCode,
+	body::Frame,
+	header::{self, HeaderMap, HeaderValue},
+};
+use rivet_config::config::guard::{Compression, CompressionEncoding};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::{metrics, response_body::ResponseBody, websocket_deflate::DeflateConfig};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Compresses eligible responses with the best encoding accepted by the client.
+pub struct Compressor {
+	enabled: bool,
+	min_size: u64,
+	content_types: Vec<String>,
+	encodings: Vec<CompressionEncoding>,
+	websocket: bool,
+}
+
+impl Compressor {
+	pub fn new(config: &Compression) -> Self {
+		Compressor {
+			enabled: config.enabled(),
+			min_size: config.min_size(),
+			content_types: config
+				.content_types()
+				.into_iter()
+				.map(|content_type| content_type.to_ascii_lowercase())
+				.collect(),
+			encodings: config.encodings(),
+			websocket: config.websocket(),
+		}
+	}
+
+	/// permessage-deflate settings offered to WebSocket clients, if enabled.
+	pub fn websocket_deflate(&self) -> Option<DeflateConfig> {
+		(self.enabled && self.websocket).then(|| DeflateConfig {
+			min_size: self.min_size.try_into().unwrap_or(usize::MAX),
+		})
+	}
+
+	/// Returns the encoding `res` should be compressed with, if any.
+	pub fn encoding_for(
+		&self,
+		method: &Method,
+		req_headers: &HeaderMap,
+		res: &Response<ResponseBody>,
+	) -> Option<CompressionEncoding> {
+		if !self.enabled || method == Method::HEAD {
+			return None;
+		}
+
+		let status = res.status();
+		if status.is_informational()
+			|| status == StatusCode::NO_CONTENT
+			|| status == StatusCode::NOT_MODIFIED
+			|| status == StatusCode::PARTIAL_CONTENT
+		{
+			return None;
+		}
+
+		let headers = res.headers();
+
+		// Already encoded by the actor
+		if headers
+			.get(header::CONTENT_ENCODING)
+			.is_some_and(|encoding| encoding != "identity")
+		{
+			return None;
+		}
+
+		if header_contains(headers, header::CACHE_CONTROL, "no-transform") {
+			return None;
+		}
+
+		let content_type = headers
+			.get(header::CONTENT_TYPE)
+			.and_then(|x| x.to_str().ok())
+			.and_then(|x| x.split(';').next())
+			.map(|x| x.trim().to_ascii_lowercase())?;
+		// Compressing an event stream would buffer events until the encoder flushes
+		if content_type == "text/event-stream" || !self.is_allowed_content_type(&content_type) {
+			return None;
+		}
+
+		// Streams without a known length are compressed regardless of size, see `compress_body`
+		let len = headers
+			.get(header::CONTENT_LENGTH)
+			.and_then(|x| x.to_str().ok())
+			.and_then(|x| x.parse::<u64>().ok())
+			.or_else(|| http_body::Body::size_hint(res.body()).exact());
+		if len.is_some_and(|len| len < self.min_size) {
+			return None;
+		}
+
+		let accept_encoding = req_headers
+			.get_all(header::ACCEPT_ENCODING)
+			.iter()
+			.filter_map(|x| x.to_str().ok())
+			.collect::<Vec<_>>()
+			.join(",");
+
+		negotiate(&accept_encoding, &self.encodings)
+	}
+
+	/// Compresses `res` if the client accepts a configured encoding and the response is eligible.
+	pub fn compress(
+		&self,
+		method: &Method,
+		req_headers: &HeaderMap,
+		res: Response<ResponseBody>,
+	) -> Response<ResponseBody> {
+		let Some(encoding) = self.encoding_for(method, req_headers, &res) else {
+			return res;
+		};
+
+		let (mut parts, body) = res.into_parts();
+
+		parts.headers.remove(header::CONTENT_LENGTH);
+		parts.headers.remove(header::ACCEPT_RANGES);
+		parts.headers.insert(
+			header::CONTENT_ENCODING,
+			HeaderValue::from_static(encoding.as_str()),
+		);
+		if !header_contains(&parts.headers, header::VARY, "accept-encoding")
+			&& !header_contains(&parts.headers, header::VARY, "*")
+		{
+			parts
+				.headers
+				.append(header::VARY, HeaderValue::from_static("accept-encoding"));
+		}
+
+		// The compressed body is no longer byte-for-byte identical
+		if let Some(etag) = parts.headers.get(header::ETAG)
+			&& !etag.as_bytes().starts_with(b"W/")
+		{
+			let mut weak = b"W/".to_vec();
+			weak.extend_from_slice(etag.as_bytes());
+			if let Ok(weak) = HeaderValue::from_bytes(&weak) {
+				parts.headers.insert(header::ETAG, weak);
+			}
+		}
+
+		metrics::PROXY_RESPONSE_COMPRESSED_TOTAL
+			.with_label_values(&[encoding.as_str()])
+			.inc();
+
+		Response::from_parts(parts, compress_body(body, encoding))
+	}
+
+	fn is_allowed_content_type(&self, content_type: &str) -> bool {
+		self.content_types.iter().any(|allowed| {
+			if let Some(prefix) = allowed.strip_suffix("/*") {
+				content_type
+					.split_once('/')
+					.is_some_and(|(kind, _)| kind == prefix)
+			} else {
+				allowed == content_type
+			}
+		})
+	}
+}
+
+/// Picks the encoding with the highest quality value in `accept_encoding`. Ties go to the
+/// encoding listed first in `encodings`.
+pub fn negotiate(
+	accept_encoding: &str,
+	encodings: &[CompressionEncoding],
+) -> Option<CompressionEncoding> {
+	let mut wildcard_q = None;
+	let mut qs = Vec::new();
+
+	for entry in accept_encoding.split(',') {
+		let mut params = entry.split(';');
+		let Some(coding) = params.next().map(|x| x.trim().to_ascii_lowercase()) else {
+			continue;
+		};
+		if coding.is_empty() {
+			continue;
+		}
+
+		let q = params
+			.filter_map(|param| param.trim().strip_prefix("q="))
+			.find_map(|q| q.trim().parse::<f32>().ok())
+			.unwrap_or(1.0);
+
+		if coding == "*" {
+			wildcard_q = Some(q);
+		} else {
+			qs.push((coding, q));
+		}
+	}
+
+	let mut best: Option<(CompressionEncoding, f32)> = None;
+	for encoding in encodings {
+		let q = qs
+			.iter()
+			.find(|(coding, _)| coding == encoding.as_str())
+			.map(|(_, q)| *q)
+			.or(wildcard_q)
+			.unwrap_or(0.0);
+
+		if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) {
+			best = Some((*encoding, q));
+		}
+	}
+
+	best.map(|(encoding, _)| encoding)
+}
+
+/// Compresses `body` as it streams. Whatever was encoded so far is flushed whenever the upstream
+/// has no frame ready, so streamed chunks reach the client without waiting for the encoder to
+/// fill a block.
+fn compress_body(body: ResponseBody, encoding: CompressionEncoding) -> ResponseBody {
+	let state = CompressState {
+		body,
+		encoder: Encoder::new(encoding),
+		unflushed: false,
+	};
+	let stream = futures_util::stream::try_unfold(Some(state), next_compressed_frame);
+
+	ResponseBody::Compressed(StreamBody::new(stream).boxed_unsync())
+}
+
+struct CompressState {
+	body: ResponseBody,
+	encoder: Encoder,
+	/// Data was written to the encoder since the last flush.
+	unflushed: bool,
+}
+
+async fn next_compressed_frame(
+	state: Option<CompressState>,
+) -> Result<Option<(Frame<Bytes>, Option<CompressState>)>, BoxError> {
+	let Some(mut state) = state else {
+		return Ok(None);
+	};
+
+	loop {
+		let frame = match state.body.frame().now_or_never() {
+			Some(frame) => frame,
+			None => {
+				if state.unflushed {
+					state.encoder.flush().await?;
+					state.unflushed = false;
+
+					let chunk = state.encoder.take();
+					if !chunk.is_empty() {
+						return Ok(Some((Frame::data(chunk), Some(state))));
+					}
+				}
+
+				state.body.frame().await
+			}
+		};
+
+		let Some(frame) = frame else {
+			state.encoder.shutdown().await?;
+			return Ok(Some((Frame::data(state.encoder.take()), None)));
+		};
+
+		// Trailers are dropped, they cannot follow an encoded body
+		let Ok(data) = frame?.into_data() else {
+			continue;
+		};
+		state.encoder.write(&data).await?;
+		state.unflushed = true;
+
+		// Encoders emit blocks on their own once enough input is buffered
+		let chunk = state.encoder.take();
+		if !chunk.is_empty() {
+			return Ok(Some((Frame::data(chunk), Some(state))));
+		}
+	}
+}
+
+/// Encoders writing into an in-memory buffer, so writes and flushes never wait.
+enum Encoder {
+	Zstd(ZstdEncoder<Vec<u8>>),
+	Br(BrotliEncoder<Vec<u8>>),
+	Gzip(GzipEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+	fn new(encoding: CompressionEncoding) -> Self {
+		match encoding {
+			CompressionEncoding::Zstd => {
+				Encoder::Zstd(ZstdEncoder::with_quality(Vec::new(), Level::Default))
+			}
+			// The default brotli level is too slow to run on every response
+			CompressionEncoding::Br => {
+				Encoder::Br(BrotliEncoder::with_quality(Vec::new(), Level::Precise(4)))
+			}
+			CompressionEncoding::Gzip => {
+				Encoder::Gzip(GzipEncoder::with_quality(Vec::new(), Level::Default))
+			}
+		}
+	}
+
+	fn writer(&mut self) -> &mut (dyn AsyncWrite + Unpin + Send) {
+		match self {
+			Encoder::Zstd(encoder) => encoder,
+			Encoder::Br(encoder) => encoder,
+			Encoder::Gzip(encoder) => encoder,
+		}
+	}
+
+	async fn write(&mut self, data: &[u8]) -> std::io::Result<()> {
+		self.writer().write_all(data).await
+	}
+
+	async fn flush(&mut self) -> std::io::Result<()> {
+		self.writer().flush().await
+	}
+
+	async fn shutdown(&mut self) -> std::io::Result<()> {
+		self.writer().shutdown().await
+	}
+
+	/// Takes the output encoded so far.
+	fn take(&mut self) -> Bytes {
+		let buf = match self {
+			Encoder::Zstd(encoder) => encoder.get_mut(),
+			Encoder::Br(encoder) => encoder.get_mut(),
+			Encoder::Gzip(encoder) => encoder.get_mut(),
+		};
+
+		Bytes::from(std::mem::take(buf))
+	}
+}
+
+fn header_contains(headers: &HeaderMap, name: header::HeaderName, token: &str) -> bool {
+	headers
+		.get_all(name)
+		.iter()
+		.filter_map(|x| x.to_str().ok())
+		.flat_map(|x| x.split(','))
+		.any(|x| x.trim().eq_ignore_ascii_case(token))
+}
@@ -1,7 +1,7 @@
 use anyhow::{Result, bail};
 use async_trait::async_trait;
 use bytes::Bytes;
-use http_body_util::Full;
+use http_body_util::{Full, combinators::UnsyncBoxBody};
 use hyper::{Request, Response};
 use tokio_tungstenite::tungstenite::protocol::frame::CloseFrame;
 
@@ -9,6 +9,9 @@ use crate::WebSocketHandle;
 use crate::request_context::RequestContext;
 use crate::response_body::ResponseBody;
 
+/// Request body passed to `CustomServeTrait::handle_streaming_request` without being buffered.
+pub type StreamingBody = UnsyncBoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>;
+
 pub enum HibernationResult {
 	Continue,
 	Close,
@@ -24,6 +27,22 @@ pub trait CustomServeTrait: Send + Sync {
 		req_ctx: &mut RequestContext,
 	) -> Result<Response<ResponseBody>>;
 
+	/// Whether `handle_streaming_request` can serve requests that guard streams instead of buffering,
+	/// such as gRPC.
+	fn supports_streaming_requests(&self) -> bool {
+		false
+	}
+
+	/// Handle an HTTP request with a streaming body. The body has not been read, so the request is
+	/// not retried.
+	async fn handle_streaming_request(
+		&self,
+		_req: Request<StreamingBody>,
+		_req_ctx: &mut RequestContext,
+	) -> Result<Response<ResponseBody>> {
+		bail!("service does not support streaming requests");
+	}
+
 	/// Handle a WebSocket connection after upgrade. Supports connection retries.
 	async fn handle_websocket(
 		&self,
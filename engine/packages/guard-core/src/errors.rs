@@ -36,6 +36,29 @@ pub struct RateLimit {
 	pub ip: String,
 }
 
+#[derive(RivetError, Serialize, Deserialize)]
+#[error(
+	"guard",
+	"rate_limit_exceeded",
+	"Rate limit exceeded. Try again later.",
+	"Rate limit '{rule}' exceeded. Retry after {retry_after_secs} seconds."
+)]
+pub struct RateLimitExceeded {
+	pub rule: String,
+	pub retry_after_secs: u64,
+}
+
+#[derive(RivetError, Serialize, Deserialize)]
+#[error(
+	"guard",
+	"unauthorized",
+	"Request was rejected by the namespace auth policy.",
+	"Request was rejected by the namespace auth policy: {reason}."
+)]
+pub struct Unauthorized {
+	pub reason: String,
+}
+
 #[derive(RivetError, Serialize, Deserialize)]
 #[error(
 	"guard",
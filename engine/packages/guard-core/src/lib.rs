@@ -1,7 +1,10 @@
+pub mod access_log;
 pub mod cert_resolver;
+pub mod compression;
 pub mod custom_serve;
 pub mod errors;
 pub mod metrics;
+pub mod mirror;
 pub mod proxy_service;
 pub mod request_context;
 mod response_body;
@@ -10,9 +13,11 @@ mod server;
 mod task_group;
 pub mod types;
 pub mod utils;
+pub mod websocket_deflate;
 pub mod websocket_handle;
 
-pub use cert_resolver::CertResolverFn;
+pub use access_log::{AccessLogEntry, AccessLogFn};
+pub use cert_resolver::{CertRequest, CertResolverFn};
 pub use custom_serve::CustomServeTrait;
 pub use proxy_service::{ProxyService, ProxyState};
 pub use response_body::ResponseBody;
@@ -75,6 +75,35 @@ lazy_static! {
 		*REGISTRY
 	).unwrap();
 
+	// MARK: Compression
+	pub static ref PROXY_RESPONSE_COMPRESSED_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_proxy_response_compressed_total",
+		"Total number of responses compressed by guard.",
+		&["encoding"],
+		*REGISTRY
+	).unwrap();
+
+	// MARK: Mirror
+	pub static ref MIRROR_RESPONSE_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_mirror_response_total",
+		"Total number of mirrored requests by the status of the primary and the shadow response.",
+		&["mirror", "variant", "status"],
+		*REGISTRY
+	).unwrap();
+	pub static ref MIRROR_SHADOW_DURATION: HistogramVec = register_histogram_vec_with_registry!(
+		"guard_mirror_shadow_duration",
+		"Duration of requests to the shadow target in seconds.",
+		&["mirror"],
+		BUCKETS.to_vec(),
+		*REGISTRY
+	).unwrap();
+	pub static ref MIRROR_DROPPED_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_mirror_dropped_total",
+		"Total number of sampled requests not copied because too many copies were in flight.",
+		&["mirror"],
+		*REGISTRY
+	).unwrap();
+
 	// MARK: WebSockets
 	pub static ref WEBSOCKET_SEND_DURATION: HistogramVec = register_histogram_vec_with_registry!(
 		"guard_websocket_send_duration",
@@ -0,0 +1,170 @@
+use std::{sync::Arc, time::Instant};
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::{
+	HeaderMap, Method, Request,
+	header::{self, HeaderName, HeaderValue},
+};
+use rivet_config::config::guard;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
+
+use crate::{
+	metrics,
+	proxy_service::{HttpClient, http_client},
+};
+
+/// Set on copies sent to the shadow target, with the mirror's name as the value.
+pub const X_RIVET_MIRROR: HeaderName = HeaderName::from_static("x-rivet-mirror");
+
+/// Copies HTTP requests to a shadow target and discards its responses.
+///
+/// Both the primary and the shadow response status of every copied request are recorded in
+/// `guard_mirror_response_total` under the `primary` and `shadow` variants, so the error rates of
+/// both versions can be compared on the same traffic.
+pub struct Mirror {
+	name: String,
+	url: Url,
+	sample_rate: f64,
+	route_kinds: Option<Vec<String>>,
+	methods: Vec<Method>,
+	timeout: std::time::Duration,
+	in_flight: Arc<Semaphore>,
+	client: HttpClient,
+}
+
+impl Mirror {
+	pub fn new(config: &guard::Mirror) -> Self {
+		Mirror {
+			name: config.name().to_string(),
+			url: config.url.clone(),
+			sample_rate: config.sample_rate(),
+			route_kinds: config.route_kinds.clone(),
+			methods: config
+				.methods()
+				.iter()
+				.filter_map(|method| {
+					Method::from_bytes(method.to_ascii_uppercase().as_bytes()).ok()
+				})
+				.collect(),
+			timeout: config.timeout(),
+			in_flight: Arc::new(Semaphore::new(config.max_in_flight())),
+			// Separate connection pool so a slow shadow target does not affect proxied requests
+			client: http_client(),
+		}
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Whether a request with `method` handled by the router `route_kind` is copied. Samples
+	/// `sample_rate`.
+	pub fn should_mirror(&self, method: &Method, route_kind: Option<&str>) -> bool {
+		if !self.methods.contains(method) {
+			return false;
+		}
+		if let Some(route_kinds) = &self.route_kinds {
+			let Some(route_kind) = route_kind else {
+				return false;
+			};
+			if !route_kinds.iter().any(|x| x == route_kind) {
+				return false;
+			}
+		}
+
+		self.sample_rate >= 1.0 || rand::random::<f64>() < self.sample_rate
+	}
+
+	/// Reserves a slot for a copy. Returns `None` and records the drop if `max_in_flight` copies
+	/// are already being sent. The slot is freed when the permit is dropped.
+	pub fn try_reserve(&self) -> Option<OwnedSemaphorePermit> {
+		let permit = self.in_flight.clone().try_acquire_owned().ok();
+		if permit.is_none() {
+			metrics::MIRROR_DROPPED_TOTAL
+				.with_label_values(&[self.name.as_str()])
+				.inc();
+		}
+
+		permit
+	}
+
+	/// Builds the copy of a request. `path` includes the query.
+	pub fn build_request(
+		&self,
+		method: &Method,
+		path: &str,
+		headers: &HeaderMap,
+		body: Bytes,
+	) -> Result<Request<Full<Bytes>>> {
+		let uri = format!(
+			"{}{}",
+			self.url.as_str().trim_end_matches('/'),
+			if path.starts_with('/') {
+				path.to_string()
+			} else {
+				format!("/{path}")
+			}
+		);
+
+		let mut builder = Request::builder().method(method.clone()).uri(uri);
+		let req_headers = builder
+			.headers_mut()
+			.context("request builder unexpectedly in error state")?;
+		for (key, value) in headers {
+			// Host is set from the shadow URL and the body is sent with a known length
+			if key != header::HOST
+				&& key != header::CONTENT_LENGTH
+				&& key != header::TRANSFER_ENCODING
+			{
+				req_headers.append(key.clone(), value.clone());
+			}
+		}
+		req_headers.insert(X_RIVET_MIRROR, HeaderValue::from_str(&self.name)?);
+
+		builder
+			.body(Full::new(body))
+			.context("failed to build mirrored request")
+	}
+
+	/// Sends a copy to the shadow target and records its response. The response body is read to
+	/// the end so the duration covers the whole response, then discarded.
+	pub async fn send(&self, req: Request<Full<Bytes>>) {
+		let start = Instant::now();
+		let res = tokio::time::timeout(self.timeout, async {
+			let res = self.client.request(req).await?;
+			let status = res.status();
+			res.into_body().collect().await?;
+			anyhow::Ok(status)
+		})
+		.await;
+
+		let status = match res {
+			Ok(Ok(status)) => status.as_u16().to_string(),
+			Ok(Err(err)) => {
+				tracing::debug!(?err, mirror=%self.name, "mirrored request failed");
+				"error".to_string()
+			}
+			Err(_) => {
+				tracing::debug!(mirror=%self.name, "mirrored request timed out");
+				"timeout".to_string()
+			}
+		};
+
+		metrics::MIRROR_SHADOW_DURATION
+			.with_label_values(&[self.name.as_str()])
+			.observe(start.elapsed().as_secs_f64());
+		metrics::MIRROR_RESPONSE_TOTAL
+			.with_label_values(&[self.name.as_str(), "shadow", status.as_str()])
+			.inc();
+	}
+
+	/// Records the status the client received for a copied request.
+	pub fn record_primary(&self, status: u16) {
+		metrics::MIRROR_RESPONSE_TOTAL
+			.with_label_values(&[self.name.as_str(), "primary", &status.to_string()])
+			.inc();
+	}
+}
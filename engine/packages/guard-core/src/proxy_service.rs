@@ -30,12 +30,16 @@ use tracing::Instrument;
 use url::Url;
 
 use crate::RouteTarget;
+use crate::access_log::{AccessLogBody, AccessLogEntry, AccessLogFn};
+use crate::compression::Compressor;
+use crate::mirror::Mirror;
 use crate::request_context::RequestContext;
 use crate::response_body::ResponseBody;
 use crate::route::{CacheKeyFn, ResolveRouteOutput, RouteCache, RoutingFn, RoutingOutput};
 use crate::utils::InFlightCounter;
 use crate::{
-	WebSocketHandle, custom_serve::HibernationResult, errors, metrics, task_group::TaskGroup, utils,
+	WebSocketHandle, custom_serve::HibernationResult, errors, metrics, task_group::TaskGroup,
+	utils, websocket_deflate,
 };
 
 pub const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
@@ -50,17 +54,44 @@ fn websocket_config(guard_config: &rivet_config::config::guard::Guard) -> WebSoc
 		.max_frame_size(Some(guard_config.websocket_max_frame_size()))
 }
 
+// NOTE: Using the hyper legacy client is the only option currently.
+// This is what reqwest uses under the hood. Eventually we'll migrate to h3 once it's ready.
+pub(crate) type HttpClient = Client<
+	hyper_rustls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>,
+	Full<Bytes>,
+>;
+
+pub(crate) fn http_client() -> HttpClient {
+	let https_connector_builder =
+		match hyper_rustls::HttpsConnectorBuilder::new().with_native_roots() {
+			Ok(builder) => builder,
+			Err(err) => {
+				tracing::warn!(
+					?err,
+					"failed to load native TLS roots; falling back to webpki roots"
+				);
+				hyper_rustls::HttpsConnectorBuilder::new().with_webpki_roots()
+			}
+		};
+	let https_connector = https_connector_builder
+		.https_or_http()
+		.enable_http1()
+		.enable_http2()
+		.build();
+	Client::builder(TokioExecutor::new())
+		.pool_idle_timeout(Duration::from_secs(30))
+		.build(https_connector)
+}
+
 // State shared across all request handlers
 pub struct ProxyState {
 	config: rivet_config::Config,
 	routing_fn: RoutingFn,
 	cache_key_fn: CacheKeyFn,
-	// NOTE: Using the hyper legacy client is the only option currently.
-	// This is what reqwest uses under the hood. Eventually we'll migrate to h3 once it's ready.
-	client: Client<
-		hyper_rustls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>,
-		Full<Bytes>,
-	>,
+	access_log_fn: Option<AccessLogFn>,
+	compressor: Compressor,
+	mirror: Option<Arc<Mirror>>,
+	client: HttpClient,
 	route_cache: RouteCache,
 	// We use moka::Cache instead of scc::HashMap because it automatically handles TTL and capacity
 	rate_limiters: Cache<std::net::IpAddr, Arc<Mutex<rivet_util::throttle::RateLimiter>>>,
@@ -75,32 +106,24 @@ impl ProxyState {
 		config: rivet_config::Config,
 		routing_fn: RoutingFn,
 		cache_key_fn: CacheKeyFn,
+		access_log_fn: Option<AccessLogFn>,
 	) -> Self {
-		let https_connector_builder =
-			match hyper_rustls::HttpsConnectorBuilder::new().with_native_roots() {
-				Ok(builder) => builder,
-				Err(err) => {
-					tracing::warn!(
-						?err,
-						"failed to load native TLS roots; falling back to webpki roots"
-					);
-					hyper_rustls::HttpsConnectorBuilder::new().with_webpki_roots()
-				}
-			};
-		let https_connector = https_connector_builder
-			.https_or_http()
-			.enable_http1()
-			.enable_http2()
-			.build();
-		let client = Client::builder(TokioExecutor::new())
-			.pool_idle_timeout(Duration::from_secs(30))
-			.build(https_connector);
+		let client = http_client();
 		let route_cache_ttl = config.guard().route_cache_ttl();
+		let compressor = Compressor::new(&config.guard().compression());
+		let mirror = config
+			.guard()
+			.mirror
+			.as_ref()
+			.map(|mirror| Arc::new(Mirror::new(mirror)));
 
 		Self {
 			config,
 			routing_fn,
 			cache_key_fn,
+			access_log_fn,
+			compressor,
+			mirror,
 			client,
 			route_cache: RouteCache::new(route_cache_ttl),
 			rate_limiters: Cache::builder()
@@ -116,6 +139,42 @@ impl ProxyState {
 		}
 	}
 
+	/// Sends a copy of an HTTP request to the shadow target if the request is mirrored.
+	fn mirror_request(&self, req_ctx: &mut RequestContext, body: &Bytes) {
+		let Some(mirror) = &self.mirror else {
+			return;
+		};
+		if !mirror.should_mirror(&req_ctx.method, req_ctx.access_log.route_kind) {
+			return;
+		}
+		let Some(permit) = mirror.try_reserve() else {
+			return;
+		};
+
+		let req = match mirror.build_request(
+			&req_ctx.method,
+			&req_ctx.path,
+			&req_ctx.headers,
+			body.clone(),
+		) {
+			Ok(req) => req,
+			Err(err) => {
+				tracing::debug!(?err, "failed to build mirrored request");
+				return;
+			}
+		};
+		req_ctx.mirrored = true;
+
+		let mirror = mirror.clone();
+		self.tasks.spawn(
+			async move {
+				mirror.send(req).await;
+				drop(permit);
+			}
+			.instrument(tracing::info_span!("mirror_request_task")),
+		);
+	}
+
 	#[tracing::instrument(skip_all)]
 	async fn resolve_route(
 		&self,
@@ -365,6 +424,8 @@ impl ProxyService {
 	#[tracing::instrument(name = "guard_request", skip_all, fields(ray_id, req_id, uri=%req.uri()))]
 	pub async fn process(&self, mut req: Request<BodyIncoming>) -> Result<Response<ResponseBody>> {
 		let start_time = Instant::now();
+		let start_ts = rivet_util::timestamp::now();
+		let version = req.version();
 
 		let request_ids = RequestIds::new(self.state.config.dc_label());
 		req.extensions_mut().insert(request_ids);
@@ -397,10 +458,12 @@ impl ProxyService {
 			.get(X_RIVET_RAY_ID)
 			.and_then(|h| h.to_str().ok())
 			.and_then(|id| Id::parse(id).ok());
+		// HTTP/2 requests carry the host in the `:authority` pseudo-header instead
 		let host = req
 			.headers()
 			.get(hyper::header::HOST)
 			.and_then(|h| h.to_str().ok())
+			.or_else(|| req.uri().authority().map(|authority| authority.as_str()))
 			.unwrap_or("unknown")
 			.to_string();
 		let uri_string = req.uri().to_string();
@@ -492,9 +555,10 @@ impl ProxyService {
 				// HTTP errors in a meaningful way resulting in unhelpful errors for the user
 				if is_websocket {
 					tracing::debug!("Upgrading client connection to WebSocket for error proxy");
-					match hyper_tungstenite::upgrade(
+					match websocket_deflate::upgrade(
 						mock_req,
-						Some(websocket_config(self.state.config.guard())),
+						websocket_config(self.state.config.guard()),
+						self.state.compressor.websocket_deflate(),
 					) {
 						Ok((client_response, client_ws)) => {
 							tracing::debug!("Client WebSocket upgrade for error proxy successful");
@@ -615,6 +679,10 @@ impl ProxyService {
 			res.headers_mut().insert(X_RIVET_RAY_ID, ray_id_value);
 		}
 
+		for (name, value) in &req_ctx.response_headers {
+			res.headers_mut().insert(name, value.clone());
+		}
+
 		// Add cors headers to response
 		if let Some(cors) = &req_ctx.cors {
 			let headers = res.headers_mut();
@@ -663,10 +731,25 @@ impl ProxyService {
 			}
 		}
 
+		// Compress the response if the client accepts it. Upgraded websockets negotiate
+		// permessage-deflate during the upgrade instead.
+		if !is_websocket {
+			res = self
+				.state
+				.compressor
+				.compress(&req_ctx.method, &req_ctx.headers, res);
+		}
+
 		// Set span status code
 		let status = res.status().as_u16();
 		current_span.set_attribute("http.response.status_code", status as i64);
 
+		if req_ctx.mirrored
+			&& let Some(mirror) = &self.state.mirror
+		{
+			mirror.record_primary(status);
+		}
+
 		let content_length = res
 			.headers()
 			.get(hyper::header::CONTENT_LENGTH)
@@ -688,6 +771,33 @@ impl ProxyService {
 			"Request completed"
 		);
 
+		// Record the request once the response body finishes
+		if let Some(access_log_fn) = &self.state.access_log_fn {
+			let entry = AccessLogEntry {
+				ts: start_ts,
+				ray_id: req_ctx.ray_id,
+				req_id: req_ctx.req_id,
+				client_ip: req_ctx.client_ip,
+				method: req_ctx.method.clone(),
+				version,
+				host: req_ctx.host.clone(),
+				path: req_ctx.path.clone(),
+				headers: req_ctx.headers.clone(),
+				is_websocket,
+				status,
+				bytes_in: req_ctx.bytes_in,
+				bytes_out: 0,
+				route_duration: req_ctx.route_duration,
+				upstream_duration: req_ctx.upstream_duration,
+				duration: Duration::ZERO,
+				fields: req_ctx.access_log.clone(),
+			};
+
+			let (parts, body) = res.into_parts();
+			let body = AccessLogBody::new(body, entry, start_time, access_log_fn.clone());
+			res = Response::from_parts(parts, ResponseBody::AccessLog(Box::new(body)));
+		}
+
 		Ok(res)
 	}
 
@@ -700,8 +810,9 @@ impl ProxyService {
 		// Resolve target
 		let target_res = self.state.resolve_route(req_ctx, false).await;
 
-		let duration_secs = req_ctx.start_time.elapsed().as_secs_f64();
-		metrics::RESOLVE_ROUTE_DURATION.observe(duration_secs);
+		let route_duration = req_ctx.start_time.elapsed();
+		req_ctx.route_duration = Some(route_duration);
+		metrics::RESOLVE_ROUTE_DURATION.observe(route_duration.as_secs_f64());
 
 		let target = target_res?;
 
@@ -729,11 +840,13 @@ impl ProxyService {
 		metrics::PROXY_REQUEST_PENDING.inc();
 		metrics::PROXY_REQUEST_TOTAL.inc();
 
+		let upstream_start = Instant::now();
 		let res = if hyper_tungstenite::is_upgrade_request(&req) {
 			self.handle_websocket_upgrade(req, req_ctx, target).await
 		} else {
 			self.handle_http_request(req, req_ctx, target).await
 		};
+		req_ctx.upstream_duration = Some(upstream_start.elapsed());
 
 		let status = match &res {
 			Ok(resp) => resp.status().as_u16().to_string(),
@@ -789,6 +902,8 @@ impl ProxyService {
 							.build()
 						})?
 						.to_bytes();
+				req_ctx.bytes_in = req_body.len() as u64;
+				self.state.mirror_request(req_ctx, &req_body);
 
 				// Use a value-returning loop to handle both errors and successful responses
 				let mut attempts = 0;
@@ -937,9 +1052,38 @@ impl ProxyService {
 				}
 				.build());
 			}
+			ResolveRouteOutput::CustomServe(handler)
+				if handler.supports_streaming_requests()
+					&& utils::is_streaming_request(&req_ctx.headers) =>
+			{
+				let (mut req_parts, body) = req.into_parts();
+				// Use the headers as modified by the routing function
+				req_parts.headers = req_ctx.headers.clone();
+
+				// The body is passed through as it arrives. It is never buffered, so it is not
+				// subject to the max request body size, and the request is neither mirrored nor
+				// retried.
+				let req_streaming = hyper::Request::from_parts(
+					req_parts,
+					body.map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync>)
+						.boxed_unsync(),
+				);
+
+				let res = handler
+					.handle_streaming_request(req_streaming, req_ctx)
+					.await;
+
+				// Release in-flight counter and request ID before returning
+				self.state
+					.release_in_flight(req_ctx.client_ip, req_ctx.in_flight_request_id)
+					.await;
+				return res;
+			}
 			ResolveRouteOutput::CustomServe(mut handler) => {
 				// Collect request body
-				let (req_parts, body) = req.into_parts();
+				let (mut req_parts, body) = req.into_parts();
+				// Use the headers as modified by the routing function
+				req_parts.headers = req_ctx.headers.clone();
 				let req_body =
 					Limited::new(body, self.state.config.guard().http_max_request_body_size())
 						.collect()
@@ -951,6 +1095,8 @@ impl ProxyService {
 							.build()
 						})?
 						.to_bytes();
+				req_ctx.bytes_in = req_body.len() as u64;
+				self.state.mirror_request(req_ctx, &req_body);
 				let req_collected =
 					hyper::Request::from_parts(req_parts, Full::<Bytes>::new(req_body));
 
@@ -1042,11 +1188,12 @@ impl ProxyService {
 			}
 		}
 
-		// Handle WebSocket upgrade properly with hyper_tungstenite
+		// Handle WebSocket upgrade, negotiating permessage-deflate if enabled
 		tracing::debug!(path=%req_ctx.path, "Upgrading client connection to WebSocket");
-		let (client_response, client_ws) = match hyper_tungstenite::upgrade(
+		let (client_response, client_ws) = match websocket_deflate::upgrade(
 			req,
-			Some(websocket_config(self.state.config.guard())),
+			websocket_config(self.state.config.guard()),
+			self.state.compressor.websocket_deflate(),
 		) {
 			Ok(x) => {
 				tracing::debug!("Client WebSocket upgrade successful");
@@ -1193,6 +1340,12 @@ impl ProxyService {
 								return;
 							}
 
+							// Extensions were negotiated with the client by guard, the upstream
+							// connection is plain
+							ws_request
+								.headers_mut()
+								.remove(hyper::header::SEC_WEBSOCKET_EXTENSIONS);
+
 							match tokio::time::timeout(
 								Duration::from_secs(5), // 5 second timeout per connection attempt
 								tokio_tungstenite::connect_async_with_config(
@@ -1914,8 +2067,14 @@ impl ProxyServiceFactory {
 		config: rivet_config::Config,
 		routing_fn: RoutingFn,
 		cache_key_fn: CacheKeyFn,
+		access_log_fn: Option<AccessLogFn>,
 	) -> Self {
-		let state = Arc::new(ProxyState::new(config, routing_fn, cache_key_fn));
+		let state = Arc::new(ProxyState::new(
+			config,
+			routing_fn,
+			cache_key_fn,
+			access_log_fn,
+		));
 		Self { state }
 	}
 
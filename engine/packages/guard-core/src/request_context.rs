@@ -7,6 +7,8 @@ use std::{
 	time::{Duration, Instant},
 };
 
+use crate::access_log::AccessLogFields;
+
 #[derive(Clone)]
 pub struct RequestContext {
 	pub(crate) remote_addr: SocketAddr,
@@ -31,6 +33,14 @@ pub struct RequestContext {
 
 	pub(crate) in_flight_request_id: Option<protocol::RequestId>,
 	pub(crate) cors: Option<CorsConfig>,
+	pub(crate) response_headers: HeaderMap,
+
+	pub(crate) access_log: AccessLogFields,
+	pub(crate) bytes_in: u64,
+	pub(crate) route_duration: Option<Duration>,
+	pub(crate) upstream_duration: Option<Duration>,
+	/// Whether a copy of the request was sent to the mirror's shadow target.
+	pub(crate) mirrored: bool,
 }
 
 impl RequestContext {
@@ -78,6 +88,13 @@ impl RequestContext {
 
 			in_flight_request_id: None,
 			cors: None,
+			response_headers: HeaderMap::new(),
+
+			access_log: AccessLogFields::default(),
+			bytes_in: 0,
+			route_duration: None,
+			upstream_duration: None,
+			mirrored: false,
 		}
 	}
 
@@ -109,10 +126,20 @@ impl RequestContext {
 		&self.headers
 	}
 
+	/// Headers forwarded to custom serve handlers. Routing functions can modify them before the
+	/// request is served.
+	pub fn headers_mut(&mut self) -> &mut HeaderMap {
+		&mut self.headers
+	}
+
 	pub fn is_websocket(&self) -> bool {
 		self.is_websocket
 	}
 
+	pub fn client_ip(&self) -> IpAddr {
+		self.client_ip
+	}
+
 	pub fn elapsed(&self) -> Duration {
 		self.start_time.elapsed()
 	}
@@ -125,6 +152,16 @@ impl RequestContext {
 	pub fn set_cors(&mut self, cors_config: CorsConfig) {
 		self.cors = Some(cors_config);
 	}
+
+	/// Headers added to the response sent to the client, including error responses.
+	pub fn response_headers_mut(&mut self) -> &mut HeaderMap {
+		&mut self.response_headers
+	}
+
+	/// Fields recorded in the access log for this request.
+	pub fn access_log_mut(&mut self) -> &mut AccessLogFields {
+		&mut self.access_log
+	}
 }
 
 #[derive(Clone, Debug)]
@@ -1,7 +1,9 @@
 use bytes::Bytes;
-use http_body_util::Full;
+use http_body_util::{Full, combinators::UnsyncBoxBody};
 use hyper::body::Incoming as BodyIncoming;
 
+use crate::access_log::AccessLogBody;
+
 /// Response body type that can handle both streaming and buffered responses
 #[derive(Debug)]
 pub enum ResponseBody {
@@ -9,6 +11,12 @@ pub enum ResponseBody {
 	Full(Full<Bytes>),
 	/// Streaming response body
 	Incoming(BodyIncoming),
+	/// Response body recorded in the access log
+	AccessLog(Box<AccessLogBody>),
+	/// Response body compressed by guard
+	Compressed(UnsyncBoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>),
+	/// Streaming response body produced by a custom serve handler, may end with trailers
+	Stream(UnsyncBoxBody<Bytes, Box<dyn std::error::Error + Send + Sync>>),
 }
 
 impl http_body::Body for ResponseBody {
@@ -46,6 +54,9 @@ impl http_body::Body for ResponseBody {
 					std::task::Poll::Pending => std::task::Poll::Pending,
 				}
 			}
+			ResponseBody::AccessLog(body) => std::pin::Pin::new(body.as_mut()).poll_frame(cx),
+			ResponseBody::Compressed(body) => std::pin::Pin::new(body).poll_frame(cx),
+			ResponseBody::Stream(body) => std::pin::Pin::new(body).poll_frame(cx),
 		}
 	}
 
@@ -53,6 +64,9 @@ impl http_body::Body for ResponseBody {
 		match self {
 			ResponseBody::Full(body) => body.is_end_stream(),
 			ResponseBody::Incoming(body) => body.is_end_stream(),
+			ResponseBody::AccessLog(body) => body.is_end_stream(),
+			ResponseBody::Compressed(body) => body.is_end_stream(),
+			ResponseBody::Stream(body) => body.is_end_stream(),
 		}
 	}
 
@@ -60,6 +74,9 @@ impl http_body::Body for ResponseBody {
 		match self {
 			ResponseBody::Full(body) => body.size_hint(),
 			ResponseBody::Incoming(body) => body.size_hint(),
+			ResponseBody::AccessLog(body) => body.size_hint(),
+			ResponseBody::Compressed(body) => body.size_hint(),
+			ResponseBody::Stream(body) => body.size_hint(),
 		}
 	}
 }
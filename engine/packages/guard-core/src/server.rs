@@ -14,7 +14,8 @@ use rivet_runtime::TermSignal;
 use tokio_rustls::TlsAcceptor;
 use tracing::Instrument;
 
-use crate::cert_resolver::{CertResolverFn, create_tls_config};
+use crate::access_log::AccessLogFn;
+use crate::cert_resolver::{ACME_TLS_ALPN_PROTOCOL, CertResolverFn, create_tls_config};
 use crate::metrics;
 use crate::proxy_service::ProxyServiceFactory;
 use crate::route::{CacheKeyFn, RoutingFn};
@@ -28,6 +29,7 @@ pub async fn run_server(
 	routing_fn: RoutingFn,
 	cache_key_fn: CacheKeyFn,
 	cert_resolver_fn: Option<CertResolverFn>,
+	access_log_fn: Option<AccessLogFn>,
 ) -> Result<()> {
 	// Set up HTTP server
 	let http_addr: std::net::SocketAddr = (config.guard().host(), config.guard().port()).into();
@@ -35,6 +37,7 @@ pub async fn run_server(
 		config.clone(),
 		routing_fn.clone(),
 		cache_key_fn.clone(),
+		access_log_fn.clone(),
 	));
 	let http_listener = tokio::net::TcpListener::bind(http_addr).await?;
 
@@ -47,13 +50,15 @@ pub async fn run_server(
 			config.clone(),
 			routing_fn.clone(),
 			cache_key_fn.clone(),
+			access_log_fn.clone(),
 		));
 		let listener = tokio::net::TcpListener::bind(https_addr).await?;
 
 		// Configure TLS if resolver function is provided
 		let acceptor = if let Some(resolver_fn) = cert_resolver_fn {
 			// Create a TLS server config using our certificate resolver
-			let server_config = create_tls_config(resolver_fn);
+			let server_config =
+				create_tls_config(resolver_fn, https.acme_tls_alpn(), https.http2());
 
 			Some(TlsAcceptor::from(Arc::new(server_config)))
 		} else {
@@ -189,6 +194,10 @@ pub async fn run_server(
 											.instrument(tracing::info_span!("accept"))
 											.await
 										{
+											Result::Ok(tls_stream) if tls_stream.get_ref().1.alpn_protocol() == Some(ACME_TLS_ALPN_PROTOCOL) => {
+												// The ACME server only validates the challenge certificate
+												tracing::debug!("TLS-ALPN-01 challenge handshake completed for {}", remote_addr);
+											}
 											Result::Ok(tls_stream) => {
 												tracing::debug!("TLS handshake successful for {}", remote_addr);
 
@@ -129,6 +129,7 @@ pub(crate) fn add_proxy_headers_with_addr(
 }
 
 pub(crate) fn err_into_response(err: anyhow::Error) -> Result<Response<ResponseBody>> {
+	let mut retry_after_secs = None;
 	let (status, error_response) =
 		if let Some(rivet_err) = err.chain().find_map(|x| x.downcast_ref::<RivetError>()) {
 			let status = match (rivet_err.group(), rivet_err.code()) {
@@ -136,6 +137,13 @@ pub(crate) fn err_into_response(err: anyhow::Error) -> Result<Response<ResponseB
 				("api", "unauthorized") => StatusCode::UNAUTHORIZED,
 				("api", "forbidden") => StatusCode::FORBIDDEN,
 				("guard", "rate_limit") => StatusCode::TOO_MANY_REQUESTS,
+				("guard", "rate_limit_exceeded") => {
+					retry_after_secs = rivet_err
+						.metadata()
+						.and_then(|meta| meta.get("retry_after_secs")?.as_u64());
+					StatusCode::TOO_MANY_REQUESTS
+				}
+				("guard", "unauthorized") => StatusCode::UNAUTHORIZED,
 				("guard", "upstream_error") => StatusCode::BAD_GATEWAY,
 				("guard", "routing_error") => StatusCode::BAD_GATEWAY,
 				("guard", "request_timeout") => StatusCode::GATEWAY_TIMEOUT,
@@ -176,13 +184,33 @@ pub(crate) fn err_into_response(err: anyhow::Error) -> Result<Response<ResponseB
 	let body_json = serde_json::to_vec(&error_response)?;
 	let bytes = Bytes::from(body_json);
 
-	Response::builder()
+	let mut builder = Response::builder()
 		.status(status)
-		.header(hyper::header::CONTENT_TYPE, "application/json")
+		.header(hyper::header::CONTENT_TYPE, "application/json");
+	if let Some(retry_after_secs) = retry_after_secs {
+		builder = builder.header(hyper::header::RETRY_AFTER, retry_after_secs);
+	}
+
+	builder
 		.body(ResponseBody::Full(Full::new(bytes)))
 		.map_err(Into::into)
 }
 
+/// Whether the request body is streamed to the handler instead of buffered. gRPC calls can be
+/// full-duplex, so the response may start before the request body ends.
+pub(crate) fn is_streaming_request(headers: &hyper::HeaderMap) -> bool {
+	headers
+		.get(hyper::header::CONTENT_TYPE)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.split(';').next())
+		.is_some_and(|value| {
+			value
+				.trim()
+				.to_ascii_lowercase()
+				.starts_with("application/grpc")
+		})
+}
+
 pub(crate) fn should_retry_request(res: &Result<Response<ResponseBody>>) -> bool {
 	match res {
 		Ok(resp) => should_retry_request_inner(resp.status(), resp.headers()),
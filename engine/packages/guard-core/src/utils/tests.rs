@@ -33,3 +33,33 @@ fn skips_non_service_unavailable_with_rivet_error_header() {
 
 	assert!(!should_retry_request_inner(StatusCode::NOT_FOUND, &headers));
 }
+
+#[test]
+fn streams_grpc_requests() {
+	for content_type in [
+		"application/grpc",
+		"application/grpc+proto",
+		"application/grpc-web-text; charset=utf-8",
+		"Application/GRPC-Web",
+	] {
+		let mut headers = hyper::HeaderMap::new();
+		headers.insert(
+			hyper::header::CONTENT_TYPE,
+			HeaderValue::from_static(content_type),
+		);
+
+		assert!(is_streaming_request(&headers), "{content_type}");
+	}
+}
+
+#[test]
+fn buffers_non_grpc_requests() {
+	let mut headers = hyper::HeaderMap::new();
+	assert!(!is_streaming_request(&headers));
+
+	headers.insert(
+		hyper::header::CONTENT_TYPE,
+		HeaderValue::from_static("application/json"),
+	);
+	assert!(!is_streaming_request(&headers));
+}
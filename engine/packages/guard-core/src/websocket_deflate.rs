@@ -0,0 +1,570 @@
+//! permessage-deflate (RFC 7692) for WebSockets accepted by guard.
+//!
+//! tungstenite does not implement the extension, so [`DeflateStream`] sits between the upgraded
+//! connection and tungstenite. It inflates compressed messages from the client into plain frames
+//! before tungstenite reads them and deflates data frames tungstenite writes to the client.
+//!
+//! Both directions are negotiated without context takeover, so every message is compressed on its
+//! own and idle connections do not hold compressor state.
+
+use std::{
+	future::IntoFuture,
+	io,
+	pin::Pin,
+	task::{Context, Poll, ready},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use flate2::{Compress, Decompress, FlushCompress, FlushDecompress, Status};
+use futures_util::{FutureExt, future::BoxFuture};
+use http_body_util::Full;
+use hyper::{
+	Request, Response, StatusCode,
+	header::{self, HeaderMap, HeaderValue},
+	upgrade::Upgraded,
+};
+use hyper_tungstenite::tungstenite::{
+	self,
+	error::ProtocolError,
+	handshake::derive_accept_key,
+	protocol::{Role, WebSocketConfig},
+};
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_tungstenite::WebSocketStream;
+
+/// Extension accepted in the upgrade response.
+const EXTENSION_RESPONSE: &str =
+	"permessage-deflate; server_no_context_takeover; client_no_context_takeover";
+/// Tail of a sync flushed deflate block, stripped from compressed messages (RFC 7692 7.2.1).
+const DEFLATE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+/// Frames tungstenite wrote that have not reached the connection yet before writes wait.
+const WRITE_BUFFER_HIGH_WATER: usize = 64 * 1024;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+
+/// Connection of a WebSocket accepted by guard.
+pub type ClientIo = DeflateStream<TokioIo<Upgraded>>;
+
+/// Settings of permessage-deflate on a connection.
+#[derive(Debug, Clone, Copy)]
+pub struct DeflateConfig {
+	/// Messages smaller than this are sent uncompressed.
+	pub min_size: usize,
+}
+
+/// Client WebSocket that becomes available once the upgrade response was sent.
+pub struct ClientWebSocket {
+	on_upgrade: hyper::upgrade::OnUpgrade,
+	config: WebSocketConfig,
+	deflate: Option<DeflateConfig>,
+}
+
+impl IntoFuture for ClientWebSocket {
+	type Output = Result<WebSocketStream<ClientIo>, tungstenite::Error>;
+	type IntoFuture = BoxFuture<'static, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		async move {
+			let upgraded = self
+				.on_upgrade
+				.await
+				.map_err(|err| tungstenite::Error::Io(io::Error::other(err)))?;
+			let io = DeflateStream::new(TokioIo::new(upgraded), self.deflate, &self.config);
+
+			Ok(WebSocketStream::from_raw_socket(io, Role::Server, Some(self.config)).await)
+		}
+		.boxed()
+	}
+}
+
+/// Accepts a WebSocket upgrade request, negotiating permessage-deflate if `deflate` is set and the
+/// client offers it. Same as `hyper_tungstenite::upgrade` otherwise.
+pub fn upgrade<B>(
+	mut req: Request<B>,
+	config: WebSocketConfig,
+	deflate: Option<DeflateConfig>,
+) -> Result<(Response<Full<Bytes>>, ClientWebSocket), ProtocolError> {
+	let key = req
+		.headers()
+		.get(header::SEC_WEBSOCKET_KEY)
+		.ok_or(ProtocolError::MissingSecWebSocketKey)?;
+	if req
+		.headers()
+		.get(header::SEC_WEBSOCKET_VERSION)
+		.is_none_or(|version| version.as_bytes() != b"13")
+	{
+		return Err(ProtocolError::MissingSecWebSocketVersionHeader);
+	}
+
+	let deflate = deflate.filter(|_| accepts_deflate_offer(req.headers()));
+
+	let mut builder = Response::builder()
+		.status(StatusCode::SWITCHING_PROTOCOLS)
+		.header(header::CONNECTION, "upgrade")
+		.header(header::UPGRADE, "websocket")
+		.header(
+			header::SEC_WEBSOCKET_ACCEPT,
+			derive_accept_key(key.as_bytes()),
+		);
+	if deflate.is_some() {
+		builder = builder.header(
+			header::SEC_WEBSOCKET_EXTENSIONS,
+			HeaderValue::from_static(EXTENSION_RESPONSE),
+		);
+	}
+	let res = builder
+		.body(Full::new(Bytes::new()))
+		.expect("invalid upgrade response");
+
+	let websocket = ClientWebSocket {
+		on_upgrade: hyper::upgrade::on(&mut req),
+		config,
+		deflate,
+	};
+
+	Ok((res, websocket))
+}
+
+/// Whether the client offers permessage-deflate with parameters guard can accept. Guard always
+/// compresses with a 32 KiB window, so offers limiting the server window are declined.
+pub fn accepts_deflate_offer(headers: &HeaderMap) -> bool {
+	headers
+		.get_all(header::SEC_WEBSOCKET_EXTENSIONS)
+		.iter()
+		.filter_map(|x| x.to_str().ok())
+		.flat_map(|x| x.split(','))
+		.any(|offer| {
+			let mut params = offer.split(';').map(str::trim);
+			if !params
+				.next()
+				.is_some_and(|name| name.eq_ignore_ascii_case("permessage-deflate"))
+			{
+				return false;
+			}
+
+			let mut seen = Vec::new();
+			params.all(|param| {
+				let (name, value) = match param.split_once('=') {
+					Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+					None => (param, None),
+				};
+				if seen.contains(&name) {
+					return false;
+				}
+				seen.push(name);
+
+				match (name, value) {
+					("server_no_context_takeover" | "client_no_context_takeover", None) => true,
+					("client_max_window_bits", None) => true,
+					("client_max_window_bits", Some(bits)) => bits
+						.parse::<u8>()
+						.is_ok_and(|bits| (8..=15).contains(&bits)),
+					("server_max_window_bits", Some(bits)) => bits == "15",
+					_ => false,
+				}
+			})
+		})
+}
+
+/// Connection wrapper that translates between permessage-deflate frames on the wire and the plain
+/// frames tungstenite reads and writes. Passes bytes through untouched if the extension was not
+/// negotiated.
+pub struct DeflateStream<S> {
+	inner: S,
+	deflate: Option<DeflateConfig>,
+	max_frame_size: usize,
+	max_message_size: usize,
+
+	/// Bytes read from the client that do not form a complete frame yet.
+	read_buf: BytesMut,
+	/// Frames ready for tungstenite to read.
+	read_out: BytesMut,
+	/// Compressed message being received from the client.
+	inflating: Option<(u8, Vec<u8>)>,
+	read_eof: bool,
+
+	/// Bytes written by tungstenite that do not form a complete frame yet.
+	write_buf: BytesMut,
+	/// Frames ready to be written to the client.
+	write_out: BytesMut,
+}
+
+impl<S> DeflateStream<S> {
+	pub fn new(inner: S, deflate: Option<DeflateConfig>, config: &WebSocketConfig) -> Self {
+		DeflateStream {
+			inner,
+			deflate,
+			max_frame_size: config.max_frame_size.unwrap_or(usize::MAX),
+			max_message_size: config.max_message_size.unwrap_or(usize::MAX),
+			read_buf: BytesMut::new(),
+			read_out: BytesMut::new(),
+			inflating: None,
+			read_eof: false,
+			write_buf: BytesMut::new(),
+			write_out: BytesMut::new(),
+		}
+	}
+
+	/// Moves complete frames from `read_buf` to `read_out`, inflating compressed messages.
+	fn process_read(&mut self) -> io::Result<()> {
+		while let Some(frame) = take_frame(&mut self.read_buf, self.max_frame_size)? {
+			match (frame.opcode, frame.rsv1) {
+				(OPCODE_TEXT | OPCODE_BINARY, _) if self.inflating.is_some() => {
+					return Err(invalid_data("new message before the previous one finished"));
+				}
+				(OPCODE_TEXT | OPCODE_BINARY, true) => {
+					self.inflating = Some((frame.opcode, Vec::new()));
+				}
+				(OPCODE_CONTINUATION, true) if self.inflating.is_some() => {
+					return Err(invalid_data("continuation frame has rsv1 set"));
+				}
+				(OPCODE_CONTINUATION, false) if self.inflating.is_some() => {}
+				// Control frames and uncompressed messages are passed through, tungstenite rejects
+				// them if they are invalid
+				_ => {
+					self.read_out.extend_from_slice(&frame.raw);
+					continue;
+				}
+			}
+
+			let (opcode, payload) = self.inflating.as_mut().expect("no compressed message");
+			payload.extend_from_slice(&frame.payload());
+			if payload.len() > self.max_message_size {
+				return Err(invalid_data("compressed message too large"));
+			}
+
+			if frame.fin {
+				let opcode = *opcode;
+				let message = inflate(payload, self.max_message_size)?;
+				self.inflating = None;
+
+				// Split so every frame stays within the limit tungstenite enforces
+				let mut offset = 0;
+				loop {
+					let end = offset
+						.saturating_add(self.max_frame_size.max(1))
+						.min(message.len());
+					let opcode = if offset == 0 {
+						opcode
+					} else {
+						OPCODE_CONTINUATION
+					};
+					let fin = end == message.len();
+					write_frame(
+						&mut self.read_out,
+						fin,
+						false,
+						opcode,
+						true,
+						&message[offset..end],
+					);
+
+					if fin {
+						break;
+					}
+					offset = end;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Moves complete frames from `write_buf` to `write_out`, deflating data messages.
+	fn process_write(&mut self, deflate: DeflateConfig) -> io::Result<()> {
+		while let Some(frame) = take_frame(&mut self.write_buf, usize::MAX)? {
+			// Fragmented messages are rare from guard and are sent uncompressed
+			let compressible = frame.fin
+				&& !frame.rsv1
+				&& frame.mask.is_none()
+				&& matches!(frame.opcode, OPCODE_TEXT | OPCODE_BINARY)
+				&& frame.payload_len >= deflate.min_size;
+			if compressible {
+				let payload = &frame.raw[frame.raw.len() - frame.payload_len..];
+				let compressed = deflate_message(payload)?;
+				if compressed.len() < payload.len() {
+					write_frame(
+						&mut self.write_out,
+						true,
+						true,
+						frame.opcode,
+						false,
+						&compressed,
+					);
+					continue;
+				}
+			}
+
+			self.write_out.extend_from_slice(&frame.raw);
+		}
+
+		Ok(())
+	}
+}
+
+impl<S: AsyncWrite + Unpin> DeflateStream<S> {
+	fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		while !self.write_out.is_empty() {
+			let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.write_out))?;
+			if n == 0 {
+				return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+			}
+			self.write_out.advance(n);
+		}
+
+		Poll::Ready(Ok(()))
+	}
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for DeflateStream<S> {
+	fn poll_read(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &mut ReadBuf<'_>,
+	) -> Poll<io::Result<()>> {
+		let this = self.get_mut();
+		if this.deflate.is_none() {
+			return Pin::new(&mut this.inner).poll_read(cx, buf);
+		}
+
+		loop {
+			if !this.read_out.is_empty() {
+				let n = this.read_out.len().min(buf.remaining());
+				buf.put_slice(&this.read_out[..n]);
+				this.read_out.advance(n);
+				return Poll::Ready(Ok(()));
+			}
+
+			if this.read_eof {
+				// Hand a truncated frame to tungstenite so it reports the error
+				let rest = this.read_buf.split();
+				this.read_out.unsplit(rest);
+				if this.read_out.is_empty() {
+					return Poll::Ready(Ok(()));
+				}
+				continue;
+			}
+
+			this.read_buf.reserve(8 * 1024);
+			let n = ready!(tokio_util::io::poll_read_buf(
+				Pin::new(&mut this.inner),
+				cx,
+				&mut this.read_buf,
+			))?;
+			if n == 0 {
+				this.read_eof = true;
+			}
+
+			this.process_read()?;
+		}
+	}
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for DeflateStream<S> {
+	fn poll_write(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		let this = self.get_mut();
+		let Some(deflate) = this.deflate else {
+			return Pin::new(&mut this.inner).poll_write(cx, buf);
+		};
+
+		if this.poll_drain(cx)?.is_pending() && this.write_out.len() >= WRITE_BUFFER_HIGH_WATER {
+			return Poll::Pending;
+		}
+
+		this.write_buf.extend_from_slice(buf);
+		this.process_write(deflate)?;
+
+		// Start sending right away, a pending write is picked up by the next write or flush
+		if let Poll::Ready(Err(err)) = this.poll_drain(cx) {
+			return Poll::Ready(Err(err));
+		}
+
+		Poll::Ready(Ok(buf.len()))
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		let this = self.get_mut();
+		ready!(this.poll_drain(cx))?;
+		Pin::new(&mut this.inner).poll_flush(cx)
+	}
+
+	fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		let this = self.get_mut();
+		ready!(this.poll_drain(cx))?;
+		Pin::new(&mut this.inner).poll_shutdown(cx)
+	}
+}
+
+struct Frame {
+	fin: bool,
+	rsv1: bool,
+	opcode: u8,
+	mask: Option<[u8; 4]>,
+	payload_len: usize,
+	/// Header and payload as read.
+	raw: Bytes,
+}
+
+impl Frame {
+	/// Unmasked payload.
+	fn payload(&self) -> Vec<u8> {
+		let mut payload = self.raw[self.raw.len() - self.payload_len..].to_vec();
+		if let Some(mask) = self.mask {
+			for (i, byte) in payload.iter_mut().enumerate() {
+				*byte ^= mask[i % 4];
+			}
+		}
+
+		payload
+	}
+}
+
+/// Splits the first frame off `buf` once it is complete.
+fn take_frame(buf: &mut BytesMut, max_frame_size: usize) -> io::Result<Option<Frame>> {
+	if buf.len() < 2 {
+		return Ok(None);
+	}
+
+	let masked = buf[1] & 0x80 != 0;
+	let (payload_len, mut header_len) = match buf[1] & 0x7f {
+		126 => {
+			if buf.len() < 4 {
+				return Ok(None);
+			}
+			(u16::from_be_bytes([buf[2], buf[3]]) as u64, 4)
+		}
+		127 => {
+			if buf.len() < 10 {
+				return Ok(None);
+			}
+			(
+				u64::from_be_bytes(buf[2..10].try_into().expect("8 bytes")),
+				10,
+			)
+		}
+		len => (len as u64, 2),
+	};
+	if payload_len > max_frame_size as u64 {
+		return Err(invalid_data("frame too large"));
+	}
+	let payload_len = payload_len as usize;
+
+	let mask = if masked {
+		if buf.len() < header_len + 4 {
+			return Ok(None);
+		}
+		let mask = buf[header_len..header_len + 4].try_into().expect("4 bytes");
+		header_len += 4;
+		Some(mask)
+	} else {
+		None
+	};
+
+	if buf.len() < header_len + payload_len {
+		return Ok(None);
+	}
+
+	Ok(Some(Frame {
+		fin: buf[0] & 0x80 != 0,
+		rsv1: buf[0] & 0x40 != 0,
+		opcode: buf[0] & 0x0f,
+		mask,
+		payload_len,
+		raw: buf.split_to(header_len + payload_len).freeze(),
+	}))
+}
+
+/// Writes a frame. Masked frames use a zero key, which leaves the payload as is.
+fn write_frame(
+	out: &mut BytesMut,
+	fin: bool,
+	rsv1: bool,
+	opcode: u8,
+	masked: bool,
+	payload: &[u8],
+) {
+	out.put_u8(((fin as u8) << 7) | ((rsv1 as u8) << 6) | opcode);
+
+	let mask_bit = (masked as u8) << 7;
+	if payload.len() < 126 {
+		out.put_u8(mask_bit | payload.len() as u8);
+	} else if payload.len() <= u16::MAX as usize {
+		out.put_u8(mask_bit | 126);
+		out.put_u16(payload.len() as u16);
+	} else {
+		out.put_u8(mask_bit | 127);
+		out.put_u64(payload.len() as u64);
+	}
+	if masked {
+		out.put_u32(0);
+	}
+
+	out.extend_from_slice(payload);
+}
+
+/// Compresses a whole message with a fresh context.
+fn deflate_message(data: &[u8]) -> io::Result<Vec<u8>> {
+	let mut compress = Compress::new(flate2::Compression::default(), false);
+	let mut out = Vec::with_capacity(data.len() / 2 + 64);
+
+	loop {
+		let consumed = compress.total_in() as usize;
+		compress
+			.compress_vec(&data[consumed..], &mut out, FlushCompress::Sync)
+			.map_err(io::Error::other)?;
+
+		// The flush is complete once all input is consumed without filling the output
+		if compress.total_in() as usize == data.len() && out.len() < out.capacity() {
+			break;
+		}
+		out.reserve(out.capacity());
+	}
+
+	if out.ends_with(&DEFLATE_TAIL) {
+		out.truncate(out.len() - DEFLATE_TAIL.len());
+	}
+
+	Ok(out)
+}
+
+/// Decompresses a whole message with a fresh context.
+fn inflate(data: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+	let mut decompress = Decompress::new(false);
+	let input = [data, &DEFLATE_TAIL].concat();
+	let mut out = Vec::with_capacity((data.len() * 4).clamp(64, max_size.max(64)));
+
+	loop {
+		let (total_in, total_out) = (decompress.total_in(), decompress.total_out());
+		let status = decompress
+			.decompress_vec(&input[total_in as usize..], &mut out, FlushDecompress::Sync)
+			.map_err(invalid_data)?;
+		if out.len() > max_size {
+			return Err(invalid_data("decompressed message too large"));
+		}
+
+		let consumed_all = decompress.total_in() as usize == input.len();
+		if status == Status::StreamEnd || (consumed_all && out.len() < out.capacity()) {
+			break;
+		}
+		if out.len() < out.capacity()
+			&& decompress.total_in() == total_in
+			&& decompress.total_out() == total_out
+		{
+			return Err(invalid_data("truncated compressed message"));
+		}
+		out.reserve(out.capacity());
+	}
+
+	Ok(out)
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, err)
+}
@@ -1,9 +1,6 @@
 use anyhow::*;
 use futures_util::{SinkExt, StreamExt, stream::Peekable};
-use hyper::upgrade::Upgraded;
-use hyper_tungstenite::HyperWebsocket;
 use hyper_tungstenite::tungstenite::Message;
-use hyper_util::rt::TokioIo;
 use rivet_perf::{perf_finish, perf_start};
 use std::sync::Arc;
 use std::time::Instant;
@@ -11,12 +8,11 @@ use tokio::sync::Mutex;
 use tokio_tungstenite::WebSocketStream;
 
 use crate::metrics;
+use crate::websocket_deflate::{ClientIo, ClientWebSocket};
 
-pub type WebSocketReceiver =
-	Peekable<futures_util::stream::SplitStream<WebSocketStream<TokioIo<Upgraded>>>>;
+pub type WebSocketReceiver = Peekable<futures_util::stream::SplitStream<WebSocketStream<ClientIo>>>;
 
-pub type WebSocketSender =
-	futures_util::stream::SplitSink<WebSocketStream<TokioIo<Upgraded>>, Message>;
+pub type WebSocketSender = futures_util::stream::SplitSink<WebSocketStream<ClientIo>, Message>;
 
 #[derive(Clone)]
 pub struct WebSocketHandle {
@@ -26,7 +22,7 @@ pub struct WebSocketHandle {
 
 impl WebSocketHandle {
 	#[tracing::instrument(skip_all)]
-	pub async fn new(websocket: HyperWebsocket) -> Result<Self> {
+	pub async fn new(websocket: ClientWebSocket) -> Result<Self> {
 		let ws_stream = websocket.await?;
 		let (ws_tx, ws_rx) = ws_stream.split();
 
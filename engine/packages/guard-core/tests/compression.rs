@@ -0,0 +1,206 @@
+use std::time::Duration;
+
+use async_compression::tokio::bufread::GzipDecoder;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::{
+	HeaderMap, Method, Response, StatusCode,
+	body::Frame,
+	header::{self, HeaderValue},
+};
+use rivet_config::config::guard::{Compression, CompressionEncoding};
+use rivet_guard_core::{
+	ResponseBody,
+	compression::{Compressor, negotiate},
+};
+use tokio::{io::AsyncReadExt, sync::mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+const ALL: &[CompressionEncoding] = &[
+	CompressionEncoding::Zstd,
+	CompressionEncoding::Br,
+	CompressionEncoding::Gzip,
+];
+
+fn compressor() -> Compressor {
+	Compressor::new(&Compression {
+		enabled: Some(true),
+		..Default::default()
+	})
+}
+
+fn req_headers(accept_encoding: &str) -> HeaderMap {
+	let mut headers = HeaderMap::new();
+	headers.insert(
+		header::ACCEPT_ENCODING,
+		HeaderValue::from_str(accept_encoding).unwrap(),
+	);
+	headers
+}
+
+fn response(content_type: &str, body: Vec<u8>) -> Response<ResponseBody> {
+	Response::builder()
+		.status(StatusCode::OK)
+		.header(header::CONTENT_TYPE, content_type)
+		.header(header::CONTENT_LENGTH, body.len())
+		.header(header::ETAG, "\"abc\"")
+		.body(ResponseBody::Full(Full::new(Bytes::from(body))))
+		.unwrap()
+}
+
+#[test]
+fn negotiate_prefers_server_order_on_ties() {
+	assert_eq!(
+		negotiate("gzip, br, zstd", ALL),
+		Some(CompressionEncoding::Zstd)
+	);
+	assert_eq!(negotiate("gzip, br", ALL), Some(CompressionEncoding::Br));
+	assert_eq!(negotiate("*", ALL), Some(CompressionEncoding::Zstd));
+}
+
+#[test]
+fn negotiate_respects_quality_values() {
+	assert_eq!(
+		negotiate("zstd;q=0.5, gzip;q=0.9", ALL),
+		Some(CompressionEncoding::Gzip)
+	);
+	assert_eq!(
+		negotiate("*;q=0.1, zstd;q=0", ALL),
+		Some(CompressionEncoding::Br)
+	);
+	assert_eq!(negotiate("identity", ALL), None);
+	assert_eq!(negotiate("", ALL), None);
+	assert_eq!(negotiate("gzip;q=0", ALL), None);
+}
+
+#[test]
+fn skips_ineligible_responses() {
+	let compressor = compressor();
+	let headers = req_headers("gzip");
+	let body = vec![b'a'; 4096];
+
+	// Too small
+	let res = response("text/plain", vec![b'a'; 16]);
+	assert_eq!(compressor.encoding_for(&Method::GET, &headers, &res), None);
+
+	// Content type not allowed
+	let res = response("image/png", body.clone());
+	assert_eq!(compressor.encoding_for(&Method::GET, &headers, &res), None);
+
+	// Server-sent events
+	let res = response("text/event-stream", body.clone());
+	assert_eq!(compressor.encoding_for(&Method::GET, &headers, &res), None);
+
+	// Already encoded
+	let mut res = response("text/plain", body.clone());
+	res.headers_mut()
+		.insert(header::CONTENT_ENCODING, HeaderValue::from_static("br"));
+	assert_eq!(compressor.encoding_for(&Method::GET, &headers, &res), None);
+
+	// No transform
+	let mut res = response("text/plain", body.clone());
+	res.headers_mut().insert(
+		header::CACHE_CONTROL,
+		HeaderValue::from_static("public, no-transform"),
+	);
+	assert_eq!(compressor.encoding_for(&Method::GET, &headers, &res), None);
+
+	// Head request
+	let res = response("text/plain", body.clone());
+	assert_eq!(compressor.encoding_for(&Method::HEAD, &headers, &res), None);
+
+	// Eligible, with content type parameters
+	let res = response("Text/HTML; charset=utf-8", body);
+	assert_eq!(
+		compressor.encoding_for(&Method::GET, &headers, &res),
+		Some(CompressionEncoding::Gzip)
+	);
+}
+
+#[test]
+fn disabled_by_default() {
+	let compressor = Compressor::new(&Compression::default());
+	let res = response("application/json", vec![b'a'; 4096]);
+	assert_eq!(
+		compressor.encoding_for(&Method::GET, &req_headers("gzip"), &res),
+		None
+	);
+	assert!(compressor.websocket_deflate().is_none());
+}
+
+#[tokio::test]
+async fn compresses_body() {
+	let body = b"hello world ".repeat(512);
+	let res = compressor().compress(
+		&Method::GET,
+		&req_headers("gzip"),
+		response("application/json", body.clone()),
+	);
+
+	let headers = res.headers();
+	assert_eq!(headers[header::CONTENT_ENCODING], "gzip");
+	assert_eq!(headers[header::VARY], "accept-encoding");
+	assert_eq!(headers[header::ETAG], "W/\"abc\"");
+	assert!(headers.get(header::CONTENT_LENGTH).is_none());
+
+	let compressed = res.into_body().collect().await.unwrap().to_bytes();
+	assert!(compressed.len() < body.len());
+
+	let mut decompressed = Vec::new();
+	GzipDecoder::new(&compressed[..])
+		.read_to_end(&mut decompressed)
+		.await
+		.unwrap();
+	assert_eq!(decompressed, body);
+}
+
+#[tokio::test]
+async fn compresses_streamed_body_without_delaying_chunks() {
+	let (tx, rx) = mpsc::channel::<Result<Frame<Bytes>, BoxError>>(4);
+	let res = Response::builder()
+		.status(StatusCode::OK)
+		.header(header::CONTENT_TYPE, "text/html")
+		.body(ResponseBody::Stream(
+			StreamBody::new(ReceiverStream::new(rx)).boxed_unsync(),
+		))
+		.unwrap();
+
+	let headers = req_headers("gzip");
+	assert_eq!(
+		compressor().encoding_for(&Method::GET, &headers, &res),
+		Some(CompressionEncoding::Gzip)
+	);
+	let mut body = compressor()
+		.compress(&Method::GET, &headers, res)
+		.into_body();
+
+	// The first chunk is flushed while the upstream has nothing else ready
+	let first = b"<html><body>".to_vec();
+	tx.send(Ok(Frame::data(Bytes::from(first.clone()))))
+		.await
+		.unwrap();
+	let mut compressed = tokio::time::timeout(Duration::from_secs(5), body.frame())
+		.await
+		.expect("first chunk was held back")
+		.unwrap()
+		.unwrap()
+		.into_data()
+		.unwrap()
+		.to_vec();
+
+	let rest = b"hello world ".repeat(512);
+	tx.send(Ok(Frame::data(Bytes::from(rest.clone()))))
+		.await
+		.unwrap();
+	drop(tx);
+	compressed.extend_from_slice(&body.collect().await.unwrap().to_bytes());
+
+	let mut decompressed = Vec::new();
+	GzipDecoder::new(&compressed[..])
+		.read_to_end(&mut decompressed)
+		.await
+		.unwrap();
+	assert_eq!(decompressed, [first, rest].concat());
+}
@@ -0,0 +1,177 @@
+use bytes::Bytes;
+use http_body_util::BodyExt;
+use hyper::{HeaderMap, Method, header::HeaderValue};
+use rivet_config::config::guard;
+use rivet_guard_core::{
+	metrics,
+	mirror::{Mirror, X_RIVET_MIRROR},
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+fn mirror(url: &str, name: &str, route_kinds: Option<Vec<String>>) -> Mirror {
+	Mirror::new(&guard::Mirror {
+		url: url.parse().unwrap(),
+		name: Some(name.to_string()),
+		sample_rate: None,
+		route_kinds,
+		timeout_ms: Some(5_000),
+		methods: None,
+		max_in_flight: None,
+	})
+}
+
+#[tokio::test]
+async fn build_request_copies_request() {
+	let mirror = mirror("http://shadow.internal:8080/v2/", "build", None);
+
+	let mut headers = HeaderMap::new();
+	headers.insert("host", HeaderValue::from_static("api.example.com"));
+	headers.insert("content-length", HeaderValue::from_static("5"));
+	headers.insert("authorization", HeaderValue::from_static("Bearer abc"));
+	headers.append("x-custom", HeaderValue::from_static("a"));
+	headers.append("x-custom", HeaderValue::from_static("b"));
+
+	let req = mirror
+		.build_request(
+			&Method::POST,
+			"/actors?namespace=default",
+			&headers,
+			Bytes::from_static(b"hello"),
+		)
+		.unwrap();
+
+	assert_eq!(req.method(), Method::POST);
+	assert_eq!(
+		req.uri().to_string(),
+		"http://shadow.internal:8080/v2/actors?namespace=default"
+	);
+	assert!(req.headers().get("host").is_none());
+	assert!(req.headers().get("content-length").is_none());
+	assert_eq!(req.headers()["authorization"], "Bearer abc");
+	assert_eq!(req.headers().get_all("x-custom").iter().count(), 2);
+	assert_eq!(req.headers()[X_RIVET_MIRROR], "build");
+	assert_eq!(
+		req.into_body().collect().await.unwrap().to_bytes(),
+		Bytes::from_static(b"hello")
+	);
+}
+
+#[test]
+fn should_mirror_filters_route_kinds() {
+	let all = mirror("http://shadow.internal", "all", None);
+	assert!(all.should_mirror(&Method::GET, None));
+	assert!(all.should_mirror(&Method::GET, Some("gateway")));
+
+	let api = mirror(
+		"http://shadow.internal",
+		"api",
+		Some(vec!["api".to_string()]),
+	);
+	assert!(api.should_mirror(&Method::GET, Some("api")));
+	assert!(!api.should_mirror(&Method::GET, Some("gateway")));
+	assert!(!api.should_mirror(&Method::GET, None));
+
+	let none = Mirror::new(&guard::Mirror {
+		url: "http://shadow.internal".parse().unwrap(),
+		name: None,
+		sample_rate: Some(0.0),
+		route_kinds: None,
+		timeout_ms: None,
+		methods: None,
+		max_in_flight: None,
+	});
+	assert_eq!(none.name(), "shadow");
+	assert!(!none.should_mirror(&Method::GET, Some("api")));
+}
+
+#[test]
+fn should_mirror_filters_methods() {
+	let read_only = mirror("http://shadow.internal", "read-only", None);
+	assert!(read_only.should_mirror(&Method::GET, None));
+	assert!(read_only.should_mirror(&Method::HEAD, None));
+	assert!(read_only.should_mirror(&Method::OPTIONS, None));
+	assert!(!read_only.should_mirror(&Method::POST, None));
+	assert!(!read_only.should_mirror(&Method::PUT, None));
+	assert!(!read_only.should_mirror(&Method::DELETE, None));
+
+	let post = Mirror::new(&guard::Mirror {
+		url: "http://shadow.internal".parse().unwrap(),
+		name: Some("post".to_string()),
+		sample_rate: None,
+		route_kinds: None,
+		timeout_ms: None,
+		methods: Some(vec!["post".to_string()]),
+		max_in_flight: None,
+	});
+	assert!(post.should_mirror(&Method::POST, None));
+	assert!(!post.should_mirror(&Method::GET, None));
+}
+
+#[test]
+fn try_reserve_drops_copies_over_limit() {
+	let mirror = Mirror::new(&guard::Mirror {
+		url: "http://shadow.internal".parse().unwrap(),
+		name: Some("limited".to_string()),
+		sample_rate: None,
+		route_kinds: None,
+		timeout_ms: None,
+		methods: None,
+		max_in_flight: Some(2),
+	});
+
+	let first = mirror.try_reserve().expect("first copy should be reserved");
+	let _second = mirror
+		.try_reserve()
+		.expect("second copy should be reserved");
+	assert!(mirror.try_reserve().is_none());
+	assert_eq!(
+		metrics::MIRROR_DROPPED_TOTAL
+			.with_label_values(&["limited"])
+			.get(),
+		1
+	);
+
+	// Finished copies free their slot
+	drop(first);
+	assert!(mirror.try_reserve().is_some());
+}
+
+#[tokio::test]
+async fn send_records_shadow_status() {
+	let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let addr = listener.local_addr().unwrap();
+	let server = tokio::spawn(async move {
+		let (mut stream, _) = listener.accept().await.unwrap();
+		let mut buf = vec![0; 4096];
+		let n = stream.read(&mut buf).await.unwrap();
+		stream
+			.write_all(b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 4\r\n\r\nnope")
+			.await
+			.unwrap();
+		String::from_utf8_lossy(&buf[..n]).to_lowercase()
+	});
+
+	let mirror = mirror(&format!("http://{addr}"), "send", None);
+	let req = mirror
+		.build_request(&Method::GET, "/health", &HeaderMap::new(), Bytes::new())
+		.unwrap();
+	mirror.send(req).await;
+	mirror.record_primary(200);
+
+	let received = server.await.unwrap();
+	assert!(received.starts_with("get /health http/1.1"));
+	assert!(received.contains("x-rivet-mirror: send"));
+
+	assert_eq!(
+		metrics::MIRROR_RESPONSE_TOTAL
+			.with_label_values(&["send", "shadow", "503"])
+			.get(),
+		1
+	);
+	assert_eq!(
+		metrics::MIRROR_RESPONSE_TOTAL
+			.with_label_values(&["send", "primary", "200"])
+			.get(),
+		1
+	);
+}
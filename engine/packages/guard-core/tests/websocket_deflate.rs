@@ -0,0 +1,199 @@
+use flate2::{Compress, Decompress, FlushCompress, FlushDecompress};
+use futures_util::{SinkExt, StreamExt};
+use hyper::{
+	HeaderMap,
+	header::{self, HeaderValue},
+};
+use hyper_tungstenite::tungstenite::{
+	Message,
+	protocol::{Role, WebSocketConfig},
+};
+use rivet_guard_core::websocket_deflate::{DeflateConfig, DeflateStream, accepts_deflate_offer};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+use tokio_tungstenite::WebSocketStream;
+
+const DEFLATE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+fn offer(extensions: &str) -> HeaderMap {
+	let mut headers = HeaderMap::new();
+	headers.insert(
+		header::SEC_WEBSOCKET_EXTENSIONS,
+		HeaderValue::from_str(extensions).unwrap(),
+	);
+	headers
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+	let mut compress = Compress::new(flate2::Compression::default(), false);
+	let mut out = Vec::with_capacity(data.len() + 64);
+	compress
+		.compress_vec(data, &mut out, FlushCompress::Sync)
+		.unwrap();
+	assert!(out.ends_with(&DEFLATE_TAIL));
+	out.truncate(out.len() - DEFLATE_TAIL.len());
+	out
+}
+
+fn inflate(data: &[u8]) -> Vec<u8> {
+	let mut decompress = Decompress::new(false);
+	let mut out = Vec::with_capacity(64 * 1024);
+	decompress
+		.decompress_vec(
+			&[data, &DEFLATE_TAIL].concat(),
+			&mut out,
+			FlushDecompress::Sync,
+		)
+		.unwrap();
+	out
+}
+
+/// Writes a masked client frame.
+async fn write_client_frame(client: &mut DuplexStream, first_byte: u8, payload: &[u8]) {
+	let mask = [1, 2, 3, 4];
+	let mut frame = vec![first_byte];
+	if payload.len() < 126 {
+		frame.push(0x80 | payload.len() as u8);
+	} else {
+		frame.push(0x80 | 126);
+		frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+	}
+	frame.extend_from_slice(&mask);
+	frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+
+	client.write_all(&frame).await.unwrap();
+}
+
+/// Reads an unmasked server frame, returns its first byte and payload.
+async fn read_server_frame(client: &mut DuplexStream) -> (u8, Vec<u8>) {
+	let mut header = [0; 2];
+	client.read_exact(&mut header).await.unwrap();
+	assert_eq!(header[1] & 0x80, 0, "server frames are not masked");
+
+	let len = match header[1] & 0x7f {
+		126 => client.read_u16().await.unwrap() as usize,
+		127 => client.read_u64().await.unwrap() as usize,
+		len => len as usize,
+	};
+	let mut payload = vec![0; len];
+	client.read_exact(&mut payload).await.unwrap();
+
+	(header[0], payload)
+}
+
+async fn server(
+	io: DuplexStream,
+	deflate: Option<DeflateConfig>,
+) -> WebSocketStream<DeflateStream<DuplexStream>> {
+	let config = WebSocketConfig::default().max_frame_size(Some(1024));
+	WebSocketStream::from_raw_socket(
+		DeflateStream::new(io, deflate, &config),
+		Role::Server,
+		Some(config),
+	)
+	.await
+}
+
+#[test]
+fn negotiates_supported_offers() {
+	assert!(accepts_deflate_offer(&offer("permessage-deflate")));
+	assert!(accepts_deflate_offer(&offer(
+		"permessage-deflate; client_max_window_bits"
+	)));
+	assert!(accepts_deflate_offer(&offer(
+		"permessage-deflate; server_no_context_takeover; client_max_window_bits=10"
+	)));
+	// A later offer is accepted if an earlier one is not
+	assert!(accepts_deflate_offer(&offer(
+		"permessage-deflate; server_max_window_bits=10, permessage-deflate"
+	)));
+
+	assert!(!accepts_deflate_offer(&HeaderMap::new()));
+	assert!(!accepts_deflate_offer(&offer("x-webkit-deflate-frame")));
+	assert!(!accepts_deflate_offer(&offer(
+		"permessage-deflate; server_max_window_bits=10"
+	)));
+	assert!(!accepts_deflate_offer(&offer(
+		"permessage-deflate; client_no_context_takeover; client_no_context_takeover"
+	)));
+	assert!(!accepts_deflate_offer(&offer(
+		"permessage-deflate; unknown"
+	)));
+}
+
+#[tokio::test]
+async fn inflates_client_messages() {
+	let (mut client, io) = tokio::io::duplex(64 * 1024);
+	let mut server = server(io, Some(DeflateConfig { min_size: 16 })).await;
+
+	// Compressed single frame
+	let text = "hello world ".repeat(64);
+	write_client_frame(&mut client, 0xc1, &deflate(text.as_bytes())).await;
+	assert_eq!(
+		server.next().await.unwrap().unwrap(),
+		Message::text(text.clone())
+	);
+
+	// Compressed and fragmented, larger than the frame size once inflated
+	let data = b"binary ".repeat(1024);
+	let compressed = deflate(&data);
+	let (head, tail) = compressed.split_at(compressed.len() / 2);
+	write_client_frame(&mut client, 0x42, head).await;
+	write_client_frame(&mut client, 0x89, b"ping").await;
+	write_client_frame(&mut client, 0x80, tail).await;
+	assert_eq!(
+		server.next().await.unwrap().unwrap(),
+		Message::Ping(b"ping".to_vec().into())
+	);
+	assert_eq!(server.next().await.unwrap().unwrap(), Message::binary(data));
+
+	// Uncompressed messages are still accepted
+	write_client_frame(&mut client, 0x81, b"plain").await;
+	assert_eq!(
+		server.next().await.unwrap().unwrap(),
+		Message::text("plain")
+	);
+}
+
+#[tokio::test]
+async fn deflates_server_messages() {
+	let (mut client, io) = tokio::io::duplex(64 * 1024);
+	let mut server = server(io, Some(DeflateConfig { min_size: 16 })).await;
+
+	let text = "hello world ".repeat(64);
+	server.send(Message::text(text.clone())).await.unwrap();
+	let (first_byte, payload) = read_server_frame(&mut client).await;
+	assert_eq!(first_byte, 0xc1, "fin, rsv1 and text opcode");
+	assert!(payload.len() < text.len());
+	assert_eq!(inflate(&payload), text.as_bytes());
+
+	// Below the minimum size
+	server.send(Message::text("small")).await.unwrap();
+	assert_eq!(
+		read_server_frame(&mut client).await,
+		(0x81, b"small".to_vec())
+	);
+
+	// Control frames are never compressed
+	server
+		.send(Message::Pong(vec![b'a'; 64].into()))
+		.await
+		.unwrap();
+	assert_eq!(read_server_frame(&mut client).await, (0x8a, vec![b'a'; 64]));
+}
+
+#[tokio::test]
+async fn passes_through_without_extension() {
+	let (mut client, io) = tokio::io::duplex(64 * 1024);
+	let mut server = server(io, None).await;
+
+	let text = "hello world ".repeat(64);
+	server.send(Message::text(text.clone())).await.unwrap();
+	assert_eq!(
+		read_server_frame(&mut client).await,
+		(0x81, text.into_bytes())
+	);
+
+	// Compressed frames are rejected since the extension was not negotiated
+	write_client_frame(&mut client, 0xc1, &deflate(b"hello")).await;
+	assert!(server.next().await.unwrap().is_err());
+}
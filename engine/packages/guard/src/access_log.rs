@@ -0,0 +1,238 @@
+use std::{
+	borrow::Cow,
+	collections::{BTreeMap, HashSet},
+	net::IpAddr,
+	sync::Arc,
+	time::Duration,
+};
+
+use chrono::{TimeZone, Utc};
+use gas::prelude::Id;
+use rivet_config::config::guard::{AccessLog, AccessLogFormat};
+use rivet_guard_core::{AccessLogEntry, AccessLogFn};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::metrics;
+
+/// Lines buffered for the writer before new lines are dropped.
+const CHANNEL_CAPACITY: usize = 8192;
+/// Max lines written between flushes.
+const WRITE_BATCH_SIZE: usize = 1024;
+/// Prefix of the access log file names.
+const FILE_PREFIX: &str = "access";
+
+const REDACTED: &str = "[redacted]";
+/// Query params that carry credentials.
+const CREDENTIAL_QUERY_PARAMS: &[&str] = &["rvt-token"];
+
+/// Creates the access log function if access logs are enabled. Lines are written to the rotating
+/// file by a background task so requests never wait on disk.
+pub fn create_access_log_function(config: &rivet_config::Config) -> Option<AccessLogFn> {
+	let access_log = config.guard().access_log.clone()?;
+
+	let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+	tokio::spawn(write_loop(access_log.clone(), rx));
+
+	let format = access_log.format();
+	let sample_rate = access_log.sample_rate();
+	let redact_headers = access_log
+		.redact_headers()
+		.into_iter()
+		.map(|header| header.to_ascii_lowercase())
+		.collect::<HashSet<_>>();
+
+	Some(Arc::new(move |entry: AccessLogEntry| {
+		// Server errors are always logged
+		if entry.status < 500 && rand::random::<f64>() >= sample_rate {
+			return;
+		}
+
+		let line = match format {
+			AccessLogFormat::Json => format_json(&entry, &redact_headers),
+			AccessLogFormat::Common => format_common(&entry),
+		};
+
+		if tx.try_send(line).is_err() {
+			metrics::ACCESS_LOG_DROPPED_TOTAL.inc();
+		}
+	}))
+}
+
+async fn write_loop(access_log: AccessLog, mut rx: mpsc::Receiver<String>) {
+	let mut file =
+		rivet_logs::RotatingFile::new(access_log.path.clone(), FILE_PREFIX, access_log.retention());
+	let mut lines = Vec::with_capacity(WRITE_BATCH_SIZE);
+
+	while rx.recv_many(&mut lines, WRITE_BATCH_SIZE).await > 0 {
+		for line in lines.drain(..) {
+			if let Err(err) = file.write_all(line.as_bytes()).await {
+				tracing::warn!(?err, "failed to write access log");
+				metrics::ACCESS_LOG_DROPPED_TOTAL.inc();
+			}
+		}
+
+		if let Err(err) = file.flush().await {
+			tracing::warn!(?err, "failed to flush access log");
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+	ts: i64,
+	ray_id: Id,
+	req_id: Id,
+	client_ip: IpAddr,
+	method: &'a str,
+	host: &'a str,
+	path: &'a str,
+	protocol: &'static str,
+	status: u16,
+	bytes_in: u64,
+	bytes_out: u64,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	route: Option<&'static str>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	namespace_id: Option<Id>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	actor_id: Option<Id>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	upstream: Option<&'a str>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	route_duration_ms: Option<f64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	wake_duration_ms: Option<f64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	upstream_duration_ms: Option<f64>,
+	duration_ms: f64,
+	headers: BTreeMap<&'a str, String>,
+}
+
+/// Formats an entry as a JSON line. Values of headers in `redact_headers` (lowercase) are
+/// replaced.
+pub fn format_json(entry: &AccessLogEntry, redact_headers: &HashSet<String>) -> String {
+	let mut headers = BTreeMap::<&str, String>::new();
+	for (name, value) in &entry.headers {
+		let value = if redact_headers.contains(name.as_str()) {
+			REDACTED
+		} else {
+			value.to_str().unwrap_or("[invalid]")
+		};
+
+		// Repeated headers are joined like they would be when folded
+		headers
+			.entry(name.as_str())
+			.and_modify(|existing| {
+				existing.push_str(", ");
+				existing.push_str(value);
+			})
+			.or_insert_with(|| value.to_string());
+	}
+
+	let line = JsonLine {
+		ts: entry.ts,
+		ray_id: entry.ray_id,
+		req_id: entry.req_id,
+		client_ip: entry.client_ip,
+		method: entry.method.as_str(),
+		host: &entry.host,
+		path: &redact_path(&entry.path),
+		protocol: if entry.is_websocket {
+			"websocket"
+		} else {
+			"http"
+		},
+		status: entry.status,
+		bytes_in: entry.bytes_in,
+		bytes_out: entry.bytes_out,
+		route: entry.fields.route_kind,
+		namespace_id: entry.fields.namespace_id,
+		actor_id: entry.fields.actor_id,
+		upstream: entry.fields.upstream.as_deref(),
+		route_duration_ms: entry.route_duration.map(as_ms),
+		wake_duration_ms: entry.fields.wake_duration.map(as_ms),
+		upstream_duration_ms: entry.upstream_duration.map(as_ms),
+		duration_ms: as_ms(entry.duration),
+		headers,
+	};
+
+	let mut line = serde_json::to_string(&line).expect("failed to serialize access log line");
+	line.push('\n');
+	line
+}
+
+/// Formats an entry in the Common Log Format.
+pub fn format_common(entry: &AccessLogEntry) -> String {
+	let time = Utc
+		.timestamp_millis_opt(entry.ts)
+		.single()
+		.unwrap_or_default()
+		.format("%d/%b/%Y:%H:%M:%S %z");
+	let bytes_out = if entry.bytes_out == 0 {
+		"-".to_string()
+	} else {
+		entry.bytes_out.to_string()
+	};
+
+	format!(
+		"{} - - [{time}] \"{} {} {:?}\" {} {bytes_out}\n",
+		entry.client_ip,
+		entry.method,
+		redact_path(&entry.path),
+		entry.version,
+		entry.status,
+	)
+}
+
+/// Replaces actor tokens in a request path: the `{token}` of `/gateway/{actor_id}@{token}` and the
+/// values of credential query params.
+fn redact_path(path: &str) -> Cow<'_, str> {
+	let (base, query) = match path.split_once('?') {
+		Some((base, query)) => (base, Some(query)),
+		None => (path, None),
+	};
+
+	let mut redacted = false;
+	let base = match base.strip_prefix("/gateway/") {
+		Some(rest) => {
+			let (actor_segment, tail) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+			match actor_segment.split_once('@') {
+				Some((actor_id, _token)) => {
+					redacted = true;
+					Cow::Owned(format!("/gateway/{actor_id}@{REDACTED}{tail}"))
+				}
+				None => Cow::Borrowed(base),
+			}
+		}
+		None => Cow::Borrowed(base),
+	};
+
+	let query = query.map(|query| {
+		query
+			.split('&')
+			.map(|pair| {
+				let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+				if CREDENTIAL_QUERY_PARAMS.contains(&key) {
+					redacted = true;
+					Cow::Owned(format!("{key}={REDACTED}"))
+				} else {
+					Cow::Borrowed(pair)
+				}
+			})
+			.collect::<Vec<_>>()
+			.join("&")
+	});
+
+	if !redacted {
+		return Cow::Borrowed(path);
+	}
+	match query {
+		Some(query) => Cow::Owned(format!("{base}?{query}")),
+		None => Cow::Owned(base.into_owned()),
+	}
+}
+
+fn as_ms(duration: Duration) -> f64 {
+	duration.as_secs_f64() * 1000.0
+}
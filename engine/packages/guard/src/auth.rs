@@ -0,0 +1,521 @@
+use std::{
+	collections::HashMap,
+	sync::{LazyLock, Mutex, PoisonError},
+	time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use gas::prelude::*;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use ring::{hmac, signature};
+use rivet_guard_core::errors::Unauthorized;
+use rivet_types::namespaces::{ApiKey, AuthMethod, Jwk, Jwks};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::metrics;
+
+/// Prefix of the headers guard forwards the verified identity in. Headers with this prefix are
+/// removed from every request to an actor, so actors can trust them.
+pub const AUTH_HEADER_PREFIX: &str = "x-rivet-auth-";
+pub const X_RIVET_AUTH_RULE: HeaderName = HeaderName::from_static("x-rivet-auth-rule");
+pub const X_RIVET_AUTH_METHOD: HeaderName = HeaderName::from_static("x-rivet-auth-method");
+pub const X_RIVET_AUTH_SUBJECT: HeaderName = HeaderName::from_static("x-rivet-auth-subject");
+/// Unpadded base64url JSON of the verified JWT claims.
+pub const X_RIVET_AUTH_CLAIMS: HeaderName = HeaderName::from_static("x-rivet-auth-claims");
+
+/// How long fetched JWKS are cached. Tokens signed with a key that is not in the cached JWKS
+/// refetch it sooner, see [`JWKS_REFETCH_INTERVAL`].
+const JWKS_CACHE_TTL_MS: i64 = 5 * 60 * 1000;
+/// Minimum time between refetches of a JWKS caused by tokens with an unknown `kid`, so tokens
+/// with made up key ids cannot make guard hammer the issuer.
+const JWKS_REFETCH_INTERVAL: Duration = Duration::from_secs(30);
+/// Clock skew allowed when checking `exp` and `nbf`.
+const JWT_LEEWAY_SECS: i64 = 30;
+
+/// Last refetch of each JWKS URL on this node.
+static JWKS_REFETCHES: LazyLock<Mutex<HashMap<String, Instant>>> =
+	LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The parts of a request to an actor that auth rules check.
+pub struct AuthRequest<'a> {
+	pub namespace_id: Id,
+	pub actor_name: Option<&'a str>,
+	/// Serialized key of the actor, if it has one.
+	pub actor_key: Option<&'a str>,
+	/// HTTP method of the request. Websockets connect with `GET`.
+	pub method: &'a str,
+	/// Gateway token, or the bearer token for HTTP requests without one.
+	pub token: Option<&'a str>,
+	/// Path and query the actor receives.
+	pub path: &'a str,
+}
+
+/// Identity of a request accepted by an auth rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthIdentity {
+	pub rule: String,
+	pub method: &'static str,
+	pub subject: Option<String>,
+	pub claims: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Checks a request against the auth policy of its namespace. Returns `None` if no rule applies
+/// to the request and fails with `guard.unauthorized` if no applicable rule accepts it.
+#[tracing::instrument(skip_all, fields(namespace_id=%req.namespace_id))]
+pub async fn authenticate(
+	ctx: &StandaloneCtx,
+	req: AuthRequest<'_>,
+) -> Result<Option<AuthIdentity>> {
+	let policy = ctx
+		.op(namespace::ops::get_policies_local::Input {
+			namespace_id: req.namespace_id,
+		})
+		.await?
+		.auth;
+
+	let rules = policy
+		.rules
+		.iter()
+		.filter(|rule| {
+			rule.actor_name
+				.as_deref()
+				.is_none_or(|actor_name| req.actor_name == Some(actor_name))
+		})
+		.collect::<Vec<_>>();
+	if rules.is_empty() {
+		return Ok(None);
+	}
+
+	let namespace_id = req.namespace_id.to_string();
+	let Some(token) = req.token else {
+		metrics::AUTH_TOTAL
+			.with_label_values(&[namespace_id.as_str(), "", "missing"])
+			.inc();
+		return Err(Unauthorized {
+			reason: "missing credentials".to_string(),
+		}
+		.build());
+	};
+
+	let now = util::timestamp::now() / 1000;
+	let mut first_reason = None;
+	for rule in rules {
+		let res = match &rule.method {
+			AuthMethod::Jwt {
+				jwks_url,
+				jwks,
+				issuer,
+				audience,
+			} => {
+				let fetched;
+				let jwks = match (jwks, jwks_url) {
+					(Some(jwks), _) => jwks,
+					(None, Some(jwks_url)) => match fetch_jwks(ctx, jwks_url, token).await {
+						Ok(jwks) => {
+							fetched = jwks;
+							&fetched
+						}
+						// An unreachable issuer only rejects this rule, other rules can still
+						// accept the request
+						Err(err) => {
+							tracing::warn!(?err, rule=%rule.name, "failed to fetch jwks");
+							first_reason.get_or_insert_with(|| "failed to fetch jwks".to_string());
+							continue;
+						}
+					},
+					(None, None) => continue,
+				};
+
+				verify_jwt(token, jwks, issuer.as_deref(), audience.as_deref(), now).map(|claims| {
+					AuthIdentity {
+						rule: rule.name.clone(),
+						method: "jwt",
+						subject: claims
+							.get("sub")
+							.and_then(|sub| sub.as_str())
+							.map(ToString::to_string),
+						claims: Some(claims),
+					}
+				})
+			}
+			AuthMethod::ApiKey { keys } => verify_api_key(token, keys).map(|key| AuthIdentity {
+				rule: rule.name.clone(),
+				method: "api_key",
+				subject: Some(key.name.clone()),
+				claims: None,
+			}),
+			AuthMethod::SignedUrl {
+				secret: Some(secret),
+			} => verify_signed_url_token(
+				token,
+				secret,
+				req.method,
+				req.actor_name.unwrap_or_default(),
+				req.actor_key.unwrap_or_default(),
+				req.path,
+				now,
+			)
+			.map(|_| AuthIdentity {
+				rule: rule.name.clone(),
+				method: "signed_url",
+				subject: None,
+				claims: None,
+			}),
+			AuthMethod::SignedUrl { secret: None } => continue,
+		};
+
+		match res {
+			Ok(identity) => {
+				metrics::AUTH_TOTAL
+					.with_label_values(&[namespace_id.as_str(), rule.name.as_str(), "accepted"])
+					.inc();
+				return Ok(Some(identity));
+			}
+			Err(reason) => {
+				first_reason.get_or_insert(reason);
+			}
+		}
+	}
+
+	metrics::AUTH_TOTAL
+		.with_label_values(&[namespace_id.as_str(), "", "rejected"])
+		.inc();
+
+	Err(Unauthorized {
+		reason: first_reason.unwrap_or_else(|| "no rule accepted the credentials".to_string()),
+	}
+	.build())
+}
+
+/// Removes client-sent auth headers and sets the headers of the verified identity, if any.
+pub fn set_auth_headers(headers: &mut HeaderMap, identity: Option<&AuthIdentity>) -> Result<()> {
+	let client_headers = headers
+		.keys()
+		.filter(|name| name.as_str().starts_with(AUTH_HEADER_PREFIX))
+		.cloned()
+		.collect::<Vec<_>>();
+	for name in client_headers {
+		headers.remove(name);
+	}
+
+	let Some(identity) = identity else {
+		return Ok(());
+	};
+
+	headers.insert(X_RIVET_AUTH_RULE, HeaderValue::from_str(&identity.rule)?);
+	headers.insert(
+		X_RIVET_AUTH_METHOD,
+		HeaderValue::from_static(identity.method),
+	);
+	// Subjects are arbitrary strings, skip ones that are not valid header values
+	if let Some(subject) = identity
+		.subject
+		.as_deref()
+		.and_then(|subject| HeaderValue::from_str(subject).ok())
+	{
+		headers.insert(X_RIVET_AUTH_SUBJECT, subject);
+	}
+	if let Some(claims) = &identity.claims {
+		let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?);
+		headers.insert(X_RIVET_AUTH_CLAIMS, HeaderValue::from_str(&claims)?);
+	}
+
+	Ok(())
+}
+
+/// Reads the bearer token of an `Authorization` header.
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+	headers
+		.get(hyper::header::AUTHORIZATION)?
+		.to_str()
+		.ok()?
+		.strip_prefix("Bearer ")
+		.map(str::trim)
+}
+
+/// Verifies a compact JWT against `jwks` and returns its claims. Requires an `exp` claim.
+pub fn verify_jwt(
+	token: &str,
+	jwks: &Jwks,
+	issuer: Option<&str>,
+	audience: Option<&str>,
+	now: i64,
+) -> std::result::Result<serde_json::Map<String, serde_json::Value>, String> {
+	let invalid = || "invalid jwt".to_string();
+
+	let mut parts = token.split('.');
+	let (Some(header_b64), Some(claims_b64), Some(signature_b64), None) =
+		(parts.next(), parts.next(), parts.next(), parts.next())
+	else {
+		return Err(invalid());
+	};
+
+	let header = URL_SAFE_NO_PAD
+		.decode(header_b64)
+		.ok()
+		.and_then(|x| serde_json::from_slice::<serde_json::Value>(&x).ok())
+		.ok_or_else(invalid)?;
+	let signature = URL_SAFE_NO_PAD
+		.decode(signature_b64)
+		.map_err(|_| invalid())?;
+	let alg = header
+		.get("alg")
+		.and_then(|alg| alg.as_str())
+		.ok_or_else(invalid)?;
+	let kid = header.get("kid").and_then(|kid| kid.as_str());
+
+	let message = &token.as_bytes()[..header_b64.len() + 1 + claims_b64.len()];
+	let verified = jwks
+		.keys
+		.iter()
+		.filter(|jwk| kid.is_none() || jwk.kid.as_deref() == kid)
+		.filter(|jwk| jwk.alg.as_deref().is_none_or(|jwk_alg| jwk_alg == alg))
+		.any(|jwk| verify_jwk_signature(jwk, alg, message, &signature));
+	if !verified {
+		return Err("jwt signature does not match any key".to_string());
+	}
+
+	let claims = URL_SAFE_NO_PAD
+		.decode(claims_b64)
+		.ok()
+		.and_then(|x| serde_json::from_slice::<serde_json::Map<_, _>>(&x).ok())
+		.ok_or_else(invalid)?;
+
+	let exp = claims
+		.get("exp")
+		.and_then(|exp| exp.as_i64())
+		.ok_or_else(|| "jwt is missing exp claim".to_string())?;
+	if exp + JWT_LEEWAY_SECS < now {
+		return Err("jwt expired".to_string());
+	}
+	if let Some(nbf) = claims.get("nbf").and_then(|nbf| nbf.as_i64())
+		&& nbf - JWT_LEEWAY_SECS > now
+	{
+		return Err("jwt is not valid yet".to_string());
+	}
+	if let Some(issuer) = issuer
+		&& claims.get("iss").and_then(|iss| iss.as_str()) != Some(issuer)
+	{
+		return Err("jwt has wrong issuer".to_string());
+	}
+	if let Some(audience) = audience {
+		let matches = match claims.get("aud") {
+			Some(serde_json::Value::String(aud)) => aud == audience,
+			Some(serde_json::Value::Array(auds)) => {
+				auds.iter().any(|aud| aud.as_str() == Some(audience))
+			}
+			_ => false,
+		};
+		if !matches {
+			return Err("jwt has wrong audience".to_string());
+		}
+	}
+
+	Ok(claims)
+}
+
+fn verify_jwk_signature(jwk: &Jwk, alg: &str, message: &[u8], sig: &[u8]) -> bool {
+	let decode = |x: &Option<String>| x.as_deref().and_then(|x| URL_SAFE_NO_PAD.decode(x).ok());
+
+	match (alg, jwk.kty.as_str(), jwk.crv.as_deref()) {
+		("RS256", "RSA", _) => {
+			let (Some(n), Some(e)) = (decode(&jwk.n), decode(&jwk.e)) else {
+				return false;
+			};
+			signature::RsaPublicKeyComponents { n, e }
+				.verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, sig)
+				.is_ok()
+		}
+		("ES256", "EC", Some("P-256")) => {
+			let (Some(x), Some(y)) = (decode(&jwk.x), decode(&jwk.y)) else {
+				return false;
+			};
+			// Uncompressed SEC1 point
+			let mut point = vec![0x04];
+			point.extend(x);
+			point.extend(y);
+			signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, point)
+				.verify(message, sig)
+				.is_ok()
+		}
+		("EdDSA", "OKP", Some("Ed25519")) => {
+			let Some(x) = decode(&jwk.x) else {
+				return false;
+			};
+			signature::UnparsedPublicKey::new(&signature::ED25519, x)
+				.verify(message, sig)
+				.is_ok()
+		}
+		_ => false,
+	}
+}
+
+/// Returns the key whose hash matches `token`.
+pub fn verify_api_key<'a>(
+	token: &str,
+	keys: &'a [ApiKey],
+) -> std::result::Result<&'a ApiKey, String> {
+	let hash = Sha256::digest(token.as_bytes());
+
+	keys.iter()
+		.find(|key| {
+			hex::decode(&key.sha256)
+				.is_ok_and(|key_hash| bool::from(key_hash.ct_eq(hash.as_slice())))
+		})
+		.ok_or_else(|| "unknown api key".to_string())
+}
+
+/// Builds a signed URL token for a `method` request to `path` of the actor named `actor_name`
+/// with the serialized key `actor_key`, valid until `expires` (unix seconds).
+pub fn sign_url_token(
+	secret: &str,
+	expires: i64,
+	method: &str,
+	actor_name: &str,
+	actor_key: &str,
+	path: &str,
+) -> String {
+	let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+	let tag = hmac::sign(
+		&key,
+		signed_url_message(expires, method, actor_name, actor_key, path).as_bytes(),
+	);
+
+	format!("{expires}.{}", URL_SAFE_NO_PAD.encode(tag.as_ref()))
+}
+
+pub fn verify_signed_url_token(
+	token: &str,
+	secret: &str,
+	method: &str,
+	actor_name: &str,
+	actor_key: &str,
+	path: &str,
+	now: i64,
+) -> std::result::Result<(), String> {
+	let invalid = || "invalid signed url token".to_string();
+
+	let (expires, signature) = token.split_once('.').ok_or_else(invalid)?;
+	let expires = expires.parse::<i64>().map_err(|_| invalid())?;
+	let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+
+	let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+	hmac::verify(
+		&key,
+		signed_url_message(expires, method, actor_name, actor_key, path).as_bytes(),
+		&signature,
+	)
+	.map_err(|_| invalid())?;
+
+	if expires < now {
+		return Err("signed url expired".to_string());
+	}
+
+	Ok(())
+}
+
+fn signed_url_message(
+	expires: i64,
+	method: &str,
+	actor_name: &str,
+	actor_key: &str,
+	path: &str,
+) -> String {
+	format!("{expires}\n{method}\n{actor_name}\n{actor_key}\n{path}")
+}
+
+/// Fetches the JWKS to verify `token` with. A cached JWKS without the token's `kid` is refetched,
+/// so keys the issuer rotated in are accepted before the cache expires.
+async fn fetch_jwks(ctx: &StandaloneCtx, jwks_url: &str, token: &str) -> Result<Jwks> {
+	let jwks = fetch_cached_jwks(ctx, jwks_url).await?;
+
+	let Some(kid) = jwt_kid(token) else {
+		return Ok(jwks);
+	};
+	if jwks
+		.keys
+		.iter()
+		.any(|jwk| jwk.kid.as_deref() == Some(kid.as_str()))
+	{
+		return Ok(jwks);
+	}
+
+	{
+		let mut refetches = JWKS_REFETCHES
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner);
+		let now = Instant::now();
+		refetches.retain(|_, last| now.duration_since(*last) < JWKS_REFETCH_INTERVAL);
+		if refetches.contains_key(jwks_url) {
+			return Ok(jwks);
+		}
+		refetches.insert(jwks_url.to_string(), now);
+	}
+
+	// Unknown kids are client controlled, so only this node refetches
+	tracing::debug!(%jwks_url, %kid, "refetching jwks for unknown kid");
+	ctx.cache()
+		.clone()
+		.request()
+		.local()
+		.purge("guard.jwks", [jwks_url.to_string()])
+		.await?;
+
+	fetch_cached_jwks(ctx, jwks_url).await
+}
+
+/// Reads the `kid` of a JWT's header without verifying the token.
+fn jwt_kid(token: &str) -> Option<String> {
+	let header = URL_SAFE_NO_PAD.decode(token.split('.').next()?).ok()?;
+	let header = serde_json::from_slice::<serde_json::Value>(&header).ok()?;
+
+	header.get("kid")?.as_str().map(ToString::to_string)
+}
+
+/// Fetches a JWKS, cached on this node. JWKS URLs are set by users, so they are only fetched
+/// from public https hosts unless `guard.allow_private_jwks_urls` is enabled.
+async fn fetch_cached_jwks(ctx: &StandaloneCtx, jwks_url: &str) -> Result<Jwks> {
+	let allow_private = ctx.config().guard().allow_private_jwks_urls();
+
+	let jwks = ctx
+		.cache()
+		.clone()
+		.request()
+		.local()
+		.ttl(JWKS_CACHE_TTL_MS)
+		.fetch_one_json(
+			"guard.jwks",
+			jwks_url.to_string(),
+			|mut cache, jwks_url| async move {
+				let (url, client) = if allow_private {
+					(
+						url::Url::parse(&jwks_url).context("invalid jwks url")?,
+						rivet_pools::reqwest::client().await?,
+					)
+				} else {
+					(
+						rivet_pools::reqwest::check_public_url(&jwks_url)
+							.map_err(|reason| anyhow::anyhow!("invalid jwks url: {reason}"))?,
+						rivet_pools::reqwest::client_public().await?,
+					)
+				};
+				let jwks = client
+					.get(url)
+					.send()
+					.await?
+					.error_for_status()?
+					.json::<Jwks>()
+					.await
+					.context("invalid jwks")?;
+
+				cache.resolve(&jwks_url, jwks);
+
+				Ok(cache)
+			},
+		)
+		.await?;
+
+	jwks.context("jwks not found")
+}
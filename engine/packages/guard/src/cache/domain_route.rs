@@ -0,0 +1,48 @@
+use anyhow::Result;
+use gas::prelude::*;
+use namespace::keys::domain_route::ResolvedDomainRoute;
+use universaldb::utils::IsolationLevel::*;
+
+/// How long guard nodes keep serving domain routes after they change. Hostnames without routes
+/// are cached for as long so unknown hosts do not read the database on every request.
+const CACHE_TTL_MS: i64 = 10_000;
+
+/// Reads the domain routes of `hostname` in this datacenter. Cached since it is read on every
+/// request to a hostname that is not served by Rivet itself.
+#[tracing::instrument(skip_all, fields(%hostname))]
+pub async fn resolve(ctx: &StandaloneCtx, hostname: &str) -> Result<Vec<ResolvedDomainRoute>> {
+	let routes = ctx
+		.cache()
+		.clone()
+		.request()
+		.ttl(CACHE_TTL_MS)
+		.negative_ttl(CACHE_TTL_MS)
+		.fetch_one_json(
+			"guard.domain_route",
+			hostname.to_string(),
+			move |mut cache, hostname| async move {
+				let routes = ctx
+					.udb()?
+					.txn("guard_resolve_domain_route", |tx| {
+						let hostname = hostname.clone();
+						async move {
+							let tx = tx.with_subspace(namespace::keys::subspace());
+							namespace::keys::domain_route::resolve(&tx, &hostname, Snapshot).await
+						}
+					})
+					.custom_instrument(tracing::info_span!("guard_resolve_domain_route_tx"))
+					.await?;
+
+				// Unknown hosts come from the client's Host header, they are only cached as misses
+				// on this node instead of in the shared tier
+				if !routes.is_empty() {
+					cache.resolve(&hostname, routes);
+				}
+
+				Ok(cache)
+			},
+		)
+		.await?;
+
+	Ok(routes.unwrap_or_default())
+}
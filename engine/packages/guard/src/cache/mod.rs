@@ -7,6 +7,7 @@ use std::{
 use gas::prelude::*;
 use rivet_guard_core::{CacheKeyFn, request_context::RequestContext};
 
+pub mod domain_route;
 pub mod pegboard_gateway;
 
 use crate::routing::{SEC_WEBSOCKET_PROTOCOL, WS_PROTOCOL_TARGET, X_RIVET_TARGET, actor_path};
@@ -0,0 +1,73 @@
+use std::{collections::HashMap, net::IpAddr};
+
+use anyhow::{Context, Result};
+use gas::prelude::*;
+use util::geo::{Coordinates, GeoIpDatabase};
+
+/// Locates clients and datacenters to pick the region closest to a client.
+pub struct GeoLocator {
+	db: GeoIpDatabase,
+	/// Keyed by datacenter label.
+	datacenters: HashMap<u16, Coordinates>,
+}
+
+impl GeoLocator {
+	pub fn new(db: GeoIpDatabase, datacenters: HashMap<u16, Coordinates>) -> Self {
+		GeoLocator { db, datacenters }
+	}
+
+	/// Loads the GeoIP database configured in `guard.geo`. Returns `None` if geo is not
+	/// configured.
+	pub fn from_config(config: &rivet_config::Config) -> Result<Option<Self>> {
+		let Some(geo) = &config.guard().geo else {
+			return Ok(None);
+		};
+
+		let db = GeoIpDatabase::open(&geo.geoip_database_path)?;
+		tracing::info!(networks=%db.len(), "loaded geoip database");
+
+		let datacenters = geo
+			.datacenters
+			.iter()
+			.map(|(name, location)| {
+				let dc = config.dc_for_name(name).with_context(|| {
+					format!("guard.geo.datacenters references unknown datacenter {name:?}")
+				})?;
+				Ok((
+					dc.datacenter_label,
+					Coordinates::new(location.latitude, location.longitude),
+				))
+			})
+			.collect::<Result<_>>()?;
+
+		Ok(Some(GeoLocator::new(db, datacenters)))
+	}
+
+	/// Returns the datacenter out of `dc_labels` closest to `client_ip`. Returns `None` if the
+	/// client cannot be located or none of the datacenters has a location.
+	pub fn nearest(&self, client_ip: IpAddr, dc_labels: &[u16]) -> Option<u16> {
+		let client = self.db.lookup(client_ip)?;
+
+		dc_labels
+			.iter()
+			.filter_map(|dc_label| {
+				let location = self.datacenters.get(dc_label)?;
+				Some((*dc_label, client.distance_km(location)))
+			})
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(dc_label, _)| dc_label)
+	}
+}
+
+/// Picks the datacenter for a follow-first-client region rule: the first client's datacenter while
+/// it still runs the pool, otherwise the datacenter nearest to the current client. Returns `None`
+/// if neither is known.
+pub fn follow_first_client(
+	first_dc_label: Option<u16>,
+	nearest_dc_label: Option<u16>,
+	enabled_dc_labels: &[u16],
+) -> Option<u16> {
+	first_dc_label
+		.filter(|dc_label| enabled_dc_labels.contains(dc_label))
+		.or(nearest_dc_label)
+}
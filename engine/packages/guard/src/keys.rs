@@ -0,0 +1,393 @@
+use anyhow::Result;
+use gas::prelude::Id;
+use serde::{Deserialize, Serialize};
+use universaldb::prelude::*;
+
+use crate::tls::CertificateKind;
+
+const HTTP_01: usize = 0;
+const TLS_ALPN_01: usize = 1;
+
+pub fn subspace() -> universaldb::utils::Subspace {
+	universaldb::utils::Subspace::new(&(RIVET, GUARD))
+}
+
+/// A certificate chain and its private key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificatePem {
+	pub cert_chain_pem: String,
+	pub private_key_pem: String,
+}
+
+/// A certificate issued by an ACME server, shared by every guard node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedCertificate {
+	/// Domains the certificate was ordered for.
+	pub domains: Vec<String>,
+	pub pem: CertificatePem,
+	/// Epoch milliseconds.
+	pub not_after_ts: i64,
+}
+
+/// Credentials of the ACME account registered with a directory.
+#[derive(Debug)]
+pub struct AcmeAccountKey {
+	directory_url: String,
+}
+
+impl AcmeAccountKey {
+	pub fn new(directory_url: String) -> Self {
+		AcmeAccountKey { directory_url }
+	}
+}
+
+impl FormalKey for AcmeAccountKey {
+	type Value = instant_acme::AccountCredentials;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		serde_json::from_slice(raw).map_err(Into::into)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		serde_json::to_vec(&value).map_err(Into::into)
+	}
+}
+
+impl TuplePack for AcmeAccountKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACME_ACCOUNT, &self.directory_url);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for AcmeAccountKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, directory_url)) = <(usize, String)>::unpack(input, tuple_depth)?;
+		let v = AcmeAccountKey { directory_url };
+
+		Ok((input, v))
+	}
+}
+
+#[derive(Debug)]
+pub struct AcmeCertificateKey {
+	kind: CertificateKind,
+}
+
+impl AcmeCertificateKey {
+	pub fn new(kind: CertificateKind) -> Self {
+		AcmeCertificateKey { kind }
+	}
+}
+
+impl FormalKey for AcmeCertificateKey {
+	type Value = IssuedCertificate;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		serde_json::from_slice(raw).map_err(Into::into)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		serde_json::to_vec(&value).map_err(Into::into)
+	}
+}
+
+impl TuplePack for AcmeCertificateKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACME_CERTIFICATE, self.kind as usize);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for AcmeCertificateKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, kind)) = <(usize, usize)>::unpack(input, tuple_depth)?;
+		let kind = CertificateKind::from_repr(kind).ok_or_else(|| {
+			PackError::Message(format!("invalid certificate kind `{kind}` in key").into())
+		})?;
+		let v = AcmeCertificateKey { kind };
+
+		Ok((input, v))
+	}
+}
+
+/// Held by the guard node currently ordering a certificate so other nodes do not place duplicate
+/// orders.
+#[derive(Debug)]
+pub struct AcmeCertificateLeaseKey {
+	kind: CertificateKind,
+}
+
+impl AcmeCertificateLeaseKey {
+	pub fn new(kind: CertificateKind) -> Self {
+		AcmeCertificateLeaseKey { kind }
+	}
+}
+
+impl FormalKey for AcmeCertificateLeaseKey {
+	/// Lease expiration timestamp.
+	type Value = i64;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(i64::from_be_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.to_be_bytes().to_vec())
+	}
+}
+
+impl TuplePack for AcmeCertificateLeaseKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACME_CERTIFICATE, self.kind as usize, LEASE);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for AcmeCertificateLeaseKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, kind, _)) = <(usize, usize, usize)>::unpack(input, tuple_depth)?;
+		let kind = CertificateKind::from_repr(kind).ok_or_else(|| {
+			PackError::Message(format!("invalid certificate kind `{kind}` in key").into())
+		})?;
+		let v = AcmeCertificateLeaseKey { kind };
+
+		Ok((input, v))
+	}
+}
+
+/// Key authorization served at `/.well-known/acme-challenge/{token}`.
+#[derive(Debug)]
+pub struct AcmeHttpChallengeKey {
+	token: String,
+}
+
+impl AcmeHttpChallengeKey {
+	pub fn new(token: String) -> Self {
+		AcmeHttpChallengeKey { token }
+	}
+}
+
+impl FormalKey for AcmeHttpChallengeKey {
+	type Value = String;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		String::from_utf8(raw.to_vec()).map_err(Into::into)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.into_bytes())
+	}
+}
+
+impl TuplePack for AcmeHttpChallengeKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACME_CHALLENGE, HTTP_01, &self.token);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for AcmeHttpChallengeKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, _, token)) = <(usize, usize, String)>::unpack(input, tuple_depth)?;
+		let v = AcmeHttpChallengeKey { token };
+
+		Ok((input, v))
+	}
+}
+
+/// Self-signed certificate served to ACME servers validating a TLS-ALPN-01 challenge for a domain.
+#[derive(Debug)]
+pub struct AcmeTlsAlpnChallengeKey {
+	pub domain: String,
+}
+
+impl AcmeTlsAlpnChallengeKey {
+	pub fn new(domain: String) -> Self {
+		AcmeTlsAlpnChallengeKey { domain }
+	}
+
+	pub fn subspace() -> AcmeTlsAlpnChallengeSubspaceKey {
+		AcmeTlsAlpnChallengeSubspaceKey::new()
+	}
+}
+
+impl FormalKey for AcmeTlsAlpnChallengeKey {
+	type Value = CertificatePem;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		serde_json::from_slice(raw).map_err(Into::into)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		serde_json::to_vec(&value).map_err(Into::into)
+	}
+}
+
+impl TuplePack for AcmeTlsAlpnChallengeKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACME_CHALLENGE, TLS_ALPN_01, &self.domain);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for AcmeTlsAlpnChallengeKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, _, domain)) = <(usize, usize, String)>::unpack(input, tuple_depth)?;
+		let v = AcmeTlsAlpnChallengeKey { domain };
+
+		Ok((input, v))
+	}
+}
+
+pub struct AcmeTlsAlpnChallengeSubspaceKey {}
+
+impl AcmeTlsAlpnChallengeSubspaceKey {
+	pub fn new() -> Self {
+		AcmeTlsAlpnChallengeSubspaceKey {}
+	}
+}
+
+impl TuplePack for AcmeTlsAlpnChallengeSubspaceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACME_CHALLENGE, TLS_ALPN_01);
+		t.pack(w, tuple_depth)
+	}
+}
+
+/// Requests admitted by every guard node in one fixed window of a rate limit rule. Keys sort by
+/// expiration so expired windows can be cleared with a single range.
+#[derive(Debug)]
+pub struct RateLimitWindowKey {
+	expire_ts: i64,
+	namespace_id: Id,
+	rule: String,
+	key: String,
+	window_start_ts: i64,
+}
+
+impl RateLimitWindowKey {
+	pub fn new(
+		namespace_id: Id,
+		rule: String,
+		key: String,
+		window_start_ts: i64,
+		period_ms: i64,
+	) -> Self {
+		RateLimitWindowKey {
+			// Windows are read while they are the current or previous window
+			expire_ts: window_start_ts + period_ms * 2,
+			namespace_id,
+			rule,
+			key,
+			window_start_ts,
+		}
+	}
+}
+
+impl FormalKey for RateLimitWindowKey {
+	/// Request count.
+	type Value = i64;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(i64::from_le_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.to_le_bytes().to_vec())
+	}
+}
+
+impl TuplePack for RateLimitWindowKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (
+			RATE_LIMIT_WINDOW,
+			self.expire_ts,
+			self.namespace_id,
+			&self.rule,
+			&self.key,
+			self.window_start_ts,
+		);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for RateLimitWindowKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, expire_ts, namespace_id, rule, key, window_start_ts)) =
+			<(usize, i64, Id, String, String, i64)>::unpack(input, tuple_depth)?;
+		let v = RateLimitWindowKey {
+			expire_ts,
+			namespace_id,
+			rule,
+			key,
+			window_start_ts,
+		};
+
+		Ok((input, v))
+	}
+}
+
+/// Rate limit windows that expired before `expire_ts`, or all windows when unset.
+pub struct RateLimitWindowSubspaceKey {
+	expire_ts: Option<i64>,
+}
+
+impl RateLimitWindowSubspaceKey {
+	pub fn new() -> Self {
+		RateLimitWindowSubspaceKey { expire_ts: None }
+	}
+
+	pub fn with_expire_ts(expire_ts: i64) -> Self {
+		RateLimitWindowSubspaceKey {
+			expire_ts: Some(expire_ts),
+		}
+	}
+}
+
+impl TuplePack for RateLimitWindowSubspaceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let mut offset = VersionstampOffset::None { size: 0 };
+
+		let t = (RATE_LIMIT_WINDOW,);
+		offset += t.pack(w, tuple_depth)?;
+
+		if let Some(expire_ts) = &self.expire_ts {
+			offset += expire_ts.pack(w, tuple_depth)?;
+		}
+
+		Ok(offset)
+	}
+}
@@ -1,9 +1,14 @@
 use anyhow::*;
 use gas::prelude::*;
 
+pub mod access_log;
+pub mod auth;
 pub mod cache;
 pub mod errors;
+pub mod geo;
+pub mod keys;
 pub mod metrics;
+pub mod rate_limit;
 pub mod routing;
 pub mod shared_state;
 pub mod tls;
@@ -22,13 +27,14 @@ pub async fn start(config: rivet_config::Config, pools: rivet_pools::Pools) -> R
 	)?;
 
 	// Share shared context
-	let shared_state = shared_state::SharedState::new(&config, ctx.ups()?);
+	let shared_state = shared_state::SharedState::new(&config, ctx.ups()?, (*ctx.udb()?).clone())?;
 	shared_state.start().await?;
 
 	// Create handlers
 	let routing_fn = routing::create_routing_function(&ctx, shared_state.clone());
 	let cache_key_fn = cache::create_cache_key_function();
 	let cert_resolver = tls::create_cert_resolver(&ctx).await?;
+	let access_log_fn = access_log::create_access_log_function(&config);
 
 	if let Some(_) = &cert_resolver {
 		tracing::info!("TLS certificate resolver configured");
@@ -38,5 +44,12 @@ pub async fn start(config: rivet_config::Config, pools: rivet_pools::Pools) -> R
 
 	// Start the server
 	tracing::info!("starting proxy server");
-	rivet_guard_core::run_server(config, routing_fn, cache_key_fn, cert_resolver).await
+	rivet_guard_core::run_server(
+		config,
+		routing_fn,
+		cache_key_fn,
+		cert_resolver,
+		access_log_fn,
+	)
+	.await
 }
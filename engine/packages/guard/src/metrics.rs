@@ -95,4 +95,58 @@ lazy_static! {
 			*REGISTRY
 		)
 		.unwrap();
+	pub static ref TLS_CERTIFICATE_RELOAD_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_tls_certificate_reload_total",
+		"Total TLS certificate reloads after the certificate changed.",
+		&["kind", "result"],
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref TLS_CERTIFICATE_EXPIRE_TS: IntGaugeVec = register_int_gauge_vec_with_registry!(
+		"guard_tls_certificate_expire_ts",
+		"Expiration timestamp of the served ACME certificate in milliseconds.",
+		&["kind"],
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref ACME_ORDER_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_acme_order_total",
+		"Total ACME certificate orders placed by this node.",
+		&["kind", "result"],
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref RATE_LIMIT_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_rate_limit_total",
+		"Total requests checked against a rate limit rule.",
+		&["namespace_id", "rule", "route", "result"],
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref RATE_LIMIT_WINDOW_COUNT: IntGauge = register_int_gauge_with_registry!(
+		"guard_rate_limit_window_count",
+		"Number of rate limit windows tracked by this node.",
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref RATE_LIMIT_SYNC_DURATION: Histogram = register_histogram_with_registry!(
+		"guard_rate_limit_sync_duration",
+		"Duration of syncing rate limit windows with UniversalDB.",
+		BUCKETS.to_vec(),
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref AUTH_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"guard_auth_total",
+		"Total requests checked against a namespace auth policy.",
+		&["namespace_id", "rule", "result"],
+		*REGISTRY
+	)
+	.unwrap();
+	pub static ref ACCESS_LOG_DROPPED_TOTAL: IntCounter = register_int_counter_with_registry!(
+		"guard_access_log_dropped_total",
+		"Total access log lines dropped because the writer fell behind or failed.",
+		*REGISTRY
+	)
+	.unwrap();
 }
@@ -0,0 +1,586 @@
+use std::{
+	collections::HashMap,
+	net::IpAddr,
+	sync::{Arc, Mutex, PoisonError},
+	time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use gas::prelude::*;
+use rivet_types::namespaces::{RateLimitKey, RateLimitRoute, RateLimitRule};
+use sha2::{Digest, Sha256};
+use universaldb::{options::MutationType, utils::IsolationLevel::*};
+
+use crate::{keys, metrics};
+
+/// How often request counts are exchanged with the other guard nodes.
+const SYNC_INTERVAL: Duration = Duration::from_millis(500);
+/// Windows synced in a single transaction.
+const SYNC_BATCH_SIZE: usize = 512;
+/// How often windows that can no longer be read are cleared from UniversalDB.
+const GC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The parts of a routed request that rate limit rules match on.
+pub struct RateLimitRequest<'a> {
+	pub namespace_id: Id,
+	pub route: RateLimitRoute,
+	pub actor_name: Option<&'a str>,
+	pub actor_id: Option<Id>,
+	pub token: Option<&'a str>,
+	pub client_ip: IpAddr,
+}
+
+impl RateLimitRequest<'_> {
+	fn matches(&self, rule: &RateLimitRule) -> bool {
+		let route_matches = rule.routes.is_empty() || rule.routes.contains(&self.route);
+		let actor_matches = match &rule.actor_name {
+			Some(actor_name) => self.actor_name == Some(actor_name.as_str()),
+			None => true,
+		};
+
+		route_matches && actor_matches
+	}
+
+	/// The value requests are counted by, or `None` if the rule does not apply to this request.
+	fn key(&self, rule: &RateLimitRule) -> Option<String> {
+		match rule.key {
+			RateLimitKey::Namespace => Some(String::new()),
+			RateLimitKey::ClientIp => Some(self.client_ip.to_string()),
+			RateLimitKey::ActorId => self.actor_id.map(|actor_id| actor_id.to_string()),
+			// Tokens are hashed since keys are stored in UniversalDB. Tokenless requests would
+			// otherwise share one limit, letting anonymous clients starve each other.
+			RateLimitKey::Token => Some(match self.token {
+				Some(token) => hex::encode(Sha256::digest(token.as_bytes())),
+				None => format!("ip:{}", self.client_ip),
+			}),
+		}
+	}
+}
+
+/// Approximates a sliding window by weighting the count of the previous fixed window by how much
+/// of it still overlaps the sliding window.
+///
+/// Counts from other guard nodes arrive on each sync, so traffic spread across nodes can exceed
+/// the limit by up to one sync interval worth of requests.
+#[derive(Debug)]
+pub struct SlidingWindow {
+	limit: u64,
+	period_ms: i64,
+	/// Start of the current fixed window.
+	window_start_ts: i64,
+	/// Requests admitted by every node in the previous window.
+	prev_count: u64,
+	/// Requests admitted by every node in the current window as of the last sync.
+	synced_count: u64,
+	/// Requests admitted by this node in the current window since the last sync.
+	pending: u64,
+	/// Requests admitted by this node in the previous window that have not been synced.
+	pending_prev: u64,
+	last_used: Instant,
+}
+
+impl SlidingWindow {
+	pub fn new(limit: u64, period_ms: i64, now: i64) -> Self {
+		SlidingWindow {
+			limit,
+			period_ms,
+			window_start_ts: now - now.rem_euclid(period_ms),
+			prev_count: 0,
+			synced_count: 0,
+			pending: 0,
+			pending_prev: 0,
+			last_used: Instant::now(),
+		}
+	}
+
+	fn roll(&mut self, now: i64) {
+		let window_start_ts = now - now.rem_euclid(self.period_ms);
+		if window_start_ts == self.window_start_ts {
+			return;
+		}
+
+		if window_start_ts == self.window_start_ts + self.period_ms {
+			self.prev_count = self.synced_count + self.pending;
+			self.pending_prev = self.pending;
+		} else {
+			// Requests older than the previous window no longer count
+			self.prev_count = 0;
+			self.pending_prev = 0;
+		}
+
+		self.window_start_ts = window_start_ts;
+		self.synced_count = 0;
+		self.pending = 0;
+	}
+
+	/// Returns how long to wait in milliseconds before a request would be admitted, or `None` if a
+	/// request can be admitted now.
+	pub fn check(&mut self, now: i64) -> Option<i64> {
+		self.roll(now);
+		self.last_used = Instant::now();
+
+		let period = self.period_ms as f64;
+		let elapsed = (now - self.window_start_ts) as f64;
+		let limit = self.limit as f64;
+		let prev = self.prev_count as f64;
+		let current = (self.synced_count + self.pending) as f64;
+
+		let estimate = prev * (period - elapsed) / period + current;
+		if estimate + 1.0 <= limit {
+			return None;
+		}
+
+		let retry_after = if current + 1.0 > limit {
+			// Wait for the next window, where the current count decays as the previous count
+			(period - elapsed) + period * (1.0 - (limit - 1.0) / current)
+		} else {
+			// Wait for enough of the previous window to slide out
+			(period - elapsed) - (limit - 1.0 - current) * period / prev
+		};
+
+		Some((retry_after.ceil() as i64).max(1))
+	}
+
+	/// Counts an admitted request.
+	pub fn record(&mut self) {
+		self.pending += 1;
+	}
+
+	/// Updates the limit of the window when the rule changed.
+	fn update(&mut self, limit: u64, period_ms: i64, now: i64) {
+		if self.period_ms != period_ms {
+			*self = SlidingWindow::new(limit, period_ms, now);
+		} else {
+			self.limit = limit;
+		}
+	}
+
+	fn take_pending(&mut self, now: i64) -> SyncBatch {
+		self.roll(now);
+
+		let batch = SyncBatch {
+			window_start_ts: self.window_start_ts,
+			period_ms: self.period_ms,
+			pending: self.pending,
+			pending_prev: self.pending_prev,
+		};
+		self.synced_count += self.pending;
+		self.pending = 0;
+		self.pending_prev = 0;
+
+		batch
+	}
+
+	/// Applies the counts read from UniversalDB, which include the requests of `batch`.
+	fn apply_sync(&mut self, batch: &SyncBatch, count: u64, prev_count: u64) {
+		if self.period_ms != batch.period_ms {
+			return;
+		}
+
+		if self.window_start_ts == batch.window_start_ts {
+			self.synced_count = self.synced_count.max(count);
+			self.prev_count = self.prev_count.max(prev_count);
+		} else if self.window_start_ts == batch.window_start_ts + batch.period_ms {
+			// Rolled over while syncing
+			self.prev_count = self.prev_count.max(count + self.pending_prev);
+		}
+	}
+
+	/// Puts back the requests of a failed sync.
+	fn restore(&mut self, batch: SyncBatch) {
+		if self.period_ms != batch.period_ms {
+			return;
+		}
+
+		if self.window_start_ts == batch.window_start_ts {
+			self.synced_count -= batch.pending.min(self.synced_count);
+			self.pending += batch.pending;
+			self.pending_prev += batch.pending_prev;
+		} else if self.window_start_ts == batch.window_start_ts + batch.period_ms {
+			self.pending_prev += batch.pending;
+		}
+	}
+
+	/// Windows that were not used within their period skip syncing. Their counts from other nodes
+	/// are only refreshed by the first sync after they are used again.
+	fn needs_sync(&self) -> bool {
+		self.pending > 0
+			|| self.pending_prev > 0
+			|| self.last_used.elapsed() <= Duration::from_millis(self.period_ms as u64)
+	}
+
+	fn is_idle(&self) -> bool {
+		self.pending == 0
+			&& self.pending_prev == 0
+			&& self.last_used.elapsed() > Duration::from_millis(self.period_ms as u64 * 2)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct WindowKey {
+	namespace_id: Id,
+	rule: String,
+	key: String,
+}
+
+struct SyncBatch {
+	window_start_ts: i64,
+	period_ms: i64,
+	pending: u64,
+	pending_prev: u64,
+}
+
+/// Enforces the rate limit policies of namespaces. Every guard node counts requests locally and
+/// adds its counts to shared windows in UniversalDB every sync interval.
+#[derive(Clone)]
+pub struct RateLimiter(Arc<RateLimiterInner>);
+
+struct RateLimiterInner {
+	udb: universaldb::Database,
+	windows: Mutex<HashMap<WindowKey, SlidingWindow>>,
+}
+
+impl RateLimiter {
+	pub fn new(udb: universaldb::Database) -> Self {
+		RateLimiter(Arc::new(RateLimiterInner {
+			udb,
+			windows: Mutex::new(HashMap::new()),
+		}))
+	}
+
+	pub fn start(&self) {
+		let limiter = self.clone();
+		tokio::spawn(async move {
+			let mut sync_interval = tokio::time::interval(SYNC_INTERVAL);
+			sync_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+			let mut gc_interval = tokio::time::interval(GC_INTERVAL);
+			gc_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+			loop {
+				tokio::select! {
+					_ = sync_interval.tick() => {
+						if let Err(err) = limiter.sync().await {
+							tracing::warn!(?err, "failed to sync rate limit windows");
+						}
+					}
+					_ = gc_interval.tick() => {
+						if let Err(err) = limiter.gc().await {
+							tracing::warn!(?err, "failed to clear expired rate limit windows");
+						}
+					}
+				}
+			}
+		});
+	}
+
+	/// Counts a request against the namespace's rate limit policy. Fails with
+	/// `guard.rate_limit_exceeded` if any matching rule is over its limit, in which case the
+	/// request is not counted.
+	#[tracing::instrument(skip_all, fields(namespace_id=%req.namespace_id))]
+	pub async fn check(&self, ctx: &StandaloneCtx, req: RateLimitRequest<'_>) -> Result<()> {
+		let policy = ctx
+			.op(namespace::ops::get_policies_local::Input {
+				namespace_id: req.namespace_id,
+			})
+			.await?
+			.rate_limit;
+		if policy.rules.is_empty() {
+			return Ok(());
+		}
+
+		let rules = policy
+			.rules
+			.iter()
+			.filter(|rule| req.matches(rule))
+			.filter_map(|rule| {
+				let key = req.key(rule)?;
+				Some((
+					rule,
+					WindowKey {
+						namespace_id: req.namespace_id,
+						rule: rule.name.clone(),
+						key,
+					},
+				))
+			})
+			.collect::<Vec<_>>();
+		if rules.is_empty() {
+			return Ok(());
+		}
+
+		let now = util::timestamp::now();
+		let namespace_id = req.namespace_id.to_string();
+		let route = route_label(req.route);
+
+		let denied = {
+			let mut windows = self
+				.0
+				.windows
+				.lock()
+				.unwrap_or_else(PoisonError::into_inner);
+
+			// Check every rule before counting so a denied request does not use up other limits
+			let mut denied = None;
+			for (rule, key) in &rules {
+				let period_ms = rule.period_ms as i64;
+				let window = windows
+					.entry(key.clone())
+					.or_insert_with(|| SlidingWindow::new(rule.requests, period_ms, now));
+				window.update(rule.requests, period_ms, now);
+
+				if let Some(retry_after_ms) = window.check(now) {
+					if denied.as_ref().is_none_or(|(_, denied_retry_after_ms)| {
+						retry_after_ms > *denied_retry_after_ms
+					}) {
+						denied = Some((rule.name.clone(), retry_after_ms));
+					}
+				}
+			}
+
+			if denied.is_none() {
+				for (_, key) in &rules {
+					if let Some(window) = windows.get_mut(key) {
+						window.record();
+					}
+				}
+			}
+
+			metrics::RATE_LIMIT_WINDOW_COUNT.set(windows.len() as i64);
+
+			denied
+		};
+
+		if let Some((rule, retry_after_ms)) = denied {
+			metrics::RATE_LIMIT_TOTAL
+				.with_label_values(&[namespace_id.as_str(), rule.as_str(), route, "limited"])
+				.inc();
+
+			return Err(rivet_guard_core::errors::RateLimitExceeded {
+				rule,
+				retry_after_secs: (retry_after_ms as u64).div_ceil(1000),
+			}
+			.build());
+		}
+
+		for (rule, _) in &rules {
+			metrics::RATE_LIMIT_TOTAL
+				.with_label_values(&[namespace_id.as_str(), rule.name.as_str(), route, "allowed"])
+				.inc();
+		}
+
+		Ok(())
+	}
+
+	/// Adds the requests counted since the last sync to the shared windows and reads back the
+	/// counts of every node, for windows that are in use.
+	async fn sync(&self) -> Result<()> {
+		let now = util::timestamp::now();
+		let batches = {
+			let mut windows = self
+				.0
+				.windows
+				.lock()
+				.unwrap_or_else(PoisonError::into_inner);
+
+			windows.retain(|_, window| !window.is_idle());
+			metrics::RATE_LIMIT_WINDOW_COUNT.set(windows.len() as i64);
+
+			windows
+				.iter_mut()
+				.filter(|(_, window)| window.needs_sync())
+				.map(|(key, window)| (key.clone(), window.take_pending(now)))
+				.collect::<Vec<_>>()
+		};
+
+		let start = Instant::now();
+		let mut batches = batches.into_iter().peekable();
+		let mut res = Ok(());
+		while batches.peek().is_some() {
+			let chunk = batches.by_ref().take(SYNC_BATCH_SIZE).collect::<Vec<_>>();
+
+			match self.sync_chunk(&chunk).await {
+				Ok(counts) => {
+					let mut windows = self
+						.0
+						.windows
+						.lock()
+						.unwrap_or_else(PoisonError::into_inner);
+					for ((key, batch), (count, prev_count)) in chunk.iter().zip(counts) {
+						if let Some(window) = windows.get_mut(key) {
+							window.apply_sync(batch, count, prev_count);
+						}
+					}
+				}
+				Err(err) => {
+					let mut windows = self
+						.0
+						.windows
+						.lock()
+						.unwrap_or_else(PoisonError::into_inner);
+					for (key, batch) in chunk.into_iter().chain(batches.by_ref()) {
+						if let Some(window) = windows.get_mut(&key) {
+							window.restore(batch);
+						}
+					}
+
+					res = Err(err);
+					break;
+				}
+			}
+		}
+		metrics::RATE_LIMIT_SYNC_DURATION.observe(start.elapsed().as_secs_f64());
+
+		res
+	}
+
+	/// Returns the counts of the current and previous window of each entry.
+	async fn sync_chunk(&self, chunk: &[(WindowKey, SyncBatch)]) -> Result<Vec<(u64, u64)>> {
+		self.0
+			.udb
+			.txn("guard_rate_limit_sync", |tx| async move {
+				let tx = tx.with_subspace(keys::subspace());
+
+				futures_util::future::try_join_all(
+					chunk
+						.iter()
+						.map(|(key, batch)| sync_window(&tx, key, batch)),
+				)
+				.await
+			})
+			.custom_instrument(tracing::info_span!("guard_rate_limit_sync_tx"))
+			.await
+	}
+
+	/// Clears windows that are older than the previous window of their rule.
+	async fn gc(&self) -> Result<()> {
+		let now = util::timestamp::now();
+
+		self.0
+			.udb
+			.txn("guard_rate_limit_gc", |tx| async move {
+				let tx = tx.with_subspace(keys::subspace());
+
+				let begin = tx.pack(&keys::RateLimitWindowSubspaceKey::new());
+				let end = tx.pack(&keys::RateLimitWindowSubspaceKey::with_expire_ts(now));
+				tx.clear_range(&begin, &end);
+
+				Ok(())
+			})
+			.custom_instrument(tracing::info_span!("guard_rate_limit_gc_tx"))
+			.await
+	}
+}
+
+/// Adds the pending requests of a window and returns the counts of its current and previous window.
+async fn sync_window(
+	tx: &universaldb::Transaction,
+	key: &WindowKey,
+	batch: &SyncBatch,
+) -> Result<(u64, u64)> {
+	let current_key = keys::RateLimitWindowKey::new(
+		key.namespace_id,
+		key.rule.clone(),
+		key.key.clone(),
+		batch.window_start_ts,
+		batch.period_ms,
+	);
+	let prev_key = keys::RateLimitWindowKey::new(
+		key.namespace_id,
+		key.rule.clone(),
+		key.key.clone(),
+		batch.window_start_ts - batch.period_ms,
+		batch.period_ms,
+	);
+
+	// Snapshot reads since every node adds to the same keys
+	let (count, prev_count) = tokio::try_join!(
+		tx.read_opt(&current_key, Snapshot),
+		tx.read_opt(&prev_key, Snapshot),
+	)?;
+
+	if batch.pending > 0 {
+		tx.atomic_op(
+			&current_key,
+			&(batch.pending as i64).to_le_bytes(),
+			MutationType::Add,
+		);
+	}
+	if batch.pending_prev > 0 {
+		tx.atomic_op(
+			&prev_key,
+			&(batch.pending_prev as i64).to_le_bytes(),
+			MutationType::Add,
+		);
+	}
+
+	Ok((
+		count.unwrap_or_default().max(0) as u64 + batch.pending,
+		prev_count.unwrap_or_default().max(0) as u64 + batch.pending_prev,
+	))
+}
+
+fn route_label(route: RateLimitRoute) -> &'static str {
+	match route {
+		RateLimitRoute::Http => "http",
+		RateLimitRoute::Websocket => "websocket",
+		RateLimitRoute::Api => "api",
+	}
+}
+
+// Tests are inline because `RateLimitRequest::key` is private.
+#[cfg(test)]
+mod tests {
+	use rivet_types::namespaces::{RateLimitKey, RateLimitRoute, RateLimitRule};
+
+	use super::*;
+
+	fn request(token: Option<&str>, client_ip: &str) -> RateLimitRequest<'_> {
+		RateLimitRequest {
+			namespace_id: Id::new_v1(1),
+			route: RateLimitRoute::Api,
+			actor_name: None,
+			actor_id: None,
+			token,
+			client_ip: client_ip.parse().unwrap(),
+		}
+	}
+
+	#[test]
+	fn tokenless_requests_are_keyed_by_client_ip() {
+		let rule = RateLimitRule {
+			name: "per-token".to_string(),
+			key: RateLimitKey::Token,
+			routes: Vec::new(),
+			actor_name: None,
+			requests: 10,
+			period_ms: 1_000,
+		};
+
+		let a = request(None, "203.0.113.1").key(&rule);
+		let b = request(None, "203.0.113.2").key(&rule);
+		assert_ne!(a, b);
+		assert_eq!(a, request(None, "203.0.113.1").key(&rule));
+
+		// Tokens share a limit across client IPs
+		assert_eq!(
+			request(Some("secret"), "203.0.113.1").key(&rule),
+			request(Some("secret"), "203.0.113.2").key(&rule)
+		);
+		assert_ne!(request(Some("secret"), "203.0.113.1").key(&rule), a);
+	}
+
+	#[test]
+	fn only_used_windows_need_sync() {
+		let mut window = SlidingWindow::new(10, 1_000, 0);
+		assert!(window.needs_sync());
+
+		window.last_used = Instant::now() - Duration::from_millis(1_500);
+		assert!(!window.needs_sync());
+
+		// Unsynced requests are synced even once the window is no longer used
+		window.record();
+		assert!(window.needs_sync());
+		window.take_pending(0);
+		assert!(!window.needs_sync());
+	}
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use gas::prelude::*;
+use http_body_util::Full;
+use hyper::{Request, Response, StatusCode};
+use rivet_config::config::guard::AcmeChallenge;
+use rivet_guard_core::custom_serve::CustomServeTrait;
+use rivet_guard_core::request_context::RequestContext;
+use rivet_guard_core::{ResponseBody, RoutingOutput};
+
+use crate::{errors, tls};
+
+const PATH_PREFIX: &str = "/.well-known/acme-challenge/";
+
+/// Returns the token of an HTTP-01 challenge request, if guard answers HTTP-01 challenges.
+pub fn token_from_path<'a>(config: &rivet_config::Config, path: &'a str) -> Option<&'a str> {
+	let http_01 = config
+		.guard()
+		.https
+		.as_ref()
+		.and_then(|https| https.acme.as_ref())
+		.is_some_and(|acme| matches!(acme.challenge(), AcmeChallenge::Http01));
+	if !http_01 {
+		return None;
+	}
+
+	path.strip_prefix(PATH_PREFIX)
+		.filter(|token| !token.is_empty() && !token.contains('/'))
+}
+
+pub fn route_request(ctx: &StandaloneCtx, token: &str) -> RoutingOutput {
+	RoutingOutput::CustomServe(Arc::new(AcmeChallengeService {
+		ctx: ctx.clone(),
+		token: token.to_string(),
+	}))
+}
+
+/// Answers HTTP-01 challenges with the key authorization written by the node that placed the
+/// order.
+struct AcmeChallengeService {
+	ctx: StandaloneCtx,
+	token: String,
+}
+
+#[async_trait]
+impl CustomServeTrait for AcmeChallengeService {
+	async fn handle_request(
+		&self,
+		_req: Request<Full<Bytes>>,
+		req_ctx: &mut RequestContext,
+	) -> Result<Response<ResponseBody>> {
+		let Some(key_authorization) =
+			tls::acme::read_http_challenge(&*self.ctx.udb()?, &self.token).await?
+		else {
+			return Err(errors::NoRoute {
+				host: req_ctx.hostname().to_string(),
+				path: req_ctx.path().to_string(),
+			}
+			.build());
+		};
+
+		Ok(Response::builder()
+			.status(StatusCode::OK)
+			.header(hyper::header::CONTENT_TYPE, "application/octet-stream")
+			.body(ResponseBody::Full(Full::new(Bytes::from(
+				key_authorization,
+			))))?)
+	}
+}
@@ -45,6 +45,23 @@ pub enum QueryActorQuery {
 }
 
 impl QueryActorQuery {
+	pub fn namespace_and_name(&self) -> (&str, &str) {
+		match self {
+			QueryActorQuery::Get {
+				namespace, name, ..
+			}
+			| QueryActorQuery::GetOrCreate {
+				namespace, name, ..
+			} => (namespace, name),
+		}
+	}
+
+	pub fn key(&self) -> &[String] {
+		match self {
+			QueryActorQuery::Get { key, .. } | QueryActorQuery::GetOrCreate { key, .. } => key,
+		}
+	}
+
 	pub fn skip_ready_wait(&self) -> bool {
 		match self {
 			QueryActorQuery::Get {
@@ -366,7 +383,7 @@ fn decode_query_input(value: &str) -> Result<Vec<u8>> {
 
 /// Split a path into the base path and the raw query string (without `?`).
 /// Fragments are stripped.
-fn split_path_and_query(path: &str) -> (&str, Option<&str>) {
+pub(crate) fn split_path_and_query(path: &str) -> (&str, Option<&str>) {
 	let path = match path.find('#') {
 		Some(pos) => &path[..pos],
 		None => path,
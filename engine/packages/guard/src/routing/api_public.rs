@@ -8,10 +8,11 @@ use http_body_util::{BodyExt, Full};
 use hyper::{Request, Response};
 use rivet_guard_core::request_context::RequestContext;
 use rivet_guard_core::{CustomServeTrait, ResponseBody, RoutingOutput};
+use rivet_types::namespaces::RateLimitRoute;
 use tower::Service;
 
 use super::{Phase, phase_timeout};
-use crate::{errors, metrics};
+use crate::{errors, metrics, rate_limit::RateLimitRequest, shared_state::SharedState};
 
 struct ApiPublicService {
 	router: axum::Router,
@@ -50,12 +51,19 @@ impl CustomServeTrait for ApiPublicService {
 
 /// Route requests to the api-public service
 #[tracing::instrument(skip_all)]
-pub async fn route_request(ctx: &StandaloneCtx, target: &str) -> Result<Option<RoutingOutput>> {
+pub async fn route_request(
+	ctx: &StandaloneCtx,
+	shared_state: &SharedState,
+	req_ctx: &mut RequestContext,
+	target: &str,
+) -> Result<Option<RoutingOutput>> {
 	// Check target
 	if target != "api-public" {
 		return Ok(None);
 	}
 
+	check_rate_limit(ctx, shared_state, req_ctx).await?;
+
 	// Create the router once
 	let router = phase_timeout(
 		Phase::new("route_api_public", &metrics::ROUTE_API_PUBLIC_DURATION),
@@ -75,3 +83,93 @@ pub async fn route_request(ctx: &StandaloneCtx, target: &str) -> Result<Option<R
 
 	return Ok(Some(RoutingOutput::CustomServe(service)));
 }
+
+/// Checks the rate limit policy of the namespace the request targets. Requests without a
+/// namespace are not rate limited.
+async fn check_rate_limit(
+	ctx: &StandaloneCtx,
+	shared_state: &SharedState,
+	req_ctx: &mut RequestContext,
+) -> Result<()> {
+	let Some(namespace_name) = namespace_name(req_ctx.path()) else {
+		return Ok(());
+	};
+
+	let Some(namespace) = ctx
+		.op(namespace::ops::resolve_for_name_global::Input {
+			name: namespace_name,
+		})
+		.await?
+	else {
+		// Let api-public respond with the not found error
+		return Ok(());
+	};
+	req_ctx.access_log_mut().namespace_id = Some(namespace.namespace_id);
+
+	let token = req_ctx
+		.headers()
+		.get(hyper::header::AUTHORIZATION)
+		.and_then(|x| x.to_str().ok())
+		.and_then(|x| x.strip_prefix("Bearer "));
+
+	shared_state
+		.rate_limiter
+		.check(
+			ctx,
+			RateLimitRequest {
+				namespace_id: namespace.namespace_id,
+				route: RateLimitRoute::Api,
+				actor_name: None,
+				actor_id: None,
+				token,
+				client_ip: req_ctx.client_ip(),
+			},
+		)
+		.await
+}
+
+/// The namespace a request targets: the `{namespace}` of `/namespaces/{namespace}/...` paths, or
+/// the `namespace` query parameter.
+fn namespace_name(path: &str) -> Option<String> {
+	let (base, query) = match path.split_once('?') {
+		Some((base, query)) => (base, Some(query)),
+		None => (path, None),
+	};
+
+	let mut segments = base.split('/').filter(|segment| !segment.is_empty());
+	if segments.next() == Some("namespaces")
+		&& let Some(segment) = segments.next()
+	{
+		return urlencoding::decode(segment)
+			.ok()
+			.map(|name| name.into_owned());
+	}
+
+	url::form_urlencoded::parse(query?.as_bytes())
+		.find(|(key, _)| key == "namespace")
+		.map(|(_, value)| value.into_owned())
+}
+
+// Tests are inline because `namespace_name` is private.
+#[cfg(test)]
+mod tests {
+	use super::namespace_name;
+
+	#[test]
+	fn namespace_from_path_or_query() {
+		assert_eq!(
+			namespace_name("/namespaces/prod/rate-limit-policy").as_deref(),
+			Some("prod")
+		);
+		assert_eq!(
+			namespace_name("/namespaces/my%20ns/domains?namespace=other").as_deref(),
+			Some("my ns")
+		);
+		assert_eq!(
+			namespace_name("/actors?namespace=prod&limit=1").as_deref(),
+			Some("prod")
+		);
+		assert_eq!(namespace_name("/namespaces?limit=1"), None);
+		assert_eq!(namespace_name("/actors"), None);
+	}
+}
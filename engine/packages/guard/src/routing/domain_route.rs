@@ -0,0 +1,219 @@
+use anyhow::Result;
+use gas::prelude::*;
+use namespace::{
+	keys::domain_route::ResolvedDomainRoute,
+	utils::{DomainKeyPart, parse_domain_key_template},
+};
+
+use crate::{
+	errors,
+	routing::actor_path::{QueryActorQuery, split_path_and_query},
+};
+
+/// A request matched to a domain route.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DomainRouteMatch {
+	pub namespace_id: Id,
+	pub query: QueryActorQuery,
+	/// Path forwarded to the actor, with the path prefix and the segments used by the key removed.
+	pub stripped_path: String,
+}
+
+/// Returns true if `hostname` is served by Rivet itself and never has domain routes.
+pub fn is_platform_host(config: &rivet_config::Config, hostname: &str) -> bool {
+	if hostname == "localhost" || hostname.parse::<std::net::IpAddr>().is_ok() {
+		return true;
+	}
+
+	let matches = |host: Option<&str>| host.is_some_and(|host| host.eq_ignore_ascii_case(hostname));
+
+	config.topology().datacenters.iter().any(|dc| {
+		matches(dc.public_url.host_str())
+			|| matches(dc.proxy_url().host_str())
+			|| dc
+				.valid_hosts
+				.iter()
+				.flatten()
+				.any(|host| matches(Some(host)))
+	})
+}
+
+/// Picks the route with the longest path prefix matching `path` and builds the actor query for
+/// it. `subdomain` is the label matched by a wildcard hostname. Returns `None` if no route
+/// matches or the path is missing segments used by the key.
+pub fn match_route(
+	routes: &[ResolvedDomainRoute],
+	subdomain: Option<&str>,
+	path: &str,
+	skip_ready_wait: bool,
+) -> Result<Option<DomainRouteMatch>> {
+	let (base_path, query) = split_path_and_query(path);
+
+	let Some((resolved, remaining_path)) = routes
+		.iter()
+		.filter_map(|resolved| {
+			let path_prefix = resolved.route.path_prefix.as_deref().unwrap_or("");
+			let remaining_path = base_path.strip_prefix(path_prefix)?;
+
+			(remaining_path.is_empty() || remaining_path.starts_with('/')).then_some((
+				resolved,
+				path_prefix.len(),
+				remaining_path,
+			))
+		})
+		.max_by_key(|(_, prefix_len, _)| *prefix_len)
+		.map(|(resolved, _, remaining_path)| (resolved, remaining_path))
+	else {
+		return Ok(None);
+	};
+
+	let segments = remaining_path
+		.split('/')
+		.filter(|segment| !segment.is_empty())
+		.collect::<Vec<_>>();
+
+	let mut consumed_segments = 0;
+	let mut key = Vec::with_capacity(resolved.route.actor.key.len());
+	for template in &resolved.route.actor.key {
+		// Templates are validated when the route is written
+		let parts = parse_domain_key_template(template)
+			.map_err(|reason| anyhow!("invalid domain route key: {reason}"))?;
+
+		let mut component = String::new();
+		for part in parts {
+			match part {
+				DomainKeyPart::Literal(literal) => component.push_str(literal),
+				DomainKeyPart::Subdomain => {
+					let Some(subdomain) = subdomain else {
+						return Ok(None);
+					};
+					component.push_str(subdomain);
+				}
+				DomainKeyPart::PathSegment(idx) => {
+					let Some(segment) = segments.get(idx) else {
+						return Ok(None);
+					};
+					let segment = urlencoding::decode(segment).map_err(|_| {
+						errors::QueryInvalidPercentEncoding {
+							name: format!("path.{idx}"),
+						}
+						.build()
+					})?;
+
+					component.push_str(&segment);
+					consumed_segments = consumed_segments.max(idx + 1);
+				}
+			}
+		}
+
+		key.push(component);
+	}
+
+	let mut stripped_path = String::new();
+	for segment in &segments[consumed_segments..] {
+		stripped_path.push('/');
+		stripped_path.push_str(segment);
+	}
+	// Keep the trailing slash of the original path
+	if stripped_path.is_empty()
+		|| (remaining_path.ends_with('/') && consumed_segments < segments.len())
+	{
+		stripped_path.push('/');
+	}
+	if let Some(query) = query {
+		stripped_path.push('?');
+		stripped_path.push_str(query);
+	}
+
+	let actor = &resolved.route.actor;
+	let query = if let Some(pool_name) = &actor.pool_name {
+		QueryActorQuery::GetOrCreate {
+			namespace: resolved.namespace_name.clone(),
+			name: actor.name.clone(),
+			pool_name: pool_name.clone(),
+			key,
+			input: None,
+			region: None,
+			crash_policy: None,
+			skip_ready_wait,
+		}
+	} else {
+		QueryActorQuery::Get {
+			namespace: resolved.namespace_name.clone(),
+			name: actor.name.clone(),
+			key,
+			skip_ready_wait,
+		}
+	};
+
+	Ok(Some(DomainRouteMatch {
+		namespace_id: resolved.namespace_id,
+		query,
+		stripped_path,
+	}))
+}
+
+/// Builds a query gateway path that resolves to the same actor as `query`. Used to forward
+/// domain-routed requests to a peer datacenter, which receives the request on its own hostname.
+pub fn query_gateway_path(query: &QueryActorQuery, stripped_path: &str) -> String {
+	let (base_path, actor_query) = split_path_and_query(stripped_path);
+
+	let mut params = Vec::new();
+	let (name, key) = match query {
+		QueryActorQuery::Get {
+			namespace,
+			name,
+			key,
+			skip_ready_wait,
+		} => {
+			params.push(("rvt-namespace", namespace.clone()));
+			params.push(("rvt-method", "get".to_string()));
+			if *skip_ready_wait {
+				params.push(("rvt-skip-ready-wait", "true".to_string()));
+			}
+			(name, key)
+		}
+		QueryActorQuery::GetOrCreate {
+			namespace,
+			name,
+			pool_name,
+			key,
+			region,
+			skip_ready_wait,
+			..
+		} => {
+			params.push(("rvt-namespace", namespace.clone()));
+			params.push(("rvt-method", "getOrCreate".to_string()));
+			params.push(("rvt-pool", pool_name.clone()));
+			if let Some(region) = region {
+				params.push(("rvt-region", region.clone()));
+			}
+			if *skip_ready_wait {
+				params.push(("rvt-skip-ready-wait", "true".to_string()));
+			}
+			(name, key)
+		}
+	};
+	if !key.is_empty() {
+		params.push(("rvt-key", key.join(",")));
+	}
+
+	let mut path = format!("/gateway/{}", urlencoding::encode(name));
+	if base_path != "/" {
+		path.push_str(base_path);
+	}
+	path.push('?');
+	path.push_str(
+		&params
+			.iter()
+			.map(|(name, value)| format!("{name}={}", urlencoding::encode(value)))
+			.collect::<Vec<_>>()
+			.join("&"),
+	);
+	if let Some(actor_query) = actor_query.filter(|x| !x.is_empty()) {
+		path.push('&');
+		path.push_str(actor_query);
+	}
+
+	path
+}
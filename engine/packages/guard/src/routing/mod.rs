@@ -9,8 +9,10 @@ use rivet_perf::{perf_finish, perf_start};
 
 use crate::{errors, metrics, shared_state::SharedState};
 
+mod acme_challenge;
 pub mod actor_path;
 mod api_public;
+pub mod domain_route;
 mod envoy;
 pub mod pegboard_gateway;
 mod runner;
@@ -128,9 +130,19 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 			async move {
 				tracing::debug!(hostname=%req_ctx.hostname(), path=%req_ctx.path(), "Routing request");
 
+				if let Some(token) = acme_challenge::token_from_path(ctx.config(), req_ctx.path()) {
+					metrics::ROUTE_TOTAL
+						.with_label_values(&["acme_challenge"])
+						.inc();
+					let routing_output = acme_challenge::route_request(&ctx, token);
+					req_ctx.access_log_mut().route_kind = Some("acme_challenge");
+					return Ok(routing_output);
+				}
+
 				if ws_health::matches_path(req_ctx.path()) {
 					if ctx.config().guard().enable_websocket_health_route() {
 						metrics::ROUTE_TOTAL.with_label_values(&["ws_health"]).inc();
+						req_ctx.access_log_mut().route_kind = Some("ws_health");
 						return Ok(ws_health::route_request());
 					}
 
@@ -143,6 +155,23 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					.build());
 				}
 
+				// MARK: Domain-based routing
+
+				// Route custom domains to actors
+				if let Some(routing_output) = phase_timeout(
+					route_dispatch_phase("pegboard_domain"),
+					ctx.config().guard().route_dispatch_timeout(),
+					pegboard_gateway::route_request_domain_based(&ctx, &shared_state, req_ctx),
+					|elapsed, timeout| route_dispatch_timeout("pegboard_domain", elapsed, timeout),
+				)
+				.await?
+				{
+					metrics::ROUTE_TOTAL.with_label_values(&["domain"]).inc();
+					req_ctx.access_log_mut().route_kind = Some("domain");
+
+					return Ok(routing_output);
+				}
+
 				// MARK: Path-based routing
 
 				// Route actor
@@ -155,6 +184,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 				.await?
 				{
 					metrics::ROUTE_TOTAL.with_label_values(&["gateway"]).inc();
+					req_ctx.access_log_mut().route_kind = Some("gateway");
 
 					return Ok(routing_output);
 				}
@@ -169,6 +199,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 				.await?
 				{
 					metrics::ROUTE_TOTAL.with_label_values(&["runner"]).inc();
+					req_ctx.access_log_mut().route_kind = Some("runner");
 
 					return Ok(routing_output);
 				}
@@ -183,6 +214,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 				.await?
 				{
 					metrics::ROUTE_TOTAL.with_label_values(&["envoy"]).inc();
+					req_ctx.access_log_mut().route_kind = Some("envoy");
 
 					return Ok(routing_output);
 				}
@@ -225,6 +257,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					.await?
 					{
 						metrics::ROUTE_TOTAL.with_label_values(&["gateway"]).inc();
+						req_ctx.access_log_mut().route_kind = Some("gateway");
 
 						return Ok(routing_output);
 					}
@@ -240,6 +273,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					.await?
 					{
 						metrics::ROUTE_TOTAL.with_label_values(&["runner"]).inc();
+						req_ctx.access_log_mut().route_kind = Some("runner");
 
 						return Ok(routing_output);
 					}
@@ -253,6 +287,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					.await?
 					{
 						metrics::ROUTE_TOTAL.with_label_values(&["envoy"]).inc();
+						req_ctx.access_log_mut().route_kind = Some("envoy");
 
 						return Ok(routing_output);
 					}
@@ -260,7 +295,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					if let Some(routing_output) = phase_timeout(
 						route_dispatch_phase("api_public_header"),
 						ctx.config().guard().route_dispatch_timeout(),
-						api_public::route_request(&ctx, &target),
+						api_public::route_request(&ctx, &shared_state, req_ctx, &target),
 						|elapsed, timeout| {
 							route_dispatch_timeout("api_public_header", elapsed, timeout)
 						},
@@ -268,6 +303,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					.await?
 					{
 						metrics::ROUTE_TOTAL.with_label_values(&["api"]).inc();
+						req_ctx.access_log_mut().route_kind = Some("api");
 
 						return Ok(routing_output);
 					}
@@ -276,7 +312,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					if let Some(routing_output) = phase_timeout(
 						route_dispatch_phase("api_public_default"),
 						ctx.config().guard().route_dispatch_timeout(),
-						api_public::route_request(&ctx, "api-public"),
+						api_public::route_request(&ctx, &shared_state, req_ctx, "api-public"),
 						|elapsed, timeout| {
 							route_dispatch_timeout("api_public_default", elapsed, timeout)
 						},
@@ -284,6 +320,7 @@ pub fn create_routing_function(ctx: &StandaloneCtx, shared_state: SharedState) -
 					.await?
 					{
 						metrics::ROUTE_TOTAL.with_label_values(&["api"]).inc();
+						req_ctx.access_log_mut().route_kind = Some("api");
 
 						return Ok(routing_output);
 					}
@@ -5,25 +5,29 @@ use std::{sync::Arc, time::Duration};
 
 use anyhow::Result;
 use gas::{ctx::message::SubscriptionHandle, prelude::*};
-use hyper::header::HeaderName;
+use hyper::header::{HeaderName, HeaderValue};
 use rivet_guard_core::{RouteConfig, RouteTarget, RoutingOutput, request_context::RequestContext};
+use rivet_types::namespaces::RateLimitRoute;
 
 use super::{
 	SEC_WEBSOCKET_PROTOCOL, WS_PROTOCOL_ACTOR, WS_PROTOCOL_SKIP_READY_WAIT, WS_PROTOCOL_TOKEN,
 	X_RIVET_SKIP_READY_WAIT, X_RIVET_TOKEN, actor_path::ParsedActorPath,
 };
 use crate::{
-	errors, metrics,
+	auth::{self, AuthIdentity, AuthRequest},
+	cache, errors, metrics,
+	rate_limit::RateLimitRequest,
 	routing::{
 		Phase,
-		actor_path::{is_actor_gateway_path, parse_actor_path},
+		actor_path::{QueryActorQuery, is_actor_gateway_path, parse_actor_path},
+		domain_route,
 		pegboard_gateway::resolve_actor_query::ResolveQueryActorResult,
 		phase_timeout,
 	},
 	shared_state::SharedState,
 };
 use cors::{CorsPreflight, set_non_preflight_cors};
-use resolve_actor_query::resolve_query;
+use resolve_actor_query::{resolve_namespace_id, resolve_query, serialize_actor_key};
 
 /// Time to wait before starting pool error checks
 const RUNNER_POOL_ERROR_CHECK_DELAY: Duration = Duration::from_secs(1);
@@ -31,6 +35,8 @@ const RUNNER_POOL_ERROR_CHECK_DELAY: Duration = Duration::from_secs(1);
 const RUNNER_POOL_ERROR_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 
 pub const X_RIVET_ACTOR: HeaderName = HeaderName::from_static("x-rivet-actor");
+/// Name of the datacenter a query route resolved to.
+pub const X_RIVET_REGION: HeaderName = HeaderName::from_static("x-rivet-region");
 
 /// Route requests to actor services using path-based routing
 #[tracing::instrument(skip_all)]
@@ -72,25 +78,45 @@ pub async fn route_request_path_based_inner(
 
 	tracing::debug!(?actor_path, "routing using path-based actor routing");
 
-	let (actor_id, token, stripped_path, skip_ready_wait) = match actor_path {
+	let (actor_id, token, stripped_path, skip_ready_wait, auth) = match actor_path {
 		ParsedActorPath::Direct(path) => (
 			Id::parse(&path.actor_id).context("invalid actor id in path")?,
 			read_gateway_token_for_path_based(req_ctx, path.token.as_deref())?
 				.map(ToOwned::to_owned),
 			path.stripped_path.clone(),
 			read_skip_ready_wait_for_path_based(req_ctx)?,
+			RequestAuth::Pending,
 		),
 		ParsedActorPath::Query(path) => {
 			let token = read_gateway_token_for_path_based(req_ctx, path.token.as_deref())?
 				.map(ToOwned::to_owned);
 
+			// Authenticate before the query can create the actor
+			let (namespace_name, actor_name) = path.query.namespace_and_name();
+			let namespace_id = resolve_namespace_id(ctx, namespace_name).await?;
+			let identity = authenticate(
+				ctx,
+				req_ctx,
+				namespace_id,
+				Some(actor_name),
+				Some(&serialize_actor_key(path.query.key())?),
+				&path.stripped_path,
+				token.as_deref(),
+			)
+			.await?;
+
 			match phase_timeout(
 				Phase::new(
 					"route_pegboard_resolve_query",
 					&metrics::ROUTE_PEGBOARD_RESOLVE_QUERY_DURATION,
 				),
 				ctx.config().guard().route_pegboard_resolve_query_timeout(),
-				resolve_query(ctx, &path.query),
+				resolve_query(
+					ctx,
+					shared_state.geo.as_ref(),
+					req_ctx.client_ip(),
+					&path.query,
+				),
 				|elapsed, timeout| {
 					pegboard::errors::RouteResolveQueryTimeout {
 						elapsed_ms: elapsed.as_millis() as u64,
@@ -101,17 +127,33 @@ pub async fn route_request_path_based_inner(
 			)
 			.await?
 			{
-				ResolveQueryActorResult::Found { actor_id } => (
-					actor_id,
-					token,
-					path.stripped_path.clone(),
-					path.query.skip_ready_wait(),
-				),
-				ResolveQueryActorResult::Forward { dc_label } => {
+				ResolveQueryActorResult::Found { actor_id } => {
+					set_region_header(ctx, req_ctx, actor_id.label())?;
+
+					(
+						actor_id,
+						token,
+						path.stripped_path.clone(),
+						path.query.skip_ready_wait(),
+						RequestAuth::Done(identity),
+					)
+				}
+				ResolveQueryActorResult::Forward { dc_label, region } => {
 					let peer_dc = ctx
 						.config()
 						.dc_for_label(dc_label)
 						.ok_or_else(|| rivet_api_util::errors::Datacenter::NotFound.build())?;
+					set_region_header(ctx, req_ctx, dc_label)?;
+
+					// Query paths always have a query string
+					let forward_path = match region {
+						Some(region) => format!(
+							"{}&rvt-region={}",
+							req_ctx.path(),
+							urlencoding::encode(&region)
+						),
+						None => req_ctx.path().to_owned(),
+					};
 
 					return Ok(Some(RoutingOutput::Route(RouteConfig {
 						targets: vec![RouteTarget {
@@ -122,7 +164,7 @@ pub async fn route_request_path_based_inner(
 							port: peer_dc
 								.proxy_url_port()
 								.context("bad peer dc proxy url port")?,
-							path: req_ctx.path().to_owned(),
+							path: forward_path,
 						}],
 					})));
 				}
@@ -138,11 +180,170 @@ pub async fn route_request_path_based_inner(
 		&stripped_path,
 		token.as_deref(),
 		skip_ready_wait,
+		auth,
 	)
 	.await
 	.map(Some)
 }
 
+/// Route requests for custom domains to the actor their domain route resolves to
+#[tracing::instrument(skip_all)]
+pub async fn route_request_domain_based(
+	ctx: &StandaloneCtx,
+	shared_state: &SharedState,
+	req_ctx: &mut RequestContext,
+) -> Result<Option<RoutingOutput>> {
+	let hostname = req_ctx.hostname().to_ascii_lowercase();
+	if domain_route::is_platform_host(ctx.config(), &hostname) {
+		return Ok(None);
+	}
+
+	let skip_ready_wait = read_skip_ready_wait_for_path_based(req_ctx)?;
+
+	// Exact hostnames take precedence over wildcards
+	let routes = cache::domain_route::resolve(ctx, &hostname).await?;
+	let mut has_routes = !routes.is_empty();
+	let mut matched = domain_route::match_route(&routes, None, req_ctx.path(), skip_ready_wait)?;
+
+	if matched.is_none()
+		&& let Some((subdomain, parent)) = hostname.split_once('.')
+		&& parent.contains('.')
+	{
+		let routes = cache::domain_route::resolve(ctx, &format!("*.{parent}")).await?;
+		has_routes |= !routes.is_empty();
+		matched =
+			domain_route::match_route(&routes, Some(subdomain), req_ctx.path(), skip_ready_wait)?;
+	}
+
+	let Some(matched) = matched else {
+		// Domains with routes never fall through to the API
+		if has_routes {
+			return Err(errors::NoRoute {
+				host: req_ctx.hostname().to_string(),
+				path: req_ctx.path().to_string(),
+			}
+			.build());
+		}
+
+		return Ok(None);
+	};
+
+	tracing::debug!(?matched, "routing using domain-based actor routing");
+	req_ctx.access_log_mut().namespace_id = Some(matched.namespace_id);
+
+	// The websocket protocol header is optional for browsers connecting to a custom domain
+	let token = if req_ctx.is_websocket() && req_ctx.headers().get(SEC_WEBSOCKET_PROTOCOL).is_none()
+	{
+		None
+	} else {
+		read_gateway_token_for_path_based(req_ctx, None)?.map(ToOwned::to_owned)
+	};
+
+	// Authenticate before the query can create the actor
+	let identity = authenticate(
+		ctx,
+		req_ctx,
+		matched.namespace_id,
+		Some(matched.query.namespace_and_name().1),
+		Some(&serialize_actor_key(matched.query.key())?),
+		&matched.stripped_path,
+		token.as_deref(),
+	)
+	.await?;
+
+	let res = phase_timeout(
+		Phase::new(
+			"route_pegboard_resolve_query",
+			&metrics::ROUTE_PEGBOARD_RESOLVE_QUERY_DURATION,
+		)
+		.with_namespace_id(matched.namespace_id),
+		ctx.config().guard().route_pegboard_resolve_query_timeout(),
+		resolve_query(
+			ctx,
+			shared_state.geo.as_ref(),
+			req_ctx.client_ip(),
+			&matched.query,
+		),
+		|elapsed, timeout| {
+			pegboard::errors::RouteResolveQueryTimeout {
+				elapsed_ms: elapsed.as_millis() as u64,
+				timeout_ms: timeout.as_millis() as u64,
+			}
+			.build()
+		},
+	)
+	.await?;
+
+	// Peer datacenters receive the request on their own hostname, so it is forwarded with a
+	// gateway path instead
+	let (dc_label, forward_path) = match res {
+		ResolveQueryActorResult::Found { actor_id } => {
+			set_region_header(ctx, req_ctx, actor_id.label())?;
+
+			if actor_id.label() == ctx.config().dc_label() {
+				return route_request_inner(
+					ctx,
+					shared_state,
+					req_ctx,
+					actor_id,
+					&matched.stripped_path,
+					token.as_deref(),
+					skip_ready_wait,
+					RequestAuth::Done(identity),
+				)
+				.await
+				.map(Some);
+			}
+
+			req_ctx.access_log_mut().actor_id = Some(actor_id);
+
+			(
+				actor_id.label(),
+				format!("/gateway/{actor_id}{}", matched.stripped_path),
+			)
+		}
+		ResolveQueryActorResult::Forward { dc_label, region } => {
+			set_region_header(ctx, req_ctx, dc_label)?;
+
+			let mut query = matched.query.clone();
+			if let (
+				Some(region),
+				QueryActorQuery::GetOrCreate {
+					region: query_region,
+					..
+				},
+			) = (region, &mut query)
+			{
+				*query_region = Some(region);
+			}
+
+			(
+				dc_label,
+				domain_route::query_gateway_path(&query, &matched.stripped_path),
+			)
+		}
+	};
+
+	let peer_dc = ctx
+		.config()
+		.dc_for_label(dc_label)
+		.ok_or_else(|| rivet_api_util::errors::Datacenter::NotFound.build())?;
+	req_ctx.access_log_mut().upstream = Some(format!("datacenter:{}", peer_dc.name));
+
+	Ok(Some(RoutingOutput::Route(RouteConfig {
+		targets: vec![RouteTarget {
+			host: peer_dc
+				.proxy_url_host()
+				.context("bad peer dc proxy url host")?
+				.to_string(),
+			port: peer_dc
+				.proxy_url_port()
+				.context("bad peer dc proxy url port")?,
+			path: forward_path,
+		}],
+	})))
+}
+
 /// Route requests to actor services based on headers
 #[tracing::instrument(skip_all)]
 pub async fn route_request(
@@ -246,11 +447,27 @@ pub async fn route_request(
 		&stripped_path,
 		token.as_deref(),
 		skip_ready_wait,
+		RequestAuth::Pending,
 	)
 	.await
 	.map(Some)
 }
 
+/// Reports the region a query route resolved to.
+fn set_region_header(
+	ctx: &StandaloneCtx,
+	req_ctx: &mut RequestContext,
+	dc_label: u16,
+) -> Result<()> {
+	if let Some(dc) = ctx.config().dc_for_label(dc_label) {
+		req_ctx
+			.response_headers_mut()
+			.insert(X_RIVET_REGION, HeaderValue::from_str(&dc.name)?);
+	}
+
+	Ok(())
+}
+
 fn is_actor_http_request_path(path: &str) -> bool {
 	let Some(stripped) = path.strip_prefix("/request") else {
 		return false;
@@ -265,11 +482,14 @@ async fn route_request_inner(
 	req_ctx: &mut RequestContext,
 	actor_id: Id,
 	stripped_path: &str,
-	_token: Option<&str>,
+	token: Option<&str>,
 	skip_ready_wait: bool,
+	auth: RequestAuth,
 ) -> Result<RoutingOutput> {
 	// NOTE: Token validation implemented in EE
 
+	req_ctx.access_log_mut().actor_id = Some(actor_id);
+
 	// Route to peer dc where the actor lives
 	if actor_id.label() != ctx.config().dc_label() {
 		tracing::debug!(peer_dc_label=?actor_id.label(), "re-routing actor to peer dc");
@@ -278,6 +498,7 @@ async fn route_request_inner(
 			.config()
 			.dc_for_label(actor_id.label())
 			.ok_or_else(|| rivet_api_util::errors::Datacenter::NotFound.build())?;
+		req_ctx.access_log_mut().upstream = Some(format!("datacenter:{}", peer_dc.name));
 
 		return Ok(RoutingOutput::Route(RouteConfig {
 			targets: vec![RouteTarget {
@@ -363,6 +584,45 @@ async fn route_request_inner(
 		return Err(pegboard::errors::Actor::NotFound.build());
 	}
 
+	req_ctx.access_log_mut().namespace_id = Some(actor.namespace_id);
+
+	// Check rate limits before waking the actor
+	shared_state
+		.rate_limiter
+		.check(
+			ctx,
+			RateLimitRequest {
+				namespace_id: actor.namespace_id,
+				route: if req_ctx.is_websocket() {
+					RateLimitRoute::Websocket
+				} else {
+					RateLimitRoute::Http
+				},
+				actor_name: actor.name.as_deref(),
+				actor_id: Some(actor_id),
+				token,
+				client_ip: req_ctx.client_ip(),
+			},
+		)
+		.await?;
+
+	let identity = match auth {
+		RequestAuth::Pending => {
+			authenticate(
+				ctx,
+				req_ctx,
+				actor.namespace_id,
+				actor.name.as_deref(),
+				actor.key.as_deref(),
+				stripped_path,
+				token,
+			)
+			.await?
+		}
+		RequestAuth::Done(identity) => identity,
+	};
+	auth::set_auth_headers(req_ctx.headers_mut(), identity.as_ref())?;
+
 	match actor.version {
 		2 => {
 			drop(ready_sub);
@@ -374,6 +634,7 @@ async fn route_request_inner(
 			handle_actor_v2(
 				ctx,
 				shared_state,
+				req_ctx,
 				actor_id,
 				actor,
 				stripped_path,
@@ -389,6 +650,7 @@ async fn route_request_inner(
 			handle_actor_v1(
 				ctx,
 				shared_state,
+				req_ctx,
 				actor_id,
 				actor,
 				stripped_path,
@@ -412,6 +674,7 @@ async fn route_request_inner(
 async fn handle_actor_v2(
 	ctx: &StandaloneCtx,
 	shared_state: &SharedState,
+	req_ctx: &mut RequestContext,
 	actor_id: Id,
 	actor: pegboard::ops::actor::get_for_gateway::Output,
 	stripped_path: &str,
@@ -421,6 +684,8 @@ async fn handle_actor_v2(
 	mut fail_sub: SubscriptionHandle<pegboard::workflows::actor2::Failed>,
 	mut destroy_sub: SubscriptionHandle<pegboard::workflows::actor2::DestroyStarted>,
 ) -> Result<RoutingOutput> {
+	let wake_started_at = std::time::Instant::now();
+
 	// Wake actor if sleeping
 	if actor.sleeping {
 		tracing::debug!(
@@ -504,7 +769,7 @@ async fn handle_actor_v2(
 		tokio::pin!(pool_error_check_fut);
 
 		// Wait for ready, fail, or destroy
-		loop {
+		let envoy_key = loop {
 			tokio::select! {
 				res = ready_sub.next() => {
 					let envoy_key = res?.into_body().envoy_key;
@@ -601,8 +866,12 @@ async fn handle_actor_v2(
 					return Err(errors::ActorReadyTimeout { actor_id }.build());
 				}
 			}
-		}
+		};
+		req_ctx.access_log_mut().wake_duration = Some(wake_started_at.elapsed());
+
+		envoy_key
 	};
+	req_ctx.access_log_mut().upstream = Some(format!("envoy:{envoy_key}"));
 
 	let pool_name = actor
 		.runner_name_selector
@@ -635,6 +904,7 @@ async fn handle_actor_v2(
 async fn handle_actor_v1(
 	ctx: &StandaloneCtx,
 	shared_state: &SharedState,
+	req_ctx: &mut RequestContext,
 	actor_id: Id,
 	actor: pegboard::ops::actor::get_for_gateway::Output,
 	stripped_path: &str,
@@ -649,6 +919,8 @@ async fn handle_actor_v1(
 	fail_sub2: SubscriptionHandle<pegboard::workflows::actor2::Failed>,
 	destroy_sub2: SubscriptionHandle<pegboard::workflows::actor2::DestroyStarted>,
 ) -> Result<RoutingOutput> {
+	let wake_started_at = std::time::Instant::now();
+
 	// Wake actor if sleeping
 	if actor.sleeping {
 		tracing::debug!(?actor_id, "actor sleeping, waking");
@@ -696,7 +968,7 @@ async fn handle_actor_v1(
 		tokio::pin!(pool_error_check_fut);
 
 		// Wait for ready, fail, or destroy
-		loop {
+		let runner_id = loop {
 			tokio::select! {
 				res = ready_sub.next() => break res?.runner_id,
 				res = stopped_sub.next() => {
@@ -765,6 +1037,7 @@ async fn handle_actor_v1(
 					return handle_actor_v2(
 						ctx,
 						shared_state,
+						req_ctx,
 						actor_id,
 						actor,
 						stripped_path,
@@ -785,8 +1058,12 @@ async fn handle_actor_v1(
 					return Err(errors::ActorReadyTimeout { actor_id }.build());
 				}
 			}
-		}
+		};
+		req_ctx.access_log_mut().wake_duration = Some(wake_started_at.elapsed());
+
+		runner_id
 	};
+	req_ctx.access_log_mut().upstream = Some(format!("runner:{runner_id}"));
 
 	tracing::debug!(?actor_id, ?runner_id, "actor ready");
 
@@ -801,6 +1078,55 @@ async fn handle_actor_v1(
 	Ok(RoutingOutput::CustomServe(std::sync::Arc::new(gateway)))
 }
 
+/// Auth state of a request routed to an actor.
+enum RequestAuth {
+	/// The namespace was not known before the actor was fetched.
+	Pending,
+	/// Authenticated before the actor was resolved.
+	Done(Option<AuthIdentity>),
+}
+
+/// Checks the request against the auth policy of the namespace. Falls back to the bearer token if
+/// the request has no gateway token.
+async fn authenticate(
+	ctx: &StandaloneCtx,
+	req_ctx: &RequestContext,
+	namespace_id: Id,
+	actor_name: Option<&str>,
+	actor_key: Option<&str>,
+	stripped_path: &str,
+	token: Option<&str>,
+) -> Result<Option<AuthIdentity>> {
+	phase_timeout(
+		Phase::new(
+			"route_pegboard_auth_check",
+			&metrics::ROUTE_PEGBOARD_AUTH_CHECK_DURATION,
+		)
+		.with_namespace_id(namespace_id),
+		ctx.config().guard().route_auth_check_timeout(),
+		auth::authenticate(
+			ctx,
+			AuthRequest {
+				namespace_id,
+				actor_name,
+				actor_key,
+				method: req_ctx.method().as_str(),
+				token: token.or_else(|| auth::bearer_token(req_ctx.headers())),
+				path: stripped_path,
+			},
+		),
+		|elapsed, timeout| {
+			errors::RouteAuthCheckTimeout {
+				target: "actor".to_string(),
+				elapsed_ms: elapsed.as_millis() as u64,
+				timeout_ms: timeout.as_millis() as u64,
+			}
+			.build()
+		},
+	)
+	.await
+}
+
 fn read_gateway_token_for_path_based<'a>(
 	req_ctx: &'a RequestContext,
 	token_from_path: Option<&'a str>,
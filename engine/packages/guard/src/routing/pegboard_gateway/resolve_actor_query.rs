@@ -5,16 +5,28 @@
 //! resolution in `rivetkit-typescript/packages/rivetkit/src/manager/gateway.ts`
 //! (`resolveQueryActorId`).
 
+use std::net::IpAddr;
+
 use anyhow::Result;
 use base64::{Engine, engine::general_purpose::STANDARD};
 use gas::prelude::*;
-use rivet_types::actors::CrashPolicy;
+use rivet_types::{actors::CrashPolicy, namespaces::RegionMode};
 
-use crate::routing::actor_path::QueryActorQuery;
+use crate::{
+	geo::{GeoLocator, follow_first_client},
+	routing::actor_path::QueryActorQuery,
+};
 
 pub enum ResolveQueryActorResult {
-	Found { actor_id: Id },
-	Forward { dc_label: u16 },
+	Found {
+		actor_id: Id,
+	},
+	Forward {
+		dc_label: u16,
+		/// Set when the region policy chose the datacenter. The forwarded request must carry it so
+		/// the peer creates the actor there instead of choosing again.
+		region: Option<String>,
+	},
 }
 
 /// Resolve a parsed query gateway path to a concrete actor ID.
@@ -23,6 +35,8 @@ pub enum ResolveQueryActorResult {
 /// (Get or GetOrCreate).
 pub async fn resolve_query(
 	ctx: &StandaloneCtx,
+	geo: Option<&GeoLocator>,
+	client_ip: IpAddr,
 	query: &QueryActorQuery,
 ) -> Result<ResolveQueryActorResult> {
 	match query {
@@ -44,6 +58,8 @@ pub async fn resolve_query(
 		} => {
 			resolve_query_get_or_create(
 				ctx,
+				geo,
+				client_ip,
 				namespace,
 				name,
 				pool_name,
@@ -58,7 +74,7 @@ pub async fn resolve_query(
 }
 
 /// Resolve a namespace name to its ID via the namespace ops layer.
-async fn resolve_namespace_id(ctx: &StandaloneCtx, namespace_name: &str) -> Result<Id> {
+pub(super) async fn resolve_namespace_id(ctx: &StandaloneCtx, namespace_name: &str) -> Result<Id> {
 	let namespace = ctx
 		.op(namespace::ops::resolve_for_name_global::Input {
 			name: namespace_name.to_string(),
@@ -95,7 +111,10 @@ async fn get_actor_for_key(
 		}
 		pegboard::ops::actor::get_for_key::Output::NotFound => Ok(None),
 		pegboard::ops::actor::get_for_key::Output::Forward { dc_label } => {
-			Ok(Some(ResolveQueryActorResult::Forward { dc_label }))
+			Ok(Some(ResolveQueryActorResult::Forward {
+				dc_label,
+				region: None,
+			}))
 		}
 	}
 }
@@ -121,6 +140,8 @@ async fn resolve_query_get(
 /// lookup after a failed create.
 async fn resolve_query_get_or_create(
 	ctx: &StandaloneCtx,
+	geo: Option<&GeoLocator>,
+	client_ip: IpAddr,
 	namespace_name: &str,
 	name: &str,
 	pool_name: &str,
@@ -132,11 +153,30 @@ async fn resolve_query_get_or_create(
 	let namespace_id = resolve_namespace_id(ctx, namespace_name).await?;
 	let serialized_key = serialize_actor_key(key)?;
 
-	let target_dc_label =
-		resolve_query_target_dc_label(ctx, namespace_id, namespace_name, pool_name, region).await?;
+	let target = resolve_query_target_dc_label(
+		ctx,
+		geo,
+		client_ip,
+		namespace_id,
+		namespace_name,
+		name,
+		pool_name,
+		region,
+	)
+	.await?;
+	let target_dc_label = target.dc_label;
 	if target_dc_label != ctx.config().dc_label() {
+		let region = if target.from_policy {
+			ctx.config()
+				.dc_for_label(target_dc_label)
+				.map(|dc| dc.name.clone())
+		} else {
+			None
+		};
+
 		return Ok(ResolveQueryActorResult::Forward {
 			dc_label: target_dc_label,
+			region,
 		});
 	}
 
@@ -161,6 +201,7 @@ async fn resolve_query_get_or_create(
 				input: encoded_input,
 				forward_request: true,
 				datacenter_name: None,
+				fork_from: None,
 			})
 			.await
 		{
@@ -177,20 +218,39 @@ async fn resolve_query_get_or_create(
 	} else {
 		Ok(ResolveQueryActorResult::Forward {
 			dc_label: target_dc_label,
+			region: None,
 		})
 	}
 }
 
+struct TargetDc {
+	dc_label: u16,
+	/// Whether the datacenter was chosen by the namespace's region policy.
+	from_policy: bool,
+}
+
 /// Determine which datacenter to target for actor creation. Uses the explicit
-/// region if provided, otherwise picks the first datacenter that has the runner
-/// config enabled.
+/// region if provided, otherwise the namespace's region policy. Without a
+/// matching rule, picks the first datacenter that has the runner config
+/// enabled, in order of ping.
 async fn resolve_query_target_dc_label(
 	ctx: &StandaloneCtx,
+	geo: Option<&GeoLocator>,
+	client_ip: IpAddr,
 	namespace_id: Id,
 	namespace_name: &str,
+	name: &str,
 	runner_name_selector: &str,
 	region: Option<&str>,
-) -> Result<u16> {
+) -> Result<TargetDc> {
+	let no_runner_config = || {
+		pegboard::errors::Actor::NoRunnerConfigConfigured {
+			namespace: namespace_name.to_string(),
+			pool_name: runner_name_selector.to_string(),
+		}
+		.build()
+	};
+
 	let requested_dc_label = if let Some(region) = region {
 		Some(
 			ctx.config()
@@ -202,35 +262,103 @@ async fn resolve_query_target_dc_label(
 		None
 	};
 
-	let res = ctx
-		.op(
+	let (res, policy) = tokio::try_join!(
+		ctx.op(
 			pegboard::ops::runner::list_runner_config_enabled_dcs::Input {
 				namespace_id,
 				runner_name: runner_name_selector.to_string(),
 			},
-		)
-		.await?;
+		),
+		async {
+			if requested_dc_label.is_some() {
+				Ok(None)
+			} else {
+				ctx.op(namespace::ops::get_policies_local::Input { namespace_id })
+					.await
+					.map(|policies| Some(policies.region))
+			}
+		},
+	)?;
+	let enabled_dc_labels = res.dc_labels;
 
-	let target_dc_label = if let Some(requested_dc_label) = requested_dc_label {
-		res.dc_labels
+	if let Some(requested_dc_label) = requested_dc_label {
+		return enabled_dc_labels
 			.into_iter()
 			.find(|dc_label| *dc_label == requested_dc_label)
-	} else {
-		res.dc_labels.into_iter().next()
+			.map(|dc_label| TargetDc {
+				dc_label,
+				from_policy: false,
+			})
+			.ok_or_else(no_runner_config);
+	}
+
+	let closest_by_ping = enabled_dc_labels
+		.first()
+		.copied()
+		.ok_or_else(no_runner_config)?;
+
+	// Rules for the actor name take precedence
+	let rules = policy.map(|policy| policy.rules).unwrap_or_default();
+	let Some(rule) = rules
+		.iter()
+		.find(|rule| rule.actor_name.as_deref() == Some(name))
+		.or_else(|| rules.iter().find(|rule| rule.actor_name.is_none()))
+	else {
+		return Ok(TargetDc {
+			dc_label: closest_by_ping,
+			from_policy: false,
+		});
 	};
 
-	if let Some(dc_label) = target_dc_label {
-		Ok(dc_label)
-	} else {
-		Err(pegboard::errors::Actor::NoRunnerConfigConfigured {
-			namespace: namespace_name.to_string(),
-			pool_name: runner_name_selector.to_string(),
+	let nearest_dc_label = || geo.and_then(|geo| geo.nearest(client_ip, &enabled_dc_labels));
+	let or_closest_by_ping = |dc_label: Option<u16>| {
+		dc_label.unwrap_or_else(|| {
+			tracing::debug!(%client_ip, "could not locate client, using closest datacenter by ping");
+			closest_by_ping
+		})
+	};
+
+	let dc_label = match &rule.mode {
+		RegionMode::Nearest => or_closest_by_ping(nearest_dc_label()),
+		RegionMode::Pinned { region } => {
+			let pinned_dc_label = ctx
+				.config()
+				.dc_for_name(region)
+				.ok_or_else(|| rivet_api_util::errors::Datacenter::NotFound.build())?
+				.datacenter_label;
+
+			enabled_dc_labels
+				.iter()
+				.copied()
+				.find(|dc_label| *dc_label == pinned_dc_label)
+				.ok_or_else(no_runner_config)?
 		}
-		.build())
-	}
+		RegionMode::FollowFirstClient => {
+			let nearest_dc_label = nearest_dc_label();
+			let first_dc_label = ctx
+				.op(pegboard::ops::actor::claim_first_client_region::Input {
+					namespace_id,
+					name: name.to_string(),
+					dc_label: nearest_dc_label,
+				})
+				.await?
+				.dc_label;
+
+			or_closest_by_ping(follow_first_client(
+				first_dc_label,
+				nearest_dc_label,
+				&enabled_dc_labels,
+			))
+		}
+	};
+
+	Ok(TargetDc {
+		dc_label,
+		from_policy: true,
+	})
 }
 
-fn serialize_actor_key(key: &[String]) -> Result<String> {
+pub(super) fn serialize_actor_key(key: &[String]) -> Result<String> {
 	const EMPTY_KEY: &str = "/";
 	const KEY_SEPARATOR: &str = "/";
 	const KEY_SEPARATOR_CHAR: char = '/';
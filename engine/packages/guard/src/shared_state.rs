@@ -2,21 +2,31 @@ use anyhow::*;
 use std::{ops::Deref, sync::Arc};
 use universalpubsub::PubSub;
 
+use crate::{geo::GeoLocator, rate_limit::RateLimiter};
+
 #[derive(Clone)]
 pub struct SharedState(Arc<SharedStateInner>);
 
 impl SharedState {
-	pub fn new(config: &rivet_config::Config, pubsub: PubSub) -> SharedState {
-		SharedState(Arc::new(SharedStateInner {
+	pub fn new(
+		config: &rivet_config::Config,
+		pubsub: PubSub,
+		udb: universaldb::Database,
+	) -> Result<SharedState> {
+		Ok(SharedState(Arc::new(SharedStateInner {
 			pegboard_gateway: pegboard_gateway::shared_state::SharedState::new(
 				config,
 				pubsub.clone(),
 			),
 			pegboard_gateway2: pegboard_gateway2::shared_state::SharedState::new(config, pubsub),
-		}))
+			rate_limiter: RateLimiter::new(udb),
+			geo: GeoLocator::from_config(config)?,
+		})))
 	}
 
 	pub async fn start(&self) -> Result<()> {
+		self.rate_limiter.start();
+
 		tokio::try_join!(
 			self.pegboard_gateway.start(),
 			self.pegboard_gateway2.start(),
@@ -37,4 +47,7 @@ impl Deref for SharedState {
 pub struct SharedStateInner {
 	pub pegboard_gateway: pegboard_gateway::shared_state::SharedState,
 	pub pegboard_gateway2: pegboard_gateway2::shared_state::SharedState,
+	pub rate_limiter: RateLimiter,
+	/// Set if `guard.geo` is configured.
+	pub geo: Option<GeoLocator>,
 }
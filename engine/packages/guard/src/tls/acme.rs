@@ -0,0 +1,541 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::*;
+use futures_util::{StreamExt, TryStreamExt};
+use gas::prelude::*;
+use instant_acme::{
+	Account, AuthorizationStatus, ChallengeType, Identifier, NewAccount, NewOrder, OrderStatus,
+	RetryPolicy,
+};
+use rivet_config::config::guard::{Acme, AcmeChallenge};
+use universaldb::prelude::*;
+
+use super::{CertificateKind, CertificateStore, certified_key_from_pem};
+use crate::{keys, metrics};
+
+/// How long a node may hold the lease for ordering a certificate.
+const ORDER_LEASE: Duration = Duration::from_secs(10 * 60);
+/// How often TLS-ALPN-01 challenge certificates are synced from the database while challenges
+/// are pending.
+const TLS_ALPN_CHALLENGE_SYNC_INTERVAL: Duration = Duration::from_secs(2);
+/// How long to wait before retrying after a failed order.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Loads stored certificates, ordering any that are missing before returning, and spawns a task
+/// that renews them.
+pub(super) async fn start(
+	ctx: &StandaloneCtx,
+	store: Arc<CertificateStore>,
+	acme: &Acme,
+) -> Result<()> {
+	ensure!(
+		!acme.api_domains.is_empty(),
+		"guard.https.acme.api_domains cannot be empty"
+	);
+
+	let udb = (*ctx.udb()?).clone();
+
+	if matches!(acme.challenge(), AcmeChallenge::TlsAlpn01) {
+		let udb = udb.clone();
+		let store = store.clone();
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(TLS_ALPN_CHALLENGE_SYNC_INTERVAL);
+			interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+			loop {
+				interval.tick().await;
+
+				if let Err(err) = sync_tls_alpn_challenges(&udb, &store).await {
+					tracing::warn!(?err, "failed to sync tls-alpn-01 challenges");
+				}
+			}
+		});
+	}
+
+	// Serve stored certificates right away, even if they are due for renewal
+	for kind in [CertificateKind::Api, CertificateKind::Actor] {
+		if let Some(cert) = read_certificate(&udb, kind).await? {
+			install(&store, kind, &cert)?;
+		}
+	}
+
+	// Do not wait for ACME if a stored certificate is already being served
+	if store.get(CertificateKind::Api).is_none() {
+		ensure_certificates(&udb, acme, &store).await?;
+	}
+
+	let acme = acme.clone();
+	tokio::spawn(async move {
+		loop {
+			let sleep = match ensure_certificates(&udb, &acme, &store).await {
+				Result::Ok(()) => acme.check_interval(),
+				Err(err) => {
+					tracing::error!(?err, "failed to renew acme certificates");
+					RETRY_INTERVAL.min(acme.check_interval())
+				}
+			};
+
+			tokio::time::sleep(sleep).await;
+		}
+	});
+
+	Ok(())
+}
+
+/// Ensures every configured certificate is issued and up to date, ordering certificates that
+/// are missing or expiring.
+pub async fn ensure_certificates(
+	udb: &universaldb::Database,
+	acme: &Acme,
+	store: &CertificateStore,
+) -> Result<()> {
+	for (kind, domains) in [
+		(CertificateKind::Api, &acme.api_domains),
+		(CertificateKind::Actor, &acme.actor_domains),
+	] {
+		if domains.is_empty() {
+			continue;
+		}
+
+		ensure_certificate(udb, acme, store, kind, domains).await?;
+	}
+
+	Ok(())
+}
+
+/// Orders a certificate for `domains` if the stored one is missing or due for renewal, unless
+/// another node holds the order lease.
+#[tracing::instrument(skip_all, fields(kind = kind.as_str()))]
+async fn ensure_certificate(
+	udb: &universaldb::Database,
+	acme: &Acme,
+	store: &CertificateStore,
+	kind: CertificateKind,
+	domains: &[String],
+) -> Result<()> {
+	let renew_before = acme.renew_before();
+
+	let (cert, acquired) = udb
+		.txn("guard_acme_acquire_lease", |tx| async move {
+			let tx = tx.with_subspace(keys::subspace());
+			let now = util::timestamp::now();
+
+			let cert = tx
+				.read_opt(&keys::AcmeCertificateKey::new(kind), Serializable)
+				.await?;
+			if cert
+				.as_ref()
+				.is_some_and(|cert| !needs_renewal(cert, domains, renew_before, now))
+			{
+				return Ok((cert, false));
+			}
+
+			let lease_key = keys::AcmeCertificateLeaseKey::new(kind);
+			if tx
+				.read_opt(&lease_key, Serializable)
+				.await?
+				.is_some_and(|expire_ts| expire_ts > now)
+			{
+				return Ok((cert, false));
+			}
+
+			tx.write(&lease_key, now + ORDER_LEASE.as_millis() as i64)?;
+
+			Ok((cert, true))
+		})
+		.await?;
+
+	if !acquired {
+		// Another node may have renewed the certificate
+		if let Some(cert) = cert {
+			install(store, kind, &cert)?;
+		}
+
+		return Ok(());
+	}
+
+	tracing::info!(?domains, "ordering acme certificate");
+
+	let res = order_certificate(udb, acme, domains).await;
+
+	metrics::ACME_ORDER_TOTAL
+		.with_label_values(&[kind.as_str(), if res.is_ok() { "ok" } else { "error" }])
+		.inc();
+
+	let res = match res {
+		Result::Ok(cert) => install(store, kind, &cert).map(|_| Some(cert)),
+		Err(err) => Err(err),
+	};
+
+	udb.txn("guard_acme_release_lease", |tx| {
+		let cert = res.as_ref().ok().cloned().flatten();
+		async move {
+			let tx = tx.with_subspace(keys::subspace());
+
+			if let Some(cert) = cert {
+				tx.write(&keys::AcmeCertificateKey::new(kind), cert)?;
+			}
+			tx.delete(&keys::AcmeCertificateLeaseKey::new(kind));
+
+			Ok(())
+		}
+	})
+	.await?;
+
+	res?;
+
+	tracing::info!(?domains, "acme certificate issued");
+
+	Ok(())
+}
+
+fn needs_renewal(
+	cert: &keys::IssuedCertificate,
+	domains: &[String],
+	renew_before: Duration,
+	now: i64,
+) -> bool {
+	let mut issued_domains = cert.domains.clone();
+	issued_domains.sort();
+	let mut domains = domains.to_vec();
+	domains.sort();
+
+	issued_domains != domains || cert.not_after_ts - now < renew_before.as_millis() as i64
+}
+
+/// Places an order for `domains` and waits for the certificate. Challenge responses are written to
+/// the database so any guard node can answer the ACME server.
+async fn order_certificate(
+	udb: &universaldb::Database,
+	acme: &Acme,
+	domains: &[String],
+) -> Result<keys::IssuedCertificate> {
+	let account = account(udb, acme).await?;
+
+	let identifiers = domains
+		.iter()
+		.map(|domain| Identifier::Dns(domain.clone()))
+		.collect::<Vec<_>>();
+	let mut order = account.new_order(&NewOrder::new(&identifiers)).await?;
+
+	// Challenge responses written so far, cleared once the order completes or fails
+	let mut http_tokens = Vec::new();
+	let mut tls_alpn_domains = Vec::new();
+
+	let res = async {
+		// Write every challenge response before telling the ACME server they are ready
+		let mut authorizations = order.authorizations();
+		while let Some(authz) = authorizations.next().await {
+			let mut authz = authz?;
+			match authz.status {
+				AuthorizationStatus::Pending => {}
+				AuthorizationStatus::Valid => continue,
+				status => bail!("unexpected acme authorization status: {status:?}"),
+			}
+
+			let domain = authz.identifier().to_string();
+			let challenge = authz
+				.challenge(challenge_type(acme))
+				.with_context(|| format!("acme server did not offer the challenge for {domain}"))?;
+			let key_authorization = challenge.key_authorization();
+
+			let response = match acme.challenge() {
+				AcmeChallenge::Http01 => {
+					http_tokens.push(challenge.token.clone());
+					ChallengeResponse::Http01 {
+						token: challenge.token.clone(),
+						key_authorization: key_authorization.as_str().to_string(),
+					}
+				}
+				AcmeChallenge::TlsAlpn01 => {
+					tls_alpn_domains.push(domain.clone());
+					ChallengeResponse::TlsAlpn01 {
+						pem: tls_alpn_challenge_certificate(
+							&domain,
+							key_authorization.digest().as_ref(),
+						)?,
+						domain,
+					}
+				}
+			};
+
+			udb.txn("guard_acme_write_challenge", |tx| {
+				let response = response.clone();
+				async move {
+					let tx = tx.with_subspace(keys::subspace());
+
+					match response {
+						ChallengeResponse::Http01 {
+							token,
+							key_authorization,
+						} => tx.write(&keys::AcmeHttpChallengeKey::new(token), key_authorization)?,
+						ChallengeResponse::TlsAlpn01 { domain, pem } => {
+							tx.write(&keys::AcmeTlsAlpnChallengeKey::new(domain), pem)?
+						}
+					}
+
+					Ok(())
+				}
+			})
+			.await?;
+		}
+
+		// Give every guard node time to pick up the challenge certificates
+		if !tls_alpn_domains.is_empty() {
+			tokio::time::sleep(TLS_ALPN_CHALLENGE_SYNC_INTERVAL * 2).await;
+		}
+
+		let mut authorizations = order.authorizations();
+		while let Some(authz) = authorizations.next().await {
+			let mut authz = authz?;
+			if !matches!(authz.status, AuthorizationStatus::Pending) {
+				continue;
+			}
+
+			let mut challenge = authz
+				.challenge(challenge_type(acme))
+				.context("acme challenge disappeared")?;
+			challenge.set_ready().await?;
+		}
+
+		let status = order.poll_ready(&RetryPolicy::default()).await?;
+		ensure!(
+			status == OrderStatus::Ready,
+			"unexpected acme order status: {status:?}"
+		);
+
+		let private_key_pem = order.finalize().await?;
+		let cert_chain_pem = order.poll_certificate(&RetryPolicy::default()).await?;
+
+		anyhow::Ok((cert_chain_pem, private_key_pem))
+	}
+	.await;
+
+	// Challenge responses are only needed while the order is validated
+	udb.txn("guard_acme_clear_challenges", |tx| {
+		let http_tokens = http_tokens.clone();
+		let tls_alpn_domains = tls_alpn_domains.clone();
+		async move {
+			let tx = tx.with_subspace(keys::subspace());
+
+			for token in http_tokens {
+				tx.delete(&keys::AcmeHttpChallengeKey::new(token));
+			}
+			for domain in tls_alpn_domains {
+				tx.delete(&keys::AcmeTlsAlpnChallengeKey::new(domain));
+			}
+
+			Ok(())
+		}
+	})
+	.await?;
+
+	let (cert_chain_pem, private_key_pem) = res?;
+
+	Ok(keys::IssuedCertificate {
+		domains: domains.to_vec(),
+		not_after_ts: not_after_ts(&cert_chain_pem)?,
+		pem: keys::CertificatePem {
+			cert_chain_pem,
+			private_key_pem,
+		},
+	})
+}
+
+#[derive(Clone)]
+enum ChallengeResponse {
+	Http01 {
+		token: String,
+		key_authorization: String,
+	},
+	TlsAlpn01 {
+		domain: String,
+		pem: keys::CertificatePem,
+	},
+}
+
+fn challenge_type(acme: &Acme) -> ChallengeType {
+	match acme.challenge() {
+		AcmeChallenge::Http01 => ChallengeType::Http01,
+		AcmeChallenge::TlsAlpn01 => ChallengeType::TlsAlpn01,
+	}
+}
+
+/// Restores the account registered with the directory or registers a new one.
+async fn account(udb: &universaldb::Database, acme: &Acme) -> Result<Account> {
+	let directory_url = acme.directory_url.to_string();
+
+	let builder = || match &acme.directory_root_cert_path {
+		Some(path) => Account::builder_with_root(path),
+		None => Account::builder(),
+	};
+
+	let credentials = udb
+		.txn("guard_acme_read_account", |tx| {
+			let directory_url = directory_url.clone();
+			async move {
+				let tx = tx.with_subspace(keys::subspace());
+				tx.read_opt(&keys::AcmeAccountKey::new(directory_url), Serializable)
+					.await
+			}
+		})
+		.await?;
+
+	if let Some(credentials) = credentials {
+		return Ok(builder()?.from_credentials(credentials).await?);
+	}
+
+	let contact = acme
+		.contact_emails
+		.iter()
+		.map(|email| format!("mailto:{email}"))
+		.collect::<Vec<_>>();
+	let contact = contact.iter().map(String::as_str).collect::<Vec<_>>();
+
+	let (account, credentials) = builder()?
+		.create(
+			&NewAccount {
+				contact: &contact,
+				terms_of_service_agreed: true,
+				only_return_existing: false,
+			},
+			directory_url.clone(),
+			None,
+		)
+		.await?;
+
+	// Credentials are not `Clone`, so round trip them through JSON for the transaction retries
+	let credentials = serde_json::to_vec(&credentials)?;
+	udb.txn("guard_acme_write_account", |tx| {
+		let directory_url = directory_url.clone();
+		let credentials = &credentials;
+		async move {
+			let tx = tx.with_subspace(keys::subspace());
+			tx.write(
+				&keys::AcmeAccountKey::new(directory_url),
+				serde_json::from_slice(credentials)?,
+			)?;
+
+			Ok(())
+		}
+	})
+	.await?;
+
+	tracing::info!(id = account.id(), "registered acme account");
+
+	Ok(account)
+}
+
+/// Builds the self-signed certificate proving control of `domain` for TLS-ALPN-01 (RFC 8737).
+fn tls_alpn_challenge_certificate(domain: &str, digest: &[u8]) -> Result<keys::CertificatePem> {
+	let mut params = rcgen::CertificateParams::new(vec![domain.to_string()])?;
+	params.custom_extensions = vec![rcgen::CustomExtension::new_acme_identifier(digest)];
+
+	let key_pair = rcgen::KeyPair::generate()?;
+	let cert = params.self_signed(&key_pair)?;
+
+	Ok(keys::CertificatePem {
+		cert_chain_pem: cert.pem(),
+		private_key_pem: key_pair.serialize_pem(),
+	})
+}
+
+/// Epoch milliseconds of the end-entity certificate's expiration.
+fn not_after_ts(cert_chain_pem: &str) -> Result<i64> {
+	let (_, pem) = x509_parser::pem::parse_x509_pem(cert_chain_pem.as_bytes())
+		.context("failed to parse certificate pem")?;
+	let cert = pem.parse_x509().context("failed to parse certificate")?;
+
+	Ok(cert.validity().not_after.timestamp() * 1000)
+}
+
+fn install(
+	store: &CertificateStore,
+	kind: CertificateKind,
+	cert: &keys::IssuedCertificate,
+) -> Result<()> {
+	let certified_key = certified_key_from_pem(
+		cert.pem.cert_chain_pem.as_bytes(),
+		cert.pem.private_key_pem.as_bytes(),
+	)?;
+
+	store.set(kind, certified_key);
+	metrics::TLS_CERTIFICATE_EXPIRE_TS
+		.with_label_values(&[kind.as_str()])
+		.set(cert.not_after_ts);
+
+	Ok(())
+}
+
+/// Reads the certificate of a kind shared by every guard node.
+pub async fn read_certificate(
+	udb: &universaldb::Database,
+	kind: CertificateKind,
+) -> Result<Option<keys::IssuedCertificate>> {
+	udb.txn("guard_acme_read_certificate", |tx| async move {
+		let tx = tx.with_subspace(keys::subspace());
+		tx.read_opt(&keys::AcmeCertificateKey::new(kind), Snapshot)
+			.await
+	})
+	.await
+}
+
+/// Reads the HTTP-01 key authorization for a token.
+pub async fn read_http_challenge(
+	udb: &universaldb::Database,
+	token: &str,
+) -> Result<Option<String>> {
+	udb.txn("guard_acme_read_http_challenge", |tx| async move {
+		let tx = tx.with_subspace(keys::subspace());
+		tx.read_opt(
+			&keys::AcmeHttpChallengeKey::new(token.to_string()),
+			Snapshot,
+		)
+		.await
+	})
+	.await
+}
+
+/// Replaces the TLS-ALPN-01 challenge certificates of the store with the pending challenges.
+pub async fn sync_tls_alpn_challenges(
+	udb: &universaldb::Database,
+	store: &CertificateStore,
+) -> Result<()> {
+	let challenges = udb
+		.txn("guard_acme_read_tls_alpn_challenges", |tx| async move {
+			let tx = tx.with_subspace(keys::subspace());
+
+			let challenge_subspace =
+				keys::subspace().subspace(&keys::AcmeTlsAlpnChallengeKey::subspace());
+
+			tx.get_ranges_keyvalues(
+				universaldb::RangeOption {
+					mode: StreamingMode::WantAll,
+					..(&challenge_subspace).into()
+				},
+				Snapshot,
+			)
+			.map(|res| {
+				let (key, pem) = tx.read_entry::<keys::AcmeTlsAlpnChallengeKey>(&res?)?;
+				Ok((key.domain, pem))
+			})
+			.try_collect::<Vec<_>>()
+			.await
+		})
+		.await?;
+
+	let challenges = challenges
+		.into_iter()
+		.map(|(domain, pem)| {
+			let cert = certified_key_from_pem(
+				pem.cert_chain_pem.as_bytes(),
+				pem.private_key_pem.as_bytes(),
+			)?;
+			Ok((domain.to_ascii_lowercase(), cert))
+		})
+		.collect::<Result<HashMap<_, _>>>()?;
+
+	store.set_tls_alpn_challenges(challenges);
+
+	Ok(())
+}
@@ -0,0 +1,120 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::*;
+use rivet_config::config::guard::Tls;
+
+use super::{CertificateKind, CertificateStore, certified_key_from_pem};
+use crate::metrics;
+
+/// Certificate and key files of one certificate kind.
+struct CertificateFiles {
+	kind: CertificateKind,
+	cert_path: PathBuf,
+	key_path: PathBuf,
+	/// Contents of the last successfully loaded files.
+	loaded: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl CertificateFiles {
+	/// Loads the files into the store if their contents changed since the last load. Returns
+	/// whether the certificate was replaced.
+	async fn reload(&mut self, store: &CertificateStore) -> Result<bool> {
+		let cert_chain_pem = tokio::fs::read(&self.cert_path).await.with_context(|| {
+			format!(
+				"failed to read {} certificate at {}",
+				self.kind.as_str(),
+				self.cert_path.display()
+			)
+		})?;
+		let private_key_pem = tokio::fs::read(&self.key_path).await.with_context(|| {
+			format!(
+				"failed to read {} key at {}",
+				self.kind.as_str(),
+				self.key_path.display()
+			)
+		})?;
+
+		if self
+			.loaded
+			.as_ref()
+			.is_some_and(|(cert, key)| *cert == cert_chain_pem && *key == private_key_pem)
+		{
+			return Ok(false);
+		}
+
+		// Fails while a rotation has only replaced one of the two files, in which case the old
+		// certificate is kept until the next check
+		let cert = certified_key_from_pem(&cert_chain_pem, &private_key_pem)
+			.with_context(|| format!("failed to load {} certificate", self.kind.as_str()))?;
+
+		store.set(self.kind, cert);
+		self.loaded = Some((cert_chain_pem, private_key_pem));
+
+		Ok(true)
+	}
+}
+
+/// Loads the certificates from disk and spawns a task that reloads them when the files change.
+///
+/// Changes are found by polling the file contents instead of with a file watcher. Certificates are
+/// usually mounted from a Kubernetes secret, which is updated by atomically swapping a symlink to
+/// the directory holding the files. Watching the files misses that swap since the watched inodes
+/// never change, and watches are not delivered at all on network and some overlay filesystems.
+/// Reading two small files per interval works everywhere and also retries a rotation that was
+/// caught half done.
+pub(super) async fn start(store: Arc<CertificateStore>, tls: &Tls) -> Result<()> {
+	let mut files = vec![
+		CertificateFiles {
+			kind: CertificateKind::Api,
+			cert_path: tls.api_cert_path.clone(),
+			key_path: tls.api_key_path.clone(),
+			loaded: None,
+		},
+		CertificateFiles {
+			kind: CertificateKind::Actor,
+			cert_path: tls.actor_cert_path.clone(),
+			key_path: tls.actor_key_path.clone(),
+			loaded: None,
+		},
+	];
+
+	for files in &mut files {
+		files.reload(&store).await?;
+		tracing::info!(kind = files.kind.as_str(), "certificate loaded");
+	}
+
+	let reload_interval = tls.reload_interval();
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(reload_interval);
+		interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+		interval.tick().await;
+
+		loop {
+			interval.tick().await;
+
+			for files in &mut files {
+				match files.reload(&store).await {
+					Result::Ok(true) => {
+						tracing::info!(kind = files.kind.as_str(), "certificate reloaded");
+						metrics::TLS_CERTIFICATE_RELOAD_TOTAL
+							.with_label_values(&[files.kind.as_str(), "ok"])
+							.inc();
+					}
+					Result::Ok(false) => {}
+					Err(err) => {
+						tracing::warn!(
+							?err,
+							kind = files.kind.as_str(),
+							"failed to reload certificate, keeping the current one"
+						);
+						metrics::TLS_CERTIFICATE_RELOAD_TOTAL
+							.with_label_values(&[files.kind.as_str(), "error"])
+							.inc();
+					}
+				}
+			}
+		}
+	});
+
+	Ok(())
+}
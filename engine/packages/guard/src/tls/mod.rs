@@ -0,0 +1,190 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, PoisonError, RwLock},
+};
+
+use anyhow::*;
+use gas::prelude::*;
+use rivet_guard_core::{CertRequest, CertResolverFn};
+use rustls::{
+	pki_types::{CertificateDer, PrivateKeyDer},
+	sign::CertifiedKey,
+};
+
+pub mod acme;
+mod files;
+
+/// The certificate served for a hostname.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CertificateKind {
+	/// Served for the API hostnames of this datacenter.
+	Api = 0,
+	/// Served for every other hostname.
+	Actor = 1,
+}
+
+impl CertificateKind {
+	pub fn from_repr(v: usize) -> Option<Self> {
+		match v {
+			0 => Some(CertificateKind::Api),
+			1 => Some(CertificateKind::Actor),
+			_ => None,
+		}
+	}
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			CertificateKind::Api => "api",
+			CertificateKind::Actor => "actor",
+		}
+	}
+}
+
+/// Certificates served by this guard node. The resolver reads the store on every handshake, so
+/// replacing a certificate applies to new connections without touching open ones.
+#[derive(Default)]
+pub struct CertificateStore {
+	certs: RwLock<HashMap<CertificateKind, Arc<CertifiedKey>>>,
+	/// TLS-ALPN-01 challenge certificates by domain.
+	tls_alpn_challenges: RwLock<HashMap<String, Arc<CertifiedKey>>>,
+}
+
+impl CertificateStore {
+	pub fn get(&self, kind: CertificateKind) -> Option<Arc<CertifiedKey>> {
+		self.certs
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(&kind)
+			.cloned()
+	}
+
+	pub fn set(&self, kind: CertificateKind, cert: Arc<CertifiedKey>) {
+		self.certs
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.insert(kind, cert);
+	}
+
+	pub fn tls_alpn_challenge(&self, domain: &str) -> Option<Arc<CertifiedKey>> {
+		self.tls_alpn_challenges
+			.read()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(domain)
+			.cloned()
+	}
+
+	pub fn set_tls_alpn_challenges(&self, challenges: HashMap<String, Arc<CertifiedKey>>) {
+		*self
+			.tls_alpn_challenges
+			.write()
+			.unwrap_or_else(PoisonError::into_inner) = challenges;
+	}
+
+	/// Picks the certificate for a handshake. Hostnames in `api_hosts` get the API certificate
+	/// and every other hostname gets the actor certificate, falling back to the API certificate
+	/// when no actor certificate is configured.
+	pub fn resolve(
+		&self,
+		api_hosts: &HashSet<String>,
+		req: CertRequest<'_>,
+	) -> Result<Arc<CertifiedKey>> {
+		let host = req.server_name.to_ascii_lowercase();
+
+		if req.acme_tls_alpn {
+			return self
+				.tls_alpn_challenge(&host)
+				.with_context(|| format!("no tls-alpn-01 challenge for {host}"));
+		}
+
+		let kind = if api_hosts.contains(&host) {
+			CertificateKind::Api
+		} else {
+			CertificateKind::Actor
+		};
+
+		self.get(kind)
+			.or_else(|| self.get(CertificateKind::Api))
+			.with_context(|| format!("no {} certificate loaded", kind.as_str()))
+	}
+}
+
+/// Parses a PEM certificate chain and private key, checking that the key belongs to the
+/// certificate.
+pub fn certified_key_from_pem(
+	cert_chain_pem: &[u8],
+	private_key_pem: &[u8],
+) -> Result<Arc<CertifiedKey>> {
+	let cert_chain = rustls_pemfile::certs(&mut &cert_chain_pem[..])
+		.collect::<std::result::Result<Vec<CertificateDer<'static>>, _>>()
+		.context("failed to parse certificate chain")?;
+	ensure!(!cert_chain.is_empty(), "no certificates found");
+
+	let private_key: PrivateKeyDer<'static> =
+		rustls_pemfile::private_key(&mut &private_key_pem[..])
+			.context("failed to parse private key")?
+			.context("no private key found")?;
+
+	let certified_key = CertifiedKey::from_der(
+		cert_chain,
+		private_key,
+		&rustls::crypto::ring::default_provider(),
+	)
+	.context("invalid certificate or private key")?;
+
+	Ok(Arc::new(certified_key))
+}
+
+/// Hostnames that are served the API certificate: the public, proxy, and valid regional hosts of
+/// this datacenter plus the ACME API domains.
+fn api_hosts(config: &rivet_config::Config) -> Result<HashSet<String>> {
+	let current_dc = config.topology().current_dc()?;
+
+	let mut hosts = HashSet::new();
+	hosts.extend(current_dc.public_url.host_str().map(str::to_string));
+	hosts.extend(current_dc.proxy_url().host_str().map(str::to_string));
+	hosts.extend(current_dc.valid_hosts.iter().flatten().cloned());
+
+	if let Some(acme) = config.guard().https.as_ref().and_then(|x| x.acme.as_ref()) {
+		hosts.extend(acme.api_domains.iter().cloned());
+	}
+
+	Ok(hosts
+		.into_iter()
+		.map(|host| host.to_ascii_lowercase())
+		.collect())
+}
+
+/// Create a certificate resolver function for TLS
+///
+/// This function sets up a certificate resolver that will serve:
+/// - API certificate for the API hostnames of this datacenter
+/// - Actor certificate for all other hostnames
+///
+/// Certificates either come from files, which are reloaded when they change, or from an ACME
+/// server, in which case they are stored in UniversalDB and shared by every guard node.
+#[tracing::instrument(skip_all)]
+pub async fn create_cert_resolver(
+	ctx: &gas::prelude::StandaloneCtx,
+) -> Result<Option<CertResolverFn>> {
+	let Some(https) = &ctx.config().guard().https else {
+		tracing::info!("HTTPS configuration not found in Guard config - TLS disabled");
+		return Ok(None);
+	};
+
+	let store = Arc::new(CertificateStore::default());
+
+	match (&https.tls, &https.acme) {
+		(Some(tls), None) => files::start(store.clone(), tls).await?,
+		(None, Some(acme)) => acme::start(ctx, store.clone(), acme).await?,
+		(Some(_), Some(_)) => bail!("guard.https.tls and guard.https.acme cannot both be set"),
+		(None, None) => bail!("guard.https requires either tls or acme to be set"),
+	}
+
+	let api_hosts = api_hosts(ctx.config())?;
+	tracing::info!(?api_hosts, "using api certificate for hosts");
+
+	let resolver_fn: CertResolverFn =
+		Arc::new(move |req: CertRequest<'_>| store.resolve(&api_hosts, req).map_err(Into::into));
+
+	Ok(Some(resolver_fn))
+}
@@ -0,0 +1,116 @@
+use std::{collections::HashSet, time::Duration};
+
+use gas::prelude::Id;
+use hyper::{HeaderMap, Method, Version};
+use rivet_guard::access_log::{format_common, format_json};
+use rivet_guard_core::{AccessLogEntry, access_log::AccessLogFields};
+
+fn entry() -> AccessLogEntry {
+	let mut headers = HeaderMap::new();
+	headers.insert("authorization", "Bearer secret".parse().unwrap());
+	headers.insert("user-agent", "curl/8.0".parse().unwrap());
+	headers.append("accept", "text/html".parse().unwrap());
+	headers.append("accept", "application/json".parse().unwrap());
+
+	AccessLogEntry {
+		// 2024-01-02T03:04:05Z
+		ts: 1_704_164_645_000,
+		ray_id: Id::new_v1(1),
+		req_id: Id::new_v1(1),
+		client_ip: "203.0.113.7".parse().unwrap(),
+		method: Method::GET,
+		version: Version::HTTP_11,
+		host: "api.example.com".to_string(),
+		path: "/actors?namespace=default".to_string(),
+		headers,
+		is_websocket: false,
+		status: 200,
+		bytes_in: 0,
+		bytes_out: 512,
+		route_duration: Some(Duration::from_millis(3)),
+		upstream_duration: Some(Duration::from_millis(20)),
+		duration: Duration::from_millis(25),
+		fields: AccessLogFields {
+			route_kind: Some("gateway"),
+			namespace_id: Some(Id::new_v1(1)),
+			actor_id: Some(Id::new_v1(1)),
+			upstream: Some("envoy:abc".to_string()),
+			wake_duration: Some(Duration::from_millis(10)),
+		},
+	}
+}
+
+#[test]
+fn json_redacts_headers() {
+	let redact_headers = HashSet::from(["authorization".to_string()]);
+	let line = format_json(&entry(), &redact_headers);
+	assert!(line.ends_with('\n'));
+
+	let json = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+	assert_eq!(json["headers"]["authorization"], "[redacted]");
+	assert_eq!(json["headers"]["user-agent"], "curl/8.0");
+	assert_eq!(json["headers"]["accept"], "text/html, application/json");
+	assert!(!line.contains("secret"));
+}
+
+#[test]
+fn json_includes_routing_fields() {
+	let entry = entry();
+	let line = format_json(&entry, &HashSet::new());
+	let json = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+
+	assert_eq!(json["ray_id"], entry.ray_id.to_string());
+	assert_eq!(json["route"], "gateway");
+	assert_eq!(json["upstream"], "envoy:abc");
+	assert_eq!(json["status"], 200);
+	assert_eq!(json["bytes_out"], 512);
+	assert_eq!(json["protocol"], "http");
+	assert_eq!(json["wake_duration_ms"], 10.0);
+	assert_eq!(json["upstream_duration_ms"], 20.0);
+}
+
+#[test]
+fn json_omits_unknown_fields() {
+	let mut entry = entry();
+	entry.fields = AccessLogFields::default();
+	entry.route_duration = None;
+
+	let line = format_json(&entry, &HashSet::new());
+	let json = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+
+	assert!(json.get("actor_id").is_none());
+	assert!(json.get("route_duration_ms").is_none());
+	assert!(json.get("wake_duration_ms").is_none());
+}
+
+#[test]
+fn common_log_format() {
+	assert_eq!(
+		format_common(&entry()),
+		"203.0.113.7 - - [02/Jan/2024:03:04:05 +0000] \"GET /actors?namespace=default HTTP/1.1\" 200 512\n",
+	);
+
+	let mut entry = entry();
+	entry.bytes_out = 0;
+	assert!(format_common(&entry).ends_with("\" 200 -\n"));
+}
+
+#[test]
+fn redacts_actor_tokens_in_path() {
+	let mut entry = entry();
+	entry.path = "/gateway/abc@tok123/rpc?rvt-token=tok456&x=1".to_string();
+
+	let line = format_json(&entry, &HashSet::new());
+	let json = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+	assert_eq!(
+		json["path"],
+		"/gateway/abc@[redacted]/rpc?rvt-token=[redacted]&x=1"
+	);
+
+	let line = format_common(&entry);
+	assert!(line.contains("/gateway/abc@[redacted]/rpc?rvt-token=[redacted]&x=1"));
+	for line in [format_json(&entry, &HashSet::new()), format_common(&entry)] {
+		assert!(!line.contains("tok123"));
+		assert!(!line.contains("tok456"));
+	}
+}
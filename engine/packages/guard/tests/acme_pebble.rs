@@ -0,0 +1,237 @@
+//! Issues and renews certificates against a Pebble ACME server. Skipped unless
+//! `PEBBLE_DIRECTORY_URL` is set:
+//!
+//! ```sh
+//! PEBBLE_VA_NOSLEEP=1 pebble -config test/config/pebble-config.json
+//!
+//! PEBBLE_DIRECTORY_URL=https://localhost:14000/dir \
+//! PEBBLE_ROOT_CERT_PATH=test/certs/pebble.minica.pem \
+//! cargo test -p rivet-guard --test acme_pebble
+//! ```
+//!
+//! Pebble validates the challenges of `PEBBLE_TEST_DOMAIN` (defaults to `localhost`), which has to
+//! resolve to this host. Challenges are answered on Pebble's default validation ports, overridden
+//! with `PEBBLE_HTTP_PORT` and `PEBBLE_TLS_PORT`.
+
+use std::{collections::HashSet, path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::*;
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, routing::get};
+use gas::prelude::*;
+use rivet_config::config::guard::{Acme, AcmeChallenge};
+use rivet_guard::tls::{CertificateKind, CertificateStore, acme};
+use rivet_guard_core::{CertResolverFn, cert_resolver::create_tls_config};
+
+struct Pebble {
+	directory_url: url::Url,
+	root_cert_path: PathBuf,
+	domain: String,
+	http_port: u16,
+	tls_port: u16,
+}
+
+fn pebble() -> Option<Pebble> {
+	let Result::Ok(directory_url) = std::env::var("PEBBLE_DIRECTORY_URL") else {
+		eprintln!("skipping, PEBBLE_DIRECTORY_URL is not set");
+		return None;
+	};
+
+	let port = |name, default| {
+		std::env::var(name)
+			.ok()
+			.map(|port| port.parse().expect("invalid port"))
+			.unwrap_or(default)
+	};
+
+	Some(Pebble {
+		directory_url: directory_url.parse().expect("invalid PEBBLE_DIRECTORY_URL"),
+		root_cert_path: std::env::var("PEBBLE_ROOT_CERT_PATH")
+			.expect("PEBBLE_ROOT_CERT_PATH must be set with PEBBLE_DIRECTORY_URL")
+			.into(),
+		domain: std::env::var("PEBBLE_TEST_DOMAIN").unwrap_or_else(|_| "localhost".to_string()),
+		http_port: port("PEBBLE_HTTP_PORT", 5002),
+		tls_port: port("PEBBLE_TLS_PORT", 5001),
+	})
+}
+
+async fn setup_udb() -> Result<(rivet_test_deps::TestDeps, universaldb::Database)> {
+	let test_id = Uuid::new_v4();
+	let dc_label = 1;
+	let datacenters = [(
+		"test-dc".to_string(),
+		rivet_config::config::topology::Datacenter {
+			name: "test-dc".to_string(),
+			datacenter_label: dc_label,
+			is_leader: true,
+			peer_url: url::Url::parse("http://127.0.0.1:8080")?,
+			public_url: url::Url::parse("http://127.0.0.1:8081")?,
+			proxy_url: None,
+			valid_hosts: None,
+		},
+	)]
+	.into_iter()
+	.collect();
+
+	let api_peer_port = portpicker::pick_unused_port().expect("failed to pick api peer port");
+	let guard_port = portpicker::pick_unused_port().expect("failed to pick guard port");
+	let test_deps = rivet_test_deps::setup_single_datacenter(
+		test_id,
+		dc_label,
+		datacenters,
+		api_peer_port,
+		guard_port,
+	)
+	.await?;
+	let udb = (*test_deps.pools.udb()?).clone();
+
+	Ok((test_deps, udb))
+}
+
+/// Answers HTTP-01 challenges the same way guard's HTTP listener does.
+async fn serve_http_challenges(udb: universaldb::Database, port: u16) -> Result<()> {
+	let app = axum::Router::new().route(
+		"/.well-known/acme-challenge/{token}",
+		get(move |Path(token): Path<String>| {
+			let udb = udb.clone();
+			async move {
+				match acme::read_http_challenge(&udb, &token).await {
+					Result::Ok(Some(key_authorization)) => {
+						(StatusCode::OK, key_authorization).into_response()
+					}
+					_ => StatusCode::NOT_FOUND.into_response(),
+				}
+			}
+		}),
+	);
+
+	let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+	tokio::spawn(async move { axum::serve(listener, app).await });
+
+	Ok(())
+}
+
+/// Answers TLS-ALPN-01 challenges with the TLS config guard's HTTPS listener uses.
+async fn serve_tls_alpn_challenges(udb: universaldb::Database, port: u16) -> Result<()> {
+	let _ = rustls::crypto::ring::default_provider().install_default();
+
+	let store = Arc::new(CertificateStore::default());
+
+	let sync_store = store.clone();
+	tokio::spawn(async move {
+		loop {
+			if let Err(err) = acme::sync_tls_alpn_challenges(&udb, &sync_store).await {
+				tracing::warn!(?err, "failed to sync tls-alpn-01 challenges");
+			}
+
+			tokio::time::sleep(Duration::from_millis(200)).await;
+		}
+	});
+
+	let resolver_fn: CertResolverFn =
+		Arc::new(move |req| store.resolve(&HashSet::new(), req).map_err(Into::into));
+	let acceptor =
+		tokio_rustls::TlsAcceptor::from(Arc::new(create_tls_config(resolver_fn, true, false)));
+
+	let listener = tokio::net::TcpListener::bind(("0.0.0.0", port)).await?;
+	tokio::spawn(async move {
+		loop {
+			let Result::Ok((stream, _)) = listener.accept().await else {
+				continue;
+			};
+
+			// Pebble validates the challenge certificate during the handshake
+			let acceptor = acceptor.clone();
+			tokio::spawn(async move {
+				let _ = acceptor.accept(stream).await;
+			});
+		}
+	});
+
+	Ok(())
+}
+
+fn leaf_der(cert_chain_pem: &str) -> Vec<u8> {
+	rustls_pemfile::certs(&mut cert_chain_pem.as_bytes())
+		.next()
+		.expect("no certificate in chain")
+		.expect("failed to parse certificate")
+		.to_vec()
+}
+
+async fn issue_and_renew(challenge: AcmeChallenge) -> Result<()> {
+	let Some(pebble) = pebble() else {
+		return Ok(());
+	};
+
+	let (_test_deps, udb) = setup_udb().await?;
+	match challenge {
+		AcmeChallenge::Http01 => serve_http_challenges(udb.clone(), pebble.http_port).await?,
+		AcmeChallenge::TlsAlpn01 => serve_tls_alpn_challenges(udb.clone(), pebble.tls_port).await?,
+	}
+
+	let mut acme_config = Acme {
+		directory_url: pebble.directory_url.clone(),
+		directory_root_cert_path: Some(pebble.root_cert_path.clone()),
+		contact_emails: vec!["admin@example.com".to_string()],
+		api_domains: vec![pebble.domain.clone()],
+		actor_domains: Vec::new(),
+		challenge: Some(challenge),
+		renew_before_ms: None,
+		check_interval_ms: None,
+	};
+	let store = CertificateStore::default();
+
+	// Issue
+	acme::ensure_certificates(&udb, &acme_config, &store).await?;
+
+	let issued = acme::read_certificate(&udb, CertificateKind::Api)
+		.await?
+		.context("certificate was not stored")?;
+	assert_eq!(issued.domains, vec![pebble.domain.clone()]);
+	assert!(issued.not_after_ts > util::timestamp::now());
+
+	let served = store
+		.get(CertificateKind::Api)
+		.context("certificate was not installed")?;
+	assert_eq!(
+		served.cert[0].to_vec(),
+		leaf_der(&issued.pem.cert_chain_pem)
+	);
+
+	// Not due for renewal, the stored certificate is kept
+	acme::ensure_certificates(&udb, &acme_config, &store).await?;
+
+	let kept = acme::read_certificate(&udb, CertificateKind::Api)
+		.await?
+		.context("certificate was not stored")?;
+	assert_eq!(kept.pem.cert_chain_pem, issued.pem.cert_chain_pem);
+
+	// Renew, every certificate is due once the renewal window is longer than its lifetime
+	acme_config.renew_before_ms = Some(100 * 365 * 24 * 60 * 60 * 1000);
+	acme::ensure_certificates(&udb, &acme_config, &store).await?;
+
+	let renewed = acme::read_certificate(&udb, CertificateKind::Api)
+		.await?
+		.context("certificate was not stored")?;
+	assert_ne!(renewed.pem.cert_chain_pem, issued.pem.cert_chain_pem);
+
+	let served = store
+		.get(CertificateKind::Api)
+		.context("certificate was not installed")?;
+	assert_eq!(
+		served.cert[0].to_vec(),
+		leaf_der(&renewed.pem.cert_chain_pem)
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn issues_and_renews_with_http_01() -> Result<()> {
+	issue_and_renew(AcmeChallenge::Http01).await
+}
+
+#[tokio::test]
+async fn issues_and_renews_with_tls_alpn_01() -> Result<()> {
+	issue_and_renew(AcmeChallenge::TlsAlpn01).await
+}
@@ -0,0 +1,214 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hyper::header::{HeaderMap, HeaderValue};
+use ring::{
+	rand::SystemRandom,
+	signature::{Ed25519KeyPair, KeyPair},
+};
+use rivet_guard::auth::{
+	AuthIdentity, X_RIVET_AUTH_CLAIMS, X_RIVET_AUTH_RULE, X_RIVET_AUTH_SUBJECT, set_auth_headers,
+	sign_url_token, verify_api_key, verify_jwt, verify_signed_url_token,
+};
+use rivet_types::namespaces::{ApiKey, Jwk, Jwks};
+use sha2::{Digest, Sha256};
+
+const NOW: i64 = 1_700_000_000;
+
+/// Local stand-in for an identity provider's JWKS.
+fn issuer() -> (Ed25519KeyPair, Jwks) {
+	let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+	let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+	let jwks = Jwks {
+		keys: vec![Jwk {
+			kty: "OKP".to_string(),
+			kid: Some("key-1".to_string()),
+			alg: None,
+			crv: Some("Ed25519".to_string()),
+			n: None,
+			e: None,
+			x: Some(URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref())),
+			y: None,
+		}],
+	};
+
+	(key_pair, jwks)
+}
+
+fn sign_jwt(key_pair: &Ed25519KeyPair, claims: serde_json::Value) -> String {
+	let header = URL_SAFE_NO_PAD.encode(br#"{"alg":"EdDSA","kid":"key-1"}"#);
+	let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).unwrap());
+	let message = format!("{header}.{claims}");
+	let signature = URL_SAFE_NO_PAD.encode(key_pair.sign(message.as_bytes()).as_ref());
+
+	format!("{message}.{signature}")
+}
+
+#[test]
+fn jwt_verification() {
+	let (key_pair, jwks) = issuer();
+
+	let token = sign_jwt(
+		&key_pair,
+		serde_json::json!({ "sub": "user-1", "iss": "https://auth.example.com", "aud": ["rivet"], "exp": NOW + 60 }),
+	);
+	let claims = verify_jwt(
+		&token,
+		&jwks,
+		Some("https://auth.example.com"),
+		Some("rivet"),
+		NOW,
+	)
+	.unwrap();
+	assert_eq!(claims["sub"], "user-1");
+
+	assert_eq!(
+		verify_jwt(&token, &jwks, Some("https://other.example.com"), None, NOW).unwrap_err(),
+		"jwt has wrong issuer"
+	);
+	assert_eq!(
+		verify_jwt(&token, &jwks, None, Some("other"), NOW).unwrap_err(),
+		"jwt has wrong audience"
+	);
+	assert_eq!(
+		verify_jwt(&token, &jwks, None, None, NOW + 3600).unwrap_err(),
+		"jwt expired"
+	);
+
+	// Signed by a key that is not in the JWKS
+	let (other_key_pair, _) = issuer();
+	let token = sign_jwt(
+		&other_key_pair,
+		serde_json::json!({ "sub": "user-1", "exp": NOW + 60 }),
+	);
+	assert_eq!(
+		verify_jwt(&token, &jwks, None, None, NOW).unwrap_err(),
+		"jwt signature does not match any key"
+	);
+
+	// Tampered claims
+	let token = sign_jwt(
+		&key_pair,
+		serde_json::json!({ "sub": "user-1", "exp": NOW + 60 }),
+	);
+	let mut parts = token.split('.').collect::<Vec<_>>();
+	let forged = URL_SAFE_NO_PAD.encode(br#"{"sub":"admin","exp":1700000060}"#);
+	parts[1] = &forged;
+	assert!(verify_jwt(&parts.join("."), &jwks, None, None, NOW).is_err());
+
+	let token = sign_jwt(&key_pair, serde_json::json!({ "sub": "user-1" }));
+	assert_eq!(
+		verify_jwt(&token, &jwks, None, None, NOW).unwrap_err(),
+		"jwt is missing exp claim"
+	);
+}
+
+#[test]
+fn api_key_verification() {
+	let keys = [
+		ApiKey {
+			name: "backend".to_string(),
+			sha256: hex::encode(Sha256::digest(b"secret-1")),
+		},
+		ApiKey {
+			name: "worker".to_string(),
+			sha256: hex::encode(Sha256::digest(b"secret-2")).to_uppercase(),
+		},
+	];
+
+	assert_eq!(verify_api_key("secret-1", &keys).unwrap().name, "backend");
+	assert_eq!(verify_api_key("secret-2", &keys).unwrap().name, "worker");
+	assert!(verify_api_key("secret-3", &keys).is_err());
+}
+
+#[test]
+fn signed_url_verification() {
+	let secret = "0123456789abcdef0123456789abcdef";
+	let token = sign_url_token(secret, NOW + 60, "GET", "room", "lobby", "/ws?room=1");
+	let verify = |token: &str, secret: &str, method, actor_name, actor_key, path, now| {
+		verify_signed_url_token(token, secret, method, actor_name, actor_key, path, now)
+	};
+
+	assert!(verify(&token, secret, "GET", "room", "lobby", "/ws?room=1", NOW).is_ok());
+	assert_eq!(
+		verify(
+			&token,
+			secret,
+			"GET",
+			"room",
+			"lobby",
+			"/ws?room=1",
+			NOW + 120
+		)
+		.unwrap_err(),
+		"signed url expired"
+	);
+	assert!(verify(&token, secret, "GET", "room", "lobby", "/ws?room=2", NOW).is_err());
+	assert!(verify(&token, secret, "GET", "chat", "lobby", "/ws?room=1", NOW).is_err());
+	assert!(verify(&token, "wrong", "GET", "room", "lobby", "/ws?room=1", NOW).is_err());
+	// Tokens are bound to the actor key and the method
+	assert!(verify(&token, secret, "GET", "room", "other", "/ws?room=1", NOW).is_err());
+	assert!(verify(&token, secret, "POST", "room", "lobby", "/ws?room=1", NOW).is_err());
+
+	// Extending the expiry invalidates the signature
+	let (_, signature) = token.split_once('.').unwrap();
+	let extended = format!("{}.{signature}", NOW + 3600);
+	assert!(verify(&extended, secret, "GET", "room", "lobby", "/ws?room=1", NOW).is_err());
+}
+
+#[test]
+fn auth_headers_replace_client_headers() {
+	let mut headers = HeaderMap::new();
+	headers.insert(X_RIVET_AUTH_RULE, HeaderValue::from_static("forged"));
+	headers.insert(X_RIVET_AUTH_SUBJECT, HeaderValue::from_static("admin"));
+	headers.insert("x-rivet-auth-custom", HeaderValue::from_static("forged"));
+	headers.insert("x-other", HeaderValue::from_static("kept"));
+
+	let mut stripped = headers.clone();
+	set_auth_headers(&mut stripped, None).unwrap();
+	assert_eq!(stripped.len(), 1);
+	assert_eq!(stripped["x-other"], "kept");
+
+	let identity = AuthIdentity {
+		rule: "users".to_string(),
+		method: "jwt",
+		subject: Some("user-1".to_string()),
+		claims: serde_json::json!({ "sub": "user-1" }).as_object().cloned(),
+	};
+	set_auth_headers(&mut headers, Some(&identity)).unwrap();
+	assert_eq!(headers[X_RIVET_AUTH_RULE], "users");
+	assert_eq!(headers[X_RIVET_AUTH_SUBJECT], "user-1");
+	assert!(headers.get("x-rivet-auth-custom").is_none());
+
+	let claims = URL_SAFE_NO_PAD
+		.decode(headers[X_RIVET_AUTH_CLAIMS].as_bytes())
+		.unwrap();
+	assert_eq!(
+		serde_json::from_slice::<serde_json::Value>(&claims).unwrap(),
+		serde_json::json!({ "sub": "user-1" })
+	);
+}
+
+#[test]
+fn jwks_urls_must_be_public_https() {
+	use rivet_pools::reqwest::check_public_url;
+
+	assert!(check_public_url("https://issuer.example.com/.well-known/jwks.json").is_ok());
+	assert!(check_public_url("https://1.1.1.1/jwks.json").is_ok());
+
+	for url in [
+		"http://issuer.example.com/jwks.json",
+		"https://localhost/jwks.json",
+		"https://api.localhost./jwks.json",
+		"https://127.0.0.1/jwks.json",
+		"https://10.0.0.1/jwks.json",
+		"https://192.168.1.1/jwks.json",
+		"https://169.254.169.254/latest/meta-data",
+		"https://100.64.0.1/jwks.json",
+		"https://[::1]/jwks.json",
+		"https://[fe80::1]/jwks.json",
+		"https://[fd00::1]/jwks.json",
+		"https://[::ffff:127.0.0.1]/jwks.json",
+		"not a url",
+	] {
+		assert!(check_public_url(url).is_err(), "{url} should be rejected");
+	}
+}
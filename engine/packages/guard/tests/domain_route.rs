@@ -0,0 +1,128 @@
+use gas::prelude::Id;
+use namespace::keys::domain_route::ResolvedDomainRoute;
+use rivet_guard::routing::{
+	actor_path::QueryActorQuery,
+	domain_route::{match_route, query_gateway_path},
+};
+use rivet_types::namespaces::{DomainRoute, DomainRouteActor};
+
+fn route(
+	hostname: &str,
+	path_prefix: Option<&str>,
+	key: &[&str],
+	pool_name: Option<&str>,
+) -> ResolvedDomainRoute {
+	ResolvedDomainRoute {
+		namespace_id: Id::new_v1(1),
+		namespace_name: "default".to_string(),
+		route: DomainRoute {
+			hostname: hostname.to_string(),
+			path_prefix: path_prefix.map(ToString::to_string),
+			actor: DomainRouteActor {
+				name: "room".to_string(),
+				key: key.iter().map(ToString::to_string).collect(),
+				pool_name: pool_name.map(ToString::to_string),
+			},
+		},
+	}
+}
+
+fn key(query: &QueryActorQuery) -> &[String] {
+	match query {
+		QueryActorQuery::Get { key, .. } | QueryActorQuery::GetOrCreate { key, .. } => key,
+	}
+}
+
+#[test]
+fn subdomain_key() {
+	let routes = [route("*.example.com", None, &["room-{subdomain}"], None)];
+
+	let matched = match_route(&routes, Some("123"), "/index.html?x=1", false)
+		.unwrap()
+		.unwrap();
+	assert_eq!(key(&matched.query), ["room-123"]);
+	assert_eq!(matched.stripped_path, "/index.html?x=1");
+	assert!(matches!(matched.query, QueryActorQuery::Get { .. }));
+
+	// Subdomain placeholders need a wildcard match
+	assert!(match_route(&routes, None, "/", false).unwrap().is_none());
+}
+
+#[test]
+fn path_key_strips_segments() {
+	let routes = [route(
+		"api.customer.com",
+		Some("/rooms"),
+		&["{path.0}"],
+		Some("default"),
+	)];
+
+	let matched = match_route(&routes, None, "/rooms/a%20b/chat/", true)
+		.unwrap()
+		.unwrap();
+	assert_eq!(key(&matched.query), ["a b"]);
+	assert_eq!(matched.stripped_path, "/chat/");
+	assert!(matches!(
+		matched.query,
+		QueryActorQuery::GetOrCreate {
+			skip_ready_wait: true,
+			..
+		}
+	));
+
+	let matched = match_route(&routes, None, "/rooms/abc?x=1", false)
+		.unwrap()
+		.unwrap();
+	assert_eq!(matched.stripped_path, "/?x=1");
+
+	// Missing key segment
+	assert!(
+		match_route(&routes, None, "/rooms", false)
+			.unwrap()
+			.is_none()
+	);
+	// Prefix only matches whole segments
+	assert!(
+		match_route(&routes, None, "/roomsabc/x", false)
+			.unwrap()
+			.is_none()
+	);
+}
+
+#[test]
+fn longest_prefix_wins() {
+	let routes = [
+		route("api.customer.com", None, &["root"], None),
+		route("api.customer.com", Some("/v2"), &["v2"], None),
+	];
+
+	let matched = match_route(&routes, None, "/v2/users", false)
+		.unwrap()
+		.unwrap();
+	assert_eq!(key(&matched.query), ["v2"]);
+	assert_eq!(matched.stripped_path, "/users");
+
+	let matched = match_route(&routes, None, "/v3/users", false)
+		.unwrap()
+		.unwrap();
+	assert_eq!(key(&matched.query), ["root"]);
+	assert_eq!(matched.stripped_path, "/v3/users");
+}
+
+#[test]
+fn gateway_path_for_peer_datacenter() {
+	let routes = [route(
+		"*.example.com",
+		None,
+		&["{subdomain}", "main"],
+		Some("default"),
+	)];
+	let matched = match_route(&routes, Some("abc"), "/ws?token=1", false)
+		.unwrap()
+		.unwrap();
+
+	assert_eq!(
+		query_gateway_path(&matched.query, &matched.stripped_path),
+		"/gateway/room/ws?rvt-namespace=default&rvt-method=getOrCreate&rvt-pool=default&rvt-key=abc%2Cmain&token=1",
+	);
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use gas::prelude::util::geo::{Coordinates, GeoIpDatabase};
+use rivet_guard::geo::{GeoLocator, follow_first_client};
+
+const US_EAST: u16 = 1;
+const EU_CENTRAL: u16 = 2;
+const AP_SOUTHEAST: u16 = 3;
+
+fn locator() -> GeoLocator {
+	let db = GeoIpDatabase::parse(
+		"\
+network,latitude,longitude
+1.0.0.0/24,40.7128,-74.0060
+2.0.0.0/24,48.8566,2.3522
+3.0.0.0/24,-33.8688,151.2093
+",
+	)
+	.unwrap();
+
+	GeoLocator::new(
+		db,
+		HashMap::from([
+			(US_EAST, Coordinates::new(38.9, -77.0)),
+			(EU_CENTRAL, Coordinates::new(50.1, 8.7)),
+			(AP_SOUTHEAST, Coordinates::new(1.35, 103.8)),
+		]),
+	)
+}
+
+#[test]
+fn nearest_picks_closest_datacenter() {
+	let geo = locator();
+	let all = [US_EAST, EU_CENTRAL, AP_SOUTHEAST];
+
+	assert_eq!(geo.nearest("1.0.0.1".parse().unwrap(), &all), Some(US_EAST));
+	assert_eq!(
+		geo.nearest("2.0.0.1".parse().unwrap(), &all),
+		Some(EU_CENTRAL)
+	);
+	assert_eq!(
+		geo.nearest("3.0.0.1".parse().unwrap(), &all),
+		Some(AP_SOUTHEAST)
+	);
+}
+
+#[test]
+fn nearest_only_considers_given_datacenters() {
+	let geo = locator();
+
+	assert_eq!(
+		geo.nearest("2.0.0.1".parse().unwrap(), &[US_EAST, AP_SOUTHEAST]),
+		Some(US_EAST)
+	);
+	// Datacenters without a location are skipped
+	assert_eq!(
+		geo.nearest("2.0.0.1".parse().unwrap(), &[4, AP_SOUTHEAST]),
+		Some(AP_SOUTHEAST)
+	);
+	assert_eq!(geo.nearest("2.0.0.1".parse().unwrap(), &[4]), None);
+}
+
+#[test]
+fn nearest_requires_located_client() {
+	let geo = locator();
+
+	assert_eq!(
+		geo.nearest("9.9.9.9".parse().unwrap(), &[US_EAST, EU_CENTRAL]),
+		None
+	);
+}
+
+#[test]
+fn from_config_rejects_unknown_datacenters() {
+	let path = std::env::temp_dir().join(format!("rivet-guard-geoip-{}.csv", std::process::id()));
+	std::fs::write(&path, "network,latitude,longitude\n1.0.0.0/24,40.7,-74.0\n").unwrap();
+
+	let config = |datacenter: &str| {
+		let root = serde_json::from_value::<rivet_config::config::Root>(serde_json::json!({
+			"guard": {
+				"geo": {
+					"geoip_database_path": path,
+					"datacenters": {
+						datacenter: { "latitude": 38.9, "longitude": -77.0 },
+					},
+				},
+			},
+		}))
+		.unwrap();
+		rivet_config::Config::from_root(root)
+	};
+
+	assert!(
+		GeoLocator::from_config(&config("default"))
+			.unwrap()
+			.is_some()
+	);
+	let err = GeoLocator::from_config(&config("us-typo"))
+		.err()
+		.expect("unknown datacenter should be rejected");
+	assert!(err.to_string().contains("us-typo"));
+
+	std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn follow_first_client_keeps_first_client_region() {
+	let geo = locator();
+	let all = [US_EAST, EU_CENTRAL, AP_SOUTHEAST];
+
+	// The first client is in the US and claims us-east
+	let first = geo.nearest("1.0.0.1".parse().unwrap(), &all);
+	assert_eq!(follow_first_client(first, first, &all), Some(US_EAST));
+
+	// A second client in Europe still lands in the first client's datacenter
+	let nearest = geo.nearest("2.0.0.1".parse().unwrap(), &all);
+	assert_eq!(nearest, Some(EU_CENTRAL));
+	assert_eq!(follow_first_client(first, nearest, &all), Some(US_EAST));
+
+	// Falls back to the nearest datacenter once the first one stops running the pool
+	assert_eq!(
+		follow_first_client(first, nearest, &[EU_CENTRAL, AP_SOUTHEAST]),
+		Some(EU_CENTRAL)
+	);
+	// No claim yet and an unlocated client
+	assert_eq!(follow_first_client(None, None, &all), None);
+}
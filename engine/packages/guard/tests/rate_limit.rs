@@ -0,0 +1,72 @@
+use rivet_guard::rate_limit::SlidingWindow;
+
+const PERIOD_MS: i64 = 1000;
+
+fn admit(window: &mut SlidingWindow, now: i64) -> Option<i64> {
+	let res = window.check(now);
+	if res.is_none() {
+		window.record();
+	}
+	res
+}
+
+#[test]
+fn admits_up_to_limit_within_window() {
+	let mut window = SlidingWindow::new(3, PERIOD_MS, 10_000);
+
+	assert_eq!(admit(&mut window, 10_000), None);
+	assert_eq!(admit(&mut window, 10_100), None);
+	assert_eq!(admit(&mut window, 10_200), None);
+
+	let retry_after_ms = admit(&mut window, 10_300).unwrap();
+	assert!(retry_after_ms > 0);
+	assert!(retry_after_ms <= 2 * PERIOD_MS);
+}
+
+#[test]
+fn previous_window_is_weighted_by_overlap() {
+	let mut window = SlidingWindow::new(4, PERIOD_MS, 10_000);
+	for _ in 0..4 {
+		assert_eq!(admit(&mut window, 10_500), None);
+	}
+
+	// At the start of the next window the previous window still fully overlaps
+	assert!(admit(&mut window, 11_000).is_some());
+
+	// Halfway through the next window half of the previous requests still count
+	assert_eq!(admit(&mut window, 11_500), None);
+	assert_eq!(admit(&mut window, 11_500), None);
+	assert!(admit(&mut window, 11_500).is_some());
+}
+
+#[test]
+fn retry_after_admits_next_request() {
+	let mut window = SlidingWindow::new(2, PERIOD_MS, 10_000);
+	assert_eq!(admit(&mut window, 10_000), None);
+	assert_eq!(admit(&mut window, 10_000), None);
+
+	let retry_after_ms = admit(&mut window, 10_200).unwrap();
+	assert_eq!(admit(&mut window, 10_200 + retry_after_ms), None);
+}
+
+#[test]
+fn old_windows_are_forgotten() {
+	let mut window = SlidingWindow::new(1, PERIOD_MS, 10_000);
+	assert_eq!(admit(&mut window, 10_000), None);
+	assert!(admit(&mut window, 10_900).is_some());
+
+	// Two windows later nothing overlaps anymore
+	assert_eq!(admit(&mut window, 12_000), None);
+}
+
+#[test]
+fn denied_requests_are_not_counted() {
+	let mut window = SlidingWindow::new(2, PERIOD_MS, 10_000);
+	assert_eq!(admit(&mut window, 10_000), None);
+	assert_eq!(admit(&mut window, 10_000), None);
+	for _ in 0..10 {
+		assert!(admit(&mut window, 10_500).is_some());
+	}
+
+	assert_eq!(admit(&mut window, 11_500), None);
+}
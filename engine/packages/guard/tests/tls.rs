@@ -0,0 +1,143 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
+
+use rivet_guard::tls::{CertificateKind, CertificateStore, certified_key_from_pem};
+use rivet_guard_core::CertRequest;
+use rustls::sign::CertifiedKey;
+
+fn generate(domain: &str) -> (String, String) {
+	let key_pair = rcgen::KeyPair::generate().unwrap();
+	let cert = rcgen::CertificateParams::new(vec![domain.to_string()])
+		.unwrap()
+		.self_signed(&key_pair)
+		.unwrap();
+
+	(cert.pem(), key_pair.serialize_pem())
+}
+
+fn certified_key(domain: &str) -> Arc<CertifiedKey> {
+	let (cert, key) = generate(domain);
+	certified_key_from_pem(cert.as_bytes(), key.as_bytes()).unwrap()
+}
+
+fn request(server_name: &str) -> CertRequest<'_> {
+	CertRequest {
+		server_name,
+		acme_tls_alpn: false,
+	}
+}
+
+#[test]
+fn resolves_certificate_by_server_name() {
+	let api = certified_key("api.example.com");
+	let actor = certified_key("*.actors.example.com");
+
+	let store = CertificateStore::default();
+	store.set(CertificateKind::Api, api.clone());
+	store.set(CertificateKind::Actor, actor.clone());
+
+	let api_hosts = HashSet::from(["api.example.com".to_string()]);
+
+	let resolved = store
+		.resolve(&api_hosts, request("api.example.com"))
+		.unwrap();
+	assert!(Arc::ptr_eq(&resolved, &api));
+
+	let resolved = store
+		.resolve(&api_hosts, request("API.Example.com"))
+		.unwrap();
+	assert!(Arc::ptr_eq(&resolved, &api));
+
+	let resolved = store
+		.resolve(&api_hosts, request("foo.actors.example.com"))
+		.unwrap();
+	assert!(Arc::ptr_eq(&resolved, &actor));
+}
+
+#[test]
+fn falls_back_to_api_certificate_without_actor_certificate() {
+	let api = certified_key("api.example.com");
+
+	let store = CertificateStore::default();
+	store.set(CertificateKind::Api, api.clone());
+
+	let resolved = store
+		.resolve(&HashSet::new(), request("foo.actors.example.com"))
+		.unwrap();
+	assert!(Arc::ptr_eq(&resolved, &api));
+}
+
+#[test]
+fn replaced_certificate_applies_to_new_handshakes() {
+	let old = certified_key("api.example.com");
+	let new = certified_key("api.example.com");
+
+	let store = CertificateStore::default();
+	store.set(CertificateKind::Api, old.clone());
+	let api_hosts = HashSet::from(["api.example.com".to_string()]);
+
+	let before = store
+		.resolve(&api_hosts, request("api.example.com"))
+		.unwrap();
+	store.set(CertificateKind::Api, new.clone());
+	let after = store
+		.resolve(&api_hosts, request("api.example.com"))
+		.unwrap();
+
+	assert!(Arc::ptr_eq(&before, &old));
+	assert!(Arc::ptr_eq(&after, &new));
+}
+
+#[test]
+fn resolves_tls_alpn_challenge_certificate() {
+	let api = certified_key("api.example.com");
+	let challenge = certified_key("api.example.com");
+
+	let store = CertificateStore::default();
+	store.set(CertificateKind::Api, api.clone());
+	store.set_tls_alpn_challenges(HashMap::from([(
+		"api.example.com".to_string(),
+		challenge.clone(),
+	)]));
+
+	let api_hosts = HashSet::from(["api.example.com".to_string()]);
+
+	let resolved = store
+		.resolve(
+			&api_hosts,
+			CertRequest {
+				server_name: "api.example.com",
+				acme_tls_alpn: true,
+			},
+		)
+		.unwrap();
+	assert!(Arc::ptr_eq(&resolved, &challenge));
+
+	// Challenge certificates are never served to regular clients
+	let resolved = store
+		.resolve(&api_hosts, request("api.example.com"))
+		.unwrap();
+	assert!(Arc::ptr_eq(&resolved, &api));
+
+	assert!(
+		store
+			.resolve(
+				&api_hosts,
+				CertRequest {
+					server_name: "other.example.com",
+					acme_tls_alpn: true,
+				},
+			)
+			.is_err()
+	);
+}
+
+#[test]
+fn rejects_mismatched_key() {
+	let (cert, _) = generate("api.example.com");
+	let (_, key) = generate("api.example.com");
+
+	assert!(certified_key_from_pem(cert.as_bytes(), key.as_bytes()).is_err());
+}
@@ -1,8 +1,10 @@
+mod rotating_file;
 #[cfg(unix)]
 mod unix;
 #[cfg(windows)]
 mod windows;
 
+pub use rotating_file::RotatingFile;
 #[cfg(unix)]
 pub use unix::Logs;
 #[cfg(windows)]
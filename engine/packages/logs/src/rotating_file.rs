@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use anyhow::*;
+use chrono::{Duration, TimeZone, Utc};
+use tokio::{
+	fs,
+	io::{AsyncWriteExt, BufWriter},
+};
+
+/// Appends to a file that is rotated on the same daily schedule as [`crate::Logs`]. Unlike
+/// `Logs`, stdout and stderr are left untouched so several rotating files can share a directory.
+pub struct RotatingFile {
+	path: PathBuf,
+	prefix: String,
+	retention: Duration,
+
+	next_rotation: chrono::DateTime<Utc>,
+	file: Option<BufWriter<fs::File>>,
+}
+
+impl RotatingFile {
+	pub fn new(path: PathBuf, prefix: impl Into<String>, retention: std::time::Duration) -> Self {
+		RotatingFile {
+			path,
+			prefix: prefix.into(),
+			retention: chrono::Duration::from_std(retention).expect("invalid retention duration"),
+
+			next_rotation: Utc.timestamp_opt(0, 0).unwrap(),
+			file: None,
+		}
+	}
+
+	/// Writes `buf` to the current file, rotating first if the day changed.
+	pub async fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+		if self.file.is_none() || Utc::now() >= self.next_rotation {
+			self.rotate().await?;
+		}
+
+		let file = self.file.as_mut().context("file not open")?;
+		file.write_all(buf).await?;
+
+		Ok(())
+	}
+
+	pub async fn flush(&mut self) -> Result<()> {
+		if let Some(file) = &mut self.file {
+			file.flush().await?;
+		}
+
+		Ok(())
+	}
+
+	async fn rotate(&mut self) -> Result<()> {
+		// Flush the previous file before replacing it
+		self.flush().await?;
+
+		let now = Utc::now();
+		self.next_rotation = Utc.from_utc_datetime(
+			&(now
+				.date_naive()
+				.and_hms_opt(0, 0, 0)
+				.context("invalid date")?
+				+ Duration::days(1)),
+		);
+
+		fs::create_dir_all(&self.path).await?;
+
+		let file_name = format!("{}-{}", self.prefix, now.format("%m-%d-%y"));
+		let file = fs::OpenOptions::new()
+			.write(true)
+			.create(true)
+			.append(true)
+			.open(self.path.join(file_name))
+			.await?;
+		self.file = Some(BufWriter::new(file));
+
+		self.prune().await
+	}
+
+	/// Remove files with this file's prefix that are older than `self.retention`.
+	async fn prune(&self) -> Result<()> {
+		let mut entries = fs::read_dir(&self.path).await?;
+		let prefix = format!("{}-", self.prefix);
+		let mut pruned = 0;
+
+		while let Some(entry) = entries.next_entry().await? {
+			if !entry.file_name().to_string_lossy().starts_with(&prefix) {
+				continue;
+			}
+
+			let metadata = entry.metadata().await?;
+			let modified = chrono::DateTime::<Utc>::from(metadata.modified()?);
+
+			if modified < Utc::now() - self.retention {
+				pruned += 1;
+				fs::remove_file(entry.path()).await?;
+			}
+		}
+
+		if pruned != 0 {
+			tracing::debug!(prefix=%self.prefix, "pruned {pruned} rotating files");
+		}
+
+		Ok(())
+	}
+}
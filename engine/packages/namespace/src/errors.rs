@@ -26,6 +26,62 @@ pub enum Namespace {
 		"Failed to update namespace: {reason}"
 	)]
 	InvalidUpdate { reason: String },
+
+	#[error(
+		"invalid_rate_limit_policy",
+		"Invalid rate limit policy.",
+		"Invalid rate limit policy: {reason}"
+	)]
+	InvalidRateLimitPolicy { reason: String },
+
+	#[error(
+		"invalid_domain_route",
+		"Invalid domain route.",
+		"Invalid domain route: {reason}"
+	)]
+	InvalidDomainRoute { reason: String },
+
+	#[error(
+		"domain_in_use",
+		"The domain is already routed by another namespace.",
+		"The domain {hostname} is already routed by another namespace."
+	)]
+	DomainInUse { hostname: String },
+
+	#[error("domain_route_not_found", "The domain route does not exist.")]
+	DomainRouteNotFound,
+
+	#[error(
+		"domain_not_verified",
+		"Control of the domain could not be verified.",
+		"Control of the domain {hostname} could not be verified. Add a TXT record named {record_name} with the value {record_value}."
+	)]
+	DomainNotVerified {
+		hostname: String,
+		record_name: String,
+		record_value: String,
+	},
+
+	#[error(
+		"invalid_auth_policy",
+		"Invalid auth policy.",
+		"Invalid auth policy: {reason}"
+	)]
+	InvalidAuthPolicy { reason: String },
+
+	#[error(
+		"invalid_canary_policy",
+		"Invalid canary policy.",
+		"Invalid canary policy: {reason}"
+	)]
+	InvalidCanaryPolicy { reason: String },
+
+	#[error(
+		"invalid_region_policy",
+		"Invalid region policy.",
+		"Invalid region policy: {reason}"
+	)]
+	InvalidRegionPolicy { reason: String },
 }
 
 #[derive(RivetError, Debug, Deserialize, Serialize)]
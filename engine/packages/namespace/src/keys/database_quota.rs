@@ -0,0 +1,160 @@
+use anyhow::Result;
+use gas::prelude::*;
+use universaldb::{prelude::*, utils::IsolationLevel};
+
+/// A storage limit on the SQLite databases of a namespace in this datacenter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::FromRepr, strum::EnumIter)]
+pub enum DatabaseLimit {
+	/// Commits that would grow the namespace past this limit are rejected.
+	Hard = 0,
+	/// Commits past this limit succeed but are logged and counted.
+	Soft = 1,
+}
+
+/// Storage limits of a namespace's SQLite databases. Unset limits are unlimited.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseQuota {
+	pub hard_limit_bytes: Option<i64>,
+	pub soft_limit_bytes: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct DatabaseQuotaKey {
+	pub namespace_id: Id,
+	pub limit: DatabaseLimit,
+}
+
+impl DatabaseQuotaKey {
+	pub fn new(namespace_id: Id, limit: DatabaseLimit) -> Self {
+		DatabaseQuotaKey {
+			namespace_id,
+			limit,
+		}
+	}
+}
+
+impl FormalKey for DatabaseQuotaKey {
+	/// Bytes.
+	type Value = i64;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(i64::from_be_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.to_be_bytes().to_vec())
+	}
+}
+
+impl TuplePack for DatabaseQuotaKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (DATA, self.namespace_id, DATABASE_QUOTA, self.limit as usize);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for DatabaseQuotaKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, namespace_id, _, limit)) =
+			<(usize, Id, usize, usize)>::unpack(input, tuple_depth)?;
+		let limit = DatabaseLimit::from_repr(limit).ok_or_else(|| {
+			PackError::Message(format!("invalid database limit `{limit}` in key").into())
+		})?;
+
+		let v = DatabaseQuotaKey {
+			namespace_id,
+			limit,
+		};
+
+		Ok((input, v))
+	}
+}
+
+/// Present while commits leave a namespace over its database soft limit. Lets the commit that
+/// crosses the limit be told apart from later ones, since the usage rollup lags behind commits.
+#[derive(Debug)]
+pub struct SoftLimitExceededKey {
+	pub namespace_id: Id,
+}
+
+impl SoftLimitExceededKey {
+	pub fn new(namespace_id: Id) -> Self {
+		SoftLimitExceededKey { namespace_id }
+	}
+}
+
+impl FormalKey for SoftLimitExceededKey {
+	type Value = ();
+
+	fn deserialize(&self, _raw: &[u8]) -> Result<Self::Value> {
+		Ok(())
+	}
+
+	fn serialize(&self, _value: Self::Value) -> Result<Vec<u8>> {
+		Ok(Vec::new())
+	}
+}
+
+impl TuplePack for SoftLimitExceededKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (DATA, self.namespace_id, SOFT_LIMIT_EXCEEDED);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for SoftLimitExceededKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, namespace_id, _)) = <(usize, Id, usize)>::unpack(input, tuple_depth)?;
+
+		let v = SoftLimitExceededKey { namespace_id };
+
+		Ok((input, v))
+	}
+}
+
+/// Reads the database storage limits of a namespace. `tx` must be in the namespace subspace.
+pub async fn read(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	isolation_level: IsolationLevel,
+) -> Result<DatabaseQuota> {
+	let hard_key = DatabaseQuotaKey::new(namespace_id, DatabaseLimit::Hard);
+	let soft_key = DatabaseQuotaKey::new(namespace_id, DatabaseLimit::Soft);
+	let (hard_limit_bytes, soft_limit_bytes) = tokio::try_join!(
+		tx.read_opt(&hard_key, isolation_level),
+		tx.read_opt(&soft_key, isolation_level),
+	)?;
+
+	Ok(DatabaseQuota {
+		hard_limit_bytes,
+		soft_limit_bytes,
+	})
+}
+
+/// Replaces the database storage limits of a namespace. `tx` must be in the namespace subspace.
+///
+/// The next commit over a new soft limit is reported as crossing it.
+pub fn write(tx: &universaldb::Transaction, namespace_id: Id, quota: DatabaseQuota) -> Result<()> {
+	tx.delete(&SoftLimitExceededKey::new(namespace_id));
+
+	for (limit, bytes) in [
+		(DatabaseLimit::Hard, quota.hard_limit_bytes),
+		(DatabaseLimit::Soft, quota.soft_limit_bytes),
+	] {
+		let key = DatabaseQuotaKey::new(namespace_id, limit);
+		match bytes {
+			Some(bytes) => tx.write(&key, bytes)?,
+			None => tx.delete(&key),
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,412 @@
+use anyhow::Result;
+use futures_util::TryStreamExt;
+use gas::prelude::*;
+use rivet_types::namespaces::DomainRoute;
+use universaldb::{options::StreamingMode, prelude::*, utils::IsolationLevel};
+
+use crate::errors;
+
+/// Path prefix used in keys for routes without a prefix.
+const ROOT_PATH_PREFIX: &str = "/";
+
+#[derive(Debug)]
+pub struct DomainRouteKey {
+	pub namespace_id: Id,
+	pub hostname: String,
+	pub path_prefix: String,
+}
+
+impl DomainRouteKey {
+	pub fn new(namespace_id: Id, hostname: String, path_prefix: String) -> Self {
+		DomainRouteKey {
+			namespace_id,
+			hostname,
+			path_prefix,
+		}
+	}
+
+	pub fn subspace(namespace_id: Id) -> DomainRouteSubspaceKey {
+		DomainRouteSubspaceKey::new(namespace_id)
+	}
+}
+
+impl FormalKey for DomainRouteKey {
+	type Value = DomainRoute;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		serde_json::from_slice(raw).map_err(Into::into)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		serde_json::to_vec(&value).map_err(Into::into)
+	}
+}
+
+impl TuplePack for DomainRouteKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (
+			DATA,
+			self.namespace_id,
+			DOMAIN_ROUTE,
+			&self.hostname,
+			&self.path_prefix,
+		);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for DomainRouteKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, namespace_id, _, hostname, path_prefix)) =
+			<(usize, Id, usize, String, String)>::unpack(input, tuple_depth)?;
+
+		let v = DomainRouteKey {
+			namespace_id,
+			hostname,
+			path_prefix,
+		};
+
+		Ok((input, v))
+	}
+}
+
+pub struct DomainRouteSubspaceKey {
+	namespace_id: Id,
+}
+
+impl DomainRouteSubspaceKey {
+	pub fn new(namespace_id: Id) -> Self {
+		DomainRouteSubspaceKey { namespace_id }
+	}
+}
+
+impl TuplePack for DomainRouteSubspaceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (DATA, self.namespace_id, DOMAIN_ROUTE);
+		t.pack(w, tuple_depth)
+	}
+}
+
+/// Index of domain routes across all namespaces, read by guard.
+#[derive(Debug)]
+pub struct ByHostnameKey {
+	pub hostname: String,
+	pub path_prefix: String,
+}
+
+impl ByHostnameKey {
+	pub fn new(hostname: String, path_prefix: String) -> Self {
+		ByHostnameKey {
+			hostname,
+			path_prefix,
+		}
+	}
+
+	pub fn subspace(hostname: String) -> ByHostnameSubspaceKey {
+		ByHostnameSubspaceKey::new(hostname)
+	}
+}
+
+impl FormalKey for ByHostnameKey {
+	/// Namespace id.
+	type Value = Id;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(Id::from_slice(raw)?)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.as_bytes())
+	}
+}
+
+impl TuplePack for ByHostnameKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (BY_HOSTNAME, &self.hostname, &self.path_prefix);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for ByHostnameKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, hostname, path_prefix)) =
+			<(usize, String, String)>::unpack(input, tuple_depth)?;
+
+		let v = ByHostnameKey {
+			hostname,
+			path_prefix,
+		};
+
+		Ok((input, v))
+	}
+}
+
+pub struct ByHostnameSubspaceKey {
+	hostname: String,
+}
+
+impl ByHostnameSubspaceKey {
+	pub fn new(hostname: String) -> Self {
+		ByHostnameSubspaceKey { hostname }
+	}
+}
+
+impl TuplePack for ByHostnameSubspaceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (BY_HOSTNAME, &self.hostname);
+		t.pack(w, tuple_depth)
+	}
+}
+
+/// Namespace that owns a hostname. Only stored in the leader datacenter, which is where hostnames
+/// are claimed, so concurrent claims from different datacenters cannot both succeed.
+#[derive(Debug)]
+pub struct DomainOwnerKey {
+	pub hostname: String,
+}
+
+impl DomainOwnerKey {
+	pub fn new(hostname: String) -> Self {
+		DomainOwnerKey { hostname }
+	}
+}
+
+impl FormalKey for DomainOwnerKey {
+	/// Namespace id.
+	type Value = Id;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(Id::from_slice(raw)?)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.as_bytes())
+	}
+}
+
+impl TuplePack for DomainOwnerKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (DOMAIN_OWNER, &self.hostname);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for DomainOwnerKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, hostname)) = <(usize, String)>::unpack(input, tuple_depth)?;
+
+		let v = DomainOwnerKey { hostname };
+
+		Ok((input, v))
+	}
+}
+
+/// A domain route with the namespace it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedDomainRoute {
+	pub namespace_id: Id,
+	pub namespace_name: String,
+	pub route: DomainRoute,
+}
+
+fn path_prefix_key(route: &DomainRoute) -> String {
+	route
+		.path_prefix
+		.clone()
+		.unwrap_or_else(|| ROOT_PATH_PREFIX.to_string())
+}
+
+/// Lists the domain routes of a namespace. `tx` must be in the namespace subspace.
+pub async fn list(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	isolation_level: IsolationLevel,
+) -> Result<Vec<DomainRoute>> {
+	let subspace = super::subspace().subspace(&DomainRouteKey::subspace(namespace_id));
+	let mut stream = tx.get_ranges_keyvalues(
+		universaldb::RangeOption {
+			mode: StreamingMode::WantAll,
+			..(&subspace).into()
+		},
+		isolation_level,
+	);
+
+	let mut routes = Vec::new();
+	while let Some(entry) = stream.try_next().await? {
+		let (_, route) = tx.read_entry::<DomainRouteKey>(&entry)?;
+		routes.push(route);
+	}
+
+	Ok(routes)
+}
+
+/// Reads every route for `hostname`, across all namespaces. `tx` must be in the namespace
+/// subspace.
+pub async fn resolve(
+	tx: &universaldb::Transaction,
+	hostname: &str,
+	isolation_level: IsolationLevel,
+) -> Result<Vec<ResolvedDomainRoute>> {
+	let subspace = super::subspace().subspace(&ByHostnameKey::subspace(hostname.to_string()));
+
+	let mut stream = tx.get_ranges_keyvalues(
+		universaldb::RangeOption {
+			mode: StreamingMode::WantAll,
+			..(&subspace).into()
+		},
+		isolation_level,
+	);
+
+	let mut entries = Vec::new();
+	while let Some(entry) = stream.try_next().await? {
+		entries.push(tx.read_entry::<ByHostnameKey>(&entry)?);
+	}
+
+	let mut routes = Vec::with_capacity(entries.len());
+	for (key, namespace_id) in entries {
+		let route_key = DomainRouteKey::new(namespace_id, key.hostname, key.path_prefix);
+		let name_key = super::NameKey::new(namespace_id);
+		let (Some(route), Some(namespace_name)) = tokio::try_join!(
+			tx.read_opt(&route_key, isolation_level),
+			tx.read_opt(&name_key, isolation_level),
+		)?
+		else {
+			continue;
+		};
+
+		routes.push(ResolvedDomainRoute {
+			namespace_id,
+			namespace_name,
+			route,
+		});
+	}
+
+	Ok(routes)
+}
+
+/// Reads the namespace that owns `hostname`. `tx` must be in the namespace subspace of the leader
+/// datacenter.
+pub async fn owner(
+	tx: &universaldb::Transaction,
+	hostname: &str,
+	isolation_level: IsolationLevel,
+) -> Result<Option<Id>> {
+	tx.read_opt(&DomainOwnerKey::new(hostname.to_string()), isolation_level)
+		.await
+}
+
+/// Claims `hostname` for a namespace. Fails if another namespace owns it. Callers verify that the
+/// namespace controls the hostname before claiming it. `tx` must be in the namespace subspace of
+/// the leader datacenter.
+pub async fn claim(tx: &universaldb::Transaction, namespace_id: Id, hostname: &str) -> Result<()> {
+	match owner(tx, hostname, IsolationLevel::Serializable).await? {
+		Some(owner_id) if owner_id == namespace_id => Ok(()),
+		Some(_) => Err(errors::Namespace::DomainInUse {
+			hostname: hostname.to_string(),
+		}
+		.build()),
+		None => {
+			tx.write(&DomainOwnerKey::new(hostname.to_string()), namespace_id)?;
+			Ok(())
+		}
+	}
+}
+
+/// Creates or replaces a domain route. Fails if another namespace already routes the hostname.
+/// `tx` must be in the namespace subspace.
+pub async fn write(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	route: DomainRoute,
+) -> Result<()> {
+	let subspace = super::subspace().subspace(&ByHostnameKey::subspace(route.hostname.clone()));
+	let mut stream = tx.get_ranges_keyvalues(
+		universaldb::RangeOption {
+			mode: StreamingMode::WantAll,
+			..(&subspace).into()
+		},
+		IsolationLevel::Serializable,
+	);
+
+	while let Some(entry) = stream.try_next().await? {
+		let (_, owner_id) = tx.read_entry::<ByHostnameKey>(&entry)?;
+		if owner_id != namespace_id {
+			return Err(errors::Namespace::DomainInUse {
+				hostname: route.hostname.clone(),
+			}
+			.build());
+		}
+	}
+
+	let path_prefix = path_prefix_key(&route);
+	tx.write(
+		&ByHostnameKey::new(route.hostname.clone(), path_prefix.clone()),
+		namespace_id,
+	)?;
+	tx.write(
+		&DomainRouteKey::new(namespace_id, route.hostname.clone(), path_prefix),
+		route,
+	)?;
+
+	Ok(())
+}
+
+/// Deletes a domain route. Deleting the last route of a hostname releases the claim on it, so
+/// another namespace can claim it after verifying control. Returns false if the route does not
+/// exist. `tx` must be in the namespace subspace.
+pub async fn delete(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	hostname: String,
+	path_prefix: Option<String>,
+) -> Result<bool> {
+	let path_prefix = path_prefix.unwrap_or_else(|| ROOT_PATH_PREFIX.to_string());
+	let route_key = DomainRouteKey::new(namespace_id, hostname.clone(), path_prefix.clone());
+
+	if !tx.exists(&route_key, IsolationLevel::Serializable).await? {
+		return Ok(false);
+	}
+
+	let subspace = super::subspace().subspace(&ByHostnameKey::subspace(hostname.clone()));
+	let mut stream = tx.get_ranges_keyvalues(
+		universaldb::RangeOption {
+			mode: StreamingMode::WantAll,
+			..(&subspace).into()
+		},
+		IsolationLevel::Serializable,
+	);
+	let mut other_routes = false;
+	while let Some(entry) = stream.try_next().await? {
+		let (key, _) = tx.read_entry::<ByHostnameKey>(&entry)?;
+		other_routes |= key.path_prefix != path_prefix;
+	}
+
+	tx.delete(&route_key);
+	tx.delete(&ByHostnameKey::new(hostname.clone(), path_prefix));
+	if !other_routes {
+		// Only exists in the leader datacenter
+		tx.delete(&DomainOwnerKey::new(hostname));
+	}
+
+	Ok(true)
+}
@@ -2,7 +2,11 @@ use anyhow::Result;
 use gas::prelude::*;
 use universaldb::prelude::*;
 
+pub mod database_quota;
+pub mod domain_route;
 pub mod metric;
+pub mod policy;
+pub mod usage;
 
 pub fn subspace() -> universaldb::utils::Subspace {
 	universaldb::utils::Subspace::new(&(RIVET, NAMESPACE))
@@ -0,0 +1,121 @@
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use gas::prelude::*;
+use rivet_types::namespaces::{AuthPolicy, CanaryPolicy, RateLimitPolicy, RegionPolicy};
+use serde::{Serialize, de::DeserializeOwned};
+use universaldb::{prelude::*, utils::IsolationLevel};
+
+/// A namespace policy, stored as JSON under its own key.
+pub trait Policy: Serialize + DeserializeOwned + Default {
+	/// Last element of the key tuple.
+	const KEY: usize;
+
+	/// Policies without rules are deleted instead of stored.
+	fn is_empty(&self) -> bool;
+}
+
+impl Policy for RateLimitPolicy {
+	const KEY: usize = RATE_LIMIT_POLICY;
+
+	fn is_empty(&self) -> bool {
+		self.rules.is_empty()
+	}
+}
+
+impl Policy for AuthPolicy {
+	const KEY: usize = AUTH_POLICY;
+
+	fn is_empty(&self) -> bool {
+		self.rules.is_empty()
+	}
+}
+
+impl Policy for CanaryPolicy {
+	const KEY: usize = CANARY_POLICY;
+
+	fn is_empty(&self) -> bool {
+		self.rules.is_empty()
+	}
+}
+
+impl Policy for RegionPolicy {
+	const KEY: usize = REGION_POLICY;
+
+	fn is_empty(&self) -> bool {
+		self.rules.is_empty()
+	}
+}
+
+#[derive(Debug)]
+pub struct PolicyKey<P> {
+	pub namespace_id: Id,
+	policy: PhantomData<P>,
+}
+
+impl<P> PolicyKey<P> {
+	pub fn new(namespace_id: Id) -> Self {
+		PolicyKey {
+			namespace_id,
+			policy: PhantomData,
+		}
+	}
+}
+
+impl<P: Policy> FormalKey for PolicyKey<P> {
+	type Value = P;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		serde_json::from_slice(raw).map_err(Into::into)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		serde_json::to_vec(&value).map_err(Into::into)
+	}
+}
+
+impl<P: Policy> TuplePack for PolicyKey<P> {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (DATA, self.namespace_id, P::KEY);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de, P: Policy> TupleUnpack<'de> for PolicyKey<P> {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, namespace_id, key)) = <(usize, Id, usize)>::unpack(input, tuple_depth)?;
+		if key != P::KEY {
+			return Err(PackError::Message("policy key kind does not match".into()));
+		}
+
+		Ok((input, PolicyKey::new(namespace_id)))
+	}
+}
+
+/// Reads a policy of a namespace. `tx` must be in the namespace subspace.
+pub async fn read<P: Policy>(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	isolation_level: IsolationLevel,
+) -> Result<P> {
+	Ok(tx
+		.read_opt(&PolicyKey::<P>::new(namespace_id), isolation_level)
+		.await?
+		.unwrap_or_default())
+}
+
+/// Replaces a policy of a namespace. `tx` must be in the namespace subspace.
+pub fn write<P: Policy>(tx: &universaldb::Transaction, namespace_id: Id, policy: P) -> Result<()> {
+	let key = PolicyKey::<P>::new(namespace_id);
+	if policy.is_empty() {
+		tx.delete(&key);
+	} else {
+		tx.write(&key, policy)?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,265 @@
+use anyhow::Result;
+use gas::prelude::*;
+use universaldb::prelude::*;
+
+/// A category of metered storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::FromRepr, strum::EnumIter)]
+pub enum StorageKind {
+	/// Bytes stored in actor KV.
+	ActorKv = 0,
+	/// Bytes stored in the actor's SQLite database (depot).
+	Sqlite = 1,
+	/// Bytes of the actor workflow's history, state, input, and output.
+	WorkflowHistory = 2,
+	/// Bytes of signals queued for the actor workflow that have not been received yet.
+	WorkflowQueue = 3,
+}
+
+impl std::fmt::Display for StorageKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			StorageKind::ActorKv => write!(f, "actor_kv"),
+			StorageKind::Sqlite => write!(f, "sqlite"),
+			StorageKind::WorkflowHistory => write!(f, "workflow_history"),
+			StorageKind::WorkflowQueue => write!(f, "workflow_queue"),
+		}
+	}
+}
+
+/// Rollup of the bytes a namespace stores in this datacenter for a single storage kind. The sum of
+/// every [`ActorStorageUsageKey`] of the namespace with the same kind.
+#[derive(Debug)]
+pub struct StorageUsageKey {
+	pub namespace_id: Id,
+	pub kind: StorageKind,
+}
+
+impl StorageUsageKey {
+	pub fn new(namespace_id: Id, kind: StorageKind) -> Self {
+		StorageUsageKey { namespace_id, kind }
+	}
+
+	pub fn subspace(namespace_id: Id) -> StorageUsageSubspaceKey {
+		StorageUsageSubspaceKey::new(namespace_id)
+	}
+
+	pub fn entire_subspace() -> StorageUsageSubspaceKey {
+		StorageUsageSubspaceKey::entire()
+	}
+}
+
+impl FormalKey for StorageUsageKey {
+	// IMPORTANT: Uses LE bytes, not BE
+	/// Bytes.
+	type Value = i64;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(i64::from_le_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.to_le_bytes().to_vec())
+	}
+}
+
+impl TuplePack for StorageUsageKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (STORAGE_USAGE, self.namespace_id, self.kind as usize);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for StorageUsageKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, namespace_id, kind)) = <(usize, Id, usize)>::unpack(input, tuple_depth)?;
+		let kind = StorageKind::from_repr(kind).ok_or_else(|| {
+			PackError::Message(format!("invalid storage kind `{kind}` in key").into())
+		})?;
+
+		let v = StorageUsageKey { namespace_id, kind };
+
+		Ok((input, v))
+	}
+}
+
+pub struct StorageUsageSubspaceKey {
+	namespace_id: Option<Id>,
+}
+
+impl StorageUsageSubspaceKey {
+	pub fn new(namespace_id: Id) -> Self {
+		StorageUsageSubspaceKey {
+			namespace_id: Some(namespace_id),
+		}
+	}
+
+	pub fn entire() -> Self {
+		StorageUsageSubspaceKey { namespace_id: None }
+	}
+}
+
+impl TuplePack for StorageUsageSubspaceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let mut offset = VersionstampOffset::None { size: 0 };
+
+		offset += STORAGE_USAGE.pack(w, tuple_depth)?;
+
+		if let Some(namespace_id) = &self.namespace_id {
+			offset += namespace_id.pack(w, tuple_depth)?;
+		}
+
+		Ok(offset)
+	}
+}
+
+/// Bytes a single actor stores for a single storage kind, as of its last metering pass.
+#[derive(Debug)]
+pub struct ActorStorageUsageKey {
+	pub namespace_id: Id,
+	pub actor_id: Id,
+	pub kind: StorageKind,
+}
+
+impl ActorStorageUsageKey {
+	pub fn new(namespace_id: Id, actor_id: Id, kind: StorageKind) -> Self {
+		ActorStorageUsageKey {
+			namespace_id,
+			actor_id,
+			kind,
+		}
+	}
+
+	pub fn subspace(namespace_id: Id) -> ActorStorageUsageSubspaceKey {
+		ActorStorageUsageSubspaceKey::new(namespace_id)
+	}
+
+	pub fn subspace_with_actor(namespace_id: Id, actor_id: Id) -> ActorStorageUsageSubspaceKey {
+		ActorStorageUsageSubspaceKey::new_with_actor(namespace_id, actor_id)
+	}
+}
+
+impl FormalKey for ActorStorageUsageKey {
+	// IMPORTANT: Uses LE bytes, not BE
+	/// Bytes.
+	type Value = i64;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(i64::from_le_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.to_le_bytes().to_vec())
+	}
+}
+
+impl TuplePack for ActorStorageUsageKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (
+			ACTOR_STORAGE_USAGE,
+			self.namespace_id,
+			self.actor_id,
+			self.kind as usize,
+		);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for ActorStorageUsageKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, namespace_id, actor_id, kind)) =
+			<(usize, Id, Id, usize)>::unpack(input, tuple_depth)?;
+		let kind = StorageKind::from_repr(kind).ok_or_else(|| {
+			PackError::Message(format!("invalid storage kind `{kind}` in key").into())
+		})?;
+
+		let v = ActorStorageUsageKey {
+			namespace_id,
+			actor_id,
+			kind,
+		};
+
+		Ok((input, v))
+	}
+}
+
+pub struct ActorStorageUsageSubspaceKey {
+	namespace_id: Id,
+	actor_id: Option<Id>,
+}
+
+impl ActorStorageUsageSubspaceKey {
+	pub fn new(namespace_id: Id) -> Self {
+		ActorStorageUsageSubspaceKey {
+			namespace_id,
+			actor_id: None,
+		}
+	}
+
+	pub fn new_with_actor(namespace_id: Id, actor_id: Id) -> Self {
+		ActorStorageUsageSubspaceKey {
+			namespace_id,
+			actor_id: Some(actor_id),
+		}
+	}
+}
+
+impl TuplePack for ActorStorageUsageSubspaceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let mut offset = VersionstampOffset::None { size: 0 };
+
+		let t = (ACTOR_STORAGE_USAGE, self.namespace_id);
+		offset += t.pack(w, tuple_depth)?;
+
+		if let Some(actor_id) = &self.actor_id {
+			offset += actor_id.pack(w, tuple_depth)?;
+		}
+
+		Ok(offset)
+	}
+}
+
+/// Record the bytes an actor currently stores for a storage kind and fold the change into the
+/// namespace rollup. Returns the change in bytes since the previous record.
+pub async fn record_actor(
+	tx: &universaldb::Transaction,
+	namespace_id: Id,
+	actor_id: Id,
+	kind: StorageKind,
+	bytes: i64,
+) -> Result<i64> {
+	let actor_key = ActorStorageUsageKey::new(namespace_id, actor_id, kind);
+	let prev = tx.read_opt(&actor_key, Serializable).await?.unwrap_or(0);
+
+	if bytes == 0 {
+		tx.delete(&actor_key);
+	} else {
+		tx.write(&actor_key, bytes)?;
+	}
+
+	let delta = bytes - prev;
+	if delta != 0 {
+		tx.atomic_op(
+			&StorageUsageKey::new(namespace_id, kind),
+			&delta.to_le_bytes(),
+			MutationType::Add,
+		);
+	}
+
+	Ok(delta)
+}
@@ -0,0 +1,59 @@
+use gas::prelude::*;
+use rivet_types::namespaces::{AuthPolicy, CanaryPolicy, RateLimitPolicy, RegionPolicy};
+use serde::{Deserialize, Serialize};
+use universaldb::utils::IsolationLevel::*;
+
+use crate::keys;
+
+/// How long the previous policies keep being applied after they change.
+const CACHE_TTL_MS: i64 = 10_000;
+
+#[derive(Debug)]
+pub struct Input {
+	pub namespace_id: Id,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Output {
+	pub rate_limit: RateLimitPolicy,
+	pub auth: AuthPolicy,
+	pub canary: CanaryPolicy,
+	pub region: RegionPolicy,
+}
+
+/// Reads the policies of a namespace in this datacenter. Cached briefly since guard reads them for
+/// every routed request and pegboard every time an actor is created.
+#[operation]
+pub async fn namespace_get_policies_local(ctx: &OperationCtx, input: &Input) -> Result<Output> {
+	let policies = ctx
+		.cache()
+		.clone()
+		.request()
+		.ttl(CACHE_TTL_MS)
+		.fetch_one_json(
+			"namespace.get_policies_local",
+			input.namespace_id,
+			move |mut cache, namespace_id| async move {
+				let policies = ctx
+					.udb()?
+					.txn("namespace_get_policies_local", |tx| async move {
+						let tx = tx.with_subspace(keys::subspace());
+						Ok(Output {
+							rate_limit: keys::policy::read(&tx, namespace_id, Snapshot).await?,
+							auth: keys::policy::read(&tx, namespace_id, Snapshot).await?,
+							canary: keys::policy::read(&tx, namespace_id, Snapshot).await?,
+							region: keys::policy::read(&tx, namespace_id, Snapshot).await?,
+						})
+					})
+					.custom_instrument(tracing::info_span!("namespace_get_policies_local_tx"))
+					.await?;
+
+				cache.resolve(&namespace_id, policies);
+
+				Ok(cache)
+			},
+		)
+		.await?;
+
+	Ok(policies.unwrap_or_default())
+}
@@ -0,0 +1,72 @@
+use gas::prelude::*;
+use universaldb::utils::IsolationLevel::*;
+
+use crate::keys::{
+	self,
+	usage::{ActorStorageUsageKey, StorageKind, StorageUsageKey},
+};
+
+#[derive(Debug)]
+pub struct Input {
+	pub namespace_id: Id,
+	/// Read the usage of a single actor instead of the whole namespace.
+	pub actor_id: Option<Id>,
+}
+
+/// Bytes stored in this datacenter, as of the last metering pass.
+#[derive(Debug, Default, Clone)]
+pub struct Output {
+	pub actor_kv_bytes: i64,
+	pub sqlite_bytes: i64,
+	pub workflow_history_bytes: i64,
+	pub workflow_queue_bytes: i64,
+}
+
+#[operation]
+pub async fn namespace_get_storage_usage_local(
+	ctx: &OperationCtx,
+	input: &Input,
+) -> Result<Output> {
+	let namespace_id = input.namespace_id;
+	let actor_id = input.actor_id;
+
+	let (actor_kv_bytes, sqlite_bytes, workflow_history_bytes, workflow_queue_bytes) = ctx
+		.udb()?
+		.txn("namespace_get_storage_usage_local", |tx| async move {
+			let tx = tx.with_subspace(keys::subspace());
+
+			let read = |kind| {
+				let tx = tx.clone();
+				async move {
+					let bytes = if let Some(actor_id) = actor_id {
+						tx.read_opt(
+							&ActorStorageUsageKey::new(namespace_id, actor_id, kind),
+							Snapshot,
+						)
+						.await?
+					} else {
+						tx.read_opt(&StorageUsageKey::new(namespace_id, kind), Snapshot)
+							.await?
+					};
+
+					anyhow::Ok(bytes.unwrap_or_default().max(0))
+				}
+			};
+
+			tokio::try_join!(
+				read(StorageKind::ActorKv),
+				read(StorageKind::Sqlite),
+				read(StorageKind::WorkflowHistory),
+				read(StorageKind::WorkflowQueue),
+			)
+		})
+		.custom_instrument(tracing::info_span!("namespace_get_storage_usage_local_tx"))
+		.await?;
+
+	Ok(Output {
+		actor_kv_bytes,
+		sqlite_bytes,
+		workflow_history_bytes,
+		workflow_queue_bytes,
+	})
+}
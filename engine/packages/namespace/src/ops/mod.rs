@@ -1,5 +1,7 @@
 pub mod get_global;
 pub mod get_local;
+pub mod get_policies_local;
+pub mod get_storage_usage_local;
 pub mod list;
 pub mod resolve_for_name_global;
 pub mod resolve_for_name_local;
@@ -17,39 +17,42 @@ pub async fn namespace_resolve_for_name_global(
 		})
 		.await
 	} else {
-		let leader_dc = ctx.config().leader_dc()?;
+		let leader_dc = ctx.config().leader_dc()?.clone();
 		let client = rivet_pools::reqwest::client().await?;
 
+		// Namespace lookups are on the hot path of gateway query routing, so serve stale values
+		// instead of blocking on the leader and cache unknown names briefly
 		ctx.cache()
 			.clone()
 			.request()
-			.fetch_one_json("namespace.resolve_for_name_global", input.name.clone(), {
-				let leader_dc = leader_dc.clone();
-				let client = client.clone();
-				move |mut cache, key| {
+			.stale_while_revalidate(rivet_util::duration::minutes(5))
+			.negative_ttl(rivet_util::duration::seconds(5))
+			.fetch_one_json_swr(
+				"namespace.resolve_for_name_global",
+				input.name.clone(),
+				move |mut cache, key: String| {
 					let leader_dc = leader_dc.clone();
 					let client = client.clone();
 					async move {
 						let url = leader_dc.peer_url.join("/namespaces")?;
 						let res = client
 							.get(url)
-							.query(&[("name", &input.name)])
+							.query(&[("name", &key)])
 							.send()
 							.custom_instrument(tracing::info_span!("namespaces_http_request"))
 							.await?;
 
 						let res = rivet_api_util::parse_response::<ListResponse>(res).await?;
 
-						let ns = res.namespaces.into_iter().next();
-
-						cache.resolve(&key, ns);
+						if let Some(ns) = res.namespaces.into_iter().next() {
+							cache.resolve(&key, ns);
+						}
 
 						Ok(cache)
 					}
-				}
-			})
+				},
+			)
 			.await
-			.map(|x| x.flatten())
 	}
 }
 
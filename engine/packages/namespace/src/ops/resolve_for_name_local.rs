@@ -18,18 +18,23 @@ pub async fn namespace_resolve_for_name_local(
 		return Err(errors::Namespace::NotLeader.build());
 	}
 
+	let udb = ctx.udb()?;
+
+	// See `resolve_for_name_global`
 	ctx.cache()
 		.clone()
 		.request()
-		.fetch_one_json(
+		.stale_while_revalidate(rivet_util::duration::minutes(5))
+		.negative_ttl(rivet_util::duration::seconds(5))
+		.fetch_one_json_swr(
 			"namespace.resolve_for_name_local",
 			input.name.clone(),
-			move |mut cache, key| {
+			move |mut cache, key: String| {
+				let udb = udb.clone();
 				async move {
-					let ns = ctx
-						.udb()?
+					let ns = udb
 						.txn("namespace_resolve_for_name_local", |tx| {
-							let name = input.name.clone();
+							let name = key.clone();
 							async move {
 								let tx = tx.with_subspace(keys::subspace());
 
@@ -9,3 +9,54 @@ pub fn runner_config_variant(runner_config: &RunnerConfig) -> RunnerConfigVarian
 		RunnerConfigKind::Serverless { .. } => RunnerConfigVariant::Serverless,
 	}
 }
+
+/// Part of a domain route key template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomainKeyPart<'a> {
+	Literal(&'a str),
+	/// `{subdomain}`
+	Subdomain,
+	/// `{path.N}`
+	PathSegment(usize),
+}
+
+/// Parses a key component of a domain route. Returns the reason if the template is invalid.
+pub fn parse_domain_key_template(template: &str) -> Result<Vec<DomainKeyPart<'_>>, String> {
+	let mut parts = Vec::new();
+	let mut rest = template;
+
+	while !rest.is_empty() {
+		let Some(start) = rest.find(['{', '}']) else {
+			parts.push(DomainKeyPart::Literal(rest));
+			break;
+		};
+		if rest.as_bytes()[start] == b'}' {
+			return Err(format!("unmatched `}}` in key `{template}`"));
+		}
+		if start > 0 {
+			parts.push(DomainKeyPart::Literal(&rest[..start]));
+		}
+
+		let Some(len) = rest[start..].find('}') else {
+			return Err(format!("unmatched `{{` in key `{template}`"));
+		};
+		let placeholder = &rest[start + 1..start + len];
+		let part = if placeholder == "subdomain" {
+			DomainKeyPart::Subdomain
+		} else if let Some(idx) = placeholder
+			.strip_prefix("path.")
+			.and_then(|idx| idx.parse::<usize>().ok())
+		{
+			DomainKeyPart::PathSegment(idx)
+		} else {
+			return Err(format!(
+				"unknown placeholder `{{{placeholder}}}` in key `{template}`, expected `{{subdomain}}` or `{{path.N}}`"
+			));
+		};
+		parts.push(part);
+
+		rest = &rest[start + len + 1..];
+	}
+
+	Ok(parts)
+}
@@ -1,5 +1,6 @@
 use futures_util::FutureExt;
 use gas::prelude::*;
+use rivet_api_builder::ApiCtx;
 use serde::{Deserialize, Serialize};
 use universaldb::utils::IsolationLevel::*;
 
@@ -50,16 +51,30 @@ pub async fn namespace(ctx: &mut WorkflowCtx, input: &Input) -> Result<()> {
 		return Ok(());
 	}
 
+	// Name lookups cache misses, so a lookup made before the namespace existed must not outlive it
+	ctx.v(2)
+		.activity(PurgeCacheInput {
+			namespace_id: input.namespace_id,
+			name: input.name.clone(),
+		})
+		.await?;
+
 	ctx.msg(CreateComplete {})
 		.topic(("namespace_id", input.namespace_id))
 		.send()
 		.await?;
 
-	// Does nothing yet
+	let namespace_id = input.namespace_id;
+	let name = input.name.clone();
 	ctx.repeat(|ctx| {
+		let name = name.clone();
 		async move {
 			ctx.listen::<Update>().await?;
 
+			ctx.v(2)
+				.activity(PurgeCacheInput { namespace_id, name })
+				.await?;
+
 			Ok(Loop::<()>::Continue)
 		}
 		.boxed()
@@ -176,3 +191,32 @@ async fn insert_db(
 		.await
 		.map_err(Into::into)
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+struct PurgeCacheInput {
+	namespace_id: Id,
+	name: String,
+}
+
+/// Purges cached namespace lookups in every datacenter.
+#[activity(PurgeCache)]
+async fn purge_cache(ctx: &ActivityCtx, input: &PurgeCacheInput) -> Result<()> {
+	let api_ctx = ApiCtx::new_from_activity(ctx)?;
+
+	rivet_api_util::cache_purge_global(
+		&api_ctx,
+		"namespace.resolve_for_name_global",
+		vec![input.name.clone()],
+	)
+	.await?;
+	rivet_api_util::cache_purge_global(
+		&api_ctx,
+		"namespace.resolve_for_name_local",
+		vec![input.name.clone()],
+	)
+	.await?;
+	rivet_api_util::cache_purge_global(&api_ctx, "namespace.get_global", vec![input.namespace_id])
+		.await?;
+
+	Ok(())
+}
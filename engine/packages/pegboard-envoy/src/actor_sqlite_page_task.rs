@@ -29,6 +29,7 @@ impl Key {
 pub(super) enum Message {
 	GetPages(protocol::ToRivetSqliteGetPagesRequest),
 	Commit(protocol::ToRivetSqliteCommitRequest),
+	GetRowChangeLog(protocol::ToRivetSqliteGetRowChangeLogRequest),
 }
 
 pub(super) async fn task(
@@ -71,6 +72,18 @@ pub(super) async fn task(
 					])
 					.observe(timed_response.commit_completed_at.elapsed().as_secs_f64());
 			}
+			Ok(Some(Message::GetRowChangeLog(req))) => {
+				let response = ws_to_tunnel_task::handle_sqlite_get_row_change_log_response(
+					&ctx, &conn, req.data,
+				)
+				.await;
+				ws_to_tunnel_task::send_sqlite_get_row_change_log_response(
+					&conn,
+					req.request_id,
+					response,
+				)
+				.await?;
+			}
 			Ok(None) | Err(_) => return Ok(TaskExit::SqlitePage(key)),
 		}
 	}
@@ -7,7 +7,7 @@ use std::{
 };
 
 use anyhow::Context;
-use depot::conveyer::Db;
+use depot::{cold::ColdStore, conveyer::Db};
 use depot_client::database::NativeDatabaseHandle;
 use futures_util::StreamExt;
 use futures_util::TryStreamExt;
@@ -49,7 +49,12 @@ pub struct Conn {
 	/// Envoys can reconnect to different worker nodes mid-flight, so request handlers
 	/// lazily populate it and lifecycle commands only evict stale cache entries.
 	pub actor_dbs: HashMap<String, Arc<Db>>,
+	/// Object store backing the depot cold tier, if configured.
+	pub cold_store: Option<Arc<dyn ColdStore>>,
 	pub remote_sqlite_executors: RemoteSqliteExecutors,
+	/// Actors with a SQLite replicator wakeup in flight. Set to true when another commit lands
+	/// during the send, so one more wakeup follows it.
+	pub sqlite_replicator_wakeups: Arc<HashMap<Id, bool>>,
 	pub pool: RunnerConfig,
 	pub connected_at: Instant,
 	pub last_rtt: AtomicU32,
@@ -364,6 +369,10 @@ pub async fn init_conn(
 		.await?;
 	}
 
+	let cold_store = depot::cold::shared(ctx.config())
+		.await
+		.context("failed to build depot cold store")?;
+
 	let conn = Arc::new(Conn {
 		namespace_id: namespace.namespace_id,
 		namespace_name,
@@ -376,7 +385,9 @@ pub async fn init_conn(
 		udb: conn_udb,
 		node_id,
 		actor_dbs: HashMap::new(),
+		cold_store,
 		remote_sqlite_executors: HashMap::new(),
+		sqlite_replicator_wakeups: Arc::new(HashMap::new()),
 		pool: pool.config,
 		connected_at: Instant::now(),
 		last_rtt: AtomicU32::new(0),
@@ -262,6 +262,7 @@ fn to_envoy_tunnel_message_kind_name(kind: &protocol::ToEnvoyTunnelMessageKind)
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(_) => "ToEnvoyWebSocketOpen",
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketMessage(_) => "ToEnvoyWebSocketMessage",
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketClose(_) => "ToEnvoyWebSocketClose",
+		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(_) => "ToEnvoyWebSocketCredit",
 	}
 }
 
@@ -274,6 +275,7 @@ fn to_envoy_tunnel_message_inner_data_len(kind: &protocol::ToEnvoyTunnelMessageK
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketMessage(msg) => msg.data.len(),
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestAbort
 		| protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(_)
-		| protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketClose(_) => 0,
+		| protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketClose(_)
+		| protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(_) => 0,
 	}
 }
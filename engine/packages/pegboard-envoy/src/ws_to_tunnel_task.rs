@@ -635,6 +635,7 @@ fn message_kind_label(msg: &protocol::ToRivet) -> &'static str {
 		protocol::ToRivet::ToRivetKvRequest(_) => "kv_request",
 		protocol::ToRivet::ToRivetSqliteGetPagesRequest(_) => "sqlite_get_pages",
 		protocol::ToRivet::ToRivetSqliteCommitRequest(_) => "sqlite_commit",
+		protocol::ToRivet::ToRivetSqliteGetRowChangeLogRequest(_) => "sqlite_get_row_change_log",
 		protocol::ToRivet::ToRivetSqliteExecRequest(_) => "sqlite_exec",
 		protocol::ToRivet::ToRivetSqliteExecuteRequest(_) => "sqlite_execute",
 		protocol::ToRivet::ToRivetSqliteExecuteBatchRequest(_) => "sqlite_execute_batch",
@@ -737,6 +738,24 @@ async fn dispatch_message(
 			let key = actor_sqlite_page_task::Key::new(req.data.actor_id.clone(), generation);
 			task_manager.enqueue_sqlite_page(key, actor_sqlite_page_task::Message::Commit(req))?;
 		}
+		protocol::ToRivet::ToRivetSqliteGetRowChangeLogRequest(req) => {
+			let Some(generation) = req.data.expected_generation else {
+				send_sqlite_get_row_change_log_response(
+					&conn,
+					req.request_id,
+					protocol::SqliteGetRowChangeLogResponse::SqliteErrorResponse(
+						sqlite_protocol_error_response(
+							"sqlite get_row_change_log missing expectedGeneration",
+						),
+					),
+				)
+				.await?;
+				return Ok(None);
+			};
+			let key = actor_sqlite_page_task::Key::new(req.data.actor_id.clone(), generation);
+			task_manager
+				.enqueue_sqlite_page(key, actor_sqlite_page_task::Message::GetRowChangeLog(req))?;
+		}
 		protocol::ToRivet::ToRivetSqliteExecRequest(req) => {
 			let key =
 				actor_remote_sqlite_task::Key::new(req.data.actor_id.clone(), req.data.generation);
@@ -1115,6 +1134,31 @@ pub(super) async fn handle_sqlite_commit_response(
 	}
 }
 
+pub(super) async fn handle_sqlite_get_row_change_log_response(
+	ctx: &StandaloneCtx,
+	conn: &Conn,
+	request: protocol::SqliteGetRowChangeLogRequest,
+) -> protocol::SqliteGetRowChangeLogResponse {
+	let start = Instant::now();
+	let actor_id = request.actor_id.clone();
+	let response = match handle_sqlite_get_row_change_log(ctx, conn, request).await {
+		Ok(response) => response,
+		Err(err) => {
+			tracing::error!(actor_id = %actor_id, ?err, "sqlite get_row_change_log request failed");
+			protocol::SqliteGetRowChangeLogResponse::SqliteErrorResponse(sqlite_error_response(
+				&err,
+			))
+		}
+	};
+	record_sqlite_request_metrics(
+		conn,
+		"get_row_change_log",
+		sqlite_get_row_change_log_response_kind(&response),
+		start,
+	);
+	response
+}
+
 pub(super) async fn handle_remote_sqlite_exec_response(
 	ctx: &StandaloneCtx,
 	conn: &Conn,
@@ -1481,6 +1525,7 @@ fn tunnel_message_kind_name(kind: &protocol::ToRivetTunnelMessageKind) -> &'stat
 		ToRivetTunnelMessageKind::ToRivetWebSocketMessage(_) => "ToRivetWebSocketMessage",
 		ToRivetTunnelMessageKind::ToRivetWebSocketMessageAck(_) => "ToRivetWebSocketMessageAck",
 		ToRivetTunnelMessageKind::ToRivetWebSocketClose(_) => "ToRivetWebSocketClose",
+		ToRivetTunnelMessageKind::ToRivetWebSocketCredit(_) => "ToRivetWebSocketCredit",
 	}
 }
 
@@ -1556,21 +1601,34 @@ async fn handle_sqlite_commit(
 
 	let actor_id = request.actor_id.clone();
 	let actor_db = actor_db(ctx, conn, actor_id.clone()).await?;
-	let engine_result = actor_db
-		.commit_with_options(
-			request
-				.dirty_pages
-				.into_iter()
-				.map(pump_dirty_page)
-				.collect(),
-			request.db_size_pages,
-			request.now_ms,
-			depot::types::CommitOptions {
-				expected_head_txid: request.expected_head_txid,
-				disable_size_cap: ctx.config().sqlite().unstable_disable_commit_size_cap(),
-			},
-		)
-		.await;
+	let dirty_pages = request
+		.dirty_pages
+		.into_iter()
+		.map(pump_dirty_page)
+		.collect();
+	let options = depot::types::CommitOptions {
+		expected_head_txid: request.expected_head_txid,
+		disable_size_cap: ctx.config().sqlite().unstable_disable_commit_size_cap(),
+		..Default::default()
+	};
+	let engine_result = match request.row_changes {
+		Some(row_changes) => {
+			actor_db
+				.commit_with_row_changes(
+					dirty_pages,
+					request.db_size_pages,
+					request.now_ms,
+					options,
+					pump_row_change_log_append(row_changes),
+				)
+				.await
+		}
+		None => {
+			actor_db
+				.commit_with_options(dirty_pages, request.db_size_pages, request.now_ms, options)
+				.await
+		}
+	};
 	let response_measure = perf_start!(
 		&crate::metrics::SQLITE_COMMIT_ENVOY_RESPONSE_DURATION,
 		slow_ms = 100,
@@ -1585,11 +1643,15 @@ async fn handle_sqlite_commit(
 		},
 	);
 	let response = match engine_result {
-		Ok(result) => Ok(protocol::SqliteCommitResponse::SqliteCommitOk(
-			protocol::SqliteCommitOk {
-				head_txid: Some(result.head_txid),
-			},
-		)),
+		Ok(result) => {
+			wake_sqlite_replicator(ctx, conn, &actor_id);
+
+			Ok(protocol::SqliteCommitResponse::SqliteCommitOk(
+				protocol::SqliteCommitOk {
+					head_txid: Some(result.head_txid),
+				},
+			))
+		}
 		Err(err) => match depot_error(&err) {
 			Some(SqliteStorageError::CommitTooLarge {
 				actual_size_bytes,
@@ -1610,6 +1672,82 @@ async fn handle_sqlite_commit(
 	Ok(response)
 }
 
+/// Wakes the actor's SQLite replicator so a replicated database ships the commit right away. The
+/// signal is sent in the background, off the commit path, and dropped when the actor has no
+/// replicator.
+fn wake_sqlite_replicator(ctx: &StandaloneCtx, conn: &Conn, actor_id: &str) {
+	let Ok(actor_id) = Id::parse(actor_id) else {
+		return;
+	};
+	let wakeups = conn.sqlite_replicator_wakeups.clone();
+	let ctx = ctx.clone();
+	tokio::spawn(async move {
+		match wakeups.entry_async(actor_id).await {
+			// The wakeup in flight sends another one after it
+			scc::hash_map::Entry::Occupied(mut entry) => {
+				*entry.get_mut() = true;
+				return;
+			}
+			scc::hash_map::Entry::Vacant(entry) => {
+				entry.insert_entry(false);
+			}
+		}
+
+		loop {
+			if let Err(err) = ctx
+				.signal(pegboard::workflows::sqlite_replicator::ChangesCommitted {})
+				.to_workflow::<pegboard::workflows::sqlite_replicator::Workflow>()
+				.tag("actor_id", actor_id)
+				.graceful_not_found()
+				.send()
+				.await
+			{
+				tracing::warn!(?err, %actor_id, "failed to wake sqlite replicator");
+			}
+
+			match wakeups.entry_async(actor_id).await {
+				scc::hash_map::Entry::Occupied(mut entry) if *entry.get() => {
+					*entry.get_mut() = false;
+				}
+				scc::hash_map::Entry::Occupied(entry) => {
+					entry.remove();
+					break;
+				}
+				scc::hash_map::Entry::Vacant(_) => break,
+			}
+		}
+	});
+}
+
+async fn handle_sqlite_get_row_change_log(
+	ctx: &StandaloneCtx,
+	conn: &Conn,
+	request: protocol::SqliteGetRowChangeLogRequest,
+) -> Result<protocol::SqliteGetRowChangeLogResponse> {
+	validate_sqlite_actor_for_request(ctx, conn, &request.actor_id, request.expected_generation)
+		.await?;
+
+	let actor_db = actor_db(ctx, conn, request.actor_id).await?;
+	let log = actor_db.row_change_log().await?;
+	Ok(
+		protocol::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(
+			protocol::SqliteGetRowChangeLogOk {
+				log: log.map(|log| protocol::SqliteRowChangeLog {
+					floor: protocol_row_change_position(log.floor),
+					changes: log
+						.changes
+						.into_iter()
+						.map(|change| protocol::SqliteLoggedRowChange {
+							position: protocol_row_change_position(change.position),
+							change: change.change,
+						})
+						.collect(),
+				}),
+			},
+		),
+	)
+}
+
 fn sqlite_commit_response_kind(response: &protocol::SqliteCommitResponse) -> &'static str {
 	match response {
 		protocol::SqliteCommitResponse::SqliteCommitOk(_) => "ok",
@@ -1617,6 +1755,15 @@ fn sqlite_commit_response_kind(response: &protocol::SqliteCommitResponse) -> &'s
 	}
 }
 
+fn sqlite_get_row_change_log_response_kind(
+	response: &protocol::SqliteGetRowChangeLogResponse,
+) -> &'static str {
+	match response {
+		protocol::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(_) => "ok",
+		protocol::SqliteGetRowChangeLogResponse::SqliteErrorResponse(_) => "error",
+	}
+}
+
 fn sqlite_get_pages_response_kind(response: &protocol::SqliteGetPagesResponse) -> &'static str {
 	match response {
 		protocol::SqliteGetPagesResponse::SqliteGetPagesOk(_) => "ok",
@@ -2025,6 +2172,31 @@ fn pump_dirty_page(page: protocol::SqliteDirtyPage) -> depot::types::DirtyPage {
 	}
 }
 
+fn pump_row_change_log_append(
+	log: protocol::SqliteRowChangeLogAppend,
+) -> depot::types::RowChangeLogAppend {
+	depot::types::RowChangeLogAppend {
+		changes: log
+			.changes
+			.into_iter()
+			.map(|change| (change.index, change.change))
+			.collect(),
+		floor: depot::types::RowChangePosition {
+			txid: log.floor.txid,
+			index: log.floor.index,
+		},
+	}
+}
+
+fn protocol_row_change_position(
+	position: depot::types::RowChangePosition,
+) -> protocol::SqliteRowChangePosition {
+	protocol::SqliteRowChangePosition {
+		txid: position.txid,
+		index: position.index,
+	}
+}
+
 fn bind_param_from_protocol(param: protocol::SqliteBindParam) -> BindParam {
 	match param {
 		protocol::SqliteBindParam::SqliteValueNull => BindParam::Null,
@@ -2121,12 +2293,10 @@ async fn actor_db(ctx: &StandaloneCtx, conn: &Conn, actor_id: String) -> Result<
 		.await
 		.or_insert_with(|| {
 			if compaction_disabled {
-				return Arc::new(Db::new(
-					conn.udb.clone(),
-					conn.namespace_id,
-					actor_id,
-					conn.node_id,
-				));
+				return Arc::new(
+					Db::new(conn.udb.clone(), conn.namespace_id, actor_id, conn.node_id)
+						.with_cold_store(conn.cold_store.clone()),
+				);
 			}
 
 			let compaction_signaler = Arc::new(move |_signal: DeltasAvailable| {
@@ -2152,13 +2322,16 @@ async fn actor_db(ctx: &StandaloneCtx, conn: &Conn, actor_id: String) -> Result<
 				.boxed()
 			});
 
-			Arc::new(Db::new_with_compaction_signaler(
-				conn.udb.clone(),
-				conn.namespace_id,
-				actor_id,
-				conn.node_id,
-				compaction_signaler,
-			))
+			Arc::new(
+				Db::new_with_compaction_signaler(
+					conn.udb.clone(),
+					conn.namespace_id,
+					actor_id,
+					conn.node_id,
+					compaction_signaler,
+				)
+				.with_cold_store(conn.cold_store.clone()),
+			)
 		})
 		.get()
 		.clone();
@@ -2224,7 +2397,8 @@ fn tunnel_message_inner_data_len(kind: &protocol::ToRivetTunnelMessageKind) -> u
 		ToRivetTunnelMessageKind::ToRivetResponseAbort
 		| ToRivetTunnelMessageKind::ToRivetWebSocketOpen(_)
 		| ToRivetTunnelMessageKind::ToRivetWebSocketMessageAck(_)
-		| ToRivetTunnelMessageKind::ToRivetWebSocketClose(_) => 0,
+		| ToRivetTunnelMessageKind::ToRivetWebSocketClose(_)
+		| ToRivetTunnelMessageKind::ToRivetWebSocketCredit(_) => 0,
 	}
 }
 
@@ -2298,6 +2472,21 @@ pub(super) async fn send_sqlite_commit_response(
 	.await
 }
 
+pub(super) async fn send_sqlite_get_row_change_log_response(
+	conn: &Conn,
+	request_id: u32,
+	data: protocol::SqliteGetRowChangeLogResponse,
+) -> Result<()> {
+	send_to_envoy(
+		conn,
+		protocol::ToEnvoy::ToEnvoySqliteGetRowChangeLogResponse(
+			protocol::ToEnvoySqliteGetRowChangeLogResponse { request_id, data },
+		),
+		"sqlite get_row_change_log response",
+	)
+	.await
+}
+
 pub(super) async fn send_sqlite_exec_response(
 	conn: &Conn,
 	request_id: u32,
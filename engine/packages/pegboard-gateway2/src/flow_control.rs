@@ -0,0 +1,355 @@
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+
+use gas::prelude::*;
+use rivet_config::config::pegboard::SlowConsumerPolicy;
+use tokio::{sync::Semaphore, time::Instant};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::metrics;
+
+pub const SLOW_CONSUMER_CLOSE_REASON: &str = "ws.slow_consumer";
+
+/// Per-connection WebSocket flow control.
+pub struct FlowControl {
+	namespace_id: Id,
+	pool_name: String,
+	pub policy: SlowConsumerPolicy,
+	pub close_code: u16,
+	/// Max bytes of actor messages buffered for the client.
+	pub max_buffered_size: usize,
+	/// Whether the actor was granted a send window and waits for credit from the gateway.
+	pub send_credit: bool,
+	/// Bytes of client messages the actor has not handled yet. Not set if the envoy does not
+	/// return credit.
+	in_flight: Option<Arc<Semaphore>>,
+	max_in_flight_size: usize,
+}
+
+impl FlowControl {
+	pub fn new(
+		config: &rivet_config::Config,
+		namespace_id: Id,
+		pool_name: String,
+		envoy_flow_control: bool,
+	) -> Self {
+		let pegboard = config.pegboard();
+		let policy = pegboard.gateway_websocket_slow_consumer_policy();
+		let max_in_flight_size = pegboard.gateway_websocket_max_in_flight_size() as usize;
+
+		FlowControl {
+			namespace_id,
+			pool_name,
+			policy,
+			close_code: pegboard.gateway_websocket_slow_consumer_close_code(),
+			max_buffered_size: pegboard.gateway_websocket_max_buffered_size() as usize,
+			send_credit: envoy_flow_control && policy == SlowConsumerPolicy::Pause,
+			in_flight: envoy_flow_control.then(|| Arc::new(Semaphore::new(max_in_flight_size))),
+			max_in_flight_size,
+		}
+	}
+
+	/// Send window granted to the actor in the websocket open message.
+	pub fn send_window(config: &rivet_config::Config) -> Option<u32> {
+		let pegboard = config.pegboard();
+
+		(pegboard.gateway_websocket_slow_consumer_policy() == SlowConsumerPolicy::Pause)
+			.then(|| pegboard.gateway_websocket_max_buffered_size() as u32)
+	}
+
+	/// Waits until a client message of `len` bytes fits in the actor's in-flight window. Messages
+	/// larger than the window wait for the whole window.
+	pub async fn acquire_in_flight(&self, len: usize) {
+		let Some(in_flight) = &self.in_flight else {
+			return;
+		};
+
+		let permits = len.min(self.max_in_flight_size) as u32;
+		if in_flight.available_permits() < permits as usize {
+			self.record("backpressure");
+		}
+
+		// The semaphore is never closed
+		if let Ok(permit) = in_flight.acquire_many(permits).await {
+			permit.forget();
+		}
+	}
+
+	/// Returns bytes the actor handled to the in-flight window.
+	pub fn release_in_flight(&self, bytes: u32) {
+		let Some(in_flight) = &self.in_flight else {
+			return;
+		};
+
+		// Never grow the window past its size, even if the envoy returns more than it was sent
+		let free = self
+			.max_in_flight_size
+			.saturating_sub(in_flight.available_permits());
+		in_flight.add_permits((bytes as usize).min(free));
+	}
+
+	pub fn record(&self, outcome: &str) {
+		metrics::WEBSOCKET_BACKPRESSURE_TOTAL
+			.with_label_values(&[
+				self.namespace_id.to_string().as_str(),
+				self.pool_name.as_str(),
+				outcome,
+			])
+			.inc();
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Admit {
+	Buffered,
+	/// The buffer is full and the policy drops messages.
+	Dropped,
+	/// The buffer is full and the client must be disconnected.
+	Disconnect,
+}
+
+/// Actor messages waiting to be written to the client. A message counts towards the buffered size
+/// until it is written.
+pub struct ClientBuffer {
+	policy: SlowConsumerPolicy,
+	max_size: usize,
+	send_credit: bool,
+	msgs: VecDeque<Message>,
+	size: usize,
+	pending_credit: usize,
+}
+
+impl ClientBuffer {
+	pub fn new(policy: SlowConsumerPolicy, max_size: usize, send_credit: bool) -> Self {
+		ClientBuffer {
+			policy,
+			max_size,
+			send_credit,
+			msgs: VecDeque::new(),
+			size: 0,
+			pending_credit: 0,
+		}
+	}
+
+	pub fn size(&self) -> usize {
+		self.size
+	}
+
+	/// Buffers a message unless it overflows the buffer. A message larger than the buffer is
+	/// accepted when the buffer is empty.
+	pub fn push(&mut self, msg: Message) -> Admit {
+		let len = msg.len();
+		if self.size > 0 && self.size + len > self.max_size {
+			// Actors paused by a send window never overflow the buffer, so a paused overflow is an
+			// envoy without flow control
+			return match self.policy {
+				SlowConsumerPolicy::Drop => Admit::Dropped,
+				SlowConsumerPolicy::Disconnect | SlowConsumerPolicy::Pause => Admit::Disconnect,
+			};
+		}
+
+		self.size += len;
+		self.msgs.push_back(msg);
+
+		Admit::Buffered
+	}
+
+	/// Next message to write to the client.
+	pub fn pop(&mut self) -> Option<Message> {
+		self.msgs.pop_front()
+	}
+
+	/// Records a written message. Returns the credit to send to the actor, batched for bursts of
+	/// small messages.
+	pub fn written(&mut self, len: usize) -> Option<u32> {
+		self.size -= len;
+
+		if !self.send_credit {
+			return None;
+		}
+
+		self.pending_credit += len;
+		if self.msgs.is_empty() || self.pending_credit >= self.max_size / 4 {
+			Some(std::mem::take(&mut self.pending_credit) as u32)
+		} else {
+			None
+		}
+	}
+
+	/// Takes every message not yet written.
+	pub fn drain(&mut self) -> impl Iterator<Item = Message> + '_ {
+		self.size = 0;
+		self.msgs.drain(..)
+	}
+}
+
+/// Token bucket over bytes. `rivet_util::throttle::RateLimiter` only hands out one token per
+/// acquire.
+pub struct ByteRateLimiter {
+	capacity: f64,
+	bytes_per_sec: f64,
+	tokens: f64,
+	last_refill: Instant,
+}
+
+impl ByteRateLimiter {
+	pub fn new(burst: u64, bytes_per_sec: u64) -> Self {
+		ByteRateLimiter {
+			capacity: burst as f64,
+			bytes_per_sec: bytes_per_sec.max(1) as f64,
+			tokens: burst as f64,
+			last_refill: Instant::now(),
+		}
+	}
+
+	/// Waits until `len` bytes are available. Messages larger than the burst wait for a full
+	/// bucket. Returns true if the caller was throttled.
+	pub async fn acquire(&mut self, len: usize) -> bool {
+		let cost = (len as f64).min(self.capacity);
+
+		self.refill();
+		let throttled = self.tokens < cost;
+		if throttled {
+			let wait = (cost - self.tokens) / self.bytes_per_sec;
+			tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+			self.refill();
+		}
+
+		self.tokens -= cost;
+
+		throttled
+	}
+
+	fn refill(&mut self) {
+		let now = Instant::now();
+		let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+		self.tokens = (self.tokens + elapsed * self.bytes_per_sec).min(self.capacity);
+		self.last_refill = now;
+	}
+}
+
+// Tests are inline because `flow_control` is private.
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use rivet_config::config::pegboard::SlowConsumerPolicy;
+	use tokio::time::Instant;
+	use tokio_tungstenite::tungstenite::Message;
+
+	use super::{Admit, ByteRateLimiter, ClientBuffer};
+
+	fn msg(len: usize) -> Message {
+		Message::Binary(vec![0; len].into())
+	}
+
+	#[test]
+	fn drop_policy_discards_overflow() {
+		let mut buffer = ClientBuffer::new(SlowConsumerPolicy::Drop, 100, false);
+
+		assert_eq!(buffer.push(msg(60)), Admit::Buffered);
+		assert_eq!(buffer.push(msg(60)), Admit::Dropped);
+		assert_eq!(buffer.push(msg(40)), Admit::Buffered);
+		assert_eq!(buffer.size(), 100);
+
+		// Dropped messages are never written
+		let lens = buffer.drain().map(|msg| msg.len()).collect::<Vec<_>>();
+		assert_eq!(lens, vec![60, 40]);
+		assert_eq!(buffer.size(), 0);
+	}
+
+	#[test]
+	fn disconnect_policy_disconnects_on_overflow() {
+		let mut buffer = ClientBuffer::new(SlowConsumerPolicy::Disconnect, 100, false);
+
+		assert_eq!(buffer.push(msg(100)), Admit::Buffered);
+		assert_eq!(buffer.push(msg(1)), Admit::Disconnect);
+	}
+
+	#[test]
+	fn in_progress_write_counts_towards_buffer() {
+		let mut buffer = ClientBuffer::new(SlowConsumerPolicy::Disconnect, 100, false);
+
+		assert_eq!(buffer.push(msg(80)), Admit::Buffered);
+		let written = buffer.pop().expect("message must be buffered");
+		assert_eq!(buffer.push(msg(40)), Admit::Disconnect);
+
+		assert_eq!(buffer.written(written.len()), None);
+		assert_eq!(buffer.push(msg(40)), Admit::Buffered);
+	}
+
+	#[test]
+	fn oversized_message_fits_empty_buffer() {
+		for policy in [
+			SlowConsumerPolicy::Drop,
+			SlowConsumerPolicy::Disconnect,
+			SlowConsumerPolicy::Pause,
+		] {
+			let mut buffer = ClientBuffer::new(policy, 100, false);
+			assert_eq!(buffer.push(msg(500)), Admit::Buffered);
+		}
+	}
+
+	#[test]
+	fn pause_policy_without_credit_disconnects_on_overflow() {
+		// Envoys without flow control are not paused by a send window
+		let mut buffer = ClientBuffer::new(SlowConsumerPolicy::Pause, 100, false);
+
+		assert_eq!(buffer.push(msg(100)), Admit::Buffered);
+		assert_eq!(buffer.push(msg(1)), Admit::Disconnect);
+	}
+
+	#[test]
+	fn pause_policy_returns_written_bytes_as_credit() {
+		let mut buffer = ClientBuffer::new(SlowConsumerPolicy::Pause, 100, true);
+
+		// An actor with a 100 byte window fills it without overflowing
+		for _ in 0..10 {
+			assert_eq!(buffer.push(msg(10)), Admit::Buffered);
+		}
+
+		// Credit is batched in quarters of the window and flushed once the buffer is empty
+		let mut credit = Vec::new();
+		while let Some(msg) = buffer.pop() {
+			credit.extend(buffer.written(msg.len()));
+		}
+		assert_eq!(credit, vec![30, 30, 30, 10]);
+		assert_eq!(credit.iter().sum::<u32>(), 100);
+		assert_eq!(buffer.size(), 0);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn byte_rate_limiter_allows_burst() {
+		let mut limiter = ByteRateLimiter::new(1000, 100);
+		let start = Instant::now();
+
+		assert!(!limiter.acquire(600).await);
+		assert!(!limiter.acquire(400).await);
+		assert_eq!(start.elapsed(), Duration::ZERO);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn byte_rate_limiter_throttles_past_burst() {
+		let mut limiter = ByteRateLimiter::new(1000, 100);
+		let start = Instant::now();
+
+		assert!(!limiter.acquire(1000).await);
+		assert!(limiter.acquire(200).await);
+		assert_eq!(start.elapsed(), Duration::from_secs(2));
+
+		// Tokens refill while idle
+		tokio::time::advance(Duration::from_secs(10)).await;
+		let start = Instant::now();
+		assert!(!limiter.acquire(1000).await);
+		assert_eq!(start.elapsed(), Duration::ZERO);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn byte_rate_limiter_caps_oversized_messages_at_burst() {
+		let mut limiter = ByteRateLimiter::new(1000, 100);
+		let start = Instant::now();
+
+		assert!(!limiter.acquire(5000).await);
+		assert!(limiter.acquire(5000).await);
+		assert_eq!(start.elapsed(), Duration::from_secs(10));
+	}
+}
@@ -2,13 +2,17 @@ use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use bytes::Bytes;
 use gas::prelude::*;
-use http_body_util::{BodyExt, Full};
-use hyper::{Request, Response, StatusCode, body::Body};
+use http_body_util::{BodyExt, Full, StreamBody};
+use hyper::{
+	HeaderMap, Request, Response,
+	body::Body,
+	header::{HeaderName, HeaderValue},
+};
 use rivet_envoy_protocol as protocol;
 use rivet_error::*;
 use rivet_guard_core::{
 	ResponseBody, WebSocketHandle,
-	custom_serve::{CustomServeTrait, HibernationResult},
+	custom_serve::{CustomServeTrait, HibernationResult, StreamingBody},
 	errors::{
 		ActorStoppedWhileWaiting, ActorStoppedWhileWaitingForWebSocketOpen,
 		GatewayResponseStartTimeout, TunnelMessageTimeout, TunnelRequestAborted,
@@ -20,22 +24,31 @@ use rivet_guard_core::{
 };
 use std::{
 	collections::HashMap,
-	sync::{Arc, atomic::AtomicU64},
+	sync::{
+		Arc,
+		atomic::{AtomicU64, Ordering},
+	},
 	time::{Duration, Instant},
 };
-use tokio::sync::watch;
+use tokio::sync::{mpsc, watch};
 use tokio_tungstenite::tungstenite::protocol::frame::{CloseFrame, coding::CloseCode};
 use universaldb::utils::IsolationLevel::*;
 
-use crate::shared_state::{
-	InFlightRequestCtx, RequestProtocol, RequestStopResult, SharedState, display_id,
+use crate::{
+	flow_control::FlowControl,
+	shared_state::{
+		InFlightRequestCtx, RequestProtocol, RequestStopResult, SharedState, display_id,
+	},
 };
 
+mod flow_control;
 mod hibernation_task;
 mod keepalive_task;
 pub mod metrics;
 mod metrics_task;
 mod ping_task;
+mod request_body_task;
+mod response_body_task;
 pub mod shared_state;
 mod tunnel_to_ws_task;
 mod ws_to_tunnel_task;
@@ -47,6 +60,13 @@ const PHASE_WAITING_FOR_RESPONSE_START: &str = "waiting_for_response_start";
 const PHASE_PRE_WEBSOCKET_OPEN: &str = "pre_websocket_open";
 const PHASE_WAITING_FOR_WEBSOCKET_OPEN: &str = "waiting_for_websocket_open";
 const SLOW_WEBSOCKET_OPEN_WAIT_THRESHOLD: Duration = Duration::from_secs(1);
+/// Response frames buffered for a client reading a streaming response.
+const RESPONSE_FRAME_BUFFER: usize = 16;
+/// First envoy protocol version that carries HTTP trailers.
+const TRAILERS_PROTOCOL_VERSION: u16 = 7;
+/// How long the protocol version of an envoy is cached. An envoy that reconnects with a newer
+/// version gets trailers once this expires.
+const ENVOY_PROTOCOL_VERSION_CACHE_TTL_MS: i64 = 60_000;
 
 #[derive(RivetError, Serialize, Deserialize)]
 #[error(
@@ -56,6 +76,26 @@ const SLOW_WEBSOCKET_OPEN_WAIT_THRESHOLD: Duration = Duration::from_secs(1);
 )]
 pub struct WebsocketPendingLimitReached;
 
+#[derive(RivetError, Serialize, Deserialize)]
+#[error(
+	"guard",
+	"envoy_protocol_outdated",
+	"The actor's envoy does not support this request.",
+	"The actor's envoy uses envoy-protocol v{envoy_protocol_version}, but {feature} require v{required_version}."
+)]
+pub struct EnvoyProtocolOutdated {
+	pub feature: String,
+	pub envoy_protocol_version: u16,
+	pub required_version: u16,
+}
+
+/// Body of an HTTP request forwarded to the actor.
+enum RequestBody {
+	Full(Bytes),
+	/// Forwarded as it arrives, used for gRPC.
+	Stream(StreamingBody),
+}
+
 #[derive(Debug)]
 enum LifecycleResult {
 	ServerClose(protocol::ToRivetWebSocketClose),
@@ -113,33 +153,15 @@ impl PegboardGateway2 {
 	async fn handle_request_inner(
 		&self,
 		ctx: &StandaloneCtx,
-		req: Request<Full<Bytes>>,
+		headers: HashMap<String, String>,
+		body: RequestBody,
+		ingress_bytes: Arc<AtomicU64>,
 		req_ctx: &mut RequestContext,
 	) -> Result<Response<ResponseBody>> {
 		// Use the actor ID from the gateway instance
 		let actor_id = self.actor_id.to_string();
 		let request_id = req_ctx.in_flight_request_id()?;
 
-		// Extract request parts
-		let headers = req
-			.headers()
-			.iter()
-			.filter_map(|(name, value)| {
-				value
-					.to_str()
-					.ok()
-					.map(|value_str| (name.to_string(), value_str.to_string()))
-			})
-			.collect::<HashMap<_, _>>();
-
-		// NOTE: Size constraints have already been applied by guard
-		let body_bytes = req
-			.into_body()
-			.collect()
-			.await
-			.context("failed to read body")?
-			.to_bytes();
-
 		let mut stopped_sub = ctx
 			.subscribe::<pegboard::workflows::actor2::Stopped>(("actor_id", self.actor_id))
 			.await?;
@@ -174,6 +196,25 @@ impl PegboardGateway2 {
 			.build());
 		}
 
+		// Older envoys cannot receive trailers, which gRPC depends on
+		let trailers = if let RequestBody::Stream(_) = &body {
+			let envoy_protocol_version = self.envoy_protocol_version(ctx).await?;
+			let trailers = envoy_protocol_version >= TRAILERS_PROTOCOL_VERSION;
+
+			if !trailers && is_grpc(&headers) {
+				return Err(EnvoyProtocolOutdated {
+					feature: "grpc requests".to_string(),
+					envoy_protocol_version,
+					required_version: TRAILERS_PROTOCOL_VERSION,
+				}
+				.build());
+			}
+
+			trailers
+		} else {
+			false
+		};
+
 		// Build subject to publish to
 		let tunnel_subject = pegboard::pubsub_subjects::EnvoyReceiverSubject::new(
 			self.namespace_id,
@@ -200,20 +241,21 @@ impl PegboardGateway2 {
 			)
 			.await?;
 
+		let mut request_body = None;
 		let res = async {
 			// Start request
+			let (body, stream_body) = match body {
+				RequestBody::Full(body) => ((!body.is_empty()).then(|| body.to_vec()), None),
+				RequestBody::Stream(body) => (None, Some(body)),
+			};
 			let message = protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestStart(
 				protocol::ToEnvoyRequestStart {
 					actor_id: actor_id.clone(),
 					method: req_ctx.method().to_string(),
 					path: self.path.clone(),
 					headers,
-					body: if body_bytes.is_empty() {
-						None
-					} else {
-						Some(body_bytes.to_vec())
-					},
-					stream: false,
+					body,
+					stream: stream_body.is_some(),
 				},
 			);
 
@@ -231,6 +273,18 @@ impl PegboardGateway2 {
 				res = in_flight_req.send_message(message, false) => res?,
 			}
 
+			if let Some(stream_body) = stream_body {
+				request_body = Some(tokio::spawn(
+					request_body_task::task(
+						in_flight_req.clone(),
+						stream_body,
+						trailers,
+						ingress_bytes.clone(),
+					)
+					.in_current_span(),
+				));
+			}
+
 			// Wait for response
 			tracing::debug!("gateway waiting for response from tunnel");
 			let fut = async {
@@ -304,33 +358,201 @@ impl PegboardGateway2 {
 				})??;
 			tracing::debug!("response handler task ended");
 
-			// Build HTTP response
-			let mut response_builder =
-				Response::builder().status(StatusCode::from_u16(response_start.status)?);
+			anyhow::Ok(response_start)
+		}
+		.await;
+
+		let response_start = match res {
+			Ok(response_start) => response_start,
+			Err(err) => {
+				request_body_task::abort(&in_flight_req, request_body).await;
+				in_flight_req.stop(RequestStopResult::EnvoyError).await;
 
-			// Add headers from actor
-			for (key, value) in response_start.headers {
-				response_builder = response_builder.header(key, value);
+				return Err(err);
 			}
+		};
 
-			// Add body
-			let body = response_start.body.unwrap_or_default();
-			let response =
-				response_builder.body(ResponseBody::Full(Full::new(Bytes::from(body))))?;
+		// Build HTTP response
+		let mut response_builder = Response::builder().status(response_start.status);
+
+		// Add headers from actor
+		for (key, value) in response_start.headers {
+			response_builder = response_builder.header(key, value);
+		}
 
-			in_flight_req.stop(RequestStopResult::Success).await;
+		if response_start.stream {
+			// The response body is forwarded by a separate task, which stops the in flight request
+			// once the response finishes
+			let (frame_tx, frame_rx) = mpsc::channel(RESPONSE_FRAME_BUFFER);
+			let mut egress_bytes = 0;
+			if let Some(body) = response_start.body.filter(|body| !body.is_empty()) {
+				egress_bytes = body.len();
+				// The channel is empty
+				let _ = frame_tx.try_send(Ok(hyper::body::Frame::data(Bytes::from(body))));
+			}
+
+			let body = StreamBody::new(futures_util::stream::unfold(
+				frame_rx,
+				|mut frame_rx| async move { frame_rx.recv().await.map(|frame| (frame, frame_rx)) },
+			));
+			let response = match response_builder.body(ResponseBody::Stream(body.boxed_unsync())) {
+				Ok(response) => response,
+				Err(err) => {
+					request_body_task::abort(&in_flight_req, request_body).await;
+					in_flight_req.stop(RequestStopResult::EnvoyError).await;
+
+					return Err(err.into());
+				}
+			};
+
+			tokio::spawn(
+				response_body_task::task(
+					ctx.clone(),
+					self.actor_id,
+					self.namespace_id,
+					in_flight_req,
+					stopped_sub,
+					msg_rx,
+					drop_rx,
+					request_body,
+					ingress_bytes,
+					egress_bytes,
+					frame_tx,
+				)
+				.in_current_span(),
+			);
 
 			Ok(response)
+		} else {
+			request_body_task::abort(&in_flight_req, request_body).await;
+
+			let body = response_start.body.unwrap_or_default();
+			let res = response_builder
+				.body(ResponseBody::Full(Full::new(Bytes::from(body))))
+				.map_err(Into::into);
+
+			in_flight_req
+				.stop(if res.is_ok() {
+					RequestStopResult::Success
+				} else {
+					RequestStopResult::EnvoyError
+				})
+				.await;
+
+			res
 		}
-		.await;
+	}
 
-		if res.is_err() {
-			in_flight_req.stop(RequestStopResult::EnvoyError).await;
+	/// Streamed request and response bodies are counted as they are forwarded, since their size is
+	/// not known up front.
+	async fn handle_request_with_metrics(
+		&self,
+		headers: HashMap<String, String>,
+		body: RequestBody,
+		req_ctx: &mut RequestContext,
+	) -> Result<Response<ResponseBody>> {
+		let ctx = self.ctx.with_ray(req_ctx.ray_id(), req_ctx.req_id())?;
+		let req_body_size = match &body {
+			RequestBody::Full(body) => body.len(),
+			RequestBody::Stream(_) => 0,
+		};
+		let ingress_bytes = Arc::new(AtomicU64::new(0));
+
+		let (res, metrics_res) = tokio::join!(
+			self.handle_request_inner(&ctx, headers, body, ingress_bytes.clone(), req_ctx),
+			record_req_metrics(
+				&ctx,
+				self.actor_id,
+				self.namespace_id,
+				Metric::HttpIngress(req_body_size),
+			),
+		);
+
+		// The response body task records streamed responses once they finish
+		let streamed_response =
+			matches!(&res, Ok(res) if matches!(res.body(), ResponseBody::Stream(_)));
+		let response_size = match &res {
+			Ok(res) => res.size_hint().upper().unwrap_or(res.size_hint().lower()),
+			Err(_) => 0,
+		};
+		// The request body was fully forwarded or aborted by now
+		let streamed_ingress = ingress_bytes.load(Ordering::Acquire);
+
+		if let Err(err) = metrics_res {
+			tracing::error!(?err, "http req ingress metrics failed");
+		} else if !streamed_response {
+			let actor_id = self.actor_id;
+			let namespace_id = self.namespace_id;
+			let envoy_key = self.envoy_key.clone();
+			tokio::spawn(
+				async move {
+					if let Err(err) = record_req_metrics(
+						&ctx,
+						actor_id,
+						namespace_id,
+						Metric::HttpFinish(streamed_ingress as usize, response_size as usize),
+					)
+					.await
+					{
+						tracing::error!(
+							?err,
+							?namespace_id,
+							%envoy_key,
+							"http req egress metrics failed, likely corrupt now",
+						);
+					}
+				}
+				.in_current_span(),
+			);
 		}
 
 		res
 	}
 
+	/// Cached per envoy since every streamed request reads it.
+	async fn envoy_protocol_version(&self, ctx: &StandaloneCtx) -> Result<u16> {
+		let protocol_version = ctx
+			.cache()
+			.clone()
+			.request()
+			.ttl(ENVOY_PROTOCOL_VERSION_CACHE_TTL_MS)
+			.fetch_one_json(
+				"pegboard_gateway2.envoy_protocol_version",
+				(self.namespace_id, self.envoy_key.clone()),
+				move |mut cache, key| async move {
+					let (namespace_id, envoy_key) = key.clone();
+					let protocol_version = ctx
+						.udb()?
+						.txn("gateway_envoy_protocol_version", |tx| {
+							let envoy_key = envoy_key.clone();
+							async move {
+								let tx = tx.with_subspace(pegboard::keys::subspace());
+
+								tx.read_opt(
+									&pegboard::keys::envoy::ProtocolVersionKey::new(
+										namespace_id,
+										envoy_key,
+									),
+									Serializable,
+								)
+								.await
+							}
+						})
+						.await?;
+
+					// Envoys write their version on connect, so a missing one is not cached
+					if let Some(protocol_version) = protocol_version {
+						cache.resolve(&key, protocol_version);
+					}
+
+					Ok(cache)
+				},
+			)
+			.await?;
+
+		Ok(protocol_version.unwrap_or_default())
+	}
+
 	async fn handle_websocket_inner(
 		&self,
 		ctx: &StandaloneCtx,
@@ -410,9 +632,10 @@ impl PegboardGateway2 {
 			.await?;
 
 		let res = async {
-			// If we are reconnecting after hibernation, don't send an open message
-			let can_hibernate = if after_hibernation {
-				true
+			// If we are reconnecting after hibernation, don't send an open message. Flow control is
+			// only negotiated by the open message.
+			let (can_hibernate, envoy_flow_control) = if after_hibernation {
+				(true, false)
 			} else {
 				// Send WebSocket open message
 				let open_message = protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(
@@ -420,6 +643,7 @@ impl PegboardGateway2 {
 						actor_id: self.actor_id.to_string(),
 						path: self.path.clone(),
 						headers: request_headers,
+						send_window: FlowControl::send_window(ctx.config()),
 					},
 				);
 
@@ -642,9 +866,16 @@ impl PegboardGateway2 {
 					.toggle_hibernatable(open_msg.can_hibernate)
 					.await?;
 
-				open_msg.can_hibernate
+				(open_msg.can_hibernate, open_msg.flow_control)
 			};
 
+			let flow_control = Arc::new(FlowControl::new(
+				ctx.config(),
+				self.namespace_id,
+				self.pool_name.clone(),
+				envoy_flow_control,
+			));
+
 			let ingress_bytes = Arc::new(AtomicU64::new(0));
 			let egress_bytes = Arc::new(AtomicU64::new(0));
 
@@ -668,6 +899,7 @@ impl PegboardGateway2 {
 					drop_rx,
 					can_hibernate,
 					egress_bytes.clone(),
+					flow_control.clone(),
 					tunnel_to_ws_abort_rx,
 				)
 				.in_current_span(),
@@ -678,6 +910,7 @@ impl PegboardGateway2 {
 					in_flight_req.clone(),
 					ws_rx,
 					ingress_bytes.clone(),
+					flow_control,
 					ws_to_tunnel_abort_rx,
 				)
 				.in_current_span(),
@@ -919,57 +1152,41 @@ impl CustomServeTrait for PegboardGateway2 {
 		req: Request<Full<Bytes>>,
 		req_ctx: &mut RequestContext,
 	) -> Result<Response<ResponseBody>> {
-		let ctx = self.ctx.with_ray(req_ctx.ray_id(), req_ctx.req_id())?;
-		let req_body_size_hint = req.body().size_hint();
+		let (parts, body) = req.into_parts();
 
-		let (res, metrics_res) = tokio::join!(
-			self.handle_request_inner(&ctx, req, req_ctx),
-			record_req_metrics(
-				&ctx,
-				self.actor_id,
-				self.namespace_id,
-				Metric::HttpIngress(
-					req_body_size_hint
-						.upper()
-						.unwrap_or(req_body_size_hint.lower()) as usize
-				),
-			),
-		);
+		// NOTE: Size constraints have already been applied by guard
+		let body = body
+			.collect()
+			.await
+			.context("failed to read body")?
+			.to_bytes();
 
-		let response_size = match &res {
-			Ok(res) => res.size_hint().upper().unwrap_or(res.size_hint().lower()),
-			Err(_) => 0,
-		};
+		self.handle_request_with_metrics(
+			headers_to_map(&parts.headers),
+			RequestBody::Full(body),
+			req_ctx,
+		)
+		.await
+	}
 
-		if let Err(err) = metrics_res {
-			tracing::error!(?err, "http req ingress metrics failed");
-		} else {
-			let actor_id = self.actor_id;
-			let namespace_id = self.namespace_id;
-			let envoy_key = self.envoy_key.clone();
-			tokio::spawn(
-				async move {
-					if let Err(err) = record_req_metrics(
-						&ctx,
-						actor_id,
-						namespace_id,
-						Metric::HttpEgress(response_size as usize),
-					)
-					.await
-					{
-						tracing::error!(
-							?err,
-							?namespace_id,
-							%envoy_key,
-							"http req egress metrics failed, likely corrupt now",
-						);
-					}
-				}
-				.in_current_span(),
-			);
-		}
+	fn supports_streaming_requests(&self) -> bool {
+		true
+	}
 
-		res
+	#[tracing::instrument(skip_all, fields(actor_id=?self.actor_id, actor_key=?self.actor_key, actor_generation=?self.actor_generation, namespace_id=?self.namespace_id, pool_name=%self.pool_name, envoy_key=%self.envoy_key))]
+	async fn handle_streaming_request(
+		&self,
+		req: Request<StreamingBody>,
+		req_ctx: &mut RequestContext,
+	) -> Result<Response<ResponseBody>> {
+		let (parts, body) = req.into_parts();
+
+		self.handle_request_with_metrics(
+			headers_to_map(&parts.headers),
+			RequestBody::Stream(body),
+			req_ctx,
+		)
+		.await
 	}
 
 	#[tracing::instrument(skip_all, fields(actor_id=?self.actor_id, actor_key=?self.actor_key, actor_generation=?self.actor_generation, namespace_id=?self.namespace_id, pool_name=%self.pool_name, envoy_key=%self.envoy_key))]
@@ -1217,7 +1434,8 @@ impl CustomServeTrait for PegboardGateway2 {
 #[derive(Debug)]
 enum Metric {
 	HttpIngress(usize),
-	HttpEgress(usize),
+	// Streamed ingress, Egress
+	HttpFinish(usize, usize),
 	WebsocketOpen,
 	// Ingress, Egress
 	WebsocketTransfer(usize, usize),
@@ -1226,6 +1444,37 @@ enum Metric {
 	WebsocketStopHibernate,
 }
 
+/// Converts headers to the tunnel representation. Headers that are not valid UTF-8 are dropped.
+fn headers_to_map(headers: &HeaderMap) -> HashMap<String, String> {
+	headers
+		.iter()
+		.filter_map(|(name, value)| {
+			value
+				.to_str()
+				.ok()
+				.map(|value_str| (name.to_string(), value_str.to_string()))
+		})
+		.collect()
+}
+
+fn is_grpc(headers: &HashMap<String, String>) -> bool {
+	headers
+		.get("content-type")
+		.is_some_and(|content_type| content_type.starts_with("application/grpc"))
+}
+
+/// Converts headers from the tunnel representation. Invalid headers are dropped.
+fn map_to_headers(map: HashMap<String, String>) -> HeaderMap {
+	map.into_iter()
+		.filter_map(|(name, value)| {
+			Some((
+				HeaderName::try_from(name).ok()?,
+				HeaderValue::try_from(value).ok()?,
+			))
+		})
+		.collect()
+}
+
 #[tracing::instrument(skip_all, fields(?actor_id, ?metric))]
 async fn record_req_metrics(
 	ctx: &StandaloneCtx,
@@ -1284,7 +1533,18 @@ fn metric_inc(tx: &universaldb::Transaction, namespace_id: Id, name: &str, metri
 				1,
 			);
 		}
-		Metric::HttpEgress(size) => {
+		Metric::HttpFinish(ingress_size, egress_size) => {
+			if *ingress_size > 0 {
+				namespace::keys::metric::inc(
+					&tx.with_subspace(namespace::keys::subspace()),
+					namespace_id,
+					namespace::keys::metric::Metric::GatewayIngress(
+						name.to_string(),
+						"http".to_string(),
+					),
+					(*ingress_size).try_into().unwrap_or_default(),
+				);
+			}
 			namespace::keys::metric::inc(
 				&tx.with_subspace(namespace::keys::subspace()),
 				namespace_id,
@@ -1292,7 +1552,7 @@ fn metric_inc(tx: &universaldb::Transaction, namespace_id: Id, name: &str, metri
 					name.to_string(),
 					"http".to_string(),
 				),
-				(*size).try_into().unwrap_or_default(),
+				(*egress_size).try_into().unwrap_or_default(),
 			);
 			namespace::keys::metric::inc(
 				&tx.with_subspace(namespace::keys::subspace()),
@@ -59,4 +59,10 @@ lazy_static::lazy_static! {
 		&["namespace_id", "pool_name", "kind"],
 		*REGISTRY
 	).unwrap();
+	pub static ref WEBSOCKET_BACKPRESSURE_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"gateway2_websocket_backpressure_total",
+		"WebSocket messages held back or discarded by flow control, by outcome.",
+		&["namespace_id", "pool_name", "outcome"],
+		*REGISTRY
+	).unwrap();
 }
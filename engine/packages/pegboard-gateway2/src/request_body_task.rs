@@ -0,0 +1,135 @@
+use std::sync::{
+	Arc,
+	atomic::{AtomicU64, Ordering},
+};
+
+use anyhow::Result;
+use http_body_util::BodyExt;
+use rivet_envoy_protocol as protocol;
+use rivet_guard_core::custom_serve::StreamingBody;
+use tokio::task::JoinHandle;
+
+use crate::{
+	headers_to_map,
+	shared_state::{InFlightRequestHandle, display_id},
+};
+
+/// Forwards a streaming request body to the actor as it arrives. The finishing chunk carries the
+/// request trailers. Requests with trailers are aborted if the envoy cannot receive them. Forwarded
+/// bytes are added to `ingress_bytes`.
+#[tracing::instrument(name = "request_body_task", skip_all)]
+pub async fn task(
+	in_flight_req: InFlightRequestHandle,
+	mut body: StreamingBody,
+	trailers_supported: bool,
+	ingress_bytes: Arc<AtomicU64>,
+) -> Result<()> {
+	let mut trailers = None;
+
+	while let Some(frame) = body.frame().await {
+		let frame = match frame {
+			Ok(frame) => frame,
+			Err(err) => {
+				tracing::debug!(?err, "failed to read request body, aborting request");
+
+				in_flight_req
+					.send_message(
+						protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestAbort,
+						false,
+					)
+					.await?;
+
+				return Ok(());
+			}
+		};
+
+		match frame.into_data() {
+			Ok(data) => {
+				if data.is_empty() {
+					continue;
+				}
+
+				tracing::trace!(
+					request_id=%display_id(&in_flight_req.request_id),
+					data_len=data.len(),
+					"forwarding request body chunk to envoy"
+				);
+
+				in_flight_req
+					.send_message(
+						protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestChunk(
+							protocol::ToEnvoyRequestChunk {
+								body: data.to_vec(),
+								finish: false,
+								trailers: None,
+							},
+						),
+						false,
+					)
+					.await?;
+				ingress_bytes.fetch_add(data.len() as u64, Ordering::AcqRel);
+			}
+			Err(frame) => {
+				if let Ok(frame_trailers) = frame.into_trailers() {
+					trailers = Some(headers_to_map(&frame_trailers));
+				}
+			}
+		}
+	}
+
+	if trailers.is_some() && !trailers_supported {
+		tracing::debug!("envoy does not support request trailers, aborting request");
+
+		in_flight_req
+			.send_message(
+				protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestAbort,
+				false,
+			)
+			.await?;
+
+		return Ok(());
+	}
+
+	in_flight_req
+		.send_message(
+			protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestChunk(
+				protocol::ToEnvoyRequestChunk {
+					body: Vec::new(),
+					finish: true,
+					trailers,
+				},
+			),
+			false,
+		)
+		.await?;
+
+	Ok(())
+}
+
+/// Stops forwarding the request body. The envoy is told to abort the request if the body was not
+/// fully forwarded yet. Returns true if an abort was sent.
+pub async fn abort(
+	in_flight_req: &InFlightRequestHandle,
+	request_body: Option<JoinHandle<Result<()>>>,
+) -> bool {
+	let Some(request_body) = request_body else {
+		return false;
+	};
+	if request_body.is_finished() {
+		return false;
+	}
+
+	request_body.abort();
+
+	if let Err(err) = in_flight_req
+		.send_message(
+			protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestAbort,
+			false,
+		)
+		.await
+	{
+		tracing::debug!(?err, "failed to send request abort");
+	}
+
+	true
+}
@@ -0,0 +1,174 @@
+use std::{
+	collections::VecDeque,
+	sync::{
+		Arc,
+		atomic::{AtomicU64, Ordering},
+	},
+};
+
+use bytes::Bytes;
+use gas::prelude::*;
+use hyper::body::Frame;
+use rivet_envoy_protocol as protocol;
+use rivet_guard_core::errors::{
+	ActorStoppedWhileWaiting, TunnelMessageTimeout, TunnelRequestAborted, TunnelResponseClosed,
+};
+use tokio::{
+	sync::{mpsc, watch},
+	task::JoinHandle,
+};
+
+use crate::{
+	Metric, map_to_headers, record_req_metrics, request_body_task,
+	shared_state::{InFlightRequestHandle, MsgGcReason, RequestStopResult, display_id},
+};
+
+const PHASE_STREAMING_RESPONSE: &str = "streaming_response";
+
+pub type ResponseFrame = Result<Frame<Bytes>, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Forwards response chunks from the actor to the client body until the response finishes. The
+/// client body is bounded, so a slow client stops the task from reading further chunks. Records the
+/// streamed bytes and stops the in flight request once done.
+#[tracing::instrument(name = "response_body_task", skip_all)]
+pub async fn task(
+	ctx: StandaloneCtx,
+	actor_id: Id,
+	namespace_id: Id,
+	in_flight_req: InFlightRequestHandle,
+	mut stopped_sub: message::SubscriptionHandle<pegboard::workflows::actor2::Stopped>,
+	mut msg_rx: mpsc::UnboundedReceiver<protocol::ToRivetTunnelMessageKind>,
+	mut drop_rx: watch::Receiver<Option<MsgGcReason>>,
+	request_body: Option<JoinHandle<anyhow::Result<()>>>,
+	ingress_bytes: Arc<AtomicU64>,
+	mut egress_bytes: usize,
+	frame_tx: mpsc::Sender<ResponseFrame>,
+) {
+	// Frames of the last chunk waiting for room in the client body
+	let mut pending = VecDeque::<ResponseFrame>::new();
+	let mut finished = false;
+
+	let result = loop {
+		if finished && pending.is_empty() {
+			break RequestStopResult::Success;
+		}
+
+		tokio::select! {
+			res = frame_tx.reserve(), if !pending.is_empty() => match res {
+				Ok(permit) => {
+					if let Some(frame) = pending.pop_front() {
+						permit.send(frame);
+					}
+				}
+				Err(_) => {
+					tracing::debug!("client closed streaming response");
+					break RequestStopResult::ClientDisconnect;
+				}
+			},
+			res = msg_rx.recv(), if pending.is_empty() && !finished => match res {
+				Some(protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(chunk)) => {
+					tracing::trace!(
+						request_id=%display_id(&in_flight_req.request_id),
+						data_len=chunk.body.len(),
+						finish=chunk.finish,
+						"forwarding response body chunk to client"
+					);
+
+					if !chunk.body.is_empty() {
+						egress_bytes += chunk.body.len();
+						pending.push_back(Ok(Frame::data(Bytes::from(chunk.body))));
+					}
+
+					if chunk.finish {
+						if let Some(trailers) = chunk.trailers {
+							pending.push_back(Ok(Frame::trailers(map_to_headers(trailers))));
+						}
+
+						finished = true;
+					}
+				}
+				Some(protocol::ToRivetTunnelMessageKind::ToRivetResponseAbort) => {
+					tracing::warn!("streaming response aborted");
+					let _ = frame_tx.try_send(Err(TunnelRequestAborted {
+						phase: PHASE_STREAMING_RESPONSE.to_owned(),
+					}
+					.build()
+					.into()));
+
+					break RequestStopResult::EnvoyError;
+				}
+				Some(_) => {
+					tracing::warn!("received non-response message from pubsub");
+				}
+				None => {
+					let _ = frame_tx.try_send(Err(TunnelResponseClosed {
+						phase: PHASE_STREAMING_RESPONSE.to_owned(),
+					}
+					.build()
+					.into()));
+
+					break RequestStopResult::EnvoyError;
+				}
+			},
+			_ = drop_rx.changed() => {
+				tracing::warn!(reason=?drop_rx.borrow(), "tunnel message timeout");
+				let _ = frame_tx.try_send(Err(TunnelMessageTimeout {
+					phase: PHASE_STREAMING_RESPONSE.to_owned(),
+					reason: format!("{:?}", drop_rx.borrow().as_ref()),
+				}
+				.build()
+				.into()));
+
+				break RequestStopResult::EnvoyError;
+			}
+			_ = stopped_sub.next() => {
+				tracing::debug!("actor stopped while streaming response");
+				let _ = frame_tx.try_send(Err(ActorStoppedWhileWaiting {
+					actor_id: actor_id.to_string(),
+					phase: PHASE_STREAMING_RESPONSE.to_owned(),
+				}
+				.build()
+				.into()));
+
+				break RequestStopResult::EnvoyError;
+			}
+			_ = frame_tx.closed() => {
+				tracing::debug!("client closed streaming response");
+				break RequestStopResult::ClientDisconnect;
+			}
+		}
+	};
+
+	// Let the envoy release the request if it is still reading the request body or the client is
+	// gone
+	let aborted = request_body_task::abort(&in_flight_req, request_body).await;
+	if !aborted
+		&& matches!(result, RequestStopResult::ClientDisconnect)
+		&& let Err(err) = in_flight_req
+			.send_message(
+				protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestAbort,
+				false,
+			)
+			.await
+	{
+		tracing::debug!(?err, "failed to send request abort");
+	}
+
+	in_flight_req.stop(result).await;
+
+	let ingress_bytes = ingress_bytes.load(Ordering::Acquire);
+	if let Err(err) = record_req_metrics(
+		&ctx,
+		actor_id,
+		namespace_id,
+		Metric::HttpFinish(ingress_bytes as usize, egress_bytes),
+	)
+	.await
+	{
+		tracing::error!(
+			?err,
+			?namespace_id,
+			"http req egress metrics failed, likely corrupt now"
+		);
+	}
+}
@@ -73,6 +73,7 @@ fn to_envoy_tunnel_message_kind_name(kind: &protocol::ToEnvoyTunnelMessageKind)
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(_) => "ToEnvoyWebSocketOpen",
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketMessage(_) => "ToEnvoyWebSocketMessage",
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketClose(_) => "ToEnvoyWebSocketClose",
+		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(_) => "ToEnvoyWebSocketCredit",
 	}
 }
 
@@ -87,6 +88,7 @@ fn to_rivet_tunnel_message_kind_name(kind: &protocol::ToRivetTunnelMessageKind)
 			"ToRivetWebSocketMessageAck"
 		}
 		protocol::ToRivetTunnelMessageKind::ToRivetWebSocketClose(_) => "ToRivetWebSocketClose",
+		protocol::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(_) => "ToRivetWebSocketCredit",
 	}
 }
 
@@ -569,9 +571,15 @@ impl InFlightRequestHandle {
 			protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(_)
 		);
 
-		// Increment message index for next message
+		// Increment message index for next message. Credit is not part of the message sequence the
+		// envoy validates for hibernatable websockets.
 		let current_message_index = req.message_index;
-		req.message_index = req.message_index.wrapping_add(1);
+		if !matches!(
+			&message_kind,
+			protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(_)
+		) {
+			req.message_index = req.message_index.wrapping_add(1);
+		}
 
 		// Check if this is a WebSocket message for hibernation tracking
 		let is_ws_message = matches!(
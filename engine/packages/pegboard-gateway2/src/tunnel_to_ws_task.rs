@@ -4,6 +4,7 @@ use std::sync::{
 };
 
 use anyhow::Result;
+use futures_util::{FutureExt, future::BoxFuture};
 use gas::prelude::*;
 use rivet_envoy_protocol as protocol;
 use rivet_guard_core::{
@@ -16,7 +17,10 @@ use tokio::sync::{mpsc, watch};
 use tokio_tungstenite::tungstenite::Message;
 
 use super::LifecycleResult;
-use crate::shared_state::{InFlightRequestHandle, MsgGcReason, display_id};
+use crate::{
+	flow_control::{Admit, ClientBuffer, FlowControl, SLOW_CONSUMER_CLOSE_REASON},
+	shared_state::{InFlightRequestHandle, MsgGcReason, display_id},
+};
 
 #[tracing::instrument(name = "tunnel_to_ws_task", skip_all)]
 pub async fn task(
@@ -27,10 +31,54 @@ pub async fn task(
 	mut drop_rx: watch::Receiver<Option<MsgGcReason>>,
 	can_hibernate: bool,
 	egress_bytes: Arc<AtomicU64>,
+	flow_control: Arc<FlowControl>,
 	mut tunnel_to_ws_abort_rx: watch::Receiver<()>,
 ) -> Result<LifecycleResult> {
+	// Messages from the actor are buffered here so a slow client does not stall the tunnel
+	let mut buffer = ClientBuffer::new(
+		flow_control.policy,
+		flow_control.max_buffered_size,
+		flow_control.send_credit,
+	);
+	let mut write: Option<BoxFuture<'static, Result<usize>>> = None;
+
 	loop {
+		if write.is_none()
+			&& let Some(msg) = buffer.pop()
+		{
+			let client_ws = client_ws.clone();
+			write = Some(
+				async move {
+					let len = msg.len();
+					client_ws.send(msg).await?;
+					Ok(len)
+				}
+				.boxed(),
+			);
+		}
+
 		tokio::select! {
+			res = async { write.as_mut().expect("write must exist").await }, if write.is_some() => {
+				write = None;
+				let len = res?;
+				egress_bytes.fetch_add(len as u64, Ordering::AcqRel);
+				tracing::trace!(
+					request_id=%display_id(&in_flight_req.request_id),
+					data_len=len,
+					"sent websocket message to client"
+				);
+
+				if let Some(bytes) = buffer.written(len) {
+					in_flight_req
+						.send_message(
+							protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(
+								protocol::ToEnvoyWebSocketCredit { bytes },
+							),
+							false,
+						)
+						.await?;
+				}
+			}
 			res = msg_rx.recv() => {
 				if let Some(msg) = res {
 					match msg {
@@ -51,14 +99,34 @@ pub async fn task(
 								)
 							};
 
-							egress_bytes.fetch_add(msg.len() as u64, Ordering::AcqRel);
-							client_ws.send(msg).await?;
-							tracing::trace!(
-								request_id=%display_id(&in_flight_req.request_id),
-								data_len,
-								binary,
-								"sent websocket message to client"
-							);
+							match buffer.push(msg) {
+								Admit::Buffered => {}
+								Admit::Dropped => {
+									tracing::debug!(
+										request_id=%display_id(&in_flight_req.request_id),
+										data_len,
+										buffered_size=buffer.size(),
+										"client buffer full, dropping websocket message"
+									);
+									flow_control.record("drop");
+								}
+								Admit::Disconnect => {
+									tracing::debug!(
+										request_id=%display_id(&in_flight_req.request_id),
+										buffered_size=buffer.size(),
+										"client buffer full, disconnecting slow consumer"
+									);
+									flow_control.record("disconnect");
+									return Ok(LifecycleResult::ServerClose(protocol::ToRivetWebSocketClose {
+										code: Some(flow_control.close_code),
+										reason: Some(SLOW_CONSUMER_CLOSE_REASON.to_owned()),
+										hibernate: false,
+									}));
+								}
+							}
+						}
+						protocol::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(credit) => {
+							flow_control.release_in_flight(credit.bytes);
 						}
 						protocol::ToRivetTunnelMessageKind::ToRivetWebSocketMessageAck(ack) => {
 							tracing::debug!(
@@ -73,6 +141,16 @@ pub async fn task(
 						protocol::ToRivetTunnelMessageKind::ToRivetWebSocketClose(close) => {
 							tracing::debug!(?close, "server closed websocket");
 
+							// Deliver messages the actor sent before closing
+							if let Some(write) = write.take() {
+								let len = write.await?;
+								egress_bytes.fetch_add(len as u64, Ordering::AcqRel);
+							}
+							for msg in buffer.drain() {
+								egress_bytes.fetch_add(msg.len() as u64, Ordering::AcqRel);
+								client_ws.send(msg).await?;
+							}
+
 							if can_hibernate && close.hibernate {
 								return Err(WebSocketServiceHibernate.build());
 							} else {
@@ -12,7 +12,10 @@ use tokio::sync::{Mutex, watch};
 use tokio_tungstenite::tungstenite::Message;
 
 use super::LifecycleResult;
-use crate::shared_state::{InFlightRequestHandle, display_id};
+use crate::{
+	flow_control::{ByteRateLimiter, FlowControl},
+	shared_state::{InFlightRequestHandle, display_id},
+};
 
 #[tracing::instrument(name = "ws_to_tunnel_task", skip_all)]
 pub async fn task(
@@ -20,6 +23,7 @@ pub async fn task(
 	in_flight_req: InFlightRequestHandle,
 	ws_rx: Arc<Mutex<WebSocketReceiver>>,
 	ingress_bytes: Arc<AtomicU64>,
+	flow_control: Arc<FlowControl>,
 	mut ws_to_tunnel_abort_rx: watch::Receiver<()>,
 ) -> Result<LifecycleResult> {
 	let mut ws_rx = ws_rx.lock().await;
@@ -38,12 +42,32 @@ pub async fn task(
 			),
 		},
 	);
+	let mut byte_rate_limit = ByteRateLimiter::new(
+		ctx.config().pegboard().gateway_websocket_rate_limit_bytes(),
+		ctx.config()
+			.pegboard()
+			.gateway_websocket_rate_limit_bytes_per_sec(),
+	);
 
 	loop {
 		tokio::select! {
 			res = async {
 				rate_limit.acquire().await;
-				ws_rx.try_next().await
+				let msg = ws_rx.try_next().await?;
+
+				// Hold the message until it fits in the byte rate limit and the actor's in-flight
+				// window. The client is not read from in the meantime.
+				if let Some(msg) = &msg {
+					if byte_rate_limit.acquire(msg.len()).await {
+						flow_control.record("throttle");
+					}
+
+					if matches!(msg, Message::Binary(_) | Message::Text(_)) {
+						flow_control.acquire_in_flight(msg.len()).await;
+					}
+				}
+
+				anyhow::Ok(msg)
 			} => {
 				if let Some(msg) = res? {
 					ingress_bytes.fetch_add(msg.len() as u64, Ordering::AcqRel);
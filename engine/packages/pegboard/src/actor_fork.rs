@@ -0,0 +1,241 @@
+use anyhow::Result;
+use depot::{
+	conveyer::branch as depot_branch,
+	error::SqliteStorageError,
+	types::{BucketId, SnapshotSelector},
+};
+use futures_util::TryStreamExt;
+use gas::prelude::*;
+use universaldb::prelude::*;
+
+use crate::keys;
+
+/// Upper bound of KV bytes copied per transaction. Keeps each copy well under the UDB transaction
+/// size limit.
+const KV_COPY_BATCH_BYTES: usize = 1024 * 1024;
+/// How many times the KV copy starts over because the source wrote to its KV while it was copied.
+const KV_COPY_ATTEMPTS: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct ForkSource {
+	pub actor_id: Id,
+	/// Snapshot of the source SQLite database to fork.
+	pub selector: SnapshotSelector,
+}
+
+/// Seeds a new actor's storage from another actor in the same namespace.
+///
+/// The SQLite database becomes a copy-on-write branch of the source at the selected snapshot. KV
+/// has no history, so it is copied as it is now even when an earlier snapshot is selected; the copy
+/// is a consistent image of the source's KV at one point in time even while the source keeps
+/// writing. On failure, anything already created
+/// for the new actor is removed again.
+#[tracing::instrument(skip_all, fields(source_actor_id=%source.actor_id, %actor_id))]
+pub async fn fork_storage(
+	db: &universaldb::Database,
+	namespace_id: Id,
+	source: &ForkSource,
+	actor_id: Id,
+) -> Result<()> {
+	let res = fork_storage_inner(db, namespace_id, source, actor_id).await;
+	if res.is_err() {
+		if let Err(err) = discard_forked_storage(db, namespace_id, actor_id).await {
+			tracing::warn!(?err, "failed to discard storage of failed fork");
+		}
+	}
+
+	res
+}
+
+async fn fork_storage_inner(
+	db: &universaldb::Database,
+	namespace_id: Id,
+	source: &ForkSource,
+	actor_id: Id,
+) -> Result<()> {
+	let bucket = BucketId::from_gas_id(namespace_id);
+	if let Err(err) = depot_branch::fork_database_into(
+		db,
+		bucket,
+		source.actor_id.to_string(),
+		source.selector.clone(),
+		bucket,
+		actor_id.to_string(),
+	)
+	.await
+	{
+		let depot_err = err
+			.chain()
+			.find_map(|source| source.downcast_ref::<SqliteStorageError>());
+		match depot_err {
+			// The source never opened a v2 database. Any v1 SQLite data lives in KV and is copied
+			// below.
+			Some(SqliteStorageError::DatabaseNotFound)
+				if matches!(source.selector, SnapshotSelector::Latest) =>
+			{
+				tracing::debug!("fork source has no sqlite database, copying kv only");
+			}
+			Some(depot_err) => return Err(depot_err.clone().build()),
+			None => return Err(err),
+		}
+	}
+
+	for attempt in 0..KV_COPY_ATTEMPTS {
+		if attempt > 0 {
+			tracing::debug!(attempt, "fork source kv changed during copy, copying again");
+			clear_kv(db, actor_id).await?;
+		}
+
+		let generation = read_kv_generation(db, source.actor_id).await?;
+		copy_kv(db, source.actor_id, actor_id).await?;
+
+		// The batches above read at different versions. They add up to a consistent image only if no
+		// write to the source's KV landed since the generation was read. The check commits together
+		// with the fork source marker, so a write racing the check conflicts with it.
+		let source_actor_id = source.actor_id;
+		let consistent = db
+			.txn("pegboard_actor_fork_write_source", |tx| async move {
+				let tx = tx.with_subspace(keys::subspace());
+				let current = tx
+					.read_opt(
+						&keys::actor::KvGenerationKey::new(source_actor_id),
+						Serializable,
+					)
+					.await?
+					.unwrap_or_default();
+				if current != generation {
+					return Ok(false);
+				}
+
+				tx.write(&keys::actor::ForkSourceKey::new(actor_id), source_actor_id)?;
+
+				Ok(true)
+			})
+			.custom_instrument(tracing::info_span!("actor_fork_write_source_tx"))
+			.await?;
+		if consistent {
+			return Ok(());
+		}
+	}
+
+	Err(crate::errors::Actor::ForkSourceBusy.build())
+}
+
+/// Removes the storage `fork_storage` created for an actor whose creation did not go through.
+#[tracing::instrument(skip_all, fields(%actor_id))]
+pub async fn discard_forked_storage(
+	db: &universaldb::Database,
+	namespace_id: Id,
+	actor_id: Id,
+) -> Result<()> {
+	clear_kv(db, actor_id).await?;
+
+	let bucket = BucketId::from_gas_id(namespace_id);
+	let database_id = actor_id.to_string();
+	let database_branch_id = db
+		.txn("pegboard_actor_fork_resolve_database", |tx| {
+			let database_id = database_id.clone();
+			async move {
+				depot_branch::resolve_database_branch(&tx, bucket, &database_id, Serializable).await
+			}
+		})
+		.custom_instrument(tracing::info_span!("actor_fork_resolve_database_tx"))
+		.await?;
+	if let Some(database_branch_id) = database_branch_id {
+		depot_branch::delete_database(db, bucket, database_branch_id).await?;
+	}
+
+	Ok(())
+}
+
+/// Reads the actor an actor was forked from, if any.
+#[tracing::instrument(skip_all, fields(%actor_id))]
+pub async fn read_fork_source(db: &universaldb::Database, actor_id: Id) -> Result<Option<Id>> {
+	db.txn("pegboard_actor_fork_read_source", |tx| async move {
+		let tx = tx.with_subspace(keys::subspace());
+		tx.read_opt(&keys::actor::ForkSourceKey::new(actor_id), Snapshot)
+			.await
+	})
+	.custom_instrument(tracing::info_span!("actor_fork_read_source_tx"))
+	.await
+}
+
+async fn read_kv_generation(db: &universaldb::Database, actor_id: Id) -> Result<i64> {
+	db.txn("pegboard_actor_fork_read_kv_generation", |tx| async move {
+		let tx = tx.with_subspace(keys::subspace());
+		Ok(tx
+			.read_opt(&keys::actor::KvGenerationKey::new(actor_id), Serializable)
+			.await?
+			.unwrap_or_default())
+	})
+	.custom_instrument(tracing::info_span!("actor_fork_read_kv_generation_tx"))
+	.await
+}
+
+/// Clears the target's KV and fork source marker.
+async fn clear_kv(db: &universaldb::Database, actor_id: Id) -> Result<()> {
+	db.txn("pegboard_actor_fork_clear_kv", |tx| async move {
+		tx.clear_subspace_range(&keys::actor_kv::subspace(actor_id));
+		tx.with_subspace(keys::subspace())
+			.delete(&keys::actor::ForkSourceKey::new(actor_id));
+
+		Ok(())
+	})
+	.custom_instrument(tracing::info_span!("actor_fork_clear_kv_tx"))
+	.await
+}
+
+/// Copies the source actor's KV subspace into the target's in bounded batches.
+async fn copy_kv(db: &universaldb::Database, source_actor_id: Id, actor_id: Id) -> Result<()> {
+	let source_subspace = keys::actor_kv::subspace(source_actor_id);
+	let target_prefix = keys::actor_kv::subspace(actor_id).bytes().to_vec();
+	let source_prefix = source_subspace.bytes().to_vec();
+	let (mut cursor, end) = source_subspace.range();
+
+	loop {
+		let next_cursor = db
+			.txn("pegboard_actor_fork_copy_kv", |tx| {
+				let cursor = cursor.clone();
+				let end = end.clone();
+				let source_prefix = source_prefix.clone();
+				let target_prefix = target_prefix.clone();
+
+				async move {
+					let informal = tx.informal();
+					let mut stream = informal.get_ranges_keyvalues(
+						RangeOption {
+							mode: StreamingMode::Iterator,
+							..(cursor.as_slice(), end.as_slice()).into()
+						},
+						Snapshot,
+					);
+
+					let mut copied_bytes = 0;
+					while let Some(entry) = stream.try_next().await? {
+						let Some(suffix) = entry.key().strip_prefix(source_prefix.as_slice())
+						else {
+							continue;
+						};
+
+						let mut target_key = target_prefix.clone();
+						target_key.extend_from_slice(suffix);
+						tx.informal().set(&target_key, entry.value());
+
+						copied_bytes += entry.key().len() + entry.value().len();
+						if copied_bytes >= KV_COPY_BATCH_BYTES {
+							return Ok(Some(universaldb::utils::end_of_key_range(entry.key())));
+						}
+					}
+
+					Ok(None)
+				}
+			})
+			.custom_instrument(tracing::info_span!("actor_fork_copy_kv_tx"))
+			.await?;
+
+		match next_cursor {
+			Some(next_cursor) => cursor = next_cursor,
+			None => return Ok(()),
+		}
+	}
+}
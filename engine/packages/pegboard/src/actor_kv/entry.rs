@@ -8,6 +8,7 @@ pub struct EntryBuilder {
 	metadata: Option<ep::KvMetadata>,
 	value: Vec<u8>,
 	next_idx: usize,
+	sealed: bool,
 }
 
 impl EntryBuilder {
@@ -17,6 +18,7 @@ impl EntryBuilder {
 			metadata: None,
 			value: Vec::new(),
 			next_idx: 0,
+			sealed: false,
 		}
 	}
 
@@ -35,10 +37,25 @@ impl EntryBuilder {
 		}
 	}
 
+	pub fn mark_sealed(&mut self) {
+		self.sealed = true;
+	}
+
 	pub fn build(self) -> Result<(ep::KvKey, ep::KvValue, ep::KvMetadata)> {
+		// Only flagged values are decrypted, so plaintext values written before encryption was
+		// enabled are returned as stored whatever bytes they start with.
+		let value = if self.sealed {
+			depot::encryption::installed()
+				.context("actor kv value is encrypted but no encryption master key is configured")?
+				.open_sealed(&self.value)
+				.context("decrypt actor kv value")?
+		} else {
+			self.value
+		};
+
 		Ok((
 			self.key.0,
-			self.value,
+			value,
 			self.metadata.context("no metadata for key")?,
 		))
 	}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use depot::types::BucketId;
 use entry::EntryBuilder;
 use futures_util::{StreamExt, TryStreamExt};
 use gas::prelude::*;
@@ -12,6 +13,7 @@ use crate::keys;
 mod entry;
 mod metrics;
 pub mod preload;
+pub mod reencrypt;
 mod utils;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -33,6 +35,16 @@ pub struct Recipient {
 	pub name: String,
 }
 
+/// Marks the actor's KV as changed. Called in every transaction that writes to it, so readers that
+/// span several transactions (see `actor_fork`) can tell whether they saw a consistent image.
+fn bump_generation(tx: &universaldb::Transaction, actor_id: Id) {
+	tx.with_subspace(keys::subspace()).atomic_op(
+		&keys::actor::KvGenerationKey::new(actor_id),
+		&1i64.to_le_bytes(),
+		MutationType::Add,
+	);
+}
+
 /// Returns estimated size of the given actor kv subspace.
 #[tracing::instrument(skip_all)]
 pub async fn estimate_kv_size(tx: &universaldb::Transaction, actor_id: Id) -> Result<i64> {
@@ -147,6 +159,11 @@ pub async fn get(
 						let value = metadata_key.deserialize(entry.value())?;
 
 						current_entry.append_metadata(value);
+					} else if tx
+						.unpack::<keys::actor_kv::EntrySealedKey>(&entry.key())
+						.is_ok()
+					{
+						current_entry.mark_sealed();
 					} else {
 						bail!("unexpected sub key");
 					}
@@ -261,6 +278,11 @@ pub async fn list(
 					let value = metadata_key.deserialize(entry.value())?;
 
 					curr.append_metadata(value);
+				} else if tx
+					.unpack::<keys::actor_kv::EntrySealedKey>(&entry.key())
+					.is_ok()
+				{
+					curr.mark_sealed();
 				} else {
 					bail!("unexpected sub key");
 				}
@@ -307,14 +329,30 @@ pub async fn put(
 	metrics::ACTOR_KV_KEYS_PER_OP
 		.with_label_values(&["put"])
 		.observe(keys.len() as f64);
+	// Values are sealed once up front so transaction retries reuse the same ciphertext.
+	let (stored_values, sealed) = match depot::encryption::installed() {
+		Some(keyring) => {
+			let data_key = keyring
+				.current_data_key(db, BucketId::from_gas_id(recipient.namespace_id))
+				.await?;
+			let stored_values = values
+				.iter()
+				.map(|value| data_key.seal(value))
+				.collect::<Result<Vec<_>>>()?;
+			(stored_values, true)
+		}
+		None => (values.clone(), false),
+	};
 	let keys = &keys;
 	let values = &values;
+	let stored_values = &stored_values;
 	let result = db
 		.txn("pegboard_kv_put", |tx| {
 			async move {
 				let total_size = estimate_kv_size(&tx, recipient.actor_id).await? as usize;
 
 				validate_entries(&keys, &values, total_size)?;
+				bump_generation(&tx, recipient.actor_id);
 
 				let subspace = &keys::actor_kv::subspace(recipient.actor_id);
 				let tx = tx.with_subspace(subspace.clone());
@@ -342,7 +380,7 @@ pub async fn put(
 							let key = keys::actor_kv::KeyWrapper(
 								keys.get(i).context("index should exist")?.clone(),
 							);
-							let value = values.get(i).context("index should exist")?;
+							let value = stored_values.get(i).context("index should exist")?;
 							// Clear previous key data before setting
 							tx.clear_subspace_range(&subspace.subspace(&key));
 
@@ -355,6 +393,14 @@ pub async fn put(
 								},
 							)?;
 
+							if sealed {
+								tx.set(
+									&subspace
+										.pack(&keys::actor_kv::EntrySealedKey::new(key.clone())),
+									&[],
+								);
+							}
+
 							// Set key data in chunks
 							for start in (0..value.len()).step_by(VALUE_CHUNK_SIZE) {
 								let idx = start / VALUE_CHUNK_SIZE;
@@ -403,6 +449,8 @@ pub async fn delete(
 	let result = db
 		.txn("pegboard_kv_delete", |tx| {
 			async move {
+				bump_generation(&tx, recipient.actor_id);
+
 				// Total written bytes (rounded up to nearest chunk)
 				let total_size = keys.iter().fold(0, |s, key| s + key.len());
 				let total_size_chunked = (total_size as u64)
@@ -457,6 +505,8 @@ pub async fn delete_range(
 			let start = start.clone();
 			let end = end.clone();
 			async move {
+				bump_generation(&tx, recipient.actor_id);
+
 				// Total written bytes (rounded up to nearest chunk)
 				let total_size = start.len() + end.len();
 				let total_size_chunked = (total_size as u64)
@@ -497,6 +547,7 @@ pub async fn delete_range(
 pub async fn delete_all(db: &universaldb::Database, recipient: &Recipient) -> Result<()> {
 	db.txn("pegboard_kv_delete_all", |tx| async move {
 		tx.clear_subspace_range(&keys::actor_kv::subspace(recipient.actor_id));
+		bump_generation(&tx, recipient.actor_id);
 
 		// Total written bytes (rounded up to nearest chunk)
 		namespace::keys::metric::inc(
@@ -126,6 +126,11 @@ pub(crate) async fn batch_preload(
 					{
 						let metadata = metadata_key.deserialize(fdb_kv.value())?;
 						b.append_metadata(metadata);
+					} else if tx
+						.unpack::<keys::actor_kv::EntrySealedKey>(&fdb_kv.key())
+						.is_ok()
+					{
+						b.mark_sealed();
 					} else {
 						bail!("unexpected sub key in preload get");
 					}
@@ -232,6 +237,11 @@ pub(crate) async fn batch_preload(
 					{
 						let metadata = metadata_key.deserialize(fdb_kv.value())?;
 						curr.append_metadata(metadata);
+					} else if tx
+						.unpack::<keys::actor_kv::EntrySealedKey>(&fdb_kv.key())
+						.is_ok()
+					{
+						curr.mark_sealed();
 					} else {
 						bail!("unexpected sub key in preload prefix scan");
 					}
@@ -0,0 +1,126 @@
+//! Re-seals actor KV values sealed under a retired data key so retired data keys and master keys
+//! stop being needed. Only values flagged with `EntrySealedKey` are touched.
+
+use anyhow::Result;
+use depot::encryption::{Keyring, rotate, sealed_key_id};
+use futures_util::TryStreamExt;
+use gas::prelude::*;
+use universaldb::prelude::*;
+
+use crate::keys;
+
+/// Rows scanned per page. Every entry has at least a metadata row and a chunk row.
+const SCAN_BATCH_ROWS: usize = 1024;
+
+#[derive(Debug, Default)]
+pub struct ReencryptPage {
+	pub values_reencrypted: usize,
+	pub bytes_reencrypted: u64,
+	/// `None` once the KV of every actor was scanned.
+	pub next_cursor: Option<Vec<u8>>,
+}
+
+/// Re-seals the sealed values in one page of the KV of all actors, starting at `cursor` or at the
+/// first actor when `None`.
+#[tracing::instrument(skip_all)]
+pub async fn reencrypt_page(
+	db: &universaldb::Database,
+	keyring: &Keyring,
+	cursor: Option<Vec<u8>>,
+) -> Result<ReencryptPage> {
+	let entire_subspace = keys::actor_kv::entire_subspace();
+	let (begin, end) = entire_subspace.range();
+	let cursor = cursor.unwrap_or(begin);
+
+	let (sealed_entries, row_count, last_key) = db
+		.txn("pegboard_kv_reencrypt_scan", |tx| {
+			let entire_subspace = entire_subspace.clone();
+			let cursor = cursor.clone();
+			let end = end.clone();
+			async move {
+				let mut stream = tx.get_ranges_keyvalues(
+					universaldb::RangeOption {
+						mode: universaldb::options::StreamingMode::WantAll,
+						limit: Some(SCAN_BATCH_ROWS),
+						..(cursor.as_slice(), end.as_slice()).into()
+					},
+					Snapshot,
+				);
+
+				let mut sealed_entries = Vec::new();
+				let mut row_count = 0;
+				let mut last_key = None;
+				while let Some(entry) = stream.try_next().await? {
+					row_count += 1;
+					// Only metadata and sealed flag rows have three elements
+					if let Ok((actor_id, key, SEALED)) =
+						entire_subspace
+							.unpack::<(Id, keys::actor_kv::KeyWrapper, usize)>(entry.key())
+					{
+						sealed_entries.push((actor_id, key));
+					}
+					last_key = Some(entry.key().to_vec());
+				}
+
+				Ok((sealed_entries, row_count, last_key))
+			}
+		})
+		.custom_instrument(tracing::info_span!("kv_reencrypt_scan_tx"))
+		.await?;
+
+	let mut page = ReencryptPage {
+		next_cursor: last_key
+			.filter(|_| row_count >= SCAN_BATCH_ROWS)
+			.map(|last_key| universaldb::utils::end_of_key_range(&last_key)),
+		..Default::default()
+	};
+
+	for (actor_id, key) in sealed_entries {
+		let (begin, end) = keys::actor_kv::subspace(actor_id)
+			.subspace(&(key, DATA))
+			.range();
+
+		// The data key is looked up from the header of the first chunk, which holds the whole
+		// header since chunks are larger than it
+		let first_chunk = db
+			.txn("pegboard_kv_reencrypt_header", |tx| {
+				let begin = begin.clone();
+				let end = end.clone();
+				async move {
+					let mut stream = tx.get_ranges_keyvalues(
+						universaldb::RangeOption {
+							mode: universaldb::options::StreamingMode::Small,
+							limit: Some(1),
+							..(begin.as_slice(), end.as_slice()).into()
+						},
+						Snapshot,
+					);
+
+					Ok(stream.try_next().await?.map(|entry| entry.value().to_vec()))
+				}
+			})
+			.await?;
+		let Some(key_id) = first_chunk
+			.as_deref()
+			.map(sealed_key_id)
+			.transpose()?
+			.flatten()
+		else {
+			continue;
+		};
+
+		let data_key = keyring.current_data_key(db, key_id.bucket_id).await?;
+		if key_id == data_key.id() {
+			continue;
+		}
+
+		if let Some(bytes) =
+			rotate::reencrypt_chunked_value(db, keyring, &data_key, &begin, &end).await?
+		{
+			page.values_reencrypted += 1;
+			page.bytes_reencrypted += bytes;
+		}
+	}
+
+	Ok(page)
+}
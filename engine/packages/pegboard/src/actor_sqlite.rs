@@ -65,6 +65,7 @@ pub struct MigrateV1ToV2Output {
 }
 
 pub async fn migrate_v1_to_v2(
+	config: &rivet_config::Config,
 	db: universaldb::Database,
 	input: MigrateV1ToV2Input,
 ) -> Result<MigrateV1ToV2Output> {
@@ -74,12 +75,16 @@ pub async fn migrate_v1_to_v2(
 		name: input.name,
 	};
 
-	let migrated = maybe_migrate_v1_to_v2(&db, &recipient).await?;
+	let migrated = maybe_migrate_v1_to_v2(config, &db, &recipient).await?;
 
 	Ok(MigrateV1ToV2Output { migrated })
 }
 
-async fn maybe_migrate_v1_to_v2(db: &universaldb::Database, recipient: &Recipient) -> Result<bool> {
+async fn maybe_migrate_v1_to_v2(
+	config: &rivet_config::Config,
+	db: &universaldb::Database,
+	recipient: &Recipient,
+) -> Result<bool> {
 	if !crate::actor_kv::sqlite_v1_data_exists(db, recipient.actor_id).await? {
 		return Ok(false);
 	}
@@ -125,12 +130,15 @@ async fn maybe_migrate_v1_to_v2(db: &universaldb::Database, recipient: &Recipien
 			bytes: bytes.to_vec(),
 		})
 		.collect::<Vec<_>>();
-	let actor_db = Db::new(
+	let actor_db = Db::from_config(
+		config,
 		Arc::new(db.clone()),
 		recipient.namespace_id,
 		actor_id.clone(),
 		NodeId::new(),
-	);
+	)
+	.await
+	.map_err(|err| migration_error(&actor_id, "open", err))?;
 	actor_db
 		.commit(dirty_pages, recovered.total_pages, timestamp::now())
 		.await
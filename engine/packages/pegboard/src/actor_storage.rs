@@ -0,0 +1,114 @@
+use anyhow::Result;
+use depot::types::BucketId;
+use gas::prelude::*;
+use namespace::keys::usage::StorageKind;
+use strum::IntoEnumIterator;
+use universaldb::prelude::*;
+
+use crate::keys;
+
+/// Measures everything an actor stores (besides KV, which is scanned separately by the caller) and
+/// records it in the per-actor and per-namespace storage usage rollups.
+#[tracing::instrument(skip_all, fields(%namespace_id, %actor_id))]
+pub async fn record_usage(
+	ctx: &ActivityCtx,
+	namespace_id: Id,
+	actor_id: Id,
+	name: &str,
+	kv_bytes: i64,
+) -> Result<()> {
+	let workflow_id = ctx
+		.udb()?
+		.txn("pegboard_actor_storage_read_workflow_id", |tx| async move {
+			let tx = tx.with_subspace(keys::subspace());
+
+			tx.read_opt(&keys::actor::WorkflowIdKey::new(actor_id), Snapshot)
+				.await
+		})
+		.custom_instrument(tracing::info_span!("actor_storage_read_workflow_id_tx"))
+		.await?;
+
+	let workflow_size = if let Some(workflow_id) = workflow_id {
+		ctx.get_workflow_storage_size(workflow_id).await?
+	} else {
+		Default::default()
+	};
+
+	ctx.udb()?
+		.txn("pegboard_actor_record_storage_usage", |tx| async move {
+			tx.priority(Priority::Low)?;
+
+			// Actor databases live in their namespace's bucket
+			let sqlite_bytes = depot::conveyer::quota::read_in_bucket(
+				&tx,
+				BucketId::from_gas_id(namespace_id),
+				&actor_id.to_string(),
+			)
+			.await?;
+
+			let ns_tx = tx.with_subspace(namespace::keys::subspace());
+			for (kind, bytes) in [
+				(StorageKind::ActorKv, kv_bytes),
+				(StorageKind::Sqlite, sqlite_bytes),
+				(StorageKind::WorkflowHistory, workflow_size.history_bytes),
+				(StorageKind::WorkflowQueue, workflow_size.queue_bytes),
+			] {
+				let delta = namespace::keys::usage::record_actor(
+					&ns_tx,
+					namespace_id,
+					actor_id,
+					kind,
+					bytes,
+				)
+				.await?;
+
+				if kind == StorageKind::Sqlite && delta != 0 {
+					namespace::keys::metric::inc(
+						&ns_tx,
+						namespace_id,
+						namespace::keys::metric::Metric::SqliteStorageUsed(name.to_string()),
+						delta,
+					);
+				}
+			}
+
+			Ok(())
+		})
+		.custom_instrument(tracing::info_span!("actor_record_storage_usage_tx"))
+		.await
+}
+
+/// Removes an actor's contribution from the storage usage rollups. Called once its storage has been
+/// deleted on destroy.
+#[tracing::instrument(skip_all, fields(%namespace_id, %actor_id))]
+pub async fn clear_usage(
+	ctx: &ActivityCtx,
+	namespace_id: Id,
+	actor_id: Id,
+	name: &str,
+) -> Result<()> {
+	ctx.udb()?
+		.txn("pegboard_actor_clear_storage_usage", |tx| async move {
+			tx.priority(Priority::Low)?;
+
+			let ns_tx = tx.with_subspace(namespace::keys::subspace());
+			for kind in StorageKind::iter() {
+				let delta =
+					namespace::keys::usage::record_actor(&ns_tx, namespace_id, actor_id, kind, 0)
+						.await?;
+
+				if kind == StorageKind::Sqlite && delta != 0 {
+					namespace::keys::metric::inc(
+						&ns_tx,
+						namespace_id,
+						namespace::keys::metric::Metric::SqliteStorageUsed(name.to_string()),
+						delta,
+					);
+				}
+			}
+
+			Ok(())
+		})
+		.custom_instrument(tracing::info_span!("actor_clear_storage_usage_tx"))
+		.await
+}
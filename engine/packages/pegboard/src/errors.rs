@@ -84,6 +84,25 @@ pub enum Actor {
 		remaining: usize,
 		payload_size: usize,
 	},
+
+	#[error(
+		"fork_source_not_found",
+		"The actor to fork from does not exist in this namespace."
+	)]
+	ForkSourceNotFound,
+
+	#[error(
+		"fork_source_in_different_datacenter",
+		"The actor to fork from lives in a different datacenter. Create the fork in the source actor's datacenter.",
+		"The actor to fork from lives in the datacenter '{datacenter_label}'. Create the fork in the source actor's datacenter."
+	)]
+	ForkSourceInDifferentDatacenter { datacenter_label: u16 },
+
+	#[error(
+		"fork_source_busy",
+		"The actor to fork from kept writing to its KV storage while it was being copied. Retry the fork, or fork while the source actor is idle."
+	)]
+	ForkSourceBusy,
 }
 
 #[derive(RivetError, Debug, Clone, Deserialize, Serialize)]
@@ -768,3 +768,106 @@ impl<'de> TupleUnpack<'de> for GenerationKey {
 		Ok((input, v))
 	}
 }
+
+/// Actor this actor's storage was forked from.
+#[derive(Debug)]
+pub struct ForkSourceKey {
+	actor_id: Id,
+}
+
+impl ForkSourceKey {
+	pub fn new(actor_id: Id) -> Self {
+		ForkSourceKey { actor_id }
+	}
+}
+
+impl FormalKey for ForkSourceKey {
+	type Value = Id;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(Id::from_slice(raw)?)
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.as_bytes().to_vec())
+	}
+}
+
+impl TuplePack for ForkSourceKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACTOR, DATA, self.actor_id, FORK_SOURCE);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for ForkSourceKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, _, actor_id, key_type)) =
+			<(usize, usize, Id, usize)>::unpack(input, tuple_depth)?;
+
+		if key_type != FORK_SOURCE {
+			return Err(PackError::Message("expected FORK_SOURCE key type".into()));
+		}
+
+		let v = ForkSourceKey { actor_id };
+
+		Ok((input, v))
+	}
+}
+
+/// Counter bumped by every write to the actor's KV. Lets a reader that copies the KV across several
+/// transactions detect that it changed underneath it.
+#[derive(Debug)]
+pub struct KvGenerationKey {
+	actor_id: Id,
+}
+
+impl KvGenerationKey {
+	pub fn new(actor_id: Id) -> Self {
+		KvGenerationKey { actor_id }
+	}
+}
+
+impl FormalKey for KvGenerationKey {
+	type Value = i64;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		// NOTE: Atomic ops use little endian
+		Ok(i64::from_le_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		// NOTE: Atomic ops use little endian
+		Ok(value.to_le_bytes().to_vec())
+	}
+}
+
+impl TuplePack for KvGenerationKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (ACTOR, DATA, self.actor_id, KV, GENERATION);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for KvGenerationKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, _, actor_id, kv, key_type)) =
+			<(usize, usize, Id, usize, usize)>::unpack(input, tuple_depth)?;
+
+		if kv != KV || key_type != GENERATION {
+			return Err(PackError::Message("expected KV GENERATION key type".into()));
+		}
+
+		let v = KvGenerationKey { actor_id };
+
+		Ok((input, v))
+	}
+}
@@ -7,6 +7,11 @@ pub fn subspace(actor_id: Id) -> universaldb::utils::Subspace {
 	universaldb::utils::Subspace::new(&(RIVET, PEGBOARD, ACTOR_KV, actor_id))
 }
 
+/// KV of every actor.
+pub fn entire_subspace() -> universaldb::utils::Subspace {
+	universaldb::utils::Subspace::new(&(RIVET, PEGBOARD, ACTOR_KV))
+}
+
 /// Wraps a key with a trailing NIL byte for exact key matching.
 ///
 /// Encodes as: `[NESTED, ...bytes..., NIL]`
@@ -181,3 +186,40 @@ impl<'de> TupleUnpack<'de> for EntryMetadataKey {
 		Ok((input, v))
 	}
 }
+
+/// Present when the entry's value is sealed with the namespace's data key. Values written before
+/// encryption was enabled have no flag and are stored in plaintext.
+#[derive(Debug)]
+pub struct EntrySealedKey {
+	pub key: KeyWrapper,
+}
+
+impl EntrySealedKey {
+	pub fn new(key: KeyWrapper) -> Self {
+		EntrySealedKey { key }
+	}
+}
+
+impl TuplePack for EntrySealedKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (&self.key, SEALED);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for EntrySealedKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (key, data)) = <(KeyWrapper, usize)>::unpack(input, tuple_depth)?;
+		if data != SEALED {
+			return Err(PackError::Message("expected SEALED data".into()));
+		}
+
+		let v = EntrySealedKey { key };
+
+		Ok((input, v))
+	}
+}
@@ -66,3 +66,123 @@ impl<'de> TupleUnpack<'de> for ReservationByKeyKey {
 		Ok((input, v))
 	}
 }
+
+/// Datacenters that hold a read replica of an actor's SQLite database.
+#[derive(Debug)]
+pub struct SqliteReplicasKey {
+	namespace_id: Id,
+	actor_id: Id,
+}
+
+impl SqliteReplicasKey {
+	pub fn new(namespace_id: Id, actor_id: Id) -> Self {
+		SqliteReplicasKey {
+			namespace_id,
+			actor_id,
+		}
+	}
+}
+
+impl FormalKey for SqliteReplicasKey {
+	// Replica datacenter labels
+	type Value = Vec<u16>;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		ensure!(raw.len() % 2 == 0, "invalid sqlite replicas value length");
+
+		Ok(raw
+			.chunks_exact(2)
+			.map(|label| u16::from_be_bytes([label[0], label[1]]))
+			.collect())
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value
+			.into_iter()
+			.flat_map(|label| label.to_be_bytes())
+			.collect())
+	}
+}
+
+impl TuplePack for SqliteReplicasKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (
+			EPOXY_V1,
+			NAMESPACE,
+			self.namespace_id,
+			SQLITE_REPLICAS,
+			self.actor_id,
+		);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for SqliteReplicasKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, _, namespace_id, _, actor_id)) =
+			<(usize, usize, Id, usize, Id)>::unpack(input, tuple_depth)?;
+		let v = SqliteReplicasKey {
+			namespace_id,
+			actor_id,
+		};
+
+		Ok((input, v))
+	}
+}
+
+/// Datacenter chosen for the first client that created an actor with this name.
+#[derive(Debug)]
+pub struct FirstClientRegionKey {
+	namespace_id: Id,
+	name: String,
+}
+
+impl FirstClientRegionKey {
+	pub fn new(namespace_id: Id, name: String) -> Self {
+		FirstClientRegionKey { namespace_id, name }
+	}
+}
+
+impl FormalKey for FirstClientRegionKey {
+	// Datacenter label
+	type Value = u16;
+
+	fn deserialize(&self, raw: &[u8]) -> Result<Self::Value> {
+		Ok(u16::from_be_bytes(raw.try_into()?))
+	}
+
+	fn serialize(&self, value: Self::Value) -> Result<Vec<u8>> {
+		Ok(value.to_be_bytes().to_vec())
+	}
+}
+
+impl TuplePack for FirstClientRegionKey {
+	fn pack<W: std::io::Write>(
+		&self,
+		w: &mut W,
+		tuple_depth: TupleDepth,
+	) -> std::io::Result<VersionstampOffset> {
+		let t = (
+			EPOXY_V1,
+			NAMESPACE,
+			self.namespace_id,
+			FIRST_CLIENT_REGION,
+			&self.name,
+		);
+		t.pack(w, tuple_depth)
+	}
+}
+
+impl<'de> TupleUnpack<'de> for FirstClientRegionKey {
+	fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+		let (input, (_, _, namespace_id, _, name)) =
+			<(usize, usize, Id, usize, String)>::unpack(input, tuple_depth)?;
+		let v = FirstClientRegionKey { namespace_id, name };
+
+		Ok((input, v))
+	}
+}
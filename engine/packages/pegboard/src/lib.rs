@@ -1,7 +1,9 @@
 use gas::prelude::*;
 
+pub mod actor_fork;
 pub mod actor_kv;
 pub mod actor_sqlite;
+pub mod actor_storage;
 pub mod envoy_expire_scheduler;
 pub mod errors;
 pub mod keys;
@@ -32,6 +34,8 @@ pub fn registry() -> WorkflowResult<Registry> {
 	registry.register_workflow::<serverless::backfill::Workflow>()?;
 	registry.register_workflow::<metrics_aggregator::Workflow>()?;
 	registry.register_workflow::<actor_runner_name_selector_backfill::Workflow>()?;
+	registry.register_workflow::<sqlite_replicator::Workflow>()?;
+	registry.register_workflow::<actor_kv_key_rotator::Workflow>()?;
 
 	Ok(registry)
 }
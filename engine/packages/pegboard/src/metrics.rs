@@ -38,6 +38,20 @@ lazy_static::lazy_static! {
 		*REGISTRY
 	).unwrap();
 
+	pub static ref ACTOR_CREATE_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"pegboard_actor_create_total",
+		"Count of actor creations by the pool chosen for the actor.",
+		&["namespace_id", "pool_name", "variant", "result"],
+		*REGISTRY
+	).unwrap();
+
+	pub static ref ACTOR_ERROR_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
+		"pegboard_actor_error_total",
+		"Count of actors that failed to allocate or stopped with an error.",
+		&["namespace_id", "pool_name", "error"],
+		*REGISTRY
+	).unwrap();
+
 	pub static ref RUNNER_VERSION_UPGRADE_DRAIN_TOTAL: IntCounterVec = register_int_counter_vec_with_registry!(
 		"pegboard_runner_version_upgrade_drain_total",
 		"Count of runners drained due to version upgrade.",
@@ -205,4 +219,11 @@ lazy_static::lazy_static! {
 		&["namespace_id", "pool_name", "strategy"],
 		*REGISTRY
 	).unwrap();
+
+	pub static ref NAMESPACE_STORAGE_BYTES: IntGaugeVec = register_int_gauge_vec_with_registry!(
+		"pegboard_namespace_storage_bytes",
+		"Total bytes stored by a namespace's actors in this datacenter.",
+		&["namespace_id", "kind"],
+		*REGISTRY
+	).unwrap();
 }
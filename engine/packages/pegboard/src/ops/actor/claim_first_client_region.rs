@@ -0,0 +1,87 @@
+use epoxy::ops::propose::{
+	CheckAndSetCommand, Command, CommandKind, ConsensusFailedReason, Proposal, ProposalResult,
+};
+use gas::prelude::*;
+use universaldb::prelude::*;
+
+use crate::keys;
+
+#[derive(Debug)]
+pub struct Input {
+	pub namespace_id: Id,
+	pub name: String,
+	/// Datacenter closest to the current client. Only stored if no client claimed a region for
+	/// this actor name yet. `None` if the client could not be located, in which case nothing is
+	/// claimed so an unlocated client never decides the region for everyone after it.
+	pub dc_label: Option<u16>,
+}
+
+#[derive(Debug)]
+pub struct Output {
+	/// Datacenter chosen for the first client. `None` if no located client created an actor with
+	/// this name yet.
+	pub dc_label: Option<u16>,
+}
+
+/// Returns the region of the first client that created an actor with this name, claiming
+/// `dc_label` if there is none yet. Claims are global and never change.
+#[operation]
+pub async fn pegboard_actor_claim_first_client_region(
+	ctx: &OperationCtx,
+	input: &Input,
+) -> Result<Output> {
+	let region_key =
+		keys::epoxy::ns::FirstClientRegionKey::new(input.namespace_id, input.name.clone());
+	let value = ctx
+		.op(epoxy::ops::kv::get_optimistic::Input {
+			replica_id: ctx.config().epoxy_replica_id(),
+			key: keys::subspace().pack(&region_key),
+			caching_behavior: epoxy_protocol::protocol::CachingBehavior::Optimistic,
+			target_replicas: None,
+			save_empty: false,
+		})
+		.await?
+		.value;
+	if let Some(value) = value {
+		return Ok(Output {
+			dc_label: Some(region_key.deserialize(&value)?),
+		});
+	}
+
+	let Some(dc_label) = input.dc_label else {
+		return Ok(Output { dc_label: None });
+	};
+
+	let proposal_result = ctx
+		.op(epoxy::ops::propose::Input {
+			proposal: Proposal {
+				commands: vec![Command {
+					kind: CommandKind::CheckAndSetCommand(CheckAndSetCommand {
+						key: keys::subspace().pack(&region_key),
+						expect_one_of: vec![None],
+						new_value: Some(region_key.serialize(dc_label)?),
+					}),
+				}],
+			},
+			mutable: false,
+			purge_cache: false,
+			target_replicas: None,
+		})
+		.await?;
+
+	match proposal_result {
+		ProposalResult::Committed => Ok(Output {
+			dc_label: Some(dc_label),
+		}),
+		// Another client claimed a region first
+		ProposalResult::ConsensusFailed {
+			reason:
+				ConsensusFailedReason::ExpectedValueDoesNotMatch {
+					current_value: Some(current_value),
+				},
+		} => Ok(Output {
+			dc_label: Some(region_key.deserialize(&current_value)?),
+		}),
+		res => bail!("consensus failed: {res:?}"),
+	}
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use gas::prelude::*;
 use moka::future::Cache;
+use rand::Rng;
 use rivet_api_util::{Method, request_remote_datacenter};
 use rivet_types::actors::{Actor, CrashPolicy};
 use std::sync::{Arc, OnceLock};
@@ -28,6 +29,8 @@ pub struct Input {
 	/// Providing this value will cause an error if attempting to create an actor where the key is
 	/// reserved in a different datacenter.
 	pub datacenter_name: Option<String>,
+	/// Seeds the actor's storage from another actor in this datacenter before it starts.
+	pub fork_from: Option<crate::actor_fork::ForkSource>,
 }
 
 #[derive(Debug)]
@@ -63,6 +66,58 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 		return Err(crate::errors::Actor::CreationRateLimit.build());
 	}
 
+	let (pool_name, variant) = select_pool(ctx, input).await?;
+
+	let mut forwarded = false;
+	let res = create_inner(ctx, input, &pool_name, &mut forwarded).await;
+
+	// Forwarded creates are counted by the datacenter that handles them
+	if !forwarded {
+		crate::metrics::ACTOR_CREATE_TOTAL
+			.with_label_values(&[
+				input.namespace_id.to_string().as_str(),
+				pool_name.as_str(),
+				variant,
+				if res.is_ok() { "ok" } else { "error" },
+			])
+			.inc();
+	}
+
+	res
+}
+
+/// Picks the runner pool for a new actor. The namespace's canary policy can send a share of the
+/// actors created for a pool to a canary pool instead.
+async fn select_pool(ctx: &OperationCtx, input: &Input) -> Result<(String, &'static str)> {
+	let policy = ctx
+		.op(namespace::ops::get_policies_local::Input {
+			namespace_id: input.namespace_id,
+		})
+		.await?
+		.canary;
+
+	let roll = rand::thread_rng().gen_range(0..100);
+	if let Some(canary_pool_name) =
+		crate::utils::canary_pool(&policy, &input.runner_name_selector, &input.name, roll)
+	{
+		tracing::debug!(
+			actor_id=?input.actor_id,
+			pool_name=%canary_pool_name,
+			"routing actor to canary pool"
+		);
+
+		return Ok((canary_pool_name.to_string(), "canary"));
+	}
+
+	Ok((input.runner_name_selector.clone(), "primary"))
+}
+
+async fn create_inner(
+	ctx: &OperationCtx,
+	input: &Input,
+	pool_name: &str,
+	forwarded: &mut bool,
+) -> Result<Output> {
 	// Set up subscriptions before dispatching workflow
 	let (
 		mut create_sub,
@@ -80,7 +135,7 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 		ctx.subscribe::<crate::workflows::actor2::Failed>(("actor_id", input.actor_id)),
 		ctx.subscribe::<crate::workflows::actor2::DestroyStarted>(("actor_id", input.actor_id)),
 		ctx.op(crate::ops::runner_config::get::Input {
-			runners: vec![(input.namespace_id, input.runner_name_selector.clone())],
+			runners: vec![(input.namespace_id, pool_name.to_string())],
 			bypass_cache: false,
 		}),
 	)?;
@@ -90,20 +145,27 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 		.next()
 		.map(|p| p.protocol_version.is_some())
 		.unwrap_or_default();
+
+	if let Some(fork_from) = &input.fork_from {
+		fork_storage(ctx, input, fork_from).await?;
+	}
+
 	if actor_v2 {
 		// Dispatch actor workflow
-		ctx.workflow(crate::workflows::actor2::Input {
-			actor_id: input.actor_id,
-			name: input.name.clone(),
-			pool_name: input.runner_name_selector.clone(),
-			key: input.key.clone(),
-			namespace_id: input.namespace_id,
-			input: input.input.clone(),
-			from_v1: false,
-		})
-		.tag("actor_id", input.actor_id)
-		.dispatch()
-		.await?;
+		let res = ctx
+			.workflow(crate::workflows::actor2::Input {
+				actor_id: input.actor_id,
+				name: input.name.clone(),
+				pool_name: pool_name.to_string(),
+				key: input.key.clone(),
+				namespace_id: input.namespace_id,
+				input: input.input.clone(),
+				from_v1: false,
+			})
+			.tag("actor_id", input.actor_id)
+			.dispatch()
+			.await;
+		discard_fork_on_err(ctx, input, res).await?;
 
 		// Wait for actor creation to complete, fail, or be destroyed
 		tokio::select! {
@@ -115,10 +177,15 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 				// Check if this request needs to be forwarded
 				//
 				// We cannot forward if `datacenter_name` is specified because this actor is being
-				// restricted to the given datacenter.
-				if input.forward_request && input.datacenter_name.is_none() {
+				// restricted to the given datacenter. Forks are restricted to the source actor's
+				// datacenter.
+				if input.forward_request
+					&& input.datacenter_name.is_none()
+					&& input.fork_from.is_none()
+				{
 					if let crate::errors::Actor::KeyReservedInDifferentDatacenter { datacenter_label } = &error {
 						// Forward the request to the correct datacenter
+						*forwarded = true;
 						return forward_to_datacenter(
 							ctx,
 							*datacenter_label,
@@ -142,18 +209,20 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 		}
 	} else {
 		// Dispatch actor workflow
-		ctx.workflow(crate::workflows::actor::Input {
-			actor_id: input.actor_id,
-			name: input.name.clone(),
-			runner_name_selector: input.runner_name_selector.clone(),
-			key: input.key.clone(),
-			namespace_id: input.namespace_id,
-			crash_policy: input.crash_policy,
-			input: input.input.clone(),
-		})
-		.tag("actor_id", input.actor_id)
-		.dispatch()
-		.await?;
+		let res = ctx
+			.workflow(crate::workflows::actor::Input {
+				actor_id: input.actor_id,
+				name: input.name.clone(),
+				runner_name_selector: pool_name.to_string(),
+				key: input.key.clone(),
+				namespace_id: input.namespace_id,
+				crash_policy: input.crash_policy,
+				input: input.input.clone(),
+			})
+			.tag("actor_id", input.actor_id)
+			.dispatch()
+			.await;
+		discard_fork_on_err(ctx, input, res).await?;
 
 		// Wait for actor creation to complete, fail, or be destroyed
 		tokio::select! {
@@ -165,10 +234,15 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 				// Check if this request needs to be forwarded
 				//
 				// We cannot forward if `datacenter_name` is specified because this actor is being
-				// restricted to the given datacenter.
-				if input.forward_request && input.datacenter_name.is_none() {
+				// restricted to the given datacenter. Forks are restricted to the source actor's
+				// datacenter.
+				if input.forward_request
+					&& input.datacenter_name.is_none()
+					&& input.fork_from.is_none()
+				{
 					if let crate::errors::Actor::KeyReservedInDifferentDatacenter { datacenter_label } = &error {
 						// Forward the request to the correct datacenter
+						*forwarded = true;
 						return forward_to_datacenter(
 							ctx,
 							*datacenter_label,
@@ -209,6 +283,62 @@ pub async fn pegboard_actor_create(ctx: &OperationCtx, input: &Input) -> Result<
 	Ok(Output { actor })
 }
 
+/// Validates the fork source and copies its storage to the new actor.
+async fn fork_storage(
+	ctx: &OperationCtx,
+	input: &Input,
+	fork_from: &crate::actor_fork::ForkSource,
+) -> Result<()> {
+	let source_dc_label = fork_from.actor_id.label();
+	if source_dc_label != ctx.config().dc_label() {
+		return Err(crate::errors::Actor::ForkSourceInDifferentDatacenter {
+			datacenter_label: source_dc_label,
+		}
+		.build());
+	}
+
+	let source_actor = ctx
+		.op(crate::ops::actor::get::Input {
+			actor_ids: vec![fork_from.actor_id],
+			fetch_error: false,
+		})
+		.await?
+		.actors
+		.into_iter()
+		.next()
+		.filter(|actor| actor.namespace_id == input.namespace_id)
+		.ok_or_else(|| crate::errors::Actor::ForkSourceNotFound.build())?;
+
+	crate::actor_fork::fork_storage(&*ctx.udb()?, input.namespace_id, fork_from, input.actor_id)
+		.await?;
+
+	tracing::debug!(
+		source_actor_id=?source_actor.actor_id,
+		actor_id=?input.actor_id,
+		"forked actor storage"
+	);
+
+	Ok(())
+}
+
+/// Once dispatched, the actor workflow owns a forked actor's storage and clears it on destroy. Until
+/// then nothing would, so a failed dispatch removes it here.
+async fn discard_fork_on_err<T>(ctx: &OperationCtx, input: &Input, res: Result<T>) -> Result<T> {
+	if res.is_err() && input.fork_from.is_some() {
+		if let Err(err) = crate::actor_fork::discard_forked_storage(
+			&*ctx.udb()?,
+			input.namespace_id,
+			input.actor_id,
+		)
+		.await
+		{
+			tracing::warn!(?err, actor_id=?input.actor_id, "failed to discard forked storage");
+		}
+	}
+
+	res
+}
+
 /// Forward the actor creation request to the correct datacenter
 async fn forward_to_datacenter(
 	ctx: &OperationCtx,
@@ -252,6 +382,7 @@ async fn forward_to_datacenter(
 			input,
 			runner_name_selector,
 			crash_policy,
+			fork_from: None,
 		}),
 	)
 	.await?;
@@ -12,6 +12,7 @@ pub struct Input {
 pub struct Output {
 	pub namespace_id: Id,
 	pub workflow_id: Id,
+	pub name: Option<String>,
 	pub key: Option<String>,
 	// NOTE: None if older actor has not received the new key
 	pub runner_name_selector: Option<String>,
@@ -35,6 +36,7 @@ pub async fn pegboard_actor_get_for_gateway(
 
 			let namespace_id_key = keys::actor::NamespaceIdKey::new(input.actor_id);
 			let workflow_id_key = keys::actor::WorkflowIdKey::new(input.actor_id);
+			let name_key = keys::actor::NameKey::new(input.actor_id);
 			let key_key = keys::actor::KeyKey::new(input.actor_id);
 			let runner_name_selector_key = keys::actor::RunnerNameSelectorKey::new(input.actor_id);
 			let sleep_ts_key = keys::actor::SleepTsKey::new(input.actor_id);
@@ -47,6 +49,7 @@ pub async fn pegboard_actor_get_for_gateway(
 			let (
 				namespace_id_entry,
 				workflow_id_entry,
+				name,
 				key,
 				runner_name_selector,
 				sleeping,
@@ -58,6 +61,7 @@ pub async fn pegboard_actor_get_for_gateway(
 			) = tokio::try_join!(
 				tx.read_opt(&namespace_id_key, Serializable),
 				tx.read_opt(&workflow_id_key, Serializable),
+				tx.read_opt(&name_key, Serializable),
 				tx.read_opt(&key_key, Serializable),
 				tx.read_opt(&runner_name_selector_key, Serializable),
 				tx.exists(&sleep_ts_key, Serializable),
@@ -78,6 +82,7 @@ pub async fn pegboard_actor_get_for_gateway(
 			Ok(Some(Output {
 				namespace_id,
 				workflow_id,
+				name,
 				key,
 				runner_name_selector,
 				sleeping,
@@ -0,0 +1,41 @@
+use gas::prelude::*;
+use universaldb::utils::FormalKey;
+
+use crate::keys;
+
+#[derive(Debug)]
+pub struct Input {
+	pub namespace_id: Id,
+	pub actor_id: Id,
+}
+
+#[derive(Debug)]
+pub struct Output {
+	/// Datacenters holding a read replica of the actor's SQLite database.
+	pub datacenter_labels: Vec<u16>,
+}
+
+#[operation]
+pub async fn pegboard_actor_get_sqlite_replicas(
+	ctx: &OperationCtx,
+	input: &Input,
+) -> Result<Output> {
+	let replicas_key = keys::epoxy::ns::SqliteReplicasKey::new(input.namespace_id, input.actor_id);
+	let value = ctx
+		.op(epoxy::ops::kv::get_optimistic::Input {
+			replica_id: ctx.config().epoxy_replica_id(),
+			key: keys::subspace().pack(&replicas_key),
+			caching_behavior: epoxy_protocol::protocol::CachingBehavior::Optimistic,
+			target_replicas: None,
+			save_empty: false,
+		})
+		.await?
+		.value;
+
+	let datacenter_labels = match value {
+		Some(value) => replicas_key.deserialize(&value)?,
+		None => Vec::new(),
+	};
+
+	Ok(Output { datacenter_labels })
+}
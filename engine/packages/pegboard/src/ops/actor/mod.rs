@@ -1,3 +1,4 @@
+pub mod claim_first_client_region;
 pub mod create;
 pub mod get;
 pub mod get_for_gateway;
@@ -5,7 +6,9 @@ pub mod get_for_key;
 pub mod get_for_kv;
 pub mod get_for_runner;
 pub mod get_reservation_for_key;
+pub mod get_sqlite_replicas;
 pub mod hibernating_request;
 pub mod list_for_ns;
 pub mod list_names;
+pub mod set_sqlite_replicas;
 mod util;
@@ -0,0 +1,61 @@
+use epoxy::ops::propose::{Command, CommandKind, Proposal, SetCommand};
+use gas::prelude::*;
+use universaldb::prelude::*;
+
+use crate::keys;
+
+#[derive(Debug)]
+pub struct Input {
+	pub namespace_id: Id,
+	pub actor_id: Id,
+	/// Datacenters that should hold a read replica. The actor's own datacenter is ignored.
+	pub datacenter_labels: Vec<u16>,
+}
+
+/// Replaces the read replica set of an actor's SQLite database. Must run in the actor's
+/// datacenter, which ships changes to the replicas.
+#[operation]
+pub async fn pegboard_actor_set_sqlite_replicas(ctx: &OperationCtx, input: &Input) -> Result<()> {
+	let mut datacenter_labels = input
+		.datacenter_labels
+		.iter()
+		.copied()
+		.filter(|label| *label != input.actor_id.label())
+		.collect::<Vec<_>>();
+	datacenter_labels.sort_unstable();
+	datacenter_labels.dedup();
+
+	let replicas_key = keys::epoxy::ns::SqliteReplicasKey::new(input.namespace_id, input.actor_id);
+	ctx.op(epoxy::ops::propose::Input {
+		proposal: Proposal {
+			commands: vec![Command {
+				kind: CommandKind::SetCommand(SetCommand {
+					key: keys::subspace().pack(&replicas_key),
+					value: if datacenter_labels.is_empty() {
+						None
+					} else {
+						Some(replicas_key.serialize(datacenter_labels.clone())?)
+					},
+				}),
+			}],
+		},
+		purge_cache: true,
+		mutable: true,
+		target_replicas: None,
+	})
+	.await?;
+
+	// The replicator stops on its own once it reads an empty replica set
+	if !datacenter_labels.is_empty() {
+		ctx.workflow(crate::workflows::sqlite_replicator::Input {
+			namespace_id: input.namespace_id,
+			actor_id: input.actor_id,
+		})
+		.tag("actor_id", input.actor_id)
+		.unique()
+		.dispatch()
+		.await?;
+	}
+
+	Ok(())
+}
@@ -3,6 +3,7 @@ use rivet_cache::Cache;
 use rivet_runner_protocol as protocol;
 use rivet_types::{
 	keys::namespace::runner_config::RunnerConfigVariant,
+	namespaces::CanaryPolicy,
 	runner_configs::{RunnerConfig, RunnerConfigKind},
 };
 
@@ -41,6 +42,28 @@ pub fn runner_config_variant(runner_config: &RunnerConfig) -> RunnerConfigVarian
 	}
 }
 
+/// The canary pool an actor is sent to, if any. `roll` is a uniform random number in `0..100`.
+/// Rules for the actor's name take precedence over rules for every actor of the pool.
+pub fn canary_pool<'a>(
+	policy: &'a CanaryPolicy,
+	pool_name: &str,
+	actor_name: &str,
+	roll: u32,
+) -> Option<&'a str> {
+	policy
+		.rules
+		.iter()
+		.filter(|rule| rule.pool_name == pool_name)
+		.filter(|rule| {
+			rule.actor_name
+				.as_deref()
+				.is_none_or(|rule_actor_name| rule_actor_name == actor_name)
+		})
+		.max_by_key(|rule| rule.actor_name.is_some())
+		.filter(|rule| roll < rule.weight)
+		.map(|rule| rule.canary_pool_name.as_str())
+}
+
 pub async fn purge_runner_config_caches(
 	cache: &Cache,
 	namespace_id: Id,
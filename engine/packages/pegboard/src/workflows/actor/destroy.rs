@@ -190,6 +190,8 @@ async fn clear_kv(ctx: &ActivityCtx, input: &ClearKvInput) -> Result<ClearKvOutp
 
 			// Matches `delete_all` from actor kv
 			tx.clear_subspace_range(&subspace);
+			tx.with_subspace(crate::keys::subspace())
+				.delete(&crate::keys::actor::KvGenerationKey::new(input.actor_id));
 			crate::actor_sqlite::clear_v2_storage_for_destroy(&tx, input.actor_id);
 
 			Ok(final_size)
@@ -6,7 +6,7 @@ use universaldb::prelude::*;
 
 use crate::keys;
 
-const METRICS_INTERVAL_MS: i64 = util::duration::seconds(60);
+const METRICS_INTERVAL_MS: i64 = util::billing::METERING_INTERVAL;
 const EARLY_TXN_TIMEOUT: Duration = Duration::from_millis(2500);
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -109,6 +109,7 @@ pub(crate) async fn pegboard_actor_metrics(ctx: &mut WorkflowCtx, input: &Input)
 			}
 
 			ctx.activity(RecordKvMetricsInput {}).await?;
+			ctx.v(2).activity(RecordStorageUsageInput {}).await?;
 
 			if destroy {
 				Ok(Loop::Break(()))
@@ -123,6 +124,7 @@ pub(crate) async fn pegboard_actor_metrics(ctx: &mut WorkflowCtx, input: &Input)
 	// The actor's kv is deleted on destroy, so remove its contribution from the running kv
 	// storage total.
 	ctx.v(2).activity(RemoveKvMetricsInput {}).await?;
+	ctx.v(2).activity(RemoveStorageUsageInput {}).await?;
 
 	Ok(())
 }
@@ -358,6 +360,33 @@ async fn remove_kv_metrics(ctx: &ActivityCtx, input: &RemoveKvMetricsInput) -> R
 	Ok(())
 }
 
+#[derive(Debug, Serialize, Deserialize, Hash)]
+struct RecordStorageUsageInput {}
+
+#[activity(RecordStorageUsage)]
+async fn record_storage_usage(ctx: &ActivityCtx, input: &RecordStorageUsageInput) -> Result<()> {
+	let state = ctx.state::<State>()?;
+
+	crate::actor_storage::record_usage(
+		ctx,
+		state.namespace_id,
+		state.actor_id,
+		&state.name,
+		state.last_kv_storage_size,
+	)
+	.await
+}
+
+#[derive(Debug, Serialize, Deserialize, Hash)]
+struct RemoveStorageUsageInput {}
+
+#[activity(RemoveStorageUsage)]
+async fn remove_storage_usage(ctx: &ActivityCtx, input: &RemoveStorageUsageInput) -> Result<()> {
+	let state = ctx.state::<State>()?;
+
+	crate::actor_storage::clear_usage(ctx, state.namespace_id, state.actor_id, &state.name).await
+}
+
 #[signal("pegboard_actor_metrics_pause")]
 pub struct Pause {
 	pub ts: i64,
@@ -1133,6 +1133,17 @@ pub enum ActorError {
 }
 
 impl ActorError {
+	/// Label used in metrics.
+	pub fn kind(&self) -> &'static str {
+		match self {
+			ActorError::ConcurrentActorLimitReached => "concurrent_actor_limit_reached",
+			ActorError::NoEnvoys => "no_envoys",
+			ActorError::EnvoyNoResponse { .. } => "envoy_no_response",
+			ActorError::EnvoyConnectionLost { .. } => "envoy_connection_lost",
+			ActorError::Crashed { .. } => "crashed",
+		}
+	}
+
 	/// Used to determine the category of this error.
 	///
 	/// Actor errors will not override envoy errors.
@@ -1154,6 +1165,9 @@ async fn destroy(ctx: &mut WorkflowCtx, input: &Input) -> Result<()> {
 
 	ctx.activity(UpdateStateAndDbInput {}).await?;
 	ctx.activity(ClearKvInput {}).await?;
+	// The metrics workflow is not running for these actors, so its storage usage has to be
+	// cleared here
+	ctx.v(2).activity(ClearStorageUsageInput {}).await?;
 
 	ctx.msg(DestroyComplete {})
 		.topic(("actor_id", input.actor_id))
@@ -1250,6 +1264,8 @@ async fn clear_kv(ctx: &ActivityCtx, input: &ClearKvInput) -> Result<ClearKvOutp
 
 			// Matches `delete_all` from actor kv
 			tx.clear_subspace_range(&subspace);
+			tx.with_subspace(crate::keys::subspace())
+				.delete(&crate::keys::actor::KvGenerationKey::new(actor_id));
 			crate::actor_sqlite::clear_v2_storage_for_destroy(&tx, actor_id);
 
 			Ok(final_size)
@@ -1260,6 +1276,19 @@ async fn clear_kv(ctx: &ActivityCtx, input: &ClearKvInput) -> Result<ClearKvOutp
 	Ok(ClearKvOutput { final_size })
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct ClearStorageUsageInput {}
+
+#[activity(ClearStorageUsage)]
+async fn clear_storage_usage(ctx: &ActivityCtx, input: &ClearStorageUsageInput) -> Result<()> {
+	let state = ctx.state::<State>()?;
+
+	let namespace_id = state.namespace_id;
+	let actor_id = state.actor_id;
+	let name = state.name.clone();
+	crate::actor_storage::clear_usage(ctx, namespace_id, actor_id, &name).await
+}
+
 #[message("pegboard_actor2_create_complete")]
 pub struct CreateComplete {}
 
@@ -775,6 +775,14 @@ pub async fn set_error(ctx: &ActivityCtx, input: &SetErrorInput) -> Result<()> {
 		return Ok(());
 	}
 
+	crate::metrics::ACTOR_ERROR_TOTAL
+		.with_label_values(&[
+			state.namespace_id.to_string().as_str(),
+			state.pool_name.as_str(),
+			input.error.kind(),
+		])
+		.inc();
+
 	state.error = Some(input.error.clone());
 	Ok(())
 }
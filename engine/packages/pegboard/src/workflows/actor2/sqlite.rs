@@ -18,6 +18,7 @@ pub async fn migrate_sqlite_v1_to_v2(
 	let db = (*udb).clone();
 
 	actor_sqlite::migrate_v1_to_v2(
+		ctx.config(),
 		db,
 		actor_sqlite::MigrateV1ToV2Input {
 			actor_id: input.actor_id,
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use futures_util::FutureExt;
+use gas::prelude::*;
+
+use crate::actor_kv;
+
+/// How long the rotator idles before re-reading the config while encryption is disabled.
+const DISABLED_RECHECK_INTERVAL_MS: u64 = 5 * 60 * 1000;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Input {}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ReencryptState {
+	cursor: Option<Vec<u8>>,
+	values_reencrypted: usize,
+	bytes_reencrypted: u64,
+}
+
+/// Singleton loop that re-seals actor KV values sealed under retired data keys. Data keys are
+/// rotated by depot's key rotator. The KV of all actors is scanned one page per activity.
+#[workflow]
+pub async fn pegboard_actor_kv_key_rotator(ctx: &mut WorkflowCtx, input: &Input) -> Result<()> {
+	ctx.repeat(|ctx| {
+		async move {
+			let state = ctx
+				.loope(ReencryptState::default(), |ctx, state| {
+					async move {
+						let page = ctx
+							.activity(ReencryptPageInput {
+								cursor: state.cursor.clone(),
+							})
+							.await?;
+						state.values_reencrypted += page.values_reencrypted;
+						state.bytes_reencrypted += page.bytes_reencrypted;
+
+						let Some(next_cursor) = page.next_cursor else {
+							return Ok(Loop::Break(std::mem::take(state)));
+						};
+						state.cursor = Some(next_cursor);
+
+						Ok(Loop::Continue)
+					}
+					.boxed()
+				})
+				.await?;
+
+			tracing::debug!(
+				values_reencrypted = state.values_reencrypted,
+				bytes_reencrypted = state.bytes_reencrypted,
+				"re-encrypted actor kv values"
+			);
+
+			let next_scan_in_ms = ctx
+				.config()
+				.sqlite()
+				.encryption
+				.as_ref()
+				.map(|encryption| encryption.rotation_scan_interval_ms())
+				.unwrap_or(DISABLED_RECHECK_INTERVAL_MS);
+			ctx.sleep(Duration::from_millis(next_scan_in_ms)).await?;
+
+			Ok(Loop::<()>::Continue)
+		}
+		.boxed()
+	})
+	.await?;
+
+	Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+struct ReencryptPageInput {
+	/// Where the previous page stopped. `None` starts at the first actor.
+	cursor: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReencryptPageOutput {
+	values_reencrypted: usize,
+	bytes_reencrypted: u64,
+	/// `None` once the KV of every actor was scanned.
+	next_cursor: Option<Vec<u8>>,
+}
+
+#[activity(ReencryptPage)]
+#[timeout = 120]
+async fn reencrypt_page(
+	ctx: &ActivityCtx,
+	input: &ReencryptPageInput,
+) -> Result<ReencryptPageOutput> {
+	// Encryption can be disabled after the rotator was dispatched. Keep the workflow idle instead
+	// of failing so re-enabling it does not need a new dispatch.
+	let Some(keyring) = depot::encryption::init(ctx.config())? else {
+		return Ok(ReencryptPageOutput {
+			values_reencrypted: 0,
+			bytes_reencrypted: 0,
+			next_cursor: None,
+		});
+	};
+
+	let udb = ctx.udb()?;
+	let page = actor_kv::reencrypt::reencrypt_page(&udb, &keyring, input.cursor.clone()).await?;
+
+	Ok(ReencryptPageOutput {
+		values_reencrypted: page.values_reencrypted,
+		bytes_reencrypted: page.bytes_reencrypted,
+		next_cursor: page.next_cursor,
+	})
+}
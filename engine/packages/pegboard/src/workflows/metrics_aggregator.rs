@@ -25,6 +25,8 @@ pub async fn pegboard_metrics_aggregator(ctx: &mut WorkflowCtx, input: &Input) -
 			))
 			.await?;
 
+			ctx.v(2).activity(AggregateStorageUsageInput {}).await?;
+
 			ctx.sleep(TICK_RATE).await?;
 
 			Ok(Loop::<()>::Continue)
@@ -280,3 +282,83 @@ async fn aggregate_serverless_desired_slots(
 
 	Ok(())
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Hash)]
+struct AggregateStorageUsageInput {}
+
+/// Scans namespace storage usage rollups and exports them as metrics.
+#[activity(AggregateStorageUsage)]
+async fn aggregate_storage_usage(
+	ctx: &ActivityCtx,
+	_input: &AggregateStorageUsageInput,
+) -> Result<()> {
+	metrics::NAMESPACE_STORAGE_BYTES.reset();
+
+	let mut last_key = Vec::new();
+	loop {
+		last_key = ctx
+			.udb()?
+			.txn("pegboard_metrics_aggregate_storage_usage", |tx| {
+				let last_key = &last_key;
+				async move {
+					let start = Instant::now();
+					let tx = tx.with_subspace(namespace::keys::subspace());
+					let mut new_last_key = Vec::new();
+
+					let storage_usage_subspace = namespace::keys::subspace()
+						.subspace(&namespace::keys::usage::StorageUsageKey::entire_subspace());
+					let range = storage_usage_subspace.range();
+
+					let range_start = if last_key.is_empty() {
+						&range.0
+					} else {
+						&last_key
+					};
+					let range_end = &storage_usage_subspace.range().1;
+
+					let mut stream = tx.get_ranges_keyvalues(
+						universaldb::RangeOption {
+							mode: StreamingMode::WantAll,
+							..(range_start.as_slice(), range_end.as_slice()).into()
+						},
+						Snapshot,
+					);
+
+					loop {
+						if start.elapsed() > EARLY_TXN_TIMEOUT {
+							tracing::warn!("timed out processing storage usage metrics");
+							break;
+						}
+
+						let Some(entry) = stream.try_next().await? else {
+							new_last_key = Vec::new();
+							break;
+						};
+
+						let (storage_usage_key, bytes) =
+							tx.read_entry::<namespace::keys::usage::StorageUsageKey>(&entry)?;
+
+						if bytes != 0 {
+							metrics::NAMESPACE_STORAGE_BYTES
+								.with_label_values(&[
+									&storage_usage_key.namespace_id.to_string(),
+									&storage_usage_key.kind.to_string(),
+								])
+								.set(bytes);
+						}
+
+						new_last_key = [entry.key(), &[0xff]].concat();
+					}
+
+					Ok(new_last_key)
+				}
+			})
+			.await?;
+
+		if last_key.is_empty() {
+			break;
+		}
+	}
+
+	Ok(())
+}
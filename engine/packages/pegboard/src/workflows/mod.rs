@@ -1,5 +1,6 @@
 pub mod actor;
 pub mod actor2;
+pub mod actor_kv_key_rotator;
 pub mod actor_migration_fix_backfill;
 pub mod actor_runner_name_selector_backfill;
 pub mod metrics_aggregator;
@@ -11,3 +12,4 @@ pub mod runner_pool_backfill;
 pub mod runner_pool_error_tracker;
 pub mod runner_pool_metadata_poller;
 pub mod serverless;
+pub mod sqlite_replicator;
@@ -0,0 +1,281 @@
+use std::{collections::BTreeMap, pin::Pin, sync::Arc, time::Duration};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use depot::{
+	conveyer::Db,
+	replica::ReplicaChanges,
+	types::{ReplicaBatch, ReplicaBatchKind, ReplicaCursor, ReplicaState, encode_replica_batch},
+};
+use futures_util::{FutureExt, StreamExt};
+use gas::prelude::*;
+use rivet_api_builder::error_response::RawErrorResponse;
+use rivet_api_util::{Method, request_remote_datacenter};
+
+/// Longest the replicator waits between shipping rounds. Commits wake it right away, so this only
+/// bounds how long a lost wakeup delays shipping and how often idle replicas learn they are still
+/// caught up.
+const FALLBACK_SHIP_INTERVAL_MS: u64 = 30_000;
+/// Commit wakeups consumed per round. One round ships every commit before it.
+const MAX_WAKEUPS_PER_ROUND: usize = 1024;
+/// Deltas shipped to a replica per request.
+const MAX_DELTAS_PER_BATCH: usize = 64;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Input {
+	pub namespace_id: Id,
+	pub actor_id: Id,
+}
+
+/// Body of the internal api-peer request that applies a batch to a replica.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApplyReplicaBatchRequest {
+	pub namespace_id: Id,
+	pub actor_id: Id,
+	/// Base64 of the encoded `ReplicaBatch`.
+	pub batch: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApplyReplicaBatchResponse {
+	pub state: ReplicaState,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct State {
+	/// Last source position applied by each replica datacenter. Missing entries resync.
+	cursors: BTreeMap<u16, ReplicaCursor>,
+}
+
+/// Ships committed changes of an actor's SQLite database to its replica datacenters. Runs in the
+/// actor's datacenter until the replica set is cleared or the actor is destroyed.
+#[workflow]
+pub async fn pegboard_sqlite_replicator(ctx: &mut WorkflowCtx, input: &Input) -> Result<()> {
+	ctx.loope(State::default(), |ctx, state| {
+		let input = input.clone();
+		async move {
+			let res = ctx
+				.activity(ShipChangesInput {
+					namespace_id: input.namespace_id,
+					actor_id: input.actor_id,
+					cursors: state.cursors.clone(),
+				})
+				.await?;
+
+			match res {
+				ShipChangesOutput::Shipped {
+					cursors,
+					reached_head,
+				} => {
+					state.cursors = cursors;
+
+					// Replicas behind by more than one batch keep shipping without waiting
+					if !reached_head {
+						return Ok(Loop::Continue);
+					}
+				}
+				ShipChangesOutput::Stop => return Ok(Loop::Break(())),
+			}
+
+			ctx.listen_n_with_timeout::<ChangesCommitted>(
+				Duration::from_millis(FALLBACK_SHIP_INTERVAL_MS),
+				MAX_WAKEUPS_PER_ROUND,
+			)
+			.await?;
+
+			Ok(Loop::Continue)
+		}
+		.boxed()
+	})
+	.await?;
+
+	Ok(())
+}
+
+/// Sent after a commit to the actor's database so the replicator ships it without waiting for its
+/// fallback round.
+#[signal("pegboard_sqlite_replicator_changes_committed")]
+#[derive(Debug)]
+pub struct ChangesCommitted {}
+
+#[derive(Debug, Serialize, Deserialize, Hash)]
+struct ShipChangesInput {
+	namespace_id: Id,
+	actor_id: Id,
+	cursors: BTreeMap<u16, ReplicaCursor>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum ShipChangesOutput {
+	Shipped {
+		cursors: BTreeMap<u16, ReplicaCursor>,
+		/// False if a replica still has changes to ship.
+		reached_head: bool,
+	},
+	Stop,
+}
+
+#[activity(ShipChanges)]
+#[timeout = 600]
+async fn ship_changes(ctx: &ActivityCtx, input: &ShipChangesInput) -> Result<ShipChangesOutput> {
+	let replicas = ctx
+		.op(crate::ops::actor::get_sqlite_replicas::Input {
+			namespace_id: input.namespace_id,
+			actor_id: input.actor_id,
+		})
+		.await?;
+	if replicas.datacenter_labels.is_empty() {
+		tracing::debug!(actor_id=?input.actor_id, "sqlite replica set is empty, stopping replicator");
+		return Ok(ShipChangesOutput::Stop);
+	}
+
+	let actor = ctx
+		.op(crate::ops::actor::get::Input {
+			actor_ids: vec![input.actor_id],
+			fetch_error: false,
+		})
+		.await?
+		.actors
+		.into_iter()
+		.next();
+	if actor.is_none_or(|actor| actor.destroy_ts.is_some()) {
+		tracing::debug!(actor_id=?input.actor_id, "actor destroyed, stopping replicator");
+		return Ok(ShipChangesOutput::Stop);
+	}
+
+	let udb = ctx.pools().udb()?;
+	let db = Db::from_config(
+		ctx.config(),
+		Arc::new((*udb).clone()),
+		input.namespace_id,
+		input.actor_id.to_string(),
+		ctx.pools().node_id(),
+	)
+	.await?;
+
+	let mut cursors = BTreeMap::new();
+	let mut reached_head = true;
+	for dc_label in replicas.datacenter_labels {
+		let cursor = input.cursors.get(&dc_label).copied();
+		match ship_to_replica(ctx, input, &db, dc_label, cursor).await {
+			Ok((new_cursor, replica_reached_head)) => {
+				// A replica that did not advance waits for the next round instead of spinning
+				reached_head &= replica_reached_head || new_cursor == cursor;
+				if let Some(new_cursor) = new_cursor {
+					cursors.insert(dc_label, new_cursor);
+				}
+			}
+			Err(err) if is_out_of_sync(&err) => {
+				tracing::warn!(?err, actor_id=?input.actor_id, dc_label, "sqlite replica out of sync, resyncing");
+			}
+			Err(err) => {
+				// Keep the cursor so the next round retries from the same position
+				tracing::warn!(?err, actor_id=?input.actor_id, dc_label, "failed to ship sqlite changes to replica");
+				if let Some(cursor) = cursor {
+					cursors.insert(dc_label, cursor);
+				}
+			}
+		}
+	}
+
+	Ok(ShipChangesOutput::Shipped {
+		cursors,
+		reached_head,
+	})
+}
+
+/// Ships the next batch the replica is missing. Returns the replica's new cursor and whether it
+/// reached the source head.
+async fn ship_to_replica(
+	ctx: &ActivityCtx,
+	input: &ShipChangesInput,
+	db: &Db,
+	dc_label: u16,
+	cursor: Option<ReplicaCursor>,
+) -> Result<(Option<ReplicaCursor>, bool)> {
+	let Some(changes) = db
+		.replica_changes_after(cursor, MAX_DELTAS_PER_BATCH)
+		.await?
+	else {
+		return Ok((cursor, true));
+	};
+
+	match changes {
+		// Batches without deltas still tell the replica how recently it was caught up
+		ReplicaChanges::Deltas { head, deltas } => {
+			let state =
+				send_batch(ctx, input, dc_label, head, ReplicaBatchKind::Deltas(deltas)).await?;
+
+			Ok((state.cursor, state.cursor == Some(head)))
+		}
+		ReplicaChanges::ResyncRequired { reason } => {
+			tracing::info!(actor_id=?input.actor_id, dc_label, %reason, "resyncing sqlite replica");
+
+			let Some(snapshot) = db.replica_snapshot().await? else {
+				return Ok((None, true));
+			};
+			let mut pages = snapshot.pages.peekable();
+			let state = loop {
+				let batch = pages.next().await.transpose()?.unwrap_or_default();
+				let last = Pin::new(&mut pages).peek().await.is_none();
+				let state = send_batch(
+					ctx,
+					input,
+					dc_label,
+					snapshot.head,
+					ReplicaBatchKind::Snapshot {
+						db_size_pages: snapshot.db_size_pages,
+						pages: batch,
+						last,
+					},
+				)
+				.await?;
+
+				if last {
+					break state;
+				}
+			};
+
+			// Commits that landed during the snapshot ship in the next round
+			Ok((state.cursor, false))
+		}
+	}
+}
+
+async fn send_batch(
+	ctx: &ActivityCtx,
+	input: &ShipChangesInput,
+	dc_label: u16,
+	source_head: ReplicaCursor,
+	kind: ReplicaBatchKind,
+) -> Result<ReplicaState> {
+	let batch = encode_replica_batch(ReplicaBatch {
+		source_dc_label: ctx.config().dc_label(),
+		source_head,
+		observed_at_ms: util::timestamp::now(),
+		kind,
+	})?;
+
+	let res = request_remote_datacenter::<ApplyReplicaBatchResponse>(
+		ctx.config(),
+		dc_label,
+		"/depot/replicas/apply",
+		Method::POST,
+		None::<()>,
+		Some(&ApplyReplicaBatchRequest {
+			namespace_id: input.namespace_id,
+			actor_id: input.actor_id,
+			batch: BASE64_STANDARD.encode(batch),
+		}),
+	)
+	.await?;
+
+	Ok(res.state)
+}
+
+fn is_out_of_sync(err: &anyhow::Error) -> bool {
+	err.chain()
+		.find_map(|x| x.downcast_ref::<RawErrorResponse>())
+		.is_some_and(|err| err.1.group == "depot" && err.1.code == "replica_out_of_sync")
+}
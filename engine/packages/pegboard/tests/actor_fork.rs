@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use depot::{
+	conveyer::Db,
+	types::{DirtyPage, SQLITE_PAGE_SIZE, SnapshotSelector},
+};
+use gas::prelude::{Id, util::timestamp};
+use pegboard::{
+	actor_fork::{self, ForkSource},
+	actor_kv::{self, Recipient},
+};
+use rivet_pools::NodeId;
+use tempfile::Builder;
+
+async fn test_db() -> Result<universaldb::Database> {
+	let path = Builder::new()
+		.prefix("pegboard-actor-fork-")
+		.tempdir()?
+		.keep();
+	let driver = universaldb::driver::RocksDbDatabaseDriver::new(path).await?;
+
+	Ok(universaldb::Database::new(Arc::new(driver)))
+}
+
+fn recipient(namespace_id: Id, actor_id: Id) -> Recipient {
+	Recipient {
+		actor_id,
+		namespace_id,
+		name: "default".to_string(),
+	}
+}
+
+fn depot_db(db: &universaldb::Database, namespace_id: Id, actor_id: Id) -> Db {
+	Db::new(
+		Arc::new(db.clone()),
+		namespace_id,
+		actor_id.to_string(),
+		NodeId::new(),
+	)
+}
+
+fn page(fill: u8) -> Vec<u8> {
+	vec![fill; SQLITE_PAGE_SIZE as usize]
+}
+
+async fn kv_value(
+	db: &universaldb::Database,
+	recipient: &Recipient,
+	key: &[u8],
+) -> Result<Option<Vec<u8>>> {
+	let (_, values, _) = actor_kv::get(db, recipient, vec![key.to_vec()]).await?;
+
+	Ok(values.into_iter().next())
+}
+
+#[tokio::test]
+async fn fork_copies_sqlite_and_kv_and_diverges() -> Result<()> {
+	let db = test_db().await?;
+	let namespace_id = Id::new_v1(1);
+	let source_actor_id = Id::new_v1(1);
+	let actor_id = Id::new_v1(1);
+	let source = recipient(namespace_id, source_actor_id);
+	let target = recipient(namespace_id, actor_id);
+
+	depot_db(&db, namespace_id, source_actor_id)
+		.commit(
+			vec![DirtyPage {
+				pgno: 1,
+				bytes: page(0x11),
+			}],
+			1,
+			timestamp::now(),
+		)
+		.await?;
+	actor_kv::put(
+		&db,
+		&source,
+		vec![b"a".to_vec(), b"b".to_vec()],
+		vec![b"one".to_vec(), vec![7; 40_000]],
+	)
+	.await?;
+
+	actor_fork::fork_storage(
+		&db,
+		namespace_id,
+		&ForkSource {
+			actor_id: source_actor_id,
+			selector: SnapshotSelector::Latest,
+		},
+		actor_id,
+	)
+	.await?;
+
+	assert_eq!(kv_value(&db, &target, b"a").await?, Some(b"one".to_vec()));
+	assert_eq!(kv_value(&db, &target, b"b").await?, Some(vec![7; 40_000]));
+	let pages = depot_db(&db, namespace_id, actor_id)
+		.get_pages(vec![1])
+		.await?;
+	assert_eq!(pages[0].bytes, Some(page(0x11)));
+	assert_eq!(
+		actor_fork::read_fork_source(&db, actor_id).await?,
+		Some(source_actor_id)
+	);
+
+	// Writes to the source after the fork do not reach the fork
+	actor_kv::put(&db, &source, vec![b"a".to_vec()], vec![b"two".to_vec()]).await?;
+	depot_db(&db, namespace_id, source_actor_id)
+		.commit(
+			vec![DirtyPage {
+				pgno: 1,
+				bytes: page(0x22),
+			}],
+			1,
+			timestamp::now(),
+		)
+		.await?;
+	assert_eq!(kv_value(&db, &target, b"a").await?, Some(b"one".to_vec()));
+	let pages = depot_db(&db, namespace_id, actor_id)
+		.get_pages(vec![1])
+		.await?;
+	assert_eq!(pages[0].bytes, Some(page(0x11)));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn discard_forked_storage_removes_kv_and_database() -> Result<()> {
+	let db = test_db().await?;
+	let namespace_id = Id::new_v1(1);
+	let source_actor_id = Id::new_v1(1);
+	let actor_id = Id::new_v1(1);
+	let source = recipient(namespace_id, source_actor_id);
+	let target = recipient(namespace_id, actor_id);
+
+	depot_db(&db, namespace_id, source_actor_id)
+		.commit(
+			vec![DirtyPage {
+				pgno: 1,
+				bytes: page(0x33),
+			}],
+			1,
+			timestamp::now(),
+		)
+		.await?;
+	actor_kv::put(&db, &source, vec![b"a".to_vec()], vec![b"one".to_vec()]).await?;
+	actor_fork::fork_storage(
+		&db,
+		namespace_id,
+		&ForkSource {
+			actor_id: source_actor_id,
+			selector: SnapshotSelector::Latest,
+		},
+		actor_id,
+	)
+	.await?;
+
+	actor_fork::discard_forked_storage(&db, namespace_id, actor_id).await?;
+
+	assert_eq!(kv_value(&db, &target, b"a").await?, None);
+	assert_eq!(actor_fork::read_fork_source(&db, actor_id).await?, None);
+	assert!(
+		depot_db(&db, namespace_id, actor_id)
+			.get_pages(vec![1])
+			.await
+			.map(|pages| pages[0].bytes.is_none())
+			.unwrap_or(true),
+		"discarded fork should no longer resolve its database"
+	);
+	// The source is untouched
+	assert_eq!(kv_value(&db, &source, b"a").await?, Some(b"one".to_vec()));
+
+	Ok(())
+}
@@ -117,6 +117,7 @@ async fn migrate(
 	actor_id: Id,
 ) -> Result<pegboard::actor_sqlite::MigrateV1ToV2Output> {
 	pegboard::actor_sqlite::migrate_v1_to_v2(
+		&rivet_config::Config::from_root(Default::default()),
 		db.clone(),
 		pegboard::actor_sqlite::MigrateV1ToV2Input {
 			actor_id,
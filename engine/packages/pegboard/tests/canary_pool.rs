@@ -0,0 +1,64 @@
+use pegboard::utils::canary_pool;
+use rivet_types::namespaces::{CanaryPolicy, CanaryRule};
+
+fn rule(
+	pool_name: &str,
+	canary_pool_name: &str,
+	weight: u32,
+	actor_name: Option<&str>,
+) -> CanaryRule {
+	CanaryRule {
+		pool_name: pool_name.to_string(),
+		canary_pool_name: canary_pool_name.to_string(),
+		weight,
+		actor_name: actor_name.map(|x| x.to_string()),
+	}
+}
+
+#[test]
+fn canary_pool_follows_weight() {
+	let policy = CanaryPolicy {
+		rules: vec![rule("default", "default-canary", 10, None)],
+	};
+
+	assert_eq!(
+		canary_pool(&policy, "default", "counter", 0),
+		Some("default-canary")
+	);
+	assert_eq!(
+		canary_pool(&policy, "default", "counter", 9),
+		Some("default-canary")
+	);
+	assert_eq!(canary_pool(&policy, "default", "counter", 10), None);
+	assert_eq!(canary_pool(&policy, "default", "counter", 99), None);
+}
+
+#[test]
+fn canary_pool_ignores_other_pools() {
+	let policy = CanaryPolicy {
+		rules: vec![rule("other", "other-canary", 100, None)],
+	};
+
+	assert_eq!(canary_pool(&policy, "default", "counter", 0), None);
+	assert_eq!(
+		canary_pool(&CanaryPolicy::default(), "default", "counter", 0),
+		None
+	);
+}
+
+#[test]
+fn canary_pool_prefers_actor_name_rules() {
+	let policy = CanaryPolicy {
+		rules: vec![
+			rule("default", "actor-canary", 0, Some("counter")),
+			rule("default", "pool-canary", 100, None),
+		],
+	};
+
+	// The actor name rule wins even with a weight of 0
+	assert_eq!(canary_pool(&policy, "default", "counter", 0), None);
+	assert_eq!(
+		canary_pool(&policy, "default", "chat", 0),
+		Some("pool-canary")
+	);
+}
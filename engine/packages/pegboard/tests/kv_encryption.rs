@@ -0,0 +1,205 @@
+use anyhow::{Context, Result};
+use gas::prelude::*;
+use pegboard::actor_kv as kv;
+use rivet_envoy_protocol as ep;
+use universaldb::prelude::*;
+
+const MASTER_KEY: [u8; 32] = [9; 32];
+
+async fn setup_recipient(test_name: &str) -> Result<(rivet_test_deps::TestDeps, kv::Recipient)> {
+	let test_id = Uuid::new_v4();
+	let dc_label = 1;
+	let datacenters = [(
+		"test-dc".to_string(),
+		rivet_config::config::topology::Datacenter {
+			name: "test-dc".to_string(),
+			datacenter_label: dc_label,
+			is_leader: true,
+			peer_url: url::Url::parse("http://127.0.0.1:8080")?,
+			public_url: url::Url::parse("http://127.0.0.1:8081")?,
+			proxy_url: None,
+			valid_hosts: None,
+		},
+	)]
+	.into_iter()
+	.collect();
+
+	let api_peer_port = portpicker::pick_unused_port().expect("failed to pick api peer port");
+	let guard_port = portpicker::pick_unused_port().expect("failed to pick guard port");
+	let test_deps = rivet_test_deps::setup_single_datacenter(
+		test_id,
+		dc_label,
+		datacenters,
+		api_peer_port,
+		guard_port,
+	)
+	.await?;
+
+	let recipient = kv::Recipient {
+		actor_id: Id::new_v1(dc_label),
+		namespace_id: Id::new_v1(dc_label),
+		name: test_name.to_string(),
+	};
+
+	Ok((test_deps, recipient))
+}
+
+/// Writes an entry the way `put` did before encryption was enabled: metadata and value, no sealed
+/// flag.
+async fn put_legacy_plaintext(
+	db: &universaldb::Database,
+	actor_id: Id,
+	key: ep::KvKey,
+	value: ep::KvValue,
+) -> Result<()> {
+	db.txn("test_pegboard_kv_put_legacy", |tx| {
+		let key = key.clone();
+		let value = value.clone();
+		async move {
+			let subspace = pegboard::keys::actor_kv::subspace(actor_id);
+			let tx = tx.with_subspace(subspace.clone());
+			let key = pegboard::keys::actor_kv::KeyWrapper(key);
+			tx.write(
+				&pegboard::keys::actor_kv::EntryMetadataKey::new(key.clone()),
+				ep::KvMetadata {
+					version: b"legacy".to_vec(),
+					update_ts: 0,
+				},
+			)?;
+			tx.set(
+				&subspace.pack(&pegboard::keys::actor_kv::EntryValueChunkKey::new(key, 0)),
+				&value,
+			);
+			Ok(())
+		}
+	})
+	.await
+}
+
+#[tokio::test]
+async fn sealed_flag_decides_decryption_not_value_bytes() -> Result<()> {
+	depot::encryption::install(depot::encryption::Keyring::new(&MASTER_KEY, &[])?);
+	let (test_deps, recipient) = setup_recipient("kv_encryption").await?;
+	let db = &test_deps.pools.udb()?;
+
+	// Plaintext user values that happen to start with the sealed magic
+	let mut lookalike = b"DENC".to_vec();
+	lookalike.resize(256, 0xab);
+	put_legacy_plaintext(
+		db,
+		recipient.actor_id,
+		b"legacy".to_vec(),
+		lookalike.clone(),
+	)
+	.await?;
+	kv::put(
+		db,
+		&recipient,
+		vec![b"sealed".to_vec()],
+		vec![lookalike.clone()],
+	)
+	.await?;
+
+	let (keys, values, _) =
+		kv::get(db, &recipient, vec![b"legacy".to_vec(), b"sealed".to_vec()]).await?;
+	assert_eq!(keys, vec![b"legacy".to_vec(), b"sealed".to_vec()]);
+	assert_eq!(values, vec![lookalike.clone(), lookalike.clone()]);
+
+	// The sealed entry is stored as ciphertext
+	let actor_id = recipient.actor_id;
+	let stored = db
+		.txn("test_pegboard_kv_read_raw", |tx| async move {
+			let subspace = pegboard::keys::actor_kv::subspace(actor_id);
+			let value = tx
+				.informal()
+				.get(
+					&subspace.pack(&pegboard::keys::actor_kv::EntryValueChunkKey::new(
+						pegboard::keys::actor_kv::KeyWrapper(b"sealed".to_vec()),
+						0,
+					)),
+					Serializable,
+				)
+				.await?;
+			Ok(value.map(|value| value.to_vec()))
+		})
+		.await?
+		.expect("sealed value should exist");
+	assert_ne!(stored.as_slice(), lookalike.as_slice());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn rotation_reseals_kv_values_under_the_new_key() -> Result<()> {
+	let keyring = depot::encryption::install(depot::encryption::Keyring::new(&MASTER_KEY, &[])?);
+	let (test_deps, recipient) = setup_recipient("kv_encryption_rotate").await?;
+	let db = &test_deps.pools.udb()?;
+	let bucket_id = depot::types::BucketId::from_gas_id(recipient.namespace_id);
+
+	// Spans several value chunks
+	let value = (0..25_000).map(|i| i as u8).collect::<Vec<_>>();
+	kv::put(db, &recipient, vec![b"big".to_vec()], vec![value.clone()]).await?;
+	assert_eq!(
+		first_chunk_key_version(db, recipient.actor_id, b"big").await?,
+		1
+	);
+
+	keyring.rotate_data_key(db, bucket_id).await?;
+
+	let mut values_reencrypted = 0;
+	let mut cursor = None;
+	loop {
+		let page = kv::reencrypt::reencrypt_page(db, &keyring, cursor).await?;
+		values_reencrypted += page.values_reencrypted;
+		let Some(next_cursor) = page.next_cursor else {
+			break;
+		};
+		cursor = Some(next_cursor);
+	}
+	assert_eq!(values_reencrypted, 1);
+	assert_eq!(
+		first_chunk_key_version(db, recipient.actor_id, b"big").await?,
+		2
+	);
+
+	let (_, values, _) = kv::get(db, &recipient, vec![b"big".to_vec()]).await?;
+	assert_eq!(values, vec![value]);
+
+	// Nothing left to re-seal
+	let page = kv::reencrypt::reencrypt_page(db, &keyring, None).await?;
+	assert_eq!(page.values_reencrypted, 0);
+
+	Ok(())
+}
+
+async fn first_chunk_key_version(
+	db: &universaldb::Database,
+	actor_id: Id,
+	key: &[u8],
+) -> Result<u32> {
+	let key = key.to_vec();
+	let chunk = db
+		.txn("test_pegboard_kv_read_first_chunk", |tx| {
+			let key = key.clone();
+			async move {
+				let subspace = pegboard::keys::actor_kv::subspace(actor_id);
+				let value = tx
+					.informal()
+					.get(
+						&subspace.pack(&pegboard::keys::actor_kv::EntryValueChunkKey::new(
+							pegboard::keys::actor_kv::KeyWrapper(key),
+							0,
+						)),
+						Serializable,
+					)
+					.await?;
+				Ok(value.map(|value| value.to_vec()))
+			}
+		})
+		.await?
+		.context("value chunk should exist")?;
+
+	Ok(depot::encryption::sealed_key_id(&chunk)?
+		.context("value should be sealed")?
+		.version)
+}
@@ -50,10 +50,36 @@ pub async fn setup(config: &Config) -> Result<Option<UdbPool>> {
 					.await?,
 			) as universaldb::DatabaseDriverHandle
 		}
-		config::Database::FileSystem(fs) => {
-			Arc::new(universaldb::driver::RocksDbDatabaseDriver::new(fs.path.clone()).await?)
-				as universaldb::DatabaseDriverHandle
-		}
+		config::Database::FileSystem(fs) => match &fs.replication {
+			Some(replication) => {
+				// NATS is inherited from the UPS config in `validate_and_set_defaults`.
+				let nats = replication
+					.nats
+					.as_ref()
+					.context("replicated file system database requires nats")?;
+
+				let replicated_config = universaldb::driver::replicated::ReplicatedConfig {
+					path: fs.path.clone(),
+					node_id: replication.node_id.clone(),
+					members: replication.members.clone(),
+					nats: universaldb::driver::postgres::NatsConfig {
+						addresses: nats.addresses.clone(),
+						username: nats.username.clone(),
+						password: nats.password.as_ref().map(|p| p.read().clone()),
+						client_capacity: nats.client_capacity,
+						subscription_capacity: nats.subscription_capacity,
+					},
+				};
+
+				Arc::new(
+					universaldb::driver::ReplicatedDatabaseDriver::new(replicated_config).await?,
+				) as universaldb::DatabaseDriverHandle
+			}
+			None => {
+				Arc::new(universaldb::driver::RocksDbDatabaseDriver::new(fs.path.clone()).await?)
+					as universaldb::DatabaseDriverHandle
+			}
+		},
 	};
 
 	tracing::debug!("udb started");
@@ -1,8 +1,17 @@
-use reqwest::Client;
+use std::{
+	net::{IpAddr, SocketAddr},
+	sync::Arc,
+};
+
+use reqwest::{
+	Client,
+	dns::{Addrs, Name, Resolve, Resolving},
+};
 use tokio::sync::OnceCell;
 
 static CLIENT: OnceCell<Client> = OnceCell::const_new();
 static CLIENT_NO_TIMEOUT: OnceCell<Client> = OnceCell::const_new();
+static CLIENT_PUBLIC: OnceCell<Client> = OnceCell::const_new();
 static CLIENT_USER_AGENT: &str = concat!("RivetEngine/", env!("CARGO_PKG_VERSION"));
 
 pub async fn client() -> Result<Client, reqwest::Error> {
@@ -23,3 +32,101 @@ pub async fn client_no_timeout() -> Result<Client, reqwest::Error> {
 		.await
 		.cloned()
 }
+
+/// Client for URLs configured by users. It only speaks https, does not follow redirects and
+/// refuses to connect to hosts that resolve to non-public addresses, so user URLs cannot reach
+/// services on the internal network.
+///
+/// The resolver is not consulted for IP literal hosts, check URLs with [`check_public_url`]
+/// before requesting them.
+pub async fn client_public() -> Result<Client, reqwest::Error> {
+	CLIENT_PUBLIC
+		.get_or_try_init(|| async {
+			Client::builder()
+				.user_agent(CLIENT_USER_AGENT)
+				.timeout(std::time::Duration::from_secs(30))
+				.https_only(true)
+				.redirect(reqwest::redirect::Policy::none())
+				.dns_resolver(Arc::new(PublicResolver))
+				.build()
+		})
+		.await
+		.cloned()
+}
+
+/// Checks that a user-configured URL is https and does not name a non-public host. Hostnames are
+/// checked again when [`client_public`] resolves them.
+pub fn check_public_url(url: &str) -> Result<url::Url, String> {
+	let url = url::Url::parse(url).map_err(|err| format!("invalid url: {err}"))?;
+	if url.scheme() != "https" {
+		return Err("url must be https".to_string());
+	}
+
+	let public = match url.host() {
+		Some(url::Host::Domain(domain)) => {
+			let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+			domain != "localhost" && !domain.ends_with(".localhost")
+		}
+		Some(url::Host::Ipv4(ip)) => is_public_ip(IpAddr::V4(ip)),
+		Some(url::Host::Ipv6(ip)) => is_public_ip(IpAddr::V6(ip)),
+		None => false,
+	};
+	if !public {
+		return Err("url must not point to a private, loopback or link-local host".to_string());
+	}
+
+	Ok(url)
+}
+
+/// Whether `ip` is publicly routable. Private, loopback, link-local, shared, documentation,
+/// multicast and unspecified addresses are not.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+	match ip {
+		IpAddr::V4(ip) => {
+			let [a, b, ..] = ip.octets();
+			!(ip.is_private()
+				|| ip.is_loopback()
+				|| ip.is_link_local()
+				|| ip.is_unspecified()
+				|| ip.is_broadcast()
+				|| ip.is_documentation()
+				|| ip.is_multicast()
+				// Shared address space (100.64.0.0/10)
+				|| (a == 100 && (b & 0xc0) == 64)
+				// "This network" (0.0.0.0/8)
+				|| a == 0)
+		}
+		IpAddr::V6(ip) => {
+			if let Some(ip) = ip.to_ipv4_mapped() {
+				return is_public_ip(IpAddr::V4(ip));
+			}
+
+			let first = ip.segments()[0];
+			!(ip.is_loopback()
+				|| ip.is_unspecified()
+				|| ip.is_multicast()
+				// Unique local (fc00::/7)
+				|| (first & 0xfe00) == 0xfc00
+				// Link-local (fe80::/10)
+				|| (first & 0xffc0) == 0xfe80)
+		}
+	}
+}
+
+/// Resolver that fails for hosts with any non-public address.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+	fn resolve(&self, name: Name) -> Resolving {
+		Box::pin(async move {
+			let addrs = tokio::net::lookup_host((name.as_str(), 0))
+				.await?
+				.collect::<Vec<SocketAddr>>();
+			if addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+				return Err(format!("{} resolves to a non-public address", name.as_str()).into());
+			}
+
+			Ok(Box::new(addrs.into_iter()) as Addrs)
+		})
+	}
+}
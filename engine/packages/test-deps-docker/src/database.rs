@@ -82,7 +82,10 @@ impl TestDatabase {
 				std::fs::create_dir_all(&temp_dir)?;
 
 				let config = rivet_config::config::Database::FileSystem(
-					rivet_config::config::db::FileSystem { path: temp_dir },
+					rivet_config::config::db::FileSystem {
+						path: temp_dir,
+						replication: None,
+					},
 				);
 
 				Ok((config, None))
@@ -87,6 +87,10 @@ pub async fn setup_single_datacenter(
 		host: None,
 		port: Some(guard_port),
 		https: None,
+		// Test hostnames are not in public DNS
+		verify_domain_ownership: Some(false),
+		// Tests serve JWKS from a local http server
+		allow_private_jwks_urls: Some(true),
 		..Default::default()
 	});
 	root.metrics = rivet_config::config::metrics::Metrics {
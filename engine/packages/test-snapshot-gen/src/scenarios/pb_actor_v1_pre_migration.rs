@@ -45,6 +45,7 @@ impl Scenario for PbActorV1PreMigration {
 			crash_policy: CrashPolicy::Sleep,
 			forward_request: false,
 			datacenter_name: None,
+			fork_from: None,
 		})
 		.await?;
 
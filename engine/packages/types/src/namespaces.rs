@@ -8,3 +8,245 @@ pub struct Namespace {
 	pub display_name: String,
 	pub create_ts: i64,
 }
+
+/// Rate limits applied by guard to requests for a namespace. Limits are shared by every guard node
+/// in a datacenter and apply to each datacenter separately.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitPolicy {
+	pub rules: Vec<RateLimitRule>,
+}
+
+/// Requests matching a rule are counted per value of its key. Requests past the limit are rejected
+/// with a 429 and a `Retry-After` header.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitRule {
+	/// Unique within the policy. Used in errors and metrics.
+	pub name: String,
+	/// Route kinds the rule applies to. Applies to every route kind when empty.
+	#[serde(default)]
+	pub routes: Vec<RateLimitRoute>,
+	/// Only applies to actors with this name. Rules with an actor name never match API requests.
+	#[serde(default)]
+	pub actor_name: Option<String>,
+	/// What requests are counted by.
+	pub key: RateLimitKey,
+	/// Requests allowed per period.
+	pub requests: u64,
+	/// Length of the window requests are counted in, in milliseconds. At most one day.
+	pub period_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitRoute {
+	/// HTTP requests to actors.
+	Http,
+	/// WebSocket connections to actors.
+	Websocket,
+	/// Requests to the public API.
+	Api,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+	/// One limit shared by the whole namespace.
+	Namespace,
+	ClientIp,
+	/// Requests without an actor, such as API requests, are not counted.
+	ActorId,
+	/// The token the request authenticated with. Requests without a token are counted by client
+	/// IP.
+	Token,
+}
+
+/// Routes requests for a domain to an actor of the namespace. Routes are stored in every
+/// datacenter, so requests can reach any datacenter's guard.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DomainRoute {
+	/// Hostname the route matches, e.g. `api.customer.com`. A leading `*.` matches exactly one
+	/// subdomain label, e.g. `*.example.com` matches `room-123.example.com`.
+	///
+	/// The first route of a hostname requires a TXT record `_rivet-challenge.{hostname}` (without
+	/// the `*.`) with the value `rivet-namespace={namespace_id}`. The hostname then belongs to
+	/// the namespace until its last route is deleted.
+	pub hostname: String,
+	/// Only requests under this path are routed. The prefix is stripped before the request
+	/// reaches the actor. Routes every path when unset.
+	#[serde(default)]
+	pub path_prefix: Option<String>,
+	pub actor: DomainRouteActor,
+}
+
+/// Actor a domain route resolves to, looked up by name and key like a `get` or `getOrCreate`
+/// gateway query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DomainRouteActor {
+	pub name: String,
+	/// Key template. `{subdomain}` is replaced with the label matched by a wildcard hostname and
+	/// `{path.N}` with the Nth path segment after the prefix. Path segments used by the key are
+	/// stripped before the request reaches the actor.
+	#[serde(default)]
+	pub key: Vec<String>,
+	/// Creates the actor with runners from this pool if none exists for the key. Requests for
+	/// missing actors fail when unset.
+	#[serde(default)]
+	pub pool_name: Option<String>,
+}
+
+/// Authentication guard requires before routing requests to actors of a namespace. Requests are
+/// verified before the actor is woken, and the verified identity is forwarded to the actor in
+/// `x-rivet-auth-*` headers.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuthPolicy {
+	pub rules: Vec<AuthRule>,
+}
+
+/// A request to an actor must be accepted by one of the rules that apply to it, otherwise it is
+/// rejected with a 401. Requests no rule applies to are not checked.
+///
+/// Credentials are read from the gateway token, or from the `Authorization: Bearer` header for
+/// HTTP requests.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuthRule {
+	/// Unique within the policy. Forwarded to the actor with the verified identity.
+	pub name: String,
+	/// Only applies to actors with this name.
+	#[serde(default)]
+	pub actor_name: Option<String>,
+	pub method: AuthMethod,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+	/// JSON Web Tokens signed with RS256, ES256, or EdDSA by a key in a JWKS. The `sub` claim is
+	/// forwarded as the subject.
+	Jwt {
+		/// URL of the JWKS. Must be https on a public host. Guard caches it for 5 minutes.
+		#[serde(default)]
+		jwks_url: Option<String>,
+		/// Inline JWKS, used instead of `jwks_url`.
+		#[serde(default)]
+		jwks: Option<Jwks>,
+		/// Required `iss` claim.
+		#[serde(default)]
+		issuer: Option<String>,
+		/// Required entry in the `aud` claim.
+		#[serde(default)]
+		audience: Option<String>,
+	},
+	/// Static API keys. The key name is forwarded as the subject.
+	ApiKey { keys: Vec<ApiKey> },
+	/// Short-lived tokens signed with a shared secret, for URLs handed to clients that cannot
+	/// hold credentials.
+	///
+	/// The token is `{expires}.{signature}`, where `expires` is a unix timestamp in seconds and
+	/// `signature` is the unpadded base64url HMAC-SHA256 of
+	/// `{expires}\n{method}\n{actor name}\n{actor key}\n{path}`. `method` is the HTTP method,
+	/// `GET` for websockets. `actor key` is the serialized actor key, empty for actors without one.
+	/// `path` is the path and query the actor receives, without gateway parameters.
+	SignedUrl {
+		/// At least 32 bytes. Never returned when reading the policy. Omit it on write to keep the
+		/// secret of the existing rule with the same name.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		secret: Option<String>,
+	},
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKey {
+	pub name: String,
+	/// Hex-encoded SHA-256 of the key, so keys are never stored.
+	pub sha256: String,
+}
+
+/// JSON Web Key Set.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Jwks {
+	pub keys: Vec<Jwk>,
+}
+
+/// Public key of a JWKS. Fields other than the ones used to verify signatures are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Jwk {
+	pub kty: String,
+	#[serde(default)]
+	pub kid: Option<String>,
+	#[serde(default)]
+	pub alg: Option<String>,
+	#[serde(default)]
+	pub crv: Option<String>,
+	/// RSA modulus.
+	#[serde(default)]
+	pub n: Option<String>,
+	/// RSA exponent.
+	#[serde(default)]
+	pub e: Option<String>,
+	#[serde(default)]
+	pub x: Option<String>,
+	#[serde(default)]
+	pub y: Option<String>,
+}
+
+/// Weighted routing of newly created actors to canary runner pools, so a new runner version can
+/// be rolled out to a share of actors before it replaces the current one.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CanaryPolicy {
+	pub rules: Vec<CanaryRule>,
+}
+
+/// Sends `weight` percent of the actors created for `pool_name` to `canary_pool_name` instead.
+/// Actors keep the pool they were created with, so lowering the weight does not move existing
+/// actors. Rules with an actor name take precedence over rules without one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CanaryRule {
+	pub pool_name: String,
+	pub canary_pool_name: String,
+	/// Percentage of created actors routed to the canary pool, between 0 and 100.
+	pub weight: u32,
+	/// Only applies to actors with this name.
+	#[serde(default)]
+	pub actor_name: Option<String>,
+}
+
+/// Where guard creates actors for `getOrCreate` requests that do not set a region. Requests that
+/// set a region always use it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RegionPolicy {
+	pub rules: Vec<RegionRule>,
+}
+
+/// Rules with an actor name take precedence over rules without one. Actors no rule applies to are
+/// created in the datacenter the request entered, or the closest one by ping that can run them.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RegionRule {
+	/// Only applies to actors with this name.
+	#[serde(default)]
+	pub actor_name: Option<String>,
+	pub mode: RegionMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionMode {
+	/// The datacenter closest to the client that sent the request, located with guard's GeoIP
+	/// database.
+	Nearest,
+	/// Always the given region.
+	Pinned { region: String },
+	/// The datacenter closest to the first client that created an actor with this name. Later
+	/// actors with the same name are created in the same region, wherever their client is.
+	FollowFirstClient,
+}
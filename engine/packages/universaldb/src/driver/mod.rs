@@ -12,9 +12,11 @@ use crate::{
 };
 
 pub mod postgres;
+pub mod replicated;
 pub mod rocksdb;
 
 pub use postgres::PostgresDatabaseDriver;
+pub use replicated::ReplicatedDatabaseDriver;
 pub use rocksdb::RocksDbDatabaseDriver;
 
 pub type BoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
@@ -1,12 +1,12 @@
-mod codec;
+pub(super) mod codec;
 mod commit;
 mod database;
-mod nats;
+pub(super) mod nats;
 mod resolver;
 mod shared;
 mod transaction;
 mod transaction_task;
-mod transport;
+pub(super) mod transport;
 
 pub use database::{PostgresConfig, PostgresDatabaseDriver, PostgresSslConfig};
 pub use nats::NatsConfig;
@@ -137,7 +137,7 @@ pub async fn run_commit_subscriber(
 
 /// FNV-1a 64-bit hash. Deterministic across processes (unlike `DefaultHasher`), used only to derive a
 /// stable cluster subject prefix.
-fn fnv1a_64(bytes: &[u8]) -> u64 {
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
 	const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
 	const PRIME: u64 = 0x0000_0100_0000_01b3;
 	let mut hash = OFFSET;
@@ -0,0 +1,144 @@
+use std::{
+	sync::Arc,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, bail};
+
+use crate::{
+	driver::{postgres::codec::decode_commit_request, rocksdb::transaction_task::TransactionTask},
+	tuple::Versionstamp,
+};
+
+use super::{
+	codec::Entry,
+	log::{self, APPLIED_KEY, REPLICATION_CF},
+	shared::ReplicatedShared,
+};
+
+/// Max committed entries applied in a single rocksdb transaction.
+const APPLY_BATCH_SIZE: usize = 256;
+/// Backoff before retrying after a failed apply.
+const APPLY_RETRY: Duration = Duration::from_secs(1);
+const GC_INTERVAL: Duration = Duration::from_secs(30);
+/// Failover dedup records older than this are garbage collected. Matches the postgres driver: must be
+/// well beyond the longest a member could spend resending a commit across a leader failover.
+const DEDUP_RECORD_MAX_AGE: Duration = Duration::from_secs(120);
+/// Applied entries retained in the log so lagging followers can catch up from it. A member that
+/// falls further behind must be re-seeded from a checkpoint.
+const LOG_RETAIN_ENTRIES: u64 = 100_000;
+
+/// Per-process apply loop. Applies committed entries to the local replica in log order whenever the
+/// commit index advances. Runs on every member, including the leader.
+pub async fn run_apply_loop(shared: Arc<ReplicatedShared>) {
+	loop {
+		if let Err(err) = apply_committed(&shared) {
+			tracing::error!(?err, "failed to apply udb log entries");
+			tokio::time::sleep(APPLY_RETRY).await;
+			continue;
+		}
+
+		shared.commit_advanced().await;
+	}
+}
+
+fn apply_committed(shared: &ReplicatedShared) -> Result<()> {
+	loop {
+		let applied = shared.read_version();
+		let commit_index = shared.commit_index();
+		if applied >= commit_index {
+			return Ok(());
+		}
+
+		let max = APPLY_BATCH_SIZE.min((commit_index - applied) as usize);
+		let entries = shared.log.entries(applied + 1, max)?;
+		let Some(last) = entries.last() else {
+			bail!("committed udb log entry {} missing", applied + 1);
+		};
+		let last_index = last.index;
+
+		apply_entries(shared, entries)?;
+		shared.set_applied(last_index);
+	}
+}
+
+/// Apply entries to the default column family in one rocksdb transaction, along with their dedup
+/// records and the new applied index, so a crash never leaves a partially applied entry.
+fn apply_entries(shared: &ReplicatedShared, entries: Vec<Entry>) -> Result<()> {
+	let cf = shared
+		.db
+		.cf_handle(REPLICATION_CF)
+		.context("replication column family missing")?;
+	let applied_at = now_millis();
+	let txn = shared.db.transaction();
+
+	let mut last_index = 0;
+	for entry in entries {
+		last_index = entry.index;
+		if entry.commit.is_empty() {
+			continue;
+		}
+
+		let commit = decode_commit_request(&entry.commit)?;
+		TransactionTask::apply_operations(&txn, commit.operations, &versionstamp(entry.stamp))?;
+
+		if !commit.client_node_id.is_empty() {
+			txn.put_cf(
+				&cf,
+				log::dedup_key(&commit.client_node_id, commit.client_seq),
+				entry.index.to_be_bytes(),
+			)
+			.context("failed to write dedup record")?;
+			txn.put_cf(
+				&cf,
+				log::dedup_ts_key(applied_at, &commit.client_node_id, commit.client_seq),
+				b"",
+			)
+			.context("failed to write dedup record")?;
+		}
+	}
+
+	txn.put_cf(&cf, APPLIED_KEY, last_index.to_be_bytes())
+		.context("failed to write applied index")?;
+	txn.commit()
+		.context("failed to commit applied udb log entries")?;
+
+	Ok(())
+}
+
+/// Periodically garbage-collect old dedup records and compact applied entries out of the log.
+pub async fn run_gc_loop(shared: Arc<ReplicatedShared>) {
+	let mut interval = tokio::time::interval(GC_INTERVAL);
+	interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+	loop {
+		interval.tick().await;
+
+		let before = now_millis().saturating_sub(DEDUP_RECORD_MAX_AGE.as_millis() as u64);
+		if let Err(err) = shared.log.gc_dedup(before) {
+			tracing::error!(?err, "failed udb dedup gc");
+		}
+
+		let below = shared.read_version().saturating_sub(LOG_RETAIN_ENTRIES);
+		if below > 0 {
+			if let Err(err) = shared.log.compact(below) {
+				tracing::error!(?err, "failed udb log compaction");
+			}
+		}
+	}
+}
+
+/// Build the versionstamp for every versionstamped operation in an entry from its leader-assigned
+/// stamp, so all members write identical versionstamps.
+fn versionstamp(stamp: u64) -> Versionstamp {
+	let mut bytes = [0u8; 12];
+	bytes[0..8].copy_from_slice(&stamp.to_be_bytes());
+	Versionstamp::from(bytes)
+}
+
+fn now_millis() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_millis() as u64
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use rivet_universaldb_replication::{self as proto, versioned};
+use vbare::OwnedVersionedData;
+
+pub use proto::{
+	AppendReply, AppendRequest, Entry, ReadIndexReply, ReadIndexRequest, VoteReply, VoteRequest,
+};
+
+/// Encode a log entry. Used both on the wire (inside an append request) and as the on-disk format of
+/// each member's local log.
+pub fn encode_entry(entry: Entry) -> Result<Vec<u8>> {
+	versioned::Entry::wrap_latest(entry).serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+/// Decode a log entry produced by [`encode_entry`].
+pub fn decode_entry(payload: &[u8]) -> Result<Entry> {
+	versioned::Entry::deserialize_with_embedded_version(payload)
+}
+
+pub fn encode_vote_request(request: VoteRequest) -> Result<Vec<u8>> {
+	versioned::VoteRequest::wrap_latest(request)
+		.serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+pub fn decode_vote_request(payload: &[u8]) -> Result<VoteRequest> {
+	versioned::VoteRequest::deserialize_with_embedded_version(payload)
+}
+
+pub fn encode_vote_reply(reply: VoteReply) -> Result<Vec<u8>> {
+	versioned::VoteReply::wrap_latest(reply)
+		.serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+pub fn decode_vote_reply(payload: &[u8]) -> Result<VoteReply> {
+	versioned::VoteReply::deserialize_with_embedded_version(payload)
+}
+
+pub fn encode_append_request(request: AppendRequest) -> Result<Vec<u8>> {
+	versioned::AppendRequest::wrap_latest(request)
+		.serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+pub fn decode_append_request(payload: &[u8]) -> Result<AppendRequest> {
+	versioned::AppendRequest::deserialize_with_embedded_version(payload)
+}
+
+pub fn encode_append_reply(reply: AppendReply) -> Result<Vec<u8>> {
+	versioned::AppendReply::wrap_latest(reply)
+		.serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+pub fn decode_append_reply(payload: &[u8]) -> Result<AppendReply> {
+	versioned::AppendReply::deserialize_with_embedded_version(payload)
+}
+
+pub fn encode_read_index_request(request: ReadIndexRequest) -> Result<Vec<u8>> {
+	versioned::ReadIndexRequest::wrap_latest(request)
+		.serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+pub fn decode_read_index_request(payload: &[u8]) -> Result<ReadIndexRequest> {
+	versioned::ReadIndexRequest::deserialize_with_embedded_version(payload)
+}
+
+pub fn encode_read_index_reply(reply: ReadIndexReply) -> Result<Vec<u8>> {
+	versioned::ReadIndexReply::wrap_latest(reply)
+		.serialize_with_embedded_version(proto::PROTOCOL_VERSION)
+}
+
+pub fn decode_read_index_reply(payload: &[u8]) -> Result<ReadIndexReply> {
+	versioned::ReadIndexReply::deserialize_with_embedded_version(payload)
+}
@@ -0,0 +1,137 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+	driver::postgres::{
+		codec::{decode_commit_reply, encode_commit_request},
+		transport::CommitOutcome,
+	},
+	error::DatabaseError,
+	options::ConflictRangeType,
+	tx_ops::{self, Operation},
+};
+
+use super::shared::{LeaderInfo, ReplicatedShared};
+
+/// How long to wait for a leader to be elected before giving up a submit as retryable.
+const LEADER_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Poll cadence while waiting for a leader to appear.
+const LEADER_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Per-attempt timeout for a NATS commit request. Covers a full replication round-trip.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(6);
+/// How many times a commit resends the same request (same dedup key) across leader failover before
+/// giving up as retryable.
+const MAX_SUBMIT_ATTEMPTS: usize = 8;
+/// Backoff between resends.
+const RESEND_BACKOFF: Duration = Duration::from_millis(100);
+/// How long a committed transaction waits for the local replica to apply its own commit, so the next
+/// transaction on this member reads its writes.
+const APPLY_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Submit a transaction's commit to the leader and await the result.
+///
+/// Mirrors the postgres multi-node submit path: the request is resent with the same dedup key across
+/// leader failover, so the leader applies it at most once. A read-only transaction submits nothing.
+pub async fn submit(
+	shared: &Arc<ReplicatedShared>,
+	read_version: u64,
+	operations: Vec<Operation>,
+	conflict_ranges: Vec<(Vec<u8>, Vec<u8>, ConflictRangeType)>,
+) -> Result<()> {
+	if tx_ops::is_read_only(&operations, &conflict_ranges) {
+		return Ok(());
+	}
+
+	let client_seq = shared.next_commit_seq();
+	let payload = encode_commit_request(
+		read_version,
+		&conflict_ranges,
+		&operations,
+		shared.node_id.as_bytes(),
+		client_seq,
+	)
+	.context("failed to encode commit request")?;
+
+	let submit_start = Instant::now();
+	for attempt in 0..MAX_SUBMIT_ATTEMPTS {
+		let leader = wait_for_leader(shared).await?;
+		let subject = shared.subjects.commit(&leader.leader_id);
+
+		let request = shared.client.request(subject, payload.clone().into());
+		match tokio::time::timeout(REQUEST_TIMEOUT, request).await {
+			Ok(Ok(msg)) => match decode_commit_reply(&msg.payload) {
+				Ok(CommitOutcome::Committed { commit_version }) => {
+					tracing::debug!(
+						client_seq,
+						attempt,
+						commit_version,
+						wait_ms = submit_start.elapsed().as_millis() as u64,
+						"udb commit resolved: committed"
+					);
+
+					if !shared
+						.wait_for_applied(commit_version as u64, APPLY_WAIT_TIMEOUT)
+						.await
+					{
+						tracing::warn!(
+							commit_version,
+							"udb member did not apply its own commit in time; later reads may be stale"
+						);
+					}
+
+					return Ok(());
+				}
+				Ok(CommitOutcome::Conflict) => {
+					return Err(DatabaseError::NotCommitted.into());
+				}
+				Err(err) => {
+					tracing::warn!(?err, client_seq, "malformed udb commit reply; resending");
+				}
+			},
+			// Indeterminate (no responder / transport error / timeout): the leader may have been
+			// deposed before or after committing. Resend the same dedup key.
+			Ok(Err(err)) => {
+				tracing::debug!(
+					?err,
+					client_seq,
+					attempt,
+					"udb commit request errored; resending"
+				);
+			}
+			Err(_) => {
+				tracing::debug!(
+					client_seq,
+					attempt,
+					"udb commit request timed out; resending"
+				);
+			}
+		}
+
+		tokio::time::sleep(RESEND_BACKOFF).await;
+	}
+
+	tracing::warn!(
+		client_seq,
+		wait_ms = submit_start.elapsed().as_millis() as u64,
+		"udb commit exhausted resend attempts; treating as not committed"
+	);
+	Err(DatabaseError::NotCommitted.into())
+}
+
+/// Wait for a known leader, returning a retryable error if none is elected in time.
+pub(super) async fn wait_for_leader(shared: &Arc<ReplicatedShared>) -> Result<LeaderInfo> {
+	let deadline = Instant::now() + LEADER_WAIT_TIMEOUT;
+	loop {
+		if let Some(leader) = shared.current_leader() {
+			return Ok(leader);
+		}
+		if Instant::now() >= deadline {
+			return Err(DatabaseError::NotCommitted.into());
+		}
+		tokio::time::sleep(LEADER_POLL_INTERVAL).await;
+	}
+}
@@ -0,0 +1,264 @@
+use std::{
+	collections::HashSet,
+	path::{Path, PathBuf},
+	sync::{
+		Arc,
+		atomic::{AtomicI32, Ordering},
+	},
+};
+
+use anyhow::{Context, Result, bail, ensure};
+use rocksdb::{OptimisticTransactionDB, Options, checkpoint::Checkpoint};
+use tokio::task::JoinHandle;
+
+use crate::{
+	RetryableTransaction, Transaction,
+	driver::{BoxFut, DatabaseDriver, Erased, postgres::NatsConfig},
+	error::DatabaseError,
+	transaction::TXN_TIMEOUT,
+	utils::{MaybeCommitted, calculate_tx_retry_backoff},
+};
+
+use super::{
+	apply, election,
+	log::{LogStore, REPLICATION_CF},
+	nats::{self, Subjects},
+	shared::{ReplicatedShared, Role},
+	transaction::ReplicatedTransactionDriver,
+};
+
+#[derive(Clone, Debug)]
+pub struct ReplicatedConfig {
+	/// Local RocksDB path of this member's replica.
+	pub path: PathBuf,
+	/// Id of this member. Must be one of `members` and stable across restarts.
+	pub node_id: String,
+	/// Ids of every member of the replica set, including this one. Must be an odd number (3 or 5)
+	/// so a majority quorum survives the loss of one (or two) members.
+	pub members: Vec<String>,
+	/// NATS deployment used for elections, log replication, and commit transport.
+	pub nats: NatsConfig,
+}
+
+/// Point-in-time replication status of a member, as seen by that member.
+#[derive(Clone, Debug)]
+pub struct ReplicationStatus {
+	pub epoch: u64,
+	/// Node id of the leader of `epoch`, if known.
+	pub leader: Option<String>,
+	pub is_leader: bool,
+	pub commit_index: u64,
+	pub applied_index: u64,
+}
+
+/// File system driver replicated across a small replica set of engine nodes. One member is elected
+/// leader per epoch; it resolves conflicts for every commit and replicates them through a log to a
+/// majority before acknowledging. Every member applies the committed log to its own RocksDB replica
+/// and serves reads from it once it has caught up to the leader's read index.
+pub struct ReplicatedDatabaseDriver {
+	shared: Arc<ReplicatedShared>,
+	max_retries: AtomicI32,
+	handles: Vec<JoinHandle<()>>,
+}
+
+impl ReplicatedDatabaseDriver {
+	pub async fn new(config: ReplicatedConfig) -> Result<Self> {
+		tracing::info!(
+			db_path=%config.path.display(),
+			node_id=%config.node_id,
+			members=?config.members,
+			"starting replicated file system driver"
+		);
+
+		let unique = config.members.iter().collect::<HashSet<_>>();
+		ensure!(
+			unique.len() == config.members.len(),
+			"replicated udb members must be unique"
+		);
+		ensure!(
+			config.members.len() % 2 == 1,
+			"replicated udb requires an odd number of members, got {}",
+			config.members.len()
+		);
+		if !unique.contains(&config.node_id) {
+			bail!(
+				"replicated udb node id {:?} is not one of the members",
+				config.node_id
+			);
+		}
+
+		std::fs::create_dir_all(&config.path).context("failed to create database directory")?;
+
+		let mut opts = Options::default();
+		opts.create_if_missing(true);
+		opts.create_missing_column_families(true);
+		opts.set_max_open_files(10000);
+		opts.set_keep_log_file_num(10);
+		opts.set_max_total_wal_size(64 * 1024 * 1024); // 64MiB
+		opts.set_write_buffer_size(256 * 1024 * 1024); // 256MiB for conflict detection
+
+		tracing::debug!(path=%config.path.display(), "opening rocksdb");
+		let db = Arc::new(
+			OptimisticTransactionDB::open_cf(&opts, &config.path, ["default", REPLICATION_CF])
+				.context("failed to open rocksdb")?,
+		);
+
+		let log = LogStore::open(db.clone())?;
+		let client = crate::driver::postgres::nats::connect(&config.nats).await?;
+		let subjects = Subjects::new(&config.members);
+		let peers = config
+			.members
+			.iter()
+			.filter(|member| **member != config.node_id)
+			.cloned()
+			.collect();
+
+		let shared = ReplicatedShared::new(db, log, config.node_id, peers, client, subjects)?;
+
+		// Serve votes and appends before the election timer can start an election, so this member
+		// never asks for votes it could not itself answer.
+		let rpc_shared = shared.clone();
+		let handles = vec![
+			tokio::spawn(async move {
+				if let Err(err) = nats::run_rpc_server(rpc_shared).await {
+					tracing::error!(?err, "udb replication rpc server failed");
+				}
+			}),
+			tokio::spawn(apply::run_apply_loop(shared.clone())),
+			tokio::spawn(apply::run_gc_loop(shared.clone())),
+			tokio::spawn(election::run_election_timer(shared.clone())),
+		];
+
+		Ok(ReplicatedDatabaseDriver {
+			shared,
+			max_retries: AtomicI32::new(10),
+			handles,
+		})
+	}
+
+	/// This member's current view of the replica set.
+	pub async fn status(&self) -> ReplicationStatus {
+		let state = self.shared.state.lock().await;
+		let leader = self
+			.shared
+			.current_leader()
+			.filter(|leader| leader.epoch == state.epoch)
+			.map(|leader| leader.leader_id);
+
+		ReplicationStatus {
+			epoch: state.epoch,
+			leader,
+			is_leader: state.role == Role::Leader,
+			commit_index: self.shared.commit_index(),
+			applied_index: self.shared.read_version(),
+		}
+	}
+
+	fn stop(&self) {
+		for handle in &self.handles {
+			handle.abort();
+		}
+		// Ends the leadership term, if any.
+		self.shared.shutdown.cancel();
+	}
+}
+
+impl DatabaseDriver for ReplicatedDatabaseDriver {
+	fn create_txn(&self) -> Result<Transaction> {
+		Ok(Transaction::new(Arc::new(
+			ReplicatedTransactionDriver::new(self.shared.clone()),
+		)))
+	}
+
+	fn run<'a>(
+		&'a self,
+		closure: Box<dyn Fn(RetryableTransaction) -> BoxFut<'a, Result<Erased>> + Send + Sync + 'a>,
+	) -> BoxFut<'a, Result<Erased>> {
+		Box::pin(async move {
+			let mut maybe_committed = MaybeCommitted(false);
+			let max_retries = self.max_retries.load(Ordering::SeqCst);
+
+			for attempt in 0..max_retries {
+				let tx = self.create_txn()?;
+				let mut retryable = RetryableTransaction::new(tx);
+				retryable.maybe_committed = maybe_committed;
+
+				// Execute transaction
+				let error =
+					match tokio::time::timeout(TXN_TIMEOUT, closure(retryable.clone())).await {
+						Ok(Ok(res)) => match retryable.inner.driver.commit_ref().await {
+							Ok(_) => return Ok(res),
+							Err(e) => e,
+						},
+						Ok(Err(e)) => e,
+						Err(_) => anyhow::Error::from(DatabaseError::TransactionTooOld),
+					};
+
+				let chain = error
+					.chain()
+					.find_map(|x| x.downcast_ref::<DatabaseError>());
+
+				if let Some(db_error) = chain {
+					// Handle retry or return error
+					if db_error.is_retryable() {
+						if db_error.is_maybe_committed() {
+							maybe_committed = MaybeCommitted(true);
+						}
+
+						let backoff_ms = calculate_tx_retry_backoff(attempt as usize);
+						tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
+						continue;
+					}
+				}
+
+				return Err(error);
+			}
+
+			Err(DatabaseError::MaxRetriesReached.into())
+		})
+	}
+
+	fn txn_retry_limit(&self, limit: i32) -> Result<()> {
+		self.max_retries.store(limit, Ordering::SeqCst);
+		Ok(())
+	}
+
+	/// Checkpoints this member's local replica, including its copy of the replication log.
+	fn checkpoint(&self, path: &Path) -> Result<()> {
+		let cp = Checkpoint::new(&*self.shared.db).context("failed to create checkpoint handle")?;
+		cp.create_checkpoint(path)
+			.context("failed to create rocksdb checkpoint")?;
+		Ok(())
+	}
+
+	fn shutdown<'a>(&'a self) -> BoxFut<'a, ()> {
+		Box::pin(async move {
+			let was_leader = self.shared.state.lock().await.role == Role::Leader;
+			self.stop();
+
+			// Wake the remaining members so they elect immediately instead of waiting out the
+			// election timeout.
+			if was_leader {
+				tracing::info!(node_id = %self.shared.node_id, "handing off udb leadership");
+				if let Err(err) = self
+					.shared
+					.client
+					.publish(self.shared.subjects.election(), Vec::new().into())
+					.await
+				{
+					tracing::debug!(?err, "failed to publish udb election wake");
+				}
+				let _ = self.shared.client.flush().await;
+			}
+		})
+	}
+}
+
+impl Drop for ReplicatedDatabaseDriver {
+	fn drop(&mut self) {
+		// Stop replying to votes and appends so a dropped member looks dead to the rest of the
+		// replica set, which then elects a new leader.
+		self.stop();
+		self.shared.db.cancel_all_background_work(true);
+	}
+}
@@ -0,0 +1,281 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use futures_util::{StreamExt, stream::FuturesUnordered};
+
+use super::{
+	codec::{self, AppendReply, AppendRequest, VoteReply, VoteRequest},
+	leader,
+	log::LogPosition,
+	shared::{LeaderInfo, ReplicatedShared, Role},
+};
+
+/// A follower that has not heard from a leader for a random duration in this range starts an
+/// election. Randomized so members rarely time out together and split the vote.
+pub const ELECTION_TIMEOUT_MIN: Duration = Duration::from_millis(1500);
+pub const ELECTION_TIMEOUT_MAX: Duration = Duration::from_millis(3000);
+/// How long a candidate waits for vote replies.
+const VOTE_TIMEOUT: Duration = Duration::from_millis(500);
+/// Upper bound on the jitter before electing after a departing leader's wake, so the remaining
+/// members do not all stand at once.
+const WAKE_JITTER_MAX_MS: u64 = 300;
+
+/// Per-process election timer. Starts an election whenever this member has not heard from a leader
+/// within its election timeout, or immediately (after a short jitter) when a departing leader
+/// publishes the election wake.
+pub async fn run_election_timer(shared: Arc<ReplicatedShared>) {
+	let mut wake = match shared.client.subscribe(shared.subjects.election()).await {
+		Ok(sub) => Some(sub),
+		Err(err) => {
+			tracing::warn!(
+				?err,
+				"failed to subscribe to udb election wake; relying on timeout"
+			);
+			None
+		}
+	};
+
+	// A single member replica set has no one to wait for.
+	if shared.peers.is_empty() {
+		if let Err(err) = run_election(&shared).await {
+			tracing::warn!(?err, "udb election errored");
+		}
+	}
+
+	loop {
+		let timeout = random_between(ELECTION_TIMEOUT_MIN, ELECTION_TIMEOUT_MAX);
+		let woke_at = tokio::select! {
+			_ = tokio::time::sleep(timeout) => None,
+			msg = async {
+				match &mut wake {
+					Some(sub) => sub.next().await,
+					None => std::future::pending().await,
+				}
+			} => {
+				if msg.is_none() {
+					wake = None;
+					continue;
+				}
+
+				let woke_at = Instant::now();
+				tokio::time::sleep(Duration::from_millis(
+					rand::random::<u64>() % WAKE_JITTER_MAX_MS,
+				))
+				.await;
+				Some(woke_at)
+			}
+		};
+
+		let should_elect = {
+			let state = shared.state.lock().await;
+			match woke_at {
+				// Skip if another member already won our vote (or became leader) since the wake.
+				Some(woke_at) => state.role != Role::Leader && state.last_heard < woke_at,
+				None => state.role != Role::Leader && state.last_heard.elapsed() >= timeout,
+			}
+		};
+
+		if should_elect {
+			if let Err(err) = run_election(&shared).await {
+				tracing::warn!(?err, "udb election errored");
+			}
+		}
+	}
+}
+
+/// Stand for election in a new epoch. Becomes leader and spawns the leadership term if a quorum
+/// grants its vote.
+async fn run_election(shared: &Arc<ReplicatedShared>) -> Result<()> {
+	let (epoch, last) = {
+		let mut state = shared.state.lock().await;
+		let epoch = state.epoch + 1;
+		shared.log.save_hard_state(epoch, Some(&shared.node_id))?;
+		state.epoch = epoch;
+		state.voted_for = Some(shared.node_id.clone());
+		state.role = Role::Candidate;
+		state.last_heard = Instant::now();
+
+		(epoch, shared.log.last())
+	};
+	shared.set_leader(None);
+
+	tracing::debug!(epoch, node_id = %shared.node_id, "starting udb election");
+
+	let payload = codec::encode_vote_request(VoteRequest {
+		epoch,
+		candidate_id: shared.node_id.clone(),
+		last_log_epoch: last.epoch,
+		last_log_index: last.index,
+	})?;
+
+	let mut votes = 1;
+	let mut replies = shared
+		.peers
+		.iter()
+		.map(|peer| {
+			let request = shared
+				.client
+				.request(shared.subjects.vote(peer), payload.clone().into());
+			tokio::time::timeout(VOTE_TIMEOUT, request)
+		})
+		.collect::<FuturesUnordered<_>>();
+
+	while votes < shared.quorum() {
+		let Some(res) = replies.next().await else {
+			break;
+		};
+		let Ok(Ok(msg)) = res else {
+			continue;
+		};
+		let reply = match codec::decode_vote_reply(&msg.payload) {
+			Ok(reply) => reply,
+			Err(err) => {
+				tracing::debug!(?err, "malformed udb vote reply");
+				continue;
+			}
+		};
+
+		if reply.epoch > epoch {
+			shared.observe_epoch(reply.epoch).await?;
+			return Ok(());
+		}
+		if reply.granted {
+			votes += 1;
+		}
+	}
+
+	if votes < shared.quorum() {
+		tracing::debug!(epoch, votes, "lost udb election");
+		return Ok(());
+	}
+
+	let mut state = shared.state.lock().await;
+	if state.epoch != epoch || state.role != Role::Candidate {
+		// A newer epoch started while votes were in flight.
+		return Ok(());
+	}
+
+	let term = shared.shutdown.child_token();
+	state.role = Role::Leader;
+	state.term = Some(term.clone());
+	drop(state);
+
+	tracing::info!(epoch, votes, node_id = %shared.node_id, "won udb election");
+
+	tokio::spawn(leader::lead(shared.clone(), epoch, term));
+
+	Ok(())
+}
+
+/// Handle a candidate's vote request. A vote is granted at most once per epoch, and only to a
+/// candidate whose log is at least as up to date as ours, so a new leader always holds every
+/// committed entry.
+pub async fn handle_vote(shared: &ReplicatedShared, request: VoteRequest) -> Result<VoteReply> {
+	let mut state = shared.state.lock().await;
+
+	if request.epoch > state.epoch {
+		shared.step_down(&mut state, request.epoch)?;
+	}
+	if request.epoch < state.epoch {
+		return Ok(VoteReply {
+			epoch: state.epoch,
+			granted: false,
+		});
+	}
+
+	let candidate_last = LogPosition {
+		epoch: request.last_log_epoch,
+		index: request.last_log_index,
+	};
+	let can_vote = state
+		.voted_for
+		.as_deref()
+		.is_none_or(|voted_for| voted_for == request.candidate_id);
+	let granted = can_vote && candidate_last >= shared.log.last();
+
+	if granted {
+		shared
+			.log
+			.save_hard_state(state.epoch, Some(&request.candidate_id))?;
+		state.voted_for = Some(request.candidate_id);
+		state.last_heard = Instant::now();
+	}
+
+	Ok(VoteReply {
+		epoch: state.epoch,
+		granted,
+	})
+}
+
+/// Handle the leader's append request: check that our log matches the leader's at `prev_log_index`,
+/// merge the new entries, and advance the commit index. Rejected appends carry a hint of where the
+/// leader should retry from.
+pub async fn handle_append(
+	shared: &ReplicatedShared,
+	request: AppendRequest,
+) -> Result<AppendReply> {
+	let mut state = shared.state.lock().await;
+
+	if request.epoch < state.epoch {
+		// A deposed leader. The reply carries our epoch so it steps down.
+		return Ok(AppendReply {
+			epoch: state.epoch,
+			success: false,
+			match_index: 0,
+		});
+	}
+	if request.epoch > state.epoch || state.role != Role::Follower {
+		shared.step_down(&mut state, request.epoch)?;
+	}
+
+	state.last_heard = Instant::now();
+	shared.set_leader(Some(LeaderInfo {
+		epoch: request.epoch,
+		leader_id: request.leader_id,
+	}));
+
+	// Applied entries are committed, so they match every future leader's log (and may already be
+	// compacted away).
+	let applied = shared.read_version();
+	if request.prev_log_index > applied {
+		let prev_epoch = shared.log.epoch_at(request.prev_log_index)?;
+		if prev_epoch != Some(request.prev_log_epoch) {
+			let hint = shared
+				.log
+				.last()
+				.index
+				.min(request.prev_log_index - 1)
+				.max(applied);
+
+			return Ok(AppendReply {
+				epoch: state.epoch,
+				success: false,
+				match_index: hint,
+			});
+		}
+	}
+
+	let match_index = request.prev_log_index + request.entries.len() as u64;
+	let entries = request
+		.entries
+		.into_iter()
+		.filter(|entry| entry.index > applied)
+		.collect();
+	shared.log.merge(entries)?;
+
+	shared.advance_commit_index(request.commit_index.min(match_index));
+
+	Ok(AppendReply {
+		epoch: state.epoch,
+		success: true,
+		match_index,
+	})
+}
+
+fn random_between(min: Duration, max: Duration) -> Duration {
+	let spread = (max - min).as_millis() as u64;
+	min + Duration::from_millis(rand::random::<u64>() % spread.max(1))
+}
@@ -0,0 +1,526 @@
+use std::{
+	collections::HashMap,
+	sync::Arc,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result, bail};
+use futures_util::{FutureExt, StreamExt, stream::FuturesUnordered};
+use tokio::sync::{Mutex, Notify, mpsc, watch};
+use tokio_util::{sync::CancellationToken, task::AbortOnDropHandle};
+
+use crate::{
+	conflict_tracker::TransactionConflictTracker,
+	driver::postgres::{
+		codec::encode_commit_request,
+		nats::run_commit_subscriber,
+		transport::{COMMIT_QUEUE_BOUND, CommitJob, CommitOutcome},
+	},
+	transaction::TXN_TIMEOUT,
+};
+
+use super::{
+	codec::{self, AppendRequest, Entry, ReadIndexReply},
+	election::ELECTION_TIMEOUT_MAX,
+	shared::{LeaderInfo, ReplicatedShared, Role},
+};
+
+/// Max commits resolved and appended per batch (group commit). Amortizes the replication round-trip
+/// and fsync across the batch.
+const DRAIN_BATCH_SIZE: usize = 256;
+/// How often the leader sends heartbeats (empty appends) so followers do not start an election. Must
+/// be well under `ELECTION_TIMEOUT_MIN`.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(300);
+/// Per-request timeout for an append sent to a single follower.
+const APPEND_TIMEOUT: Duration = Duration::from_secs(1);
+/// Max entries shipped in a single append request to a lagging follower.
+const MAX_APPEND_ENTRIES: usize = 512;
+/// How long a batch may wait for a quorum of followers before the leader gives up and steps down.
+const REPLICATE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long the leader waits for its own replica to apply a committed batch before responding.
+const APPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Replication progress of a single follower.
+struct Peer {
+	id: String,
+	progress: Mutex<Progress>,
+}
+
+struct Progress {
+	/// Next log index to send.
+	next_index: u64,
+	/// Highest log index known to be persisted by the follower.
+	match_index: u64,
+}
+
+/// A single leadership term.
+struct Term {
+	shared: Arc<ReplicatedShared>,
+	epoch: u64,
+	peers: Vec<Peer>,
+	/// Pinged to send an append to every follower right away instead of at the next heartbeat tick.
+	heartbeat_now: Notify,
+	/// Set once the term's no-op entry is committed. Read indexes are only handed out after this,
+	/// since entries from earlier epochs may not be known to be committed before then.
+	epoch_committed: watch::Sender<bool>,
+}
+
+/// Leader entry point: commit the epoch, then resolve and replicate commits until this member is
+/// deposed, loses contact with a quorum, or shuts down.
+pub async fn lead(shared: Arc<ReplicatedShared>, epoch: u64, token: CancellationToken) {
+	let last = shared.log.last();
+	let term = Arc::new(Term {
+		shared: shared.clone(),
+		epoch,
+		peers: shared
+			.peers
+			.iter()
+			.map(|id| Peer {
+				id: id.clone(),
+				progress: Mutex::new(Progress {
+					next_index: last.index + 1,
+					match_index: 0,
+				}),
+			})
+			.collect(),
+		heartbeat_now: Notify::new(),
+		epoch_committed: watch::Sender::new(false),
+	});
+
+	shared.set_leader(Some(LeaderInfo {
+		epoch,
+		leader_id: shared.node_id.clone(),
+	}));
+
+	// Each term gets its own NATS-fed commit queue. Dropping the subscriber on step-down stops
+	// accepting commits; followers resend to the next leader.
+	let (tx, mut rx) = mpsc::channel(COMMIT_QUEUE_BOUND);
+	let _subscriber = AbortOnDropHandle::new(tokio::spawn(run_commit_subscriber(
+		shared.client.clone(),
+		shared.subjects.commit(&shared.node_id),
+		tx,
+	)));
+	let mut heartbeat = AbortOnDropHandle::new(tokio::spawn(heartbeat_loop(term.clone())));
+	let mut read_index = AbortOnDropHandle::new(tokio::spawn(read_index_loop(term.clone())));
+
+	let res = tokio::select! {
+		_ = token.cancelled() => Ok(()),
+		res = &mut heartbeat => res.context("udb heartbeat task failed").and_then(|res| res),
+		res = &mut read_index => res.context("udb read index task failed").and_then(|res| res),
+		res = drain_loop(&term, &mut rx) => res,
+	};
+	if let Err(err) = res {
+		tracing::warn!(?err, epoch, "udb leader term ended");
+	}
+
+	{
+		let mut state = shared.state.lock().await;
+		if state.epoch == epoch && state.role == Role::Leader {
+			if let Err(err) = shared.step_down(&mut state, epoch) {
+				tracing::error!(?err, "failed to step down from udb leader");
+			}
+		}
+	}
+	shared.set_leader(None);
+
+	tracing::info!(epoch, node_id = %shared.node_id, "stepped down from udb leader");
+}
+
+/// Sends an append to every follower each heartbeat interval, catching up lagging followers and
+/// delivering the commit index. Returns (stepping down) if a quorum has been unreachable for longer
+/// than the election timeout, since the other members will have elected a new leader by then.
+async fn heartbeat_loop(term: Arc<Term>) -> Result<()> {
+	let mut last_quorum_contact = Instant::now();
+
+	loop {
+		if term.confirm_quorum().await {
+			last_quorum_contact = Instant::now();
+		} else if last_quorum_contact.elapsed() > ELECTION_TIMEOUT_MAX {
+			bail!("lost contact with a quorum of udb members");
+		}
+
+		tokio::select! {
+			_ = tokio::time::sleep(HEARTBEAT_INTERVAL) => {}
+			_ = term.heartbeat_now.notified() => {}
+		}
+	}
+}
+
+/// Answers read index requests for the term (see `read_index::sync`). Requests that queue up while a
+/// quorum confirmation is in flight are answered together by the next one.
+async fn read_index_loop(term: Arc<Term>) -> Result<()> {
+	let shared = &term.shared;
+	let mut sub = shared
+		.client
+		.subscribe(shared.subjects.read_index(&shared.node_id))
+		.await
+		.context("failed to subscribe to udb read index subject")?;
+
+	term.epoch_committed
+		.subscribe()
+		.wait_for(|committed| *committed)
+		.await
+		.context("udb term ended before committing its epoch")?;
+
+	loop {
+		let Some(msg) = sub.next().await else {
+			bail!("udb read index subscription closed");
+		};
+		let mut batch = vec![msg];
+		while let Some(Some(msg)) = sub.next().now_or_never() {
+			batch.push(msg);
+		}
+
+		// Everything acknowledged before these requests were sent is at or below the commit index
+		// now. It is only safe to hand out if this member still leads the epoch.
+		let read_index = shared.commit_index();
+		let success = term.confirm_quorum().await;
+
+		let payload = codec::encode_read_index_reply(ReadIndexReply {
+			epoch: term.epoch,
+			success,
+			read_index,
+		})?;
+		for msg in batch {
+			if let Err(err) = codec::decode_read_index_request(&msg.payload) {
+				tracing::warn!(?err, "malformed udb read index request");
+				continue;
+			}
+			let Some(reply_subject) = msg.reply else {
+				continue;
+			};
+			if let Err(err) = shared
+				.client
+				.publish(reply_subject, payload.clone().into())
+				.await
+			{
+				tracing::debug!(?err, "failed to publish udb read index reply");
+			}
+		}
+
+		if !success {
+			tracing::debug!(
+				epoch = term.epoch,
+				"udb leader failed to confirm read index"
+			);
+		}
+	}
+}
+
+/// Commit the epoch with a no-op entry, then drain batches of commit jobs until the queue closes.
+async fn drain_loop(term: &Term, rx: &mut mpsc::Receiver<CommitJob>) -> Result<()> {
+	let shared = &term.shared;
+	let mut stamp = last_stamp(shared)?;
+
+	// Entries from earlier epochs are only committed indirectly, by committing an entry from this
+	// epoch after them. Once the no-op is applied, the dedup records of every earlier commit are also
+	// visible to the dedup check below.
+	let noop_index = shared.log.last().index + 1;
+	term.append_and_commit(vec![Entry {
+		epoch: term.epoch,
+		index: noop_index,
+		stamp: next_stamp(&mut stamp),
+		commit: Vec::new(),
+	}])
+	.await?;
+	term.epoch_committed.send_replace(true);
+
+	// The cold-window floor, as in the postgres resolver: the tracker starts empty, so reject commits
+	// whose read version predates this epoch until the window warms (one TXN_TIMEOUT).
+	let recovery_version = noop_index;
+	let recovery_deadline = Instant::now() + TXN_TIMEOUT;
+	let tracker = TransactionConflictTracker::new();
+
+	tracing::debug!(
+		epoch = term.epoch,
+		recovery_version,
+		"udb leader entering drain loop"
+	);
+
+	loop {
+		let mut batch = Vec::with_capacity(DRAIN_BATCH_SIZE);
+		rx.recv_many(&mut batch, DRAIN_BATCH_SIZE).await;
+		if batch.is_empty() {
+			return Ok(());
+		}
+
+		term.drain_batch(
+			&tracker,
+			recovery_version,
+			recovery_deadline,
+			&mut stamp,
+			batch,
+		)
+		.await?;
+	}
+}
+
+impl Term {
+	async fn drain_batch(
+		&self,
+		tracker: &TransactionConflictTracker,
+		recovery_version: u64,
+		recovery_deadline: Instant,
+		stamp: &mut u64,
+		mut jobs: Vec<CommitJob>,
+	) -> Result<()> {
+		let shared = &self.shared;
+		let batch_start = Instant::now();
+		let cold_window = Instant::now() < recovery_deadline;
+
+		let mut outcomes = vec![None; jobs.len()];
+		let mut entries = Vec::new();
+		let mut batch_dedup = HashMap::new();
+		let mut next_index = shared.log.last().index + 1;
+
+		for (i, job) in jobs.iter_mut().enumerate() {
+			// A commit already applied by a prior leader (its reply was lost to a failover) or
+			// resent within this batch responds with the recorded version and is not applied again.
+			if let Some(key) = &job.dedup_key {
+				let client_seq = key.client_seq as u64;
+				let applied = shared
+					.log
+					.applied_commit(&key.client_node_id, client_seq)?
+					.or_else(|| {
+						batch_dedup
+							.get(&(key.client_node_id.clone(), client_seq))
+							.copied()
+					});
+				if let Some(index) = applied {
+					outcomes[i] = Some(CommitOutcome::Committed {
+						commit_version: index as i64,
+					});
+					continue;
+				}
+			}
+
+			let conflicted = if cold_window && job.read_version < recovery_version {
+				true
+			} else {
+				tracker
+					.check_and_insert(
+						job.read_version,
+						next_index,
+						std::mem::take(&mut job.conflict_ranges),
+					)
+					.await
+			};
+			if conflicted {
+				outcomes[i] = Some(CommitOutcome::Conflict);
+				continue;
+			}
+
+			let (client_node_id, client_seq) = job
+				.dedup_key
+				.as_ref()
+				.map(|key| (key.client_node_id.clone(), key.client_seq as u64))
+				.unwrap_or_default();
+			let commit = encode_commit_request(
+				job.read_version,
+				&[],
+				&job.operations,
+				&client_node_id,
+				client_seq,
+			)
+			.context("failed to encode log entry commit")?;
+			if !client_node_id.is_empty() {
+				batch_dedup.insert((client_node_id, client_seq), next_index);
+			}
+
+			entries.push(Entry {
+				epoch: self.epoch,
+				index: next_index,
+				stamp: next_stamp(stamp),
+				commit,
+			});
+			outcomes[i] = Some(CommitOutcome::Committed {
+				commit_version: next_index as i64,
+			});
+			next_index += 1;
+		}
+
+		let committed = entries.len();
+		if !entries.is_empty() {
+			// On failure the term ends without responding. Followers resend to the next leader, which
+			// dedups any entry that did commit.
+			self.append_and_commit(entries).await?;
+		}
+
+		futures_util::stream::iter(jobs.into_iter().zip(outcomes))
+			.for_each_concurrent(None, |(job, outcome)| async move {
+				job.responder
+					.respond(outcome.expect("every job must be resolved"))
+					.await;
+			})
+			.await;
+
+		tracing::debug!(
+			epoch = self.epoch,
+			committed,
+			cold_window,
+			commit_index = shared.commit_index(),
+			batch_ms = batch_start.elapsed().as_millis() as u64,
+			"udb leader processed commit batch"
+		);
+
+		Ok(())
+	}
+
+	/// Append entries to the local log, replicate them to a quorum, and wait until they are applied
+	/// locally so the leader's own reads observe them.
+	async fn append_and_commit(&self, entries: Vec<Entry>) -> Result<()> {
+		let shared = &self.shared;
+		let target = entries.last().context("no entries to commit")?.index;
+
+		shared.log.append(&entries)?;
+		self.replicate(target).await?;
+		shared.advance_commit_index(target);
+
+		// Deliver the new commit index to followers now rather than at the next heartbeat.
+		self.heartbeat_now.notify_one();
+
+		if !shared.wait_for_applied(target, APPLY_TIMEOUT).await {
+			bail!("timed out applying udb log entry {target}");
+		}
+
+		Ok(())
+	}
+
+	/// Send one round of appends and return whether a quorum (counting the leader) still accepts this
+	/// term.
+	async fn confirm_quorum(&self) -> bool {
+		let acks = 1 + self
+			.peers
+			.iter()
+			.map(|peer| self.send_append(peer))
+			.collect::<FuturesUnordered<_>>()
+			.filter(|res| std::future::ready(matches!(res, Ok(true))))
+			.count()
+			.await;
+
+		acks >= self.shared.quorum()
+	}
+
+	/// Wait until a quorum (counting the leader) has persisted every entry up to `target`.
+	async fn replicate(&self, target: u64) -> Result<()> {
+		let quorum = self.shared.quorum();
+		let mut acks = 1;
+		if acks >= quorum {
+			return Ok(());
+		}
+
+		let mut pending = self
+			.peers
+			.iter()
+			.map(|peer| self.sync_peer(peer, target))
+			.collect::<FuturesUnordered<_>>();
+		let deadline = tokio::time::sleep(REPLICATE_TIMEOUT);
+		tokio::pin!(deadline);
+
+		loop {
+			tokio::select! {
+				res = pending.next() => match res {
+					Some(Ok(())) => {
+						acks += 1;
+						if acks >= quorum {
+							return Ok(());
+						}
+					}
+					Some(Err(err)) => {
+						tracing::debug!(?err, target, "failed to replicate udb log to member");
+					}
+					None => bail!("failed to replicate udb log entry {target} to a quorum"),
+				},
+				_ = &mut deadline => bail!("timed out replicating udb log entry {target}"),
+			}
+		}
+	}
+
+	/// Send appends to a single follower until it has persisted `target`.
+	async fn sync_peer(&self, peer: &Peer, target: u64) -> Result<()> {
+		loop {
+			self.send_append(peer).await?;
+			if peer.progress.lock().await.match_index >= target {
+				return Ok(());
+			}
+		}
+	}
+
+	/// Send one append to a follower, carrying the entries it is missing (up to
+	/// [`MAX_APPEND_ENTRIES`]). Returns whether the follower accepted it.
+	async fn send_append(&self, peer: &Peer) -> Result<bool> {
+		let shared = &self.shared;
+		let mut progress = peer.progress.lock().await;
+
+		let prev_log_index = progress.next_index - 1;
+		let Some(prev_log_epoch) = shared.log.epoch_at(prev_log_index)? else {
+			bail!(
+				"udb member {} is missing compacted log entry {prev_log_index}; re-seed it from a \
+				checkpoint",
+				peer.id
+			);
+		};
+		let entries = shared
+			.log
+			.entries(progress.next_index, MAX_APPEND_ENTRIES)?;
+
+		let payload = codec::encode_append_request(AppendRequest {
+			epoch: self.epoch,
+			leader_id: shared.node_id.clone(),
+			prev_log_index,
+			prev_log_epoch,
+			entries,
+			commit_index: shared.commit_index(),
+		})?;
+		let request = shared
+			.client
+			.request(shared.subjects.append(&peer.id), payload.into());
+		let msg = tokio::time::timeout(APPEND_TIMEOUT, request)
+			.await
+			.context("udb append timed out")?
+			.context("udb append failed")?;
+		let reply = codec::decode_append_reply(&msg.payload)?;
+
+		if reply.epoch > self.epoch {
+			drop(progress);
+			shared.observe_epoch(reply.epoch).await?;
+			bail!("deposed by udb epoch {}", reply.epoch);
+		}
+
+		if reply.success {
+			progress.match_index = progress.match_index.max(reply.match_index);
+			progress.next_index = reply.match_index + 1;
+		} else {
+			// Walk back towards the point where the logs agree.
+			progress.next_index = (reply.match_index + 1).clamp(1, prev_log_index.max(1));
+		}
+
+		Ok(reply.success)
+	}
+}
+
+/// Versionstamp version of the last entry in the log, or 0 if the log is empty.
+fn last_stamp(shared: &ReplicatedShared) -> Result<u64> {
+	let last = shared.log.last();
+	if last.index == 0 {
+		return Ok(0);
+	}
+
+	Ok(shared
+		.log
+		.entries(last.index, 1)?
+		.first()
+		.map(|entry| entry.stamp)
+		.unwrap_or(0))
+}
+
+/// Next versionstamp version: wall clock microseconds, forced monotonic. Using wall clock keeps
+/// versionstamps ordered after ones written by the single-node file system driver.
+fn next_stamp(stamp: &mut u64) -> u64 {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+		.as_micros() as u64;
+	*stamp = now.max(*stamp + 1);
+	*stamp
+}
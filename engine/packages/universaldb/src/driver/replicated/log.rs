@@ -0,0 +1,339 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result, bail};
+use rocksdb::{
+	BoundColumnFamily, Direction, IteratorMode, OptimisticTransactionDB, WriteBatchWithTransaction,
+	WriteOptions,
+};
+
+use super::codec::{self, Entry};
+
+/// Column family holding the replication state. User data lives in the default column family so the
+/// read path is identical to the single-node file system driver.
+pub const REPLICATION_CF: &str = "replication";
+
+const EPOCH_KEY: &[u8] = b"epoch";
+const VOTED_FOR_KEY: &[u8] = b"voted_for";
+/// Highest log index whose operations have been applied to the default column family. Written in the
+/// same rocksdb transaction as the applied data.
+pub const APPLIED_KEY: &[u8] = b"applied";
+const LOG_PREFIX: u8 = b'l';
+/// `d{client_node_id}{client_seq}` -> commit index of an applied commit, for failover dedup.
+const DEDUP_PREFIX: u8 = b'd';
+/// `t{applied_at}{client_node_id}{client_seq}`, indexing dedup records by age for garbage collection.
+const DEDUP_TS_PREFIX: u8 = b't';
+
+/// Position of a log entry. Ordered by epoch first, which is the "at least as up to date" comparison
+/// used when granting votes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogPosition {
+	pub epoch: u64,
+	pub index: u64,
+}
+
+/// Durable replication state of a single member: the election hard state (epoch and vote) and the
+/// replicated log. Every write is synced before returning, since a member must never forget a vote or
+/// an acknowledged entry across a crash.
+pub struct LogStore {
+	db: Arc<OptimisticTransactionDB>,
+	/// Cached position of the last entry in the log.
+	last: Mutex<LogPosition>,
+}
+
+impl LogStore {
+	pub fn open(db: Arc<OptimisticTransactionDB>) -> Result<Self> {
+		let store = LogStore {
+			db,
+			last: Mutex::new(LogPosition::default()),
+		};
+
+		let last = {
+			let cf = store.cf()?;
+			let mut iter = store.db.iterator_cf(
+				&cf,
+				IteratorMode::From(&log_key(u64::MAX), Direction::Reverse),
+			);
+			match iter.next() {
+				Some(res) => {
+					let (key, value) = res.context("failed to read last log entry")?;
+					if key.first() == Some(&LOG_PREFIX) {
+						let entry = codec::decode_entry(&value)?;
+						LogPosition {
+							epoch: entry.epoch,
+							index: entry.index,
+						}
+					} else {
+						LogPosition::default()
+					}
+				}
+				None => LogPosition::default(),
+			}
+		};
+		*store.last.lock().expect("poisoned") = last;
+
+		Ok(store)
+	}
+
+	fn cf(&self) -> Result<Arc<BoundColumnFamily<'_>>> {
+		self.db
+			.cf_handle(REPLICATION_CF)
+			.context("replication column family missing")
+	}
+
+	fn write(&self, batch: WriteBatchWithTransaction<true>) -> Result<()> {
+		let mut write_opts = WriteOptions::default();
+		write_opts.set_sync(true);
+		self.db
+			.write_opt(batch, &write_opts)
+			.context("failed to write replication state")
+	}
+
+	/// The persisted election epoch and the member voted for in that epoch.
+	pub fn hard_state(&self) -> Result<(u64, Option<String>)> {
+		let cf = self.cf()?;
+		let epoch = self
+			.db
+			.get_cf(&cf, EPOCH_KEY)
+			.context("failed to read epoch")?
+			.map(|v| decode_u64(&v))
+			.transpose()?
+			.unwrap_or(0);
+		let voted_for = self
+			.db
+			.get_cf(&cf, VOTED_FOR_KEY)
+			.context("failed to read vote")?
+			.map(String::from_utf8)
+			.transpose()
+			.context("invalid vote")?;
+
+		Ok((epoch, voted_for))
+	}
+
+	pub fn save_hard_state(&self, epoch: u64, voted_for: Option<&str>) -> Result<()> {
+		let cf = self.cf()?;
+		let mut batch = WriteBatchWithTransaction::<true>::default();
+		batch.put_cf(&cf, EPOCH_KEY, epoch.to_be_bytes());
+		match voted_for {
+			Some(voted_for) => batch.put_cf(&cf, VOTED_FOR_KEY, voted_for.as_bytes()),
+			None => batch.delete_cf(&cf, VOTED_FOR_KEY),
+		}
+		self.write(batch)
+	}
+
+	pub fn applied_index(&self) -> Result<u64> {
+		let cf = self.cf()?;
+		Ok(self
+			.db
+			.get_cf(&cf, APPLIED_KEY)
+			.context("failed to read applied index")?
+			.map(|v| decode_u64(&v))
+			.transpose()?
+			.unwrap_or(0))
+	}
+
+	pub fn last(&self) -> LogPosition {
+		*self.last.lock().expect("poisoned")
+	}
+
+	/// Epoch of the entry at `index`. Index 0 is the empty prefix every log shares. Returns `None` if
+	/// the entry does not exist (past the end of the log, or compacted away).
+	pub fn epoch_at(&self, index: u64) -> Result<Option<u64>> {
+		if index == 0 {
+			return Ok(Some(0));
+		}
+
+		let cf = self.cf()?;
+		self.db
+			.get_cf(&cf, log_key(index))
+			.context("failed to read log entry")?
+			.map(|v| codec::decode_entry(&v).map(|entry| entry.epoch))
+			.transpose()
+	}
+
+	/// Up to `max` consecutive entries starting at `from`.
+	pub fn entries(&self, from: u64, max: usize) -> Result<Vec<Entry>> {
+		let cf = self.cf()?;
+		let iter = self
+			.db
+			.iterator_cf(&cf, IteratorMode::From(&log_key(from), Direction::Forward));
+
+		let mut entries = Vec::new();
+		for res in iter {
+			if entries.len() >= max {
+				break;
+			}
+
+			let (key, value) = res.context("failed to read log entry")?;
+			if key.first() != Some(&LOG_PREFIX) {
+				break;
+			}
+			entries.push(codec::decode_entry(&value)?);
+		}
+
+		if let Some(first) = entries.first() {
+			if first.index != from {
+				bail!(
+					"log entry {from} was compacted (oldest retained is {})",
+					first.index
+				);
+			}
+		}
+
+		Ok(entries)
+	}
+
+	/// Append entries to the end of the log. Used by the leader, whose log is authoritative.
+	pub fn append(&self, entries: &[Entry]) -> Result<()> {
+		let Some(last_entry) = entries.last() else {
+			return Ok(());
+		};
+
+		let cf = self.cf()?;
+		let mut batch = WriteBatchWithTransaction::<true>::default();
+		for entry in entries {
+			batch.put_cf(
+				&cf,
+				log_key(entry.index),
+				codec::encode_entry(entry.clone())?,
+			);
+		}
+		self.write(batch)?;
+
+		*self.last.lock().expect("poisoned") = LogPosition {
+			epoch: last_entry.epoch,
+			index: last_entry.index,
+		};
+
+		Ok(())
+	}
+
+	/// Merge entries received from the leader into the log. Entries already present with the same
+	/// epoch are skipped; the first entry that disagrees truncates the rest of the local log (an
+	/// uncommitted suffix from a deposed leader) before the leader's entries are written.
+	pub fn merge(&self, entries: Vec<Entry>) -> Result<()> {
+		let mut pending = Vec::with_capacity(entries.len());
+		let mut truncate_from = None;
+		for entry in entries {
+			if truncate_from.is_none() && pending.is_empty() {
+				match self.epoch_at(entry.index)? {
+					Some(epoch) if epoch == entry.epoch => continue,
+					Some(_) => truncate_from = Some(entry.index),
+					None => {}
+				}
+			}
+			pending.push(entry);
+		}
+
+		let Some(last_entry) = pending.last() else {
+			return Ok(());
+		};
+		let last = LogPosition {
+			epoch: last_entry.epoch,
+			index: last_entry.index,
+		};
+
+		let cf = self.cf()?;
+		let mut batch = WriteBatchWithTransaction::<true>::default();
+		if let Some(from) = truncate_from {
+			tracing::debug!(from, "truncating conflicting udb log suffix");
+			let end = self.last().index + 1;
+			for index in from..end.max(from) {
+				batch.delete_cf(&cf, log_key(index));
+			}
+		}
+		for entry in pending {
+			batch.put_cf(&cf, log_key(entry.index), codec::encode_entry(entry)?);
+		}
+		self.write(batch)?;
+
+		*self.last.lock().expect("poisoned") = last;
+
+		Ok(())
+	}
+
+	/// Drop every entry before `below`. Only applied entries may be compacted.
+	pub fn compact(&self, below: u64) -> Result<()> {
+		let cf = self.cf()?;
+		self.db
+			.delete_range_cf(&cf, log_key(0), log_key(below))
+			.context("failed to compact udb log")
+	}
+
+	/// Commit index of the commit with this failover dedup key, if it was already applied.
+	pub fn applied_commit(&self, client_node_id: &[u8], client_seq: u64) -> Result<Option<u64>> {
+		let cf = self.cf()?;
+		self.db
+			.get_cf(&cf, dedup_key(client_node_id, client_seq))
+			.context("failed to read dedup record")?
+			.map(|v| decode_u64(&v))
+			.transpose()
+	}
+
+	/// Delete dedup records applied before `before` (unix millis). Returns the number deleted.
+	pub fn gc_dedup(&self, before: u64) -> Result<usize> {
+		let cf = self.cf()?;
+		let iter = self.db.iterator_cf(
+			&cf,
+			IteratorMode::From(&[DEDUP_TS_PREFIX], Direction::Forward),
+		);
+
+		let mut batch = WriteBatchWithTransaction::<true>::default();
+		let mut count = 0;
+		for res in iter {
+			let (key, _) = res.context("failed to read dedup record")?;
+			if key.first() != Some(&DEDUP_TS_PREFIX) || key.len() < 9 {
+				break;
+			}
+			let applied_at = decode_u64(&key[1..9])?;
+			if applied_at >= before {
+				break;
+			}
+
+			let mut record_key = Vec::with_capacity(key.len() - 8);
+			record_key.push(DEDUP_PREFIX);
+			record_key.extend_from_slice(&key[9..]);
+			batch.delete_cf(&cf, record_key);
+			batch.delete_cf(&cf, &key);
+			count += 1;
+		}
+
+		if count > 0 {
+			self.db
+				.write(batch)
+				.context("failed to delete dedup records")?;
+		}
+
+		Ok(count)
+	}
+}
+
+/// Key of the dedup record for a commit. Written by the apply loop alongside the commit's data.
+pub fn dedup_key(client_node_id: &[u8], client_seq: u64) -> Vec<u8> {
+	let mut key = Vec::with_capacity(1 + client_node_id.len() + 8);
+	key.push(DEDUP_PREFIX);
+	key.extend_from_slice(client_node_id);
+	key.extend_from_slice(&client_seq.to_be_bytes());
+	key
+}
+
+/// Age index key of a dedup record.
+pub fn dedup_ts_key(applied_at: u64, client_node_id: &[u8], client_seq: u64) -> Vec<u8> {
+	let mut key = Vec::with_capacity(9 + client_node_id.len() + 8);
+	key.push(DEDUP_TS_PREFIX);
+	key.extend_from_slice(&applied_at.to_be_bytes());
+	key.extend_from_slice(client_node_id);
+	key.extend_from_slice(&client_seq.to_be_bytes());
+	key
+}
+
+fn log_key(index: u64) -> [u8; 9] {
+	let mut key = [0u8; 9];
+	key[0] = LOG_PREFIX;
+	key[1..].copy_from_slice(&index.to_be_bytes());
+	key
+}
+
+pub fn decode_u64(value: &[u8]) -> Result<u64> {
+	let bytes: [u8; 8] = value.try_into().context("invalid u64 value")?;
+	Ok(u64::from_be_bytes(bytes))
+}
@@ -0,0 +1,14 @@
+mod apply;
+mod codec;
+mod commit;
+mod database;
+mod election;
+mod leader;
+mod log;
+mod nats;
+mod read_index;
+mod shared;
+mod transaction;
+mod transaction_task;
+
+pub use database::{ReplicatedConfig, ReplicatedDatabaseDriver, ReplicationStatus};
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+
+use super::{codec, election, shared::ReplicatedShared};
+use crate::driver::postgres::nats::fnv1a_64;
+
+/// Replica-set-scoped UniversalDB NATS subjects. The prefix is derived from the sorted member list so
+/// two replica sets that share one NATS deployment do not cross-deliver votes, appends, or commits.
+#[derive(Clone)]
+pub struct Subjects {
+	prefix: String,
+}
+
+impl Subjects {
+	pub fn new(members: &[String]) -> Self {
+		let mut members = members.to_vec();
+		members.sort();
+
+		Subjects {
+			prefix: format!(
+				"udb.replicated.{:016x}",
+				fnv1a_64(members.join(",").as_bytes())
+			),
+		}
+	}
+
+	/// Subject a member sends a commit request to, and the elected leader subscribes to. Namespaced by
+	/// the leader's node id so only the current leader receives commits.
+	pub fn commit(&self, leader_id: &str) -> String {
+		format!("{}.commit.{leader_id}", self.prefix)
+	}
+
+	/// Subject a member asks the leader for a read index on. Namespaced by the leader's node id like
+	/// the commit subject.
+	pub fn read_index(&self, leader_id: &str) -> String {
+		format!("{}.read_index.{leader_id}", self.prefix)
+	}
+
+	/// Subject a member receives vote requests on.
+	pub fn vote(&self, node_id: &str) -> String {
+		format!("{}.vote.{node_id}", self.prefix)
+	}
+
+	/// Subject a member receives append requests (replication and heartbeats) on.
+	pub fn append(&self, node_id: &str) -> String {
+		format!("{}.append.{node_id}", self.prefix)
+	}
+
+	/// Subject a departing leader publishes to so the remaining members elect immediately.
+	pub fn election(&self) -> String {
+		format!("{}.election", self.prefix)
+	}
+}
+
+/// Serve this member's vote and append subjects. Requests are handled one at a time per subject; the
+/// handlers serialize on the election state anyway.
+pub async fn run_rpc_server(shared: Arc<ReplicatedShared>) -> Result<()> {
+	let mut vote_sub = shared
+		.client
+		.subscribe(shared.subjects.vote(&shared.node_id))
+		.await
+		.context("failed to subscribe to udb vote subject")?;
+	let mut append_sub = shared
+		.client
+		.subscribe(shared.subjects.append(&shared.node_id))
+		.await
+		.context("failed to subscribe to udb append subject")?;
+
+	loop {
+		let (msg, reply) = tokio::select! {
+			msg = vote_sub.next() => {
+				let Some(msg) = msg else { break };
+				let reply = match codec::decode_vote_request(&msg.payload) {
+					Ok(request) => election::handle_vote(&shared, request)
+						.await
+						.and_then(codec::encode_vote_reply),
+					Err(err) => Err(err),
+				};
+				(msg, reply)
+			}
+			msg = append_sub.next() => {
+				let Some(msg) = msg else { break };
+				let reply = match codec::decode_append_request(&msg.payload) {
+					Ok(request) => election::handle_append(&shared, request)
+						.await
+						.and_then(codec::encode_append_reply),
+					Err(err) => Err(err),
+				};
+				(msg, reply)
+			}
+		};
+
+		let Some(reply_subject) = msg.reply else {
+			tracing::warn!("udb replication request missing reply subject; dropping");
+			continue;
+		};
+
+		match reply {
+			Ok(payload) => {
+				if let Err(err) = shared.client.publish(reply_subject, payload.into()).await {
+					tracing::debug!(?err, "failed to publish udb replication reply");
+				}
+			}
+			// No reply: the requester times out and retries.
+			Err(err) => tracing::warn!(?err, "failed to handle udb replication request"),
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,81 @@
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::error::DatabaseError;
+
+use super::{
+	codec::{self, ReadIndexRequest},
+	commit::wait_for_leader,
+	shared::ReplicatedShared,
+};
+
+/// How long a transaction keeps asking for a read index across leader failover before giving up as
+/// retryable.
+const READ_INDEX_TIMEOUT: Duration = Duration::from_secs(5);
+/// Per-attempt timeout for a NATS read index request. Covers a heartbeat round-trip to a quorum.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+/// Backoff between attempts.
+const RETRY_BACKOFF: Duration = Duration::from_millis(50);
+/// How long to wait for the local replica to apply up to the read index.
+const APPLY_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Waits until the local replica has applied every entry the leader had committed when this was
+/// called. A snapshot opened afterwards observes every commit acknowledged before the transaction
+/// started, even on a follower.
+///
+/// The leader only hands out its commit index after a quorum confirms it still leads its epoch, so a
+/// deposed leader that has not noticed yet cannot serve stale reads either.
+pub async fn sync(shared: &Arc<ReplicatedShared>) -> Result<()> {
+	let deadline = Instant::now() + READ_INDEX_TIMEOUT;
+
+	loop {
+		let leader = wait_for_leader(shared).await?;
+		let payload = codec::encode_read_index_request(ReadIndexRequest {
+			epoch: leader.epoch,
+		})
+		.context("failed to encode read index request")?;
+
+		let request = shared.client.request(
+			shared.subjects.read_index(&leader.leader_id),
+			payload.into(),
+		);
+		match tokio::time::timeout(REQUEST_TIMEOUT, request).await {
+			Ok(Ok(msg)) => match codec::decode_read_index_reply(&msg.payload) {
+				Ok(reply) if reply.success => {
+					if shared
+						.wait_for_applied(reply.read_index, APPLY_WAIT_TIMEOUT)
+						.await
+					{
+						return Ok(());
+					}
+
+					tracing::warn!(
+						read_index = reply.read_index,
+						applied_index = shared.read_version(),
+						"udb member did not apply up to the read index in time"
+					);
+					return Err(DatabaseError::TransactionTooOld.into());
+				}
+				Ok(reply) => {
+					if reply.epoch > leader.epoch {
+						shared.observe_epoch(reply.epoch).await?;
+					}
+					tracing::debug!(epoch = reply.epoch, "udb read index rejected by leader");
+				}
+				Err(err) => tracing::warn!(?err, "malformed udb read index reply"),
+			},
+			// No responder (the leader stepped down) or transport error
+			Ok(Err(err)) => tracing::debug!(?err, "udb read index request errored"),
+			Err(_) => tracing::debug!("udb read index request timed out"),
+		}
+
+		if Instant::now() >= deadline {
+			return Err(DatabaseError::TransactionTooOld.into());
+		}
+		tokio::time::sleep(RETRY_BACKOFF).await;
+	}
+}
@@ -0,0 +1,221 @@
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicU64, Ordering},
+	},
+	time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use rocksdb::OptimisticTransactionDB;
+use tokio::sync::{Mutex, Notify, watch};
+use tokio_util::sync::CancellationToken;
+
+use super::{log::LogStore, nats::Subjects};
+
+/// The role a member currently plays in its epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+	Follower,
+	Candidate,
+	Leader,
+}
+
+/// In-memory election state. `epoch` and `voted_for` mirror the persisted hard state and are always
+/// written to the log store before being changed here.
+pub struct ElectionState {
+	pub epoch: u64,
+	pub voted_for: Option<String>,
+	pub role: Role,
+	/// Last time this member heard from a leader or granted a vote. Drives the election timeout.
+	pub last_heard: Instant,
+	/// Cancelled when this member stops leading the current epoch.
+	pub term: Option<CancellationToken>,
+}
+
+/// The leader of an epoch, as seen by this member.
+#[derive(Clone, Debug)]
+pub struct LeaderInfo {
+	pub epoch: u64,
+	/// Node id of the leader, used to build its commit subject.
+	pub leader_id: String,
+}
+
+/// Process-wide state shared by the transaction tasks, the apply loop, and the election and leader
+/// tasks. Every member is a follower (it serves reads from its replica and submits its own commits)
+/// and a candidate leader.
+pub struct ReplicatedShared {
+	pub db: Arc<OptimisticTransactionDB>,
+	pub log: LogStore,
+	/// Id of this member. Names its NATS subjects and is the dedup `client_node_id`.
+	pub node_id: String,
+	/// Ids of the other members of the replica set.
+	pub peers: Vec<String>,
+	pub client: async_nats::Client,
+	pub subjects: Subjects,
+	pub state: Mutex<ElectionState>,
+	/// Cancelled when the driver shuts down. Each leadership term is a child of this token.
+	pub shutdown: CancellationToken,
+	/// Highest log index applied to the local replica; the read version.
+	applied_index: AtomicU64,
+	/// Pinged whenever `applied_index` advances.
+	applied_notify: Notify,
+	/// Highest log index known to be committed.
+	commit_index: AtomicU64,
+	/// Pinged whenever `commit_index` advances, waking the apply loop.
+	commit_notify: Notify,
+	/// Per-process monotonic commit sequence, the dedup `client_seq`.
+	commit_seq: AtomicU64,
+	leader_tx: watch::Sender<Option<LeaderInfo>>,
+	leader_rx: watch::Receiver<Option<LeaderInfo>>,
+}
+
+impl ReplicatedShared {
+	pub fn new(
+		db: Arc<OptimisticTransactionDB>,
+		log: LogStore,
+		node_id: String,
+		peers: Vec<String>,
+		client: async_nats::Client,
+		subjects: Subjects,
+	) -> Result<Arc<Self>> {
+		let (epoch, voted_for) = log.hard_state()?;
+		let applied_index = log.applied_index()?;
+		let (leader_tx, leader_rx) = watch::channel(None);
+
+		Ok(Arc::new(ReplicatedShared {
+			db,
+			log,
+			node_id,
+			peers,
+			client,
+			subjects,
+			state: Mutex::new(ElectionState {
+				epoch,
+				voted_for,
+				role: Role::Follower,
+				last_heard: Instant::now(),
+				term: None,
+			}),
+			shutdown: CancellationToken::new(),
+			applied_index: AtomicU64::new(applied_index),
+			applied_notify: Notify::new(),
+			// Everything applied was committed.
+			commit_index: AtomicU64::new(applied_index),
+			commit_notify: Notify::new(),
+			commit_seq: AtomicU64::new(0),
+			leader_tx,
+			leader_rx,
+		}))
+	}
+
+	/// Number of members (including this one) that must persist an entry before it is committed.
+	pub fn quorum(&self) -> usize {
+		(self.peers.len() + 1) / 2 + 1
+	}
+
+	/// The follower read version: every entry at or below it is visible to a snapshot taken after
+	/// reading it.
+	pub fn read_version(&self) -> u64 {
+		self.applied_index.load(Ordering::SeqCst)
+	}
+
+	pub fn commit_index(&self) -> u64 {
+		self.commit_index.load(Ordering::SeqCst)
+	}
+
+	/// Allocate the next per-process commit sequence for the failover dedup key.
+	pub fn next_commit_seq(&self) -> u64 {
+		self.commit_seq.fetch_add(1, Ordering::Relaxed)
+	}
+
+	/// Advance the commit index monotonically and wake the apply loop.
+	pub fn advance_commit_index(&self, index: u64) {
+		let prev = self.commit_index.fetch_max(index, Ordering::SeqCst);
+		if index > prev {
+			self.commit_notify.notify_one();
+		}
+	}
+
+	/// Resolves once the commit index has advanced since the last call. Only the apply loop waits on
+	/// this.
+	pub async fn commit_advanced(&self) {
+		self.commit_notify.notified().await
+	}
+
+	/// Record that every entry up to `index` has been applied to the local replica.
+	pub fn set_applied(&self, index: u64) {
+		let prev = self.applied_index.fetch_max(index, Ordering::SeqCst);
+		if index > prev {
+			self.applied_notify.notify_waiters();
+		}
+	}
+
+	/// Wait until the local replica has applied `index`. Returns false if `timeout` elapses first.
+	pub async fn wait_for_applied(&self, index: u64, timeout: Duration) -> bool {
+		let deadline = tokio::time::Instant::now() + timeout;
+		loop {
+			let notified = self.applied_notify.notified();
+			tokio::pin!(notified);
+			notified.as_mut().enable();
+
+			if self.read_version() >= index {
+				return true;
+			}
+			if tokio::time::timeout_at(deadline, notified).await.is_err() {
+				return false;
+			}
+		}
+	}
+
+	/// Current known leader, if any.
+	pub fn current_leader(&self) -> Option<LeaderInfo> {
+		self.leader_rx.borrow().clone()
+	}
+
+	pub fn set_leader(&self, leader: Option<LeaderInfo>) {
+		let changed = match (self.leader_rx.borrow().as_ref(), leader.as_ref()) {
+			(Some(prev), Some(next)) => {
+				prev.epoch != next.epoch || prev.leader_id != next.leader_id
+			}
+			(None, None) => false,
+			_ => true,
+		};
+		if changed {
+			tracing::debug!(
+				epoch = leader.as_ref().map(|l| l.epoch),
+				leader_id = leader.as_ref().map(|l| l.leader_id.as_str()),
+				self_node = %self.node_id,
+				"udb member observed leader change"
+			);
+			let _ = self.leader_tx.send(leader);
+		}
+	}
+
+	/// Step down to follower, adopting `epoch` if it is newer. Stops leading if this member was the
+	/// leader.
+	pub fn step_down(&self, state: &mut ElectionState, epoch: u64) -> Result<()> {
+		if epoch > state.epoch {
+			self.log.save_hard_state(epoch, None)?;
+			state.epoch = epoch;
+			state.voted_for = None;
+			self.set_leader(None);
+		}
+
+		if let Some(term) = state.term.take() {
+			term.cancel();
+		}
+		state.role = Role::Follower;
+
+		Ok(())
+	}
+
+	/// Step down if a peer reported a newer epoch than ours.
+	pub async fn observe_epoch(&self, epoch: u64) -> Result<()> {
+		let mut state = self.state.lock().await;
+		if epoch > state.epoch {
+			self.step_down(&mut state, epoch)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::{
+	driver::rocksdb::transaction_task::{Snapshot, TransactionTask as RocksDbTransactionTask},
+	options::ConflictRangeType,
+	tx_ops::Operation,
+	value::{Slice, Values},
+};
+
+use super::{commit, read_index, shared::ReplicatedShared};
+
+pub enum TransactionCommand {
+	Get {
+		key: Vec<u8>,
+		response: oneshot::Sender<Result<Option<Slice>>>,
+	},
+	GetKey {
+		key: Vec<u8>,
+		or_equal: bool,
+		offset: i32,
+		response: oneshot::Sender<Result<Option<Slice>>>,
+	},
+	GetRange {
+		begin: Vec<u8>,
+		begin_or_equal: bool,
+		begin_offset: i32,
+		end: Vec<u8>,
+		end_or_equal: bool,
+		end_offset: i32,
+		limit: Option<usize>,
+		reverse: bool,
+		response: oneshot::Sender<Result<Values>>,
+	},
+	Commit {
+		operations: Vec<Operation>,
+		conflict_ranges: Vec<(Vec<u8>, Vec<u8>, ConflictRangeType)>,
+		response: oneshot::Sender<Result<()>>,
+	},
+	GetEstimatedRangeSize {
+		begin: Vec<u8>,
+		end: Vec<u8>,
+		response: oneshot::Sender<Result<i64>>,
+	},
+}
+
+/// TransactionTask owns a single pinned snapshot of the local replica for the lifetime of a
+/// transaction.
+///
+/// The snapshot is opened on the first read, after syncing with the leader's read index so reads are
+/// linearizable even on a follower. Reads go directly against the snapshot using the file system
+/// driver's read path. Commits delegate to [`commit::submit`], which sends the request to the leader
+/// and awaits the result. The `read_version` is captured from the applied index before the snapshot
+/// is opened, so no entry with `index <= read_version` can be invisible to the snapshot.
+pub struct TransactionTask {
+	shared: Arc<ReplicatedShared>,
+	receiver: mpsc::UnboundedReceiver<TransactionCommand>,
+}
+
+impl TransactionTask {
+	pub fn new(
+		shared: Arc<ReplicatedShared>,
+		receiver: mpsc::UnboundedReceiver<TransactionCommand>,
+	) -> Self {
+		TransactionTask { shared, receiver }
+	}
+
+	pub async fn run(mut self) {
+		let shared = &self.shared;
+		let mut view = None;
+
+		while let Some(command) = self.receiver.recv().await {
+			match command {
+				TransactionCommand::Get { key, response } => {
+					let result = match open_view(shared, &mut view).await {
+						Ok((_, snapshot)) => RocksDbTransactionTask::handle_get(snapshot, &key),
+						Err(err) => Err(err),
+					};
+					let _ = response.send(result);
+				}
+				TransactionCommand::GetKey {
+					key,
+					or_equal,
+					offset,
+					response,
+				} => {
+					let result = match open_view(shared, &mut view).await {
+						Ok((_, snapshot)) => {
+							RocksDbTransactionTask::handle_get_key(snapshot, &key, or_equal, offset)
+						}
+						Err(err) => Err(err),
+					};
+					let _ = response.send(result);
+				}
+				TransactionCommand::GetRange {
+					begin,
+					begin_or_equal,
+					begin_offset,
+					end,
+					end_or_equal,
+					end_offset,
+					limit,
+					reverse,
+					response,
+				} => {
+					let result = match open_view(shared, &mut view).await {
+						Ok((_, snapshot)) => RocksDbTransactionTask::handle_get_range(
+							snapshot,
+							begin,
+							begin_or_equal,
+							begin_offset,
+							end,
+							end_or_equal,
+							end_offset,
+							limit,
+							reverse,
+						),
+						Err(err) => Err(err),
+					};
+					let _ = response.send(result);
+				}
+				TransactionCommand::Commit {
+					operations,
+					conflict_ranges,
+					response,
+				} => {
+					// Release the snapshot and submit the commit to the leader. A transaction that
+					// never read has no read conflicts, so the applied index serves as its read
+					// version.
+					let read_version = view
+						.take()
+						.map(|(read_version, _)| read_version)
+						.unwrap_or_else(|| shared.read_version());
+					let result =
+						commit::submit(shared, read_version, operations, conflict_ranges).await;
+					let _ = response.send(result);
+					return;
+				}
+				TransactionCommand::GetEstimatedRangeSize {
+					begin,
+					end,
+					response,
+				} => {
+					let range = rocksdb::Range::new(&begin, &end);
+					let size = shared
+						.db
+						.get_approximate_sizes(&[range])
+						.first()
+						.copied()
+						.unwrap_or(0) as i64;
+					let _ = response.send(Ok(size));
+				}
+			}
+		}
+	}
+}
+
+/// Opens the transaction's snapshot on first use. Returns the read version and the snapshot.
+async fn open_view<'a, 'b>(
+	shared: &'a Arc<ReplicatedShared>,
+	view: &'b mut Option<(u64, Snapshot<'a>)>,
+) -> Result<&'b (u64, Snapshot<'a>)> {
+	if view.is_none() {
+		read_index::sync(shared).await?;
+
+		// Capture the read version BEFORE opening the snapshot so the snapshot reflects every entry
+		// with index <= read_version.
+		let read_version = shared.read_version();
+		*view = Some((read_version, shared.db.snapshot()));
+	}
+
+	Ok(view.as_ref().expect("view opened above"))
+}
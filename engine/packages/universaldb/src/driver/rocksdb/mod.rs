@@ -1,5 +1,5 @@
 mod database;
 mod transaction;
-mod transaction_task;
+pub(super) mod transaction_task;
 
 pub use database::RocksDbDatabaseDriver;
@@ -12,6 +12,7 @@ use crate::{
 	conflict_tracker::TransactionConflictTracker,
 	error::DatabaseError,
 	options::{ConflictRangeType, MutationType},
+	tuple::Versionstamp,
 	tx_ops::{self, Operation},
 	value::{KeyValue, Slice, Values},
 	versionstamp::{generate_versionstamp, substitute_raw_versionstamp},
@@ -36,7 +37,7 @@ fn iter_bytes_to_vec(bytes: &[u8]) -> Vec<u8> {
 }
 
 /// The point-in-time view a single UDB transaction reads from.
-type Snapshot<'a> = SnapshotWithThreadMode<'a, OptimisticTransactionDB>;
+pub type Snapshot<'a> = SnapshotWithThreadMode<'a, OptimisticTransactionDB>;
 
 pub enum TransactionCommand {
 	Get {
@@ -177,14 +178,14 @@ impl TransactionTask {
 		self.db.transaction_opt(&write_opts, &txn_opts)
 	}
 
-	fn handle_get(snapshot: &Snapshot<'_>, key: &[u8]) -> Result<Option<Slice>> {
+	pub fn handle_get(snapshot: &Snapshot<'_>, key: &[u8]) -> Result<Option<Slice>> {
 		Ok(snapshot
 			.get(key)
 			.context("failed to read key from rocksdb")?
 			.map(|v| v.into()))
 	}
 
-	fn handle_get_key(
+	pub fn handle_get_key(
 		snapshot: &Snapshot<'_>,
 		key: &[u8],
 		or_equal: bool,
@@ -279,9 +280,45 @@ impl TransactionTask {
 
 		// Create a new transaction for this commit
 		let txn = self.create_transaction();
-		let transaction_versionstamp = generate_versionstamp(0);
+		Self::apply_operations(&txn, operations, &generate_versionstamp(0))?;
 
-		// Apply all operations to the transaction
+		// rocksdb generates both start and commit versions from the in-process counter.
+		let commit_version = self.txn_conflict_tracker.next_global_version();
+		if self
+			.txn_conflict_tracker
+			.check_and_insert(start_version, commit_version, conflict_ranges)
+			.await
+		{
+			return Err(DatabaseError::NotCommitted.into());
+		}
+
+		// Commit the transaction (this consumes txn)
+		match txn.commit() {
+			Ok(_) => Ok(()),
+			Err(e) => {
+				// If the txn failed due to a rocksdb error, remove it from the conflict tracker
+				self.txn_conflict_tracker.remove(commit_version).await;
+
+				let err_str = e.to_string();
+
+				// Check if this is a conflict error
+				if err_str.contains("conflict") || err_str.contains("Resource busy") {
+					// Return retryable error
+					Err(DatabaseError::NotCommitted.into())
+				} else {
+					Err(e).context("rocksdb commit error")
+				}
+			}
+		}
+	}
+
+	/// Apply a commit's operations to a rocksdb transaction. Every versionstamped operation in the
+	/// commit is stamped with `versionstamp`.
+	pub fn apply_operations(
+		txn: &RocksDbTransaction<'_, OptimisticTransactionDB>,
+		operations: Vec<Operation>,
+		versionstamp: &Versionstamp,
+	) -> Result<()> {
 		for op in operations {
 			match op {
 				Operation::SetValue { key, value } => {
@@ -324,7 +361,7 @@ impl TransactionTask {
 					op_type,
 				} => {
 					if matches!(op_type, MutationType::SetVersionstampedKey) {
-						let key = substitute_raw_versionstamp(key, &transaction_versionstamp)
+						let key = substitute_raw_versionstamp(key, versionstamp)
 							.map_err(anyhow::Error::msg)
 							.context("failed substituting versionstamped key")?;
 						txn.put(key, &param)
@@ -333,7 +370,7 @@ impl TransactionTask {
 					}
 
 					if matches!(op_type, MutationType::SetVersionstampedValue) {
-						let value = substitute_raw_versionstamp(param, &transaction_versionstamp)
+						let value = substitute_raw_versionstamp(param, versionstamp)
 							.map_err(anyhow::Error::msg)
 							.context("failed substituting versionstamped value")?;
 						txn.put(key, &value)
@@ -363,37 +400,10 @@ impl TransactionTask {
 			}
 		}
 
-		// rocksdb generates both start and commit versions from the in-process counter.
-		let commit_version = self.txn_conflict_tracker.next_global_version();
-		if self
-			.txn_conflict_tracker
-			.check_and_insert(start_version, commit_version, conflict_ranges)
-			.await
-		{
-			return Err(DatabaseError::NotCommitted.into());
-		}
-
-		// Commit the transaction (this consumes txn)
-		match txn.commit() {
-			Ok(_) => Ok(()),
-			Err(e) => {
-				// If the txn failed due to a rocksdb error, remove it from the conflict tracker
-				self.txn_conflict_tracker.remove(commit_version).await;
-
-				let err_str = e.to_string();
-
-				// Check if this is a conflict error
-				if err_str.contains("conflict") || err_str.contains("Resource busy") {
-					// Return retryable error
-					Err(DatabaseError::NotCommitted.into())
-				} else {
-					Err(e).context("rocksdb commit error")
-				}
-			}
-		}
+		Ok(())
 	}
 
-	fn handle_get_range(
+	pub fn handle_get_range(
 		snapshot: &Snapshot<'_>,
 		begin: Vec<u8>,
 		begin_or_equal: bool,
@@ -158,4 +158,25 @@ define_keys! {
 	(130, GENERATION, "generation"),
 	(131, ENVOY_HASH_IDX, "envoy_hash_idx"),
 	(132, VIRTUAL_NODES, "virtual_nodes"),
+	(133, EXPIRE_TS, "expire_ts"),
+	(134, STORAGE_USAGE, "storage_usage"),
+	(135, ACTOR_STORAGE_USAGE, "actor_storage_usage"),
+	(136, FORK_SOURCE, "fork_source"),
+	(137, SQLITE_REPLICAS, "sqlite_replicas"),
+	(138, DATABASE_QUOTA, "database_quota"),
+	(139, GUARD, "guard"),
+	(140, ACME_ACCOUNT, "acme_account"),
+	(141, ACME_CERTIFICATE, "acme_certificate"),
+	(142, ACME_CHALLENGE, "acme_challenge"),
+	(143, RATE_LIMIT_POLICY, "rate_limit_policy"),
+	(144, RATE_LIMIT_WINDOW, "rate_limit_window"),
+	(145, DOMAIN_ROUTE, "domain_route"),
+	(146, BY_HOSTNAME, "by_hostname"),
+	(147, AUTH_POLICY, "auth_policy"),
+	(148, CANARY_POLICY, "canary_policy"),
+	(149, REGION_POLICY, "region_policy"),
+	(150, FIRST_CLIENT_REGION, "first_client_region"),
+	(151, SOFT_LIMIT_EXCEEDED, "soft_limit_exceeded"),
+	(152, DOMAIN_OWNER, "domain_owner"),
+	(153, SEALED, "sealed"),
 }
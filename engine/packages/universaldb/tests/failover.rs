@@ -2,7 +2,15 @@ use std::{sync::Arc, time::Duration};
 
 use rivet_test_deps_docker::{TestDatabase, TestPubSub};
 use tokio_postgres::NoTls;
-use universaldb::{Database, driver::postgres::NatsConfig, utils::IsolationLevel::*};
+use universaldb::{
+	Database,
+	driver::{
+		ReplicatedDatabaseDriver,
+		postgres::NatsConfig,
+		replicated::{ReplicatedConfig, ReplicationStatus},
+	},
+	utils::IsolationLevel::*,
+};
 use uuid::Uuid;
 
 const ALPHA_KEY: &[u8] = b"failover/alpha";
@@ -315,3 +323,186 @@ async fn test_postgres_graceful_handoff() {
 	drop(db1);
 	drop(db2);
 }
+
+const REPLICA_MEMBERS: [&str; 3] = ["node-a", "node-b", "node-c"];
+
+/// A single member of a replicated file system replica set. The driver handle is kept alongside the
+/// `Database` so the test can inspect the member's view of the election.
+struct Replica {
+	node_id: &'static str,
+	driver: Arc<ReplicatedDatabaseDriver>,
+	db: Database,
+}
+
+impl Replica {
+	async fn status(&self) -> ReplicationStatus {
+		self.driver.status().await
+	}
+}
+
+/// Boot a 3 member replicated replica set sharing one NATS deployment, each member with its own
+/// RocksDB replica under `dir`.
+async fn make_replica_set(dir: &std::path::Path, nats: &NatsConfig) -> Vec<Replica> {
+	let members = REPLICA_MEMBERS
+		.iter()
+		.map(|member| member.to_string())
+		.collect::<Vec<_>>();
+
+	let mut replicas = Vec::new();
+	for node_id in REPLICA_MEMBERS {
+		let driver = Arc::new(
+			ReplicatedDatabaseDriver::new(ReplicatedConfig {
+				path: dir.join(node_id),
+				node_id: node_id.to_string(),
+				members: members.clone(),
+				nats: nats.clone(),
+			})
+			.await
+			.unwrap(),
+		);
+		let db = Database::new(driver.clone());
+		replicas.push(Replica {
+			node_id,
+			driver,
+			db,
+		});
+	}
+	replicas
+}
+
+/// Poll the replicas until one of them leads an epoch above `min_epoch`, returning its index.
+async fn wait_for_replica_leader(replicas: &[Replica], min_epoch: u64, timeout: Duration) -> usize {
+	let deadline = tokio::time::Instant::now() + timeout;
+	loop {
+		for (i, replica) in replicas.iter().enumerate() {
+			let status = replica.status().await;
+			if status.is_leader && status.epoch > min_epoch {
+				return i;
+			}
+		}
+		if tokio::time::Instant::now() >= deadline {
+			panic!("timed out waiting for a replicated udb leader");
+		}
+		tokio::time::sleep(Duration::from_millis(100)).await;
+	}
+}
+
+/// Exercises leader failover for the replicated file system driver: three members replicate one
+/// log, the elected leader is killed, and the survivors must elect a new leader in a higher epoch,
+/// preserve the dead leader's committed data, and resume accepting commits.
+#[tokio::test]
+async fn test_replicated_leader_failover() {
+	let _ = tracing_subscriber::fmt()
+		.with_env_filter("info")
+		.with_test_writer()
+		.try_init();
+
+	let (nats_config, _nats_docker) = setup_nats().await;
+	let dir = tempfile::tempdir().unwrap();
+
+	let mut replicas = make_replica_set(dir.path(), &nats_config).await;
+	let leader = wait_for_replica_leader(&replicas, 0, Duration::from_secs(15)).await;
+	let status_before = replicas[leader].status().await;
+	let follower = (leader + 1) % replicas.len();
+
+	// The leader commits data, and a follower reads it from its own replica. Reads sync with the
+	// leader's read index first, so the follower observes the write as soon as it is acknowledged.
+	write_key(&replicas[leader].db, ALPHA_KEY, b"1").await;
+	assert_eq!(
+		read_key(&replicas[follower].db, ALPHA_KEY).await,
+		Some(b"1".to_vec()),
+		"follower must see the leader's committed write"
+	);
+
+	// Followers also commit, through the leader.
+	write_key(&replicas[follower].db, BETA_KEY, b"1").await;
+	assert_eq!(
+		read_key(&replicas[leader].db, BETA_KEY).await,
+		Some(b"1".to_vec()),
+		"leader must see a follower's committed write"
+	);
+
+	// Kill the leader. Dropping the driver stops it answering votes and appends.
+	let dead = replicas.remove(leader);
+	let dead_node_id = dead.node_id;
+	drop(dead);
+
+	// The survivors still form a majority and elect a new leader in a higher epoch.
+	let new_leader =
+		wait_for_replica_leader(&replicas, status_before.epoch, Duration::from_secs(15)).await;
+	assert_ne!(
+		replicas[new_leader].node_id, dead_node_id,
+		"a surviving member must become the new leader"
+	);
+
+	// The data the dead leader committed survives the failover on every survivor.
+	for replica in &replicas {
+		assert_eq!(
+			read_key(&replica.db, ALPHA_KEY).await,
+			Some(b"1".to_vec()),
+			"committed data must survive leader failover"
+		);
+	}
+
+	// The new leader resumes accepting commits, from any survivor.
+	for replica in &replicas {
+		write_key(&replica.db, BETA_KEY, replica.node_id.as_bytes()).await;
+		assert_eq!(
+			read_key(&replica.db, BETA_KEY).await,
+			Some(replica.node_id.as_bytes().to_vec()),
+			"new leader must accept and durably apply commits"
+		);
+	}
+
+	let status_after = replicas[new_leader].status().await;
+	assert!(
+		status_after.commit_index > status_before.commit_index,
+		"a post-failover commit must advance the commit index"
+	);
+}
+
+/// Exercises graceful leader handoff for the replicated file system driver: a leader that is shut
+/// down cleanly wakes the other members so they elect immediately instead of waiting out the
+/// election timeout.
+#[tokio::test]
+async fn test_replicated_graceful_handoff() {
+	let _ = tracing_subscriber::fmt()
+		.with_env_filter("info")
+		.with_test_writer()
+		.try_init();
+
+	let (nats_config, _nats_docker) = setup_nats().await;
+	let dir = tempfile::tempdir().unwrap();
+
+	let mut replicas = make_replica_set(dir.path(), &nats_config).await;
+	let leader = wait_for_replica_leader(&replicas, 0, Duration::from_secs(15)).await;
+	let status_before = replicas[leader].status().await;
+
+	write_key(&replicas[leader].db, ALPHA_KEY, b"1").await;
+
+	let handoff_start = tokio::time::Instant::now();
+	let departing = replicas.remove(leader);
+	departing.db.shutdown().await;
+
+	let new_leader =
+		wait_for_replica_leader(&replicas, status_before.epoch, Duration::from_secs(5)).await;
+	let handoff_elapsed = handoff_start.elapsed();
+	assert!(
+		handoff_elapsed < Duration::from_secs(2),
+		"graceful handoff must beat the election timeout (took {handoff_elapsed:?})"
+	);
+	assert_ne!(replicas[new_leader].node_id, departing.node_id);
+
+	assert_eq!(
+		read_key(&replicas[new_leader].db, ALPHA_KEY).await,
+		Some(b"1".to_vec()),
+		"committed data must survive graceful handoff"
+	);
+	write_key(&replicas[new_leader].db, BETA_KEY, b"2").await;
+	assert_eq!(
+		read_key(&replicas[new_leader].db, BETA_KEY).await,
+		Some(b"2".to_vec())
+	);
+
+	drop(departing);
+}
@@ -8,4 +8,16 @@ pub enum Ups {
 	RequestTimeout,
 	#[error("publish_failed", "Failed to publish message after retries")]
 	PublishFailed,
+	#[error("stream_cancelled", "Request stream was cancelled by the requester.")]
+	StreamCancelled,
+	#[error(
+		"stream_unsubscribed",
+		"Reply subscription closed before the request stream completed."
+	)]
+	StreamUnsubscribed,
+	#[error(
+		"stream_expired",
+		"Request stream deadline passed before the reply was sent."
+	)]
+	StreamExpired,
 }
@@ -3,8 +3,10 @@ pub mod driver;
 pub mod errors;
 pub mod metrics;
 pub mod pubsub;
+pub mod stream;
 pub mod subject;
 
 pub use driver::*;
 pub use pubsub::{Message, NextOutput, PubSub, Subscriber};
+pub use stream::{ReplyStream, RequestStreamOpts, StreamOutput, StreamReplier};
 pub use subject::{InboxSubject, Subject};
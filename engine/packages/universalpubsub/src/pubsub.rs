@@ -19,7 +19,7 @@ use crate::subject::{InboxSubject, Subject};
 const GC_INTERVAL: Duration = Duration::from_secs(60);
 
 pub struct PubSubInner {
-	driver: PubSubDriverHandle,
+	pub(crate) driver: PubSubDriverHandle,
 	chunk_tracker: ChunkTracker,
 	// Local in-memory subscribers by subject (shared across all drivers)
 	local_subscribers: HashMap<String, broadcast::Sender<Vec<u8>>>,
@@ -87,7 +87,7 @@ impl PubSub {
 	}
 
 	#[tracing::instrument(skip_all, fields(%subject))]
-	async fn subscribe_inner<T: Subject>(
+	pub(crate) async fn subscribe_inner<T: Subject>(
 		&self,
 		subject: T,
 		reply_id: Option<Uuid>,
@@ -174,7 +174,7 @@ impl PubSub {
 	}
 
 	#[tracing::instrument(skip_all, fields(%subject, ?opts, message_id = tracing::field::Empty))]
-	async fn publish_inner<T: Subject>(
+	pub(crate) async fn publish_inner<T: Subject>(
 		&self,
 		subject: T,
 		payload: &[u8],
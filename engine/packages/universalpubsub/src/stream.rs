@@ -0,0 +1,388 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::driver::PublishOpts;
+use crate::errors;
+use crate::metrics;
+use crate::pubsub::{Message, NextOutput, PubSub, Subscriber};
+use crate::subject::{InboxSubject, Subject};
+
+/// Prefix byte for a reply that carries a payload.
+const FRAME_ITEM: u8 = 0;
+/// Prefix byte for the marker a responder sends after its last reply.
+const FRAME_END: u8 = 1;
+
+const CANCEL_SUFFIX: &str = "cancel";
+
+#[derive(Clone, Copy, Debug)]
+pub struct RequestStreamOpts {
+	/// Deadline for the whole stream, not for each individual reply.
+	pub timeout: Duration,
+	/// Stops the stream (and cancels responders) after this many replies.
+	pub max_responses: Option<usize>,
+	/// Number of end markers to wait for before the stream completes. Requests with more than one
+	/// responder are published as a broadcast.
+	pub responders: usize,
+}
+
+impl Default for RequestStreamOpts {
+	fn default() -> Self {
+		Self {
+			timeout: Duration::from_secs(30),
+			max_responses: None,
+			responders: 1,
+		}
+	}
+}
+
+impl RequestStreamOpts {
+	pub fn with_timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	pub fn with_max_responses(mut self, max_responses: usize) -> Self {
+		self.max_responses = Some(max_responses);
+		self
+	}
+
+	pub fn with_responders(mut self, responders: usize) -> Self {
+		self.responders = responders.max(1);
+		self
+	}
+}
+
+pub enum StreamOutput {
+	Message(Message),
+	/// Every responder sent its end marker or `max_responses` was reached.
+	End,
+	NoResponders,
+}
+
+impl PubSub {
+	/// Sends a request whose responders may reply multiple times before sending an end marker.
+	///
+	/// Responders must reply with `Message::reply_stream` instead of `Message::reply`.
+	#[tracing::instrument(skip_all, fields(%subject))]
+	pub async fn request_stream<T: Subject>(
+		&self,
+		subject: T,
+		payload: &[u8],
+		opts: RequestStreamOpts,
+	) -> Result<ReplyStream> {
+		let reply_subject = self.driver.new_inbox();
+		let now = rivet_util::timestamp::now();
+		let request_deadline_at = i64::try_from(opts.timeout.as_millis())
+			.ok()
+			.and_then(|timeout_ms| now.checked_add(timeout_ms));
+
+		// Use a regular subscription instead of a reply subscription since reply subscriptions only
+		// resolve once
+		let subscriber = self.subscribe_inner(reply_subject.clone(), None).await?;
+
+		let publish_opts = if opts.responders > 1 {
+			PublishOpts::broadcast()
+		} else {
+			PublishOpts::one()
+		};
+		self.publish_inner(
+			subject,
+			payload,
+			Some(&reply_subject),
+			publish_opts,
+			request_deadline_at,
+		)
+		.await?;
+
+		Ok(ReplyStream {
+			pubsub: self.clone(),
+			subscriber,
+			cancel_subject: CancelSubject::new(&reply_subject.as_cow()),
+			subject_root: T::root()
+				.map(|x| x.into_owned())
+				.unwrap_or_else(|| "unknown".to_string()),
+			start: Instant::now(),
+			deadline: tokio::time::Instant::now() + opts.timeout,
+			max_responses: opts.max_responses,
+			responders: opts.responders.max(1),
+			responses: 0,
+			ends: 0,
+			finished: false,
+		})
+	}
+}
+
+/// Requester side of a streaming request. Dropping the stream before it completes cancels the
+/// responders.
+pub struct ReplyStream {
+	pubsub: PubSub,
+	subscriber: Subscriber,
+	cancel_subject: CancelSubject,
+	subject_root: String,
+	start: Instant,
+	deadline: tokio::time::Instant,
+	max_responses: Option<usize>,
+	responders: usize,
+	responses: usize,
+	ends: usize,
+	finished: bool,
+}
+
+impl ReplyStream {
+	#[tracing::instrument(skip_all, fields(subject=%self.cancel_subject))]
+	pub async fn next(&mut self) -> Result<StreamOutput> {
+		loop {
+			if self.finished {
+				return Ok(StreamOutput::End);
+			}
+
+			let msg = match tokio::time::timeout_at(self.deadline, self.subscriber.next()).await {
+				Ok(Ok(NextOutput::Message(msg))) => msg,
+				Ok(Ok(NextOutput::NoResponders)) => {
+					self.finished = true;
+					return Ok(StreamOutput::NoResponders);
+				}
+				Ok(Ok(NextOutput::Unsubscribed)) => {
+					self.cancel().await;
+					return Err(errors::Ups::StreamUnsubscribed.build().into());
+				}
+				Ok(Err(err)) => {
+					self.cancel().await;
+					return Err(err.context("failed to receive reply stream message"));
+				}
+				Err(_) => {
+					metrics::REQUEST_TIMEOUT_COUNT
+						.with_label_values(&[self.subject_root.as_str()])
+						.inc();
+
+					self.cancel().await;
+					return Err(errors::Ups::RequestTimeout.build().into());
+				}
+			};
+
+			match msg.payload.first().copied() {
+				Some(FRAME_ITEM) => {
+					if self.responses == 0 {
+						metrics::REQUEST_RESPONSE_LAG
+							.with_label_values(&[self.subject_root.as_str()])
+							.observe(self.start.elapsed().as_secs_f64());
+					}
+					self.responses += 1;
+
+					if self
+						.max_responses
+						.is_some_and(|max_responses| self.responses >= max_responses)
+					{
+						self.cancel().await;
+					}
+
+					let mut msg = msg;
+					msg.payload.remove(0);
+
+					return Ok(StreamOutput::Message(msg));
+				}
+				Some(FRAME_END) => {
+					self.ends += 1;
+
+					if self.ends >= self.responders {
+						self.finished = true;
+						return Ok(StreamOutput::End);
+					}
+				}
+				frame => {
+					tracing::warn!(?frame, "received reply stream message with invalid frame");
+				}
+			}
+		}
+	}
+
+	/// Number of replies received so far.
+	pub fn responses(&self) -> usize {
+		self.responses
+	}
+
+	/// Stops the stream and notifies all responders. Further calls to `next` return `End`.
+	pub async fn cancel(&mut self) {
+		if self.finished {
+			return;
+		}
+		self.finished = true;
+
+		if let Err(err) = self
+			.pubsub
+			.publish(&self.cancel_subject, &[], PublishOpts::broadcast())
+			.await
+		{
+			tracing::warn!(?err, "failed to publish reply stream cancellation");
+		}
+	}
+}
+
+impl Drop for ReplyStream {
+	fn drop(&mut self) {
+		if self.finished {
+			return;
+		}
+
+		// Dropped outside of a runtime, responders find out once the request deadline passes
+		let Ok(handle) = tokio::runtime::Handle::try_current() else {
+			return;
+		};
+
+		let pubsub = self.pubsub.clone();
+		let cancel_subject = self.cancel_subject.clone();
+		handle.spawn(async move {
+			if let Err(err) = pubsub
+				.publish(&cancel_subject, &[], PublishOpts::broadcast())
+				.await
+			{
+				tracing::warn!(?err, "failed to publish reply stream cancellation");
+			}
+		});
+	}
+}
+
+impl Message {
+	/// Starts replying to a streaming request (see `PubSub::request_stream`).
+	///
+	/// Cancellation is best effort: if the requester cancels before this subscribes to the
+	/// cancellation subject, the responder will only find out once the request deadline passes.
+	#[tracing::instrument(skip_all, fields(message_id=?self.message_id, reply_subject=?self.reply))]
+	pub async fn reply_stream(&self) -> Result<StreamReplier> {
+		let Some(reply_subject) = &self.reply else {
+			bail!("message has no reply subject");
+		};
+
+		let cancel_token = CancellationToken::new();
+		let mut cancel_sub = self
+			.pubsub
+			.subscribe(CancelSubject::new(reply_subject))
+			.await?;
+		let cancel_token2 = cancel_token.clone();
+		let cancel_task = tokio::spawn(async move {
+			if let Ok(NextOutput::Message(_)) = cancel_sub.next().await {
+				cancel_token2.cancel();
+			}
+		});
+
+		Ok(StreamReplier {
+			pubsub: self.pubsub.clone(),
+			reply_subject: reply_subject.clone(),
+			request_deadline_at: self.request_deadline_at,
+			cancel_token,
+			cancel_task,
+		})
+	}
+}
+
+/// Responder side of a streaming request.
+pub struct StreamReplier {
+	pubsub: PubSub,
+	reply_subject: String,
+	request_deadline_at: Option<i64>,
+	cancel_token: CancellationToken,
+	cancel_task: JoinHandle<()>,
+}
+
+impl StreamReplier {
+	#[tracing::instrument(skip_all, fields(reply_subject=%self.reply_subject))]
+	pub async fn send(&self, payload: &[u8]) -> Result<()> {
+		// A requester that timed out also cancels, so expiry is reported first
+		if self.is_request_expired() {
+			return Err(errors::Ups::StreamExpired.build().into());
+		}
+		if self.cancel_token.is_cancelled() {
+			return Err(errors::Ups::StreamCancelled.build().into());
+		}
+
+		let mut frame = Vec::with_capacity(payload.len() + 1);
+		frame.push(FRAME_ITEM);
+		frame.extend_from_slice(payload);
+
+		self.publish_frame(&frame).await
+	}
+
+	/// Sends the end marker. The requester will not receive anything sent after this.
+	#[tracing::instrument(skip_all, fields(reply_subject=%self.reply_subject))]
+	pub async fn end(self) -> Result<()> {
+		if self.is_request_expired() {
+			return Err(errors::Ups::StreamExpired.build().into());
+		}
+		if self.cancel_token.is_cancelled() {
+			return Ok(());
+		}
+
+		self.publish_frame(&[FRAME_END]).await
+	}
+
+	/// True if the requester cancelled the stream or the request deadline has passed.
+	pub fn is_cancelled(&self) -> bool {
+		self.cancel_token.is_cancelled() || self.is_request_expired()
+	}
+
+	/// Resolves once the requester cancels the stream.
+	pub async fn cancelled(&self) {
+		self.cancel_token.cancelled().await
+	}
+
+	fn is_request_expired(&self) -> bool {
+		self.request_deadline_at
+			.is_some_and(|deadline_at| rivet_util::timestamp::now() >= deadline_at)
+	}
+
+	async fn publish_frame(&self, frame: &[u8]) -> Result<()> {
+		if self.is_request_expired() {
+			return Err(errors::Ups::StreamExpired.build().into());
+		}
+
+		// Replies expect exactly one subscriber and should use local fast-path
+		if let Some(reply_subject) = InboxSubject::from_existing(&self.reply_subject) {
+			self.pubsub
+				.publish(reply_subject, frame, PublishOpts::one())
+				.await?;
+		} else {
+			self.pubsub
+				.publish(&self.reply_subject, frame, PublishOpts::one())
+				.await?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Drop for StreamReplier {
+	fn drop(&mut self) {
+		self.cancel_task.abort();
+	}
+}
+
+/// Subject the requester publishes to when it stops listening to a reply stream.
+#[derive(Clone)]
+struct CancelSubject(String);
+
+impl CancelSubject {
+	fn new(reply_subject: &str) -> Self {
+		Self(format!("{reply_subject}.{CANCEL_SUFFIX}"))
+	}
+}
+
+impl Display for CancelSubject {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl Subject for CancelSubject {
+	fn root<'a>() -> Option<Cow<'a, str>> {
+		InboxSubject::root()
+	}
+
+	fn as_str(&self) -> Option<&str> {
+		Some(&self.0)
+	}
+}
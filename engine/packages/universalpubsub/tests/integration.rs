@@ -6,7 +6,7 @@ use std::{
 	sync::Arc,
 	time::{Duration, Instant},
 };
-use universalpubsub::{NextOutput, PubSub, PublishOpts};
+use universalpubsub::{NextOutput, PubSub, PublishOpts, RequestStreamOpts, StreamOutput};
 use uuid::Uuid;
 
 fn setup_logging() {
@@ -151,6 +151,34 @@ async fn test_memory_driver() {
 	test_inner(&pubsub).await;
 }
 
+#[test]
+fn test_memory_reply_stream_drop_outside_runtime() {
+	setup_logging();
+
+	let rt = tokio::runtime::Runtime::new().unwrap();
+	let stream = rt.block_on(async {
+		let test_id = Uuid::new_v4();
+		let (pubsub_config, _docker_config) = TestPubSub::Memory.config(test_id, 1).await.unwrap();
+		let rivet_config::config::PubSub::Memory(memory) = pubsub_config else {
+			unreachable!();
+		};
+
+		let driver = universalpubsub::driver::memory::MemoryDriver::new(memory.channel);
+		let pubsub = PubSub::new_with_memory_optimization(Arc::new(driver), false);
+
+		let subject = format!("test.request_stream_drop.{}", Uuid::new_v4());
+		let _sub = pubsub.subscribe(&subject).await.unwrap();
+		pubsub
+			.request_stream(&subject, b"request", RequestStreamOpts::default())
+			.await
+			.unwrap()
+	});
+	drop(rt);
+
+	// Must not panic without a runtime to publish the cancellation on
+	drop(stream);
+}
+
 async fn test_inner(pubsub: &PubSub) {
 	let start = Instant::now();
 	test_queue_subscribe_single(pubsub).await.unwrap();
@@ -195,6 +223,26 @@ async fn test_inner(pubsub: &PubSub) {
 	let start = Instant::now();
 	test_large_payloads(&pubsub).await.unwrap();
 	tracing::info!(duration_ms = ?start.elapsed().as_millis(), "test_large_payloads completed");
+
+	let start = Instant::now();
+	test_request_stream(&pubsub).await.unwrap();
+	tracing::info!(duration_ms = ?start.elapsed().as_millis(), "test_request_stream completed");
+
+	let start = Instant::now();
+	test_request_stream_fanout(&pubsub).await.unwrap();
+	tracing::info!(duration_ms = ?start.elapsed().as_millis(), "test_request_stream_fanout completed");
+
+	let start = Instant::now();
+	test_request_stream_max_responses(&pubsub).await.unwrap();
+	tracing::info!(duration_ms = ?start.elapsed().as_millis(), "test_request_stream_max_responses completed");
+
+	let start = Instant::now();
+	test_request_stream_timeout(&pubsub).await.unwrap();
+	tracing::info!(duration_ms = ?start.elapsed().as_millis(), "test_request_stream_timeout completed");
+
+	let start = Instant::now();
+	test_reply_stream_expired(&pubsub).await.unwrap();
+	tracing::info!(duration_ms = ?start.elapsed().as_millis(), "test_reply_stream_expired completed");
 }
 
 async fn test_basic_pub_sub(pubsub: &PubSub) -> Result<()> {
@@ -497,6 +545,281 @@ async fn test_expired_request_reply_rejected(pubsub: &PubSub) -> Result<()> {
 	Ok(())
 }
 
+async fn test_request_stream(pubsub: &PubSub) -> Result<()> {
+	tracing::info!("testing request stream");
+
+	let subject = format!("test.request_stream.{}", Uuid::new_v4());
+	// Larger than the NATS max message size so replies are chunked
+	let large_payload = vec![7u8; 2 * 1024 * 1024];
+
+	{
+		let pubsub = pubsub.clone();
+		let subject = subject.clone();
+		let large_payload = large_payload.clone();
+		let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+		tokio::spawn(async move {
+			let mut sub = pubsub.subscribe(&subject).await.unwrap();
+			ready_tx.send(()).unwrap();
+			let NextOutput::Message(msg) = sub.next().await.unwrap() else {
+				panic!("expected request message");
+			};
+
+			let replier = msg.reply_stream().await.unwrap();
+			for i in 0..3u8 {
+				replier.send(&[i]).await.unwrap();
+			}
+			replier.send(&large_payload).await.unwrap();
+			replier.end().await.unwrap();
+		});
+		ready_rx.await.unwrap();
+	}
+
+	let mut stream = pubsub
+		.request_stream(&subject, b"stream request", RequestStreamOpts::default())
+		.await?;
+
+	let mut replies = Vec::new();
+	loop {
+		match stream.next().await? {
+			StreamOutput::Message(msg) => replies.push(msg.payload),
+			StreamOutput::End => break,
+			StreamOutput::NoResponders => panic!("unexpected no responders"),
+		}
+	}
+
+	assert_eq!(replies.len(), 4);
+	assert_eq!(replies[0], vec![0]);
+	assert_eq!(replies[1], vec![1]);
+	assert_eq!(replies[2], vec![2]);
+	assert_eq!(replies[3], large_payload);
+	assert!(matches!(stream.next().await?, StreamOutput::End));
+
+	Ok(())
+}
+
+async fn test_request_stream_fanout(pubsub: &PubSub) -> Result<()> {
+	tracing::info!("testing request stream fanout");
+
+	let subject = format!("test.request_stream_fanout.{}", Uuid::new_v4());
+	let responders = 3;
+
+	for i in 0..responders {
+		let pubsub = pubsub.clone();
+		let subject = subject.clone();
+		let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+		tokio::spawn(async move {
+			let mut sub = pubsub.subscribe(&subject).await.unwrap();
+			ready_tx.send(()).unwrap();
+			let NextOutput::Message(msg) = sub.next().await.unwrap() else {
+				panic!("expected request message");
+			};
+
+			let replier = msg.reply_stream().await.unwrap();
+			replier.send(&[i, 0]).await.unwrap();
+			replier.send(&[i, 1]).await.unwrap();
+			replier.end().await.unwrap();
+		});
+		ready_rx.await.unwrap();
+	}
+
+	let mut stream = pubsub
+		.request_stream(
+			&subject,
+			b"fanout request",
+			RequestStreamOpts::default().with_responders(responders as usize),
+		)
+		.await?;
+
+	let mut replies = Vec::new();
+	loop {
+		match stream.next().await? {
+			StreamOutput::Message(msg) => replies.push(msg.payload),
+			StreamOutput::End => break,
+			StreamOutput::NoResponders => panic!("unexpected no responders"),
+		}
+	}
+
+	replies.sort();
+	let expected = (0..responders)
+		.flat_map(|i| [vec![i, 0], vec![i, 1]])
+		.collect::<Vec<_>>();
+	assert_eq!(replies, expected);
+
+	Ok(())
+}
+
+async fn test_request_stream_max_responses(pubsub: &PubSub) -> Result<()> {
+	tracing::info!("testing request stream max responses");
+
+	let subject = format!("test.request_stream_max.{}", Uuid::new_v4());
+	let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+	let (replier_ready_tx, replier_ready_rx) = tokio::sync::oneshot::channel();
+	let (cancelled_tx, cancelled_rx) = tokio::sync::oneshot::channel();
+
+	{
+		let pubsub = pubsub.clone();
+		let subject = subject.clone();
+		tokio::spawn(async move {
+			let mut sub = pubsub.subscribe(&subject).await.unwrap();
+			ready_tx.send(()).unwrap();
+			let NextOutput::Message(msg) = sub.next().await.unwrap() else {
+				panic!("expected request message");
+			};
+
+			let replier = msg.reply_stream().await.unwrap();
+			replier_ready_tx.send(()).unwrap();
+
+			// Reply until the requester cancels
+			let mut sent = 0u32;
+			loop {
+				tokio::select! {
+					_ = replier.cancelled() => break,
+					_ = tokio::time::sleep(Duration::from_millis(10)) => {
+						if replier.send(&sent.to_be_bytes()).await.is_err() {
+							break;
+						}
+						sent += 1;
+					}
+				}
+			}
+
+			let err = replier.send(b"after cancel").await.err().unwrap();
+			let err = err
+				.downcast_ref::<RivetError>()
+				.expect("expected errors::Ups");
+			cancelled_tx.send(err.code().to_string()).unwrap();
+		});
+		ready_rx.await.unwrap();
+	}
+
+	let mut stream = pubsub
+		.request_stream(
+			&subject,
+			b"capped request",
+			RequestStreamOpts::default().with_max_responses(2),
+		)
+		.await?;
+
+	let mut replies = 0;
+	loop {
+		match stream.next().await? {
+			StreamOutput::Message(_) => replies += 1,
+			StreamOutput::End => break,
+			StreamOutput::NoResponders => panic!("unexpected no responders"),
+		}
+	}
+	assert_eq!(replies, 2);
+	replier_ready_rx.await.unwrap();
+
+	let code = tokio::time::timeout(Duration::from_secs(5), cancelled_rx).await??;
+	assert_eq!(code, "stream_cancelled");
+
+	Ok(())
+}
+
+async fn test_request_stream_timeout(pubsub: &PubSub) -> Result<()> {
+	tracing::info!("testing request stream timeout");
+
+	let subject = format!("test.request_stream_timeout.{}", Uuid::new_v4());
+	let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+
+	{
+		let pubsub = pubsub.clone();
+		let subject = subject.clone();
+		tokio::spawn(async move {
+			let mut sub = pubsub.subscribe(&subject).await.unwrap();
+			ready_tx.send(()).unwrap();
+			let NextOutput::Message(msg) = sub.next().await.unwrap() else {
+				panic!("expected request message");
+			};
+
+			// Never sends an end marker
+			let replier = msg.reply_stream().await.unwrap();
+			replier.send(b"only reply").await.unwrap();
+			tokio::time::sleep(Duration::from_secs(1)).await;
+		});
+		ready_rx.await.unwrap();
+	}
+
+	let mut stream = pubsub
+		.request_stream(
+			&subject,
+			b"slow request",
+			RequestStreamOpts::default().with_timeout(Duration::from_millis(200)),
+		)
+		.await?;
+
+	let StreamOutput::Message(msg) = stream.next().await? else {
+		panic!("expected reply");
+	};
+	assert_eq!(msg.payload, b"only reply");
+
+	let err = stream.next().await.err().unwrap();
+	let err = err
+		.downcast_ref::<RivetError>()
+		.expect("expected errors::Ups");
+	assert_eq!(err.group(), "ups");
+	assert_eq!(err.code(), "request_timeout");
+
+	Ok(())
+}
+
+async fn test_reply_stream_expired(pubsub: &PubSub) -> Result<()> {
+	tracing::info!("testing reply stream expiry");
+
+	let subject = format!("test.reply_stream_expired.{}", Uuid::new_v4());
+	let timeout = Duration::from_millis(100);
+	let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+	let (codes_tx, codes_rx) = tokio::sync::oneshot::channel();
+
+	{
+		let pubsub = pubsub.clone();
+		let subject = subject.clone();
+		tokio::spawn(async move {
+			let mut sub = pubsub.subscribe(&subject).await.unwrap();
+			ready_tx.send(()).unwrap();
+			let NextOutput::Message(msg) = sub.next().await.unwrap() else {
+				panic!("expected request message");
+			};
+
+			let replier = msg.reply_stream().await.unwrap();
+			// The requester times out and cancels while this waits
+			tokio::time::sleep(timeout + Duration::from_millis(200)).await;
+
+			let code = |err: anyhow::Error| {
+				err.downcast_ref::<RivetError>()
+					.expect("expected errors::Ups")
+					.code()
+					.to_string()
+			};
+			let send_code = code(replier.send(b"late reply").await.err().unwrap());
+			let end_code = code(replier.end().await.err().unwrap());
+			codes_tx.send((send_code, end_code)).unwrap();
+		});
+		ready_rx.await.unwrap();
+	}
+
+	let mut stream = pubsub
+		.request_stream(
+			&subject,
+			b"expiring request",
+			RequestStreamOpts::default().with_timeout(timeout),
+		)
+		.await?;
+	let err = stream.next().await.err().unwrap();
+	let err = err
+		.downcast_ref::<RivetError>()
+		.expect("expected errors::Ups");
+	assert_eq!(err.code(), "request_timeout");
+	drop(stream);
+
+	let (send_code, end_code) = tokio::time::timeout(Duration::from_secs(5), codes_rx).await??;
+	assert_eq!(send_code, "stream_expired");
+	assert_eq!(end_code, "stream_expired");
+
+	Ok(())
+}
+
 async fn test_large_payloads(pubsub: &PubSub) -> Result<()> {
 	tracing::info!("testing large payloads with chunking");
 
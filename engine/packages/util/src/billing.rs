@@ -1,3 +1,6 @@
 use crate::duration;
 
 pub const CUTOFF_DURATION: i64 = duration::days(30);
+
+/// How often actor usage (awake time and storage) is metered. Metered values are at most this stale.
+pub const METERING_INTERVAL: i64 = duration::seconds(60);
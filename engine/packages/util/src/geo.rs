@@ -1,5 +1,180 @@
+use std::{net::IpAddr, path::Path, str::FromStr};
+
+use anyhow::{Context, Result, bail};
+use ipnet::IpNet;
+
+/// Mean radius of the earth in kilometers.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
 pub mod convert {
 	pub fn kilometers_to_miles(kilometers: f64) -> f64 {
 		kilometers * 0.621371
 	}
 }
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+	pub latitude: f64,
+	pub longitude: f64,
+}
+
+impl Coordinates {
+	pub fn new(latitude: f64, longitude: f64) -> Self {
+		Coordinates {
+			latitude,
+			longitude,
+		}
+	}
+
+	/// Great-circle distance in kilometers.
+	pub fn distance_km(&self, other: &Coordinates) -> f64 {
+		let lat_a = self.latitude.to_radians();
+		let lat_b = other.latitude.to_radians();
+		let d_lat = lat_b - lat_a;
+		let d_lon = (other.longitude - self.longitude).to_radians();
+
+		let a =
+			(d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+
+		2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+	}
+}
+
+/// Locates IP addresses using a GeoIP database in CSV format.
+///
+/// The CSV must have a header row with `network`, `latitude` and `longitude` columns. Other
+/// columns are ignored, so the MaxMind GeoLite2 City blocks files can be used as is. Files for
+/// IPv4 and IPv6 can be concatenated, repeated header rows are skipped. Networks must not
+/// overlap.
+#[derive(Debug, Default)]
+pub struct GeoIpDatabase {
+	v4: Vec<NetworkRange>,
+	v6: Vec<NetworkRange>,
+}
+
+#[derive(Debug)]
+struct NetworkRange {
+	start: u128,
+	end: u128,
+	coords: Coordinates,
+}
+
+impl GeoIpDatabase {
+	pub fn open(path: &Path) -> Result<Self> {
+		let content = std::fs::read_to_string(path)
+			.with_context(|| format!("failed to read geoip database {}", path.display()))?;
+
+		Self::parse(&content)
+			.with_context(|| format!("failed to parse geoip database {}", path.display()))
+	}
+
+	pub fn parse(content: &str) -> Result<Self> {
+		let mut db = GeoIpDatabase::default();
+		let mut columns = None;
+
+		for (i, line) in content.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+
+			if fields.first() == Some(&"network") {
+				columns = Some(Columns::from_header(&fields)?);
+				continue;
+			}
+
+			let Some(columns) = &columns else {
+				bail!("missing header row");
+			};
+
+			// Networks without a location are skipped
+			let (Some(latitude), Some(longitude)) = (
+				fields.get(columns.latitude).filter(|x| !x.is_empty()),
+				fields.get(columns.longitude).filter(|x| !x.is_empty()),
+			) else {
+				continue;
+			};
+
+			let network = fields
+				.get(columns.network)
+				.context("missing network")
+				.and_then(|x| IpNet::from_str(x).map_err(Into::into))
+				.with_context(|| format!("invalid network on line {}", i + 1))?;
+			let coords = Coordinates::new(
+				latitude
+					.parse()
+					.with_context(|| format!("invalid latitude on line {}", i + 1))?,
+				longitude
+					.parse()
+					.with_context(|| format!("invalid longitude on line {}", i + 1))?,
+			);
+
+			match network {
+				IpNet::V4(net) => db.v4.push(NetworkRange {
+					start: u32::from(net.network()) as u128,
+					end: u32::from(net.broadcast()) as u128,
+					coords,
+				}),
+				IpNet::V6(net) => db.v6.push(NetworkRange {
+					start: u128::from(net.network()),
+					end: u128::from(net.broadcast()),
+					coords,
+				}),
+			}
+		}
+
+		db.v4.sort_by_key(|range| range.start);
+		db.v6.sort_by_key(|range| range.start);
+
+		Ok(db)
+	}
+
+	pub fn len(&self) -> usize {
+		self.v4.len() + self.v6.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the location of the network containing `ip`.
+	pub fn lookup(&self, ip: IpAddr) -> Option<Coordinates> {
+		let (ranges, ip) = match ip {
+			IpAddr::V4(ip) => (&self.v4, u32::from(ip) as u128),
+			IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+				Some(ip) => (&self.v4, u32::from(ip) as u128),
+				None => (&self.v6, u128::from(ip)),
+			},
+		};
+
+		let idx = ranges.partition_point(|range| range.start <= ip);
+		let range = ranges.get(idx.checked_sub(1)?)?;
+
+		(ip <= range.end).then_some(range.coords)
+	}
+}
+
+struct Columns {
+	network: usize,
+	latitude: usize,
+	longitude: usize,
+}
+
+impl Columns {
+	fn from_header(fields: &[&str]) -> Result<Self> {
+		let find = |name: &str| {
+			fields
+				.iter()
+				.position(|field| *field == name)
+				.with_context(|| format!("missing `{name}` column"))
+		};
+
+		Ok(Columns {
+			network: find("network")?,
+			latitude: find("latitude")?,
+			longitude: find("longitude")?,
+		})
+	}
+}
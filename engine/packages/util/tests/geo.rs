@@ -0,0 +1,77 @@
+use std::net::IpAddr;
+
+use rivet_util::geo::{Coordinates, GeoIpDatabase};
+
+const DB: &str = "\
+network,geoname_id,registered_country_geoname_id,postal_code,latitude,longitude,accuracy_radius
+1.0.0.0/24,2077456,2077456,,-33.4940,143.2104,1000
+8.8.8.0/24,6252001,6252001,,37.7510,-97.8220,1000
+9.9.9.0/24,,,,,,
+network,geoname_id,registered_country_geoname_id,postal_code,latitude,longitude,accuracy_radius
+2a00:1450::/32,2921044,2921044,,51.2993,9.4910,100
+";
+
+fn ip(ip: &str) -> IpAddr {
+	ip.parse().unwrap()
+}
+
+#[test]
+fn lookup_finds_containing_network() {
+	let db = GeoIpDatabase::parse(DB).unwrap();
+	assert_eq!(db.len(), 3);
+
+	assert_eq!(
+		db.lookup(ip("8.8.8.8")),
+		Some(Coordinates::new(37.751, -97.822))
+	);
+	assert_eq!(
+		db.lookup(ip("1.0.0.255")),
+		Some(Coordinates::new(-33.494, 143.2104))
+	);
+	assert_eq!(
+		db.lookup(ip("2a00:1450:4001::1")),
+		Some(Coordinates::new(51.2993, 9.491))
+	);
+}
+
+#[test]
+fn lookup_misses_unknown_networks() {
+	let db = GeoIpDatabase::parse(DB).unwrap();
+
+	assert_eq!(db.lookup(ip("1.0.1.0")), None);
+	assert_eq!(db.lookup(ip("0.0.0.1")), None);
+	// Networks without a location are skipped
+	assert_eq!(db.lookup(ip("9.9.9.9")), None);
+	assert_eq!(db.lookup(ip("2a01::1")), None);
+}
+
+#[test]
+fn lookup_ipv4_mapped_address() {
+	let db = GeoIpDatabase::parse(DB).unwrap();
+
+	assert_eq!(
+		db.lookup(ip("::ffff:8.8.4.4")),
+		None,
+		"8.8.4.0/24 is not in the database"
+	);
+	assert_eq!(
+		db.lookup(ip("::ffff:8.8.8.8")),
+		Some(Coordinates::new(37.751, -97.822))
+	);
+}
+
+#[test]
+fn parse_requires_header() {
+	assert!(GeoIpDatabase::parse("1.0.0.0/24,1.0,2.0\n").is_err());
+	assert!(GeoIpDatabase::parse("network,latitude\n1.0.0.0/24,1.0\n").is_err());
+}
+
+#[test]
+fn distance_between_cities() {
+	let london = Coordinates::new(51.5074, -0.1278);
+	let new_york = Coordinates::new(40.7128, -74.006);
+
+	let distance = london.distance_km(&new_york);
+	assert!((distance - 5570.0).abs() < 10.0, "{distance}");
+	assert_eq!(london.distance_km(&london), 0.0);
+}
@@ -4,13 +4,17 @@ use std::sync::Arc;
 
 use crate::async_counter::AsyncCounter;
 use rivet_envoy_protocol as protocol;
+use tokio::sync::Semaphore;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::error::TryRecvError;
 use tokio::task::{JoinError, JoinSet};
 use tracing::Instrument;
 
-use crate::config::{HttpRequest, HttpResponse, WebSocketMessage};
+use crate::config::{
+	BodyFrame, HttpRequest, HttpResponse, StreamingHttpRequest, StreamingHttpResponse,
+	WebSocketMessage,
+};
 use crate::connection::ws_send;
 use crate::context::SharedContext;
 use crate::handle::EnvoyHandle;
@@ -48,6 +52,7 @@ pub enum ToActor {
 		message_id: protocol::MessageId,
 		path: String,
 		headers: BTreeMap<String, String>,
+		send_window: Option<u32>,
 	},
 	WsMsg {
 		message_id: protocol::MessageId,
@@ -57,6 +62,10 @@ pub enum ToActor {
 		message_id: protocol::MessageId,
 		close: protocol::ToEnvoyWebSocketClose,
 	},
+	WsCredit {
+		message_id: protocol::MessageId,
+		credit: protocol::ToEnvoyWebSocketCredit,
+	},
 	HwsAck {
 		gateway_id: protocol::GatewayId,
 		request_id: protocol::RequestId,
@@ -66,7 +75,7 @@ pub enum ToActor {
 
 struct PendingRequest {
 	envoy_message_index: u16,
-	body_tx: Option<mpsc::UnboundedSender<Vec<u8>>>,
+	body_tx: Option<mpsc::UnboundedSender<BodyFrame>>,
 }
 
 struct WsEntry {
@@ -74,6 +83,52 @@ struct WsEntry {
 	rivet_message_index: u16,
 	ws_handler: Option<crate::config::WebSocketHandler>,
 	outgoing_tx: mpsc::UnboundedSender<crate::config::WsOutgoing>,
+	/// Whether the gateway expects credit for messages handled by the actor.
+	flow_control: bool,
+	send_credit: Option<SendCredit>,
+}
+
+impl Drop for WsEntry {
+	fn drop(&mut self) {
+		// Wake the outgoing task if it is waiting for credit
+		if let Some(send_credit) = &self.send_credit {
+			send_credit.permits.close();
+		}
+	}
+}
+
+/// Send window granted by the gateway. Outgoing messages wait for credit once it is used up.
+#[derive(Clone)]
+struct SendCredit {
+	window: u32,
+	permits: Arc<Semaphore>,
+}
+
+impl SendCredit {
+	fn new(window: u32) -> Self {
+		SendCredit {
+			window,
+			permits: Arc::new(Semaphore::new(window as usize)),
+		}
+	}
+
+	/// Returns false if the websocket closed while waiting.
+	async fn acquire(&self, len: usize) -> bool {
+		// Messages larger than the window wait for the whole window
+		let permits = len.min(self.window as usize) as u32;
+		match self.permits.acquire_many(permits).await {
+			Ok(permit) => {
+				permit.forget();
+				true
+			}
+			Err(_) => false,
+		}
+	}
+
+	fn release(&self, bytes: u32) {
+		let free = (self.window as usize).saturating_sub(self.permits.available_permits());
+		self.permits.add_permits((bytes as usize).min(free));
+	}
 }
 
 struct ActorContext {
@@ -338,8 +393,10 @@ async fn actor_inner(
 						message_id,
 						path,
 						headers,
+						send_window,
 					} => {
-						handle_ws_open(&mut ctx, &handle, message_id, path, headers).await;
+						handle_ws_open(&mut ctx, &handle, message_id, path, headers, send_window)
+							.await;
 					}
 					ToActor::WsMsg { message_id, msg } => {
 						handle_ws_message(&mut ctx, message_id, msg).await;
@@ -347,6 +404,9 @@ async fn actor_inner(
 					ToActor::WsClose { message_id, close } => {
 						handle_ws_close(&mut ctx, message_id, close).await;
 					}
+					ToActor::WsCredit { message_id, credit } => {
+						handle_ws_credit(&mut ctx, message_id, credit);
+					}
 					ToActor::HwsAck {
 						gateway_id,
 						request_id,
@@ -528,60 +588,95 @@ fn handle_req_start(
 		.map(|(k, v)| (k.clone(), v.clone()))
 		.collect();
 
-	let body_stream = if req.stream {
-		let (body_tx, body_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+	let shared = ctx.shared.clone();
+	let handle_clone = handle.clone();
+	let actor_id = ctx.actor_id.clone();
+	let gateway_id = message_id.gateway_id;
+	let request_id = message_id.request_id;
+	let request_guard = ActiveHttpRequestGuard::new(ctx.active_http_request_count.clone());
+
+	let is_stream = req.stream;
+	let task: crate::config::BoxFuture<()> = if is_stream {
+		let (body_tx, body_rx) = mpsc::unbounded_channel::<BodyFrame>();
 		if let Some(pending) = ctx
 			.pending_requests
 			.get_mut(&[&message_id.gateway_id, &message_id.request_id])
 		{
-			pending.body_tx = Some(body_tx);
+			pending.body_tx = Some(body_tx.clone());
 		}
-		Some(body_rx)
-	} else {
-		None
-	};
 
-	let request = HttpRequest {
-		method: req.method,
-		path: req.path,
-		headers,
-		body: req.body,
-		body_stream,
-	};
+		// The request start may carry the first body chunk
+		if let Some(body) = req.body.filter(|body| !body.is_empty()) {
+			let _ = body_tx.send(BodyFrame::Data(body));
+		}
+		drop(body_tx);
 
-	let shared = ctx.shared.clone();
-	let handle_clone = handle.clone();
-	let actor_id = ctx.actor_id.clone();
-	let gateway_id = message_id.gateway_id;
-	let request_id = message_id.request_id;
-	let request_guard = ActiveHttpRequestGuard::new(ctx.active_http_request_count.clone());
+		let request = StreamingHttpRequest {
+			method: req.method,
+			path: req.path,
+			headers,
+			body: body_rx,
+		};
 
-	let task = async move {
-		let _request_guard = request_guard;
-		let response = shared
-			.config
-			.callbacks
-			.fetch(handle_clone, actor_id, gateway_id, request_id, request)
-			.await;
+		Box::pin(
+			async move {
+				let _request_guard = request_guard;
+				let response = shared
+					.config
+					.callbacks
+					.fetch_stream(handle_clone, actor_id, gateway_id, request_id, request)
+					.await;
 
-		match response {
-			Ok(response) => {
-				send_response(&shared, gateway_id, request_id, response).await;
+				match response {
+					Ok(response) => {
+						send_streaming_response(&shared, gateway_id, request_id, response).await;
+					}
+					Err(error) => {
+						tracing::error!(?error, "fetch failed");
+						send_fetch_error_response(&shared, gateway_id, request_id).await;
+					}
+				}
 			}
-			Err(error) => {
-				tracing::error!(?error, "fetch failed");
-				send_fetch_error_response(&shared, gateway_id, request_id).await;
+			.in_current_span(),
+		)
+	} else {
+		let request = HttpRequest {
+			method: req.method,
+			path: req.path,
+			headers,
+			body: req.body,
+			body_stream: None,
+		};
+
+		Box::pin(
+			async move {
+				let _request_guard = request_guard;
+				let response = shared
+					.config
+					.callbacks
+					.fetch(handle_clone, actor_id, gateway_id, request_id, request)
+					.await;
+
+				match response {
+					Ok(response) => {
+						send_response(&shared, gateway_id, request_id, response).await;
+					}
+					Err(error) => {
+						tracing::error!(?error, "fetch failed");
+						send_fetch_error_response(&shared, gateway_id, request_id).await;
+					}
+				}
 			}
-		}
-	}
-	.in_current_span();
+			.in_current_span(),
+		)
+	};
 
 	#[cfg(target_arch = "wasm32")]
 	http_request_tasks.spawn_local(task);
 	#[cfg(not(target_arch = "wasm32"))]
 	http_request_tasks.spawn(task);
 
-	if !req.stream {
+	if !is_stream {
 		ctx.pending_requests
 			.remove(&[&message_id.gateway_id, &message_id.request_id]);
 	}
@@ -629,7 +724,12 @@ fn handle_req_chunk(
 		.get(&[&message_id.gateway_id, &message_id.request_id]);
 	if let Some(pending) = pending {
 		if let Some(body_tx) = &pending.body_tx {
-			let _ = body_tx.send(chunk.body);
+			if !chunk.body.is_empty() {
+				let _ = body_tx.send(BodyFrame::Data(chunk.body));
+			}
+			if let Some(trailers) = chunk.trailers {
+				let _ = body_tx.send(BodyFrame::Trailers(trailers));
+			}
 		} else {
 			tracing::warn!("received chunk for pending request without stream controller");
 		}
@@ -644,8 +744,13 @@ fn handle_req_chunk(
 }
 
 fn handle_req_abort(ctx: &mut ActorContext, message_id: protocol::MessageId) {
-	ctx.pending_requests
-		.remove(&[&message_id.gateway_id, &message_id.request_id]);
+	if let Some(pending) = ctx
+		.pending_requests
+		.remove(&[&message_id.gateway_id, &message_id.request_id])
+		&& let Some(body_tx) = pending.body_tx
+	{
+		let _ = body_tx.send(BodyFrame::Abort);
+	}
 }
 
 fn spawn_ws_outgoing_task(
@@ -653,6 +758,7 @@ fn spawn_ws_outgoing_task(
 	gateway_id: protocol::GatewayId,
 	request_id: protocol::RequestId,
 	mut outgoing_rx: mpsc::UnboundedReceiver<crate::config::WsOutgoing>,
+	send_credit: Option<SendCredit>,
 ) {
 	let ws_task = async move {
 		let mut idx: u16 = 0;
@@ -660,6 +766,12 @@ fn spawn_ws_outgoing_task(
 			idx += 1;
 			match msg {
 				crate::config::WsOutgoing::Message { data, binary } => {
+					if let Some(send_credit) = &send_credit
+						&& !send_credit.acquire(data.len()).await
+					{
+						break;
+					}
+
 					ws_send(
 						&shared,
 						protocol::ToRivet::ToRivetTunnelMessage(protocol::ToRivetTunnelMessage {
@@ -712,6 +824,7 @@ async fn handle_ws_open(
 	message_id: protocol::MessageId,
 	path: String,
 	headers: BTreeMap<String, String>,
+	send_window: Option<u32>,
 ) {
 	let restored_ws = ctx
 		.ws_entries
@@ -827,6 +940,7 @@ async fn handle_ws_open(
 
 	match ws_result {
 		Ok(ws_handler) => {
+			let send_credit = send_window.map(SendCredit::new);
 			ctx.ws_entries.insert(
 				&[&message_id.gateway_id, &message_id.request_id],
 				WsEntry {
@@ -834,6 +948,8 @@ async fn handle_ws_open(
 					rivet_message_index: message_id.message_index,
 					ws_handler: Some(ws_handler),
 					outgoing_tx,
+					flow_control: true,
+					send_credit: send_credit.clone(),
 				},
 			);
 
@@ -842,6 +958,7 @@ async fn handle_ws_open(
 				message_id.gateway_id,
 				message_id.request_id,
 				outgoing_rx,
+				send_credit,
 			);
 
 			// Gateway wake flows still wait for a websocket-open ack before they
@@ -854,6 +971,7 @@ async fn handle_ws_open(
 				protocol::ToRivetTunnelMessageKind::ToRivetWebSocketOpen(
 					protocol::ToRivetWebSocketOpen {
 						can_hibernate: is_hibernatable,
+						flow_control: true,
 					},
 				),
 			)
@@ -955,6 +1073,9 @@ async fn handle_ws_message(
 			ws.rivet_message_index = received_index;
 		}
 
+		let flow_control = ws.flow_control;
+		let data_len = msg.data.len();
+
 		if let Some(handler) = &ws.ws_handler {
 			let sender = crate::config::WebSocketSender {
 				tx: ws.outgoing_tx.clone(),
@@ -969,11 +1090,40 @@ async fn handle_ws_message(
 			};
 			(handler.on_message)(ws_msg).await;
 		}
+
+		// Let the gateway read more from the client
+		if flow_control {
+			send_actor_message(
+				ctx,
+				message_id.gateway_id,
+				message_id.request_id,
+				protocol::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(
+					protocol::ToRivetWebSocketCredit {
+						bytes: data_len as u32,
+					},
+				),
+			)
+			.await;
+		}
 	} else {
 		tracing::warn!("received message for unknown ws");
 	}
 }
 
+fn handle_ws_credit(
+	ctx: &mut ActorContext,
+	message_id: protocol::MessageId,
+	credit: protocol::ToEnvoyWebSocketCredit,
+) {
+	let ws = ctx
+		.ws_entries
+		.get(&[&message_id.gateway_id, &message_id.request_id]);
+
+	if let Some(send_credit) = ws.and_then(|ws| ws.send_credit.as_ref()) {
+		send_credit.release(credit.bytes);
+	}
+}
+
 async fn handle_ws_close(
 	ctx: &mut ActorContext,
 	message_id: protocol::MessageId,
@@ -1064,7 +1214,9 @@ async fn handle_hws_restore(
 						hib_req.gateway_id,
 						hib_req.request_id,
 						hws_outgoing_rx,
+						None,
 					);
+					// Flow control is not negotiated for restored websockets
 					ctx.ws_entries.insert(
 						&[&hib_req.gateway_id, &hib_req.request_id],
 						WsEntry {
@@ -1072,6 +1224,8 @@ async fn handle_hws_restore(
 							rivet_message_index: meta.rivet_message_index,
 							ws_handler: Some(ws_handler),
 							outgoing_tx: hws_outgoing_tx,
+							flow_control: false,
+							send_credit: None,
 						},
 					);
 					// Gateway wake flows wait for the websocket-open ack before
@@ -1083,6 +1237,7 @@ async fn handle_hws_restore(
 						protocol::ToRivetTunnelMessageKind::ToRivetWebSocketOpen(
 							protocol::ToRivetWebSocketOpen {
 								can_hibernate: true,
+								flow_control: false,
 							},
 						),
 					)
@@ -1347,6 +1502,7 @@ async fn send_response(
 						protocol::ToRivetResponseChunk {
 							body: chunk.data,
 							finish,
+							trailers: None,
 						},
 					),
 				}),
@@ -1360,6 +1516,83 @@ async fn send_response(
 	}
 }
 
+async fn send_streaming_response(
+	shared: &SharedContext,
+	gateway_id: protocol::GatewayId,
+	request_id: protocol::RequestId,
+	mut response: StreamingHttpResponse,
+) {
+	let send = |message_index: u16, message_kind: protocol::ToRivetTunnelMessageKind| {
+		ws_send(
+			shared,
+			protocol::ToRivet::ToRivetTunnelMessage(protocol::ToRivetTunnelMessage {
+				message_id: protocol::MessageId {
+					gateway_id,
+					request_id,
+					message_index,
+				},
+				message_kind,
+			}),
+		)
+	};
+
+	send(
+		0,
+		protocol::ToRivetTunnelMessageKind::ToRivetResponseStart(protocol::ToRivetResponseStart {
+			status: response.status,
+			headers: response.headers,
+			body: None,
+			stream: true,
+		}),
+	)
+	.await;
+
+	let mut message_index: u16 = 1;
+	loop {
+		let message_kind = match response.body.recv().await {
+			Some(BodyFrame::Data(data)) => {
+				protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+					protocol::ToRivetResponseChunk {
+						body: data,
+						finish: false,
+						trailers: None,
+					},
+				)
+			}
+			Some(BodyFrame::Trailers(trailers)) => {
+				protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+					protocol::ToRivetResponseChunk {
+						body: Vec::new(),
+						finish: true,
+						trailers: Some(trailers),
+					},
+				)
+			}
+			Some(BodyFrame::Abort) => protocol::ToRivetTunnelMessageKind::ToRivetResponseAbort,
+			None => protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+				protocol::ToRivetResponseChunk {
+					body: Vec::new(),
+					finish: true,
+					trailers: None,
+				},
+			),
+		};
+		let last = !matches!(
+			message_kind,
+			protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+				protocol::ToRivetResponseChunk { finish: false, .. }
+			)
+		);
+
+		send(message_index, message_kind).await;
+		message_index = message_index.wrapping_add(1);
+
+		if last {
+			break;
+		}
+	}
+}
+
 async fn send_fetch_error_response(
 	shared: &SharedContext,
 	gateway_id: protocol::GatewayId,
@@ -1408,6 +1641,7 @@ mod tests {
 	use tokio::sync::Notify;
 	use tokio::sync::oneshot;
 	use tokio::task::yield_now;
+	use vbare::OwnedVersionedData;
 
 	use super::*;
 	use crate::config::{BoxFuture, EnvoyCallbacks, WebSocketHandler, WebSocketSender};
@@ -1646,6 +1880,108 @@ mod tests {
 		}
 	}
 
+	/// Echoes streaming request bodies back as they arrive.
+	struct EchoStreamCallbacks;
+
+	impl EnvoyCallbacks for EchoStreamCallbacks {
+		fn on_actor_start(
+			&self,
+			_handle: EnvoyHandle,
+			_actor_id: String,
+			_generation: u32,
+			_config: protocol::ActorConfig,
+			_preloaded_kv: Option<protocol::PreloadedKv>,
+		) -> BoxFuture<anyhow::Result<()>> {
+			Box::pin(async { Ok(()) })
+		}
+
+		fn on_shutdown(&self) {}
+
+		fn fetch(
+			&self,
+			_handle: EnvoyHandle,
+			_actor_id: String,
+			_gateway_id: protocol::GatewayId,
+			_request_id: protocol::RequestId,
+			_request: HttpRequest,
+		) -> BoxFuture<anyhow::Result<HttpResponse>> {
+			Box::pin(async { anyhow::bail!("fetch should not be called for streamed requests") })
+		}
+
+		fn fetch_stream(
+			&self,
+			_handle: EnvoyHandle,
+			_actor_id: String,
+			_gateway_id: protocol::GatewayId,
+			_request_id: protocol::RequestId,
+			mut request: StreamingHttpRequest,
+		) -> BoxFuture<anyhow::Result<StreamingHttpResponse>> {
+			Box::pin(async move {
+				let (body_tx, body_rx) = mpsc::unbounded_channel();
+				tokio::spawn(async move {
+					while let Some(frame) = request.body.recv().await {
+						let _ = body_tx.send(frame);
+					}
+				});
+
+				Ok(StreamingHttpResponse {
+					status: 200,
+					headers: HashMap::from([(
+						"content-type".to_string(),
+						"application/grpc".to_string(),
+					)]),
+					body: body_rx,
+				})
+			})
+		}
+
+		fn websocket(
+			&self,
+			_handle: EnvoyHandle,
+			_actor_id: String,
+			_gateway_id: protocol::GatewayId,
+			_request_id: protocol::RequestId,
+			_request: HttpRequest,
+			_path: String,
+			_headers: HashMap<String, String>,
+			_is_hibernatable: bool,
+			_is_restoring_hibernatable: bool,
+			_sender: WebSocketSender,
+		) -> BoxFuture<anyhow::Result<WebSocketHandler>> {
+			Box::pin(async { anyhow::bail!("websocket should not be called in streaming test") })
+		}
+
+		fn can_hibernate(
+			&self,
+			_actor_id: &str,
+			_gateway_id: &protocol::GatewayId,
+			_request_id: &protocol::RequestId,
+			_request: &HttpRequest,
+		) -> BoxFuture<anyhow::Result<bool>> {
+			Box::pin(async { Ok(false) })
+		}
+	}
+
+	async fn recv_tunnel_message(
+		ws_rx: &mut mpsc::UnboundedReceiver<WsTxMessage>,
+	) -> protocol::ToRivetTunnelMessageKind {
+		let msg = tokio::time::timeout(Duration::from_secs(2), ws_rx.recv())
+			.await
+			.expect("timed out waiting for tunnel message")
+			.expect("ws channel closed");
+		let WsTxMessage::Send(data) = msg else {
+			panic!("expected ws send");
+		};
+		let protocol::ToRivet::ToRivetTunnelMessage(msg) =
+			protocol::versioned::ToRivet::deserialize(&data, protocol::PROTOCOL_VERSION)
+				.expect("failed to decode message")
+		else {
+			panic!("expected tunnel message");
+		};
+
+		msg.message_kind
+	}
+
 	fn build_shared_context(
 		callbacks: Arc<dyn EnvoyCallbacks>,
 	) -> (Arc<SharedContext>, mpsc::UnboundedReceiver<ToEnvoyMessage>) {
@@ -2050,4 +2386,135 @@ mod tests {
 		assert!(waiter.await.expect("waiter should join"));
 		assert_eq!(wake_count.load(Ordering::SeqCst), 1);
 	}
+
+	#[tokio::test]
+	async fn send_credit_waits_for_gateway_credit() {
+		let send_credit = SendCredit::new(10);
+
+		assert!(send_credit.acquire(6).await);
+		let blocked = tokio::spawn({
+			let send_credit = send_credit.clone();
+			async move { send_credit.acquire(6).await }
+		});
+		yield_now().await;
+		assert!(
+			!blocked.is_finished(),
+			"send should wait until the gateway returns credit"
+		);
+
+		send_credit.release(6);
+		assert!(blocked.await.expect("send should join"));
+
+		// Credit never grows the window past its size
+		send_credit.release(100);
+		assert_eq!(send_credit.permits.available_permits(), 10);
+
+		// Messages larger than the window only wait for the whole window
+		assert!(send_credit.acquire(25).await);
+		assert_eq!(send_credit.permits.available_permits(), 0);
+	}
+
+	#[tokio::test]
+	async fn send_credit_stops_waiting_when_closed() {
+		let send_credit = SendCredit::new(4);
+		assert!(send_credit.acquire(4).await);
+
+		let blocked = tokio::spawn({
+			let send_credit = send_credit.clone();
+			async move { send_credit.acquire(1).await }
+		});
+		yield_now().await;
+
+		send_credit.permits.close();
+		assert!(!blocked.await.expect("send should join"));
+	}
+
+	#[tokio::test]
+	async fn fetch_stream_echoes_body_before_request_finishes() {
+		let (shared, _envoy_rx) = build_shared_context(Arc::new(EchoStreamCallbacks));
+		let (ws_tx, mut ws_rx) = mpsc::unbounded_channel();
+		*shared.ws_tx.lock().await = Some(ws_tx);
+		let (actor_tx, _active_http_request_count) = create_actor(
+			shared,
+			"actor-1".to_string(),
+			1,
+			actor_config(),
+			Vec::new(),
+			None,
+		);
+
+		actor_tx
+			.send(ToActor::ReqStart {
+				message_id: message_id(),
+				req: protocol::ToEnvoyRequestStart {
+					method: "POST".to_string(),
+					body: Some(b"first".to_vec()),
+					stream: true,
+					..request_start()
+				},
+			})
+			.expect("failed to send request start");
+
+		let protocol::ToRivetTunnelMessageKind::ToRivetResponseStart(start) =
+			recv_tunnel_message(&mut ws_rx).await
+		else {
+			panic!("expected response start");
+		};
+		assert_eq!(start.status, 200);
+		assert!(start.stream);
+		assert_eq!(
+			recv_tunnel_message(&mut ws_rx).await,
+			protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+				protocol::ToRivetResponseChunk {
+					body: b"first".to_vec(),
+					finish: false,
+					trailers: None,
+				}
+			)
+		);
+
+		// The response streams while the request body is still open
+		actor_tx
+			.send(ToActor::ReqChunk {
+				message_id: message_id(),
+				chunk: protocol::ToEnvoyRequestChunk {
+					body: b"second".to_vec(),
+					finish: false,
+					trailers: None,
+				},
+			})
+			.expect("failed to send request chunk");
+		assert_eq!(
+			recv_tunnel_message(&mut ws_rx).await,
+			protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+				protocol::ToRivetResponseChunk {
+					body: b"second".to_vec(),
+					finish: false,
+					trailers: None,
+				}
+			)
+		);
+
+		let trailers = HashMap::from([("grpc-status".to_string(), "0".to_string())]);
+		actor_tx
+			.send(ToActor::ReqChunk {
+				message_id: message_id(),
+				chunk: protocol::ToEnvoyRequestChunk {
+					body: Vec::new(),
+					finish: true,
+					trailers: Some(trailers.clone()),
+				},
+			})
+			.expect("failed to send request chunk");
+		assert_eq!(
+			recv_tunnel_message(&mut ws_rx).await,
+			protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+				protocol::ToRivetResponseChunk {
+					body: Vec::new(),
+					finish: true,
+					trailers: Some(trailers),
+				}
+			)
+		);
+	}
 }
@@ -8,6 +8,7 @@ use rivet_envoy_protocol as protocol;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::handle::EnvoyHandle;
+use crate::utils::spawn_detached;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
@@ -34,12 +35,74 @@ pub struct HttpResponse {
 	pub body_stream: Option<mpsc::UnboundedReceiver<ResponseChunk>>,
 }
 
+impl HttpResponse {
+	/// Converts into a streaming response. A buffered body is sent as a single data frame.
+	pub fn into_streaming(self) -> StreamingHttpResponse {
+		let (frame_tx, frame_rx) = mpsc::unbounded_channel();
+
+		let mut headers = self.headers;
+		if let Some(mut body_stream) = self.body_stream {
+			spawn_detached(async move {
+				while let Some(chunk) = body_stream.recv().await {
+					if !chunk.data.is_empty() && frame_tx.send(BodyFrame::Data(chunk.data)).is_err()
+					{
+						break;
+					}
+					if chunk.finish {
+						break;
+					}
+				}
+			});
+		} else if let Some(body) = self.body {
+			if !headers.contains_key("content-length") {
+				headers.insert("content-length".to_string(), body.len().to_string());
+			}
+			let _ = frame_tx.send(BodyFrame::Data(body));
+		}
+
+		StreamingHttpResponse {
+			status: self.status,
+			headers,
+			body: frame_rx,
+		}
+	}
+}
+
 /// A chunk in a streaming HTTP response.
 pub struct ResponseChunk {
 	pub data: Vec<u8>,
 	pub finish: bool,
 }
 
+/// HTTP request with a full-duplex streaming body, passed to `EnvoyCallbacks::fetch_stream`.
+pub struct StreamingHttpRequest {
+	pub method: String,
+	pub path: String,
+	pub headers: HashMap<String, String>,
+	/// Request body frames. The channel closes after the last frame.
+	pub body: mpsc::UnboundedReceiver<BodyFrame>,
+}
+
+/// HTTP response with a streaming body, returned from `EnvoyCallbacks::fetch_stream`. The
+/// response may be returned before the request body is fully read.
+pub struct StreamingHttpResponse {
+	pub status: u16,
+	pub headers: HashMap<String, String>,
+	/// Response body frames. The response finishes after a `Trailers` frame or once the sender is
+	/// dropped.
+	pub body: mpsc::UnboundedReceiver<BodyFrame>,
+}
+
+/// A frame in a streaming HTTP request or response body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BodyFrame {
+	Data(Vec<u8>),
+	/// HTTP trailers. Always the last frame, used by gRPC to send the call status.
+	Trailers(HashMap<String, String>),
+	/// The body was aborted by the other side. Always the last frame.
+	Abort,
+}
+
 pub struct EnvoyConfig {
 	pub version: u32,
 	pub endpoint: String,
@@ -150,6 +213,52 @@ pub trait EnvoyCallbacks: Send + Sync + 'static {
 		request: HttpRequest,
 	) -> BoxFuture<anyhow::Result<HttpResponse>>;
 
+	/// Handles requests whose body the gateway streams instead of buffering, such as gRPC. Bodies
+	/// are full-duplex and carry trailers.
+	///
+	/// Defaults to calling `fetch` with the request body on `HttpRequest::body_stream`. Trailers
+	/// are dropped.
+	fn fetch_stream(
+		&self,
+		handle: EnvoyHandle,
+		actor_id: String,
+		gateway_id: protocol::GatewayId,
+		request_id: protocol::RequestId,
+		request: StreamingHttpRequest,
+	) -> BoxFuture<anyhow::Result<StreamingHttpResponse>> {
+		let StreamingHttpRequest {
+			method,
+			path,
+			headers,
+			body: mut frames,
+		} = request;
+
+		let (body_tx, body_rx) = mpsc::unbounded_channel();
+		spawn_detached(async move {
+			while let Some(BodyFrame::Data(data)) = frames.recv().await {
+				if body_tx.send(data).is_err() {
+					break;
+				}
+			}
+		});
+
+		let response = self.fetch(
+			handle,
+			actor_id,
+			gateway_id,
+			request_id,
+			HttpRequest {
+				method,
+				path,
+				headers,
+				body: None,
+				body_stream: Some(body_rx),
+			},
+		);
+
+		Box::pin(async move { Ok(response.await?.into_streaming()) })
+	}
+
 	fn websocket(
 		&self,
 		handle: EnvoyHandle,
@@ -507,6 +507,19 @@ impl EnvoyHandle {
 		}
 	}
 
+	pub async fn sqlite_get_row_change_log(
+		&self,
+		request: protocol::SqliteGetRowChangeLogRequest,
+	) -> anyhow::Result<protocol::SqliteGetRowChangeLogResponse> {
+		match self
+			.send_sqlite_request(SqliteRequest::GetRowChangeLog(request))
+			.await?
+		{
+			SqliteResponse::GetRowChangeLog(response) => Ok(response),
+			_ => anyhow::bail!("unexpected sqlite get_row_change_log response type"),
+		}
+	}
+
 	pub async fn remote_sqlite_exec(
 		&self,
 		request: protocol::SqliteExecRequest,
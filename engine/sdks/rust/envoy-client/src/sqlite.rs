@@ -14,6 +14,7 @@ use crate::utils::{
 pub enum SqliteRequest {
 	GetPages(protocol::SqliteGetPagesRequest),
 	Commit(protocol::SqliteCommitRequest),
+	GetRowChangeLog(protocol::SqliteGetRowChangeLogRequest),
 }
 
 impl SqliteRequest {
@@ -21,6 +22,7 @@ impl SqliteRequest {
 		match self {
 			SqliteRequest::GetPages(_) => "get_pages",
 			SqliteRequest::Commit(_) => "commit",
+			SqliteRequest::GetRowChangeLog(_) => "get_row_change_log",
 		}
 	}
 }
@@ -28,6 +30,7 @@ impl SqliteRequest {
 pub enum SqliteResponse {
 	GetPages(protocol::SqliteGetPagesResponse),
 	Commit(protocol::SqliteCommitResponse),
+	GetRowChangeLog(protocol::SqliteGetRowChangeLogResponse),
 }
 
 #[derive(Clone, Debug)]
@@ -160,6 +163,18 @@ pub async fn handle_sqlite_commit_response(
 	);
 }
 
+pub async fn handle_sqlite_get_row_change_log_response(
+	ctx: &mut EnvoyContext,
+	response: protocol::ToEnvoySqliteGetRowChangeLogResponse,
+) {
+	handle_sqlite_response(
+		ctx,
+		response.request_id,
+		SqliteResponse::GetRowChangeLog(response.data),
+		"sqlite_get_row_change_log",
+	);
+}
+
 pub async fn handle_remote_sqlite_exec_response(
 	ctx: &mut EnvoyContext,
 	response: protocol::ToEnvoySqliteExecResponse,
@@ -256,6 +271,11 @@ pub async fn send_single_sqlite_request(ctx: &mut EnvoyContext, request_id: u32)
 			SqliteRequest::Commit(data) => protocol::ToRivet::ToRivetSqliteCommitRequest(
 				protocol::ToRivetSqliteCommitRequest { request_id, data },
 			),
+			SqliteRequest::GetRowChangeLog(data) => {
+				protocol::ToRivet::ToRivetSqliteGetRowChangeLogRequest(
+					protocol::ToRivetSqliteGetRowChangeLogRequest { request_id, data },
+				)
+			}
 		};
 
 	ws_send(&ctx.shared, message).await;
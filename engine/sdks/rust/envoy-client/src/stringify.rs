@@ -40,10 +40,15 @@ pub fn stringify_to_rivet_tunnel_message_kind(kind: &protocol::ToRivetTunnelMess
 			)
 		}
 		protocol::ToRivetTunnelMessageKind::ToRivetResponseChunk(val) => {
+			let trailers_str = match &val.trailers {
+				Some(trailers) => stringify_map(trailers),
+				None => "null".to_string(),
+			};
 			format!(
-				"ToRivetResponseChunk{{body: {}, finish: {}}}",
+				"ToRivetResponseChunk{{body: {}, finish: {}, trailers: {}}}",
 				stringify_bytes(&val.body),
-				val.finish
+				val.finish,
+				trailers_str
 			)
 		}
 		protocol::ToRivetTunnelMessageKind::ToRivetResponseAbort => {
@@ -51,8 +56,8 @@ pub fn stringify_to_rivet_tunnel_message_kind(kind: &protocol::ToRivetTunnelMess
 		}
 		protocol::ToRivetTunnelMessageKind::ToRivetWebSocketOpen(val) => {
 			format!(
-				"ToRivetWebSocketOpen{{canHibernate: {}}}",
-				val.can_hibernate
+				"ToRivetWebSocketOpen{{canHibernate: {}, flowControl: {}}}",
+				val.can_hibernate, val.flow_control
 			)
 		}
 		protocol::ToRivetTunnelMessageKind::ToRivetWebSocketMessage(val) => {
@@ -79,6 +84,9 @@ pub fn stringify_to_rivet_tunnel_message_kind(kind: &protocol::ToRivetTunnelMess
 				val.hibernate
 			)
 		}
+		protocol::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(val) => {
+			format!("ToRivetWebSocketCredit{{bytes: {}}}", val.bytes)
+		}
 	}
 }
 
@@ -100,18 +108,27 @@ pub fn stringify_to_envoy_tunnel_message_kind(kind: &protocol::ToEnvoyTunnelMess
 			)
 		}
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestChunk(val) => {
+			let trailers_str = match &val.trailers {
+				Some(trailers) => stringify_map(trailers),
+				None => "null".to_string(),
+			};
 			format!(
-				"ToEnvoyRequestChunk{{body: {}, finish: {}}}",
+				"ToEnvoyRequestChunk{{body: {}, finish: {}, trailers: {}}}",
 				stringify_bytes(&val.body),
-				val.finish
+				val.finish,
+				trailers_str
 			)
 		}
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyRequestAbort => {
 			"ToEnvoyRequestAbort".to_string()
 		}
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(val) => {
+			let send_window_str = match &val.send_window {
+				Some(w) => w.to_string(),
+				None => "null".to_string(),
+			};
 			format!(
-				"ToEnvoyWebSocketOpen{{actorId: \"{}\", path: \"{}\", headers: {}}}",
+				"ToEnvoyWebSocketOpen{{actorId: \"{}\", path: \"{}\", headers: {}, sendWindow: {send_window_str}}}",
 				val.actor_id,
 				val.path,
 				stringify_map(&val.headers)
@@ -135,6 +152,9 @@ pub fn stringify_to_envoy_tunnel_message_kind(kind: &protocol::ToEnvoyTunnelMess
 			};
 			format!("ToEnvoyWebSocketClose{{code: {code_str}, reason: {reason_str}}}")
 		}
+		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(val) => {
+			format!("ToEnvoyWebSocketCredit{{bytes: {}}}", val.bytes)
+		}
 	}
 }
 
@@ -275,6 +295,12 @@ pub fn stringify_to_rivet(message: &protocol::ToRivet) -> String {
 				val.request_id
 			)
 		}
+		protocol::ToRivet::ToRivetSqliteGetRowChangeLogRequest(val) => {
+			format!(
+				"ToRivetSqliteGetRowChangeLogRequest{{requestId: {}, actorId: \"{}\"}}",
+				val.request_id, val.data.actor_id
+			)
+		}
 		protocol::ToRivet::ToRivetSqliteExecRequest(val) => {
 			format!(
 				"ToRivetSqliteExecRequest{{requestId: {}, actorId: \"{}\", generation: {}}}",
@@ -348,6 +374,12 @@ pub fn stringify_to_envoy(message: &protocol::ToEnvoy) -> String {
 				val.request_id
 			)
 		}
+		protocol::ToEnvoy::ToEnvoySqliteGetRowChangeLogResponse(val) => {
+			format!(
+				"ToEnvoySqliteGetRowChangeLogResponse{{requestId: {}}}",
+				val.request_id
+			)
+		}
 		protocol::ToEnvoy::ToEnvoySqliteExecResponse(val) => {
 			format!("ToEnvoySqliteExecResponse{{requestId: {}}}", val.request_id)
 		}
@@ -41,6 +41,9 @@ pub async fn handle_tunnel_message(ctx: &mut EnvoyContext, msg: protocol::ToEnvo
 		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketClose(close) => {
 			handle_ws_close(ctx, message_id, close);
 		}
+		protocol::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(credit) => {
+			handle_ws_credit(ctx, message_id, credit);
+		}
 	}
 }
 
@@ -166,6 +169,7 @@ async fn handle_ws_open(
 		message_id,
 		path: open.path,
 		headers,
+		send_window: open.send_window,
 	});
 }
 
@@ -192,6 +196,24 @@ fn handle_ws_message(
 	}
 }
 
+fn handle_ws_credit(
+	ctx: &mut EnvoyContext,
+	message_id: protocol::MessageId,
+	credit: protocol::ToEnvoyWebSocketCredit,
+) {
+	let actor_id = ctx
+		.request_to_actor
+		.get(&[&message_id.gateway_id, &message_id.request_id])
+		.cloned();
+	if let Some(actor_id) = &actor_id {
+		if let Some(actor) = ctx.get_actor(actor_id, None) {
+			let _ = actor
+				.handle
+				.send(crate::actor::ToActor::WsCredit { message_id, credit });
+		}
+	}
+}
+
 fn handle_ws_close(
 	ctx: &mut EnvoyContext,
 	message_id: protocol::MessageId,
@@ -3,6 +3,6 @@ pub mod util;
 pub mod versioned;
 
 // Re-export latest
-pub use generated::v6::*;
+pub use generated::v7::*;
 
 pub use generated::PROTOCOL_VERSION;
@@ -3,7 +3,7 @@ use std::{error::Error, fmt};
 use anyhow::{Result, bail};
 use vbare::OwnedVersionedData;
 
-use crate::generated::{v1, v2, v3, v4, v5, v6};
+use crate::generated::{v1, v2, v3, v4, v5, v6, v7};
 
 mod v1_to_v2;
 mod v2_to_v1;
@@ -15,6 +15,8 @@ mod v4_to_v5;
 mod v5_to_v4;
 mod v5_to_v6;
 mod v6_to_v5;
+mod v6_to_v7;
+mod v7_to_v6;
 
 // MARK: Protocol compatibility errors
 
@@ -25,6 +27,9 @@ pub enum ProtocolCompatibilityFeature {
 	SqlitePageRange,
 	RemoteSqliteExecution,
 	RemoteSqliteBatchExecution,
+	WebSocketFlowControl,
+	HttpTrailers,
+	SqliteRowChanges,
 }
 
 impl ProtocolCompatibilityFeature {
@@ -47,6 +52,12 @@ impl ProtocolCompatibilityFeature {
 				ProtocolCompatibilityDirection::ToEnvoy => "remote sqlite batch responses",
 				ProtocolCompatibilityDirection::ToRivet => "remote sqlite batch requests",
 			},
+			ProtocolCompatibilityFeature::WebSocketFlowControl => "websocket flow control",
+			ProtocolCompatibilityFeature::HttpTrailers => match direction {
+				ProtocolCompatibilityDirection::ToEnvoy => "http request trailers",
+				ProtocolCompatibilityDirection::ToRivet => "http response trailers",
+			},
+			ProtocolCompatibilityFeature::SqliteRowChanges => "sqlite row change logs",
 		}
 	}
 }
@@ -73,6 +84,9 @@ impl fmt::Display for ProtocolCompatibilityError {
 			| ProtocolCompatibilityFeature::SqlitePageRange
 			| ProtocolCompatibilityFeature::RemoteSqliteExecution => "require",
 			ProtocolCompatibilityFeature::RemoteSqliteBatchExecution => "require",
+			ProtocolCompatibilityFeature::WebSocketFlowControl => "requires",
+			ProtocolCompatibilityFeature::HttpTrailers => "require",
+			ProtocolCompatibilityFeature::SqliteRowChanges => "require",
 		};
 		write!(
 			f,
@@ -111,18 +125,19 @@ pub enum ToEnvoy {
 	V4(v4::ToEnvoy),
 	V5(v5::ToEnvoy),
 	V6(v6::ToEnvoy),
+	V7(v7::ToEnvoy),
 }
 
 impl OwnedVersionedData for ToEnvoy {
-	type Latest = v6::ToEnvoy;
+	type Latest = v7::ToEnvoy;
 
 	fn wrap_latest(latest: Self::Latest) -> Self {
-		Self::V6(latest)
+		Self::V7(latest)
 	}
 
 	fn unwrap_latest(self) -> Result<Self::Latest> {
 		match self {
-			Self::V6(x) => Ok(x),
+			Self::V7(x) => Ok(x),
 			_ => bail!("version not latest"),
 		}
 	}
@@ -135,6 +150,7 @@ impl OwnedVersionedData for ToEnvoy {
 			4 => Ok(Self::V4(serde_bare::from_slice(payload)?)),
 			5 => Ok(Self::V5(serde_bare::from_slice(payload)?)),
 			6 => Ok(Self::V6(serde_bare::from_slice(payload)?)),
+			7 => Ok(Self::V7(serde_bare::from_slice(payload)?)),
 			_ => bail!("invalid version: {version}"),
 		}
 	}
@@ -147,6 +163,7 @@ impl OwnedVersionedData for ToEnvoy {
 			Self::V4(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V5(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V6(x) => serde_bare::to_vec(&x).map_err(Into::into),
+			Self::V7(x) => serde_bare::to_vec(&x).map_err(Into::into),
 		}
 	}
 
@@ -157,11 +174,13 @@ impl OwnedVersionedData for ToEnvoy {
 			Self::v3_to_v4,
 			Self::v4_to_v5,
 			Self::v5_to_v6,
+			Self::v6_to_v7,
 		]
 	}
 
 	fn serialize_converters() -> Vec<impl Fn(Self) -> Result<Self>> {
 		vec![
+			Self::v7_to_v6,
 			Self::v6_to_v5,
 			Self::v5_to_v4,
 			Self::v4_to_v3,
@@ -232,6 +251,18 @@ impl ToEnvoy {
 			_ => bail!("unexpected version"),
 		}
 	}
+	fn v6_to_v7(self) -> Result<Self> {
+		match self {
+			Self::V6(x) => Ok(Self::V7(v6_to_v7::convert_to_envoy_v6_to_v7(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
+	fn v7_to_v6(self) -> Result<Self> {
+		match self {
+			Self::V7(x) => Ok(Self::V6(v7_to_v6::convert_to_envoy_v7_to_v6(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
 }
 
 // MARK: ToRivet
@@ -243,18 +274,19 @@ pub enum ToRivet {
 	V4(v4::ToRivet),
 	V5(v5::ToRivet),
 	V6(v6::ToRivet),
+	V7(v7::ToRivet),
 }
 
 impl OwnedVersionedData for ToRivet {
-	type Latest = v6::ToRivet;
+	type Latest = v7::ToRivet;
 
 	fn wrap_latest(latest: Self::Latest) -> Self {
-		Self::V6(latest)
+		Self::V7(latest)
 	}
 
 	fn unwrap_latest(self) -> Result<Self::Latest> {
 		match self {
-			Self::V6(x) => Ok(x),
+			Self::V7(x) => Ok(x),
 			_ => bail!("version not latest"),
 		}
 	}
@@ -267,6 +299,7 @@ impl OwnedVersionedData for ToRivet {
 			4 => Ok(Self::V4(serde_bare::from_slice(payload)?)),
 			5 => Ok(Self::V5(serde_bare::from_slice(payload)?)),
 			6 => Ok(Self::V6(serde_bare::from_slice(payload)?)),
+			7 => Ok(Self::V7(serde_bare::from_slice(payload)?)),
 			_ => bail!("invalid version: {version}"),
 		}
 	}
@@ -279,6 +312,7 @@ impl OwnedVersionedData for ToRivet {
 			Self::V4(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V5(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V6(x) => serde_bare::to_vec(&x).map_err(Into::into),
+			Self::V7(x) => serde_bare::to_vec(&x).map_err(Into::into),
 		}
 	}
 
@@ -289,11 +323,13 @@ impl OwnedVersionedData for ToRivet {
 			Self::v3_to_v4,
 			Self::v4_to_v5,
 			Self::v5_to_v6,
+			Self::v6_to_v7,
 		]
 	}
 
 	fn serialize_converters() -> Vec<impl Fn(Self) -> Result<Self>> {
 		vec![
+			Self::v7_to_v6,
 			Self::v6_to_v5,
 			Self::v5_to_v4,
 			Self::v4_to_v3,
@@ -364,6 +400,18 @@ impl ToRivet {
 			_ => bail!("unexpected version"),
 		}
 	}
+	fn v6_to_v7(self) -> Result<Self> {
+		match self {
+			Self::V6(x) => Ok(Self::V7(v6_to_v7::convert_to_rivet_v6_to_v7(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
+	fn v7_to_v6(self) -> Result<Self> {
+		match self {
+			Self::V7(x) => Ok(Self::V6(v7_to_v6::convert_to_rivet_v7_to_v6(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
 }
 
 // MARK: ToEnvoyConn
@@ -375,18 +423,19 @@ pub enum ToEnvoyConn {
 	V4(v4::ToEnvoyConn),
 	V5(v5::ToEnvoyConn),
 	V6(v6::ToEnvoyConn),
+	V7(v7::ToEnvoyConn),
 }
 
 impl OwnedVersionedData for ToEnvoyConn {
-	type Latest = v6::ToEnvoyConn;
+	type Latest = v7::ToEnvoyConn;
 
 	fn wrap_latest(latest: Self::Latest) -> Self {
-		Self::V6(latest)
+		Self::V7(latest)
 	}
 
 	fn unwrap_latest(self) -> Result<Self::Latest> {
 		match self {
-			Self::V6(x) => Ok(x),
+			Self::V7(x) => Ok(x),
 			_ => bail!("version not latest"),
 		}
 	}
@@ -399,6 +448,7 @@ impl OwnedVersionedData for ToEnvoyConn {
 			4 => Ok(Self::V4(serde_bare::from_slice(payload)?)),
 			5 => Ok(Self::V5(serde_bare::from_slice(payload)?)),
 			6 => Ok(Self::V6(serde_bare::from_slice(payload)?)),
+			7 => Ok(Self::V7(serde_bare::from_slice(payload)?)),
 			_ => bail!("invalid version: {version}"),
 		}
 	}
@@ -411,6 +461,7 @@ impl OwnedVersionedData for ToEnvoyConn {
 			Self::V4(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V5(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V6(x) => serde_bare::to_vec(&x).map_err(Into::into),
+			Self::V7(x) => serde_bare::to_vec(&x).map_err(Into::into),
 		}
 	}
 
@@ -421,11 +472,13 @@ impl OwnedVersionedData for ToEnvoyConn {
 			Self::v3_to_v4,
 			Self::v4_to_v5,
 			Self::v5_to_v6,
+			Self::v6_to_v7,
 		]
 	}
 
 	fn serialize_converters() -> Vec<impl Fn(Self) -> Result<Self>> {
 		vec![
+			Self::v7_to_v6,
 			Self::v6_to_v5,
 			Self::v5_to_v4,
 			Self::v4_to_v3,
@@ -496,6 +549,18 @@ impl ToEnvoyConn {
 			_ => bail!("unexpected version"),
 		}
 	}
+	fn v6_to_v7(self) -> Result<Self> {
+		match self {
+			Self::V6(x) => Ok(Self::V7(v6_to_v7::convert_to_envoy_conn_v6_to_v7(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
+	fn v7_to_v6(self) -> Result<Self> {
+		match self {
+			Self::V7(x) => Ok(Self::V6(v7_to_v6::convert_to_envoy_conn_v7_to_v6(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
 }
 
 // MARK: ToGateway
@@ -507,18 +572,19 @@ pub enum ToGateway {
 	V4(v4::ToGateway),
 	V5(v5::ToGateway),
 	V6(v6::ToGateway),
+	V7(v7::ToGateway),
 }
 
 impl OwnedVersionedData for ToGateway {
-	type Latest = v6::ToGateway;
+	type Latest = v7::ToGateway;
 
 	fn wrap_latest(latest: Self::Latest) -> Self {
-		Self::V6(latest)
+		Self::V7(latest)
 	}
 
 	fn unwrap_latest(self) -> Result<Self::Latest> {
 		match self {
-			Self::V6(x) => Ok(x),
+			Self::V7(x) => Ok(x),
 			_ => bail!("version not latest"),
 		}
 	}
@@ -531,6 +597,7 @@ impl OwnedVersionedData for ToGateway {
 			4 => Ok(Self::V4(serde_bare::from_slice(payload)?)),
 			5 => Ok(Self::V5(serde_bare::from_slice(payload)?)),
 			6 => Ok(Self::V6(serde_bare::from_slice(payload)?)),
+			7 => Ok(Self::V7(serde_bare::from_slice(payload)?)),
 			_ => bail!("invalid version: {version}"),
 		}
 	}
@@ -543,6 +610,7 @@ impl OwnedVersionedData for ToGateway {
 			Self::V4(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V5(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V6(x) => serde_bare::to_vec(&x).map_err(Into::into),
+			Self::V7(x) => serde_bare::to_vec(&x).map_err(Into::into),
 		}
 	}
 
@@ -553,11 +621,13 @@ impl OwnedVersionedData for ToGateway {
 			Self::v3_to_v4,
 			Self::v4_to_v5,
 			Self::v5_to_v6,
+			Self::v6_to_v7,
 		]
 	}
 
 	fn serialize_converters() -> Vec<impl Fn(Self) -> Result<Self>> {
 		vec![
+			Self::v7_to_v6,
 			Self::v6_to_v5,
 			Self::v5_to_v4,
 			Self::v4_to_v3,
@@ -628,6 +698,18 @@ impl ToGateway {
 			_ => bail!("unexpected version"),
 		}
 	}
+	fn v6_to_v7(self) -> Result<Self> {
+		match self {
+			Self::V6(x) => Ok(Self::V7(v6_to_v7::convert_to_gateway_v6_to_v7(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
+	fn v7_to_v6(self) -> Result<Self> {
+		match self {
+			Self::V7(x) => Ok(Self::V6(v7_to_v6::convert_to_gateway_v7_to_v6(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
 }
 
 // MARK: ToOutbound
@@ -639,18 +721,19 @@ pub enum ToOutbound {
 	V4(v4::ToOutbound),
 	V5(v5::ToOutbound),
 	V6(v6::ToOutbound),
+	V7(v7::ToOutbound),
 }
 
 impl OwnedVersionedData for ToOutbound {
-	type Latest = v6::ToOutbound;
+	type Latest = v7::ToOutbound;
 
 	fn wrap_latest(latest: Self::Latest) -> Self {
-		Self::V6(latest)
+		Self::V7(latest)
 	}
 
 	fn unwrap_latest(self) -> Result<Self::Latest> {
 		match self {
-			Self::V6(x) => Ok(x),
+			Self::V7(x) => Ok(x),
 			_ => bail!("version not latest"),
 		}
 	}
@@ -663,6 +746,7 @@ impl OwnedVersionedData for ToOutbound {
 			4 => Ok(Self::V4(serde_bare::from_slice(payload)?)),
 			5 => Ok(Self::V5(serde_bare::from_slice(payload)?)),
 			6 => Ok(Self::V6(serde_bare::from_slice(payload)?)),
+			7 => Ok(Self::V7(serde_bare::from_slice(payload)?)),
 			_ => bail!("invalid version: {version}"),
 		}
 	}
@@ -675,6 +759,7 @@ impl OwnedVersionedData for ToOutbound {
 			Self::V4(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V5(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V6(x) => serde_bare::to_vec(&x).map_err(Into::into),
+			Self::V7(x) => serde_bare::to_vec(&x).map_err(Into::into),
 		}
 	}
 
@@ -685,11 +770,13 @@ impl OwnedVersionedData for ToOutbound {
 			Self::v3_to_v4,
 			Self::v4_to_v5,
 			Self::v5_to_v6,
+			Self::v6_to_v7,
 		]
 	}
 
 	fn serialize_converters() -> Vec<impl Fn(Self) -> Result<Self>> {
 		vec![
+			Self::v7_to_v6,
 			Self::v6_to_v5,
 			Self::v5_to_v4,
 			Self::v4_to_v3,
@@ -760,6 +847,18 @@ impl ToOutbound {
 			_ => bail!("unexpected version"),
 		}
 	}
+	fn v6_to_v7(self) -> Result<Self> {
+		match self {
+			Self::V6(x) => Ok(Self::V7(v6_to_v7::convert_to_outbound_v6_to_v7(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
+	fn v7_to_v6(self) -> Result<Self> {
+		match self {
+			Self::V7(x) => Ok(Self::V6(v7_to_v6::convert_to_outbound_v7_to_v6(x)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
 }
 
 // MARK: ActorCommandKeyData
@@ -771,18 +870,19 @@ pub enum ActorCommandKeyData {
 	V4(v4::ActorCommandKeyData),
 	V5(v5::ActorCommandKeyData),
 	V6(v6::ActorCommandKeyData),
+	V7(v7::ActorCommandKeyData),
 }
 
 impl OwnedVersionedData for ActorCommandKeyData {
-	type Latest = v6::ActorCommandKeyData;
+	type Latest = v7::ActorCommandKeyData;
 
 	fn wrap_latest(latest: Self::Latest) -> Self {
-		Self::V6(latest)
+		Self::V7(latest)
 	}
 
 	fn unwrap_latest(self) -> Result<Self::Latest> {
 		match self {
-			Self::V6(x) => Ok(x),
+			Self::V7(x) => Ok(x),
 			_ => bail!("version not latest"),
 		}
 	}
@@ -795,6 +895,7 @@ impl OwnedVersionedData for ActorCommandKeyData {
 			4 => Ok(Self::V4(serde_bare::from_slice(payload)?)),
 			5 => Ok(Self::V5(serde_bare::from_slice(payload)?)),
 			6 => Ok(Self::V6(serde_bare::from_slice(payload)?)),
+			7 => Ok(Self::V7(serde_bare::from_slice(payload)?)),
 			_ => bail!("invalid version: {version}"),
 		}
 	}
@@ -807,6 +908,7 @@ impl OwnedVersionedData for ActorCommandKeyData {
 			Self::V4(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V5(x) => serde_bare::to_vec(&x).map_err(Into::into),
 			Self::V6(x) => serde_bare::to_vec(&x).map_err(Into::into),
+			Self::V7(x) => serde_bare::to_vec(&x).map_err(Into::into),
 		}
 	}
 
@@ -817,11 +919,13 @@ impl OwnedVersionedData for ActorCommandKeyData {
 			Self::v3_to_v4,
 			Self::v4_to_v5,
 			Self::v5_to_v6,
+			Self::v6_to_v7,
 		]
 	}
 
 	fn serialize_converters() -> Vec<impl Fn(Self) -> Result<Self>> {
 		vec![
+			Self::v7_to_v6,
 			Self::v6_to_v5,
 			Self::v5_to_v4,
 			Self::v4_to_v3,
@@ -912,6 +1016,22 @@ impl ActorCommandKeyData {
 			_ => bail!("unexpected version"),
 		}
 	}
+	fn v6_to_v7(self) -> Result<Self> {
+		match self {
+			Self::V6(x) => Ok(Self::V7(v6_to_v7::convert_actor_command_key_data_v6_to_v7(
+				x,
+			)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
+	fn v7_to_v6(self) -> Result<Self> {
+		match self {
+			Self::V7(x) => Ok(Self::V6(v7_to_v6::convert_actor_command_key_data_v7_to_v6(
+				x,
+			)?)),
+			_ => bail!("unexpected version"),
+		}
+	}
 }
 
 // MARK: Tests
@@ -924,18 +1044,18 @@ mod tests {
 	use super::{ActorCommandKeyData, ToEnvoy};
 	use crate::{
 		PROTOCOL_VERSION,
-		generated::{v1, v2, v6},
+		generated::{v1, v2, v7},
 	};
 
 	#[test]
 	fn protocol_version_constant_matches_schema_version() {
-		assert_eq!(PROTOCOL_VERSION, 6);
+		assert_eq!(PROTOCOL_VERSION, 7);
 	}
 
 	#[test]
 	fn v1_start_command_deserializes_into_latest_without_sqlite_startup_data() -> Result<()> {
-		let payload = serde_bare::to_vec(&v1::ToEnvoy::ToEnvoyCommands(vec![
-			v1::CommandWrapper {
+		let payload =
+			serde_bare::to_vec(&v1::ToEnvoy::ToEnvoyCommands(vec![v1::CommandWrapper {
 				checkpoint: v1::ActorCheckpoint {
 					actor_id: "actor".into(),
 					generation: 7,
@@ -951,14 +1071,13 @@ mod tests {
 					hibernating_requests: Vec::new(),
 					preloaded_kv: None,
 				}),
-			}
-		]))?;
+			}]))?;
 
 		let decoded = ToEnvoy::deserialize(&payload, 1)?;
-		let v6::ToEnvoy::ToEnvoyCommands(commands) = decoded else {
+		let v7::ToEnvoy::ToEnvoyCommands(commands) = decoded else {
 			panic!("expected commands");
 		};
-		let v6::Command::CommandStartActor(start) = &commands[0].inner else {
+		let v7::Command::CommandStartActor(start) = &commands[0].inner else {
 			panic!("expected start actor");
 		};
 
@@ -985,9 +1104,9 @@ mod tests {
 
 	#[test]
 	fn actor_command_key_data_round_trips_to_v1() -> Result<()> {
-		let encoded = ActorCommandKeyData::wrap_latest(v6::ActorCommandKeyData::CommandStartActor(
-			v6::CommandStartActor {
-				config: v6::ActorConfig {
+		let encoded = ActorCommandKeyData::wrap_latest(v7::ActorCommandKeyData::CommandStartActor(
+			v7::CommandStartActor {
+				config: v7::ActorConfig {
 					name: "demo".into(),
 					key: None,
 					create_ts: 7,
@@ -1000,7 +1119,7 @@ mod tests {
 		.serialize(1)?;
 
 		let decoded = ActorCommandKeyData::deserialize(&encoded, 1)?;
-		let v6::ActorCommandKeyData::CommandStartActor(start) = decoded else {
+		let v7::ActorCommandKeyData::CommandStartActor(start) = decoded else {
 			panic!("expected start actor");
 		};
 		assert_eq!(start.config.name, "demo");
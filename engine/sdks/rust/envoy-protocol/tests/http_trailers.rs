@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use rivet_envoy_protocol::{
+	generated::v7,
+	versioned::{
+		ProtocolCompatibilityDirection, ProtocolCompatibilityError, ProtocolCompatibilityFeature,
+		ToEnvoy, ToRivet,
+	},
+};
+use vbare::OwnedVersionedData;
+
+fn message_id() -> v7::MessageId {
+	v7::MessageId {
+		gateway_id: [1, 2, 3, 4],
+		request_id: [5, 6, 7, 8],
+		message_index: 3,
+	}
+}
+
+fn trailers() -> HashMap<String, String> {
+	HashMap::from([
+		("grpc-status".to_string(), "0".to_string()),
+		("grpc-message".to_string(), "ok".to_string()),
+	])
+}
+
+fn request_chunk(
+	data: &[u8],
+	trailers: Option<HashMap<String, String>>,
+	version: u16,
+) -> Result<v7::ToEnvoyRequestChunk> {
+	let chunk = ToEnvoy::wrap_latest(v7::ToEnvoy::ToEnvoyTunnelMessage(
+		v7::ToEnvoyTunnelMessage {
+			message_id: message_id(),
+			message_kind: v7::ToEnvoyTunnelMessageKind::ToEnvoyRequestChunk(
+				v7::ToEnvoyRequestChunk {
+					body: data.to_vec(),
+					finish: true,
+					trailers,
+				},
+			),
+		},
+	))
+	.serialize(version)?;
+	let v7::ToEnvoy::ToEnvoyTunnelMessage(msg) = ToEnvoy::deserialize(&chunk, version)? else {
+		panic!("expected tunnel message");
+	};
+	let v7::ToEnvoyTunnelMessageKind::ToEnvoyRequestChunk(chunk) = msg.message_kind else {
+		panic!("expected request chunk");
+	};
+
+	Ok(chunk)
+}
+
+fn response_chunk(
+	data: &[u8],
+	trailers: Option<HashMap<String, String>>,
+	version: u16,
+) -> Result<v7::ToRivetResponseChunk> {
+	let chunk = ToRivet::wrap_latest(v7::ToRivet::ToRivetTunnelMessage(
+		v7::ToRivetTunnelMessage {
+			message_id: message_id(),
+			message_kind: v7::ToRivetTunnelMessageKind::ToRivetResponseChunk(
+				v7::ToRivetResponseChunk {
+					body: data.to_vec(),
+					finish: true,
+					trailers,
+				},
+			),
+		},
+	))
+	.serialize(version)?;
+	let v7::ToRivet::ToRivetTunnelMessage(msg) = ToRivet::deserialize(&chunk, version)? else {
+		panic!("expected tunnel message");
+	};
+	let v7::ToRivetTunnelMessageKind::ToRivetResponseChunk(chunk) = msg.message_kind else {
+		panic!("expected response chunk");
+	};
+
+	Ok(chunk)
+}
+
+fn assert_trailers_error(err: anyhow::Error, direction: ProtocolCompatibilityDirection) {
+	let err = err
+		.downcast_ref::<ProtocolCompatibilityError>()
+		.expect("expected structured protocol compatibility error");
+
+	assert_eq!(err.feature, ProtocolCompatibilityFeature::HttpTrailers);
+	assert_eq!(err.direction, direction);
+	assert_eq!(err.required_version, 7);
+	assert_eq!(err.target_version, 6);
+}
+
+#[test]
+fn chunk_trailers_round_trip_v7() -> Result<()> {
+	let chunk = request_chunk(b"req", Some(trailers()), 7)?;
+	assert_eq!(chunk.body, b"req");
+	assert!(chunk.finish);
+	assert_eq!(chunk.trailers, Some(trailers()));
+
+	let chunk = response_chunk(b"res", Some(trailers()), 7)?;
+	assert_eq!(chunk.body, b"res");
+	assert!(chunk.finish);
+	assert_eq!(chunk.trailers, Some(trailers()));
+
+	Ok(())
+}
+
+#[test]
+fn chunk_trailers_require_v7() {
+	let err = request_chunk(b"req", Some(trailers()), 6)
+		.expect_err("request trailers must not serialize below v7");
+	assert_trailers_error(err, ProtocolCompatibilityDirection::ToEnvoy);
+
+	let err = response_chunk(b"res", Some(trailers()), 6)
+		.expect_err("response trailers must not serialize below v7");
+	assert_trailers_error(err, ProtocolCompatibilityDirection::ToRivet);
+}
+
+#[test]
+fn chunks_without_trailers_serialize_below_v7() -> Result<()> {
+	let chunk = request_chunk(b"req", None, 6)?;
+	assert_eq!(chunk.body, b"req");
+	assert!(chunk.finish);
+	assert_eq!(chunk.trailers, None);
+
+	let chunk = response_chunk(b"res", None, 6)?;
+	assert_eq!(chunk.body, b"res");
+	assert!(chunk.finish);
+	assert_eq!(chunk.trailers, None);
+
+	Ok(())
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use rivet_envoy_protocol::{
+	generated::v7,
+	versioned::{
+		ProtocolCompatibilityDirection, ProtocolCompatibilityError, ProtocolCompatibilityFeature,
+		ToEnvoy, ToRivet,
+	},
+};
+use vbare::OwnedVersionedData;
+
+fn position(txid: u64, index: u32) -> v7::SqliteRowChangePosition {
+	v7::SqliteRowChangePosition { txid, index }
+}
+
+fn commit(row_changes: Option<v7::SqliteRowChangeLogAppend>) -> v7::ToRivet {
+	v7::ToRivet::ToRivetSqliteCommitRequest(v7::ToRivetSqliteCommitRequest {
+		request_id: 3,
+		data: v7::SqliteCommitRequest {
+			actor_id: "actor".into(),
+			dirty_pages: vec![v7::SqliteDirtyPage {
+				pgno: 1,
+				bytes: vec![7; 4096],
+			}],
+			db_size_pages: 1,
+			now_ms: 1_000,
+			expected_generation: Some(2),
+			expected_head_txid: Some(4),
+			row_changes,
+		},
+	})
+}
+
+fn assert_row_changes_error(err: anyhow::Error, direction: ProtocolCompatibilityDirection) {
+	let err = err
+		.downcast_ref::<ProtocolCompatibilityError>()
+		.expect("expected structured protocol compatibility error");
+
+	assert_eq!(err.feature, ProtocolCompatibilityFeature::SqliteRowChanges);
+	assert_eq!(err.direction, direction);
+	assert_eq!(err.required_version, 7);
+	assert_eq!(err.target_version, 6);
+}
+
+#[test]
+fn commit_carries_row_changes_on_v7() -> Result<()> {
+	let row_changes = v7::SqliteRowChangeLogAppend {
+		changes: vec![
+			v7::SqliteRowChange {
+				index: 0,
+				change: b"insert".to_vec(),
+			},
+			v7::SqliteRowChange {
+				index: 1,
+				change: b"delete".to_vec(),
+			},
+		],
+		floor: position(4, 0),
+	};
+	let encoded = ToRivet::wrap_latest(commit(Some(row_changes.clone()))).serialize(7)?;
+	let v7::ToRivet::ToRivetSqliteCommitRequest(decoded) = ToRivet::deserialize(&encoded, 7)?
+	else {
+		panic!("expected commit request");
+	};
+	assert_eq!(decoded.data.row_changes, Some(row_changes));
+
+	Ok(())
+}
+
+#[test]
+fn commit_without_row_changes_downgrades_to_v6() -> Result<()> {
+	let encoded = ToRivet::wrap_latest(commit(None)).serialize(6)?;
+	let v7::ToRivet::ToRivetSqliteCommitRequest(decoded) = ToRivet::deserialize(&encoded, 6)?
+	else {
+		panic!("expected commit request");
+	};
+	assert_eq!(decoded.data.expected_head_txid, Some(4));
+	assert_eq!(decoded.data.row_changes, None);
+
+	Ok(())
+}
+
+#[test]
+fn row_changes_reject_v6() {
+	let err = ToRivet::wrap_latest(commit(Some(v7::SqliteRowChangeLogAppend {
+		changes: Vec::new(),
+		floor: position(4, 0),
+	})))
+	.serialize(6)
+	.expect_err("row changes should not downgrade");
+	assert_row_changes_error(err, ProtocolCompatibilityDirection::ToRivet);
+
+	let err = ToRivet::wrap_latest(v7::ToRivet::ToRivetSqliteGetRowChangeLogRequest(
+		v7::ToRivetSqliteGetRowChangeLogRequest {
+			request_id: 5,
+			data: v7::SqliteGetRowChangeLogRequest {
+				actor_id: "actor".into(),
+				expected_generation: Some(2),
+			},
+		},
+	))
+	.serialize(6)
+	.expect_err("row change log request should not downgrade");
+	assert_row_changes_error(err, ProtocolCompatibilityDirection::ToRivet);
+
+	let err = ToEnvoy::wrap_latest(v7::ToEnvoy::ToEnvoySqliteGetRowChangeLogResponse(
+		v7::ToEnvoySqliteGetRowChangeLogResponse {
+			request_id: 5,
+			data: v7::SqliteGetRowChangeLogResponse::SqliteGetRowChangeLogOk(
+				v7::SqliteGetRowChangeLogOk { log: None },
+			),
+		},
+	))
+	.serialize(6)
+	.expect_err("row change log response should not downgrade");
+	assert_row_changes_error(err, ProtocolCompatibilityDirection::ToEnvoy);
+}
@@ -64,6 +64,7 @@ fn commit_request_roundtrip() -> anyhow::Result<()> {
 					now_ms,
 					expected_generation: None,
 					expected_head_txid: None,
+					row_changes: None,
 				},
 			},
 		))?;
@@ -153,6 +154,7 @@ fn expected_generation_optional_present_and_absent() -> anyhow::Result<()> {
 					now_ms: 99,
 					expected_generation,
 					expected_head_txid,
+					row_changes: None,
 				},
 			},
 		))?;
@@ -168,7 +170,7 @@ fn expected_generation_optional_present_and_absent() -> anyhow::Result<()> {
 
 #[test]
 fn protocol_version_constant_matches_schema_version() {
-	assert_eq!(PROTOCOL_VERSION, 6);
+	assert_eq!(PROTOCOL_VERSION, 7);
 }
 
 #[test]
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use rivet_envoy_protocol::{
+	generated::v7,
+	versioned::{
+		ProtocolCompatibilityDirection, ProtocolCompatibilityError, ProtocolCompatibilityFeature,
+		ToEnvoy, ToRivet,
+	},
+};
+use vbare::OwnedVersionedData;
+
+fn message_id() -> v7::MessageId {
+	v7::MessageId {
+		gateway_id: [1, 2, 3, 4],
+		request_id: [5, 6, 7, 8],
+		message_index: 3,
+	}
+}
+
+fn to_envoy(message_kind: v7::ToEnvoyTunnelMessageKind) -> v7::ToEnvoy {
+	v7::ToEnvoy::ToEnvoyTunnelMessage(v7::ToEnvoyTunnelMessage {
+		message_id: message_id(),
+		message_kind,
+	})
+}
+
+fn to_rivet(message_kind: v7::ToRivetTunnelMessageKind) -> v7::ToRivet {
+	v7::ToRivet::ToRivetTunnelMessage(v7::ToRivetTunnelMessage {
+		message_id: message_id(),
+		message_kind,
+	})
+}
+
+fn assert_flow_control_error(err: anyhow::Error, direction: ProtocolCompatibilityDirection) {
+	let err = err
+		.downcast_ref::<ProtocolCompatibilityError>()
+		.expect("expected structured protocol compatibility error");
+
+	assert_eq!(
+		err.feature,
+		ProtocolCompatibilityFeature::WebSocketFlowControl
+	);
+	assert_eq!(err.direction, direction);
+	assert_eq!(err.required_version, 7);
+	assert_eq!(err.target_version, 6);
+}
+
+#[test]
+fn websocket_open_drops_flow_control_below_v7() -> Result<()> {
+	let open = ToEnvoy::wrap_latest(to_envoy(
+		v7::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(v7::ToEnvoyWebSocketOpen {
+			actor_id: "actor".into(),
+			path: "/ws".into(),
+			headers: Default::default(),
+			send_window: Some(1024),
+		}),
+	))
+	.serialize(6)?;
+	let v7::ToEnvoy::ToEnvoyTunnelMessage(msg) = ToEnvoy::deserialize(&open, 6)? else {
+		panic!("expected tunnel message");
+	};
+	let v7::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketOpen(open) = msg.message_kind else {
+		panic!("expected websocket open");
+	};
+	assert_eq!(open.path, "/ws");
+	assert_eq!(open.send_window, None);
+
+	let open = ToRivet::wrap_latest(to_rivet(
+		v7::ToRivetTunnelMessageKind::ToRivetWebSocketOpen(v7::ToRivetWebSocketOpen {
+			can_hibernate: true,
+			flow_control: true,
+		}),
+	))
+	.serialize(6)?;
+	let v7::ToRivet::ToRivetTunnelMessage(msg) = ToRivet::deserialize(&open, 6)? else {
+		panic!("expected tunnel message");
+	};
+	let v7::ToRivetTunnelMessageKind::ToRivetWebSocketOpen(open) = msg.message_kind else {
+		panic!("expected websocket open");
+	};
+	assert!(open.can_hibernate);
+	assert!(!open.flow_control);
+
+	Ok(())
+}
+
+#[test]
+fn websocket_credit_requires_v7() -> Result<()> {
+	let err = ToEnvoy::wrap_latest(to_envoy(
+		v7::ToEnvoyTunnelMessageKind::ToEnvoyWebSocketCredit(v7::ToEnvoyWebSocketCredit {
+			bytes: 1024,
+		}),
+	))
+	.serialize(6)
+	.expect_err("websocket credit must not serialize below v7");
+	assert_flow_control_error(err, ProtocolCompatibilityDirection::ToEnvoy);
+
+	let err = ToRivet::wrap_latest(to_rivet(
+		v7::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(v7::ToRivetWebSocketCredit {
+			bytes: 1024,
+		}),
+	))
+	.serialize(6)
+	.expect_err("websocket credit must not serialize below v7");
+	assert_flow_control_error(err, ProtocolCompatibilityDirection::ToRivet);
+
+	let credit = ToRivet::wrap_latest(to_rivet(
+		v7::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(v7::ToRivetWebSocketCredit {
+			bytes: 1024,
+		}),
+	))
+	.serialize(7)?;
+	let v7::ToRivet::ToRivetTunnelMessage(msg) = ToRivet::deserialize(&credit, 7)? else {
+		panic!("expected tunnel message");
+	};
+	assert!(matches!(
+		msg.message_kind,
+		v7::ToRivetTunnelMessageKind::ToRivetWebSocketCredit(v7::ToRivetWebSocketCredit {
+			bytes: 1024
+		})
+	));
+
+	Ok(())
+}
@@ -0,0 +1,6 @@
+pub mod generated;
+pub mod versioned;
+
+// Re-export latest
+pub use generated::PROTOCOL_VERSION;
+pub use generated::v1::*;
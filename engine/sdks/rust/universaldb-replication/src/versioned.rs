@@ -0,0 +1,224 @@
+use anyhow::{Ok, Result, bail};
+use vbare::OwnedVersionedData;
+
+use crate::generated::v1;
+
+// Only v1 exists today. When adding v2+, generate converters with
+// `scripts/vbare-gen-converters` (see the envoy-protocol package for the
+// resulting `versioned/` module layout) and wire them in here.
+pub enum Entry {
+	V1(v1::Entry),
+}
+
+impl OwnedVersionedData for Entry {
+	type Latest = v1::Entry;
+
+	fn wrap_latest(latest: v1::Entry) -> Self {
+		Entry::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			Entry::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(Entry::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			Entry::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub enum VoteRequest {
+	V1(v1::VoteRequest),
+}
+
+impl OwnedVersionedData for VoteRequest {
+	type Latest = v1::VoteRequest;
+
+	fn wrap_latest(latest: v1::VoteRequest) -> Self {
+		VoteRequest::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			VoteRequest::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(VoteRequest::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			VoteRequest::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub enum VoteReply {
+	V1(v1::VoteReply),
+}
+
+impl OwnedVersionedData for VoteReply {
+	type Latest = v1::VoteReply;
+
+	fn wrap_latest(latest: v1::VoteReply) -> Self {
+		VoteReply::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			VoteReply::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(VoteReply::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			VoteReply::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub enum AppendRequest {
+	V1(v1::AppendRequest),
+}
+
+impl OwnedVersionedData for AppendRequest {
+	type Latest = v1::AppendRequest;
+
+	fn wrap_latest(latest: v1::AppendRequest) -> Self {
+		AppendRequest::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			AppendRequest::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(AppendRequest::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			AppendRequest::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub enum AppendReply {
+	V1(v1::AppendReply),
+}
+
+impl OwnedVersionedData for AppendReply {
+	type Latest = v1::AppendReply;
+
+	fn wrap_latest(latest: v1::AppendReply) -> Self {
+		AppendReply::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			AppendReply::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(AppendReply::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			AppendReply::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub enum ReadIndexRequest {
+	V1(v1::ReadIndexRequest),
+}
+
+impl OwnedVersionedData for ReadIndexRequest {
+	type Latest = v1::ReadIndexRequest;
+
+	fn wrap_latest(latest: v1::ReadIndexRequest) -> Self {
+		ReadIndexRequest::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			ReadIndexRequest::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(ReadIndexRequest::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			ReadIndexRequest::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
+
+pub enum ReadIndexReply {
+	V1(v1::ReadIndexReply),
+}
+
+impl OwnedVersionedData for ReadIndexReply {
+	type Latest = v1::ReadIndexReply;
+
+	fn wrap_latest(latest: v1::ReadIndexReply) -> Self {
+		ReadIndexReply::V1(latest)
+	}
+
+	fn unwrap_latest(self) -> Result<Self::Latest> {
+		match self {
+			ReadIndexReply::V1(data) => Ok(data),
+		}
+	}
+
+	fn deserialize_version(payload: &[u8], version: u16) -> Result<Self> {
+		match version {
+			1 => Ok(ReadIndexReply::V1(serde_bare::from_slice(payload)?)),
+			_ => bail!("invalid version: {version}"),
+		}
+	}
+
+	fn serialize_version(self, _version: u16) -> Result<Vec<u8>> {
+		match self {
+			ReadIndexReply::V1(data) => serde_bare::to_vec(&data).map_err(Into::into),
+		}
+	}
+}
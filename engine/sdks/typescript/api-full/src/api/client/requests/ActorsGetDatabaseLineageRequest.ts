@@ -0,0 +1,13 @@
+/**
+ * This file was auto-generated by Fern from our API Definition.
+ */
+
+/**
+ * @example
+ *     {
+ *         namespace: "namespace"
+ *     }
+ */
+export interface ActorsGetDatabaseLineageRequest {
+    namespace: string;
+}
@@ -3,6 +3,7 @@ export { type ActorsCreateRequest } from "./ActorsCreateRequest";
 export { type ActorsGetOrCreateRequest } from "./ActorsGetOrCreateRequest";
 export { type ActorsListNamesRequest } from "./ActorsListNamesRequest";
 export { type ActorsDeleteRequest } from "./ActorsDeleteRequest";
+export { type ActorsGetDatabaseLineageRequest } from "./ActorsGetDatabaseLineageRequest";
 export { type ActorsKvGetRequest } from "./ActorsKvGetRequest";
 export { type ActorsRescheduleRequest } from "./ActorsRescheduleRequest";
 export { type ActorsSleepRequest } from "./ActorsSleepRequest";
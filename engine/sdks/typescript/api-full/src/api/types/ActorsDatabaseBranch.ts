@@ -0,0 +1,11 @@
+/**
+ * This file was auto-generated by Fern from our API Definition.
+ */
+
+export interface ActorsDatabaseBranch {
+    branchId: string;
+    createTs: number;
+    parentBranchId?: string;
+    /** Restore point the branch was derived from, if it was forked or restored from one. */
+    restorePointId?: string;
+}
@@ -0,0 +1,15 @@
+/**
+ * This file was auto-generated by Fern from our API Definition.
+ */
+
+import * as Rivet from "../index";
+
+export interface ActorsGetDatabaseLineageResponse {
+    /**
+     * Branches of the actor's SQLite database from the current branch up to the root. Restores and
+     * forks each add a branch.
+     */
+    branches: Rivet.ActorsDatabaseBranch[];
+    /** Actor this actor was created from with `fork_from`. */
+    forkedFromActorId?: Rivet.RivetId;
+}
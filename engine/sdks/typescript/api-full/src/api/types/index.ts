@@ -1,7 +1,9 @@
 export * from "./Actor";
 export * from "./ActorName";
 export * from "./ActorsCreateResponse";
+export * from "./ActorsDatabaseBranch";
 export * from "./ActorsDeleteResponse";
+export * from "./ActorsGetDatabaseLineageResponse";
 export * from "./ActorsGetOrCreateResponse";
 export * from "./ActorsKvGetResponse";
 export * from "./ActorsListNamesResponse";
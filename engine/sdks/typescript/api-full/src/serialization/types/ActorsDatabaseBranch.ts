@@ -0,0 +1,26 @@
+/**
+ * This file was auto-generated by Fern from our API Definition.
+ */
+
+import * as serializers from "../index";
+import * as Rivet from "../../api/index";
+import * as core from "../../core";
+
+export const ActorsDatabaseBranch: core.serialization.ObjectSchema<
+    serializers.ActorsDatabaseBranch.Raw,
+    Rivet.ActorsDatabaseBranch
+> = core.serialization.object({
+    branchId: core.serialization.property("branch_id", core.serialization.string()),
+    createTs: core.serialization.property("create_ts", core.serialization.number()),
+    parentBranchId: core.serialization.property("parent_branch_id", core.serialization.string().optional()),
+    restorePointId: core.serialization.property("restore_point_id", core.serialization.string().optional()),
+});
+
+export declare namespace ActorsDatabaseBranch {
+    export interface Raw {
+        branch_id: string;
+        create_ts: number;
+        parent_branch_id?: string | null;
+        restore_point_id?: string | null;
+    }
+}
@@ -0,0 +1,24 @@
+/**
+ * This file was auto-generated by Fern from our API Definition.
+ */
+
+import * as serializers from "../index";
+import * as Rivet from "../../api/index";
+import * as core from "../../core";
+import { ActorsDatabaseBranch } from "./ActorsDatabaseBranch";
+import { RivetId } from "./RivetId";
+
+export const ActorsGetDatabaseLineageResponse: core.serialization.ObjectSchema<
+    serializers.ActorsGetDatabaseLineageResponse.Raw,
+    Rivet.ActorsGetDatabaseLineageResponse
+> = core.serialization.object({
+    branches: core.serialization.list(ActorsDatabaseBranch),
+    forkedFromActorId: core.serialization.property("forked_from_actor_id", RivetId.optional()),
+});
+
+export declare namespace ActorsGetDatabaseLineageResponse {
+    export interface Raw {
+        branches: ActorsDatabaseBranch.Raw[];
+        forked_from_actor_id?: RivetId.Raw | null;
+    }
+}
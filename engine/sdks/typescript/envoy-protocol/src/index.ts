@@ -767,6 +767,17 @@ function write8(bc: bare.ByteCursor, x: readonly SqliteDirtyPage[]): void {
     }
 }
 
+function read9(bc: bare.ByteCursor): SqliteRowChangeLogAppend | null {
+    return bare.readBool(bc) ? readSqliteRowChangeLogAppend(bc) : null
+}
+
+function write9(bc: bare.ByteCursor, x: SqliteRowChangeLogAppend | null): void {
+    bare.writeBool(bc, x != null)
+    if (x != null) {
+        writeSqliteRowChangeLogAppend(bc, x)
+    }
+}
+
 export type SqliteCommitRequest = {
     readonly actorId: Id
     readonly dirtyPages: readonly SqliteDirtyPage[]
@@ -774,6 +785,11 @@ export type SqliteCommitRequest = {
     readonly nowMs: i64
     readonly expectedGeneration: u64 | null
     readonly expectedHeadTxid: u64 | null
+    /**
+     * Row changes of the commit, written in the same transaction as its pages. Set while the
+     * actor captures row changes.
+     */
+    readonly rowChanges: SqliteRowChangeLogAppend | null
 }
 
 export function readSqliteCommitRequest(bc: bare.ByteCursor): SqliteCommitRequest {
@@ -784,6 +800,7 @@ export function readSqliteCommitRequest(bc: bare.ByteCursor): SqliteCommitReques
         nowMs: bare.readI64(bc),
         expectedGeneration: read2(bc),
         expectedHeadTxid: read2(bc),
+        rowChanges: read9(bc),
     }
 }
 
@@ -794,6 +811,7 @@ export function writeSqliteCommitRequest(bc: bare.ByteCursor, x: SqliteCommitReq
     bare.writeI64(bc, x.nowMs)
     write2(bc, x.expectedGeneration)
     write2(bc, x.expectedHeadTxid)
+    write9(bc, x.rowChanges)
 }
 
 export type SqliteCommitOk = {
@@ -844,6 +862,214 @@ export function writeSqliteCommitResponse(bc: bare.ByteCursor, x: SqliteCommitRe
     }
 }
 
+export type SqliteRowChangePosition = {
+    readonly txid: u64
+    readonly index: u32
+}
+
+export function readSqliteRowChangePosition(bc: bare.ByteCursor): SqliteRowChangePosition {
+    return {
+        txid: bare.readU64(bc),
+        index: bare.readU32(bc),
+    }
+}
+
+export function writeSqliteRowChangePosition(bc: bare.ByteCursor, x: SqliteRowChangePosition): void {
+    bare.writeU64(bc, x.txid)
+    bare.writeU32(bc, x.index)
+}
+
+export type SqliteRowChange = {
+    readonly index: u32
+    /**
+     * Encoded by the envoy. The engine stores it without decoding it.
+     */
+    readonly change: ArrayBuffer
+}
+
+export function readSqliteRowChange(bc: bare.ByteCursor): SqliteRowChange {
+    return {
+        index: bare.readU32(bc),
+        change: bare.readData(bc),
+    }
+}
+
+export function writeSqliteRowChange(bc: bare.ByteCursor, x: SqliteRowChange): void {
+    bare.writeU32(bc, x.index)
+    bare.writeData(bc, x.change)
+}
+
+function read10(bc: bare.ByteCursor): readonly SqliteRowChange[] {
+    const len = bare.readUintSafe(bc)
+    if (len === 0) {
+        return []
+    }
+    const result = [readSqliteRowChange(bc)]
+    for (let i = 1; i < len; i++) {
+        result[i] = readSqliteRowChange(bc)
+    }
+    return result
+}
+
+function write10(bc: bare.ByteCursor, x: readonly SqliteRowChange[]): void {
+    bare.writeUintSafe(bc, x.length)
+    for (let i = 0; i < x.length; i++) {
+        writeSqliteRowChange(bc, x[i])
+    }
+}
+
+export type SqliteRowChangeLogAppend = {
+    readonly changes: readonly SqliteRowChange[]
+    /**
+     * Logged changes at or before this position are dropped
+     */
+    readonly floor: SqliteRowChangePosition
+}
+
+export function readSqliteRowChangeLogAppend(bc: bare.ByteCursor): SqliteRowChangeLogAppend {
+    return {
+        changes: read10(bc),
+        floor: readSqliteRowChangePosition(bc),
+    }
+}
+
+export function writeSqliteRowChangeLogAppend(bc: bare.ByteCursor, x: SqliteRowChangeLogAppend): void {
+    write10(bc, x.changes)
+    writeSqliteRowChangePosition(bc, x.floor)
+}
+
+export type SqliteLoggedRowChange = {
+    readonly position: SqliteRowChangePosition
+    readonly change: ArrayBuffer
+}
+
+export function readSqliteLoggedRowChange(bc: bare.ByteCursor): SqliteLoggedRowChange {
+    return {
+        position: readSqliteRowChangePosition(bc),
+        change: bare.readData(bc),
+    }
+}
+
+export function writeSqliteLoggedRowChange(bc: bare.ByteCursor, x: SqliteLoggedRowChange): void {
+    writeSqliteRowChangePosition(bc, x.position)
+    bare.writeData(bc, x.change)
+}
+
+function read11(bc: bare.ByteCursor): readonly SqliteLoggedRowChange[] {
+    const len = bare.readUintSafe(bc)
+    if (len === 0) {
+        return []
+    }
+    const result = [readSqliteLoggedRowChange(bc)]
+    for (let i = 1; i < len; i++) {
+        result[i] = readSqliteLoggedRowChange(bc)
+    }
+    return result
+}
+
+function write11(bc: bare.ByteCursor, x: readonly SqliteLoggedRowChange[]): void {
+    bare.writeUintSafe(bc, x.length)
+    for (let i = 0; i < x.length; i++) {
+        writeSqliteLoggedRowChange(bc, x[i])
+    }
+}
+
+export type SqliteRowChangeLog = {
+    readonly floor: SqliteRowChangePosition
+    readonly changes: readonly SqliteLoggedRowChange[]
+}
+
+export function readSqliteRowChangeLog(bc: bare.ByteCursor): SqliteRowChangeLog {
+    return {
+        floor: readSqliteRowChangePosition(bc),
+        changes: read11(bc),
+    }
+}
+
+export function writeSqliteRowChangeLog(bc: bare.ByteCursor, x: SqliteRowChangeLog): void {
+    writeSqliteRowChangePosition(bc, x.floor)
+    write11(bc, x.changes)
+}
+
+export type SqliteGetRowChangeLogRequest = {
+    readonly actorId: Id
+    readonly expectedGeneration: u64 | null
+}
+
+export function readSqliteGetRowChangeLogRequest(bc: bare.ByteCursor): SqliteGetRowChangeLogRequest {
+    return {
+        actorId: readId(bc),
+        expectedGeneration: read2(bc),
+    }
+}
+
+export function writeSqliteGetRowChangeLogRequest(bc: bare.ByteCursor, x: SqliteGetRowChangeLogRequest): void {
+    writeId(bc, x.actorId)
+    write2(bc, x.expectedGeneration)
+}
+
+function read12(bc: bare.ByteCursor): SqliteRowChangeLog | null {
+    return bare.readBool(bc) ? readSqliteRowChangeLog(bc) : null
+}
+
+function write12(bc: bare.ByteCursor, x: SqliteRowChangeLog | null): void {
+    bare.writeBool(bc, x != null)
+    if (x != null) {
+        writeSqliteRowChangeLog(bc, x)
+    }
+}
+
+export type SqliteGetRowChangeLogOk = {
+    /**
+     * Not set when a commit up to the head was written without row changes
+     */
+    readonly log: SqliteRowChangeLog | null
+}
+
+export function readSqliteGetRowChangeLogOk(bc: bare.ByteCursor): SqliteGetRowChangeLogOk {
+    return {
+        log: read12(bc),
+    }
+}
+
+export function writeSqliteGetRowChangeLogOk(bc: bare.ByteCursor, x: SqliteGetRowChangeLogOk): void {
+    write12(bc, x.log)
+}
+
+export type SqliteGetRowChangeLogResponse =
+    | { readonly tag: "SqliteGetRowChangeLogOk"; readonly val: SqliteGetRowChangeLogOk }
+    | { readonly tag: "SqliteErrorResponse"; readonly val: SqliteErrorResponse }
+
+export function readSqliteGetRowChangeLogResponse(bc: bare.ByteCursor): SqliteGetRowChangeLogResponse {
+    const offset = bc.offset
+    const tag = bare.readU8(bc)
+    switch (tag) {
+        case 0:
+            return { tag: "SqliteGetRowChangeLogOk", val: readSqliteGetRowChangeLogOk(bc) }
+        case 1:
+            return { tag: "SqliteErrorResponse", val: readSqliteErrorResponse(bc) }
+        default: {
+            bc.offset = offset
+            throw new bare.BareError(offset, "invalid tag")
+        }
+    }
+}
+
+export function writeSqliteGetRowChangeLogResponse(bc: bare.ByteCursor, x: SqliteGetRowChangeLogResponse): void {
+    switch (x.tag) {
+        case "SqliteGetRowChangeLogOk": {
+            bare.writeU8(bc, 0)
+            writeSqliteGetRowChangeLogOk(bc, x.val)
+            break
+        }
+        case "SqliteErrorResponse": {
+            bare.writeU8(bc, 1)
+            writeSqliteErrorResponse(bc, x.val)
+            break
+        }
+    }
+}
+
 export type SqliteValueNull = null
 
 export type SqliteValueInteger = {
@@ -1019,7 +1245,7 @@ export function writeSqliteColumnValue(bc: bare.ByteCursor, x: SqliteColumnValue
     }
 }
 
-function read9(bc: bare.ByteCursor): readonly string[] {
+function read13(bc: bare.ByteCursor): readonly string[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1031,14 +1257,14 @@ function read9(bc: bare.ByteCursor): readonly string[] {
     return result
 }
 
-function write9(bc: bare.ByteCursor, x: readonly string[]): void {
+function write13(bc: bare.ByteCursor, x: readonly string[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         bare.writeString(bc, x[i])
     }
 }
 
-function read10(bc: bare.ByteCursor): readonly SqliteColumnValue[] {
+function read14(bc: bare.ByteCursor): readonly SqliteColumnValue[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1050,29 +1276,29 @@ function read10(bc: bare.ByteCursor): readonly SqliteColumnValue[] {
     return result
 }
 
-function write10(bc: bare.ByteCursor, x: readonly SqliteColumnValue[]): void {
+function write14(bc: bare.ByteCursor, x: readonly SqliteColumnValue[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writeSqliteColumnValue(bc, x[i])
     }
 }
 
-function read11(bc: bare.ByteCursor): readonly (readonly SqliteColumnValue[])[] {
+function read15(bc: bare.ByteCursor): readonly (readonly SqliteColumnValue[])[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
     }
-    const result = [read10(bc)]
+    const result = [read14(bc)]
     for (let i = 1; i < len; i++) {
-        result[i] = read10(bc)
+        result[i] = read14(bc)
     }
     return result
 }
 
-function write11(bc: bare.ByteCursor, x: readonly (readonly SqliteColumnValue[])[]): void {
+function write15(bc: bare.ByteCursor, x: readonly (readonly SqliteColumnValue[])[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
-        write10(bc, x[i])
+        write14(bc, x[i])
     }
 }
 
@@ -1083,21 +1309,21 @@ export type SqliteQueryResult = {
 
 export function readSqliteQueryResult(bc: bare.ByteCursor): SqliteQueryResult {
     return {
-        columns: read9(bc),
-        rows: read11(bc),
+        columns: read13(bc),
+        rows: read15(bc),
     }
 }
 
 export function writeSqliteQueryResult(bc: bare.ByteCursor, x: SqliteQueryResult): void {
-    write9(bc, x.columns)
-    write11(bc, x.rows)
+    write13(bc, x.columns)
+    write15(bc, x.rows)
 }
 
-function read12(bc: bare.ByteCursor): i64 | null {
+function read16(bc: bare.ByteCursor): i64 | null {
     return bare.readBool(bc) ? bare.readI64(bc) : null
 }
 
-function write12(bc: bare.ByteCursor, x: i64 | null): void {
+function write16(bc: bare.ByteCursor, x: i64 | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
         bare.writeI64(bc, x)
@@ -1113,18 +1339,18 @@ export type SqliteExecuteResult = {
 
 export function readSqliteExecuteResult(bc: bare.ByteCursor): SqliteExecuteResult {
     return {
-        columns: read9(bc),
-        rows: read11(bc),
+        columns: read13(bc),
+        rows: read15(bc),
         changes: bare.readI64(bc),
-        lastInsertRowId: read12(bc),
+        lastInsertRowId: read16(bc),
     }
 }
 
 export function writeSqliteExecuteResult(bc: bare.ByteCursor, x: SqliteExecuteResult): void {
-    write9(bc, x.columns)
-    write11(bc, x.rows)
+    write13(bc, x.columns)
+    write15(bc, x.rows)
     bare.writeI64(bc, x.changes)
-    write12(bc, x.lastInsertRowId)
+    write16(bc, x.lastInsertRowId)
 }
 
 export type SqliteExecRequest = {
@@ -1150,7 +1376,7 @@ export function writeSqliteExecRequest(bc: bare.ByteCursor, x: SqliteExecRequest
     bare.writeString(bc, x.sql)
 }
 
-function read13(bc: bare.ByteCursor): readonly SqliteBindParam[] {
+function read17(bc: bare.ByteCursor): readonly SqliteBindParam[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1162,21 +1388,21 @@ function read13(bc: bare.ByteCursor): readonly SqliteBindParam[] {
     return result
 }
 
-function write13(bc: bare.ByteCursor, x: readonly SqliteBindParam[]): void {
+function write17(bc: bare.ByteCursor, x: readonly SqliteBindParam[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writeSqliteBindParam(bc, x[i])
     }
 }
 
-function read14(bc: bare.ByteCursor): readonly SqliteBindParam[] | null {
-    return bare.readBool(bc) ? read13(bc) : null
+function read18(bc: bare.ByteCursor): readonly SqliteBindParam[] | null {
+    return bare.readBool(bc) ? read17(bc) : null
 }
 
-function write14(bc: bare.ByteCursor, x: readonly SqliteBindParam[] | null): void {
+function write18(bc: bare.ByteCursor, x: readonly SqliteBindParam[] | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
-        write13(bc, x)
+        write17(bc, x)
     }
 }
 
@@ -1194,7 +1420,7 @@ export function readSqliteExecuteRequest(bc: bare.ByteCursor): SqliteExecuteRequ
         actorId: readId(bc),
         generation: readSqliteGeneration(bc),
         sql: bare.readString(bc),
-        params: read14(bc),
+        params: read18(bc),
     }
 }
 
@@ -1203,7 +1429,7 @@ export function writeSqliteExecuteRequest(bc: bare.ByteCursor, x: SqliteExecuteR
     writeId(bc, x.actorId)
     writeSqliteGeneration(bc, x.generation)
     bare.writeString(bc, x.sql)
-    write14(bc, x.params)
+    write18(bc, x.params)
 }
 
 export type SqliteBatchStatement = {
@@ -1214,16 +1440,16 @@ export type SqliteBatchStatement = {
 export function readSqliteBatchStatement(bc: bare.ByteCursor): SqliteBatchStatement {
     return {
         sql: bare.readString(bc),
-        params: read14(bc),
+        params: read18(bc),
     }
 }
 
 export function writeSqliteBatchStatement(bc: bare.ByteCursor, x: SqliteBatchStatement): void {
     bare.writeString(bc, x.sql)
-    write14(bc, x.params)
+    write18(bc, x.params)
 }
 
-function read15(bc: bare.ByteCursor): readonly SqliteBatchStatement[] {
+function read19(bc: bare.ByteCursor): readonly SqliteBatchStatement[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1235,7 +1461,7 @@ function read15(bc: bare.ByteCursor): readonly SqliteBatchStatement[] {
     return result
 }
 
-function write15(bc: bare.ByteCursor, x: readonly SqliteBatchStatement[]): void {
+function write19(bc: bare.ByteCursor, x: readonly SqliteBatchStatement[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writeSqliteBatchStatement(bc, x[i])
@@ -1254,7 +1480,7 @@ export function readSqliteExecuteBatchRequest(bc: bare.ByteCursor): SqliteExecut
         namespaceId: readId(bc),
         actorId: readId(bc),
         generation: readSqliteGeneration(bc),
-        statements: read15(bc),
+        statements: read19(bc),
     }
 }
 
@@ -1262,7 +1488,7 @@ export function writeSqliteExecuteBatchRequest(bc: bare.ByteCursor, x: SqliteExe
     writeId(bc, x.namespaceId)
     writeId(bc, x.actorId)
     writeSqliteGeneration(bc, x.generation)
-    write15(bc, x.statements)
+    write19(bc, x.statements)
 }
 
 export type SqliteExecOk = {
@@ -1293,7 +1519,7 @@ export function writeSqliteExecuteOk(bc: bare.ByteCursor, x: SqliteExecuteOk): v
     writeSqliteExecuteResult(bc, x.result)
 }
 
-function read16(bc: bare.ByteCursor): readonly SqliteExecuteResult[] {
+function read20(bc: bare.ByteCursor): readonly SqliteExecuteResult[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1305,7 +1531,7 @@ function read16(bc: bare.ByteCursor): readonly SqliteExecuteResult[] {
     return result
 }
 
-function write16(bc: bare.ByteCursor, x: readonly SqliteExecuteResult[]): void {
+function write20(bc: bare.ByteCursor, x: readonly SqliteExecuteResult[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writeSqliteExecuteResult(bc, x[i])
@@ -1318,12 +1544,12 @@ export type SqliteExecuteBatchOk = {
 
 export function readSqliteExecuteBatchOk(bc: bare.ByteCursor): SqliteExecuteBatchOk {
     return {
-        results: read16(bc),
+        results: read20(bc),
     }
 }
 
 export function writeSqliteExecuteBatchOk(bc: bare.ByteCursor, x: SqliteExecuteBatchOk): void {
-    write16(bc, x.results)
+    write20(bc, x.results)
 }
 
 export type SqliteExecResponse =
@@ -1478,22 +1704,22 @@ export function writeActorName(bc: bare.ByteCursor, x: ActorName): void {
     writeJson(bc, x.metadata)
 }
 
-function read17(bc: bare.ByteCursor): string | null {
+function read21(bc: bare.ByteCursor): string | null {
     return bare.readBool(bc) ? bare.readString(bc) : null
 }
 
-function write17(bc: bare.ByteCursor, x: string | null): void {
+function write21(bc: bare.ByteCursor, x: string | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
         bare.writeString(bc, x)
     }
 }
 
-function read18(bc: bare.ByteCursor): ArrayBuffer | null {
+function read22(bc: bare.ByteCursor): ArrayBuffer | null {
     return bare.readBool(bc) ? bare.readData(bc) : null
 }
 
-function write18(bc: bare.ByteCursor, x: ArrayBuffer | null): void {
+function write22(bc: bare.ByteCursor, x: ArrayBuffer | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
         bare.writeData(bc, x)
@@ -1510,17 +1736,17 @@ export type ActorConfig = {
 export function readActorConfig(bc: bare.ByteCursor): ActorConfig {
     return {
         name: bare.readString(bc),
-        key: read17(bc),
+        key: read21(bc),
         createTs: bare.readI64(bc),
-        input: read18(bc),
+        input: read22(bc),
     }
 }
 
 export function writeActorConfig(bc: bare.ByteCursor, x: ActorConfig): void {
     bare.writeString(bc, x.name)
-    write17(bc, x.key)
+    write21(bc, x.key)
     bare.writeI64(bc, x.createTs)
-    write18(bc, x.input)
+    write22(bc, x.input)
 }
 
 export type ActorCheckpoint = {
@@ -1595,13 +1821,13 @@ export type ActorStateStopped = {
 export function readActorStateStopped(bc: bare.ByteCursor): ActorStateStopped {
     return {
         code: readStopCode(bc),
-        message: read17(bc),
+        message: read21(bc),
     }
 }
 
 export function writeActorStateStopped(bc: bare.ByteCursor, x: ActorStateStopped): void {
     writeStopCode(bc, x.code)
-    write17(bc, x.message)
+    write21(bc, x.message)
 }
 
 export type ActorState =
@@ -1674,12 +1900,12 @@ export type EventActorSetAlarm = {
 
 export function readEventActorSetAlarm(bc: bare.ByteCursor): EventActorSetAlarm {
     return {
-        alarmTs: read12(bc),
+        alarmTs: read16(bc),
     }
 }
 
 export function writeEventActorSetAlarm(bc: bare.ByteCursor, x: EventActorSetAlarm): void {
-    write12(bc, x.alarmTs)
+    write16(bc, x.alarmTs)
 }
 
 export type Event =
@@ -1761,7 +1987,7 @@ export function writePreloadedKvEntry(bc: bare.ByteCursor, x: PreloadedKvEntry):
     writeKvMetadata(bc, x.metadata)
 }
 
-function read19(bc: bare.ByteCursor): readonly PreloadedKvEntry[] {
+function read23(bc: bare.ByteCursor): readonly PreloadedKvEntry[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1773,7 +1999,7 @@ function read19(bc: bare.ByteCursor): readonly PreloadedKvEntry[] {
     return result
 }
 
-function write19(bc: bare.ByteCursor, x: readonly PreloadedKvEntry[]): void {
+function write23(bc: bare.ByteCursor, x: readonly PreloadedKvEntry[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writePreloadedKvEntry(bc, x[i])
@@ -1788,14 +2014,14 @@ export type PreloadedKv = {
 
 export function readPreloadedKv(bc: bare.ByteCursor): PreloadedKv {
     return {
-        entries: read19(bc),
+        entries: read23(bc),
         requestedGetKeys: read0(bc),
         requestedPrefixes: read0(bc),
     }
 }
 
 export function writePreloadedKv(bc: bare.ByteCursor, x: PreloadedKv): void {
-    write19(bc, x.entries)
+    write23(bc, x.entries)
     write0(bc, x.requestedGetKeys)
     write0(bc, x.requestedPrefixes)
 }
@@ -1817,7 +2043,7 @@ export function writeHibernatingRequest(bc: bare.ByteCursor, x: HibernatingReque
     writeRequestId(bc, x.requestId)
 }
 
-function read20(bc: bare.ByteCursor): readonly HibernatingRequest[] {
+function read24(bc: bare.ByteCursor): readonly HibernatingRequest[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -1829,18 +2055,18 @@ function read20(bc: bare.ByteCursor): readonly HibernatingRequest[] {
     return result
 }
 
-function write20(bc: bare.ByteCursor, x: readonly HibernatingRequest[]): void {
+function write24(bc: bare.ByteCursor, x: readonly HibernatingRequest[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writeHibernatingRequest(bc, x[i])
     }
 }
 
-function read21(bc: bare.ByteCursor): PreloadedKv | null {
+function read25(bc: bare.ByteCursor): PreloadedKv | null {
     return bare.readBool(bc) ? readPreloadedKv(bc) : null
 }
 
-function write21(bc: bare.ByteCursor, x: PreloadedKv | null): void {
+function write25(bc: bare.ByteCursor, x: PreloadedKv | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
         writePreloadedKv(bc, x)
@@ -1856,15 +2082,15 @@ export type CommandStartActor = {
 export function readCommandStartActor(bc: bare.ByteCursor): CommandStartActor {
     return {
         config: readActorConfig(bc),
-        hibernatingRequests: read20(bc),
-        preloadedKv: read21(bc),
+        hibernatingRequests: read24(bc),
+        preloadedKv: read25(bc),
     }
 }
 
 export function writeCommandStartActor(bc: bare.ByteCursor, x: CommandStartActor): void {
     writeActorConfig(bc, x.config)
-    write20(bc, x.hibernatingRequests)
-    write21(bc, x.preloadedKv)
+    write24(bc, x.hibernatingRequests)
+    write25(bc, x.preloadedKv)
 }
 
 export enum StopActorReason {
@@ -2071,7 +2297,7 @@ export function writeMessageId(bc: bare.ByteCursor, x: MessageId): void {
     writeMessageIndex(bc, x.messageIndex)
 }
 
-function read22(bc: bare.ByteCursor): ReadonlyMap<string, string> {
+function read26(bc: bare.ByteCursor): ReadonlyMap<string, string> {
     const len = bare.readUintSafe(bc)
     const result = new Map<string, string>()
     for (let i = 0; i < len; i++) {
@@ -2086,7 +2312,7 @@ function read22(bc: bare.ByteCursor): ReadonlyMap<string, string> {
     return result
 }
 
-function write22(bc: bare.ByteCursor, x: ReadonlyMap<string, string>): void {
+function write26(bc: bare.ByteCursor, x: ReadonlyMap<string, string>): void {
     bare.writeUintSafe(bc, x.size)
     for (const kv of x) {
         bare.writeString(bc, kv[0])
@@ -2111,8 +2337,8 @@ export function readToEnvoyRequestStart(bc: bare.ByteCursor): ToEnvoyRequestStar
         actorId: readId(bc),
         method: bare.readString(bc),
         path: bare.readString(bc),
-        headers: read22(bc),
-        body: read18(bc),
+        headers: read26(bc),
+        body: read22(bc),
         stream: bare.readBool(bc),
     }
 }
@@ -2121,26 +2347,43 @@ export function writeToEnvoyRequestStart(bc: bare.ByteCursor, x: ToEnvoyRequestS
     writeId(bc, x.actorId)
     bare.writeString(bc, x.method)
     bare.writeString(bc, x.path)
-    write22(bc, x.headers)
-    write18(bc, x.body)
+    write26(bc, x.headers)
+    write22(bc, x.body)
     bare.writeBool(bc, x.stream)
 }
 
+function read27(bc: bare.ByteCursor): ReadonlyMap<string, string> | null {
+    return bare.readBool(bc) ? read26(bc) : null
+}
+
+function write27(bc: bare.ByteCursor, x: ReadonlyMap<string, string> | null): void {
+    bare.writeBool(bc, x != null)
+    if (x != null) {
+        write26(bc, x)
+    }
+}
+
 export type ToEnvoyRequestChunk = {
     readonly body: ArrayBuffer
     readonly finish: boolean
+    /**
+     * HTTP trailers. Only set on the finishing chunk.
+     */
+    readonly trailers: ReadonlyMap<string, string> | null
 }
 
 export function readToEnvoyRequestChunk(bc: bare.ByteCursor): ToEnvoyRequestChunk {
     return {
         body: bare.readData(bc),
         finish: bare.readBool(bc),
+        trailers: read27(bc),
     }
 }
 
 export function writeToEnvoyRequestChunk(bc: bare.ByteCursor, x: ToEnvoyRequestChunk): void {
     bare.writeData(bc, x.body)
     bare.writeBool(bc, x.finish)
+    write27(bc, x.trailers)
 }
 
 export type ToEnvoyRequestAbort = null
@@ -2155,38 +2398,55 @@ export type ToRivetResponseStart = {
 export function readToRivetResponseStart(bc: bare.ByteCursor): ToRivetResponseStart {
     return {
         status: bare.readU16(bc),
-        headers: read22(bc),
-        body: read18(bc),
+        headers: read26(bc),
+        body: read22(bc),
         stream: bare.readBool(bc),
     }
 }
 
 export function writeToRivetResponseStart(bc: bare.ByteCursor, x: ToRivetResponseStart): void {
     bare.writeU16(bc, x.status)
-    write22(bc, x.headers)
-    write18(bc, x.body)
+    write26(bc, x.headers)
+    write22(bc, x.body)
     bare.writeBool(bc, x.stream)
 }
 
 export type ToRivetResponseChunk = {
     readonly body: ArrayBuffer
     readonly finish: boolean
+    /**
+     * HTTP trailers. Only set on the finishing chunk.
+     */
+    readonly trailers: ReadonlyMap<string, string> | null
 }
 
 export function readToRivetResponseChunk(bc: bare.ByteCursor): ToRivetResponseChunk {
     return {
         body: bare.readData(bc),
         finish: bare.readBool(bc),
+        trailers: read27(bc),
     }
 }
 
 export function writeToRivetResponseChunk(bc: bare.ByteCursor, x: ToRivetResponseChunk): void {
     bare.writeData(bc, x.body)
     bare.writeBool(bc, x.finish)
+    write27(bc, x.trailers)
 }
 
 export type ToRivetResponseAbort = null
 
+function read28(bc: bare.ByteCursor): u32 | null {
+    return bare.readBool(bc) ? bare.readU32(bc) : null
+}
+
+function write28(bc: bare.ByteCursor, x: u32 | null): void {
+    bare.writeBool(bc, x != null)
+    if (x != null) {
+        bare.writeU32(bc, x)
+    }
+}
+
 /**
  * WebSocket
  */
@@ -2194,20 +2454,27 @@ export type ToEnvoyWebSocketOpen = {
     readonly actorId: Id
     readonly path: string
     readonly headers: ReadonlyMap<string, string>
+    /**
+     * Bytes the actor may send before waiting for credit. Not set if the gateway does not
+     * grant credit for this connection.
+     */
+    readonly sendWindow: u32 | null
 }
 
 export function readToEnvoyWebSocketOpen(bc: bare.ByteCursor): ToEnvoyWebSocketOpen {
     return {
         actorId: readId(bc),
         path: bare.readString(bc),
-        headers: read22(bc),
+        headers: read26(bc),
+        sendWindow: read28(bc),
     }
 }
 
 export function writeToEnvoyWebSocketOpen(bc: bare.ByteCursor, x: ToEnvoyWebSocketOpen): void {
     writeId(bc, x.actorId)
     bare.writeString(bc, x.path)
-    write22(bc, x.headers)
+    write26(bc, x.headers)
+    write28(bc, x.sendWindow)
 }
 
 export type ToEnvoyWebSocketMessage = {
@@ -2227,11 +2494,11 @@ export function writeToEnvoyWebSocketMessage(bc: bare.ByteCursor, x: ToEnvoyWebS
     bare.writeBool(bc, x.binary)
 }
 
-function read23(bc: bare.ByteCursor): u16 | null {
+function read29(bc: bare.ByteCursor): u16 | null {
     return bare.readBool(bc) ? bare.readU16(bc) : null
 }
 
-function write23(bc: bare.ByteCursor, x: u16 | null): void {
+function write29(bc: bare.ByteCursor, x: u16 | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
         bare.writeU16(bc, x)
@@ -2245,28 +2512,52 @@ export type ToEnvoyWebSocketClose = {
 
 export function readToEnvoyWebSocketClose(bc: bare.ByteCursor): ToEnvoyWebSocketClose {
     return {
-        code: read23(bc),
-        reason: read17(bc),
+        code: read29(bc),
+        reason: read21(bc),
     }
 }
 
 export function writeToEnvoyWebSocketClose(bc: bare.ByteCursor, x: ToEnvoyWebSocketClose): void {
-    write23(bc, x.code)
-    write17(bc, x.reason)
+    write29(bc, x.code)
+    write21(bc, x.reason)
+}
+
+/**
+ * Returns bytes of the send window after the gateway delivered them to the client
+ */
+export type ToEnvoyWebSocketCredit = {
+    readonly bytes: u32
+}
+
+export function readToEnvoyWebSocketCredit(bc: bare.ByteCursor): ToEnvoyWebSocketCredit {
+    return {
+        bytes: bare.readU32(bc),
+    }
+}
+
+export function writeToEnvoyWebSocketCredit(bc: bare.ByteCursor, x: ToEnvoyWebSocketCredit): void {
+    bare.writeU32(bc, x.bytes)
 }
 
 export type ToRivetWebSocketOpen = {
     readonly canHibernate: boolean
+    /**
+     * Whether the envoy returns credit for messages delivered to the actor and honors the
+     * send window
+     */
+    readonly flowControl: boolean
 }
 
 export function readToRivetWebSocketOpen(bc: bare.ByteCursor): ToRivetWebSocketOpen {
     return {
         canHibernate: bare.readBool(bc),
+        flowControl: bare.readBool(bc),
     }
 }
 
 export function writeToRivetWebSocketOpen(bc: bare.ByteCursor, x: ToRivetWebSocketOpen): void {
     bare.writeBool(bc, x.canHibernate)
+    bare.writeBool(bc, x.flowControl)
 }
 
 export type ToRivetWebSocketMessage = {
@@ -2308,18 +2599,35 @@ export type ToRivetWebSocketClose = {
 
 export function readToRivetWebSocketClose(bc: bare.ByteCursor): ToRivetWebSocketClose {
     return {
-        code: read23(bc),
-        reason: read17(bc),
+        code: read29(bc),
+        reason: read21(bc),
         hibernate: bare.readBool(bc),
     }
 }
 
 export function writeToRivetWebSocketClose(bc: bare.ByteCursor, x: ToRivetWebSocketClose): void {
-    write23(bc, x.code)
-    write17(bc, x.reason)
+    write29(bc, x.code)
+    write21(bc, x.reason)
     bare.writeBool(bc, x.hibernate)
 }
 
+/**
+ * Returns bytes after the actor handled them
+ */
+export type ToRivetWebSocketCredit = {
+    readonly bytes: u32
+}
+
+export function readToRivetWebSocketCredit(bc: bare.ByteCursor): ToRivetWebSocketCredit {
+    return {
+        bytes: bare.readU32(bc),
+    }
+}
+
+export function writeToRivetWebSocketCredit(bc: bare.ByteCursor, x: ToRivetWebSocketCredit): void {
+    bare.writeU32(bc, x.bytes)
+}
+
 /**
  * To Rivet
  */
@@ -2337,6 +2645,7 @@ export type ToRivetTunnelMessageKind =
     | { readonly tag: "ToRivetWebSocketMessage"; readonly val: ToRivetWebSocketMessage }
     | { readonly tag: "ToRivetWebSocketMessageAck"; readonly val: ToRivetWebSocketMessageAck }
     | { readonly tag: "ToRivetWebSocketClose"; readonly val: ToRivetWebSocketClose }
+    | { readonly tag: "ToRivetWebSocketCredit"; readonly val: ToRivetWebSocketCredit }
 
 export function readToRivetTunnelMessageKind(bc: bare.ByteCursor): ToRivetTunnelMessageKind {
     const offset = bc.offset
@@ -2356,6 +2665,8 @@ export function readToRivetTunnelMessageKind(bc: bare.ByteCursor): ToRivetTunnel
             return { tag: "ToRivetWebSocketMessageAck", val: readToRivetWebSocketMessageAck(bc) }
         case 6:
             return { tag: "ToRivetWebSocketClose", val: readToRivetWebSocketClose(bc) }
+        case 7:
+            return { tag: "ToRivetWebSocketCredit", val: readToRivetWebSocketCredit(bc) }
         default: {
             bc.offset = offset
             throw new bare.BareError(offset, "invalid tag")
@@ -2399,6 +2710,11 @@ export function writeToRivetTunnelMessageKind(bc: bare.ByteCursor, x: ToRivetTun
             writeToRivetWebSocketClose(bc, x.val)
             break
         }
+        case "ToRivetWebSocketCredit": {
+            bare.writeU8(bc, 7)
+            writeToRivetWebSocketCredit(bc, x.val)
+            break
+        }
     }
 }
 
@@ -2435,6 +2751,7 @@ export type ToEnvoyTunnelMessageKind =
     | { readonly tag: "ToEnvoyWebSocketOpen"; readonly val: ToEnvoyWebSocketOpen }
     | { readonly tag: "ToEnvoyWebSocketMessage"; readonly val: ToEnvoyWebSocketMessage }
     | { readonly tag: "ToEnvoyWebSocketClose"; readonly val: ToEnvoyWebSocketClose }
+    | { readonly tag: "ToEnvoyWebSocketCredit"; readonly val: ToEnvoyWebSocketCredit }
 
 export function readToEnvoyTunnelMessageKind(bc: bare.ByteCursor): ToEnvoyTunnelMessageKind {
     const offset = bc.offset
@@ -2452,6 +2769,8 @@ export function readToEnvoyTunnelMessageKind(bc: bare.ByteCursor): ToEnvoyTunnel
             return { tag: "ToEnvoyWebSocketMessage", val: readToEnvoyWebSocketMessage(bc) }
         case 5:
             return { tag: "ToEnvoyWebSocketClose", val: readToEnvoyWebSocketClose(bc) }
+        case 6:
+            return { tag: "ToEnvoyWebSocketCredit", val: readToEnvoyWebSocketCredit(bc) }
         default: {
             bc.offset = offset
             throw new bare.BareError(offset, "invalid tag")
@@ -2490,6 +2809,11 @@ export function writeToEnvoyTunnelMessageKind(bc: bare.ByteCursor, x: ToEnvoyTun
             writeToEnvoyWebSocketClose(bc, x.val)
             break
         }
+        case "ToEnvoyWebSocketCredit": {
+            bare.writeU8(bc, 6)
+            writeToEnvoyWebSocketCredit(bc, x.val)
+            break
+        }
     }
 }
 
@@ -2524,7 +2848,7 @@ export function writeToEnvoyPing(bc: bare.ByteCursor, x: ToEnvoyPing): void {
     bare.writeI64(bc, x.ts)
 }
 
-function read24(bc: bare.ByteCursor): ReadonlyMap<string, ActorName> {
+function read30(bc: bare.ByteCursor): ReadonlyMap<string, ActorName> {
     const len = bare.readUintSafe(bc)
     const result = new Map<string, ActorName>()
     for (let i = 0; i < len; i++) {
@@ -2539,7 +2863,7 @@ function read24(bc: bare.ByteCursor): ReadonlyMap<string, ActorName> {
     return result
 }
 
-function write24(bc: bare.ByteCursor, x: ReadonlyMap<string, ActorName>): void {
+function write30(bc: bare.ByteCursor, x: ReadonlyMap<string, ActorName>): void {
     bare.writeUintSafe(bc, x.size)
     for (const kv of x) {
         bare.writeString(bc, kv[0])
@@ -2547,22 +2871,22 @@ function write24(bc: bare.ByteCursor, x: ReadonlyMap<string, ActorName>): void {
     }
 }
 
-function read25(bc: bare.ByteCursor): ReadonlyMap<string, ActorName> | null {
-    return bare.readBool(bc) ? read24(bc) : null
+function read31(bc: bare.ByteCursor): ReadonlyMap<string, ActorName> | null {
+    return bare.readBool(bc) ? read30(bc) : null
 }
 
-function write25(bc: bare.ByteCursor, x: ReadonlyMap<string, ActorName> | null): void {
+function write31(bc: bare.ByteCursor, x: ReadonlyMap<string, ActorName> | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
-        write24(bc, x)
+        write30(bc, x)
     }
 }
 
-function read26(bc: bare.ByteCursor): Json | null {
+function read32(bc: bare.ByteCursor): Json | null {
     return bare.readBool(bc) ? readJson(bc) : null
 }
 
-function write26(bc: bare.ByteCursor, x: Json | null): void {
+function write32(bc: bare.ByteCursor, x: Json | null): void {
     bare.writeBool(bc, x != null)
     if (x != null) {
         writeJson(bc, x)
@@ -2579,14 +2903,14 @@ export type ToRivetMetadata = {
 
 export function readToRivetMetadata(bc: bare.ByteCursor): ToRivetMetadata {
     return {
-        prepopulateActorNames: read25(bc),
-        metadata: read26(bc),
+        prepopulateActorNames: read31(bc),
+        metadata: read32(bc),
     }
 }
 
 export function writeToRivetMetadata(bc: bare.ByteCursor, x: ToRivetMetadata): void {
-    write25(bc, x.prepopulateActorNames)
-    write26(bc, x.metadata)
+    write31(bc, x.prepopulateActorNames)
+    write32(bc, x.metadata)
 }
 
 export type ToRivetEvents = readonly EventWrapper[]
@@ -2610,7 +2934,7 @@ export function writeToRivetEvents(bc: bare.ByteCursor, x: ToRivetEvents): void
     }
 }
 
-function read27(bc: bare.ByteCursor): readonly ActorCheckpoint[] {
+function read33(bc: bare.ByteCursor): readonly ActorCheckpoint[] {
     const len = bare.readUintSafe(bc)
     if (len === 0) {
         return []
@@ -2622,7 +2946,7 @@ function read27(bc: bare.ByteCursor): readonly ActorCheckpoint[] {
     return result
 }
 
-function write27(bc: bare.ByteCursor, x: readonly ActorCheckpoint[]): void {
+function write33(bc: bare.ByteCursor, x: readonly ActorCheckpoint[]): void {
     bare.writeUintSafe(bc, x.length)
     for (let i = 0; i < x.length; i++) {
         writeActorCheckpoint(bc, x[i])
@@ -2635,12 +2959,12 @@ export type ToRivetAckCommands = {
 
 export function readToRivetAckCommands(bc: bare.ByteCursor): ToRivetAckCommands {
     return {
-        lastCommandCheckpoints: read27(bc),
+        lastCommandCheckpoints: read33(bc),
     }
 }
 
 export function writeToRivetAckCommands(bc: bare.ByteCursor, x: ToRivetAckCommands): void {
-    write27(bc, x.lastCommandCheckpoints)
+    write33(bc, x.lastCommandCheckpoints)
 }
 
 export type ToRivetStopping = null
@@ -2764,6 +3088,23 @@ export function writeToRivetSqliteExecuteBatchRequest(bc: bare.ByteCursor, x: To
     writeSqliteExecuteBatchRequest(bc, x.data)
 }
 
+export type ToRivetSqliteGetRowChangeLogRequest = {
+    readonly requestId: u32
+    readonly data: SqliteGetRowChangeLogRequest
+}
+
+export function readToRivetSqliteGetRowChangeLogRequest(bc: bare.ByteCursor): ToRivetSqliteGetRowChangeLogRequest {
+    return {
+        requestId: bare.readU32(bc),
+        data: readSqliteGetRowChangeLogRequest(bc),
+    }
+}
+
+export function writeToRivetSqliteGetRowChangeLogRequest(bc: bare.ByteCursor, x: ToRivetSqliteGetRowChangeLogRequest): void {
+    bare.writeU32(bc, x.requestId)
+    writeSqliteGetRowChangeLogRequest(bc, x.data)
+}
+
 export type ToRivet =
     | { readonly tag: "ToRivetMetadata"; readonly val: ToRivetMetadata }
     | { readonly tag: "ToRivetEvents"; readonly val: ToRivetEvents }
@@ -2777,6 +3118,7 @@ export type ToRivet =
     | { readonly tag: "ToRivetSqliteExecRequest"; readonly val: ToRivetSqliteExecRequest }
     | { readonly tag: "ToRivetSqliteExecuteRequest"; readonly val: ToRivetSqliteExecuteRequest }
     | { readonly tag: "ToRivetSqliteExecuteBatchRequest"; readonly val: ToRivetSqliteExecuteBatchRequest }
+    | { readonly tag: "ToRivetSqliteGetRowChangeLogRequest"; readonly val: ToRivetSqliteGetRowChangeLogRequest }
 
 export function readToRivet(bc: bare.ByteCursor): ToRivet {
     const offset = bc.offset
@@ -2806,6 +3148,8 @@ export function readToRivet(bc: bare.ByteCursor): ToRivet {
             return { tag: "ToRivetSqliteExecuteRequest", val: readToRivetSqliteExecuteRequest(bc) }
         case 11:
             return { tag: "ToRivetSqliteExecuteBatchRequest", val: readToRivetSqliteExecuteBatchRequest(bc) }
+        case 12:
+            return { tag: "ToRivetSqliteGetRowChangeLogRequest", val: readToRivetSqliteGetRowChangeLogRequest(bc) }
         default: {
             bc.offset = offset
             throw new bare.BareError(offset, "invalid tag")
@@ -2874,6 +3218,11 @@ export function writeToRivet(bc: bare.ByteCursor, x: ToRivet): void {
             writeToRivetSqliteExecuteBatchRequest(bc, x.val)
             break
         }
+        case "ToRivetSqliteGetRowChangeLogRequest": {
+            bare.writeU8(bc, 12)
+            writeToRivetSqliteGetRowChangeLogRequest(bc, x.val)
+            break
+        }
     }
 }
 
@@ -2960,12 +3309,12 @@ export type ToEnvoyAckEvents = {
 
 export function readToEnvoyAckEvents(bc: bare.ByteCursor): ToEnvoyAckEvents {
     return {
-        lastEventCheckpoints: read27(bc),
+        lastEventCheckpoints: read33(bc),
     }
 }
 
 export function writeToEnvoyAckEvents(bc: bare.ByteCursor, x: ToEnvoyAckEvents): void {
-    write27(bc, x.lastEventCheckpoints)
+    write33(bc, x.lastEventCheckpoints)
 }
 
 export type ToEnvoyKvResponse = {
@@ -3070,6 +3419,23 @@ export function writeToEnvoySqliteExecuteBatchResponse(bc: bare.ByteCursor, x: T
     writeSqliteExecuteBatchResponse(bc, x.data)
 }
 
+export type ToEnvoySqliteGetRowChangeLogResponse = {
+    readonly requestId: u32
+    readonly data: SqliteGetRowChangeLogResponse
+}
+
+export function readToEnvoySqliteGetRowChangeLogResponse(bc: bare.ByteCursor): ToEnvoySqliteGetRowChangeLogResponse {
+    return {
+        requestId: bare.readU32(bc),
+        data: readSqliteGetRowChangeLogResponse(bc),
+    }
+}
+
+export function writeToEnvoySqliteGetRowChangeLogResponse(bc: bare.ByteCursor, x: ToEnvoySqliteGetRowChangeLogResponse): void {
+    bare.writeU32(bc, x.requestId)
+    writeSqliteGetRowChangeLogResponse(bc, x.data)
+}
+
 export type ToEnvoy =
     | { readonly tag: "ToEnvoyInit"; readonly val: ToEnvoyInit }
     | { readonly tag: "ToEnvoyCommands"; readonly val: ToEnvoyCommands }
@@ -3082,6 +3448,7 @@ export type ToEnvoy =
     | { readonly tag: "ToEnvoySqliteExecResponse"; readonly val: ToEnvoySqliteExecResponse }
     | { readonly tag: "ToEnvoySqliteExecuteResponse"; readonly val: ToEnvoySqliteExecuteResponse }
     | { readonly tag: "ToEnvoySqliteExecuteBatchResponse"; readonly val: ToEnvoySqliteExecuteBatchResponse }
+    | { readonly tag: "ToEnvoySqliteGetRowChangeLogResponse"; readonly val: ToEnvoySqliteGetRowChangeLogResponse }
 
 export function readToEnvoy(bc: bare.ByteCursor): ToEnvoy {
     const offset = bc.offset
@@ -3109,6 +3476,8 @@ export function readToEnvoy(bc: bare.ByteCursor): ToEnvoy {
             return { tag: "ToEnvoySqliteExecuteResponse", val: readToEnvoySqliteExecuteResponse(bc) }
         case 10:
             return { tag: "ToEnvoySqliteExecuteBatchResponse", val: readToEnvoySqliteExecuteBatchResponse(bc) }
+        case 11:
+            return { tag: "ToEnvoySqliteGetRowChangeLogResponse", val: readToEnvoySqliteGetRowChangeLogResponse(bc) }
         default: {
             bc.offset = offset
             throw new bare.BareError(offset, "invalid tag")
@@ -3173,6 +3542,11 @@ export function writeToEnvoy(bc: bare.ByteCursor, x: ToEnvoy): void {
             writeToEnvoySqliteExecuteBatchResponse(bc, x.val)
             break
         }
+        case "ToEnvoySqliteGetRowChangeLogResponse": {
+            bare.writeU8(bc, 11)
+            writeToEnvoySqliteGetRowChangeLogResponse(bc, x.val)
+            break
+        }
     }
 }
 
@@ -3445,4 +3819,4 @@ function assert(condition: boolean, message?: string): asserts condition {
     if (!condition) throw new Error(message ?? "Assertion failed")
 }
 
-export const VERSION = 6;
\ No newline at end of file
+export const VERSION = 7;
\ No newline at end of file
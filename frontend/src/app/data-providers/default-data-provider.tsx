@@ -255,6 +255,17 @@ const defaultContext = {
 			enabled: false,
 		});
 	},
+	actorLineageQueryOptions(actorId: ActorId) {
+		return queryOptions({
+			queryKey: ["actor", actorId, "lineage"] as QueryKey,
+			queryFn: async () => {
+				throw new Error("Not implemented");
+				// biome-ignore lint/correctness/noUnreachable: stub
+				return {} as Rivet.ActorsGetDatabaseLineageResponse;
+			},
+			enabled: false,
+		});
+	},
 	actorKeysQueryOptions(actorId: ActorId) {
 		return queryOptions({
 			...this.actorQueryOptions(actorId),
@@ -540,6 +540,26 @@ export const createNamespaceContext = ({
 				},
 			});
 		},
+		actorLineageQueryOptions(actorId: ActorId) {
+			return queryOptions({
+				...def.actorLineageQueryOptions(actorId),
+				queryKey: [
+					{ namespace },
+					...def.actorLineageQueryOptions(actorId).queryKey,
+				],
+				enabled: !!actorId,
+				queryFn: async () => {
+					return client.actorsGetDatabaseLineage(actorId, {
+						namespace,
+					});
+				},
+				retry: shouldRetryAllExpect403,
+				throwOnError: noThrow,
+				meta: {
+					mightRequireAuth,
+				},
+			});
+		},
 		metadataQueryOptions() {
 			return queryOptions({
 				queryKey: [{ namespace }, "metadata"] as QueryKey,
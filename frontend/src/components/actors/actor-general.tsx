@@ -151,6 +151,7 @@ export function ActorGeneral({ actorId }: ActorGeneralProps) {
 								</Dd>
 							</>
 						) : null}
+						<Lineage actorId={actorId} />
 						<Versions actorId={actorId} />
 					</Dl>
 				</Flex>
@@ -178,6 +179,65 @@ function TimestampValue({ ts }: { ts: Date }) {
 	);
 }
 
+function Lineage({ actorId }: { actorId: ActorId }) {
+	const { data: lineage } = useQuery(
+		useDataProvider().actorLineageQueryOptions(actorId),
+	);
+
+	if (!lineage) return null;
+
+	return (
+		<>
+			{lineage.forkedFromActorId ? (
+				<>
+					<Dt>Forked from</Dt>
+					<Dd className="text-mono">
+						<DiscreteCopyButton
+							size="xs"
+							value={lineage.forkedFromActorId}
+							className="-mx-2 h-auto"
+						>
+							{lineage.forkedFromActorId}
+						</DiscreteCopyButton>
+					</Dd>
+				</>
+			) : null}
+			{lineage.branches.length > 1 ? (
+				<>
+					<Dt>Database branches</Dt>
+					<Dd>
+						<Flex direction="col" gap="1">
+							{lineage.branches.map((branch, index) => (
+								<div
+									key={branch.branchId}
+									className="flex items-center gap-2"
+								>
+									<DiscreteCopyButton
+										size="xs"
+										value={branch.branchId}
+										className="-mx-2 h-auto text-mono"
+									>
+										{branch.branchId.slice(0, 8)}
+									</DiscreteCopyButton>
+									<span className="text-muted-foreground">
+										<RelativeTime
+											time={new Date(branch.createTs)}
+										/>
+										{index === 0 ? " (current)" : null}
+										{branch.restorePointId
+											? ` from ${branch.restorePointId}`
+											: null}
+									</span>
+								</div>
+							))}
+						</Flex>
+					</Dd>
+				</>
+			) : null}
+		</>
+	);
+}
+
 function Versions({ actorId }: { actorId: ActorId }) {
 	const inspector = useActorInspector();
 	const { data: status } = useQuery(
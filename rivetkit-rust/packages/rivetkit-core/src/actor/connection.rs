@@ -19,7 +19,7 @@ use crate::actor::config::ActorConfig;
 use crate::actor::context::ActorContext;
 use crate::actor::internal_storage;
 use crate::actor::lifecycle_hooks::Reply;
-use crate::actor::messages::{ActorEvent, Request};
+use crate::actor::messages::{ActorEvent, GatewayAuth, Request};
 use crate::actor::persist::{
 	decode_latest_with_embedded_version, encode_latest_with_embedded_version,
 };
@@ -156,6 +156,7 @@ struct ConnHandleInner {
 	dirty: AtomicBool,
 	subscriptions: RwLock<BTreeSet<String>>,
 	hibernation: RwLock<Option<HibernatableConnectionMetadata>>,
+	gateway_auth: RwLock<Option<GatewayAuth>>,
 	state_change_handler: RwLock<Option<StateChangeCallback>>,
 	event_sender: RwLock<Option<EventSendCallback>>,
 	transport_disconnect_handler: RwLock<Option<DisconnectCallback>>,
@@ -177,6 +178,7 @@ impl ConnHandle {
 			dirty: AtomicBool::new(false),
 			subscriptions: RwLock::new(BTreeSet::new()),
 			hibernation: RwLock::new(None),
+			gateway_auth: RwLock::new(None),
 			state_change_handler: RwLock::new(None),
 			event_sender: RwLock::new(None),
 			transport_disconnect_handler: RwLock::new(None),
@@ -299,6 +301,15 @@ impl ConnHandle {
 		self.0.hibernation.read().clone()
 	}
 
+	/// Identity verified by the gateway auth policy when the connection was opened.
+	pub fn gateway_auth(&self) -> Option<GatewayAuth> {
+		self.0.gateway_auth.read().clone()
+	}
+
+	pub(crate) fn configure_gateway_auth(&self, gateway_auth: Option<GatewayAuth>) {
+		*self.0.gateway_auth.write() = gateway_auth;
+	}
+
 	pub(crate) fn configure_state_change_handler(&self, handler: Option<StateChangeCallback>) {
 		*self.0.state_change_handler.write() = handler;
 	}
@@ -341,6 +352,13 @@ impl ConnHandle {
 			persisted.state,
 			true,
 		);
+		conn.configure_gateway_auth(GatewayAuth::from_header_lookup(|name| {
+			persisted
+				.request_headers
+				.iter()
+				.find(|(header, _)| header.eq_ignore_ascii_case(name))
+				.map(|(_, value)| value.as_str())
+		}));
 		conn.configure_hibernation(Some(HibernatableConnectionMetadata {
 			gateway_id: persisted.gateway_id,
 			request_id: persisted.request_id,
@@ -680,6 +698,7 @@ impl ActorContext {
 			is_hibernatable,
 		);
 		conn.configure_hibernation(hibernation);
+		conn.configure_gateway_auth(request.as_ref().and_then(Request::gateway_auth));
 		self.prepare_managed_conn(&conn);
 
 		if let Err(error) = prepare_connection(&conn) {
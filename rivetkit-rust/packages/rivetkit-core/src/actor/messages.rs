@@ -2,7 +2,9 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 
 use anyhow::Result;
+use rivet_envoy_client::config::BodyFrame;
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
 
 use crate::actor::connection::ConnHandle;
 use crate::actor::lifecycle_hooks::Reply;
@@ -67,6 +69,11 @@ impl Request {
 		)
 	}
 
+	/// Identity verified by the gateway auth policy of the namespace, if the request matched one.
+	pub fn gateway_auth(&self) -> Option<GatewayAuth> {
+		GatewayAuth::from_header_lookup(|name| self.headers().get(name)?.to_str().ok())
+	}
+
 	pub fn into_inner(self) -> http::Request<Vec<u8>> {
 		self.0
 	}
@@ -108,6 +115,42 @@ impl From<Request> for http::Request<Vec<u8>> {
 	}
 }
 
+/// Identity of a request accepted by a gateway auth rule.
+///
+/// Read from the `x-rivet-auth-*` headers, which the gateway strips from client requests before
+/// setting them. Only trust them for requests that reached the actor through the gateway.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GatewayAuth {
+	/// Name of the rule that accepted the request.
+	pub rule: String,
+	/// `jwt`, `api_key`, or `signed_url`.
+	pub method: String,
+	/// JWT `sub` claim or API key name.
+	pub subject: Option<String>,
+	/// Claims of a verified JWT.
+	pub claims: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl GatewayAuth {
+	pub(crate) fn from_header_lookup<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Option<Self> {
+		use base64::Engine;
+
+		let claims = get("x-rivet-auth-claims").and_then(|claims| {
+			let claims = base64::engine::general_purpose::URL_SAFE_NO_PAD
+				.decode(claims)
+				.ok()?;
+			serde_json::from_slice(&claims).ok()
+		});
+
+		Some(Self {
+			rule: get("x-rivet-auth-rule")?.to_owned(),
+			method: get("x-rivet-auth-method")?.to_owned(),
+			subject: get("x-rivet-auth-subject").map(ToOwned::to_owned),
+			claims,
+		})
+	}
+}
+
 #[derive(Clone, Debug)]
 pub struct Response(http::Response<Vec<u8>>);
 
@@ -212,6 +255,74 @@ impl From<Response> for http::Response<Vec<u8>> {
 	}
 }
 
+/// HTTP request whose body is streamed instead of buffered, such as a gRPC call. `head` carries
+/// the method, URI, and headers with an empty body.
+#[derive(Debug)]
+pub struct StreamingRequest {
+	pub head: Request,
+	/// Request body frames. The channel closes after the last frame.
+	pub body: mpsc::UnboundedReceiver<BodyFrame>,
+}
+
+impl StreamingRequest {
+	/// Reads the whole body into a buffered request. Trailers are dropped.
+	pub async fn into_buffered(self) -> Result<Request> {
+		let Self { mut head, mut body } = self;
+		let mut buffered = Vec::new();
+		while let Some(frame) = body.recv().await {
+			match frame {
+				BodyFrame::Data(data) => buffered.extend_from_slice(&data),
+				BodyFrame::Trailers(_) => break,
+				BodyFrame::Abort => {
+					return Err(invalid_http_request(
+						"body",
+						"request body was aborted".to_owned(),
+					));
+				}
+			}
+		}
+		*head.body_mut() = buffered;
+		Ok(head)
+	}
+}
+
+/// HTTP response whose body is streamed. `head` carries the status and headers; its body is
+/// ignored. Finish with a `BodyFrame::Trailers` frame to send trailers such as `grpc-status`.
+#[derive(Debug)]
+pub struct StreamingResponse {
+	pub head: Response,
+	/// Response body frames. The response finishes after a `Trailers` frame or once the sender is
+	/// dropped.
+	pub body: mpsc::UnboundedReceiver<BodyFrame>,
+}
+
+impl StreamingResponse {
+	/// Creates a response along with the sender for its body frames.
+	pub fn channel(head: Response) -> (Self, mpsc::UnboundedSender<BodyFrame>) {
+		let (body_tx, body) = mpsc::unbounded_channel();
+		(Self { head, body }, body_tx)
+	}
+}
+
+impl From<Response> for StreamingResponse {
+	/// Sends a buffered body as a single data frame.
+	fn from(response: Response) -> Self {
+		let (mut parts, body) = response.into_inner().into_parts();
+		if !parts.headers.contains_key(http::header::CONTENT_LENGTH) {
+			parts
+				.headers
+				.insert(http::header::CONTENT_LENGTH, body.len().into());
+		}
+
+		let (response, body_tx) =
+			Self::channel(http::Response::from_parts(parts, Vec::new()).into());
+		if !body.is_empty() {
+			let _ = body_tx.send(BodyFrame::Data(body));
+		}
+		response
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StateDelta {
 	ActorState(Vec<u8>),
@@ -285,6 +396,12 @@ pub enum ActorEvent {
 		request: Request,
 		reply: Reply<Response>,
 	},
+	/// Request whose body the gateway streams, such as a gRPC call. Requests for framework routes
+	/// like actions and queues are buffered and arrive as `HttpRequest` instead.
+	HttpStream {
+		request: StreamingRequest,
+		reply: Reply<StreamingResponse>,
+	},
 	QueueSend {
 		name: String,
 		body: Vec<u8>,
@@ -355,6 +472,7 @@ impl ActorEvent {
 		match self {
 			Self::Action { .. } => "action",
 			Self::HttpRequest { .. } => "http_request",
+			Self::HttpStream { .. } => "http_stream",
 			Self::QueueSend { .. } => "queue_send",
 			Self::WebSocketOpen { .. } => "websocket_open",
 			Self::ConnectionPreflight { .. } => "connection_preflight",
@@ -38,7 +38,8 @@ pub use context::{ActorContext, ActorWorkRegion, KeepAwakeRegion, WebSocketCallb
 pub use factory::{ActorEntryFn, ActorFactory};
 pub use lifecycle_hooks::{ActorEvents, ActorStart, Reply};
 pub use messages::{
-	ActorEvent, QueueSendResult, QueueSendStatus, Request, Response, StateDelta, WorkflowKvWrite,
+	ActorEvent, GatewayAuth, QueueSendResult, QueueSendStatus, Request, Response, StateDelta,
+	WorkflowKvWrite,
 };
 pub use queue::{
 	CompletableQueueMessage, EnqueueAndWaitOpts, QueueMessage, QueueNextBatchOpts, QueueNextOpts,
@@ -28,4 +28,11 @@ impl SqliteTransport for EnvoySqliteTransport {
 	) -> Result<protocol::SqliteCommitResponse> {
 		self.handle.sqlite_commit(request).await
 	}
+
+	async fn get_row_change_log(
+		&self,
+		request: protocol::SqliteGetRowChangeLogRequest,
+	) -> Result<protocol::SqliteGetRowChangeLogResponse> {
+		self.handle.sqlite_get_row_change_log(request).await
+	}
 }
@@ -7,7 +7,11 @@ use std::sync::{
 
 use anyhow::{Context, Result};
 use depot_client_types::is_head_fence_mismatch;
-pub use depot_client_types::{BindParam, ColumnValue, ExecResult, ExecuteResult, QueryResult};
+pub use depot_client_types::{
+	BindParam, ColumnValue, ExecResult, ExecuteResult, QueryResult, RowChange, RowChangeOp,
+	RowChangeSeq,
+};
+use futures::stream::BoxStream;
 #[cfg(feature = "sqlite-local")]
 use parking_lot::Mutex;
 use rivet_envoy_client::protocol;
@@ -49,6 +53,7 @@ use crate::runtime::RuntimeSpawner;
 #[cfg(feature = "sqlite-local")]
 use depot_client::{
 	database::{NativeDatabaseHandle, open_database_from_transport},
+	row_changes::{RowChangesLaggedError, RowChangesTruncatedError},
 	vfs::{SqliteVfsMetrics, SqliteVfsMetricsSnapshot},
 	worker::{
 		SQLITE_WORKER_QUEUE_CAPACITY, SqliteWorkerCloseTimeoutError, SqliteWorkerClosingError,
@@ -327,6 +332,25 @@ impl SqliteDb {
 		Err(SqliteRuntimeError::Unavailable.build())
 	}
 
+	#[cfg(feature = "sqlite-local")]
+	async fn local_query_readonly(
+		&self,
+		sql: String,
+		params: Option<Vec<BindParam>>,
+	) -> Result<QueryResult> {
+		self.open().await?;
+		self.map_local_worker_result(self.native_db_handle()?.query_readonly(sql, params).await)
+	}
+
+	#[cfg(not(feature = "sqlite-local"))]
+	async fn local_query_readonly(
+		&self,
+		_sql: String,
+		_params: Option<Vec<BindParam>>,
+	) -> Result<QueryResult> {
+		Err(SqliteRuntimeError::Unavailable.build())
+	}
+
 	#[cfg(feature = "sqlite-local")]
 	async fn local_run(&self, sql: String, params: Option<Vec<BindParam>>) -> Result<ExecResult> {
 		self.open().await?;
@@ -396,6 +420,32 @@ impl SqliteDb {
 		}
 	}
 
+	/// Runs a read-only statement against a snapshot of the latest commit.
+	///
+	/// Locally the statement runs on a read connection, so it neither waits for nor sees open
+	/// transactions. Remote databases run it as a regular query.
+	pub async fn query_readonly(
+		&self,
+		sql: impl Into<String>,
+		params: Option<Vec<BindParam>>,
+	) -> Result<QueryResult> {
+		if self.backend == SqliteBackend::RemoteEnvoy {
+			return self.query(sql, params).await;
+		}
+
+		let sql = sql.into();
+		let sql_for_log = sql.clone();
+		let binding_count = bind_param_count(&params);
+		match self.local_query_readonly(sql, params).await {
+			Ok(result) => Ok(result),
+			Err(error) => {
+				let error = self.attach_actor(error);
+				self.log_operation_error("query_readonly", &sql_for_log, binding_count, &error);
+				Err(error)
+			}
+		}
+	}
+
 	pub async fn run(
 		&self,
 		sql: impl Into<String>,
@@ -498,6 +548,46 @@ impl SqliteDb {
 		}
 	}
 
+	/// Streams net row changes of transactions committed after `after`, or after the call when
+	/// `after` is `None`. Only the local backend captures row changes.
+	pub async fn row_changes(
+		&self,
+		after: Option<RowChangeSeq>,
+	) -> Result<BoxStream<'static, Result<RowChange>>> {
+		match self.backend {
+			SqliteBackend::LocalNative => self.local_row_changes(after).await,
+			SqliteBackend::RemoteEnvoy => Err(SqliteRuntimeError::RowChangesUnsupported {
+				reason: "remote SQLite does not capture row changes".to_string(),
+			}
+			.build()),
+		}
+	}
+
+	#[cfg(feature = "sqlite-local")]
+	async fn local_row_changes(
+		&self,
+		after: Option<RowChangeSeq>,
+	) -> Result<BoxStream<'static, Result<RowChange>>> {
+		use futures::StreamExt;
+
+		self.open().await?;
+		let stream =
+			self.map_local_worker_result(self.native_db_handle()?.row_changes(after).await)?;
+		Ok(futures::stream::unfold(stream, |mut stream| async move {
+			let change = stream.recv().await?;
+			Some((change.map_err(map_local_worker_error), stream))
+		})
+		.boxed())
+	}
+
+	#[cfg(not(feature = "sqlite-local"))]
+	async fn local_row_changes(
+		&self,
+		_after: Option<RowChangeSeq>,
+	) -> Result<BoxStream<'static, Result<RowChange>>> {
+		Err(SqliteRuntimeError::Unavailable.build())
+	}
+
 	pub async fn close(&self) -> Result<()> {
 		let db = self.clone();
 		run_detached_transaction_task(
@@ -1009,6 +1099,21 @@ fn map_local_worker_error(error: anyhow::Error) -> anyhow::Error {
 		return SqliteRuntimeError::Closed.build();
 	}
 
+	if let Some(truncated) = error.downcast_ref::<RowChangesTruncatedError>() {
+		return SqliteRuntimeError::RowChangesTruncated {
+			txid: truncated.floor.txid,
+			index: truncated.floor.index,
+		}
+		.build();
+	}
+
+	if let Some(lagged) = error.downcast_ref::<RowChangesLaggedError>() {
+		return SqliteRuntimeError::RowChangesLagged {
+			skipped: lagged.skipped,
+		}
+		.build();
+	}
+
 	error
 }
 
@@ -1267,6 +1372,54 @@ fn get_named_sqlite_binding<'a>(
 	None
 }
 
+/// Event broadcast to subscribed connections for each committed row change when
+/// `broadcast_row_changes` is enabled.
+pub const ROW_CHANGE_EVENT_NAME: &str = "sqlite.rowChange";
+
+/// Tables owned by the actor runtime rather than user code.
+const INTERNAL_TABLE_PREFIX: &str = "_rivet_";
+
+pub(crate) fn is_internal_table(table: &str) -> bool {
+	table.starts_with(INTERNAL_TABLE_PREFIX)
+}
+
+/// Encodes a row change as the CBOR argument list of a `sqlite.rowChange` event.
+pub(crate) fn row_change_event_args(change: &RowChange) -> Result<Vec<u8>> {
+	let row_object = |values: &[ColumnValue]| {
+		let mut row = JsonMap::new();
+		for (column, value) in change.columns.iter().zip(values) {
+			row.insert(column.clone(), column_value_to_json(value));
+		}
+		JsonValue::Object(row)
+	};
+
+	let event = serde_json::json!({
+		"seq": {
+			"txid": change.seq.txid,
+			"index": change.seq.index,
+		},
+		"table": change.table,
+		"op": change.op.as_str(),
+		"rowid": change.rowid,
+		"primaryKey": change
+			.primary_key
+			.iter()
+			.map(column_value_to_json)
+			.collect::<Vec<_>>(),
+		"old": if change.op == RowChangeOp::Insert {
+			JsonValue::Null
+		} else {
+			row_object(&change.old_values)
+		},
+		"row": if change.op == RowChangeOp::Delete {
+			JsonValue::Null
+		} else {
+			row_object(&change.values)
+		},
+	});
+	encode_json_as_cbor(&[event])
+}
+
 fn query_result_to_json_rows(result: &QueryResult) -> JsonValue {
 	JsonValue::Array(
 		result
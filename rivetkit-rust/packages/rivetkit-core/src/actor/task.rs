@@ -35,7 +35,7 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
 
 use anyhow::{Context, Result, anyhow};
-use futures::FutureExt;
+use futures::{FutureExt, StreamExt};
 #[cfg(test)]
 use parking_lot::Mutex;
 use tokio::sync::{broadcast, mpsc, oneshot};
@@ -49,9 +49,10 @@ use crate::actor::factory::ActorFactory;
 use crate::actor::lifecycle_hooks::{ActorEvents, ActorStart, Reply};
 use crate::actor::messages::{
 	ActorEvent, QueueSendResult, Request, Response, SerializeStateReason, StateDelta,
-	WorkflowKvWrite,
+	StreamingRequest, StreamingResponse, WorkflowKvWrite,
 };
 use crate::actor::metrics::startup_phase::StartupPhase;
+use crate::actor::sqlite::{ROW_CHANGE_EVENT_NAME, is_internal_table, row_change_event_args};
 use crate::actor::state::PersistedActor;
 use crate::actor::task_types::ShutdownKind;
 use crate::actor::work_registry::ActorWorkKind;
@@ -217,6 +218,10 @@ pub enum DispatchCommand {
 		request: Request,
 		reply: oneshot::Sender<HttpDispatchResult>,
 	},
+	HttpStream {
+		request: StreamingRequest,
+		reply: oneshot::Sender<Result<StreamingResponse>>,
+	},
 	OpenWebSocket {
 		conn: ConnHandle,
 		ws: WebSocket,
@@ -238,6 +243,7 @@ impl DispatchCommand {
 			Self::Action { .. } => "action",
 			Self::QueueSend { .. } => "queue_send",
 			Self::Http { .. } => "http",
+			Self::HttpStream { .. } => "http_stream",
 			Self::OpenWebSocket { .. } => "open_websocket",
 			Self::WorkflowHistory { .. } => "workflow_history",
 			Self::WorkflowReplay { .. } => "workflow_replay",
@@ -336,7 +342,7 @@ pub struct ActorTask {
 	pub lifecycle_inbox: mpsc::UnboundedReceiver<LifecycleCommand>,
 	/// Client-originated work sent by `RegistryDispatcher` in
 	/// `registry/dispatch.rs` (Action, OpenWebSocket, Workflow*) and
-	/// `registry/http.rs` (Http, QueueSend).
+	/// `registry/http.rs` (Http, HttpStream, QueueSend).
 	pub dispatch_inbox: mpsc::UnboundedReceiver<DispatchCommand>,
 	/// Internal self-events the actor enqueues onto itself via `ActorContext`
 	/// hooks (save/inspector/activity notifications from
@@ -1002,6 +1008,22 @@ impl ActorTask {
 					}
 				}
 			}
+			DispatchCommand::HttpStream { request, reply } => {
+				match self.send_actor_event(
+					"dispatch_http_stream",
+					ActorEvent::HttpStream {
+						request,
+						reply: Reply::from(reply),
+					},
+				) {
+					Ok(()) => {
+						self.log_dispatch_command_handled(command_kind, "enqueued");
+					}
+					Err(_error) => {
+						self.log_dispatch_command_handled(command_kind, "enqueue_failed");
+					}
+				}
+			}
 			DispatchCommand::OpenWebSocket {
 				conn,
 				ws,
@@ -1096,6 +1118,9 @@ impl ActorTask {
 			DispatchCommand::Http { reply, .. } => {
 				let _ = reply.send(Err(error));
 			}
+			DispatchCommand::HttpStream { reply, .. } => {
+				let _ = reply.send(Err(error));
+			}
 			DispatchCommand::OpenWebSocket { reply, .. } => {
 				let _ = reply.send(Err(error));
 			}
@@ -1136,6 +1161,43 @@ impl ActorTask {
 		}
 	}
 
+	/// Forwards committed row changes of user tables to subscribed connections. The forwarder
+	/// ends when the database closes, so each wake subscribes again.
+	async fn start_row_change_broadcast(&self) -> Result<()> {
+		if !self.factory.config().broadcast_row_changes || !self.ctx.sql().is_enabled() {
+			return Ok(());
+		}
+
+		let mut changes = self.ctx.sql().row_changes(None).await?;
+		let ctx = self.ctx.downgrade();
+		let task = async move {
+			while let Some(change) = changes.next().await {
+				let Some(ctx) = ActorContext::from_weak(&ctx) else {
+					return;
+				};
+				let change = match change {
+					Ok(change) => change,
+					Err(error) => {
+						tracing::warn!(?error, "sqlite row change broadcast missed changes");
+						continue;
+					}
+				};
+				if is_internal_table(&change.table) {
+					continue;
+				}
+				match row_change_event_args(&change) {
+					Ok(args) => ctx.broadcast(ROW_CHANGE_EVENT_NAME, &args),
+					Err(error) => {
+						tracing::warn!(?error, "failed to encode sqlite row change event");
+					}
+				}
+			}
+		}
+		.in_current_span();
+		RuntimeSpawner::spawn(task);
+		Ok(())
+	}
+
 	async fn start_actor(&mut self) -> Result<()> {
 		let mut startup_timer = self.ctx.metrics().begin_startup_timer();
 		let actor_id = self.ctx.actor_id().to_owned();
@@ -1215,6 +1277,10 @@ impl ActorTask {
 			core_init_result,
 		)?;
 
+		self.start_row_change_broadcast()
+			.await
+			.context("start sqlite row change broadcast")?;
+
 		self.transition_to(LifecycleState::Started);
 		self.ctx
 			.metrics()
@@ -47,6 +47,7 @@ pub fn public_error_status_code(group: &str, code: &str) -> Option<u16> {
 			| "timed_out",
 		) => Some(400),
 		("kv", _) => Some(400),
+		("depot", "quota_exceeded" | "namespace_quota_exceeded") => Some(400),
 		("user", _) => Some(400),
 		_ => None,
 	}
@@ -336,4 +337,25 @@ pub(crate) enum SqliteRuntimeError {
 		"Remote SQLite generation is stale: {reason}"
 	)]
 	RemoteFenceMismatch { reason: String },
+
+	#[error(
+		"row_changes_unsupported",
+		"SQLite row changes are unsupported.",
+		"SQLite row changes are unsupported: {reason}"
+	)]
+	RowChangesUnsupported { reason: String },
+
+	#[error(
+		"row_changes_truncated",
+		"SQLite row changes are no longer retained.",
+		"SQLite row changes before txid {txid} index {index} are no longer retained. Resubscribe without a resume sequence."
+	)]
+	RowChangesTruncated { txid: u64, index: u32 },
+
+	#[error(
+		"row_changes_lagged",
+		"SQLite row change subscriber fell behind.",
+		"SQLite row change subscriber fell behind and missed {skipped} changes. Resubscribe from the last received sequence."
+	)]
+	RowChangesLagged { skipped: u64 },
 }
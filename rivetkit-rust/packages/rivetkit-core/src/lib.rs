@@ -128,28 +128,28 @@ pub use actor::context::{
 pub use actor::factory::{ActorEntryFn, ActorFactory};
 pub use actor::lifecycle_hooks::{ActorEvents, ActorStart, Reply};
 pub use actor::messages::{
-	ActorEvent, QueueSendResult, QueueSendStatus, Request, Response, SerializeStateReason,
-	StateDelta, WorkflowKvWrite,
+	ActorEvent, GatewayAuth, QueueSendResult, QueueSendStatus, Request, Response,
+	SerializeStateReason, StateDelta, StreamingRequest, StreamingResponse, WorkflowKvWrite,
 };
 pub use actor::queue::{
 	CompletableQueueMessage, EnqueueAndWaitOpts, QueueMessage, QueueNextBatchOpts, QueueNextOpts,
 	QueueTryNextBatchOpts, QueueTryNextOpts, QueueWaitOpts,
 };
 pub use actor::sqlite::{
-	BindParam, ColumnValue, ExecResult, ExecuteResult, QueryResult, SqliteBackend,
-	SqliteBatchStatement, SqliteDb, SqliteTransaction,
+	BindParam, ColumnValue, ExecResult, ExecuteResult, QueryResult, RowChange, RowChangeOp,
+	RowChangeSeq, SqliteBackend, SqliteBatchStatement, SqliteDb, SqliteTransaction,
 };
 pub use actor::state::RequestSaveOpts;
 pub use actor::task::{
 	ActionDispatchResult, ActorTask, DispatchCommand, HttpDispatchResult, LifecycleCommand,
 	LifecycleEvent, LifecycleState,
 };
-pub use rivet_envoy_client::config::ResponseChunk;
 pub use actor::task_types::ShutdownKind;
 pub use actor::work_registry::{ActorWorkKind, ActorWorkPolicy};
 pub use error::ActorLifecycle;
 pub use inspector::{Inspector, InspectorSnapshot};
 pub use registry::{CoreRegistry, EngineSpawnMode, ServeConfig};
+pub use rivet_envoy_client::config::{BodyFrame, ResponseChunk};
 pub use runtime::{RuntimeBoxFuture, RuntimeSpawner, boxed_runtime_future};
 pub use serverless::{CoreServerlessRuntime, ServerlessRequest, ServerlessResponse};
 pub use types::{
@@ -94,6 +94,23 @@ impl EnvoyCallbacks for RegistryCallbacks {
 		Box::pin(async move { dispatcher.handle_fetch(&actor_id, request).await })
 	}
 
+	fn fetch_stream(
+		&self,
+		_handle: EnvoyHandle,
+		actor_id: String,
+		_gateway_id: protocol::GatewayId,
+		_request_id: protocol::RequestId,
+		request: StreamingHttpRequest,
+	) -> EnvoyBoxFuture<anyhow::Result<StreamingHttpResponse>> {
+		tracing::info!(
+			method = %request.method,
+			path = %request.path,
+			"envoy callback: streaming fetch request"
+		);
+		let dispatcher = self.dispatcher.clone();
+		Box::pin(async move { dispatcher.handle_fetch_stream(&actor_id, request).await })
+	}
+
 	fn websocket(
 		&self,
 		_handle: EnvoyHandle,
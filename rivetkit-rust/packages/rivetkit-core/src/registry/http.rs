@@ -43,7 +43,7 @@ impl RegistryDispatcher {
 		};
 		let actor = actor_specifier_for_instance(&instance);
 		if let Some(mut response) = self.handle_inspector_fetch(&instance, &request).await? {
-			attach_actor_response_headers(&mut response, &actor);
+			attach_actor_response_headers(&mut response.headers, &actor);
 			return Ok(response);
 		}
 
@@ -60,11 +60,105 @@ impl RegistryDispatcher {
 			}
 		};
 		response.map(|mut response| {
-			attach_actor_response_headers(&mut response, &actor);
+			attach_actor_response_headers(&mut response.headers, &actor);
 			response
 		})
 	}
 
+	/// Streams requests for the raw request route, and gRPC method paths that match no framework
+	/// route, to the actor. Every other route is buffered and served by `handle_fetch`.
+	pub(super) async fn handle_fetch_stream(
+		&self,
+		actor_id: &str,
+		request: StreamingHttpRequest,
+	) -> Result<StreamingHttpResponse> {
+		let StreamingHttpRequest {
+			method,
+			path,
+			headers,
+			body,
+		} = request;
+		let request_path = normalize_actor_request_path(&path);
+		let head = Request::from_parts(&method, &request_path, headers.clone(), Vec::new())
+			.with_context(|| format!("build actor request for `{path}`"))?;
+		let route = RegistryHttpRoute::from_paths(
+			&path,
+			head.uri().path(),
+			self.handle_inspector_http_in_runtime,
+		)?;
+		let streamed = matches!(
+			route,
+			RegistryHttpRoute::UserRawRequest
+				| RegistryHttpRoute::Framework(FrameworkHttpRoute::NotFound)
+		) && !head.uri().path().starts_with("/inspector/");
+
+		let request = StreamingRequest { head, body };
+		if !streamed {
+			let body = request.into_buffered().await?.into_body();
+			let response = self
+				.handle_fetch(
+					actor_id,
+					HttpRequest {
+						method,
+						path,
+						headers,
+						body: Some(body),
+						body_stream: None,
+					},
+				)
+				.await?;
+			return Ok(response.into_streaming());
+		}
+
+		let instance = match self.active_actor(actor_id).await {
+			Ok(instance) => instance,
+			Err(error) => return Ok(inspector_anyhow_response(error).into_streaming()),
+		};
+		let actor = actor_specifier_for_instance(&instance);
+		let encoding = request_encoding(request.head.headers());
+
+		instance.ctx.cancel_sleep_timer();
+
+		let (reply_tx, reply_rx) = oneshot::channel();
+		try_send_dispatch_command(
+			&instance.dispatch,
+			DispatchCommand::HttpStream {
+				request,
+				reply: reply_tx,
+			},
+		)
+		.context("send actor task HTTP stream dispatch command")?;
+		let result = reply_rx
+			.await
+			.context("receive actor task HTTP stream dispatch reply")?;
+
+		// The envoy counts the request as active until the response body finishes, so the sleep
+		// timer stays off while the stream is open
+		rearm_sleep_after_request(instance.ctx.clone());
+
+		let mut response = match result {
+			Ok(response) => {
+				let (status, headers, _) = response.head.to_parts();
+				StreamingHttpResponse {
+					status,
+					headers,
+					body: response.body,
+				}
+			}
+			Err(error) => {
+				tracing::error!(
+					actor_id = instance.actor_id,
+					?error,
+					"actor streaming request callback failed"
+				);
+				framework_anyhow_error_response_with_actor(encoding, error, Some(&actor))?
+					.into_streaming()
+			}
+		};
+		attach_actor_response_headers(&mut response.headers, &actor);
+		Ok(response)
+	}
+
 	async fn handle_user_request_fetch(
 		&self,
 		instance: &ActorTaskHandle,
@@ -680,19 +774,15 @@ fn actor_specifier_for_instance(instance: &ActorTaskHandle) -> ActorSpecifier {
 		.with_key(format_actor_key(instance.ctx.key()))
 }
 
-fn attach_actor_response_headers(response: &mut HttpResponse, actor: &ActorSpecifier) {
-	response
-		.headers
-		.insert(HEADER_RIVET_ACTOR.to_owned(), actor.actor_id.clone());
-	response.headers.insert(
+fn attach_actor_response_headers(headers: &mut HashMap<String, String>, actor: &ActorSpecifier) {
+	headers.insert(HEADER_RIVET_ACTOR.to_owned(), actor.actor_id.clone());
+	headers.insert(
 		HEADER_RIVET_ACTOR_GENERATION.to_owned(),
 		actor.generation.to_string(),
 	);
 	if let Some(key) = actor.key.as_ref() {
 		if !key.contains('\r') && !key.contains('\n') {
-			response
-				.headers
-				.insert(HEADER_RIVET_ACTOR_KEY.to_owned(), key.clone());
+			headers.insert(HEADER_RIVET_ACTOR_KEY.to_owned(), key.clone());
 		}
 	}
 }
@@ -762,7 +852,7 @@ pub(super) fn message_boundary_error_response_with_actor(
 		body_stream: None,
 	};
 	if let Some(actor) = actor {
-		attach_actor_response_headers(&mut response, actor);
+		attach_actor_response_headers(&mut response.headers, actor);
 	}
 	Ok(response)
 }
@@ -1032,7 +1122,7 @@ pub(super) fn framework_action_error_response(
 		body_stream: None,
 	};
 	if let Some(actor) = actor {
-		attach_actor_response_headers(&mut response, actor);
+		attach_actor_response_headers(&mut response.headers, actor);
 	}
 	Ok(response)
 }
@@ -13,7 +13,8 @@ use anyhow::{Context, Result};
 use parking_lot::Mutex;
 use rivet_envoy_client::config::{
 	ActorStopHandle, BoxFuture as EnvoyBoxFuture, EnvoyCallbacks, HttpRequest, HttpResponse,
-	WebSocketHandler, WebSocketMessage, WebSocketSender,
+	StreamingHttpRequest, StreamingHttpResponse, WebSocketHandler, WebSocketMessage,
+	WebSocketSender,
 };
 use rivet_envoy_client::envoy::start_envoy;
 use rivet_envoy_client::handle::EnvoyHandle;
@@ -40,7 +41,9 @@ use crate::actor::context::{ActorContext, InspectorAttachGuard};
 use crate::actor::factory::ActorFactory;
 use crate::actor::kv::LegacyActorKv;
 use crate::actor::lifecycle_hooks::Reply;
-use crate::actor::messages::{ActorEvent, QueueSendResult, Request, Response, StateDelta};
+use crate::actor::messages::{
+	ActorEvent, QueueSendResult, Request, Response, StateDelta, StreamingRequest,
+};
 use crate::actor::task::{
 	ActorTask, DispatchCommand, LifecycleCommand, try_send_dispatch_command,
 	try_send_lifecycle_command,
@@ -4,6 +4,9 @@ mod common;
 #[path = "integration/counter.rs"]
 mod counter;
 
+#[path = "integration/grpc.rs"]
+mod grpc;
+
 #[path = "integration/metrics_endpoint.rs"]
 mod metrics_endpoint;
 
@@ -89,6 +89,9 @@ fn counter_factory() -> ActorFactory {
 					ActorEvent::HttpRequest { request: _, reply } => {
 						reply.send(Err(anyhow::anyhow!("http requests are not handled")));
 					}
+					ActorEvent::HttpStream { request: _, reply } => {
+						reply.send(Err(anyhow::anyhow!("http requests are not handled")));
+					}
 					ActorEvent::QueueSend {
 						name: _,
 						body: _,
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+
+use anyhow::{Context, Result, bail};
+use bytes::Bytes;
+use futures::channel::mpsc;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use rivetkit_core::{
+	ActorConfig, ActorEvent, ActorFactory, BodyFrame, CoreRegistry, Response, StreamingRequest,
+	StreamingResponse,
+};
+
+use crate::common::ctx::IntegrationCtx;
+
+const ACTOR_NAME: &str = "grpc-echo";
+
+#[tokio::test(flavor = "multi_thread")]
+async fn grpc_call_streams_through_gateway_with_trailers() -> Result<()> {
+	let ctx = IntegrationCtx::builder().start().await?;
+	ctx.create_default_namespace().await?;
+	let mut registry = CoreRegistry::new();
+	registry.register(ACTOR_NAME, grpc_echo_factory());
+	let registry_task = ctx.serve_registry(registry);
+
+	ctx.wait_for_envoy_ready().await?;
+	let actor = ctx.create_actor(ACTOR_NAME).await?;
+	ctx.wait_for_json_action(&actor.actor_id, "ping")
+		.await
+		.context("wait for actor")?;
+
+	// gRPC needs HTTP/2, which guard accepts in plaintext
+	let authority = ctx
+		.endpoint()
+		.strip_prefix("http://")
+		.context("endpoint should be plaintext http")?
+		.to_owned();
+	let stream = tokio::net::TcpStream::connect(&authority)
+		.await
+		.context("connect to guard")?;
+	let (mut sender, conn) =
+		hyper::client::conn::http2::handshake(TokioExecutor::new(), TokioIo::new(stream))
+			.await
+			.context("http2 handshake")?;
+	tokio::spawn(conn);
+
+	let (body_tx, body_rx) = mpsc::unbounded::<Result<Frame<Bytes>, Infallible>>();
+	let request = hyper::Request::builder()
+		.method("POST")
+		.uri(format!(
+			"http://{authority}/gateway/{}/echo.Echo/Stream",
+			actor.actor_id
+		))
+		.header("content-type", "application/grpc")
+		.header("te", "trailers")
+		.body(StreamBody::new(body_rx))?;
+
+	body_tx.unbounded_send(Ok(Frame::data(Bytes::from_static(b"first"))))?;
+	let response = sender
+		.send_request(request)
+		.await
+		.context("send grpc call")?;
+	assert_eq!(response.status(), 200);
+	let mut body = response.into_body();
+
+	// The first message is echoed while the request body is still open
+	assert_eq!(next_data(&mut body).await?, b"first");
+	body_tx.unbounded_send(Ok(Frame::data(Bytes::from_static(b"second"))))?;
+	assert_eq!(next_data(&mut body).await?, b"second");
+	drop(body_tx);
+
+	let trailers = loop {
+		let frame = body
+			.frame()
+			.await
+			.context("response ended without trailers")?
+			.context("read response frame")?;
+		if let Ok(trailers) = frame.into_trailers() {
+			break trailers;
+		}
+	};
+	assert_eq!(
+		trailers.get("grpc-status").and_then(|x| x.to_str().ok()),
+		Some("0")
+	);
+
+	registry_task.shutdown().await?;
+	ctx.shutdown().await?;
+
+	Ok(())
+}
+
+async fn next_data(body: &mut hyper::body::Incoming) -> Result<Vec<u8>> {
+	loop {
+		let Some(frame) = body.frame().await else {
+			bail!("response ended before the next message");
+		};
+		match frame.context("read response frame")?.into_data() {
+			Ok(data) if data.is_empty() => {}
+			Ok(data) => return Ok(data.to_vec()),
+			Err(_) => bail!("expected a data frame"),
+		}
+	}
+}
+
+/// Echoes every request body frame, then finishes with an OK gRPC status once the request ends.
+fn grpc_echo_factory() -> ActorFactory {
+	ActorFactory::new(ActorConfig::default(), |start| {
+		Box::pin(async move {
+			let mut events = start.events;
+			while let Some(event) = events.recv().await {
+				match event {
+					ActorEvent::Action { reply, .. } => {
+						// CBOR null
+						reply.send(Ok(vec![0xf6]));
+					}
+					ActorEvent::HttpStream { request, reply } => {
+						reply.send(echo(request));
+					}
+					ActorEvent::SerializeState { reply, .. } => {
+						reply.send(Ok(Vec::new()));
+					}
+					ActorEvent::RunGracefulCleanup { reply, .. } => {
+						reply.send(Ok(()));
+					}
+					ActorEvent::ConnectionPreflight { reply, .. }
+					| ActorEvent::ConnectionOpen { reply, .. }
+					| ActorEvent::DisconnectConn { reply, .. } => {
+						reply.send(Ok(()));
+					}
+					_ => {}
+				}
+			}
+
+			Ok(())
+		})
+	})
+}
+
+fn echo(request: StreamingRequest) -> Result<StreamingResponse> {
+	let mut request_body = request.body;
+	let (response, body_tx) = StreamingResponse::channel(Response::from_parts(
+		200,
+		HashMap::from([("content-type".to_owned(), "application/grpc".to_owned())]),
+		Vec::new(),
+	)?);
+
+	tokio::spawn(async move {
+		while let Some(BodyFrame::Data(data)) = request_body.recv().await {
+			if body_tx.send(BodyFrame::Data(data)).is_err() {
+				return;
+			}
+		}
+		let _ = body_tx.send(BodyFrame::Trailers(HashMap::from([(
+			"grpc-status".to_owned(),
+			"0".to_owned(),
+		)])));
+	});
+
+	Ok(response)
+}
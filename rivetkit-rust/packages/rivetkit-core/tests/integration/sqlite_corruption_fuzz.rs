@@ -582,6 +582,9 @@ fn sqlite_fuzz_factory() -> ActorFactory {
 					ActorEvent::HttpRequest { request: _, reply } => {
 						reply.send(Err(anyhow::anyhow!("http requests are not handled")));
 					}
+					ActorEvent::HttpStream { request: _, reply } => {
+						reply.send(Err(anyhow::anyhow!("http requests are not handled")));
+					}
 					ActorEvent::QueueSend {
 						name: _,
 						body: _,
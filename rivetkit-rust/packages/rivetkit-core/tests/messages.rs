@@ -5,7 +5,7 @@ mod moved_tests {
 
 	use http::StatusCode;
 
-	use super::{Request, Response};
+	use super::{GatewayAuth, Request, Response};
 
 	#[test]
 	fn request_from_parts_round_trips() {
@@ -48,4 +48,39 @@ mod moved_tests {
 		assert_eq!(headers.get("x-test"), Some(&"ok".to_owned()));
 		assert_eq!(body, b"done");
 	}
+
+	#[test]
+	fn request_gateway_auth_reads_trusted_headers() {
+		let request = Request::from_parts(
+			"GET",
+			"/",
+			HashMap::from([
+				("x-rivet-auth-rule".to_owned(), "users".to_owned()),
+				("x-rivet-auth-method".to_owned(), "jwt".to_owned()),
+				("x-rivet-auth-subject".to_owned(), "user-1".to_owned()),
+				// {"sub":"user-1","role":"admin"}
+				(
+					"x-rivet-auth-claims".to_owned(),
+					"eyJzdWIiOiJ1c2VyLTEiLCJyb2xlIjoiYWRtaW4ifQ".to_owned(),
+				),
+			]),
+			Vec::new(),
+		)
+		.expect("request should build");
+
+		let auth = request.gateway_auth().expect("request should have auth");
+		assert_eq!(
+			auth,
+			GatewayAuth {
+				rule: "users".to_owned(),
+				method: "jwt".to_owned(),
+				subject: Some("user-1".to_owned()),
+				claims: serde_json::json!({ "sub": "user-1", "role": "admin" })
+					.as_object()
+					.cloned(),
+			}
+		);
+
+		assert_eq!(Request::default().gateway_auth(), None);
+	}
 }
@@ -1559,3 +1559,51 @@ fn remote_head_fence_mismatch_stops_actor_once() {
 	});
 	assert!(envoy_rx.try_recv().is_err());
 }
+
+#[tokio::test]
+async fn remote_backend_rejects_row_change_subscriptions() {
+	let (handle, _envoy_rx) = test_envoy_handle();
+	let db = SqliteDb::new_with_remote_sqlite(handle, "actor-a", None, Some(1), true, true)
+		.expect("test remote sqlite should be configured");
+
+	let error = match db.row_changes(None).await {
+		Ok(_) => panic!("remote sqlite should not capture row changes"),
+		Err(error) => error,
+	};
+	let structured = rivet_error::RivetError::extract(&error);
+	assert_eq!(structured.group(), "sqlite");
+	assert_eq!(structured.code(), "row_changes_unsupported");
+}
+
+#[test]
+fn row_change_events_encode_seq_and_row_object() {
+	let change = RowChange {
+		seq: RowChangeSeq { txid: 9, index: 2 },
+		table: "items".to_string(),
+		op: RowChangeOp::Update,
+		rowid: Some(4),
+		columns: vec!["id".to_string(), "label".to_string()],
+		primary_key: vec![ColumnValue::Integer(4)],
+		old_values: vec![ColumnValue::Integer(4), ColumnValue::Null],
+		values: vec![ColumnValue::Integer(4), ColumnValue::Text("a".to_string())],
+	};
+
+	let args = row_change_event_args(&change).expect("row change should encode");
+	let decoded = ciborium::from_reader::<JsonValue, _>(Cursor::new(args))
+		.expect("row change event should decode");
+	assert_eq!(
+		decoded,
+		serde_json::json!([{
+			"seq": { "txid": 9, "index": 2 },
+			"table": "items",
+			"op": "update",
+			"rowid": 4,
+			"primaryKey": [4],
+			"old": { "id": 4, "label": null },
+			"row": { "id": 4, "label": "a" },
+		}])
+	);
+
+	assert!(is_internal_table("_rivet_actor_state"));
+	assert!(!is_internal_table("items"));
+}
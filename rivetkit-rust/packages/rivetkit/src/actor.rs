@@ -3,7 +3,7 @@ use std::sync::Arc;
 use anyhow::{Result, bail};
 use async_trait::async_trait;
 use rivetkit_core::error::ActorRuntime;
-use rivetkit_core::{Request, Response, WebSocket};
+use rivetkit_core::{Request, Response, StreamingRequest, StreamingResponse, WebSocket};
 use serde::{Serialize, de::DeserializeOwned};
 
 use crate::action::ActionSet;
@@ -95,6 +95,19 @@ pub trait Actor: Send + Sync + Sized + 'static {
 		Response::from_parts(404, Default::default(), Vec::new())
 	}
 
+	/// Handles requests whose body is streamed, such as gRPC calls. End the response with a
+	/// `BodyFrame::Trailers` frame to send trailers like `grpc-status`.
+	///
+	/// Defaults to buffering the request into `on_fetch`.
+	async fn on_fetch_stream(
+		self: Arc<Self>,
+		ctx: Ctx<Self>,
+		req: StreamingRequest,
+	) -> Result<StreamingResponse> {
+		let req = req.into_buffered().await?;
+		Ok(self.on_fetch(ctx, req).await?.into())
+	}
+
 	async fn on_websocket(
 		self: Arc<Self>,
 		_ctx: Ctx<Self>,
@@ -19,8 +19,8 @@ use rivetkit_core::actor::schedule::{
 };
 use rivetkit_core::actor::state::OnStateChangeGuard;
 use rivetkit_core::{
-	ActorContext, ActorKey, ActorKv, ConnHandle, ConnId, KeepAwakeRegion, RequestSaveOpts,
-	SqliteDb, StateDelta, actor::connection::ConnHandles, error::ActorRuntime,
+	ActorContext, ActorKey, ActorKv, ConnHandle, ConnId, GatewayAuth, KeepAwakeRegion,
+	RequestSaveOpts, SqliteDb, StateDelta, actor::connection::ConnHandles, error::ActorRuntime,
 };
 use serde::{Serialize, de::DeserializeOwned};
 use tokio_util::sync::CancellationToken;
@@ -658,6 +658,11 @@ impl<A: Actor> ConnCtx<A> {
 		decode_cbor(&self.inner.params(), "connection params")
 	}
 
+	/// Identity verified by the gateway auth policy when the connection was opened.
+	pub fn gateway_auth(&self) -> Option<GatewayAuth> {
+		self.inner.gateway_auth()
+	}
+
 	pub fn state(&self) -> Result<A::ConnState> {
 		decode_cbor(&self.inner.state(), "connection state")
 	}
@@ -7,7 +7,7 @@ use rivetkit_core::actor::schedule::ScheduledFireInfo;
 use rivetkit_core::error::ActorRuntime;
 use rivetkit_core::{
 	ActorEvent, QueueSendResult, QueueSendStatus, Reply, Request, Response, SerializeStateReason,
-	StateDelta, WebSocket,
+	StateDelta, StreamingRequest, StreamingResponse, WebSocket,
 };
 use serde::{
 	Serialize,
@@ -77,6 +77,7 @@ impl_event_set!(
 pub enum RuntimeEvent<A: Actor> {
 	Action(ActionCall<A>),
 	Http(HttpCall),
+	HttpStream(HttpStreamCall),
 	QueueSend(QueueSend<A>),
 	WebSocketOpen(WsOpen<A>),
 	ConnOpen(ConnOpen<A>),
@@ -107,6 +108,10 @@ impl<A: Actor> RuntimeEvent<A> {
 				request: Some(request),
 				reply: Some(reply),
 			}),
+			ActorEvent::HttpStream { request, reply } => Self::HttpStream(HttpStreamCall {
+				request: Some(request),
+				reply: Some(reply),
+			}),
 			ActorEvent::QueueSend {
 				name,
 				body,
@@ -1060,6 +1065,90 @@ impl HttpCall {
 	}
 }
 
+#[derive(Debug)]
+#[must_use = "reply to the streaming HTTP call or dropping it sends actor/dropped_reply"]
+pub struct HttpStreamCall {
+	pub(crate) request: Option<StreamingRequest>,
+	pub(crate) reply: Option<Reply<StreamingResponse>>,
+}
+
+impl Drop for HttpStreamCall {
+	fn drop(&mut self) {
+		if self.reply.is_some() {
+			let identifying = self
+				.request
+				.as_ref()
+				.map(|request| request.head.uri().to_string())
+				.unwrap_or_else(|| "<moved-request>".into());
+			warn_dropped_event("HttpStream", identifying);
+		}
+	}
+}
+
+/// Reply handle of a streaming HTTP call whose request was moved out with `into_request`.
+#[derive(Debug)]
+#[must_use = "reply to the deferred streaming HTTP call or dropping it sends actor/dropped_reply"]
+pub struct HttpStreamReply {
+	reply: Option<Reply<StreamingResponse>>,
+}
+
+impl Drop for HttpStreamReply {
+	fn drop(&mut self) {
+		if self.reply.is_some() {
+			warn_dropped_event("HttpStream", "<deferred>");
+		}
+	}
+}
+
+impl HttpStreamReply {
+	pub fn reply(mut self, response: StreamingResponse) {
+		if let Some(reply) = self.reply.take() {
+			reply.send(Ok(response));
+		}
+	}
+
+	pub fn reply_err(mut self, err: anyhow::Error) {
+		if let Some(reply) = self.reply.take() {
+			reply.send(Err(err));
+		}
+	}
+}
+
+impl HttpStreamCall {
+	pub fn request(&self) -> Option<&StreamingRequest> {
+		self.request.as_ref()
+	}
+
+	/// Moves the request out so its body can be read while the response is prepared.
+	pub fn into_request(mut self) -> AnyhowResult<(StreamingRequest, HttpStreamReply)> {
+		let request = self.request.take().ok_or_else(|| {
+			ActorRuntime::InvalidOperation {
+				operation: "http_stream.into_request".to_owned(),
+				reason: "request was already moved".to_owned(),
+			}
+			.build()
+		})?;
+		Ok((
+			request,
+			HttpStreamReply {
+				reply: self.reply.take(),
+			},
+		))
+	}
+
+	pub fn reply(mut self, response: StreamingResponse) {
+		if let Some(reply) = self.reply.take() {
+			reply.send(Ok(response));
+		}
+	}
+
+	pub fn reply_err(mut self, err: anyhow::Error) {
+		if let Some(reply) = self.reply.take() {
+			reply.send(Err(err));
+		}
+	}
+}
+
 #[derive(Debug)]
 #[must_use = "reply to the queue send or dropping it sends actor/dropped_reply"]
 #[allow(dead_code)]
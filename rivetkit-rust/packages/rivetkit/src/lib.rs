@@ -19,7 +19,8 @@ pub use crate::{
 	},
 	event::{
 		ActionCall, ConnClosed, ConnOpen, Destroy, Event, EventEntry, EventSet, HttpCall,
-		HttpReply, RuntimeEvent, SerializeState, Sleep, Subscribe, WsOpen,
+		HttpReply, HttpStreamCall, HttpStreamReply, RuntimeEvent, SerializeState, Sleep, Subscribe,
+		WsOpen,
 	},
 	queue::{HandlesQueue, Queue, QueueEntry, QueueMessage, QueueSet, TypedQueueMessage},
 	registry::Registry,
@@ -37,10 +38,11 @@ pub use rivetkit_core::serverless::{
 };
 pub use rivetkit_core::serverless_http;
 pub use rivetkit_core::{
-	ActorConfig, ActorKey, ActorKeySegment, ActorKv, CanHibernateWebSocket,
-	CompletableQueueMessage, ConnHandle, ConnId, EngineSpawnMode, EnqueueAndWaitOpts,
+	ActorConfig, ActorKey, ActorKeySegment, ActorKv, BodyFrame, CanHibernateWebSocket,
+	CompletableQueueMessage, ConnHandle, ConnId, EngineSpawnMode, EnqueueAndWaitOpts, GatewayAuth,
 	KeepAwakeRegion, ListOpts, QueueMessage as CoreQueueMessage, QueueNextBatchOpts, QueueNextOpts,
 	QueueTryNextBatchOpts, QueueTryNextOpts, QueueWaitOpts, Request, RequestSaveOpts, Response,
-	SaveStateOpts, SerializeStateReason, ServeConfig, SqliteDb, StateDelta, WebSocket, WsMessage,
+	SaveStateOpts, SerializeStateReason, ServeConfig, SqliteDb, StateDelta, StreamingRequest,
+	StreamingResponse, WebSocket, WsMessage,
 	sqlite::{BindParam, ColumnValue, ExecResult, QueryResult},
 };
@@ -336,6 +336,20 @@ async fn handle_actor_event<A: Actor>(
 		ActorEvent::HttpRequest { request, reply } => {
 			reply.send(actor.on_fetch(ctx, request).await);
 		}
+		ActorEvent::HttpStream { request, reply } => {
+			// Streams can stay open for the whole call, so they must not block the event loop
+			let abort = ctx.abort_signal();
+			tokio::spawn(async move {
+				tokio::select! {
+					_ = abort.cancelled() => {
+						reply.send(Err(ActorLifecycle::Stopping.build()));
+					}
+					result = actor.on_fetch_stream(ctx, request) => {
+						reply.send(result);
+					}
+				}
+			});
+		}
 		ActorEvent::QueueSend {
 			name,
 			body,
@@ -959,6 +973,37 @@ mod tests {
 		actor.await.expect("join run_actor").expect("run actor");
 	}
 
+	#[tokio::test]
+	async fn run_actor_default_fetch_stream_buffers_into_fetch() {
+		let (tx, rx) = unbounded_channel();
+		let start = lifecycle_start(Some(cbor(&LifecycleInput { count: 1 })), None, rx.into());
+		let actor = tokio::spawn(run_actor::<LifecycleActor>(start));
+
+		let (body_tx, body) = unbounded_channel();
+		body_tx
+			.send(rivetkit_core::BodyFrame::Data(b"ping".to_vec()))
+			.expect("send body frame");
+		drop(body_tx);
+
+		let (reply_tx, reply_rx) = oneshot::channel();
+		tx.send(ActorEvent::HttpStream {
+			request: rivetkit_core::StreamingRequest {
+				head: rivetkit_core::Request::default(),
+				body,
+			},
+			reply: reply_tx.into(),
+		})
+		.expect("send http stream event");
+
+		let mut response = reply_rx.await.expect("http reply").expect("http response");
+		assert_eq!(response.head.status().as_u16(), 404);
+		assert!(response.body.recv().await.is_none());
+
+		request_sleep(&tx).await;
+		drop(tx);
+		actor.await.expect("join run_actor").expect("run actor");
+	}
+
 	#[tokio::test]
 	async fn run_actor_missing_unit_input_creates_state() {
 		let (tx, rx) = unbounded_channel();
@@ -85,6 +85,7 @@ pub struct JsActorConfig {
 	pub icon: Option<String>,
 	pub has_database: Option<bool>,
 	pub remote_sqlite: Option<bool>,
+	pub broadcast_row_changes: Option<bool>,
 	pub enable_actor_runtime_socket: Option<bool>,
 	pub has_state: Option<bool>,
 	pub can_hibernate_websocket: Option<bool>,
@@ -1036,6 +1037,7 @@ impl From<JsActorConfig> for ActorConfigInput {
 			icon: value.icon,
 			has_database: value.has_database,
 			remote_sqlite: value.remote_sqlite,
+			broadcast_row_changes: value.broadcast_row_changes,
 			enable_actor_runtime_socket: value.enable_actor_runtime_socket,
 			has_state: value.has_state,
 			can_hibernate_websocket: value.can_hibernate_websocket,
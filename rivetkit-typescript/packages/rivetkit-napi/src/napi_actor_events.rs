@@ -463,6 +463,33 @@ pub(crate) async fn dispatch_event(
 				.await
 			});
 		}
+		// The TypeScript runtime has no streaming request handler, so streamed requests are
+		// buffered into `onRequest`
+		ActorEvent::HttpStream { request, reply } => {
+			let Some(callback) = bindings.on_request.clone() else {
+				reply.send(Err(missing_callback("onRequest")));
+				return;
+			};
+			let ctx = ctx.clone();
+			let timeout = config.on_request_timeout;
+			spawn_reply(tasks, abort.clone(), reply, async move {
+				let request = request.into_buffered().await?;
+				let response = with_dispatch_cancel_token(|cancel_token| {
+					with_structured_timeout(
+						"actor",
+						"action_timed_out",
+						"Action timed out",
+						None,
+						timeout,
+						async move {
+							call_http_request(&callback, &ctx, request, Some(cancel_token)).await
+						},
+					)
+				})
+				.await?;
+				Ok(response.into())
+			});
+		}
 		ActorEvent::QueueSend {
 			name,
 			body,
@@ -182,6 +182,7 @@ pub struct WasmActorConfig {
 	pub icon: Option<String>,
 	pub has_database: Option<bool>,
 	pub remote_sqlite: Option<bool>,
+	pub broadcast_row_changes: Option<bool>,
 	pub enable_actor_runtime_socket: Option<bool>,
 	pub has_state: Option<bool>,
 	pub can_hibernate_websocket: Option<bool>,
@@ -217,6 +218,7 @@ impl From<WasmActorConfig> for ActorConfigInput {
 			icon: config.icon,
 			has_database: config.has_database,
 			remote_sqlite: config.remote_sqlite,
+			broadcast_row_changes: config.broadcast_row_changes,
 			enable_actor_runtime_socket: config.enable_actor_runtime_socket,
 			has_state: config.has_state,
 			can_hibernate_websocket: config.can_hibernate_websocket,
@@ -822,6 +824,30 @@ async fn dispatch_event(callbacks: &WasmCallbacks, ctx: &WasmActorContext, event
 				reply.send(result);
 			});
 		}
+		// The TypeScript runtime has no streaming request handler, so streamed requests are
+		// buffered into `onRequest`
+		ActorEvent::HttpStream { request, reply } => {
+			let callback = callbacks.on_request.clone();
+			let ctx = ctx.clone();
+			RuntimeSpawner::spawn(async move {
+				let result = async {
+					let callback = callback
+						.as_ref()
+						.ok_or_else(|| anyhow!("wasm onRequest callback is not implemented"))?;
+					let request = request.into_buffered().await?;
+					let payload = object();
+					set_anyhow(&payload, "ctx", JsValue::from(ctx.clone()))?;
+					set_anyhow(&payload, "request", request_to_js(request)?)?;
+					let value = call_callback(callback, &payload.into()).await?;
+					Ok(response_from_js(value)?.into())
+				}
+				.await;
+				if let Err(error) = &result {
+					console_error(&format!("wasm onRequest callback failed: {error:#}"));
+				}
+				reply.send(result);
+			});
+		}
 		ActorEvent::QueueSend {
 			name,
 			body,
@@ -1067,6 +1067,8 @@ const GlobalActorOptionsBaseSchema = z
 		icon: z.string().optional(),
 		/** Enables the experimental Actor Runtime Socket for this actor. */
 		enableActorRuntimeSocket: z.boolean().default(false),
+		/** Broadcasts committed row changes of the actor database as `sqlite.rowChange` events. */
+		broadcastRowChanges: z.boolean().default(false),
 		/**
 		 * Can hibernate WebSockets for onWebSocket.
 		 *
@@ -2002,6 +2004,12 @@ export const DocActorOptionsSchema = z
 			.describe(
 				"Enables the experimental Actor Runtime Socket for this actor. Default: false",
 			),
+		broadcastRowChanges: z
+			.boolean()
+			.optional()
+			.describe(
+				"Broadcasts committed row changes of the actor database as `sqlite.rowChange` events. Default: false",
+			),
 		createVarsTimeout: z
 			.number()
 			.optional()
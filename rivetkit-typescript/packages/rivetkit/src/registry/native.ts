@@ -3501,6 +3501,7 @@ function buildActorConfig(
 		hasDatabase: config.db !== undefined || usesRemoteSqlite,
 		remoteSqlite: usesRemoteSqlite,
 		enableActorRuntimeSocket: options.enableActorRuntimeSocket === true,
+		broadcastRowChanges: options.broadcastRowChanges === true,
 		hasState:
 			config.state !== undefined ||
 			typeof config.createState === "function",